@@ -0,0 +1,76 @@
+//! Render throughput: the homepage body, its head, Markdown, and a full
+//! build. Run with `cargo bench`; compare against a saved baseline with
+//! `cargo bench -- --save-baseline main` then `--baseline main`.
+//!
+//! The full build runs the binary in the crate root offline, so it writes
+//! `target/site/` as `--generate-static` does.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use everythingsings::cache::OFFLINE_ENV;
+use everythingsings::components::generate_head_html;
+use everythingsings::markdown;
+use everythingsings::structured_data::render_with_graph;
+use everythingsings::App;
+use leptos::prelude::*;
+use std::hint::black_box;
+use std::process::Command;
+use std::time::Duration;
+
+/// A post body using every Markdown feature the renderer rewrites.
+fn sample_markdown() -> String {
+    let section = r#"## A heading with "quotes"
+
+Text with *emphasis*, `code`, a [link](https://example.com/), and a
+note.[^n] Prices -- or ranges -- of 24 MB... and "more".
+
+- One
+- Two
+
+```rust
+fn main() {}
+```
+
+[^n]: The note.
+"#;
+    section.repeat(20)
+}
+
+fn homepage(c: &mut Criterion) {
+    c.bench_function("homepage body", |b| {
+        b.iter(|| render_with_graph(|| view! { <App /> }.to_html()))
+    });
+    let (_, graph) = render_with_graph(|| view! { <App /> }.to_html());
+    c.bench_function("homepage head", |b| {
+        b.iter(|| generate_head_html(black_box(&graph), &[]))
+    });
+}
+
+fn markdown(c: &mut Criterion) {
+    let text = sample_markdown();
+    c.bench_function("markdown", |b| {
+        b.iter(|| markdown::to_html(black_box(&text)))
+    });
+}
+
+fn full_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("site");
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(30));
+    group.bench_function("full build", |b| {
+        b.iter(|| {
+            let status = Command::new(env!("CARGO_BIN_EXE_everythingsings"))
+                .arg("--generate-static")
+                .current_dir(env!("CARGO_MANIFEST_DIR"))
+                .env(OFFLINE_ENV, "1")
+                .output()
+                .expect("the binary runs")
+                .status;
+            assert!(status.success(), "the build failed");
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, homepage, markdown, full_build);
+criterion_main!(benches);
@@ -0,0 +1,160 @@
+//! Build script: generates `css_classes.rs` from `style/main.css` and
+//! records build provenance.
+//!
+//! Every class used in a selector becomes a constant (`.link-card` →
+//! `LINK_CARD`), included by `src/css.rs`. Components reference the constants
+//! instead of string literals, so using a class the stylesheet doesn't define
+//! is a compile error.
+//!
+//! The current commit hash and build time are exported as `ESART_GIT_HASH`
+//! and `ESART_BUILD_TIME` for `src/provenance.rs`. The build time honours
+//! `SOURCE_DATE_EPOCH` for reproducible builds.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const STYLESHEET: &str = "style/main.css";
+
+/// Removes `/* ... */` comments.
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Collects class names from every selector (the text before each `{` that
+/// is not an at-rule).
+fn selector_classes(css: &str) -> BTreeSet<String> {
+    let css = strip_comments(css);
+    let mut classes = BTreeSet::new();
+    let mut prelude = String::new();
+
+    for c in css.chars() {
+        match c {
+            '{' => {
+                let selector = prelude.trim();
+                if !selector.starts_with('@') {
+                    collect_classes(selector, &mut classes);
+                }
+                prelude.clear();
+            }
+            '}' | ';' => prelude.clear(),
+            _ => prelude.push(c),
+        }
+    }
+    classes
+}
+
+fn collect_classes(selector: &str, classes: &mut BTreeSet<String>) {
+    for (i, c) in selector.char_indices() {
+        if c != '.' {
+            continue;
+        }
+        let name: String = selector[i + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            classes.insert(name);
+        }
+    }
+}
+
+fn const_name(class: &str) -> String {
+    class.to_ascii_uppercase().replace('-', "_")
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp.
+fn rfc3339(seconds: u64) -> String {
+    let (days, secs) = ((seconds / 86_400) as i64, seconds % 86_400);
+    // Civil from days (Howard Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Exports the commit hash and build time, rerunning when HEAD moves.
+fn emit_provenance() {
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Watching a missing path would rerun the script on every build.
+    if let Ok(head) = fs::read_to_string(".git/HEAD") {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(reference) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{}", reference);
+        }
+    }
+
+    let hash = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=ESART_GIT_HASH={}", hash);
+
+    let seconds = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+    println!("cargo:rustc-env=ESART_BUILD_TIME={}", rfc3339(seconds));
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", STYLESHEET);
+    emit_provenance();
+
+    let css = fs::read_to_string(STYLESHEET).unwrap_or_default();
+    let classes = selector_classes(&css);
+
+    let mut out = String::from("// Generated by build.rs from style/main.css. Do not edit.\n\n");
+    for class in &classes {
+        out.push_str(&format!(
+            "/// `.{}`\npub const {}: &str = \"{}\";\n",
+            class,
+            const_name(class),
+            class
+        ));
+    }
+    out.push_str("\n/// Every class defined by the stylesheet.\npub const ALL: &[&str] = &[\n");
+    for class in &classes {
+        out.push_str(&format!("    {},\n", const_name(class)));
+    }
+    out.push_str("];\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set")).join("css_classes.rs");
+    fs::write(dest, out).expect("write css_classes.rs");
+}
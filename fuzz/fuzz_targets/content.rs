@@ -0,0 +1,8 @@
+//! Whole post source files: frontmatter and Markdown.
+
+#![no_main]
+
+use everythingsings_fuzz::build_post;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| build_post(text));
@@ -0,0 +1,9 @@
+//! Markdown bodies under valid frontmatter, so every input reaches the
+//! renderer.
+
+#![no_main]
+
+use everythingsings_fuzz::{build_post, FRONTMATTER};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|body: &str| build_post(&format!("{}{}", FRONTMATTER, body)));
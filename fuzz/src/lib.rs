@@ -0,0 +1,41 @@
+//! The content pipeline as the fuzz targets drive it: a post's source file
+//! parsed, its links resolved, and its page rendered, as a build would.
+//! Malformed content may be an error; it must never be a panic.
+
+use everythingsings::collection::Collection;
+use everythingsings::components::{PostPage, PostPageProps};
+use everythingsings::config::POSTS;
+use everythingsings::internal_links::LinkTargets;
+use leptos::prelude::*;
+use std::path::PathBuf;
+
+/// Frontmatter the `markdown` target puts before its input, with every
+/// optional rendering step on.
+pub const FRONTMATTER: &str = "+++\ntitle = \"Fuzz\"\ndate = \"2025-01-01\"\ntoc = true\n+++\n";
+
+/// Builds the post whose source file is `text`.
+pub fn build_post(text: &str) {
+    let source = (
+        "fuzz".to_string(),
+        PathBuf::from("fuzz.md"),
+        text.to_string(),
+    );
+    let Ok(mut posts) = Collection::from_sources(POSTS, vec![source]) else {
+        return;
+    };
+    let mut targets = LinkTargets::default();
+    targets.add(&posts);
+    if targets.resolve_collection(&mut posts).is_err() {
+        return;
+    }
+    for post in &posts.entries {
+        post.toc();
+        post.to_json_ld("#fuzz");
+        PostPage(PostPageProps {
+            post: post.clone(),
+            listing: posts.def.listing_path(),
+            listing_title: posts.def.title,
+        })
+        .to_html();
+    }
+}
@@ -3,7 +3,9 @@
  *
  * Manages WebGL shader backgrounds with eleven toggleable generative patterns.
  * Starts with a random shader on first visit, then persists user preference.
- * Respects prefers-reduced-motion.
+ * Respects prefers-reduced-motion. Colors come from the design tokens: each
+ * shader fades from --color-bg to --color-accent, read from the stylesheet's
+ * custom properties so the active color scheme applies.
  */
 (function() {
   'use strict';
@@ -21,6 +23,7 @@
   let currentShader = 0;
   let indicatorTimeout = null;
   let shaderSources = {};
+  let palette = { bg: [0, 0, 0], fg: [1, 1, 1] };
 
   // Vertex shader (shared by all fragment shaders)
   const vertexShaderSource = `
@@ -37,6 +40,25 @@
     return response.text();
   }
 
+  // Parse a #rgb or #rrggbb color into 0-1 channels
+  function parseHexColor(value, fallback) {
+    let hex = value.trim().replace(/^#/, '');
+    if (hex.length === 3) {
+      hex = hex.split('').map(c => c + c).join('');
+    }
+    if (!/^[0-9a-fA-F]{6}$/.test(hex)) return fallback;
+    return [0, 2, 4].map(i => parseInt(hex.slice(i, i + 2), 16) / 255);
+  }
+
+  // Read the palette from the design token custom properties
+  function readPalette() {
+    const style = getComputedStyle(document.documentElement);
+    palette = {
+      bg: parseHexColor(style.getPropertyValue('--color-bg'), palette.bg),
+      fg: parseHexColor(style.getPropertyValue('--color-accent'), palette.fg)
+    };
+  }
+
   // Compile a shader
   function compileShader(type, source) {
     const shader = gl.createShader(type);
@@ -196,9 +218,13 @@
     // Set uniforms
     const timeLoc = gl.getUniformLocation(program, 'u_time');
     const resLoc = gl.getUniformLocation(program, 'u_resolution');
+    const bgLoc = gl.getUniformLocation(program, 'u_bg');
+    const fgLoc = gl.getUniformLocation(program, 'u_fg');
 
     if (timeLoc) gl.uniform1f(timeLoc, time);
     if (resLoc) gl.uniform2f(resLoc, canvas.width, canvas.height);
+    if (bgLoc) gl.uniform3fv(bgLoc, palette.bg);
+    if (fgLoc) gl.uniform3fv(fgLoc, palette.fg);
 
     gl.drawArrays(gl.TRIANGLES, 0, 6);
     requestAnimationFrame(render);
@@ -290,6 +316,14 @@
 
     setupGeometry();
     resize();
+    readPalette();
+
+    // Follow color scheme changes, from the system or the theme toggle
+    window.matchMedia('(prefers-color-scheme: light)').addEventListener('change', readPalette);
+    new MutationObserver(readPalette).observe(document.documentElement, {
+      attributes: true,
+      attributeFilter: ['data-theme']
+    });
 
     // Load saved preference or start with random shader
     const saved = localStorage.getItem(STORAGE_KEY);
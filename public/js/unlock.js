@@ -0,0 +1,46 @@
+/**
+ * Protected Page Unlock
+ *
+ * Decrypts the content of a protected page (see src/protected.rs) with the
+ * passphrase entered in its form: PBKDF2-SHA256 key derivation, then
+ * AES-256-GCM, both through the Web Crypto API. The content replaces the
+ * form; nothing is sent anywhere.
+ */
+(function() {
+  'use strict';
+
+  const bytes = (b64) => Uint8Array.from(atob(b64), (c) => c.charCodeAt(0));
+
+  async function unlock(form, passphrase) {
+    const data = form.dataset;
+    const material = await crypto.subtle.importKey(
+      'raw', new TextEncoder().encode(passphrase), 'PBKDF2', false, ['deriveKey']
+    );
+    const key = await crypto.subtle.deriveKey(
+      { name: 'PBKDF2', hash: 'SHA-256', salt: bytes(data.salt), iterations: Number(data.iterations) },
+      material,
+      { name: 'AES-GCM', length: 256 },
+      false,
+      ['decrypt']
+    );
+    const plain = await crypto.subtle.decrypt(
+      { name: 'AES-GCM', iv: bytes(data.nonce) }, key, bytes(data.ciphertext)
+    );
+    return new TextDecoder().decode(plain);
+  }
+
+  document.querySelectorAll('form[data-ciphertext]').forEach((form) => {
+    form.addEventListener('submit', async (event) => {
+      event.preventDefault();
+      const status = form.querySelector('[role="status"]');
+      status.textContent = 'Unlocking…';
+      try {
+        const content = document.createElement('div');
+        content.innerHTML = await unlock(form, form.elements.passphrase.value);
+        form.replaceWith(content);
+      } catch (e) {
+        status.textContent = 'That passphrase did not work.';
+      }
+    });
+  });
+})();
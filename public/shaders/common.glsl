@@ -5,6 +5,8 @@ precision mediump float;
 
 uniform float u_time;
 uniform vec2 u_resolution;
+uniform vec3 u_bg;
+uniform vec3 u_fg;
 
 // Hash function for noise
 vec3 hash3(vec2 p) {
@@ -5,6 +5,8 @@ precision mediump float;
 
 uniform float u_time;
 uniform vec2 u_resolution;
+uniform vec3 u_bg;
+uniform vec3 u_fg;
 
 float hash(vec2 p) {
   return fract(sin(dot(p, vec2(127.1, 311.7))) * 43758.5453);
@@ -62,5 +64,5 @@ void main() {
   // Final composition
   float brightness = n * 0.12 + bands * 0.06 + 0.02;
 
-  gl_FragColor = vec4(vec3(brightness), 1.0);
+  gl_FragColor = vec4(mix(u_bg, u_fg, brightness), 1.0);
 }
@@ -5,6 +5,8 @@ precision mediump float;
 
 uniform float u_time;
 uniform vec2 u_resolution;
+uniform vec3 u_bg;
+uniform vec3 u_fg;
 
 float hash(float n) {
   return fract(sin(n) * 43758.5453);
@@ -96,5 +98,5 @@ void main() {
   brightness = brightness * 0.8 + 0.02;
   brightness = clamp(brightness, 0.0, 0.2);
 
-  gl_FragColor = vec4(vec3(brightness), 1.0);
+  gl_FragColor = vec4(mix(u_bg, u_fg, brightness), 1.0);
 }
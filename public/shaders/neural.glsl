@@ -3,6 +3,8 @@ precision mediump float;
 
 uniform float u_time;
 uniform vec2 u_resolution;
+uniform vec3 u_bg;
+uniform vec3 u_fg;
 
 void main() {
   vec2 uv = gl_FragCoord.xy / u_resolution.xy;
@@ -14,5 +16,5 @@ void main() {
   float pulse = 0.1 + 0.05 * sin(t * 2.0);
   float brightness = smoothstep(pulse + 0.05, pulse, d) * 0.3 + 0.02;
 
-  gl_FragColor = vec4(vec3(brightness), 1.0);
+  gl_FragColor = vec4(mix(u_bg, u_fg, brightness), 1.0);
 }
@@ -5,6 +5,8 @@ precision mediump float;
 
 uniform float u_time;
 uniform vec2 u_resolution;
+uniform vec3 u_bg;
+uniform vec3 u_fg;
 
 void main() {
   vec2 uv = gl_FragCoord.xy / u_resolution.xy;
@@ -49,5 +51,5 @@ void main() {
   // Map to subtle brightness
   float brightness = mixed * 0.14 + 0.02;
 
-  gl_FragColor = vec4(vec3(brightness), 1.0);
+  gl_FragColor = vec4(mix(u_bg, u_fg, brightness), 1.0);
 }
@@ -0,0 +1,374 @@
+//! # Static Accessibility Audit
+//!
+//! Runs simple, deterministic checks over rendered HTML and the stylesheet's
+//! theme tokens. These complement (not replace) manual testing with a screen
+//! reader, catching regressions like a new image without alt text.
+//!
+//! ## Checks
+//!
+//! - `img-alt`: every `<img>` has an `alt` attribute
+//! - `link-name`: every `<a>` has text, an `aria-label`, or an image with alt text
+//! - `single-h1`: pages have exactly one `<h1>`
+//! - `heading-order`: pages start at `<h1>` and never skip a level
+//! - `heading-section`: every `<h2>` sits in a labeled `<section>`,
+//!   `<article>`, `<aside>`, or `<nav>` (`aria-label` or `aria-labelledby`)
+//! - `duplicate-id`: `id` attributes are unique within a page
+//! - `contrast`: theme text colors reach WCAG AA (4.5:1) against the background
+//!
+//! The outline rules (`single-h1`, `heading-order`, `heading-section`) also
+//! run on every build (see [`audit_outline`]), which fails on a finding.
+
+use crate::tokens::{contrast_ratio, parse_hex_color, MIN_CONTRAST};
+use scraper::{ElementRef, Html, Selector};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Theme tokens used for text, each checked against `--color-bg`.
+const TEXT_TOKENS: &[&str] = &["--color-text", "--color-text-muted", "--color-link"];
+
+/// A single accessibility problem.
+#[derive(Debug, PartialEq)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub message: String,
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+/// Audits a single rendered HTML document.
+pub fn audit_html(html: &str) -> Vec<Finding> {
+    let doc = Html::parse_document(html);
+    let mut findings = Vec::new();
+
+    for img in doc.select(&selector("img")) {
+        if img.value().attr("alt").is_none() {
+            findings.push(Finding {
+                rule: "img-alt",
+                message: format!(
+                    "<img src=\"{}\"> has no alt attribute",
+                    img.value().attr("src").unwrap_or("")
+                ),
+            });
+        }
+    }
+
+    for link in doc.select(&selector("a")) {
+        if !has_accessible_name(&link) {
+            findings.push(Finding {
+                rule: "link-name",
+                message: format!(
+                    "<a href=\"{}\"> has no accessible name",
+                    link.value().attr("href").unwrap_or("")
+                ),
+            });
+        }
+    }
+
+    findings.extend(outline_findings(&doc));
+
+    let mut seen = HashSet::new();
+    for element in doc.select(&selector("[id]")) {
+        let id = element.value().id().unwrap_or("");
+        if !seen.insert(id.to_string()) {
+            findings.push(Finding {
+                rule: "duplicate-id",
+                message: format!("id=\"{}\" is used more than once", id),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Audits the heading outline of a single rendered HTML document: one
+/// `<h1>`, no skipped levels, and every `<h2>` in a labeled section.
+pub fn audit_outline(html: &str) -> Vec<Finding> {
+    outline_findings(&Html::parse_document(html))
+}
+
+fn heading_text(heading: &ElementRef) -> String {
+    heading.text().collect::<String>().trim().to_string()
+}
+
+fn outline_findings(doc: &Html) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let h1_count = doc.select(&selector("h1")).count();
+    if h1_count != 1 {
+        findings.push(Finding {
+            rule: "single-h1",
+            message: format!("page has {} <h1> elements", h1_count),
+        });
+    }
+
+    let mut previous = 0;
+    for heading in doc.select(&selector("h1, h2, h3, h4, h5, h6")) {
+        let level = heading.value().name()[1..].parse::<usize>().unwrap_or(1);
+        if level > previous + 1 {
+            findings.push(Finding {
+                rule: "heading-order",
+                message: format!(
+                    "<h{}> \"{}\" follows h{}",
+                    level,
+                    heading_text(&heading),
+                    previous
+                ),
+            });
+        }
+        previous = level;
+    }
+
+    for heading in doc.select(&selector("h2")) {
+        if !in_labeled_section(&heading) {
+            findings.push(Finding {
+                rule: "heading-section",
+                message: format!(
+                    "<h2> \"{}\" is not in a labeled section",
+                    heading_text(&heading)
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+/// Whether the nearest sectioning ancestor of `heading` carries an
+/// `aria-label` or `aria-labelledby`.
+fn in_labeled_section(heading: &ElementRef) -> bool {
+    heading
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|el| matches!(el.value().name(), "section" | "article" | "aside" | "nav"))
+        .is_some_and(|section| {
+            ["aria-label", "aria-labelledby"].iter().any(|attr| {
+                section
+                    .value()
+                    .attr(attr)
+                    .is_some_and(|v| !v.trim().is_empty())
+            })
+        })
+}
+
+/// Audits the heading outline of every HTML page under `dir`.
+///
+/// Returns findings grouped by site path; pages without findings are omitted.
+pub fn audit_outline_site(dir: &Path) -> std::io::Result<BTreeMap<String, Vec<Finding>>> {
+    let mut results = BTreeMap::new();
+    let mut files = Vec::new();
+    collect(dir, &mut files)?;
+    for path in files {
+        if path.extension().and_then(|e| e.to_str()) != Some("html") {
+            continue;
+        }
+        let findings = audit_outline(&std::fs::read_to_string(&path)?);
+        if !findings.is_empty() {
+            let relative = path.strip_prefix(dir).unwrap_or(&path);
+            results.insert(format!("/{}", relative.to_string_lossy()), findings);
+        }
+    }
+    Ok(results)
+}
+
+fn has_accessible_name(link: &ElementRef) -> bool {
+    if !link.text().collect::<String>().trim().is_empty() {
+        return true;
+    }
+    if link
+        .value()
+        .attr("aria-label")
+        .is_some_and(|l| !l.trim().is_empty())
+    {
+        return true;
+    }
+    link.select(&selector("img[alt]"))
+        .any(|img| !img.value().attr("alt").unwrap_or("").trim().is_empty())
+}
+
+/// Extracts the hex color custom properties of each `:root` block.
+fn theme_blocks(css: &str) -> Vec<BTreeMap<String, String>> {
+    css.match_indices(":root")
+        .filter_map(|(start, _)| {
+            let body_start = start + css[start..].find('{')? + 1;
+            let body_end = body_start + css[body_start..].find('}')?;
+            let tokens = css[body_start..body_end]
+                .split(';')
+                .filter_map(|decl| {
+                    let (name, value) = decl.split_once(':')?;
+                    let (name, value) = (name.trim(), value.trim());
+                    (name.starts_with("--color-") && value.starts_with('#'))
+                        .then(|| (name.to_string(), value.to_string()))
+                })
+                .collect::<BTreeMap<_, _>>();
+            (!tokens.is_empty()).then_some(tokens)
+        })
+        .collect()
+}
+
+/// Checks the contrast of the stylesheet's text tokens in every theme.
+pub fn audit_theme(css: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (i, tokens) in theme_blocks(css).iter().enumerate() {
+        let Some(bg) = tokens.get("--color-bg").and_then(|c| parse_hex_color(c)) else {
+            continue;
+        };
+        for name in TEXT_TOKENS {
+            let Some(fg) = tokens.get(*name).and_then(|c| parse_hex_color(c)) else {
+                continue;
+            };
+            let ratio = contrast_ratio(fg, bg);
+            if ratio < MIN_CONTRAST {
+                findings.push(Finding {
+                    rule: "contrast",
+                    message: format!(
+                        "{} on --color-bg in theme {} is {:.2}:1 (needs {}:1)",
+                        name,
+                        i + 1,
+                        ratio,
+                        MIN_CONTRAST
+                    ),
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Audits every HTML page and stylesheet under `dir`.
+///
+/// Returns findings grouped by site path; pages without findings are omitted.
+pub fn audit_site(dir: &Path) -> std::io::Result<BTreeMap<String, Vec<Finding>>> {
+    let mut results = BTreeMap::new();
+    let mut files = Vec::new();
+    collect(dir, &mut files)?;
+
+    for path in files {
+        let findings = match path.extension().and_then(|e| e.to_str()) {
+            Some("html") => audit_html(&std::fs::read_to_string(&path)?),
+            Some("css") => audit_theme(&std::fs::read_to_string(&path)?),
+            _ => continue,
+        };
+        if !findings.is_empty() {
+            let relative = path.strip_prefix(dir).unwrap_or(&path);
+            results.insert(format!("/{}", relative.to_string_lossy()), findings);
+        }
+    }
+
+    Ok(results)
+}
+
+fn collect(dir: &Path, out: &mut Vec<std::path::PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_outline;
+    use leptos::prelude::*;
+
+    fn rules(html: &str) -> Vec<&'static str> {
+        audit_html(html).into_iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn clean_page_has_no_findings() {
+        let html = r#"<body><h1>Hi</h1><section aria-labelledby="s"><h2 id="s">Sub</h2></section><a href="/">Home</a><img src="a.png" alt=""></body>"#;
+        assert!(audit_html(html).is_empty());
+    }
+
+    #[test]
+    fn missing_alt_is_reported() {
+        assert_eq!(rules(r#"<h1>x</h1><img src="a.png">"#), ["img-alt"]);
+    }
+
+    #[test]
+    fn pages_need_exactly_one_h1() {
+        assert_eq!(rules("<p>x</p>"), ["single-h1"]);
+        assert_eq!(rules("<h1>a</h1><h1>b</h1>"), ["single-h1"]);
+    }
+
+    #[test]
+    fn h2_outside_labeled_section_is_reported() {
+        assert_eq!(
+            rules("<h1>a</h1><section><h2>b</h2></section>"),
+            ["heading-section"]
+        );
+        assert_eq!(
+            rules(
+                r#"<h1>a</h1><section aria-labelledby="b"><article><h2 id="b">b</h2></article></section>"#
+            ),
+            ["heading-section"],
+            "The nearest section must be labeled"
+        );
+        assert!(
+            rules(r#"<h1>a</h1><article aria-labelledby="b"><h2 id="b">b</h2></article>"#)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn empty_link_is_reported() {
+        assert_eq!(rules(r#"<h1>x</h1><a href="/"> </a>"#), ["link-name"]);
+    }
+
+    #[test]
+    fn image_link_with_alt_has_a_name() {
+        assert!(audit_html(r#"<h1>x</h1><a href="/"><img src="a" alt="Home"></a>"#).is_empty());
+    }
+
+    #[test]
+    fn skipped_heading_level_is_reported() {
+        assert_eq!(rules("<h1>a</h1><h3>b</h3>"), ["heading-order"]);
+        assert_eq!(
+            rules(r#"<section aria-label="s"><h2>a</h2></section>"#),
+            ["single-h1", "heading-order"]
+        );
+    }
+
+    #[test]
+    fn rendered_pages_have_sound_outlines() {
+        // Skip fragments such as a lone email link
+        for html in crate::css::tests::rendered_pages() {
+            if html.starts_with("<body") {
+                assert_outline(&html);
+            }
+        }
+    }
+
+    #[test]
+    fn duplicate_ids_are_reported() {
+        assert_eq!(
+            rules(r#"<h1 id="x">a</h1><p id="x">b</p>"#),
+            ["duplicate-id"]
+        );
+    }
+
+    #[test]
+    fn low_contrast_theme_is_reported() {
+        let css = ":root { --color-bg: #ffffff; --color-text: #cccccc; }";
+        assert_eq!(audit_theme(css)[0].rule, "contrast");
+    }
+
+    #[test]
+    fn shipped_stylesheet_meets_contrast() {
+        let css = crate::tokens::generate_tokens_css();
+        assert!(audit_theme(&css).is_empty(), "{:?}", audit_theme(&css));
+    }
+
+    #[test]
+    fn rendered_homepage_passes_audit() {
+        let html = view! { <crate::App /> }.to_html();
+        assert!(audit_html(&html).is_empty(), "{:?}", audit_html(&html));
+    }
+}
@@ -0,0 +1,97 @@
+//! # Alternate Representations
+//!
+//! Every format the site publishes besides HTML is declared here once and
+//! advertised from the head as `<link rel="alternate">`, so agents can find
+//! each representation from any page instead of guessing paths:
+//!
+//! ```html
+//! <link rel="alternate" type="application/json" title="EverythingSings Profile" href="/api/profile.json" />
+//! ```
+//!
+//! [`site_alternates`] lists what describes the whole site and is linked
+//! from every page; pages add their own (oEmbed documents, `/resume.json`)
+//! through [`PageMeta::alternates`](crate::components::PageMeta). Feeds come
+//! from their own registry (see [`crate::feed::feeds`]) and precede both.
+//! A new format only needs an entry here or on its page to be discoverable.
+
+use crate::api::{LINKS_JSON_PATH, PROFILE_JSON_PATH};
+use crate::config::SITE_NAME;
+use crate::escape::escape_html;
+
+/// MIME type of RSS feeds.
+pub const RSS_TYPE: &str = "application/rss+xml";
+
+/// MIME type of plain JSON documents.
+pub const JSON_TYPE: &str = "application/json";
+
+/// MIME type of plain text documents.
+pub const TEXT_TYPE: &str = "text/plain";
+
+/// A representation of a page or the site in another format.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alternate {
+    pub media_type: &'static str,
+    pub title: String,
+    /// Site-relative or absolute URL.
+    pub href: String,
+}
+
+impl Alternate {
+    pub fn new(
+        media_type: &'static str,
+        title: impl Into<String>,
+        href: impl Into<String>,
+    ) -> Self {
+        Alternate {
+            media_type,
+            title: title.into(),
+            href: href.into(),
+        }
+    }
+
+    /// The `<link rel="alternate">` element.
+    pub fn to_html(&self) -> String {
+        format!(
+            r#"<link rel="alternate" type="{}" title="{}" href="{}" />"#,
+            self.media_type,
+            escape_html(&self.title),
+            escape_html(&self.href)
+        )
+    }
+}
+
+/// The representations of the whole site, linked from every page.
+pub fn site_alternates() -> Vec<Alternate> {
+    vec![
+        Alternate::new(
+            JSON_TYPE,
+            format!("{} Profile", SITE_NAME),
+            PROFILE_JSON_PATH,
+        ),
+        Alternate::new(JSON_TYPE, format!("{} Links", SITE_NAME), LINKS_JSON_PATH),
+        Alternate::new(TEXT_TYPE, format!("{} for LLMs", SITE_NAME), "/llms.txt"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alternates_render_as_links() {
+        let alternate = Alternate::new(RSS_TYPE, "Log & Notes", "/log/feed.xml");
+        assert_eq!(
+            alternate.to_html(),
+            r#"<link rel="alternate" type="application/rss+xml" title="Log &amp; Notes" href="/log/feed.xml" />"#
+        );
+    }
+
+    #[test]
+    fn site_alternates_have_unique_hrefs() {
+        let alternates = site_alternates();
+        let mut hrefs: Vec<_> = alternates.iter().map(|a| &a.href).collect();
+        hrefs.sort();
+        hrefs.dedup();
+        assert_eq!(hrefs.len(), alternates.len());
+    }
+}
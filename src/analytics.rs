@@ -0,0 +1,233 @@
+//! # Analytics
+//!
+//! Page-view counting is off by default. [`ANALYTICS`](crate::config::ANALYTICS)
+//! can switch on one of:
+//!
+//! - [`Analytics::Plausible`] or [`Analytics::GoatCounter`]: the provider's
+//!   cookie-free script, loaded with Subresource Integrity. Pages then also
+//!   carry a Content Security Policy allowing exactly the provider's origins
+//!   besides this site.
+//! - [`Analytics::AccessLogs`]: nothing is added to pages (no script, no
+//!   tracking pixel); views are counted from the host's access logs, e.g.
+//!   with `goatcounter import`.
+//!
+//! The integrity hash is part of the configuration so the output stays
+//! deterministic. Each build fetches the script (through [`crate::cache`])
+//! and warns with the correct hash when the configured one no longer
+//! matches, since the browser would then refuse to run the script.
+
+use crate::cache::{fetch_bytes, offline, CacheEntry, CACHE_DIR};
+use crate::config::WEBMENTION_ENDPOINT;
+use crate::embeds::PLAYER_ORIGINS;
+use crate::escape::escape_html;
+use crate::hints::origin;
+use crate::text::base64;
+use crate::theme::csp_source;
+use sha2::{Digest, Sha384};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long a cached provider script is used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How page views are counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Analytics {
+    /// No analytics.
+    #[default]
+    Off,
+    /// Plausible for the site `domain`. `integrity` is the script's SRI
+    /// hash (`sha384-…`).
+    Plausible {
+        domain: &'static str,
+        integrity: &'static str,
+    },
+    /// GoatCounter for `https://<code>.goatcounter.com`. `integrity` is the
+    /// script's SRI hash (`sha384-…`).
+    GoatCounter {
+        code: &'static str,
+        integrity: &'static str,
+    },
+    /// Counted from the host's access logs; pages are unchanged.
+    AccessLogs,
+}
+
+impl Analytics {
+    /// URL of the provider script, for script-based analytics.
+    pub fn script_src(&self) -> Option<&'static str> {
+        match self {
+            Analytics::Plausible { .. } => Some("https://plausible.io/js/script.js"),
+            // Versioned, so its hash only changes with a new version
+            Analytics::GoatCounter { .. } => Some("https://gc.zgo.at/count.v4.js"),
+            Analytics::Off | Analytics::AccessLogs => None,
+        }
+    }
+
+    /// The configured SRI hash of [`Analytics::script_src`].
+    pub fn integrity(&self) -> Option<&'static str> {
+        match self {
+            Analytics::Plausible { integrity, .. } | Analytics::GoatCounter { integrity, .. } => {
+                Some(integrity)
+            }
+            Analytics::Off | Analytics::AccessLogs => None,
+        }
+    }
+
+    /// Origin the script reports page views to.
+    fn endpoint_origin(&self) -> Option<String> {
+        match self {
+            Analytics::Plausible { .. } => Some("https://plausible.io".to_string()),
+            Analytics::GoatCounter { code, .. } => {
+                Some(format!("https://{}.goatcounter.com", code))
+            }
+            Analytics::Off | Analytics::AccessLogs => None,
+        }
+    }
+
+    /// The `<script>` element, or an empty string when no script is used.
+    pub fn script_html(&self) -> String {
+        let (Some(src), Some(integrity)) = (self.script_src(), self.integrity()) else {
+            return String::new();
+        };
+        let data = match self {
+            Analytics::Plausible { domain, .. } => {
+                format!("data-domain=\"{}\"", escape_html(domain))
+            }
+            _ => format!(
+                "data-goatcounter=\"{}/count\"",
+                escape_html(&self.endpoint_origin().unwrap_or_default())
+            ),
+        };
+        format!(
+            "<script defer src=\"{}\" integrity=\"{}\" crossorigin=\"anonymous\" {}></script>",
+            src,
+            escape_html(integrity),
+            data
+        )
+    }
+
+    /// The Content Security Policy for pages loading the script, or `None`
+    /// without one. Besides the provider, it allows this site's own
+    /// scripts, the [theme script](crate::theme) by hash, speculation rules,
+    /// inline styles (image placeholders), data URIs, the lite embeds'
+    /// players, and the guestbook form's webmention endpoint.
+    pub fn content_security_policy(&self) -> Option<String> {
+        let script_origin = origin(self.script_src()?)?;
+        let theme = csp_source()
+            .map(|hash| format!(" {}", hash))
+            .unwrap_or_default();
+        let endpoint = self.endpoint_origin()?;
+        let form_action = match WEBMENTION_ENDPOINT.and_then(origin) {
+            Some(webmention) => format!("'self' {}", webmention),
+            None => "'self'".to_string(),
+        };
+        Some(format!(
+            "default-src 'self'; script-src 'self' {}{} 'inline-speculation-rules'; \
+             connect-src 'self' {}; img-src 'self' data:; style-src 'self' 'unsafe-inline'; \
+             frame-src {}; object-src 'none'; base-uri 'self'; form-action {}",
+            script_origin,
+            theme,
+            endpoint,
+            PLAYER_ORIGINS.join(" "),
+            form_action
+        ))
+    }
+
+    /// The CSP `<meta>` element, or an empty string without a policy.
+    pub fn csp_meta_html(&self) -> String {
+        self.content_security_policy()
+            .map(|policy| {
+                format!(
+                    "<meta http-equiv=\"Content-Security-Policy\" content=\"{}\" />",
+                    escape_html(&policy)
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The SRI hash (`sha384-…`) of `script`.
+pub fn subresource_integrity(script: &[u8]) -> String {
+    format!("sha384-{}", base64(&Sha384::digest(script)))
+}
+
+/// Fetches the provider script and compares its hash with the configured
+/// one. Returns the actual hash when they differ; `None` when they match,
+/// no script is used, or the script is unavailable.
+pub fn integrity_mismatch(analytics: &Analytics) -> Option<String> {
+    let src = analytics.script_src()?;
+    let script = CacheEntry::new(Path::new(CACHE_DIR), "analytics-script.js").get_or_fetch_bytes(
+        MAX_AGE,
+        offline(),
+        || fetch_bytes(src),
+    )?;
+    let actual = subresource_integrity(&script);
+    (analytics.integrity() != Some(actual.as_str())).then_some(actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAUSIBLE: Analytics = Analytics::Plausible {
+        domain: "everythingsings.art",
+        integrity: "sha384-abc",
+    };
+
+    #[test]
+    fn off_and_log_modes_add_nothing() {
+        for analytics in [Analytics::Off, Analytics::AccessLogs] {
+            assert_eq!(analytics.script_html(), "");
+            assert_eq!(analytics.csp_meta_html(), "");
+        }
+        assert_eq!(Analytics::default(), Analytics::Off);
+    }
+
+    #[test]
+    fn scripts_carry_integrity_and_provider_data() {
+        assert_eq!(
+            PLAUSIBLE.script_html(),
+            "<script defer src=\"https://plausible.io/js/script.js\" integrity=\"sha384-abc\" \
+             crossorigin=\"anonymous\" data-domain=\"everythingsings.art\"></script>"
+        );
+        let goatcounter = Analytics::GoatCounter {
+            code: "es",
+            integrity: "sha384-xyz",
+        };
+        assert!(goatcounter
+            .script_html()
+            .contains("src=\"https://gc.zgo.at/count.v4.js\" integrity=\"sha384-xyz\""));
+        assert!(goatcounter
+            .script_html()
+            .contains("data-goatcounter=\"https://es.goatcounter.com/count\""));
+    }
+
+    #[test]
+    fn policy_allows_only_the_provider() {
+        let policy = Analytics::GoatCounter {
+            code: "es",
+            integrity: "sha384-xyz",
+        }
+        .content_security_policy()
+        .unwrap();
+        let theme = csp_source()
+            .map(|hash| format!(" {}", hash))
+            .unwrap_or_default();
+        assert!(policy.contains(&format!(
+            "script-src 'self' https://gc.zgo.at{} 'inline-speculation-rules';",
+            theme
+        )));
+        assert!(policy.contains("connect-src 'self' https://es.goatcounter.com;"));
+        assert!(policy.starts_with("default-src 'self';"));
+        assert!(policy.contains("frame-src https://www.youtube-nocookie.com "));
+        assert!(!policy.contains('*'));
+    }
+
+    #[test]
+    fn integrity_is_sha384_base64() {
+        assert_eq!(
+            subresource_integrity(b""),
+            "sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb"
+        );
+    }
+}
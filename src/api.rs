@@ -0,0 +1,183 @@
+//! # JSON API
+//!
+//! Static JSON documents under `/api/` for agents that want the profile as
+//! plain structured data, without parsing HTML or JSON-LD.
+//!
+//! ## Documents
+//!
+//! - `/api/profile.json`: name, bio, avatar, and profile links
+//! - `/api/links.json`: links by [`LinkGroup`] with descriptions, `rel`
+//!   values, and when each was last verified, versioned by
+//!   [`LINKS_SCHEMA_VERSION`]
+
+use crate::components::{rendered_links, LinkEntry, LinkGroup, LINKS};
+use crate::entity::primary_entity;
+use crate::linkcheck::VerifiedLinks;
+use crate::url::absolute;
+use serde_json::{json, Value};
+
+/// Output subdirectory for API documents.
+pub const API_DIR: &str = "api";
+
+/// Site-relative URL of the profile document.
+pub const PROFILE_JSON_PATH: &str = "/api/profile.json";
+
+/// Site-relative URL of the links document.
+pub const LINKS_JSON_PATH: &str = "/api/links.json";
+
+/// Version of the `/api/links.json` format.
+///
+/// Bump when a field is removed, renamed, or changes meaning; adding fields
+/// is backwards compatible and keeps the version.
+pub const LINKS_SCHEMA_VERSION: u32 = 1;
+
+/// The profile links as JSON objects, in display order.
+fn links_json() -> Vec<Value> {
+    LINKS
+        .iter()
+        .map(|link| {
+            json!({
+                "label": link.label,
+                "url": link.href,
+                "description": link.description,
+            })
+        })
+        .collect()
+}
+
+/// Generates `/api/profile.json` for the primary entity.
+pub fn generate_profile_json() -> String {
+    let entity = primary_entity();
+    let doc = json!({
+        "name": entity.name,
+        "bio": entity.description,
+        "url": entity.url,
+        "avatar": absolute(entity.image),
+        "links": links_json(),
+    });
+    serde_json::to_string_pretty(&doc).expect("profile serializes")
+}
+
+fn link_json(link: &LinkEntry, verified: &VerifiedLinks) -> Value {
+    json!({
+        "label": link.label,
+        "url": link.href,
+        "description": link.description,
+        "rel": link.rel.values(),
+        "verified": verified.get(link.href),
+    })
+}
+
+/// Generates `/api/links.json`: every link shown on the homepage, grouped.
+///
+/// Groups keep their display order; groups without links are omitted.
+/// `verified` is the RFC 3339 time a link was last checked, or `null`.
+pub fn generate_links_json(verified: &VerifiedLinks) -> String {
+    let links = rendered_links();
+    let groups: Vec<Value> = LinkGroup::ALL
+        .iter()
+        .filter_map(|&group| {
+            let members: Vec<Value> = links
+                .iter()
+                .filter(|l| l.group == group)
+                .map(|l| link_json(l, verified))
+                .collect();
+            (!members.is_empty()).then(|| json!({ "name": group.label(), "links": members }))
+        })
+        .collect();
+    let doc = json!({
+        "schema_version": LINKS_SCHEMA_VERSION,
+        "groups": groups,
+    });
+    serde_json::to_string_pretty(&doc).expect("links serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::BOOKING_ENABLED;
+    use crate::config::SITE_URL;
+    use crate::config::{AVATAR_PATH, SITE_NAME};
+
+    fn profile() -> Value {
+        serde_json::from_str(&generate_profile_json()).unwrap()
+    }
+
+    #[test]
+    fn profile_has_identity_fields() {
+        let json = profile();
+        assert_eq!(json["name"], SITE_NAME);
+        assert_eq!(json["url"], SITE_URL);
+        assert_eq!(json["avatar"], format!("{}{}", SITE_URL, AVATAR_PATH));
+    }
+
+    #[test]
+    fn profile_lists_every_link_in_order() {
+        let json = profile();
+        let links = json["links"].as_array().unwrap();
+        assert_eq!(links.len(), LINKS.len());
+        assert_eq!(links[0]["label"], LINKS[0].label);
+        assert_eq!(links[0]["url"], LINKS[0].href);
+    }
+
+    #[test]
+    fn path_matches_output_dir() {
+        assert!(PROFILE_JSON_PATH.starts_with(&format!("/{}/", API_DIR)));
+    }
+
+    fn links() -> Value {
+        serde_json::from_str(&generate_links_json(&VerifiedLinks::default())).unwrap()
+    }
+
+    #[test]
+    fn links_document_is_versioned() {
+        assert_eq!(links()["schema_version"], LINKS_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn links_are_grouped_in_group_order() {
+        let json = links();
+        let names: Vec<&str> = json["groups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|g| g["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["Create", "Think", "Build", "Support", "Connect"]);
+    }
+
+    #[test]
+    fn every_link_is_exported_once_with_rel() {
+        let json = links();
+        let exported: Vec<&Value> = json["groups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|g| g["links"].as_array().unwrap())
+            .collect();
+        assert_eq!(exported.len(), LINKS.len() + BOOKING_ENABLED as usize);
+        let github = exported.iter().find(|l| l["label"] == "GitHub").unwrap();
+        assert_eq!(github["rel"], json!(["me", "noopener"]));
+        assert_eq!(github["description"], "Code is art");
+    }
+
+    #[test]
+    fn links_export_verification_time() {
+        let mut verified = VerifiedLinks::default();
+        verified.record(
+            "https://github.com/EverythingSings",
+            "2025-01-17T12:00:00Z".to_string(),
+        );
+        let json: Value = serde_json::from_str(&generate_links_json(&verified)).unwrap();
+        let links: Vec<&Value> = json["groups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|g| g["links"].as_array().unwrap())
+            .collect();
+        let github = links.iter().find(|l| l["label"] == "GitHub").unwrap();
+        assert_eq!(github["verified"], "2025-01-17T12:00:00Z");
+        let shop = links.iter().find(|l| l["label"] == "Shop").unwrap();
+        assert!(shop["verified"].is_null());
+    }
+}
@@ -5,28 +5,71 @@
 //!
 //! Note: The `<head>` element is rendered separately via `generate_head_html()`
 //! in main.rs because Leptos's view! macro doesn't support the `property`
-//! attribute needed for Open Graph meta tags.
+//! attribute needed for Open Graph meta tags. Its JSON-LD is the graph the
+//! body's components contribute (see [`crate::structured_data`]).
 
-use crate::components::{LinkList, Nav, ProfileCard};
+use crate::archive::ArchivedLinks;
+use crate::components::{
+    Footer, LinkList, Nav, ProfileCards, RecentListens, SectionNav, LINKS_SECTION, LISTENS_SECTION,
+};
+use crate::css;
+use crate::entity::primary_entity;
+use crate::favicon::Favicons;
+use crate::linkcheck::VerifiedLinks;
+use crate::listens::Listen;
+use crate::schema::site_nodes;
+use crate::structured_data::contribute;
 use leptos::prelude::*;
 
 /// The root application component.
 ///
 /// Renders just the `<body>` content. The `<head>` is handled separately
-/// via `generate_head_html()` in the SSG binary.
+/// via `generate_head_html()` in the SSG binary. `listens` fills the
+/// optional "Recently listening to" strip; `verified` timestamps the links,
+/// `archived` adds their snapshot fallbacks, and `icons` their favicons.
+/// `prints` links the shop to the prints page, and `activity` GitHub to
+/// the activity page.
 #[component]
-pub fn App() -> impl IntoView {
+pub fn App(
+    #[prop(optional)] listens: Vec<Listen>,
+    #[prop(optional)] verified: VerifiedLinks,
+    #[prop(optional)] archived: ArchivedLinks,
+    #[prop(optional)] icons: Favicons,
+    #[prop(optional)] prints: bool,
+    #[prop(optional)] activity: bool,
+) -> impl IntoView {
     view! {
-        <Body />
+        <Body
+            listens=listens
+            verified=verified
+            archived=archived
+            icons=icons
+            prints=prints
+            activity=activity
+        />
     }
 }
 
 /// The body component containing the main content.
 ///
-/// Uses Schema.org WebPage microdata for semantic structure.
+/// Uses Schema.org WebPage microdata for semantic structure. Each section
+/// below the profile gets an anchor heading, listed by the [`SectionNav`].
+/// After the profile cards' entities, contributes the site, the homepage,
+/// and the properties to the page's graph (see [`site_nodes`]).
 #[component]
-pub fn Body() -> impl IntoView {
-    view! {
+pub fn Body(
+    #[prop(optional)] listens: Vec<Listen>,
+    #[prop(optional)] verified: VerifiedLinks,
+    #[prop(optional)] archived: ArchivedLinks,
+    #[prop(optional)] icons: Favicons,
+    #[prop(optional)] prints: bool,
+    #[prop(optional)] activity: bool,
+) -> impl IntoView {
+    let mut sections = vec![LINKS_SECTION];
+    if !listens.is_empty() {
+        sections.push(LISTENS_SECTION);
+    }
+    let body = view! {
         <body
             itemscope
             itemtype="https://schema.org/WebPage"
@@ -35,65 +78,98 @@ pub fn Body() -> impl IntoView {
             <noscript>
                 <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
             </noscript>
-            <main class="container">
+            <main class=css::CONTAINER>
                 <Nav />
-                <ProfileCard />
-                <LinkList />
+                <ProfileCards />
+                <SectionNav sections=sections />
+                <section aria-labelledby=LINKS_SECTION.id()>
+                    <h2 id=LINKS_SECTION.id() class=css::VISUALLY_HIDDEN>{LINKS_SECTION.title}</h2>
+                    <LinkList
+                        verified=verified
+                        archived=archived
+                        icons=icons
+                        prints=prints
+                        activity=activity
+                    />
+                </section>
+                <RecentListens listens=listens />
             </main>
-            <footer></footer>
+            <Footer />
         </body>
+    };
+    for node in site_nodes(primary_entity()) {
+        contribute(node);
     }
+    body
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    /// Helper to render a component to HTML string for testing.
-    fn render<V: IntoView + 'static>(view: V) -> String {
-        view.to_html()
-    }
+    use crate::test_utils::{assert_attr, assert_exists, parse};
 
     #[test]
     fn app_renders_body_element() {
-        let html = render(App());
-        assert!(html.contains("<body"), "App should render <body> element");
+        let html = view! { <App /> }.to_html();
+        assert!(
+            html.starts_with("<body"),
+            "App should render <body> element"
+        );
     }
 
     #[test]
     fn app_does_not_render_head() {
         // Head is rendered separately via generate_head_html()
-        let html = render(App());
-        assert!(
-            !html.contains("<head"),
-            "App should not render <head> (handled by generate_head_html)"
+        let dom = parse(&view! { <App /> }.to_html());
+        assert_eq!(
+            dom.count("head > *"),
+            0,
+            "App should not render <head> content (handled by generate_head_html)"
         );
     }
 
     #[test]
     fn body_has_webpage_microdata() {
-        let html = render(Body());
-        assert!(
-            html.contains("itemtype=\"https://schema.org/WebPage\""),
-            "Body should have WebPage microdata"
+        let dom = parse(&view! { <Body /> }.to_html());
+        assert_attr(
+            &dom,
+            "body[itemscope]",
+            "itemtype",
+            "https://schema.org/WebPage",
         );
     }
 
     #[test]
     fn body_contains_main_element() {
-        let html = render(Body());
-        assert!(
-            html.contains("<main"),
-            "Body should contain <main> element"
+        let dom = parse(&view! { <Body /> }.to_html());
+        assert_eq!(
+            dom.count("body > main"),
+            1,
+            "Body should contain one <main>"
         );
     }
 
     #[test]
     fn body_contains_footer() {
-        let html = render(Body());
-        assert!(
-            html.contains("<footer"),
-            "Body should contain <footer> element"
-        );
+        let dom = parse(&view! { <Body /> }.to_html());
+        assert_exists(&dom, "body > footer");
+    }
+
+    #[test]
+    fn section_nav_targets_section_headings() {
+        assert!(!parse(&view! { <Body /> }.to_html()).exists(".section-nav"));
+        let listens = vec![Listen {
+            track: "Track".to_string(),
+            artist: "Artist".to_string(),
+            release: None,
+            url: None,
+            now_playing: false,
+        }];
+        let dom = parse(&view! { <Body listens=listens /> }.to_html());
+        for href in dom.attrs(".section-nav a", "href") {
+            let id = &href[1..];
+            assert_exists(&dom, &format!("section[aria-labelledby={id}] > h2#{id}"));
+        }
+        assert_eq!(dom.count(".section-nav a"), 2);
     }
 }
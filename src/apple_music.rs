@@ -0,0 +1,353 @@
+//! # Apple Music Releases
+//!
+//! An album or track on Apple Music, described from the iTunes Search API
+//! at build time. A series names the release it goes with in its
+//! `series.toml`:
+//!
+//! ```toml
+//! apple_music = "https://music.apple.com/us/album/neon/1234567890"
+//! ```
+//!
+//! The lookup (through [`crate::cache`]) gives the title, artist, release
+//! date, genre, and tracklist; the artwork is self-hosted like the
+//! [lite embed](crate::embeds) thumbnails. Pages render the release
+//! statically and describe it as a Schema.org `MusicAlbum` or
+//! `MusicRecording`.
+
+use crate::cache::{cached_fetch, fetch_bytes, json_text, offline, url_key, CacheEntry, CACHE_DIR};
+use crate::embeds::{normalize, EMBEDS_DIR};
+use crate::url::absolute;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long a cached lookup is used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Edge length of the artwork requested from Apple, in pixels.
+const ARTWORK_SIZE: u32 = 600;
+
+/// Whether a release is a whole album or a single track.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReleaseKind {
+    Album,
+    Track,
+}
+
+/// One track of an album.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub number: u32,
+    pub title: String,
+    pub duration_ms: Option<u64>,
+}
+
+/// An Apple Music album or track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppleMusicItem {
+    pub kind: ReleaseKind,
+    pub title: String,
+    pub artist: String,
+    /// The album a track appears on.
+    pub album: Option<String>,
+    /// The Apple Music page.
+    pub url: String,
+    /// Artwork URL: Apple's when parsed, then the self-hosted path once
+    /// loaded (or `None` if it could not be fetched).
+    pub artwork: Option<String>,
+    /// Release date as `YYYY-MM-DD`.
+    pub release_date: Option<String>,
+    pub genre: Option<String>,
+    pub duration_ms: Option<u64>,
+    /// The album's tracks, in order; empty for a track.
+    pub tracks: Vec<Track>,
+}
+
+/// The storefront, lookup id, and kind of an Apple Music album or song URL.
+pub fn lookup_target(url: &str) -> Option<(&str, &str, ReleaseKind)> {
+    let path = url.strip_prefix("https://music.apple.com/")?;
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut segments = path.split('/');
+    let storefront = segments.next()?;
+    let kind = segments.next()?;
+    let id = segments.next_back()?;
+    let track = query.split('&').find_map(|pair| pair.strip_prefix("i="));
+    let (id, kind) = match (kind, track) {
+        ("album", Some(track)) => (track, ReleaseKind::Track),
+        ("album", None) => (id, ReleaseKind::Album),
+        ("song", _) => (id, ReleaseKind::Track),
+        _ => return None,
+    };
+    let valid = storefront.len() == 2
+        && storefront.chars().all(|c| c.is_ascii_lowercase())
+        && !id.is_empty()
+        && id.chars().all(|c| c.is_ascii_digit());
+    valid.then_some((storefront, id, kind))
+}
+
+/// The iTunes Search API lookup URL for an Apple Music URL.
+pub fn lookup_url(url: &str) -> Option<String> {
+    let (storefront, id, _) = lookup_target(url)?;
+    Some(format!(
+        "https://itunes.apple.com/lookup?id={}&entity=song&country={}",
+        id, storefront
+    ))
+}
+
+/// Formats a duration as ISO 8601, e.g. `PT3M20S`.
+pub fn iso_duration(ms: u64) -> String {
+    let secs = ms.div_ceil(1000);
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, m, s) => format!("PT{}M{}S", m, s),
+        (h, m, s) => format!("PT{}H{}M{}S", h, m, s),
+    }
+}
+
+/// Parses a lookup response for the Apple Music page `url`.
+pub fn parse_lookup(url: &str, json: &str) -> Option<AppleMusicItem> {
+    let (_, id, kind) = lookup_target(url)?;
+    let doc: Value = serde_json::from_str(json).ok()?;
+    let results = doc["results"].as_array()?;
+    let id_key = match kind {
+        ReleaseKind::Album => "collectionId",
+        ReleaseKind::Track => "trackId",
+    };
+    let item = results
+        .iter()
+        .find(|r| r[id_key].as_u64().map(|n| n.to_string()).as_deref() == Some(id))?;
+    let title = match kind {
+        ReleaseKind::Album => json_text(item, "collectionName")?,
+        ReleaseKind::Track => json_text(item, "trackName")?,
+    };
+    let mut tracks: Vec<Track> = match kind {
+        ReleaseKind::Album => results
+            .iter()
+            .filter(|r| r["wrapperType"] == "track")
+            .filter_map(|r| {
+                Some(Track {
+                    number: r["trackNumber"].as_u64()? as u32,
+                    title: json_text(r, "trackName")?,
+                    duration_ms: r["trackTimeMillis"].as_u64(),
+                })
+            })
+            .collect(),
+        ReleaseKind::Track => Vec::new(),
+    };
+    tracks.sort_by_key(|t| t.number);
+    Some(AppleMusicItem {
+        kind,
+        title,
+        artist: json_text(item, "artistName")?,
+        album: (kind == ReleaseKind::Track)
+            .then(|| json_text(item, "collectionName"))
+            .flatten(),
+        url: url.to_string(),
+        artwork: json_text(item, "artworkUrl100")
+            .map(|a| a.replace("100x100bb", &format!("{}x{}bb", ARTWORK_SIZE, ARTWORK_SIZE))),
+        release_date: json_text(item, "releaseDate").and_then(|d| d.get(..10).map(str::to_string)),
+        genre: json_text(item, "primaryGenreName"),
+        duration_ms: item["trackTimeMillis"].as_u64(),
+        tracks,
+    })
+}
+
+impl AppleMusicItem {
+    /// Schema.org type of the release.
+    pub fn schema_type(&self) -> &'static str {
+        match self.kind {
+            ReleaseKind::Album => "MusicAlbum",
+            ReleaseKind::Track => "MusicRecording",
+        }
+    }
+
+    /// The release as a Schema.org `MusicAlbum` or `MusicRecording`.
+    pub fn to_json_ld(&self) -> Value {
+        let mut item = json!({
+            "@type": self.schema_type(),
+            "name": self.title,
+            "byArtist": { "@type": "MusicGroup", "name": self.artist },
+            "url": self.url,
+        });
+        let fields = [
+            ("image", self.artwork.as_ref().map(|a| json!(absolute(a)))),
+            (
+                "datePublished",
+                self.release_date.as_ref().map(|d| json!(d)),
+            ),
+            ("genre", self.genre.as_ref().map(|g| json!(g))),
+            (
+                "duration",
+                self.duration_ms.map(|ms| json!(iso_duration(ms))),
+            ),
+            (
+                "inAlbum",
+                self.album
+                    .as_ref()
+                    .map(|a| json!({ "@type": "MusicAlbum", "name": a })),
+            ),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                item[key] = value;
+            }
+        }
+        if !self.tracks.is_empty() {
+            item["numTracks"] = json!(self.tracks.len());
+            item["track"] = self
+                .tracks
+                .iter()
+                .map(|t| {
+                    let mut track = json!({
+                        "@type": "MusicRecording",
+                        "name": t.title,
+                        "position": t.number,
+                    });
+                    if let Some(ms) = t.duration_ms {
+                        track["duration"] = json!(iso_duration(ms));
+                    }
+                    track
+                })
+                .collect();
+        }
+        item
+    }
+}
+
+/// Looks up the release at `url` and self-hosts its artwork under
+/// `<site_dir>/embeds/`. `None` when the URL is not an Apple Music album
+/// or song, or the lookup is unavailable.
+pub fn load(url: &str, site_dir: &Path) -> std::io::Result<Option<AppleMusicItem>> {
+    let Some(lookup) = lookup_url(url) else {
+        eprintln!("Warning: Not an Apple Music album or song: {}", url);
+        return Ok(None);
+    };
+    let Some(json) = cached_fetch(
+        &format!("apple-music-{}.json", url_key(url)),
+        &lookup,
+        MAX_AGE,
+    ) else {
+        return Ok(None);
+    };
+    let Some(mut item) = parse_lookup(url, &json) else {
+        eprintln!("Warning: Apple Music lookup found nothing for {}", url);
+        return Ok(None);
+    };
+
+    let artwork = item.artwork.take().and_then(|src| {
+        let file_name = format!("{}.jpg", url_key(&src));
+        let jpeg = CacheEntry::new(&Path::new(CACHE_DIR).join(EMBEDS_DIR), &file_name)
+            .get_or_fetch_bytes(MAX_AGE, offline(), || {
+                fetch_bytes(&src).and_then(|data| normalize(&data))
+            })?;
+        Some((file_name, jpeg))
+    });
+    if let Some((file_name, jpeg)) = artwork {
+        let out_dir = site_dir.join(EMBEDS_DIR);
+        std::fs::create_dir_all(&out_dir)?;
+        std::fs::write(out_dir.join(&file_name), jpeg)?;
+        item.artwork = Some(format!("/{}/{}", EMBEDS_DIR, file_name));
+    }
+    Ok(Some(item))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::config::SITE_URL;
+
+    const ALBUM_URL: &str = "https://music.apple.com/us/album/neon/1440000001";
+
+    const LOOKUP: &str = r#"{"resultCount": 3, "results": [
+        {"wrapperType": "collection", "collectionId": 1440000001,
+         "collectionName": "Neon", "artistName": "EverythingSings",
+         "artworkUrl100": "https://is1.mzstatic.com/a/100x100bb.jpg",
+         "releaseDate": "2024-11-01T08:00:00Z", "primaryGenreName": "Electronic"},
+        {"wrapperType": "track", "trackId": 1440000003, "trackNumber": 2,
+         "trackName": "Afterglow", "collectionName": "Neon",
+         "artistName": "EverythingSings", "trackTimeMillis": 200000},
+        {"wrapperType": "track", "trackId": 1440000002, "trackNumber": 1,
+         "trackName": "Dawn", "collectionName": "Neon",
+         "artistName": "EverythingSings", "trackTimeMillis": 3725500}
+    ]}"#;
+
+    pub fn sample_item() -> AppleMusicItem {
+        AppleMusicItem {
+            artwork: Some("/embeds/8899aabbccddeeff.jpg".to_string()),
+            ..parse_lookup(ALBUM_URL, LOOKUP).unwrap()
+        }
+    }
+
+    #[test]
+    fn urls_map_to_lookup_ids() {
+        assert_eq!(
+            lookup_target(ALBUM_URL),
+            Some(("us", "1440000001", ReleaseKind::Album))
+        );
+        assert_eq!(
+            lookup_target("https://music.apple.com/gb/album/neon/1440000001?i=1440000002"),
+            Some(("gb", "1440000002", ReleaseKind::Track))
+        );
+        assert_eq!(
+            lookup_target("https://music.apple.com/us/song/dawn/1440000002"),
+            Some(("us", "1440000002", ReleaseKind::Track))
+        );
+        assert_eq!(
+            lookup_target("https://music.apple.com/us/artist/es/123"),
+            None
+        );
+        assert_eq!(
+            lookup_target("https://music.apple.com/us/album/x/1&x=2"),
+            None
+        );
+        assert_eq!(
+            lookup_url(ALBUM_URL).as_deref(),
+            Some("https://itunes.apple.com/lookup?id=1440000001&entity=song&country=us")
+        );
+    }
+
+    #[test]
+    fn albums_parse_with_ordered_tracklist() {
+        let item = parse_lookup(ALBUM_URL, LOOKUP).unwrap();
+        assert_eq!(item.title, "Neon");
+        assert_eq!(item.artist, "EverythingSings");
+        assert_eq!(item.release_date.as_deref(), Some("2024-11-01"));
+        assert_eq!(
+            item.artwork.as_deref(),
+            Some("https://is1.mzstatic.com/a/600x600bb.jpg")
+        );
+        let titles: Vec<&str> = item.tracks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["Dawn", "Afterglow"]);
+    }
+
+    #[test]
+    fn tracks_parse_with_their_album() {
+        let url = "https://music.apple.com/us/album/neon/1440000001?i=1440000003";
+        let item = parse_lookup(url, LOOKUP).unwrap();
+        assert_eq!(item.kind, ReleaseKind::Track);
+        assert_eq!(item.title, "Afterglow");
+        assert_eq!(item.album.as_deref(), Some("Neon"));
+        assert!(item.tracks.is_empty());
+        assert_eq!(parse_lookup(url, r#"{"results": []}"#), None);
+    }
+
+    #[test]
+    fn durations_are_iso_8601() {
+        assert_eq!(iso_duration(200_000), "PT3M20S");
+        assert_eq!(iso_duration(3_725_500), "PT1H2M6S");
+    }
+
+    #[test]
+    fn json_ld_describes_album_and_tracks() {
+        let ld = sample_item().to_json_ld();
+        assert_eq!(ld["@type"], "MusicAlbum");
+        assert_eq!(ld["byArtist"]["name"], "EverythingSings");
+        assert_eq!(
+            ld["image"],
+            format!("{}/embeds/8899aabbccddeeff.jpg", SITE_URL)
+        );
+        assert_eq!(ld["numTracks"], 2);
+        assert_eq!(ld["track"][0]["name"], "Dawn");
+        assert_eq!(ld["track"][1]["duration"], "PT3M20S");
+        assert!(ld.get("inAlbum").is_none());
+    }
+}
@@ -0,0 +1,200 @@
+//! # Wayback Machine Archiving
+//!
+//! `archive-links` submits every external link of the generated site (see
+//! [`external_links`]) to the Internet Archive's Save Page Now API and
+//! records the snapshot URLs in `data/links-archived.json`:
+//!
+//! ```json
+//! {
+//!   "https://github.com/EverythingSings": {
+//!     "snapshot": "https://web.archive.org/web/20250117120000/https://github.com/EverythingSings",
+//!     "archived_at": "2025-01-17T12:00:00Z"
+//!   }
+//! }
+//! ```
+//!
+//! Links archived within [`REARCHIVE_AFTER`] are skipped, and requests are
+//! spaced by [`REQUEST_INTERVAL`] to stay inside the API's rate limit. With
+//! [`ARCHIVE_FALLBACK_LINKS`](crate::config::ARCHIVE_FALLBACK_LINKS) on, the
+//! homepage shows each snapshot next to its link in case the original
+//! disappears.
+
+use crate::hints::origin;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Snapshot state file, relative to the project root.
+pub const ARCHIVE_PATH: &str = "data/links-archived.json";
+
+/// Save Page Now endpoint; the target URL is appended.
+pub const SAVE_ENDPOINT: &str = "https://web.archive.org/save/";
+
+/// How long a snapshot is considered current.
+pub const REARCHIVE_AFTER: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Pause between Save API requests.
+pub const REQUEST_INTERVAL: Duration = Duration::from_secs(10);
+
+/// User agent sent with archive requests.
+const USER_AGENT: &str = concat!("everythingsings-archive/", env!("CARGO_PKG_VERSION"));
+
+/// An archived copy of a link.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// Wayback Machine URL of the snapshot.
+    pub snapshot: String,
+    /// When the snapshot was requested, RFC 3339.
+    pub archived_at: String,
+}
+
+/// Snapshots by link URL.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArchivedLinks(BTreeMap<String, Snapshot>);
+
+impl ArchivedLinks {
+    /// Loads the state from `path`; `Ok(None)` when the file is absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map(Some)
+            .map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    /// Parses the state file contents.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json)
+            .map(ArchivedLinks)
+            .map_err(|e| e.to_string())
+    }
+
+    /// The snapshot of `url`, if archived.
+    pub fn get(&self, url: &str) -> Option<&Snapshot> {
+        self.0.get(url)
+    }
+
+    /// Records a snapshot of `url`.
+    pub fn record(&mut self, url: &str, snapshot: Snapshot) {
+        self.0.insert(url.to_string(), snapshot);
+    }
+
+    /// Whether `url` needs a new snapshot: it has none, or its snapshot is
+    /// older than `cutoff` (an RFC 3339 UTC timestamp, which sorts as text).
+    pub fn is_due(&self, url: &str, cutoff: &str) -> bool {
+        self.get(url)
+            .is_none_or(|snapshot| snapshot.archived_at.as_str() < cutoff)
+    }
+
+    /// Serializes the state, sorted by URL.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.0).expect("archive state serializes")
+    }
+
+    /// Writes the state to `path`, creating its directory.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_json() + "\n")
+    }
+}
+
+/// The external `http(s)` links of the page `html`, in document order, as
+/// written: links back to `site_url` are left out.
+pub fn external_links(html: &str, site_url: &str) -> Vec<String> {
+    let own = origin(site_url);
+    let selector = Selector::parse("a[href]").expect("static selector");
+    Html::parse_document(html)
+        .select(&selector)
+        .filter_map(|a| a.value().attr("href"))
+        .filter(|href| origin(href).is_some_and(|o| Some(&o) != own.as_ref()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Resolves the snapshot location reported by the Save API: an absolute URL
+/// or a `/web/...` path on the Wayback Machine.
+pub fn snapshot_url(location: &str) -> Option<String> {
+    if location.starts_with("https://web.archive.org/web/") {
+        Some(location.to_string())
+    } else if location.starts_with("/web/") {
+        Some(format!("https://web.archive.org{}", location))
+    } else {
+        None
+    }
+}
+
+/// Asks the Wayback Machine to archive `url` and returns the snapshot URL.
+pub fn save_url(url: &str) -> Result<String, String> {
+    use ureq::ResponseExt;
+
+    let response = ureq::get(&format!("{}{}", SAVE_ENDPOINT, url))
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| format!("Save {}: {}", url, e))?;
+    let location = response
+        .headers()
+        .get("Content-Location")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| response.get_uri().to_string());
+    snapshot_url(&location).ok_or_else(|| format!("Save {}: no snapshot in response", url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(at: &str) -> Snapshot {
+        Snapshot {
+            snapshot: "https://web.archive.org/web/2025/https://a.test".to_string(),
+            archived_at: at.to_string(),
+        }
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let mut state = ArchivedLinks::default();
+        state.record("https://a.test", snapshot("2025-01-17T12:00:00Z"));
+        assert_eq!(ArchivedLinks::parse(&state.to_json()).unwrap(), state);
+    }
+
+    #[test]
+    fn only_missing_or_stale_links_are_due() {
+        let mut state = ArchivedLinks::default();
+        state.record("https://a.test", snapshot("2025-01-17T12:00:00Z"));
+        assert!(!state.is_due("https://a.test", "2025-01-01T00:00:00Z"));
+        assert!(state.is_due("https://a.test", "2025-02-01T00:00:00Z"));
+        assert!(state.is_due("https://b.test", "2025-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn only_external_links_are_archived() {
+        let html = r#"<a href="https://b.test/x">B</a><a href="/cv/">CV</a>
+            <a href="https://a.test/art/">Art</a><a href="mailto:a@b.test">Mail</a>
+            <a href="http://c.test">C</a>"#;
+        assert_eq!(
+            external_links(html, "https://a.test"),
+            ["https://b.test/x", "http://c.test"]
+        );
+    }
+
+    #[test]
+    fn snapshot_locations_are_resolved() {
+        assert_eq!(
+            snapshot_url("/web/20250117120000/https://a.test/").as_deref(),
+            Some("https://web.archive.org/web/20250117120000/https://a.test/")
+        );
+        assert_eq!(
+            snapshot_url("https://web.archive.org/web/1/https://a.test").as_deref(),
+            Some("https://web.archive.org/web/1/https://a.test")
+        );
+        assert_eq!(snapshot_url("https://web.archive.org/save/x"), None);
+    }
+}
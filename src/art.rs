@@ -1,10 +1,29 @@
 //! # Art Series Data Model
 //!
-//! Reads art series from the filesystem (`public/art/<slug>/series.toml`)
-//! and provides typed data for gallery page generation.
+//! Reads art series from the filesystem (`public/art/<dir>/series.toml`)
+//! and provides typed data for gallery page generation. A series' slug is
+//! its directory name's (see [`crate::slug`]), and a series whose page
+//! used to be at its directory name leaves a redirect there (see
+//! [`ArtSeries::old_segments`]); its images keep their URLs
+//! under the directory they are copied from, which for an unlisted series
+//! is its hashed page directory. A series may name
+//! a Mastodon thread whose replies become its comments (see
+//! [`crate::mastodon`]) and a Nostr note; both copies' reaction counts are
+//! shown on its page. A series with `unlisted = true` is only reachable by
+//! its link (see [`crate::unlisted`]).
 
+use crate::config::ART_PERMALINK;
+use crate::mastodon::MastodonThread;
+use crate::meta_tags::MetaImage;
+use crate::permalink;
+use crate::placeholder::{self, Placeholder};
+use crate::slug::{slug, Slugs};
+use crate::text::{encode_path_segment, nfc};
+use crate::unlisted;
+use crate::url::{absolute, dir_path, page_path};
 use serde::Deserialize;
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 /// Raw TOML representation of a series.
 #[derive(Deserialize)]
@@ -14,6 +33,18 @@ pub struct SeriesToml {
     pub date: String,
     pub cover: Option<String>,
     pub images: Vec<ImageToml>,
+    /// Mastodon thread holding the series' comments.
+    pub comments: Option<MastodonThread>,
+    /// `note1…` id of the series' Nostr copy.
+    pub nostr_note: Option<String>,
+    /// YouTube, Bandcamp, or Apple Music URLs shown as lite embeds.
+    #[serde(default)]
+    pub embeds: Vec<String>,
+    /// Apple Music album or track the series goes with.
+    pub apple_music: Option<String>,
+    /// Build the page at an unguessable path, left out of listings.
+    #[serde(default)]
+    pub unlisted: bool,
 }
 
 /// Raw TOML representation of an image entry.
@@ -26,14 +57,82 @@ pub struct ImageToml {
 }
 
 /// A resolved art series ready for rendering.
+///
+/// All text fields are NFC-normalized; `cover_url` and image URLs are
+/// percent-encoded.
 #[derive(Clone)]
 pub struct ArtSeries {
     pub slug: String,
+    /// The directory the series was read from, e.g. `public/art/neon`.
+    pub source: PathBuf,
     pub title: String,
     pub description: String,
     pub date: String,
     pub cover_url: String,
+    /// Blurred preview of the cover, when it could be decoded.
+    pub cover_placeholder: Option<Placeholder>,
     pub images: Vec<ArtImage>,
+    pub comments: Option<MastodonThread>,
+    pub nostr_note: Option<String>,
+    pub embeds: Vec<String>,
+    pub apple_music: Option<String>,
+    pub unlisted: bool,
+}
+
+impl ArtSeries {
+    /// Path segments of the series page: under [`ART_PERMALINK`], or a
+    /// hashed path when unlisted.
+    pub fn segments(&self) -> Vec<String> {
+        if self.unlisted {
+            return unlisted::segments(&format!("art/{}", self.slug));
+        }
+        permalink::resolve(ART_PERMALINK, &self.slug, &self.date)
+            .expect("series permalinks are checked when discovered")
+    }
+
+    /// Path segments of the page at the series' directory name, where it
+    /// was before series had slugs, when that differs from
+    /// [`segments`](Self::segments). Unlisted series never had one.
+    pub fn old_segments(&self) -> Option<Vec<String>> {
+        if self.unlisted {
+            return None;
+        }
+        let dir = nfc(self.source.file_name()?.to_str()?);
+        permalink::resolve(ART_PERMALINK, &dir, &self.date)
+            .ok()
+            .filter(|old| *old != self.segments())
+    }
+
+    /// Site-relative URL of the series page, e.g. `/art/neon-dreams/`.
+    pub fn url_path(&self) -> String {
+        page_path(&self.segments())
+    }
+
+    /// Site-relative URL of the series page's directory, which holds its
+    /// oEmbed document in either page layout, and an unlisted series'
+    /// images.
+    pub fn dir_path(&self) -> String {
+        dir_path(&self.segments())
+    }
+
+    /// The cover as a page's preview image, with its dimensions when known
+    /// and its alt text when it is one of the series' images.
+    pub fn cover_image(&self) -> MetaImage {
+        let mut image = MetaImage::new(absolute(&self.cover_url));
+        if let Some(p) = &self.cover_placeholder {
+            image = image.size(p.width, p.height);
+        }
+        match self.images.iter().find(|i| i.url == self.cover_url) {
+            Some(cover) => image.alt(&cover.alt),
+            None => image,
+        }
+    }
+}
+
+/// The series shown in listings, the sitemap, and exports: all but the
+/// unlisted ones.
+pub fn listed(series: &[ArtSeries]) -> Vec<ArtSeries> {
+    series.iter().filter(|s| !s.unlisted).cloned().collect()
 }
 
 /// A resolved image with URL paths.
@@ -43,11 +142,16 @@ pub struct ArtImage {
     pub alt: String,
     pub title: Option<String>,
     pub description: Option<String>,
+    /// Blurred preview shown until the image loads.
+    pub placeholder: Option<Placeholder>,
 }
 
 /// Discovers all art series from `<base>/art/*/series.toml`.
 ///
-/// Returns series sorted by date descending (newest first).
+/// Returns series sorted by date descending (newest first). A directory
+/// already named as its slug keeps it; others are read in name order, so
+/// directories that slug alike are numbered the same way on every build,
+/// and adding one never moves a live page.
 pub fn discover_series(base: &Path) -> Vec<ArtSeries> {
     let art_dir = base.join("art");
     if !art_dir.exists() {
@@ -56,13 +160,20 @@ pub fn discover_series(base: &Path) -> Vec<ArtSeries> {
 
     let mut series = Vec::new();
 
-    let entries = match std::fs::read_dir(&art_dir) {
-        Ok(e) => e,
+    let mut paths: Vec<_> = match std::fs::read_dir(&art_dir) {
+        Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
         Err(_) => return Vec::new(),
     };
+    paths.sort();
 
-    for entry in entries.flatten() {
-        let path = entry.path();
+    let mut slugs = Slugs::default();
+    let kept: BTreeSet<String> = paths
+        .iter()
+        .filter(|path| path.join("series.toml").exists())
+        .filter_map(|path| path.file_name()?.to_str().map(nfc))
+        .filter(|dir| slug(dir) == *dir && slugs.claim(dir))
+        .collect();
+    for path in paths {
         if !path.is_dir() {
             continue;
         }
@@ -72,8 +183,8 @@ pub fn discover_series(base: &Path) -> Vec<ArtSeries> {
             continue;
         }
 
-        let slug = match path.file_name().and_then(|n| n.to_str()) {
-            Some(s) => s.to_string(),
+        let dir = match path.file_name().and_then(|n| n.to_str()) {
+            Some(s) => nfc(s),
             None => continue,
         };
 
@@ -93,35 +204,61 @@ pub fn discover_series(base: &Path) -> Vec<ArtSeries> {
             }
         };
 
+        let slug = match kept.contains(&dir) {
+            true => dir.clone(),
+            false => slugs.unique(&dir),
+        };
+        if let Err(e) = permalink::resolve(ART_PERMALINK, &slug, &parsed.date) {
+            eprintln!("Warning: Skipping {}: {}", toml_path.display(), e);
+            continue;
+        }
+
+        // Unlisted images are copied beside their page, not to /art/<dir>/
+        let image_dir = match parsed.unlisted {
+            true => dir_path(&unlisted::segments(&format!("art/{}", slug))),
+            false => format!("/art/{}/", encode_path_segment(&dir)),
+        };
         let images: Vec<ArtImage> = parsed
             .images
             .iter()
             .map(|img| ArtImage {
-                url: format!("/art/{}/{}", slug, img.file),
-                alt: img.alt.clone(),
-                title: img.title.clone(),
-                description: img.description.clone(),
+                url: format!("{}{}", image_dir, encode_path_segment(&img.file)),
+                alt: nfc(&img.alt),
+                title: img.title.as_deref().map(nfc),
+                description: img.description.as_deref().map(nfc),
+                placeholder: placeholder::from_file(&path.join(&img.file)),
             })
             .collect();
 
         let cover_url = parsed
             .cover
             .as_ref()
-            .map(|c| format!("/art/{}/{}", slug, c))
+            .map(|c| format!("{}{}", image_dir, encode_path_segment(c)))
             .unwrap_or_else(|| {
                 images
                     .first()
                     .map(|i| i.url.clone())
                     .unwrap_or_default()
             });
+        let cover_placeholder = match &parsed.cover {
+            Some(cover) => placeholder::from_file(&path.join(cover)),
+            None => images.first().and_then(|i| i.placeholder.clone()),
+        };
 
         series.push(ArtSeries {
             slug,
-            title: parsed.title,
-            description: parsed.description,
+            source: path,
+            title: nfc(&parsed.title),
+            description: nfc(&parsed.description),
             date: parsed.date,
             cover_url,
+            cover_placeholder,
             images,
+            comments: parsed.comments,
+            nostr_note: parsed.nostr_note,
+            embeds: parsed.embeds,
+            apple_music: parsed.apple_music,
+            unlisted: parsed.unlisted,
         });
     }
 
@@ -172,6 +309,7 @@ alt = "Test image two"
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].title, "Test Series");
         assert_eq!(result[0].slug, "test-series");
+        assert_eq!(result[0].old_segments(), None, "Already its slug");
     }
 
     #[test]
@@ -230,6 +368,90 @@ alt = "b"
         assert_eq!(result[1].title, "Older");
     }
 
+    #[test]
+    fn unicode_slug_and_text_are_normalized() {
+        let tmp = tempdir();
+        // Decomposed "é", as stored by some filesystems
+        let dir = tmp.join("art").join("cafe\u{301}");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("series.toml"),
+            "title = \"Cafe\u{301} 🎨\"\ndescription = \"فن\"\ndate = \"2025-01-01\"\n[[images]]\nfile = \"ü.jpg\"\nalt = \"a\"\n",
+        )
+        .unwrap();
+
+        let result = discover_series(&tmp);
+        assert_eq!(result[0].slug, "cafe");
+        assert_eq!(result[0].title, "Caf\u{e9} 🎨");
+        assert_eq!(result[0].url_path(), "/art/cafe/");
+        assert_eq!(result[0].images[0].url, "/art/caf%C3%A9/%C3%BC.jpg");
+        assert_eq!(
+            result[0].old_segments(),
+            Some(vec!["art".into(), "café".into()])
+        );
+    }
+
+    #[test]
+    fn directories_that_slug_alike_are_numbered() {
+        let tmp = tempdir();
+        for (dir, date) in [("Neon", "2024-01-01"), ("neon", "2025-01-01")] {
+            let dir = tmp.join("art").join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("series.toml"),
+                format!(
+                    "title = \"N\"\ndescription = \"\"\ndate = \"{}\"\nimages = []\n",
+                    date
+                ),
+            )
+            .unwrap();
+        }
+
+        // "neon" keeps its slug although "Neon" is read first
+        let result = discover_series(&tmp);
+        assert_eq!(result[0].url_path(), "/art/neon/");
+        assert_eq!(result[0].old_segments(), None);
+        assert_eq!(result[1].url_path(), "/art/neon-2/");
+        assert_eq!(
+            result[1].old_segments(),
+            Some(vec!["art".into(), "Neon".into()])
+        );
+    }
+
+    #[test]
+    fn unlisted_series_get_hashed_paths() {
+        let tmp = tempdir();
+        create_test_series(&tmp);
+        let toml = tmp.join("art/test-series/series.toml");
+        let content = fs::read_to_string(&toml).unwrap();
+        fs::write(&toml, format!("unlisted = true\n{}", content)).unwrap();
+
+        let result = discover_series(&tmp);
+        assert!(result[0].unlisted);
+        assert!(result[0].url_path().starts_with("/preview/"));
+        assert_eq!(result[0].old_segments(), None);
+        assert_eq!(
+            result[0].images[0].url,
+            format!("{}001.jpg", result[0].dir_path())
+        );
+        assert!(listed(&result).is_empty());
+    }
+
+    #[test]
+    fn decodable_images_get_placeholders() {
+        let tmp = tempdir();
+        create_test_series(&tmp);
+        image::RgbImage::new(32, 16)
+            .save(tmp.join("art/test-series/001.jpg"))
+            .unwrap();
+
+        let result = discover_series(&tmp);
+        let placeholder = result[0].images[0].placeholder.as_ref().unwrap();
+        assert_eq!((placeholder.width, placeholder.height), (32, 16));
+        assert_eq!(result[0].cover_placeholder.as_ref(), Some(placeholder));
+        assert_eq!(result[0].images[1].placeholder, None, "Missing file");
+    }
+
     use std::sync::atomic::{AtomicU32, Ordering};
     static COUNTER: AtomicU32 = AtomicU32::new(0);
 
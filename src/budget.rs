@@ -0,0 +1,179 @@
+//! # Page-Weight Budgets
+//!
+//! Checks a [`BuildReport`] against size limits so the site stays small as
+//! features accumulate. Limits default to the values below and can be
+//! overridden with an optional `budget.toml` at the project root.
+
+use crate::report::BuildReport;
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+
+/// Optional budget override file, relative to the project root.
+pub const BUDGET_PATH: &str = "budget.toml";
+
+/// Size limits, in kilobytes (1 KB = 1024 bytes).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Budgets {
+    /// Maximum size of any single HTML page.
+    pub html_kb_per_page: u64,
+    /// Maximum combined size of all stylesheets.
+    pub css_kb_total: u64,
+    /// Maximum combined size of all scripts.
+    pub js_kb_total: u64,
+    /// Maximum combined size of all images.
+    pub image_kb_total: u64,
+}
+
+impl Default for Budgets {
+    fn default() -> Self {
+        Self {
+            html_kb_per_page: 24,
+            css_kb_total: 32,
+            js_kb_total: 16,
+            image_kb_total: 1024,
+        }
+    }
+}
+
+/// A single exceeded limit.
+#[derive(Debug, PartialEq)]
+pub struct BudgetViolation {
+    pub budget: &'static str,
+    pub subject: String,
+    pub actual_bytes: u64,
+    pub limit_bytes: u64,
+}
+
+impl fmt::Display for BudgetViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} is {:.1} KB (limit {} KB)",
+            self.budget,
+            self.subject,
+            self.actual_bytes as f64 / 1024.0,
+            self.limit_bytes / 1024
+        )
+    }
+}
+
+fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    let lower = path.to_ascii_lowercase();
+    extensions.iter().any(|ext| lower.ends_with(ext))
+}
+
+impl Budgets {
+    /// Loads budgets from `path`, falling back to defaults when it is absent.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    /// Returns every budget the report exceeds.
+    pub fn check(&self, report: &BuildReport) -> Vec<BudgetViolation> {
+        let mut violations = Vec::new();
+
+        for file in report
+            .files
+            .iter()
+            .filter(|f| has_extension(&f.path, &[".html"]))
+        {
+            if file.bytes > self.html_kb_per_page * 1024 {
+                violations.push(BudgetViolation {
+                    budget: "HTML per page",
+                    subject: file.path.clone(),
+                    actual_bytes: file.bytes,
+                    limit_bytes: self.html_kb_per_page * 1024,
+                });
+            }
+        }
+
+        let totals: [(&'static str, &[&str], u64); 3] = [
+            ("CSS total", &[".css"], self.css_kb_total),
+            ("JS total", &[".js"], self.js_kb_total),
+            (
+                "Image total",
+                &[
+                    ".png", ".jpg", ".jpeg", ".gif", ".webp", ".avif", ".svg", ".ico",
+                ],
+                self.image_kb_total,
+            ),
+        ];
+
+        for (budget, extensions, limit_kb) in totals {
+            let actual: u64 = report
+                .files
+                .iter()
+                .filter(|f| has_extension(&f.path, extensions))
+                .map(|f| f.bytes)
+                .sum();
+            if actual > limit_kb * 1024 {
+                violations.push(BudgetViolation {
+                    budget,
+                    subject: "all files".to_string(),
+                    actual_bytes: actual,
+                    limit_bytes: limit_kb * 1024,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ReportEntry;
+
+    fn report(files: &[(&str, u64)]) -> BuildReport {
+        BuildReport {
+            files: files
+                .iter()
+                .map(|(path, bytes)| ReportEntry {
+                    path: path.to_string(),
+                    bytes: *bytes,
+                    sha256: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn small_site_is_within_budget() {
+        let r = report(&[("/index.html", 4096), ("/main.css", 10_000)]);
+        assert!(Budgets::default().check(&r).is_empty());
+    }
+
+    #[test]
+    fn oversized_page_is_reported() {
+        let r = report(&[("/index.html", 30 * 1024)]);
+        let violations = Budgets::default().check(&r);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].subject, "/index.html");
+    }
+
+    #[test]
+    fn totals_sum_across_files() {
+        let budgets = Budgets {
+            js_kb_total: 10,
+            ..Default::default()
+        };
+        let r = report(&[("/js/a.js", 6 * 1024), ("/js/b.js", 6 * 1024)]);
+        let violations = budgets.check(&r);
+        assert_eq!(violations[0].budget, "JS total");
+    }
+
+    #[test]
+    fn partial_toml_keeps_other_defaults() {
+        let parsed: Budgets = toml::from_str("css_kb_total = 8").unwrap();
+        assert_eq!(parsed.css_kb_total, 8);
+        assert_eq!(parsed.html_kb_per_page, Budgets::default().html_kb_per_page);
+    }
+}
@@ -0,0 +1,384 @@
+//! # Build Graph
+//!
+//! `--generate-static` runs a small graph of tasks, each after the tasks
+//! it depends on ([`Task::deps`]), rather than one script of side
+//! effects. Pages render first, then the feeds, the sitemap over every
+//! page, and the manifest over every file:
+//!
+//! | Task | After | Writes |
+//! |------|-------|--------|
+//! | `assets` | | `public/`, avatar variants, the stylesheet, `tokens.json` |
+//! | `pages` | | the homepage and every page outside a collection, with the JSON API |
+//! | `posts` | | post pages, listings, co-located assets, taxonomy pages |
+//! | `feeds` | pages, posts | the posts, log, changelog, and taxonomy term feeds |
+//! | `sitemap` | assets, pages, posts | `sitemap.xml` and `llms.txt`, over every page |
+//! | `discovery` | pages, posts | `.well-known/`, `plan.txt`, the Gemini mirror, embeddings |
+//! | `manifest` | all | `status.json` and the build report, then the budgets and outline audit |
+//!
+//! Each task declares the sources it reads ([`Task::inputs`]) and the
+//! files it writes that stand for its output ([`Task::outputs`]); it also
+//! reads the outputs of the tasks it depends on.
+//!
+//! ## Partial builds
+//!
+//! `--generate-static --only <part>` reruns one of [`Task::PARTS`] into an
+//! existing build, for content editors who changed a post or a stylesheet
+//! and don't want to wait for every data source. The tasks that depend on
+//! it rerun too, so `--only posts` also rebuilds the feeds, which list the
+//! posts. Tasks over the whole site don't: a partial build leaves the
+//! sitemap, `status.json`, and the build report as the last full build
+//! wrote them, and skips the budgets. Run a full build before deploying.
+//! `--only` may be given more than once.
+//!
+//! A partial build skips a part whose outputs are newer than its inputs,
+//! the outputs of its dependencies, and the binary (which holds the
+//! config); see [`Scope::plan`]. A dependency whose outputs are missing,
+//! as in a fresh `--out-dir`, runs first, even when it isn't a part.
+
+use crate::config::POSTS;
+use crate::embeddings::EMBEDDINGS_PATH;
+use crate::feed::feed;
+use crate::media::MEDIA_PATH;
+use crate::music::RELEASES_PATH;
+use crate::plan::PLAN_PATH;
+use crate::press::PRESS_PATH;
+use crate::protected::PROTECTED_PATH;
+use crate::resume::RESUME_PATH;
+use crate::status::STATUS_PATH;
+use crate::url::{page_file, HOME};
+use crate::webmention::MODERATION_PATH;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A step of the build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Task {
+    Assets,
+    Pages,
+    Posts,
+    Feeds,
+    Sitemap,
+    Discovery,
+    Manifest,
+}
+
+impl Task {
+    /// Every task, each after the tasks it depends on.
+    pub const ALL: [Task; 7] = [
+        Task::Assets,
+        Task::Pages,
+        Task::Posts,
+        Task::Feeds,
+        Task::Sitemap,
+        Task::Discovery,
+        Task::Manifest,
+    ];
+
+    /// The tasks `--only` can name.
+    pub const PARTS: [Task; 3] = [Task::Posts, Task::Assets, Task::Feeds];
+
+    /// The task's name, as given to `--only`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Task::Assets => "assets",
+            Task::Pages => "pages",
+            Task::Posts => "posts",
+            Task::Feeds => "feeds",
+            Task::Sitemap => "sitemap",
+            Task::Discovery => "discovery",
+            Task::Manifest => "manifest",
+        }
+    }
+
+    /// The part named `name`.
+    pub fn parse_part(name: &str) -> Result<Task, String> {
+        Task::PARTS
+            .into_iter()
+            .find(|task| task.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Task::PARTS.iter().map(|t| t.name()).collect();
+                format!("Unknown part {:?}; expected {}", name, names.join(", "))
+            })
+    }
+
+    /// The tasks whose outputs this one reads or overwrites.
+    pub fn deps(self) -> &'static [Task] {
+        match self {
+            Task::Assets | Task::Pages | Task::Posts => &[],
+            Task::Feeds => &[Task::Pages, Task::Posts],
+            // The sitemap replaces any static copy in public/
+            Task::Sitemap => &[Task::Assets, Task::Pages, Task::Posts],
+            Task::Discovery => &[Task::Pages, Task::Posts],
+            Task::Manifest => &[
+                Task::Assets,
+                Task::Pages,
+                Task::Posts,
+                Task::Feeds,
+                Task::Sitemap,
+                Task::Discovery,
+            ],
+        }
+    }
+
+    /// Source files and directories the task reads, besides config.
+    pub fn inputs(self) -> &'static [&'static str] {
+        match self {
+            Task::Assets => &["public/", "style/main.css"],
+            Task::Pages => &[
+                "data/",
+                "public/art/",
+                PROTECTED_PATH,
+                PRESS_PATH,
+                RESUME_PATH,
+                MEDIA_PATH,
+                RELEASES_PATH,
+                MODERATION_PATH,
+                ".git/",
+            ],
+            Task::Posts => &[POSTS.source],
+            Task::Feeds => &[POSTS.source, MEDIA_PATH, ".git/"],
+            Task::Sitemap | Task::Discovery | Task::Manifest => &[],
+        }
+    }
+
+    /// Files the task always writes, relative to the site directory. When
+    /// they are missing the task has not run there; their age is the age
+    /// of its output.
+    pub fn outputs(self) -> Vec<PathBuf> {
+        match self {
+            Task::Assets => vec!["main.css".into(), "tokens.json".into()],
+            Task::Pages => vec![page_file(HOME)],
+            Task::Posts => vec![page_file(&[POSTS.dir])],
+            Task::Feeds => vec![feed(POSTS.key).file().into()],
+            Task::Sitemap => vec!["sitemap.xml".into(), "llms.txt".into()],
+            Task::Discovery => vec![PLAN_PATH.into(), EMBEDDINGS_PATH.into()],
+            Task::Manifest => vec![STATUS_PATH.into()],
+        }
+    }
+
+    /// How the task's outputs in `site` compare with what it reads: its
+    /// [`inputs`](Task::inputs), the outputs of its dependencies, and the
+    /// running binary.
+    pub fn state(self, site: &Path) -> State {
+        let outputs: Option<Vec<SystemTime>> = self
+            .outputs()
+            .into_iter()
+            .map(|output| modified(&site.join(output)))
+            .collect();
+        let Some(oldest_output) = outputs.and_then(|times| times.into_iter().min()) else {
+            return State::Missing;
+        };
+        let newest_input = self
+            .inputs()
+            .iter()
+            .map(PathBuf::from)
+            .chain(
+                self.deps()
+                    .iter()
+                    .flat_map(|dep| dep.outputs())
+                    .map(|o| site.join(o)),
+            )
+            .chain(std::env::current_exe().ok())
+            .filter_map(|path| newest_modified(&path))
+            .max();
+        match newest_input.is_some_and(|time| time > oldest_output) {
+            true => State::Stale,
+            false => State::Fresh,
+        }
+    }
+}
+
+/// How a task's last outputs compare with what it reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    /// Some output was never written.
+    Missing,
+    /// Something the task reads changed since.
+    Stale,
+    Fresh,
+}
+
+/// When the file at `path` was last modified, if it exists.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// When anything at or under `path` was last modified, if it exists.
+fn newest_modified(path: &Path) -> Option<SystemTime> {
+    let newest = modified(path);
+    match fs::read_dir(path) {
+        Ok(entries) => entries
+            .flatten()
+            .filter_map(|entry| newest_modified(&entry.path()))
+            .chain(newest)
+            .max(),
+        Err(_) => newest,
+    }
+}
+
+/// What a build runs: every task, or some parts and their dependents.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scope {
+    /// `None` for the whole site.
+    parts: Option<BTreeSet<Task>>,
+}
+
+impl Scope {
+    /// The whole site.
+    pub fn full() -> Self {
+        Scope::default()
+    }
+
+    /// The parts named in `names` and every part that depends on them.
+    pub fn only<S: AsRef<str>>(names: &[S]) -> Result<Self, String> {
+        let mut parts = names
+            .iter()
+            .map(|name| Task::parse_part(name.as_ref()))
+            .collect::<Result<BTreeSet<Task>, String>>()?;
+        // ALL lists dependencies first, so one pass reaches every dependent
+        for task in Task::ALL {
+            if Task::PARTS.contains(&task) && task.deps().iter().any(|d| parts.contains(d)) {
+                parts.insert(task);
+            }
+        }
+        Ok(Scope { parts: Some(parts) })
+    }
+
+    /// Whether the build runs every task.
+    pub fn is_full(&self) -> bool {
+        self.parts.is_none()
+    }
+
+    /// Whether the build runs `task`.
+    pub fn includes(&self, task: Task) -> bool {
+        self.parts
+            .as_ref()
+            .is_none_or(|parts| parts.contains(&task))
+    }
+
+    /// The tasks the build runs, in order.
+    pub fn tasks(&self) -> Vec<Task> {
+        Task::ALL
+            .into_iter()
+            .filter(|&task| self.includes(task))
+            .collect()
+    }
+
+    /// The tasks to run, in order, given each task's [`State`]. A full
+    /// build runs every task. A partial build runs the parts that aren't
+    /// [`State::Fresh`] and those depending on a task that runs, and first
+    /// any dependency of those whose outputs are [`State::Missing`].
+    pub fn plan(&self, state: impl Fn(Task) -> State) -> Vec<Task> {
+        let Some(parts) = &self.parts else {
+            return Task::ALL.to_vec();
+        };
+        let mut run: BTreeSet<Task> = parts
+            .iter()
+            .copied()
+            .filter(|&task| state(task) != State::Fresh)
+            .collect();
+        loop {
+            let before = run.len();
+            for task in Task::ALL {
+                let deps = task.deps();
+                if parts.contains(&task) && deps.iter().any(|dep| run.contains(dep)) {
+                    run.insert(task);
+                }
+                if run.contains(&task) {
+                    run.extend(deps.iter().filter(|&&dep| state(dep) == State::Missing));
+                }
+            }
+            if run.len() == before {
+                return run.into_iter().collect();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tasks_follow_their_deps() {
+        for (i, task) in Task::ALL.iter().enumerate() {
+            for dep in task.deps() {
+                assert!(Task::ALL[..i].contains(dep), "{:?} before {:?}", dep, task);
+            }
+        }
+        assert_eq!(Scope::full().tasks(), Task::ALL);
+    }
+
+    #[test]
+    fn parts_bring_their_dependents() {
+        let posts = Scope::only(&["posts"]).unwrap();
+        assert!(!posts.is_full());
+        assert_eq!(posts.tasks(), [Task::Posts, Task::Feeds]);
+        assert_eq!(Scope::only(&["assets"]).unwrap().tasks(), [Task::Assets]);
+        assert_eq!(Scope::only(&["feeds"]).unwrap().tasks(), [Task::Feeds]);
+    }
+
+    #[test]
+    fn partial_builds_skip_fresh_parts() {
+        let posts = Scope::only(&["posts"]).unwrap();
+        assert_eq!(posts.plan(|_| State::Fresh), []);
+        let stale_feeds = |task| match task {
+            Task::Feeds => State::Stale,
+            _ => State::Fresh,
+        };
+        assert_eq!(posts.plan(stale_feeds), [Task::Feeds]);
+        let stale_posts = |task| match task {
+            Task::Posts => State::Stale,
+            _ => State::Fresh,
+        };
+        assert_eq!(posts.plan(stale_posts), [Task::Posts, Task::Feeds]);
+        assert_eq!(Scope::full().plan(|_| State::Fresh), Task::ALL);
+    }
+
+    #[test]
+    fn missing_dependencies_run_first() {
+        let feeds = Scope::only(&["feeds"]).unwrap();
+        assert_eq!(
+            feeds.plan(|_| State::Missing),
+            [Task::Pages, Task::Posts, Task::Feeds]
+        );
+        let no_pages = |task| match task {
+            Task::Pages => State::Missing,
+            _ => State::Fresh,
+        };
+        assert_eq!(feeds.plan(no_pages), [], "Feeds are up to date");
+    }
+
+    #[test]
+    fn outputs_age_against_inputs() {
+        let site = std::env::temp_dir().join(format!("esart-build-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&site);
+        fs::create_dir_all(&site).unwrap();
+        assert_eq!(Task::Assets.state(&site), State::Missing);
+        fs::write(site.join("main.css"), "").unwrap();
+        fs::write(site.join("tokens.json"), "").unwrap();
+        assert_eq!(Task::Assets.state(&site), State::Fresh);
+        let old = SystemTime::UNIX_EPOCH;
+        fs::File::options()
+            .write(true)
+            .open(site.join("tokens.json"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        assert_eq!(
+            Task::Assets.state(&site),
+            State::Stale,
+            "Older than the binary"
+        );
+        fs::remove_dir_all(&site).unwrap();
+    }
+
+    #[test]
+    fn unknown_parts_are_errors() {
+        let err = Scope::only(&["assets", "post"]).unwrap_err();
+        assert!(err.contains("\"post\"") && err.contains("posts, assets, feeds"));
+        assert!(Scope::only(&["sitemap"]).is_err());
+    }
+}
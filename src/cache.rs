@@ -0,0 +1,244 @@
+//! # Cached Data Sources
+//!
+//! Build-time fetches of third-party data (e.g. the GitHub contribution
+//! calendar). Responses are cached under `data/cache/` so builds stay fast,
+//! work offline, and keep the last good snapshot when a source is down:
+//!
+//! 1. A cache entry younger than its maximum age is used as-is.
+//! 2. Otherwise the source is fetched and the cache refreshed.
+//! 3. If the fetch fails, a stale entry is used with a warning.
+//!
+//! Set `ESART_OFFLINE=1` to skip fetching and use whatever is cached, or
+//! run a build in [`with_offline`].
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Directory for cached responses, relative to the project root.
+pub const CACHE_DIR: &str = "data/cache";
+
+/// Environment variable that disables network fetches when set to `1`.
+pub const OFFLINE_ENV: &str = "ESART_OFFLINE";
+
+/// User agent sent with every request.
+const USER_AGENT: &str = concat!("everythingsings/", env!("CARGO_PKG_VERSION"));
+
+/// A named cache entry in a directory.
+pub struct CacheEntry {
+    path: PathBuf,
+}
+
+impl CacheEntry {
+    /// The entry `name` in `dir` (e.g. `github-contributions.html`).
+    pub fn new(dir: &Path, name: &str) -> Self {
+        Self {
+            path: dir.join(name),
+        }
+    }
+
+    /// When the cached copy was last written, or `None` if there is none.
+    pub fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path).ok()?.modified().ok()
+    }
+
+    /// Age of the cached copy, or `None` if there is none.
+    pub fn age(&self) -> Option<Duration> {
+        let modified = self.modified()?;
+        Some(
+            SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// The cached body, if any.
+    pub fn read(&self) -> Option<String> {
+        std::fs::read_to_string(&self.path).ok()
+    }
+
+    /// The cached body as raw bytes, if any.
+    pub fn read_bytes(&self) -> Option<Vec<u8>> {
+        std::fs::read(&self.path).ok()
+    }
+
+    fn write(&self, body: &[u8]) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, body)
+    }
+
+    /// Returns the cached body if fresh, otherwise calls `fetch` and caches
+    /// the result, falling back to the stale copy if `fetch` fails.
+    pub fn get_or_fetch(
+        &self,
+        max_age: Duration,
+        offline: bool,
+        fetch: impl FnOnce() -> Result<String, String>,
+    ) -> Option<String> {
+        self.get_or_fetch_bytes(max_age, offline, || fetch().map(String::into_bytes))
+            .and_then(|body| String::from_utf8(body).ok())
+    }
+
+    /// [`get_or_fetch`](Self::get_or_fetch) for binary bodies such as images.
+    pub fn get_or_fetch_bytes(
+        &self,
+        max_age: Duration,
+        offline: bool,
+        fetch: impl FnOnce() -> Result<Vec<u8>, String>,
+    ) -> Option<Vec<u8>> {
+        if self.age().is_some_and(|age| age < max_age) || offline {
+            return self.read_bytes();
+        }
+        match fetch() {
+            Ok(body) => {
+                if let Err(e) = self.write(&body) {
+                    eprintln!("Warning: Could not cache {}: {}", self.path.display(), e);
+                }
+                Some(body)
+            }
+            Err(e) => {
+                eprintln!("Warning: Could not refresh {}: {}", self.path.display(), e);
+                self.read_bytes()
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// Set by [`with_offline`] for the calls it makes.
+    static OFFLINE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns true when `ESART_OFFLINE=1`, or inside [`with_offline`].
+pub fn offline() -> bool {
+    OFFLINE.get() || std::env::var(OFFLINE_ENV).is_ok_and(|v| v == "1")
+}
+
+/// Runs `f` offline when `offline` is true, without touching the
+/// environment: only the cache is read, and nothing is fetched.
+pub fn with_offline<T>(offline: bool, f: impl FnOnce() -> T) -> T {
+    let outer = OFFLINE.replace(offline || OFFLINE.get());
+    let result = f();
+    OFFLINE.set(outer);
+    result
+}
+
+/// Fetches `url` as text.
+pub fn fetch_url(url: &str) -> Result<String, String> {
+    ureq::get(url)
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| format!("GET {}: {}", url, e))?
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| format!("GET {}: {}", url, e))
+}
+
+/// Fetches `url` as raw bytes.
+pub fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
+    ureq::get(url)
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| format!("GET {}: {}", url, e))?
+        .body_mut()
+        .read_to_vec()
+        .map_err(|e| format!("GET {}: {}", url, e))
+}
+
+/// A short, file-name-safe key for `url`, for naming its cache entries.
+pub fn url_key(url: &str) -> String {
+    Sha256::digest(url.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// The trimmed string at `value[key]` of a fetched JSON document, if it is
+/// non-empty.
+pub fn json_text(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Fetches `url` through the cache entry `name` in [`CACHE_DIR`].
+pub fn cached_fetch(name: &str, url: &str, max_age: Duration) -> Option<String> {
+    CacheEntry::new(Path::new(CACHE_DIR), name).get_or_fetch(max_age, offline(), || fetch_url(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn entry(name: &str) -> CacheEntry {
+        let dir = std::env::temp_dir().join(format!("esart-cache-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        CacheEntry::new(&dir, "source.json")
+    }
+
+    const DAY: Duration = Duration::from_secs(86_400);
+
+    #[test]
+    fn missing_entry_is_fetched_and_cached() {
+        let entry = entry("miss");
+        let body = entry.get_or_fetch(DAY, false, || Ok("fresh".to_string()));
+        assert_eq!(body.as_deref(), Some("fresh"));
+        assert_eq!(entry.read().as_deref(), Some("fresh"));
+    }
+
+    #[test]
+    fn fresh_entry_is_not_refetched() {
+        let entry = entry("fresh");
+        entry.write(b"cached").unwrap();
+        let body = entry.get_or_fetch(DAY, false, || panic!("should not fetch"));
+        assert_eq!(body.as_deref(), Some("cached"));
+    }
+
+    #[test]
+    fn failed_refresh_falls_back_to_stale_copy() {
+        let entry = entry("stale");
+        entry.write(b"old").unwrap();
+        let body = entry.get_or_fetch(Duration::ZERO, false, || Err("down".to_string()));
+        assert_eq!(body.as_deref(), Some("old"));
+    }
+
+    #[test]
+    fn json_text_skips_blank_strings() {
+        let doc = serde_json::json!({"a": " hi ", "b": "  ", "c": 1});
+        assert_eq!(json_text(&doc, "a").as_deref(), Some("hi"));
+        assert_eq!(json_text(&doc, "b"), None);
+        assert_eq!(json_text(&doc, "c"), None);
+        assert_eq!(json_text(&doc, "d"), None);
+    }
+
+    #[test]
+    fn url_keys_are_stable_hex() {
+        let key = url_key("https://a.test/x");
+        assert_eq!(key.len(), 16);
+        assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(key, url_key("https://a.test/x"));
+        assert_ne!(key, url_key("https://a.test/y"));
+    }
+
+    #[test]
+    fn offline_scopes_nest_and_end() {
+        assert!(with_offline(true, offline));
+        assert!(with_offline(true, || with_offline(false, offline)));
+        assert!(!OFFLINE.get());
+    }
+
+    #[test]
+    fn offline_uses_cache_only() {
+        let entry = entry("offline");
+        let body = entry.get_or_fetch(Duration::ZERO, true, || panic!("should not fetch"));
+        assert_eq!(body, None);
+    }
+}
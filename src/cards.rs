@@ -0,0 +1,276 @@
+//! # Trading Cards
+//!
+//! Reads a related site's cards manifest (named by `cards` in its
+//! [site config](crate::site)) for one page per physical card at
+//! `/cards/<slug>/`. Each page describes its card as a Schema.org
+//! `Product` with an `Offer`, the shape merchant listing rich results
+//! expect.
+//!
+//! ## Example
+//!
+//! ```toml
+//! currency = "USD"
+//!
+//! [[card]]
+//! slug = "aurora"
+//! name = "Aurora"
+//! image = "cards/aurora.jpg"     # in the site's public_dir
+//! description = "Holographic foil, signed and numbered."
+//! edition_size = 50
+//! price = "12.00"
+//! availability = "in_stock"      # in_stock | limited | preorder | sold_out
+//! shop_url = "https://shop.example/aurora"  # optional, where to buy
+//! ```
+
+use crate::escape::json_script;
+use crate::url::page_path;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Output directory of card pages within a related site.
+pub const CARDS_DIR: &str = "cards";
+
+/// Whether a card can be bought.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    InStock,
+    /// In stock, with few copies left.
+    Limited,
+    PreOrder,
+    SoldOut,
+}
+
+impl Availability {
+    /// Display label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Availability::InStock => "In stock",
+            Availability::Limited => "Few left",
+            Availability::PreOrder => "Pre-order",
+            Availability::SoldOut => "Sold out",
+        }
+    }
+
+    /// Schema.org `ItemAvailability` URL.
+    pub fn schema_url(&self) -> &'static str {
+        match self {
+            Availability::InStock => "https://schema.org/InStock",
+            Availability::Limited => "https://schema.org/LimitedAvailability",
+            Availability::PreOrder => "https://schema.org/PreOrder",
+            Availability::SoldOut => "https://schema.org/SoldOut",
+        }
+    }
+}
+
+/// One physical card.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Card {
+    pub slug: String,
+    pub name: String,
+    /// Image path relative to the site root, e.g. `cards/aurora.jpg`.
+    pub image: String,
+    pub description: String,
+    /// Number of copies printed.
+    pub edition_size: u32,
+    /// Price as a decimal string, e.g. `12.00`.
+    pub price: String,
+    pub availability: Availability,
+    /// Where to buy the card, if not on its page.
+    #[serde(default)]
+    pub shop_url: Option<String>,
+}
+
+impl Card {
+    /// Site-relative URL of the card page, e.g. `/cards/aurora/`.
+    pub fn url_path(&self) -> String {
+        page_path(&[CARDS_DIR, &self.slug])
+    }
+
+    /// Site-relative URL of the card image.
+    pub fn image_path(&self) -> String {
+        format!("/{}", self.image.trim_start_matches('/'))
+    }
+
+    /// Builds the card page JSON-LD: a `Product` by `brand` with one
+    /// `Offer`. `site_url` is the site's base URL without a trailing slash.
+    pub fn to_json_ld(&self, site_url: &str, brand: &str, currency: &str) -> String {
+        let page_url = format!("{}{}", site_url, self.url_path());
+        json_script(&json!({
+            "@context": "https://schema.org",
+            "@type": "Product",
+            "@id": format!("{}#product", page_url),
+            "name": self.name,
+            "url": page_url,
+            "image": [format!("{}{}", site_url, self.image_path())],
+            "description": self.description,
+            "sku": self.slug,
+            "brand": { "@type": "Brand", "name": brand },
+            "additionalProperty": {
+                "@type": "PropertyValue",
+                "name": "Edition size",
+                "value": self.edition_size,
+            },
+            "offers": {
+                "@type": "Offer",
+                "url": self.shop_url.clone().unwrap_or(page_url),
+                "price": self.price,
+                "priceCurrency": currency,
+                "availability": self.availability.schema_url(),
+                "itemCondition": "https://schema.org/NewCondition",
+            },
+        }))
+    }
+}
+
+/// A site's cards, in manifest order.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct CardManifest {
+    /// ISO 4217 currency of every price, e.g. `USD`.
+    pub currency: String,
+    #[serde(default, rename = "card")]
+    pub cards: Vec<Card>,
+}
+
+/// Whether `price` is a plain decimal amount such as `12` or `12.50`.
+fn is_price(price: &str) -> bool {
+    let (whole, cents) = price.split_once('.').unwrap_or((price, "00"));
+    !whole.is_empty()
+        && whole.chars().all(|c| c.is_ascii_digit())
+        && cents.len() == 2
+        && cents.chars().all(|c| c.is_ascii_digit())
+}
+
+impl CardManifest {
+    /// Loads the manifest from `path`; `Ok(None)` when the file is absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let manifest = Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Some(manifest))
+    }
+
+    /// Parses and validates manifest TOML: a three-letter currency, unique
+    /// URL-safe slugs, decimal prices, and editions of at least one.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let manifest: CardManifest =
+            toml::from_str(content).map_err(|e| format!("Could not parse: {}", e))?;
+        if manifest.currency.len() != 3
+            || !manifest.currency.chars().all(|c| c.is_ascii_uppercase())
+        {
+            return Err(format!(
+                "Currency \"{}\" must be an ISO 4217 code",
+                manifest.currency
+            ));
+        }
+        let mut slugs = BTreeSet::new();
+        for card in &manifest.cards {
+            let slug_ok = !card.slug.is_empty()
+                && card
+                    .slug
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !slug_ok || !slugs.insert(card.slug.as_str()) {
+                return Err(format!(
+                    "Slug \"{}\" must be unique lowercase letters, digits, and dashes",
+                    card.slug
+                ));
+            }
+            if !is_price(&card.price) {
+                return Err(format!(
+                    "Price of \"{}\" must be a decimal like 12.00",
+                    card.name
+                ));
+            }
+            if card.edition_size == 0 {
+                return Err(format!(
+                    "Edition size of \"{}\" must be at least 1",
+                    card.name
+                ));
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+currency = "USD"
+
+[[card]]
+slug = "aurora"
+name = "Aurora"
+image = "cards/aurora.jpg"
+description = "Holographic foil."
+edition_size = 50
+price = "12.00"
+availability = "in_stock"
+
+[[card]]
+slug = "ember-2"
+name = "Ember"
+image = "/cards/ember.jpg"
+description = "Matte black."
+edition_size = 10
+price = "30"
+availability = "sold_out"
+shop_url = "https://shop.test/ember"
+"#;
+
+    pub fn sample_manifest() -> CardManifest {
+        CardManifest::parse(TOML).unwrap()
+    }
+
+    #[test]
+    fn manifest_parses_in_order() {
+        let manifest = sample_manifest();
+        assert_eq!(manifest.cards.len(), 2);
+        assert_eq!(manifest.cards[1].availability, Availability::SoldOut);
+        assert_eq!(manifest.cards[1].image_path(), "/cards/ember.jpg");
+        assert_eq!(manifest.cards[0].url_path(), "/cards/aurora/");
+    }
+
+    #[test]
+    fn invalid_manifests_are_rejected() {
+        let bad = |from: &str, to: &str| CardManifest::parse(&TOML.replacen(from, to, 1));
+        assert!(bad("\"USD\"", "\"usd\"").unwrap_err().contains("ISO 4217"));
+        assert!(bad("\"12.00\"", "\"12.5\"").unwrap_err().contains("Aurora"));
+        assert!(bad("\"12.00\"", "\"$12\"").is_err());
+        assert!(bad("edition_size = 50", "edition_size = 0").is_err());
+        assert!(bad("\"ember-2\"", "\"aurora\"")
+            .unwrap_err()
+            .contains("unique"));
+        assert!(bad("\"ember-2\"", "\"../x\"").is_err());
+    }
+
+    #[test]
+    fn json_ld_is_a_product_with_offer() {
+        let manifest = sample_manifest();
+        let json_ld = |i: usize| -> serde_json::Value {
+            let ld = manifest.cards[i].to_json_ld("https://cards.test", "Lumimenta", "USD");
+            serde_json::from_str(&ld).unwrap()
+        };
+        let ld = json_ld(0);
+        assert_eq!(ld["@type"], "Product");
+        assert_eq!(ld["image"][0], "https://cards.test/cards/aurora.jpg");
+        assert_eq!(ld["brand"]["name"], "Lumimenta");
+        assert_eq!(ld["additionalProperty"]["value"], 50);
+        let offer = &ld["offers"];
+        assert_eq!(offer["price"], "12.00");
+        assert_eq!(offer["priceCurrency"], "USD");
+        assert_eq!(offer["availability"], "https://schema.org/InStock");
+        assert_eq!(offer["url"], "https://cards.test/cards/aurora/");
+
+        let sold = json_ld(1);
+        assert_eq!(sold["offers"]["url"], "https://shop.test/ember");
+        assert_eq!(sold["offers"]["availability"], "https://schema.org/SoldOut");
+    }
+}
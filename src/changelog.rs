@@ -0,0 +1,239 @@
+//! # Site Changelog
+//!
+//! Walks the git history at build time and keeps the commits worth telling
+//! visitors about, for the `/changelog/` page and the `/changes.xml` feed.
+//!
+//! Only [Conventional Commits](https://www.conventionalcommits.org/) whose
+//! type is `feat`, `fix`, or `perf` are listed; chores, refactors, tests,
+//! and everything without a recognised prefix are left out.
+
+use crate::datetime::iso_date;
+use crate::feed::FeedItem;
+use git2::{Repository, Sort};
+use std::path::Path;
+
+/// Output directory of the changelog page.
+pub const CHANGELOG_DIR: &str = "changelog";
+
+/// Source repository, used for commit links.
+pub const REPO_URL: &str = "https://github.com/EverythingSings/everythingsings.github.io";
+
+/// Most changes listed on the page and in the feed.
+pub const CHANGELOG_LIMIT: usize = 50;
+
+/// Description of the changelog page and feed.
+pub const CHANGELOG_DESCRIPTION: &str = "New features, fixes, and speedups to this site.";
+
+/// Kinds of change that are shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeKind {
+    Feature,
+    Fix,
+    Performance,
+}
+
+impl ChangeKind {
+    /// Maps a Conventional Commit type to a kind; `None` for hidden types.
+    pub fn from_type(commit_type: &str) -> Option<Self> {
+        match commit_type {
+            "feat" => Some(ChangeKind::Feature),
+            "fix" => Some(ChangeKind::Fix),
+            "perf" => Some(ChangeKind::Performance),
+            _ => None,
+        }
+    }
+
+    /// Display label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Feature => "New",
+            ChangeKind::Fix => "Fixed",
+            ChangeKind::Performance => "Faster",
+        }
+    }
+}
+
+/// One listed change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub scope: Option<String>,
+    pub summary: String,
+    /// Marked `!` or with a `BREAKING CHANGE:` footer.
+    pub breaking: bool,
+    /// ISO commit date, e.g. `2025-01-17`.
+    pub date: String,
+    /// Full commit hash.
+    pub hash: String,
+}
+
+impl Change {
+    /// Abbreviated commit hash.
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    /// The commit on GitHub.
+    pub fn commit_url(&self) -> String {
+        format!("{}/commit/{}", REPO_URL, self.hash)
+    }
+}
+
+/// Parses a commit message, returning `None` unless its subject is a shown
+/// Conventional Commit (`type(scope)!: summary`).
+pub fn parse_message(message: &str, date: &str, hash: &str) -> Option<Change> {
+    let subject = message.lines().next()?.trim();
+    let (head, summary) = subject.split_once(": ")?;
+    let (head, bang) = match head.strip_suffix('!') {
+        Some(head) => (head, true),
+        None => (head, false),
+    };
+    let (commit_type, scope) = match head.split_once('(') {
+        Some((t, rest)) => (t, Some(rest.strip_suffix(')')?.to_string())),
+        None => (head, None),
+    };
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return None;
+    }
+    Some(Change {
+        kind: ChangeKind::from_type(&commit_type.to_ascii_lowercase())?,
+        scope,
+        summary: summary.to_string(),
+        breaking: bang
+            || message
+                .lines()
+                .any(|l| l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:")),
+        date: date.to_string(),
+        hash: hash.to_string(),
+    })
+}
+
+/// Collects up to `limit` shown changes from the repository containing
+/// `path`, newest first.
+pub fn load_changes(path: &Path, limit: usize) -> Result<Vec<Change>, String> {
+    let repo =
+        Repository::discover(path).map_err(|e| format!("Could not open repository: {}", e))?;
+    let mut walk = repo.revwalk().map_err(|e| e.to_string())?;
+    walk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+    walk.push_head()
+        .map_err(|e| format!("Could not read HEAD: {}", e))?;
+
+    let mut changes = Vec::new();
+    for oid in walk {
+        let oid = oid.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let message = String::from_utf8_lossy(commit.message_bytes());
+        let date = iso_date(commit.time().seconds());
+        if let Some(change) = parse_message(&message, &date, &oid.to_string()) {
+            changes.push(change);
+            if changes.len() == limit {
+                break;
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Feed items for `/changes.xml`, linking to each change on `page_url`.
+pub fn feed_items(changes: &[Change], page_url: &str) -> Vec<FeedItem> {
+    changes
+        .iter()
+        .map(|c| FeedItem {
+            title: format!("{}: {}", c.kind.label(), c.summary),
+            link: format!("{}#{}", page_url, c.short_hash()),
+            description: c.summary.clone(),
+            date: c.date.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn sample_changes() -> Vec<Change> {
+        vec![
+            parse_message("feat(art)!: Add series pages", "2025-02-01", "abcdef0123").unwrap(),
+            parse_message("fix: Correct avatar size", "2025-01-20", "0123456789").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn parses_type_scope_and_summary() {
+        let change = parse_message("feat(art): Add series pages", "2025-02-01", "abc").unwrap();
+        assert_eq!(change.kind, ChangeKind::Feature);
+        assert_eq!(change.scope.as_deref(), Some("art"));
+        assert_eq!(change.summary, "Add series pages");
+        assert!(!change.breaking);
+    }
+
+    #[test]
+    fn hidden_and_unprefixed_commits_are_skipped() {
+        for message in [
+            "chore: Bump deps",
+            "refactor(css): Tidy",
+            "Add a thing",
+            "[ticket-1] Add a thing",
+            "feat(unclosed: Oops",
+            "feat: ",
+        ] {
+            assert_eq!(
+                parse_message(message, "2025-01-01", "abc"),
+                None,
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn breaking_changes_are_flagged() {
+        assert!(
+            parse_message("perf!: Drop old feed", "d", "h")
+                .unwrap()
+                .breaking
+        );
+        assert!(
+            parse_message("fix: Rename\n\nBREAKING CHANGE: URLs moved", "d", "h")
+                .unwrap()
+                .breaking
+        );
+    }
+
+    #[test]
+    fn feed_items_link_to_short_hashes() {
+        let items = feed_items(&sample_changes(), "https://x.test/changelog/");
+        assert_eq!(items[0].title, "New: Add series pages");
+        assert_eq!(items[0].link, "https://x.test/changelog/#abcdef0");
+    }
+
+    #[test]
+    fn loads_shown_commits_newest_first() {
+        let dir = std::env::temp_dir().join(format!("esart-changelog-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let repo = Repository::init(&dir).unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let mut parents = Vec::new();
+        for (i, message) in ["feat: First", "chore: Noise", "fix: Second"]
+            .iter()
+            .enumerate()
+        {
+            let time = git2::Time::new(1_737_000_000 + i as i64 * 86_400, 0);
+            let sig = git2::Signature::new("Test", "test@example.com", &time).unwrap();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            let oid = repo
+                .commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+                .unwrap();
+            parents = vec![repo.find_commit(oid).unwrap()];
+        }
+
+        let changes = load_changes(&dir, 10).unwrap();
+        let summaries: Vec<&str> = changes.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(summaries, ["Second", "First"]);
+        assert_eq!(changes[0].date, "2025-01-18");
+        assert_eq!(load_changes(&dir, 1).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,121 @@
+//! # Clean
+//!
+//! `everythingsings clean` removes what builds leave behind: the site and
+//! the related sites, the build report, the exports, and the data source
+//! caches under `data/cache/`.
+//!
+//! Every build marks its output directory with [`MARKER_FILE`] before
+//! writing to it, and an output directory is only removed when it carries
+//! the marker, so a mistyped `--out-dir` can't wipe a folder no build
+//! wrote. A directory holding the working directory is never removed.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// File marking a directory as build output.
+pub const MARKER_FILE: &str = ".everythingsings-build";
+
+/// Marks `dir` as build output, creating it.
+pub fn mark(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(MARKER_FILE),
+        concat!(
+            "Written by everythingsings ",
+            env!("CARGO_PKG_VERSION"),
+            "; `everythingsings clean` may remove this directory.\n"
+        ),
+    )
+}
+
+/// Whether `dir` is marked as build output.
+pub fn is_marked(dir: &Path) -> bool {
+    dir.join(MARKER_FILE).is_file()
+}
+
+/// Checks that the output directory `dir`, if it exists, may be removed.
+pub fn check_output(dir: &Path) -> io::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    if !is_marked(dir) {
+        return Err(io::Error::other(format!(
+            "Refusing to remove {}: it has no {} marker, so no build wrote it",
+            dir.display(),
+            MARKER_FILE
+        )));
+    }
+    if std::env::current_dir()?
+        .canonicalize()?
+        .starts_with(dir.canonicalize()?)
+    {
+        return Err(io::Error::other(format!(
+            "Refusing to remove {}: it holds the working directory",
+            dir.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Removes the output directory `dir` after [`check_output`], returning
+/// whether it existed.
+pub fn remove_output(dir: &Path) -> io::Result<bool> {
+    check_output(dir)?;
+    remove(dir)
+}
+
+/// Removes the file or directory at `path`, returning whether it existed.
+pub fn remove(path: &Path) -> io::Result<bool> {
+    match fs::symlink_metadata(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path).map(|()| true),
+        Ok(_) => fs::remove_file(path).map(|()| true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("esart-clean-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn marked_output_is_removed() {
+        let dir = temp_dir("marked");
+        mark(&dir).unwrap();
+        fs::write(dir.join("index.html"), "<p>Hi</p>").unwrap();
+        assert!(is_marked(&dir));
+        assert!(remove_output(&dir).unwrap());
+        assert!(!dir.exists());
+        assert!(!remove_output(&dir).unwrap());
+    }
+
+    #[test]
+    fn unmarked_directories_are_kept() {
+        let dir = temp_dir("unmarked");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("notes.txt"), "mine").unwrap();
+        let err = remove_output(&dir).unwrap_err();
+        assert!(err.to_string().contains(MARKER_FILE), "{}", err);
+        assert!(dir.join("notes.txt").is_file());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn files_and_missing_paths() {
+        let dir = temp_dir("files");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("build-report.json");
+        fs::write(&file, "{}").unwrap();
+        assert!(remove(&file).unwrap());
+        assert!(!remove(&file).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,593 @@
+//! # Content Collections
+//!
+//! A collection is a directory of Markdown files with TOML frontmatter,
+//! one file per entry, rendered as a page per entry plus a paginated
+//! listing and an RSS feed:
+//!
+//! ```text
+//! content/posts/hello-world.md        /posts/hello-world/
+//! content/posts/night-walk/index.md   /posts/night-walk/
+//!                                     /posts/, /posts/page/2/, …
+//!                                     /posts/feed.xml
+//! ```
+//!
+//! ```markdown
+//! +++
+//! title = "Hello, world"
+//! date = "2025-03-01"
+//! description = "Why this site has a blog now."
+//! +++
+//!
+//! The body, in Markdown.
+//! ```
+//!
+//! The pipeline is the same for every content type: [`Collection::load`]
+//! reads the directory and parses each entry's frontmatter into the type's
+//! own struct (anything implementing [`Content`]), drops drafts, and sorts
+//! newest first; [`Collection::pages`] paginates the listing (see
+//! [`crate::pagination`]) and [`Collection::feed_items`] builds the feed.
+//! A new content type is a frontmatter struct, a [`CollectionDef`] in
+//! [`crate::config`], and the components rendering its pages.
+//!
+//! Slugs come from the file (or directory) name unless the frontmatter sets
+//! `slug`, and pages live where the definition's permalink template puts
+//! them (see [`crate::permalink`]). Entries of every collection share the
+//! [taxonomies](crate::taxonomy) through [`Collection::tagged`].
+//!
+//! ## Co-located assets
+//!
+//! An entry in its own directory can keep its images and files next to it:
+//!
+//! ```text
+//! content/posts/night-walk/index.md
+//! content/posts/night-walk/cover.jpg  /posts/night-walk/cover.jpg
+//! ```
+//!
+//! Every other file in the directory (hidden files and Markdown aside) is
+//! copied into the page's directory, and relative references to them in
+//! the body, `![Cover](cover.jpg)`, are rewritten to their URL (see
+//! [`Entry::asset_link`]), so they work in feeds and under any permalink.
+
+use crate::config::{SITE_NAME, TAXONOMIES};
+use crate::excerpt::summary;
+use crate::feed::{Feed, FeedItem};
+use crate::pagination::{paginate, Pagination};
+use crate::permalink;
+use crate::slug::Slugs;
+use crate::taxonomy::{check_terms, Tagged, Terms, NO_TERMS};
+use crate::text::{encode_path_segment, nfc};
+use crate::url::{absolute, dir_path, page_path};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Frontmatter delimiter, on a line of its own before and after the TOML.
+pub const FRONTMATTER_DELIMITER: &str = "+++";
+
+/// File name of an entry that lives in its own directory.
+pub const INDEX_FILE: &str = "index.md";
+
+/// The frontmatter of one content type.
+pub trait Content: DeserializeOwned + Clone {
+    fn title(&self) -> &str;
+    /// ISO date, e.g. `2025-03-01`, used for sorting, permalinks and feeds.
+    fn date(&self) -> &str;
+    /// Summary set by hand, overriding the [excerpt](crate::excerpt).
+    fn description(&self) -> &str;
+    /// Drafts are skipped when the collection is loaded.
+    fn draft(&self) -> bool {
+        false
+    }
+    /// Slug set in the frontmatter, overriding the file name's.
+    fn slug(&self) -> Option<&str> {
+        None
+    }
+    /// Terms the entry is filed under (see [`crate::taxonomy`]).
+    fn terms(&self) -> &Terms {
+        &NO_TERMS
+    }
+}
+
+/// Where a collection is read from and published to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CollectionDef {
+    /// Feed key and name of the collection in build output, e.g. `posts`.
+    pub key: &'static str,
+    /// Source directory, relative to the project root.
+    pub source: &'static str,
+    /// Output directory of the listing, e.g. `posts` for `/posts/`.
+    pub dir: &'static str,
+    /// Permalink template of entry pages, e.g. `/posts/:slug/`.
+    pub permalink: &'static str,
+    /// Heading of the listing page and title of the feed.
+    pub title: &'static str,
+    pub description: &'static str,
+    /// Entries per listing page.
+    pub per_page: usize,
+}
+
+impl CollectionDef {
+    /// Site-relative URL of the listing's first page, e.g. `/posts/`.
+    pub fn listing_path(&self) -> String {
+        page_path(&[self.dir])
+    }
+
+    /// The collection's feed, at `/<dir>/feed.xml`, for the
+    /// [registry](crate::feed::feeds).
+    pub fn feed(&self) -> Feed {
+        Feed {
+            key: self.key,
+            title: format!("{} {}", SITE_NAME, self.title),
+            description: self.description.to_string(),
+            path: format!("/{}/feed.xml", self.dir),
+            section: Some(self.listing_path()),
+        }
+    }
+}
+
+/// One parsed entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry<T> {
+    pub slug: String,
+    /// Path segments of the entry's page.
+    pub segments: Vec<String>,
+    pub meta: T,
+    /// The Markdown body, NFC-normalized.
+    pub body: String,
+    /// Plain-text summary for listings, feeds and meta descriptions (see
+    /// [`crate::excerpt`]).
+    pub summary: String,
+    /// The file the entry was read from.
+    pub source: PathBuf,
+    /// [Internal links](crate::internal_links) in the body and the URLs
+    /// they resolve to, once resolved.
+    pub links: BTreeMap<String, String>,
+    /// [Co-located assets](self#co-located-assets), relative to the
+    /// entry's directory with `/` separators, e.g. `img/cover.jpg`.
+    pub assets: Vec<String>,
+}
+
+impl<T> Entry<T> {
+    /// Site-relative URL of the entry's page, e.g. `/posts/hello-world/`.
+    pub fn url_path(&self) -> String {
+        page_path(&self.segments)
+    }
+
+    /// The directory of an entry written as `<name>/index.md`, which can
+    /// hold its assets.
+    pub fn asset_dir(&self) -> Option<&Path> {
+        self.source
+            .ends_with(INDEX_FILE)
+            .then(|| self.source.parent())
+            .flatten()
+    }
+
+    /// Site-relative URL of the asset at `relative`, in the page's
+    /// directory, e.g. `/posts/night-walk/cover.jpg`.
+    pub fn asset_url(&self, relative: &str) -> String {
+        let file: Vec<String> = relative.split('/').map(encode_path_segment).collect();
+        format!("{}{}", dir_path(&self.segments), file.join("/"))
+    }
+
+    /// The URL a relative link or image `destination` in the body points
+    /// at, when it names one of the entry's assets.
+    pub fn asset_link(&self, destination: &str) -> Option<String> {
+        let relative = destination.strip_prefix("./").unwrap_or(destination);
+        self.assets
+            .iter()
+            .any(|asset| asset == relative)
+            .then(|| self.asset_url(relative))
+    }
+
+    /// Each asset's source file and its output path, relative to the
+    /// output directory.
+    pub fn asset_outputs(&self) -> Vec<(PathBuf, PathBuf)> {
+        let Some(dir) = self.asset_dir() else {
+            return Vec::new();
+        };
+        let page_dir: PathBuf = self.segments.iter().collect();
+        self.assets
+            .iter()
+            .map(|asset| (dir.join(asset), page_dir.join(asset)))
+            .collect()
+    }
+}
+
+/// The files under an entry's directory `dir` to copy with its page: all
+/// but hidden files and Markdown, in name order.
+pub fn list_assets(dir: &Path) -> Result<Vec<String>, String> {
+    fn walk(dir: &Path, prefix: &str, out: &mut Vec<String>) -> Result<(), String> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Could not read {}: {}", dir.display(), e))?
+            .flatten()
+            .map(|e| e.path())
+            .collect();
+        paths.sort();
+        for path in paths {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+            let relative = format!("{}{}", prefix, name);
+            if path.is_dir() {
+                walk(&path, &format!("{}/", relative), out)?;
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                continue;
+            } else if relative == "index.html" {
+                return Err(format!("{}: would overwrite the page", path.display()));
+            } else {
+                out.push(relative);
+            }
+        }
+        Ok(())
+    }
+    let mut assets = Vec::new();
+    walk(dir, "", &mut assets)?;
+    Ok(assets)
+}
+
+/// Splits `text` into its TOML frontmatter and body.
+pub fn split_frontmatter(text: &str) -> Result<(&str, &str), String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let missing = || format!("No {} frontmatter block", FRONTMATTER_DELIMITER);
+    let rest = text
+        .strip_prefix(FRONTMATTER_DELIMITER)
+        .and_then(|rest| rest.strip_prefix('\n').or(rest.strip_prefix("\r\n")))
+        .ok_or_else(missing)?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == FRONTMATTER_DELIMITER {
+            return Ok((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    Err(missing())
+}
+
+/// Parses an entry read from `source` into its frontmatter and body. The
+/// slug is left for the collection to assign.
+pub fn parse_entry<T: Content>(text: &str, source: &Path) -> Result<(T, String), String> {
+    let parse = || {
+        let (frontmatter, body) = split_frontmatter(text)?;
+        let meta: T = toml::from_str(frontmatter).map_err(|e| format!("Could not parse: {}", e))?;
+        Ok((meta, nfc(body.trim_start_matches(['\r', '\n']))))
+    };
+    parse().map_err(|e: String| format!("{}: {}", source.display(), e))
+}
+
+/// The entries of a content type, newest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Collection<T> {
+    pub def: CollectionDef,
+    pub entries: Vec<Entry<T>>,
+}
+
+impl<T: Content> Collection<T> {
+    /// Loads the collection from `def.source` under `root`; `Ok(None)` when
+    /// the directory is absent.
+    ///
+    /// Files and directories are read in name order, so names that slug
+    /// alike are numbered the same way on every build. Malformed entries
+    /// are an error naming the file.
+    pub fn load(def: CollectionDef, root: &Path) -> Result<Option<Self>, String> {
+        let dir = root.join(def.source);
+        if !dir.is_dir() {
+            return Ok(None);
+        }
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .map_err(|e| format!("Could not read {}: {}", dir.display(), e))?
+            .flatten()
+            .map(|e| e.path())
+            .collect();
+        paths.sort();
+
+        let mut sources = Vec::new();
+        for path in paths {
+            let (name, file) = if path.is_dir() {
+                (path.file_name(), path.join(INDEX_FILE))
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                (path.file_stem(), path.clone())
+            } else {
+                continue;
+            };
+            let Some(name) = name.and_then(|n| n.to_str()).map(nfc) else {
+                continue;
+            };
+            if !file.is_file() {
+                continue;
+            }
+            let text = std::fs::read_to_string(&file)
+                .map_err(|e| format!("Could not read {}: {}", file.display(), e))?;
+            sources.push((name, file, text));
+        }
+        let mut collection = Self::from_sources(def, sources)?;
+        for entry in &mut collection.entries {
+            if let Some(dir) = entry.asset_dir() {
+                entry.assets = list_assets(dir)?;
+            }
+        }
+        Ok(Some(collection))
+    }
+
+    /// Builds the collection from `(name, path, text)` sources in order.
+    pub fn from_sources(
+        def: CollectionDef,
+        sources: Vec<(String, PathBuf, String)>,
+    ) -> Result<Self, String> {
+        let mut slugs = Slugs::default();
+        let mut entries = Vec::new();
+        for (name, source, text) in sources {
+            let (meta, body) = parse_entry::<T>(&text, &source)?;
+            if meta.draft() {
+                continue;
+            }
+            check_terms(meta.terms(), TAXONOMIES)
+                .map_err(|e| format!("{}: {}", source.display(), e))?;
+            let slug = slugs.unique(meta.slug().unwrap_or(&name));
+            let segments = permalink::resolve(def.permalink, &slug, meta.date())
+                .map_err(|e| format!("{}: {}", source.display(), e))?;
+            entries.push(Entry {
+                slug,
+                segments,
+                summary: summary(meta.description(), &body),
+                meta,
+                body,
+                source,
+                links: BTreeMap::new(),
+                assets: Vec::new(),
+            });
+        }
+        entries.sort_by(|a, b| b.meta.date().cmp(a.meta.date()));
+        Ok(Collection { def, entries })
+    }
+
+    /// The listing's pages: each page's position and its entries.
+    pub fn pages(&self) -> Vec<(Pagination, &[Entry<T>])> {
+        let pages = paginate(&self.entries, self.def.per_page);
+        let count = pages.len();
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, entries)| (Pagination::new(&[self.def.dir], i + 1, count), entries))
+            .collect()
+    }
+
+    /// Every entry with its terms, for the [taxonomy](crate::taxonomy)
+    /// pages.
+    pub fn tagged(&self) -> Vec<(&Terms, Tagged)> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let tagged = Tagged {
+                    title: entry.meta.title().to_string(),
+                    url: entry.url_path(),
+                    date: entry.meta.date().to_string(),
+                    description: entry.summary.clone(),
+                };
+                (entry.meta.terms(), tagged)
+            })
+            .collect()
+    }
+
+    /// Feed items for every entry, newest first.
+    pub fn feed_items(&self) -> Vec<FeedItem> {
+        self.entries
+            .iter()
+            .map(|entry| FeedItem {
+                title: entry.meta.title().to_string(),
+                link: absolute(&entry.url_path()),
+                description: entry.summary.clone(),
+                date: entry.meta.date().to_string(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Clone, Debug, Deserialize, PartialEq)]
+    struct Note {
+        title: String,
+        date: String,
+        #[serde(default)]
+        draft: bool,
+        #[serde(default)]
+        slug: Option<String>,
+        #[serde(default)]
+        taxonomies: Terms,
+    }
+
+    impl Content for Note {
+        fn title(&self) -> &str {
+            &self.title
+        }
+        fn date(&self) -> &str {
+            &self.date
+        }
+        fn description(&self) -> &str {
+            ""
+        }
+        fn draft(&self) -> bool {
+            self.draft
+        }
+        fn slug(&self) -> Option<&str> {
+            self.slug.as_deref()
+        }
+        fn terms(&self) -> &Terms {
+            &self.taxonomies
+        }
+    }
+
+    const NOTES: CollectionDef = CollectionDef {
+        key: "notes",
+        source: "content/notes",
+        dir: "notes",
+        permalink: "/notes/:year/:slug/",
+        title: "Notes",
+        description: "Short notes.",
+        per_page: 2,
+    };
+
+    fn note(title: &str, date: &str, extra: &str) -> String {
+        format!(
+            "+++\ntitle = \"{}\"\ndate = \"{}\"\n{}+++\n\nBody of {}.\n",
+            title, date, extra, title
+        )
+    }
+
+    fn collection(notes: &[(&str, String)]) -> Result<Collection<Note>, String> {
+        Collection::from_sources(
+            NOTES,
+            notes
+                .iter()
+                .map(|(name, text)| (name.to_string(), PathBuf::from(name), text.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn frontmatter_is_split_from_body() {
+        let (front, body) = split_frontmatter("+++\na = 1\n+++\nBody\n+++\n").unwrap();
+        assert_eq!(front, "a = 1\n");
+        assert_eq!(body, "Body\n+++\n");
+        assert!(split_frontmatter("No frontmatter").is_err());
+        assert!(split_frontmatter("+++\nunterminated = true\n").is_err());
+        assert!(split_frontmatter("\u{feff}+++\r\na = 1\r\n+++\r\n").is_ok());
+    }
+
+    #[test]
+    fn entries_are_newest_first_without_drafts() {
+        let c = collection(&[
+            ("a", note("Old", "2024-01-01", "")),
+            ("b", note("New", "2025-01-01", "")),
+            ("c", note("Draft", "2025-06-01", "draft = true\n")),
+        ])
+        .unwrap();
+        let titles: Vec<&str> = c.entries.iter().map(|e| e.meta.title()).collect();
+        assert_eq!(titles, ["New", "Old"]);
+        assert_eq!(c.entries[0].body, "Body of New.\n");
+        assert_eq!(c.entries[0].summary, "Body of New.");
+        assert_eq!(c.entries[0].url_path(), "/notes/2025/b/");
+    }
+
+    #[test]
+    fn slugs_come_from_names_or_frontmatter() {
+        let c = collection(&[
+            ("Hello World", note("A", "2025-01-02", "")),
+            ("hello-world", note("B", "2025-01-01", "")),
+            ("x", note("C", "2024-01-01", "slug = \"custom\"\n")),
+        ])
+        .unwrap();
+        let slugs: Vec<&str> = c.entries.iter().map(|e| e.slug.as_str()).collect();
+        assert_eq!(slugs, ["hello-world", "hello-world-2", "custom"]);
+    }
+
+    #[test]
+    fn malformed_entries_name_their_file() {
+        let err = collection(&[("bad", "+++\ntitle = 1\n+++\n".to_string())]).unwrap_err();
+        assert!(err.starts_with("bad: "), "{}", err);
+        let err = collection(&[("undated", note("U", "someday", ""))]).unwrap_err();
+        assert!(err.contains("undated"), "{}", err);
+        let typo = note("T", "2025-01-01", "[taxonomies]\nmediums = [\"ink\"]\n");
+        let err = collection(&[("typo", typo)]).unwrap_err();
+        assert!(err.starts_with("typo: Unknown taxonomy"), "{}", err);
+    }
+
+    #[test]
+    fn listing_is_paginated() {
+        let c = collection(&[
+            ("a", note("A", "2025-01-03", "")),
+            ("b", note("B", "2025-01-02", "")),
+            ("c", note("C", "2025-01-01", "")),
+        ])
+        .unwrap();
+        let pages = c.pages();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1].0, Pagination::new(&["notes"], 2, 2));
+        assert_eq!(pages[1].1.len(), 1);
+        assert_eq!(pages[0].0.segments(), ["notes"]);
+        assert_eq!(pages[1].0.segments(), ["notes", "page", "2"]);
+    }
+
+    #[test]
+    fn entries_are_tagged_with_their_terms() {
+        let c = collection(&[(
+            "a",
+            note("A", "2025-01-03", "[taxonomies]\nmood = [\"calm\"]\n"),
+        )])
+        .unwrap();
+        let tagged = c.tagged();
+        assert_eq!(tagged[0].0["mood"], ["calm"]);
+        assert_eq!(tagged[0].1.url, "/notes/2025/a/");
+    }
+
+    #[test]
+    fn feed_links_entry_pages() {
+        let c = collection(&[("a", note("A", "2025-01-03", ""))]).unwrap();
+        let items = c.feed_items();
+        assert_eq!(items[0].link, absolute("/notes/2025/a/"));
+        assert_eq!(NOTES.feed().path, "/notes/feed.xml");
+    }
+
+    #[test]
+    fn entries_load_from_files_and_directories() {
+        let dir = std::env::temp_dir().join(format!("esart-collection-{}", std::process::id()));
+        let source = dir.join(NOTES.source);
+        std::fs::create_dir_all(source.join("walk")).unwrap();
+        std::fs::write(source.join("first.md"), note("First", "2025-01-01", "")).unwrap();
+        std::fs::write(source.join("walk/index.md"), note("Walk", "2025-02-01", "")).unwrap();
+        std::fs::write(source.join("notes.txt"), "ignored").unwrap();
+
+        let c = Collection::<Note>::load(NOTES, &dir).unwrap().unwrap();
+        let slugs: Vec<&str> = c.entries.iter().map(|e| e.slug.as_str()).collect();
+        assert_eq!(slugs, ["walk", "first"]);
+        assert_eq!(c.entries[0].source, source.join("walk/index.md"));
+        assert_eq!(
+            Collection::<Note>::load(NOTES, Path::new("nowhere")),
+            Ok(None)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn assets_next_to_an_entry_are_listed_and_linked() {
+        let dir = std::env::temp_dir().join(format!("esart-assets-{}", std::process::id()));
+        let walk = dir.join(NOTES.source).join("walk");
+        std::fs::create_dir_all(walk.join("img")).unwrap();
+        std::fs::write(walk.join("index.md"), note("Walk", "2025-02-01", "")).unwrap();
+        std::fs::write(walk.join("cover art.jpg"), "jpg").unwrap();
+        std::fs::write(walk.join("img/a.png"), "png").unwrap();
+        std::fs::write(walk.join("draft.md"), "notes").unwrap();
+        std::fs::write(walk.join(".DS_Store"), "").unwrap();
+
+        let c = Collection::<Note>::load(NOTES, &dir).unwrap().unwrap();
+        let entry = &c.entries[0];
+        assert_eq!(entry.assets, ["cover art.jpg", "img/a.png"]);
+        assert_eq!(
+            entry.asset_link("./cover art.jpg").as_deref(),
+            Some("/notes/2025/walk/cover%20art.jpg")
+        );
+        assert_eq!(
+            entry.asset_link("img/a.png").as_deref(),
+            Some("/notes/2025/walk/img/a.png")
+        );
+        assert_eq!(entry.asset_link("missing.png"), None);
+        assert_eq!(
+            entry.asset_outputs()[1],
+            (
+                walk.join("img/a.png"),
+                PathBuf::from("notes/2025/walk/img/a.png")
+            )
+        );
+
+        std::fs::write(walk.join("index.html"), "").unwrap();
+        let err = Collection::<Note>::load(NOTES, &dir).unwrap_err();
+        assert!(err.contains("would overwrite the page"), "{}", err);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
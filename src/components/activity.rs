@@ -0,0 +1,138 @@
+//! # Activity Page Component
+//!
+//! Renders the GitHub contribution calendar as an inline SVG heatmap — one
+//! column per week, one row per weekday — with no JavaScript. Cells of the
+//! same level share a single `<path>` to keep the markup small.
+
+use crate::components::{Footer, Nav};
+use crate::css;
+use crate::datetime::Date;
+use crate::github::{profile_url, ContributionDay, GITHUB_USER};
+use leptos::prelude::*;
+
+/// Cell size and gap, in SVG units.
+const CELL: u32 = 10;
+const STEP: u32 = 13;
+
+/// Fill opacity for each contribution level (0–4).
+const LEVEL_OPACITY: [&str; 5] = ["0.08", "0.3", "0.5", "0.75", "1"];
+
+/// Builds one path per level; returns `(paths, weeks)`.
+fn heatmap_paths(days: &[ContributionDay]) -> ([String; 5], u32) {
+    let offset = days
+        .first()
+        .and_then(|d| Date::parse(&d.date))
+        .map_or(0, |date| date.weekday());
+    let mut paths: [String; 5] = Default::default();
+    for (i, day) in days.iter().enumerate() {
+        let slot = offset + i as u32;
+        let (x, y) = ((slot / 7) * STEP, (slot % 7) * STEP);
+        let level = (day.level as usize).min(4);
+        paths[level].push_str(&format!("M{} {}h{}v{}h-{}z", x, y, CELL, CELL, CELL));
+    }
+    let weeks = (offset + days.len() as u32).div_ceil(7);
+    (paths, weeks)
+}
+
+/// The contribution heatmap as an SVG image.
+#[component]
+pub fn ContributionHeatmap(days: Vec<ContributionDay>) -> impl IntoView {
+    let total: u32 = days.iter().map(|d| d.count).sum();
+    let label = format!("{} contributions in the last year", total);
+    let (paths, weeks) = heatmap_paths(&days);
+
+    view! {
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox=format!("0 0 {} {}", weeks * STEP, 7 * STEP)
+            class=css::HEATMAP
+            role="img"
+            aria-label=label.clone()
+        >
+            <title>{label.clone()}</title>
+            {paths
+                .into_iter()
+                .zip(LEVEL_OPACITY)
+                .filter(|(d, _)| !d.is_empty())
+                .map(|(d, opacity)| {
+                    view! { <path d=d fill="currentColor" fill-opacity=opacity /> }
+                })
+                .collect::<Vec<_>>()}
+        </svg>
+    }
+}
+
+/// The `/activity/` page.
+#[component]
+pub fn ActivityPage(days: Vec<ContributionDay>) -> impl IntoView {
+    let total: u32 = days.iter().map(|d| d.count).sum();
+
+    view! {
+        <body itemscope itemtype="https://schema.org/WebPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 itemprop="name">"Activity"</h1>
+                <p itemprop="description">
+                    {format!("{} contributions on GitHub in the last year.", total)}
+                </p>
+                <figure>
+                    <ContributionHeatmap days=days />
+                </figure>
+                <p>
+                    <a href=profile_url(GITHUB_USER) rel="me noopener">"GitHub profile"</a>
+                </p>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    fn day(date: &str, level: u8, count: u32) -> ContributionDay {
+        ContributionDay {
+            date: date.to_string(),
+            level,
+            count,
+        }
+    }
+
+    #[test]
+    fn days_fill_columns_from_first_weekday() {
+        // 2025-01-07 is a Tuesday: row 2 of the first week
+        let (paths, weeks) = heatmap_paths(&[
+            day("2025-01-07", 1, 1),
+            day("2025-01-08", 0, 0),
+            day("2025-01-09", 0, 0),
+            day("2025-01-10", 0, 0),
+            day("2025-01-11", 0, 0),
+            day("2025-01-12", 4, 9),
+        ]);
+        assert_eq!(paths[1], "M0 26h10v10h-10z");
+        assert_eq!(paths[4], "M13 0h10v10h-10z", "Sunday starts week two");
+        assert_eq!(weeks, 2);
+    }
+
+    #[test]
+    fn heatmap_is_labelled_image() {
+        let html = ContributionHeatmap(ContributionHeatmapProps {
+            days: vec![day("2025-01-05", 2, 3), day("2025-01-06", 3, 4)],
+        })
+        .to_html();
+        let dom = parse(&html);
+        assert_attr(
+            &dom,
+            "svg",
+            "aria-label",
+            "7 contributions in the last year",
+        );
+        assert_eq!(dom.count("path"), 2, "Empty levels have no path");
+    }
+}
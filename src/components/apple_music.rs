@@ -0,0 +1,99 @@
+//! # Apple Music Release Component
+//!
+//! A static card for an Apple Music album or track (see
+//! [`crate::apple_music`]): self-hosted artwork, title, artist, and release
+//! year, with the title linking to the release on Apple Music. Marked up as a
+//! Microformats2 `h-cite` and a Schema.org `MusicAlbum` or
+//! `MusicRecording`.
+
+use crate::apple_music::AppleMusicItem;
+use crate::css;
+use leptos::prelude::*;
+
+/// The release card.
+#[component]
+pub fn AppleMusicRelease(item: AppleMusicItem) -> impl IntoView {
+    let year = item
+        .release_date
+        .as_ref()
+        .map(|d| (d.clone(), d.get(..4).unwrap_or(d).to_string()));
+    view! {
+        <figure
+            class=css::join(&["h-cite", css::RELEASE_CARD])
+            itemscope
+            itemtype=format!("https://schema.org/{}", item.schema_type())
+        >
+            {item.artwork.clone().map(|src| view! {
+                <img
+                    class=css::RELEASE_ARTWORK
+                    src=src
+                    alt=""
+                    width="160"
+                    height="160"
+                    loading="lazy"
+                    itemprop="image"
+                />
+            })}
+            <figcaption>
+                <a href=item.url.clone() class="u-url" itemprop="url" rel="noopener">
+                    <cite class="p-name" itemprop="name">{item.title.clone()}</cite>
+                </a>
+                " · "
+                <span
+                    class="p-author h-card"
+                    itemprop="byArtist"
+                    itemscope
+                    itemtype="https://schema.org/MusicGroup"
+                >
+                    <span class="p-name" itemprop="name">{item.artist.clone()}</span>
+                </span>
+                {year.map(|(date, year)| view! {
+                    " · "
+                    <time class="dt-published" itemprop="datePublished" datetime=date>{year}</time>
+                })}
+            </figcaption>
+        </figure>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apple_music::tests::sample_item;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render() -> String {
+        view! { <AppleMusicRelease item=sample_item() /> }.to_html()
+    }
+
+    #[test]
+    fn card_shows_self_hosted_artwork() {
+        let dom = parse(&render());
+        assert_attr(
+            &dom,
+            "img.release-artwork",
+            "src",
+            "/embeds/8899aabbccddeeff.jpg",
+        );
+        assert_attr(&dom, "figure", "itemtype", "https://schema.org/MusicAlbum");
+        assert_attr(
+            &dom,
+            "time[itemprop=datePublished]",
+            "datetime",
+            "2024-11-01",
+        );
+        assert_eq!(dom.text("time").as_deref(), Some("2024"));
+    }
+
+    #[test]
+    fn card_is_an_h_cite() {
+        let doc = mf2::parse(&render());
+        let cites = doc.items_of("h-cite");
+        assert_eq!(cites.len(), 1);
+        assert_eq!(cites[0].text("name"), Some("Neon"));
+        assert_eq!(
+            cites[0].text("url"),
+            Some("https://music.apple.com/us/album/neon/1440000001")
+        );
+    }
+}
@@ -4,12 +4,13 @@
 //! Uses Schema.org CollectionPage microdata.
 
 use crate::art::ArtSeries;
-use crate::components::Nav;
+use crate::components::{Footer, Nav};
+use crate::css;
 use leptos::prelude::*;
 
 /// Renders a single series card.
 fn render_series_card(series: &ArtSeries) -> impl IntoView {
-    let href = format!("/art/{}/", series.slug);
+    let href = series.url_path();
     let image_count = series.images.len();
     let count_text = if image_count == 1 {
         "1 image".to_string()
@@ -18,18 +19,20 @@ fn render_series_card(series: &ArtSeries) -> impl IntoView {
     };
 
     view! {
-        <a href=href class="series-card" itemscope itemtype="https://schema.org/ImageGallery">
+        <a href=href class=css::SERIES_CARD itemscope itemtype="https://schema.org/ImageGallery">
             <img
                 src=series.cover_url.clone()
                 alt=format!("Cover image for {}", series.title)
-                class="series-card-cover"
+                class=css::SERIES_CARD_COVER
                 itemprop="image"
                 loading="lazy"
+                decoding="async"
+                style=series.cover_placeholder.as_ref().map(|p| p.style())
             />
-            <div class="series-card-info">
+            <div class=css::SERIES_CARD_INFO>
                 <h2 itemprop="name">{series.title.clone()}</h2>
                 <p itemprop="description">{series.description.clone()}</p>
-                <span class="series-card-count">{count_text}</span>
+                <span class=css::SERIES_CARD_COUNT>{count_text}</span>
             </div>
         </a>
     }
@@ -46,17 +49,17 @@ pub fn ArtIndexPage(series: Vec<ArtSeries>) -> impl IntoView {
             <noscript>
                 <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
             </noscript>
-            <main class="container art-container">
+            <main class=css::join(&[css::CONTAINER, css::ART_CONTAINER])>
                 <Nav />
-                <header class="art-header">
+                <header class=css::ART_HEADER>
                     <h1 itemprop="name">Art Gallery</h1>
                     <p itemprop="description">AI art series by EverythingSings</p>
                 </header>
-                <div class="series-grid">
+                <section class=css::SERIES_GRID aria-label="Series">
                     {series.iter().map(render_series_card).collect::<Vec<_>>()}
-                </div>
+                </section>
             </main>
-            <footer></footer>
+            <Footer />
         </body>
     }
 }
@@ -65,20 +68,29 @@ pub fn ArtIndexPage(series: Vec<ArtSeries>) -> impl IntoView {
 mod tests {
     use super::*;
     use crate::art::ArtImage;
+    use crate::test_utils::{assert_attr, assert_exists, parse};
 
     fn sample_series() -> Vec<ArtSeries> {
         vec![ArtSeries {
             slug: "test".to_string(),
+            source: "public/art/test".into(),
             title: "Test Series".to_string(),
             description: "A test.".to_string(),
             date: "2025-06-15".to_string(),
             cover_url: "/art/test/cover.jpg".to_string(),
+            cover_placeholder: None,
             images: vec![ArtImage {
                 url: "/art/test/001.jpg".to_string(),
                 alt: "Test".to_string(),
                 title: None,
                 description: None,
+                placeholder: None,
             }],
+            comments: None,
+            nostr_note: None,
+            embeds: Vec::new(),
+            apple_music: None,
+            unlisted: false,
         }]
     }
 
@@ -91,25 +103,30 @@ mod tests {
 
     #[test]
     fn index_has_collection_page_microdata() {
-        let html = render_index();
-        assert!(html.contains("CollectionPage"));
+        let dom = parse(&render_index());
+        assert_attr(
+            &dom,
+            "body[itemscope]",
+            "itemtype",
+            "https://schema.org/CollectionPage",
+        );
     }
 
     #[test]
     fn index_has_series_card() {
-        let html = render_index();
-        assert!(html.contains("series-card"));
+        let dom = parse(&render_index());
+        assert_eq!(dom.count(".series-grid > a.series-card"), 1);
     }
 
     #[test]
     fn index_has_series_link() {
-        let html = render_index();
-        assert!(html.contains("href=\"/art/test/\""));
+        let dom = parse(&render_index());
+        assert_attr(&dom, "a.series-card", "href", "/art/test/");
     }
 
     #[test]
     fn index_has_nav() {
-        let html = render_index();
-        assert!(html.contains("site-nav"));
+        let dom = parse(&render_index());
+        assert_exists(&dom, "main > nav.site-nav");
     }
 }
@@ -1,23 +1,80 @@
 //! # Art Series Page Component
 //!
 //! Renders an individual art series page with vertically stacked images.
-//! Uses Schema.org ImageGallery + ImageObject microdata.
+//! Uses Schema.org ImageGallery + ImageObject microdata. Images carry their
+//! dimensions and a blurred [placeholder](crate::placeholder) background.
+//! The Apple Music release the series goes with, lite embeds of related
+//! videos and releases, then reaction counts and replies from the series'
+//! syndicated copies, follow the images. The page
+//! [contributes](crate::structured_data) the series' `ImageGallery` node,
+//! with all of these, to its graph.
 
+use crate::apple_music::AppleMusicItem;
 use crate::art::{ArtImage, ArtSeries};
-use crate::components::Nav;
+use crate::components::{
+    AppleMusicRelease, CommentList, Footer, LiteEmbeds, MentionSection, Nav, ReactionCounts,
+};
+use crate::css;
+use crate::embeds::Embed;
+use crate::mastodon::Comments;
+use crate::reactions::Reactions;
+use crate::structured_data::contribute;
+use crate::url::{absolute, page_path};
+use crate::webmention::Mention;
 use leptos::prelude::*;
+use serde_json::{json, Value};
+
+/// The series' `ImageGallery` node: its `release` as `mentions`, the
+/// `reactions` as interaction statistics, and the replies of `comments`.
+fn gallery_node(
+    series: &ArtSeries,
+    release: Option<&AppleMusicItem>,
+    reactions: &[Reactions],
+    comments: Option<&Comments>,
+) -> Value {
+    let url = absolute(&series.url_path());
+    let mut gallery = json!({
+        "@type": "ImageGallery",
+        "@id": format!("{}#gallery", url),
+        "name": series.title,
+        "url": url,
+        "description": series.description,
+        "numberOfItems": series.images.len(),
+    });
+    if let Some(item) = release {
+        gallery["mentions"] = item.to_json_ld();
+    }
+    let statistics: Vec<Value> = reactions
+        .iter()
+        .flat_map(Reactions::to_interaction_statistic)
+        .collect();
+    if !statistics.is_empty() {
+        gallery["interactionStatistic"] = json!(statistics);
+    }
+    if let Some(comments) = comments {
+        let replies: Vec<Value> = comments.replies.iter().map(Mention::to_comment).collect();
+        gallery["commentCount"] = json!(replies.len());
+        gallery["comment"] = json!(replies);
+    }
+    gallery
+}
 
 /// Renders a single image figure.
 fn render_image(image: &ArtImage) -> impl IntoView {
     let has_caption = image.title.is_some() || image.description.is_some();
+    let placeholder = image.placeholder.as_ref();
 
     view! {
-        <figure class="art-image" itemscope itemtype="https://schema.org/ImageObject">
+        <figure class=css::ART_IMAGE itemscope itemtype="https://schema.org/ImageObject">
             <img
                 src=image.url.clone()
                 alt=image.alt.clone()
                 itemprop="contentUrl"
                 loading="lazy"
+                decoding="async"
+                width=placeholder.map(|p| p.width)
+                height=placeholder.map(|p| p.height)
+                style=placeholder.map(|p| p.style())
             />
             {has_caption.then(|| {
                 let title = image.title.clone();
@@ -35,27 +92,51 @@ fn render_image(image: &ArtImage) -> impl IntoView {
 
 /// The art series page component.
 ///
-/// Displays a single series with a back link, header, and vertical image scroll.
+/// Displays a single series with a back link, header, vertical image scroll,
+/// its Apple Music `release`, its lite `embeds`, the `reactions` to its syndicated copies, and
+/// `comments` when the series has a thread, and a form sending webmentions to
+/// `mention_endpoint` when one is configured.
 #[component]
-pub fn ArtSeriesPage(series: ArtSeries) -> impl IntoView {
+pub fn ArtSeriesPage(
+    series: ArtSeries,
+    #[prop(optional)] release: Option<AppleMusicItem>,
+    #[prop(optional)] embeds: Vec<Embed>,
+    #[prop(optional)] reactions: Vec<Reactions>,
+    #[prop(optional)] comments: Option<Comments>,
+    #[prop(optional)] mention_endpoint: Option<&'static str>,
+) -> impl IntoView {
+    contribute(gallery_node(
+        &series,
+        release.as_ref(),
+        &reactions,
+        comments.as_ref(),
+    ));
+    let mentions = mention_endpoint.map(|endpoint| {
+        view! { <MentionSection target=absolute(&series.url_path()) endpoint=endpoint /> }
+    });
     view! {
         <body itemscope itemtype="https://schema.org/ImageGallery">
             <canvas id="shader-canvas" aria-hidden="true"></canvas>
             <noscript>
                 <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
             </noscript>
-            <main class="container art-container">
+            <main class=css::join(&[css::CONTAINER, css::ART_CONTAINER])>
                 <Nav />
-                <a href="/art/" class="back-link">{"\u{2190} All Series"}</a>
-                <header class="art-header">
+                <a href=page_path(&["art"]) class=css::BACK_LINK>{"\u{2190} All Series"}</a>
+                <header class=css::ART_HEADER>
                     <h1 itemprop="name">{series.title.clone()}</h1>
                     <p itemprop="description">{series.description.clone()}</p>
                 </header>
-                <div class="art-images">
+                <div class=css::ART_IMAGES>
                     {series.images.iter().map(render_image).collect::<Vec<_>>()}
                 </div>
+                {release.map(|item| view! { <AppleMusicRelease item=item /> })}
+                <LiteEmbeds embeds=embeds />
+                <ReactionCounts reactions=reactions />
+                {comments.map(|comments| view! { <CommentList comments=comments /> })}
+                {mentions}
             </main>
-            <footer></footer>
+            <Footer />
         </body>
     }
 }
@@ -63,67 +144,133 @@ pub fn ArtSeriesPage(series: ArtSeries) -> impl IntoView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::placeholder::Placeholder;
+    use crate::test_utils::{assert_attr, assert_exists, parse};
 
     fn sample_series() -> ArtSeries {
         ArtSeries {
             slug: "test".to_string(),
+            source: "public/art/test".into(),
             title: "Test Series".to_string(),
             description: "A test.".to_string(),
             date: "2025-06-15".to_string(),
             cover_url: "/art/test/001.jpg".to_string(),
+            cover_placeholder: None,
             images: vec![
                 ArtImage {
                     url: "/art/test/001.jpg".to_string(),
                     alt: "First image".to_string(),
                     title: Some("Dawn".to_string()),
                     description: Some("Morning light".to_string()),
+                    placeholder: Some(Placeholder {
+                        data_uri: "data:image/png;base64,AAAA".to_string(),
+                        width: 640,
+                        height: 480,
+                    }),
                 },
                 ArtImage {
                     url: "/art/test/002.jpg".to_string(),
                     alt: "Second image".to_string(),
                     title: None,
                     description: None,
+                    placeholder: None,
                 },
             ],
+            comments: None,
+            nostr_note: None,
+            embeds: Vec::new(),
+            apple_music: None,
+            unlisted: false,
         }
     }
 
     fn render_series() -> String {
         ArtSeriesPage(ArtSeriesPageProps {
             series: sample_series(),
+            release: None,
+            embeds: Vec::new(),
+            reactions: Vec::new(),
+            comments: None,
+            mention_endpoint: None,
         })
         .to_html()
     }
 
     #[test]
     fn series_has_image_gallery_microdata() {
-        let html = render_series();
-        assert!(html.contains("ImageGallery"));
+        let dom = parse(&render_series());
+        assert_attr(
+            &dom,
+            "body[itemscope]",
+            "itemtype",
+            "https://schema.org/ImageGallery",
+        );
     }
 
     #[test]
     fn series_has_image_object_microdata() {
-        let html = render_series();
-        assert!(html.contains("ImageObject"));
+        let dom = parse(&render_series());
+        assert_eq!(
+            dom.count("figure.art-image[itemtype=\"https://schema.org/ImageObject\"]"),
+            2
+        );
     }
 
     #[test]
     fn series_has_back_link() {
-        let html = render_series();
-        assert!(html.contains("href=\"/art/\""));
-        assert!(html.contains("back-link"));
+        let dom = parse(&render_series());
+        assert_attr(&dom, "a.back-link", "href", "/art/");
     }
 
     #[test]
     fn series_has_figcaption_for_titled_images() {
-        let html = render_series();
-        assert!(html.contains("<figcaption>"));
-        assert!(html.contains("Dawn"));
+        let dom = parse(&render_series());
+        assert_eq!(dom.count("figcaption"), 1, "Untitled image has no caption");
+        assert_eq!(
+            dom.text("figcaption > strong[itemprop=name]").as_deref(),
+            Some("Dawn")
+        );
+    }
+
+    #[test]
+    fn images_show_placeholder_until_loaded() {
+        let dom = parse(&render_series());
+        let first = "figure:first-child img";
+        assert_attr(
+            &dom,
+            first,
+            "style",
+            "background-image: url(data:image/png;base64,AAAA); background-size: cover;",
+        );
+        assert_attr(&dom, first, "width", "640");
+        assert_attr(&dom, first, "height", "480");
+        assert!(!dom.exists("figure:last-child img[style]"));
+    }
+
+    #[test]
+    fn embeds_and_comments_follow_images() {
+        assert!(!parse(&render_series()).exists("section.comments"));
+        let dom = parse(
+            &ArtSeriesPage(ArtSeriesPageProps {
+                series: sample_series(),
+                release: Some(crate::apple_music::tests::sample_item()),
+                embeds: vec![crate::embeds::tests::sample_embed()],
+                reactions: crate::reactions::tests::sample_reactions(),
+                comments: Some(crate::mastodon::tests::sample_comments()),
+                mention_endpoint: None,
+            })
+            .to_html(),
+        );
+        assert_exists(
+            &dom,
+            "main > .art-images + .release-card + .embeds + ul.reactions + section.comments",
+        );
+        assert_eq!(dom.count("body [itemprop=comment]"), 2);
     }
 
     #[test]
     fn series_has_nav() {
-        let html = render_series();
-        assert!(html.contains("site-nav"));
+        let dom = parse(&render_series());
+        assert_exists(&dom, "main > nav.site-nav");
     }
 }
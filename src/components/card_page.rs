@@ -0,0 +1,135 @@
+//! # Card Page Component
+//!
+//! Renders `/cards/<slug>/` on a related site for one physical trading card
+//! (see [`crate::cards`]): image, description, edition size, availability,
+//! and price. The card is a Microformats2 `h-product` and a Schema.org
+//! `Product` with an `Offer`.
+
+use crate::cards::{Availability, Card};
+use crate::components::Footer;
+use crate::config::{SITE_NAME, SITE_URL};
+use crate::css;
+use crate::site::SiteConfig;
+use leptos::prelude::*;
+
+/// A card page.
+///
+/// `currency` is the manifest's ISO 4217 code. The nav links to the site's
+/// landing page and back to the main site.
+#[component]
+pub fn CardPage(card: Card, currency: String, site: SiteConfig) -> impl IntoView {
+    let buy = (card.availability != Availability::SoldOut).then(|| {
+        card.shop_url.clone().map(|url| {
+            view! {
+                <p>
+                    <a href=url class="u-url" rel="noopener">"Buy this card"</a>
+                </p>
+            }
+        })
+    });
+    view! {
+        <body>
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <nav class=css::SITE_NAV aria-label="Site navigation">
+                    <a href=SITE_URL class=css::SITE_NAV_HOME>{SITE_NAME}</a>
+                </nav>
+                <a href="/" class=css::BACK_LINK>{format!("\u{2190} {}", site.name)}</a>
+                <article class="h-product" itemscope itemtype="https://schema.org/Product">
+                    <h1 class="p-name" itemprop="name">{card.name.clone()}</h1>
+                    <img
+                        class=css::join(&["u-photo", css::CARD_IMAGE])
+                        src=card.image_path()
+                        alt=card.name.clone()
+                        itemprop="image"
+                    />
+                    <p class="e-description" itemprop="description">{card.description.clone()}</p>
+                    <meta itemprop="sku" content=card.slug.clone() />
+                    <span itemprop="brand" itemscope itemtype="https://schema.org/Brand">
+                        <meta itemprop="name" content=site.name.clone() />
+                    </span>
+                    <dl class=css::CARD_FACTS>
+                        <dt>"Edition"</dt>
+                        <dd
+                            itemprop="additionalProperty"
+                            itemscope
+                            itemtype="https://schema.org/PropertyValue"
+                        >
+                            <meta itemprop="name" content="Edition size" />
+                            "Edition of "
+                            <span itemprop="value">{card.edition_size.to_string()}</span>
+                        </dd>
+                        <dt>"Price"</dt>
+                        <dd itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                            <data class="p-price" itemprop="price" value=card.price.clone()>
+                                {format!("{} {}", card.price, currency)}
+                            </data>
+                            <meta itemprop="priceCurrency" content=currency.clone() />
+                            " · "
+                            <link itemprop="availability" href=card.availability.schema_url() />
+                            {card.availability.label()}
+                        </dd>
+                    </dl>
+                    {buy}
+                </article>
+            </main>
+            <Footer property=site.key.clone() />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::tests::sample_manifest;
+    use crate::site::tests::sample_site;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render(i: usize) -> String {
+        CardPage(CardPageProps {
+            card: sample_manifest().cards[i].clone(),
+            currency: "USD".to_string(),
+            site: sample_site(),
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn card_is_a_product_with_offer() {
+        let dom = parse(&render(0));
+        assert_attr(&dom, "article", "itemtype", "https://schema.org/Product");
+        assert_attr(&dom, "[itemprop=offers] [itemprop=price]", "value", "12.00");
+        assert_attr(
+            &dom,
+            "[itemprop=offers] [itemprop=availability]",
+            "href",
+            "https://schema.org/InStock",
+        );
+        assert_attr(&dom, "img.card-image", "src", "/cards/aurora.jpg");
+        assert_eq!(
+            dom.text("[itemprop=additionalProperty]").as_deref(),
+            Some("Edition of 50")
+        );
+    }
+
+    #[test]
+    fn card_is_an_h_product() {
+        let doc = mf2::parse(&render(0));
+        let products = doc.items_of("h-product");
+        assert_eq!(products.len(), 1);
+        assert_eq!(products[0].text("name"), Some("Aurora"));
+    }
+
+    #[test]
+    fn sold_out_cards_have_no_buy_link() {
+        let dom = parse(&render(1));
+        assert_eq!(
+            dom.text("[itemprop=offers]").as_deref(),
+            Some("30 USD · Sold out")
+        );
+        assert!(!dom.exists("a[href=\"https://shop.test/ember\"]"));
+    }
+}
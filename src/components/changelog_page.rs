@@ -0,0 +1,90 @@
+//! # Changelog Page Component
+//!
+//! Renders `/changelog/` as a Microformats2 `h-feed` of `h-entry` changes,
+//! each linking to its commit.
+
+use crate::changelog::{Change, CHANGELOG_DESCRIPTION};
+use crate::components::{Footer, Nav, Time};
+use crate::css;
+use leptos::prelude::*;
+
+fn render_change(change: &Change) -> impl IntoView {
+    view! {
+        <li id=change.short_hash().to_string() class=css::join(&["h-entry", css::CHANGE])>
+            <Time datetime=change.date.clone() class="dt-published" />
+            " "
+            <span class="p-category">{change.kind.label()}</span>
+            {change.scope.clone().map(|scope| view! {
+                " (" <span class="p-category">{scope}</span> ")"
+            })}
+            ": "
+            <span class="p-name">{change.summary.clone()}</span>
+            {change.breaking.then(|| view! { <strong>" Breaking."</strong> })}
+            " "
+            <a href=change.commit_url() class=css::join(&["u-url", css::CHANGE_HASH])>
+                <code>{change.short_hash().to_string()}</code>
+            </a>
+        </li>
+    }
+}
+
+/// The changelog page.
+#[component]
+pub fn ChangelogPage(changes: Vec<Change>) -> impl IntoView {
+    view! {
+        <body itemscope itemtype="https://schema.org/WebPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <div class="h-feed">
+                    <h1 class="p-name" itemprop="name">"Changelog"</h1>
+                    <p itemprop="description">{CHANGELOG_DESCRIPTION}</p>
+                    <p>
+                        <a href="/changes.xml" type="application/rss+xml">"RSS feed"</a>
+                    </p>
+                    <ul class=css::CHANGE_LIST>
+                        {changes.iter().map(render_change).collect::<Vec<_>>()}
+                    </ul>
+                </div>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::changelog::tests::sample_changes;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render() -> String {
+        ChangelogPage(ChangelogPageProps {
+            changes: sample_changes(),
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn changes_are_h_entries_in_an_h_feed() {
+        let doc = mf2::parse(&render());
+        let feed = doc.items_of("h-feed")[0];
+        assert_eq!(feed.children.len(), 2);
+        assert_eq!(feed.children[0].text("name"), Some("Add series pages"));
+        assert_eq!(feed.children[1].text("published"), Some("2025-01-20"));
+    }
+
+    #[test]
+    fn changes_link_to_commits() {
+        let dom = parse(&render());
+        assert_attr(
+            &dom,
+            "li[id=abcdef0] a.u-url",
+            "href",
+            "https://github.com/EverythingSings/everythingsings.github.io/commit/abcdef0123",
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! # Comments Component
+//!
+//! Renders replies to a page as Microformats2 `h-cite`s and Schema.org
+//! `Comment`s. The guestbook shows webmentions this way; series pages show
+//! the replies to their Mastodon thread (see [`crate::mastodon`]), followed
+//! by a link to join the thread.
+
+use crate::components::Time;
+use crate::css;
+use crate::mastodon::Comments;
+use crate::webmention::Mention;
+use leptos::prelude::*;
+
+/// Renders one mention as a list item. `mf2` is its Microformats2 class,
+/// e.g. `h-cite` or `p-comment h-cite`.
+pub(crate) fn render_mention(mention: &Mention, mf2: &str) -> impl IntoView {
+    let author = match &mention.author_url {
+        Some(url) => view! {
+            <a href=url.clone() class="p-name u-url" itemprop="url" rel="nofollow ugc">
+                <span itemprop="name">{mention.author_name.clone()}</span>
+            </a>
+        }
+        .into_any(),
+        None => view! {
+            <span class="p-name" itemprop="name">{mention.author_name.clone()}</span>
+        }
+        .into_any(),
+    };
+    view! {
+        <li
+            class=css::join(&[mf2, css::MENTION])
+            itemprop="comment"
+            itemscope
+            itemtype="https://schema.org/Comment"
+        >
+            <p class=css::MENTION_META>
+                <span class="p-author h-card" itemprop="author" itemscope itemtype="https://schema.org/Person">
+                    {author}
+                </span>
+                " · "
+                <a href=mention.source.clone() class="u-url" itemprop="url" rel="nofollow ugc">
+                    <Time datetime=mention.published.clone() class="dt-published" itemprop="dateCreated" />
+                </a>
+            </p>
+            {(!mention.text.is_empty()).then(|| view! {
+                <p class="p-content" itemprop="text">{mention.text.clone()}</p>
+            })}
+        </li>
+    }
+}
+
+/// The comments section of a page discussed on Mastodon.
+#[component]
+pub fn CommentList(comments: Comments) -> impl IntoView {
+    let replies = if comments.replies.is_empty() {
+        view! { <p>"No replies yet."</p> }.into_any()
+    } else {
+        view! {
+            <ol class=css::MENTION_LIST>
+                {comments
+                    .replies
+                    .iter()
+                    .map(|reply| render_mention(reply, "p-comment h-cite"))
+                    .collect::<Vec<_>>()}
+            </ol>
+        }
+        .into_any()
+    };
+    view! {
+        <section class=css::COMMENTS aria-labelledby="comments">
+            <h2 id="comments">"Comments"</h2>
+            {replies}
+            <p>
+                <a href=comments.reply_url rel="nofollow noopener">"Reply on Mastodon"</a>
+            </p>
+        </section>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mastodon::tests::sample_comments;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render(comments: Comments) -> String {
+        view! { <CommentList comments=comments /> }.to_html()
+    }
+
+    #[test]
+    fn replies_are_comment_h_cites() {
+        let html = render(sample_comments());
+        let doc = mf2::parse(&html);
+        let cites = doc.items_of("h-cite");
+        assert_eq!(cites.len(), 2);
+        assert_eq!(cites[0].text("content"), Some("Lovely work"));
+
+        let dom = parse(&html);
+        assert_eq!(
+            dom.count(
+                "ol > li.p-comment[itemprop=comment][itemtype=\"https://schema.org/Comment\"]"
+            ),
+            2
+        );
+        assert_attr(
+            &dom,
+            "li:first-child time.dt-published",
+            "datetime",
+            "2025-01-17T10:00:00.000Z",
+        );
+    }
+
+    #[test]
+    fn section_links_to_thread() {
+        let dom = parse(&render(Comments {
+            reply_url: "https://mastodon.test/@x/1".to_string(),
+            replies: Vec::new(),
+        }));
+        assert_attr(
+            &dom,
+            "section.comments > p > a",
+            "href",
+            "https://mastodon.test/@x/1",
+        );
+        assert_eq!(dom.text("section > p").as_deref(), Some("No replies yet."));
+    }
+}
@@ -0,0 +1,226 @@
+//! # CV Page Component
+//!
+//! Renders `/cv/` from a [`Resume`] with the usual semantic layers:
+//! a Microformats2 `h-resume` (positions and education as nested `h-event`s)
+//! and Schema.org `Person` microdata with `hasOccupation`.
+
+use crate::components::{Footer, Nav};
+use crate::css;
+use crate::resume::{date_range, Education, Project, Resume, Skill, Work};
+use leptos::prelude::*;
+
+fn render_dates(start: Option<&str>, end: Option<&str>) -> impl IntoView {
+    let label = date_range(start, end);
+    let start = start.map(str::to_string);
+    let end = end.map(str::to_string);
+    label.map(|label| {
+        view! {
+            <p class=css::CV_DATES>
+                {start.map(|s| view! { <time class="dt-start" datetime=s></time> })}
+                {end.map(|e| view! { <time class="dt-end" datetime=e></time> })}
+                {label}
+            </p>
+        }
+    })
+}
+
+fn render_highlights(highlights: &[String]) -> impl IntoView {
+    (!highlights.is_empty()).then(|| {
+        view! {
+            <ul>
+                {highlights.iter().map(|h| view! { <li>{h.clone()}</li> }).collect::<Vec<_>>()}
+            </ul>
+        }
+    })
+}
+
+fn render_work(work: &Work) -> impl IntoView {
+    let org = match &work.url {
+        Some(url) => {
+            view! { <a href=url.clone() class="p-location">{work.name.clone()}</a> }.into_any()
+        }
+        None => view! { <span class="p-location">{work.name.clone()}</span> }.into_any(),
+    };
+    view! {
+        <div
+            class=css::join(&["p-experience", "h-event", css::CV_ENTRY])
+            itemprop="hasOccupation"
+            itemscope
+            itemtype="https://schema.org/Occupation"
+        >
+            <h3>
+                <span class="p-name" itemprop="name">{work.position.clone()}</span>
+                " · "
+                {org}
+            </h3>
+            {render_dates(Some(&work.start_date), work.end_date.as_deref())}
+            {(!work.summary.is_empty()).then(|| view! {
+                <p class="p-summary" itemprop="description">{work.summary.clone()}</p>
+            })}
+            {render_highlights(&work.highlights)}
+        </div>
+    }
+}
+
+fn render_project(project: &Project) -> impl IntoView {
+    let name = match &project.url {
+        Some(url) => view! { <a href=url.clone()>{project.name.clone()}</a> }.into_any(),
+        None => project.name.clone().into_any(),
+    };
+    view! {
+        <div class=css::CV_ENTRY>
+            <h3>{name}</h3>
+            {render_dates(project.start_date.as_deref(), project.end_date.as_deref())}
+            <p>{project.description.clone()}</p>
+            {render_highlights(&project.highlights)}
+        </div>
+    }
+}
+
+fn render_education(education: &Education) -> impl IntoView {
+    let study = [education.study_type.as_str(), education.area.as_str()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+    view! {
+        <div
+            class=css::join(&["p-education", "h-event", css::CV_ENTRY])
+            itemprop="alumniOf"
+            itemscope
+            itemtype="https://schema.org/EducationalOrganization"
+        >
+            <h3 class="p-name" itemprop="name">{education.institution.clone()}</h3>
+            {(!study.is_empty()).then(|| view! { <p>{study}</p> })}
+            {render_dates(education.start_date.as_deref(), education.end_date.as_deref())}
+        </div>
+    }
+}
+
+fn render_skill(skill: &Skill) -> impl IntoView {
+    let keywords = (!skill.keywords.is_empty()).then(|| format!(": {}", skill.keywords.join(", ")));
+    view! {
+        <li>
+            <span class="p-skill" itemprop="knowsAbout">{skill.name.clone()}</span>
+            {keywords}
+        </li>
+    }
+}
+
+/// The CV page.
+#[component]
+pub fn CvPage(resume: Resume) -> impl IntoView {
+    let basics = resume.basics.clone();
+
+    view! {
+        <body itemscope itemtype="https://schema.org/ProfilePage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <article
+                    class="h-resume"
+                    itemprop="mainEntity"
+                    itemscope
+                    itemtype="https://schema.org/Person"
+                >
+                    <h1 class="p-name" itemprop="name">{basics.name}</h1>
+                    {(!basics.label.is_empty()).then(|| view! {
+                        <p class=css::CV_LABEL itemprop="jobTitle">{basics.label}</p>
+                    })}
+                    <p class="p-summary" itemprop="description">{basics.summary}</p>
+
+                    {(!resume.work.is_empty()).then(|| view! {
+                        <section class=css::CV_SECTION aria-labelledby="cv-experience">
+                            <h2 id="cv-experience">"Experience"</h2>
+                            {resume.work.iter().map(render_work).collect::<Vec<_>>()}
+                        </section>
+                    })}
+                    {(!resume.projects.is_empty()).then(|| view! {
+                        <section class=css::CV_SECTION aria-labelledby="cv-projects">
+                            <h2 id="cv-projects">"Projects"</h2>
+                            {resume.projects.iter().map(render_project).collect::<Vec<_>>()}
+                        </section>
+                    })}
+                    {(!resume.skills.is_empty()).then(|| view! {
+                        <section class=css::CV_SECTION aria-labelledby="cv-skills">
+                            <h2 id="cv-skills">"Skills"</h2>
+                            <ul>{resume.skills.iter().map(render_skill).collect::<Vec<_>>()}</ul>
+                        </section>
+                    })}
+                    {(!resume.education.is_empty()).then(|| view! {
+                        <section class=css::CV_SECTION aria-labelledby="cv-education">
+                            <h2 id="cv-education">"Education"</h2>
+                            {resume.education.iter().map(render_education).collect::<Vec<_>>()}
+                        </section>
+                    })}
+
+                    <p>
+                        <a href="/resume.json" type="application/json">"resume.json"</a>
+                    </p>
+                </article>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resume::tests::sample_resume;
+    use crate::test_utils::{assert_attr, mf2, parse, Dom};
+
+    fn render() -> String {
+        CvPage(CvPageProps {
+            resume: sample_resume(),
+        })
+        .to_html()
+    }
+
+    fn dom() -> Dom {
+        parse(&render())
+    }
+
+    #[test]
+    fn cv_is_an_h_resume() {
+        let doc = mf2::parse(&render());
+        let resume = doc.items_of("h-resume")[0];
+        assert_eq!(resume.text("name"), Some("Ada"));
+        assert_eq!(resume.text("summary"), Some("Makes things."));
+    }
+
+    #[test]
+    fn positions_are_occupations() {
+        let dom = dom();
+        assert_attr(
+            &dom,
+            "[itemprop=hasOccupation]",
+            "itemtype",
+            "https://schema.org/Occupation",
+        );
+        assert_eq!(
+            dom.text("[itemprop=hasOccupation] [itemprop=name]")
+                .as_deref(),
+            Some("Artist")
+        );
+    }
+
+    #[test]
+    fn open_ended_dates_show_present() {
+        let dom = dom();
+        assert_attr(&dom, ".p-experience time.dt-start", "datetime", "2021-03");
+        assert!(dom
+            .text(".p-experience .cv-dates")
+            .unwrap()
+            .contains("Present"));
+    }
+
+    #[test]
+    fn empty_sections_are_omitted() {
+        let dom = dom();
+        assert_eq!(dom.texts("h2"), ["Experience", "Projects", "Skills"]);
+    }
+}
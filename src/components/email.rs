@@ -0,0 +1,99 @@
+//! # Email Component
+//!
+//! Renders an email address that people can read but naive scrapers can't,
+//! without JavaScript: the address is reversed, written entirely as HTML
+//! character references, and put back in reading order by CSS
+//! `direction: rtl`. Screen readers get a spoken form ("name at example dot
+//! com") in visually hidden text instead of the reversed markup.
+//!
+//! A machine-readable `mailto:` link (`u-email`, `itemprop="email"`) exposes
+//! the plain address, so it is only emitted when explicitly requested.
+
+use crate::css;
+use leptos::prelude::*;
+
+/// Reverses `address` and encodes every character as a hex character
+/// reference, e.g. `a@b` → `&#x62;&#x40;&#x61;`.
+pub fn obfuscate_email(address: &str) -> String {
+    address
+        .chars()
+        .rev()
+        .map(|c| format!("&#x{:x};", c as u32))
+        .collect()
+}
+
+/// The address as it would be read aloud, e.g. `name at example dot com`.
+pub fn spoken_email(address: &str) -> String {
+    address.replace('@', " at ").replace('.', " dot ")
+}
+
+/// An obfuscated email address.
+///
+/// With `machine_readable`, the address is wrapped in a `mailto:` link marked
+/// up as the h-card's `u-email` and Schema.org `email`.
+#[component]
+pub fn Email(address: &'static str, #[prop(optional)] machine_readable: bool) -> impl IntoView {
+    let text = view! {
+        <span class=css::EMAIL_OBFUSCATED aria-hidden="true" inner_html=obfuscate_email(address)></span>
+        <span class=css::VISUALLY_HIDDEN>{spoken_email(address)}</span>
+    };
+
+    if machine_readable {
+        view! {
+            <a href=format!("mailto:{}", address) class="u-email" itemprop="email">
+                {text}
+            </a>
+        }
+        .into_any()
+    } else {
+        text.into_any()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    const ADDRESS: &str = "hello@example.com";
+
+    fn render(machine_readable: bool) -> String {
+        Email(EmailProps {
+            address: ADDRESS,
+            machine_readable,
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn obfuscation_is_reversible() {
+        let dom = parse(&render(false));
+        let shown = dom.text(".email-obfuscated").unwrap();
+        assert_eq!(shown.chars().rev().collect::<String>(), ADDRESS);
+    }
+
+    #[test]
+    fn plain_address_is_not_in_markup_by_default() {
+        let html = render(false);
+        assert!(!html.contains(ADDRESS), "{}", html);
+        assert!(!html.contains("mailto:"));
+        assert!(!html.contains("u-email"));
+    }
+
+    #[test]
+    fn screen_readers_get_spoken_form() {
+        let dom = parse(&render(false));
+        assert_attr(&dom, ".email-obfuscated", "aria-hidden", "true");
+        assert_eq!(
+            dom.text(".visually-hidden").as_deref(),
+            Some("hello at example dot com")
+        );
+    }
+
+    #[test]
+    fn machine_readable_link_when_enabled() {
+        let dom = parse(&render(true));
+        assert_attr(&dom, "a.u-email", "href", "mailto:hello@example.com");
+        assert_attr(&dom, "a.u-email", "itemprop", "email");
+    }
+}
@@ -0,0 +1,72 @@
+//! # Footer Component
+//!
+//! Site-wide footer with the [property links](PropertyLinks), ending with
+//! the generator version, linked to the commit the page was built from.
+//! Builds with the `theme-toggle` feature add the [`ThemeToggle`] last.
+
+use crate::components::{PropertyLinks, PropertyLinksProps, ThemeToggle};
+use crate::provenance::{commit_url, version_label};
+use crate::theme::THEME_TOGGLE_ENABLED;
+use leptos::prelude::*;
+
+/// The footer. `property` is the key of the related property rendering it;
+/// `None` on the main site.
+#[component]
+pub fn Footer(
+    #[prop(optional)] children: Option<Children>,
+    #[prop(optional)] property: Option<String>,
+) -> impl IntoView {
+    view! {
+        <footer>
+            {children.map(|children| children())}
+            {PropertyLinks(PropertyLinksProps { current: property })}
+            <p>
+                <a href=commit_url() rel="noopener">
+                    <small>{version_label()}</small>
+                </a>
+            </p>
+            {THEME_TOGGLE_ENABLED.then(ThemeToggle)}
+        </footer>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::VERSION;
+    use crate::test_utils::{assert_attr, parse};
+
+    #[test]
+    fn footer_links_version_to_commit() {
+        let dom = parse(&view! { <Footer /> }.to_html());
+        assert_attr(&dom, "footer > p a", "href", &commit_url());
+        assert!(dom
+            .text("footer > p a")
+            .unwrap()
+            .starts_with(&format!("v{}", VERSION)));
+    }
+
+    #[test]
+    fn footer_links_related_properties() {
+        let dom = parse(&view! { <Footer property="lumimenta".to_string() /> }.to_html());
+        assert_attr(
+            &dom,
+            "footer a[rel=home]",
+            "href",
+            "https://everythingsings.art/",
+        );
+        assert!(dom.exists("footer a[aria-current=true]"));
+    }
+
+    #[test]
+    fn footer_has_toggle_only_with_feature() {
+        let dom = parse(&view! { <Footer /> }.to_html());
+        assert_eq!(dom.exists("footer #theme-toggle"), THEME_TOGGLE_ENABLED);
+    }
+
+    #[test]
+    fn footer_keeps_children_first() {
+        let dom = parse(&view! { <Footer><p>"Name"</p></Footer> }.to_html());
+        assert_eq!(dom.texts("footer > p")[0], "Name");
+    }
+}
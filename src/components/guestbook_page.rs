@@ -0,0 +1,122 @@
+//! # Guestbook Page Component
+//!
+//! Renders `/guestbook/`: how to sign (a plain form posting to the webmention
+//! endpoint, no script) and the moderated mentions as a Microformats2
+//! `h-feed` of `h-cite`s, each a Schema.org `Comment` on the page.
+
+use crate::components::comments::render_mention;
+use crate::components::{Footer, MentionForm, Nav};
+use crate::css;
+use crate::webmention::{Mention, GUESTBOOK_DESCRIPTION};
+use leptos::prelude::*;
+
+/// The guestbook page. `url` is the page's own address (the webmention
+/// target) and `endpoint` the webmention endpoint signatures are sent to.
+#[component]
+pub fn GuestbookPage(
+    mentions: Vec<Mention>,
+    url: String,
+    endpoint: &'static str,
+    /// Whether new signatures wait for approval before appearing.
+    #[prop(optional)]
+    moderated: bool,
+) -> impl IntoView {
+    let signatures = if mentions.is_empty() {
+        view! { <p>"No signatures yet."</p> }.into_any()
+    } else {
+        view! {
+            <ol class=css::MENTION_LIST>
+                {mentions.iter().map(|m| render_mention(m, "h-cite")).collect::<Vec<_>>()}
+            </ol>
+        }
+        .into_any()
+    };
+    view! {
+        <body itemscope itemtype="https://schema.org/WebPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <div class="h-feed">
+                    <h1 class="p-name" itemprop="name">"Guestbook"</h1>
+                    <p itemprop="description">{GUESTBOOK_DESCRIPTION}</p>
+                    <section aria-labelledby="guestbook-sign">
+                        <h2 id="guestbook-sign">"Sign"</h2>
+                        <p>
+                            "Write a post that links to "
+                            <a href=url.clone()>{url.clone()}</a>
+                            ", then send a "
+                            <a href=endpoint rel="webmention">"webmention"</a>
+                            " with its address:"
+                        </p>
+                        <MentionForm target=url.clone() endpoint=endpoint id="guestbook-source" />
+                        {moderated.then(|| view! {
+                            <p>"Signatures appear here once approved."</p>
+                        })}
+                    </section>
+                    <section aria-labelledby="guestbook-signatures">
+                        <h2 id="guestbook-signatures">"Signatures"</h2>
+                        {signatures}
+                    </section>
+                </div>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, mf2, parse};
+    use crate::webmention::tests::sample_mentions;
+
+    const URL: &str = "https://everythingsings.art/guestbook/";
+    const ENDPOINT: &str = "https://webmention.io/everythingsings.art/webmention";
+
+    fn render(mentions: Vec<Mention>) -> String {
+        view! { <GuestbookPage mentions=mentions url=URL.to_string() endpoint=ENDPOINT moderated=true /> }
+            .to_html()
+    }
+
+    #[test]
+    fn form_posts_source_and_target_to_endpoint() {
+        let dom = parse(&render(Vec::new()));
+        assert_attr(&dom, "form.mention-form", "action", ENDPOINT);
+        assert_attr(&dom, "form input[name=target]", "value", URL);
+        assert!(dom.exists("form input[name=source][type=url][required]"));
+        assert_attr(&dom, "a[rel=webmention]", "href", ENDPOINT);
+        assert_eq!(
+            dom.text("section:last-child > p").as_deref(),
+            Some("No signatures yet.")
+        );
+    }
+
+    #[test]
+    fn mentions_are_h_cites_and_comments() {
+        let html = render(sample_mentions());
+        let doc = mf2::parse(&html);
+        let feed = doc.items_of("h-feed")[0];
+        assert_eq!(feed.children.len(), 2);
+        assert_eq!(feed.children[1].text("content"), Some("Hi there"));
+        assert_eq!(
+            feed.children[1].text("published"),
+            Some("2025-01-17T12:00:00+00:00")
+        );
+
+        let dom = parse(&html);
+        assert_eq!(
+            dom.count("li[itemprop=comment][itemtype=\"https://schema.org/Comment\"]"),
+            2
+        );
+        assert_attr(
+            &dom,
+            "[itemprop=author] a[itemprop=url]",
+            "rel",
+            "nofollow ugc",
+        );
+        assert!(!dom.exists("img"), "Sender images are not embedded");
+    }
+}
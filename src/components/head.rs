@@ -5,21 +5,56 @@
 //! ## Contents
 //!
 //! - Character encoding and viewport meta tags
+//! - Title, description, and Open Graph and Twitter Card tags, serialized
+//!   by [`MetaTags`]
+//! - Content Security Policy and analytics script, when analytics is on
+//! - Generator meta tag (version, commit, build time)
+//! - Resource hints (`preconnect`, `dns-prefetch`, `preload`)
 //! - Favicon and Apple Touch Icon
 //! - Theme color and web app manifest
-//! - Open Graph and Twitter Card meta tags
-//! - JSON-LD structured data (Schema.org Person)
-//! - RSS feed link
+//! - View transition opt-in for same-origin navigations (the
+//!   `@view-transition` rule in the stylesheet; the meta tag for early
+//!   Chromium versions)
+//! - Dublin Core meta tags (`dc.creator`, `dc.title`, `dc.rights`, `dc.date`)
+//!   for archival crawlers
+//! - JSON-LD structured data: the [`@graph`](crate::schema) the homepage's
+//!   components contribute as they render (see [`crate::structured_data`])
+//! - Alternate representation links: the feeds advertised on the page (see
+//!   [`crate::feed::feeds`]), the site's JSON documents and `llms.txt`, then
+//!   the page's own (see [`crate::alternates`])
+//! - Webmention and pingback endpoint links, when configured (see
+//!   [`crate::webmention::endpoints`])
+//! - The theme script, in `theme-toggle` builds (see [`crate::theme`])
 //! - Stylesheet link
-//! - Canonical URL
+//! - Canonical URL, with `rel="prev"`/`rel="next"` on paginated listings
+//! - `robots` meta tag on pages kept out of search results
+//! - Speculation rules for internal pages the page links to
 //!
 //! Note: The Head component returns raw HTML because Leptos's view! macro
 //! doesn't support the `property` attribute needed for Open Graph meta tags.
 
-use crate::config::{AVATAR_PATH, SITE_DESCRIPTION, SITE_NAME, SITE_URL};
+use crate::alternates::{site_alternates, Alternate};
+use crate::config::{ANALYTICS, SITE_RIGHTS, SITE_URL};
+use crate::datetime::Date;
+use crate::entity::primary_entity;
+use crate::escape::escape_html;
+use crate::feed::feed_alternates;
+use crate::hints::{homepage_hints, ResourceHint};
+use crate::meta_tags::{MetaImage, MetaTags, OgType};
+use crate::oembed::oembed_alternate;
+use crate::pagination::Pagination;
+use crate::provenance::{generator, BUILD_TIME};
+use crate::schema::Graph;
+use crate::speculation::SpeculationRules;
+use crate::theme::init_script_html;
+use crate::url::absolute;
+use crate::webmention::{endpoint_links_html, endpoints};
+
+/// The `robots` meta tag of pages kept out of search results.
+pub const NOINDEX_META: &str = r#"<meta name="robots" content="noindex" />"#;
 
 /// Theme color for browser chrome (matches --color-bg in dark mode).
-const THEME_COLOR: &str = "#0d0d0d";
+const THEME_COLOR: &str = crate::tokens::DARK.bg;
 use leptos::prelude::*;
 
 /// Per-page metadata for head generation.
@@ -27,88 +62,156 @@ pub struct PageMeta {
     pub title: String,
     pub description: String,
     pub canonical_url: String,
-    pub og_type: String,
-    pub og_image: String,
+    pub og_type: OgType,
+    /// The preview image, if the page has one.
+    pub og_image: Option<MetaImage>,
     pub json_ld: String,
+    /// Representations of this page, advertised after the site-wide ones
+    /// (see [`crate::alternates`]).
+    pub alternates: Vec<Alternate>,
+    /// Position in a paginated listing, whose first page is `canonical_url`.
+    /// Each page is then canonical at its own URL and links its neighbours.
+    pub pagination: Option<Pagination>,
+    /// Keeps the page out of search results, for pages only reachable by
+    /// their link (see [`crate::unlisted`]).
+    pub noindex: bool,
+    /// Resource hints emitted right after the viewport meta tag.
+    pub hints: Vec<ResourceHint>,
+    /// Internal pages the browser may prefetch or prerender.
+    pub speculation: SpeculationRules,
 }
 
-/// Generates the JSON-LD structured data for the homepage.
-///
-/// Returns a Schema.org Person object as a JSON string.
-pub fn generate_json_ld() -> String {
+/// Dublin Core tags for a page titled `title`: the primary entity as
+/// creator, [`SITE_RIGHTS`], and the page's `date`, or the build date for
+/// an undated page.
+fn dublin_core(title: &str, date: Option<&str>) -> String {
+    let date = date
+        .and_then(Date::parse)
+        .or_else(|| Date::parse(BUILD_TIME))
+        .map(|d| d.iso())
+        .unwrap_or_default();
     format!(
-        r#"{{
-  "@context": "https://schema.org",
-  "@type": "Person",
-  "name": "{name}",
-  "url": "{url}",
-  "description": "{description}",
-  "image": "{url}{avatar}",
-  "sameAs": []
-}}"#,
-        name = SITE_NAME,
-        url = SITE_URL,
-        description = SITE_DESCRIPTION,
-        avatar = AVATAR_PATH,
+        r#"<link rel="schema.DC" href="http://purl.org/dc/elements/1.1/" />
+<meta name="dc.creator" content="{creator}" />
+<meta name="dc.title" content="{title}" />
+<meta name="dc.rights" content="{rights}" />
+<meta name="dc.date" content="{date}" />"#,
+        creator = escape_html(primary_entity().name),
+        title = escape_html(title),
+        rights = escape_html(SITE_RIGHTS),
+        date = date,
     )
 }
 
 /// Generates the complete `<head>` element for a given page.
+///
+/// All text fields of `meta` are HTML-escaped; `json_ld` must already be
+/// valid JSON built with [`json_string`](crate::escape::json_string).
 pub fn generate_head_html_for(meta: &PageMeta) -> String {
+    let page_path = meta
+        .canonical_url
+        .strip_prefix(SITE_URL)
+        .unwrap_or(&meta.canonical_url);
+    let alternates: String = feed_alternates(if page_path.is_empty() { "/" } else { page_path })
+        .iter()
+        .chain(&site_alternates())
+        .chain(&meta.alternates)
+        .map(|alternate| format!("\n{}", alternate.to_html()))
+        .collect();
+    let hints: String = meta
+        .hints
+        .iter()
+        .map(|hint| format!("\n{}", hint.to_html()))
+        .collect();
+    let speculation = match meta.speculation.to_html() {
+        html if html.is_empty() => html,
+        html => format!("\n{}", html),
+    };
+    let (url, pagination) = match &meta.pagination {
+        Some(p) => (absolute(&p.path()), p.head_links()),
+        None => (meta.canonical_url.clone(), String::new()),
+    };
+    let robots = match meta.noindex {
+        true => format!("\n{}", NOINDEX_META),
+        false => String::new(),
+    };
+    let csp = match ANALYTICS.csp_meta_html() {
+        html if html.is_empty() => html,
+        html => format!("\n{}", html),
+    };
+    let theme = match init_script_html() {
+        html if html.is_empty() => html,
+        html => format!("\n{}", html),
+    };
+    let analytics = match ANALYTICS.script_html() {
+        html if html.is_empty() => html,
+        html => format!("\n{}", html),
+    };
+    let mut tags =
+        MetaTags::new(&meta.title, &meta.description, &url).og_type(meta.og_type.clone());
+    if let Some(image) = &meta.og_image {
+        tags = tags.image(image.clone());
+    }
     format!(
         r#"<head>
-<meta charset="utf-8" />
-<meta name="viewport" content="width=device-width, initial-scale=1" />
-<title>{title}</title>
-<meta name="description" content="{description}" />
-<link rel="canonical" href="{url}" />
+<meta charset="utf-8" />{csp}
+<meta name="viewport" content="width=device-width, initial-scale=1" />{hints}
+<meta name="generator" content="{generator}" />
+{tags}{robots}
+<link rel="canonical" href="{url}" />{pagination}
 <link rel="icon" href="/favicon.ico" sizes="32x32" />
 <link rel="icon" href="/favicon.svg" type="image/svg+xml" />
 <link rel="apple-touch-icon" href="/apple-touch-icon.png" />
 <link rel="manifest" href="/site.webmanifest" />
 <meta name="theme-color" content="{theme}" />
-<meta property="og:type" content="{og_type}" />
-<meta property="og:title" content="{title}" />
-<meta property="og:description" content="{description}" />
-<meta property="og:url" content="{url}" />
-<meta property="og:image" content="{og_image}" />
-<meta name="twitter:card" content="summary" />
-<meta name="twitter:title" content="{title}" />
-<meta name="twitter:description" content="{description}" />
-<meta name="twitter:image" content="{og_image}" />
-<link rel="alternate" type="application/rss+xml" title="{name} RSS Feed" href="/feed.xml" />
-<script type="application/ld+json">{json_ld}</script>
-<link rel="stylesheet" href="/main.css" />
-<script src="/js/shader-bg.js" defer></script>
+<meta name="view-transition" content="same-origin" />
+{dublin_core}{alternates}{endpoints}
+<script type="application/ld+json">{json_ld}</script>{theme_script}
+<link rel="stylesheet" href="/main.css" />{analytics}{speculation}
 </head>"#,
-        title = meta.title,
-        description = meta.description,
-        url = meta.canonical_url,
-        og_type = meta.og_type,
-        og_image = meta.og_image,
+        tags = tags.to_html(),
+        url = escape_html(&url),
+        pagination = pagination,
+        robots = robots,
         theme = THEME_COLOR,
-        name = SITE_NAME,
         json_ld = meta.json_ld,
+        alternates = alternates,
+        endpoints = endpoint_links_html(&endpoints()),
+        hints = hints,
+        speculation = speculation,
+        csp = csp,
+        analytics = analytics,
+        theme_script = theme,
+        generator = escape_html(&generator()),
+        dublin_core = dublin_core(&meta.title, meta.og_type.date()),
     )
 }
 
-/// Generates the complete `<head>` element content as HTML string.
+/// Generates the complete `<head>` element content as HTML string, with
+/// `graph` (the homepage body's, see [`crate::structured_data`]) as its
+/// JSON-LD and speculation rules prefetching `prefetch`, the optional pages
+/// the build generated.
 ///
 /// Returns the full head HTML including Open Graph meta tags.
 /// This is used directly in SSG mode since Leptos's view! macro
 /// doesn't support the `property` attribute.
-pub fn generate_head_html() -> String {
-    let json_ld = generate_json_ld();
-    let _full_avatar_url = format!("{}{}", SITE_URL, AVATAR_PATH);
-    let hero_url = format!("{}/hero.jpg", SITE_URL);
+pub fn generate_head_html(graph: &Graph, prefetch: &[String]) -> String {
+    let json_ld = graph.to_json_ld();
+    let hero_url = absolute("/hero.jpg");
+    let primary = primary_entity();
 
     generate_head_html_for(&PageMeta {
-        title: format!("{} | Digital Artist", SITE_NAME),
-        description: SITE_DESCRIPTION.to_string(),
+        title: format!("{} | Digital Artist", primary.name),
+        description: primary.description.to_string(),
         canonical_url: SITE_URL.to_string(),
-        og_type: "profile".to_string(),
-        og_image: hero_url,
+        og_type: OgType::Profile,
+        og_image: Some(MetaImage::new(hero_url)),
         json_ld,
+        alternates: vec![oembed_alternate(primary.name, "/")],
+        pagination: None,
+        noindex: false,
+        hints: homepage_hints(),
+        speculation: SpeculationRules::prefetch(prefetch.iter().cloned()),
     })
 }
 
@@ -128,223 +231,581 @@ pub fn Head() -> impl IntoView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::App;
+    use crate::config::{SITE_DESCRIPTION, SITE_NAME};
+    use crate::escape::json_string;
+    use crate::structured_data::render_with_graph;
+    use crate::test_utils::{assert_attr, assert_exists, parse, Dom};
+    use crate::text::{meta_description, nfc, DESCRIPTION_LIMIT};
+    use proptest::prelude::*;
 
-    /// Tests use generate_head_html() directly since the component
+    /// The graph the homepage body contributes.
+    fn homepage_graph() -> Graph {
+        render_with_graph(|| view! { <App /> }.to_html()).1
+    }
+
+    /// Tests use homepage_head() directly since the component
     /// returns empty view for SSG compatibility.
-    fn render_head() -> String {
-        generate_head_html()
+    fn homepage_head() -> String {
+        generate_head_html(&homepage_graph(), &[])
+    }
+
+    fn render_head() -> Dom {
+        parse(&homepage_head())
+    }
+
+    fn json_ld() -> serde_json::Value {
+        serde_json::from_str(&homepage_graph().to_json_ld()).expect("JSON-LD should be valid JSON")
+    }
+
+    /// The primary entity's node in the homepage graph.
+    fn primary_node() -> serde_json::Value {
+        let id = primary_entity().id();
+        json_ld()["@graph"]
+            .as_array()
+            .and_then(|nodes| nodes.iter().find(|node| node["@id"] == id))
+            .cloned()
+            .expect("graph should contain the primary entity")
     }
 
     #[test]
     fn head_contains_charset() {
-        let html = render_head();
-        assert!(
-            html.contains("charset=\"utf-8\""),
-            "Head should specify UTF-8 charset"
-        );
+        let dom = render_head();
+        assert_exists(&dom, "head > meta[charset=utf-8]");
     }
 
     #[test]
     fn head_contains_viewport() {
-        let html = render_head();
-        assert!(
-            html.contains("name=\"viewport\""),
-            "Head should contain viewport meta tag"
-        );
+        let dom = render_head();
+        assert_exists(&dom, "head > meta[name=viewport][content]");
     }
 
     #[test]
     fn head_contains_title() {
-        let html = render_head();
-        assert!(
-            html.contains("<title>"),
-            "Head should contain title element"
+        let dom = render_head();
+        assert_eq!(
+            dom.count("head > title"),
+            1,
+            "Head should contain one title"
         );
         assert!(
-            html.contains(SITE_NAME),
+            dom.text("title").unwrap().contains(SITE_NAME),
             "Title should contain site name"
         );
     }
 
     #[test]
     fn head_contains_description() {
-        let html = render_head();
-        assert!(
-            html.contains("name=\"description\""),
-            "Head should contain description meta tag"
-        );
+        let dom = render_head();
+        assert_attr(&dom, "meta[name=description]", "content", SITE_DESCRIPTION);
     }
 
     #[test]
     fn head_contains_canonical_url() {
-        let html = render_head();
-        assert!(
-            html.contains("rel=\"canonical\""),
-            "Head should contain canonical link"
-        );
-        assert!(
-            html.contains(SITE_URL),
-            "Canonical should point to site URL"
-        );
+        let dom = render_head();
+        assert_attr(&dom, "link[rel=canonical]", "href", SITE_URL);
     }
 
     #[test]
     fn head_contains_open_graph_tags() {
-        let html = render_head();
-        assert!(
-            html.contains("og:type"),
-            "Head should contain Open Graph type"
+        let dom = render_head();
+        for property in ["og:type", "og:title", "og:description", "og:image"] {
+            assert_exists(&dom, &format!("meta[property=\"{}\"][content]", property));
+        }
+    }
+
+    #[test]
+    fn head_links_the_oembed_document() {
+        let dom = render_head();
+        assert_attr(
+            &dom,
+            "link[rel=alternate][type='application/json+oembed']",
+            "href",
+            &format!("{}/oembed.json", SITE_URL),
         );
-        assert!(
-            html.contains("og:title"),
-            "Head should contain Open Graph title"
+    }
+
+    #[test]
+    fn homepage_is_a_profile() {
+        let dom = render_head();
+        assert_attr(&dom, "meta[property='og:type']", "content", "profile");
+        assert!(!dom.exists("meta[property^='music:'], meta[property^='article:']"));
+    }
+
+    #[test]
+    fn og_types_carry_their_properties() {
+        let head = |og_type: OgType| {
+            parse(&generate_head_html_for(&PageMeta {
+                title: "T".to_string(),
+                description: "D".to_string(),
+                canonical_url: "https://a.test/".to_string(),
+                og_type,
+                og_image: None,
+                json_ld: "{}".to_string(),
+                alternates: Vec::new(),
+                pagination: None,
+                noindex: false,
+                hints: Vec::new(),
+                speculation: SpeculationRules::default(),
+            }))
+        };
+        let album = head(OgType::MusicAlbum {
+            musician: "https://a.test/".to_string(),
+            release_date: "2025-03-07".to_string(),
+        });
+        assert_attr(&album, "meta[property='og:type']", "content", "music.album");
+        assert_attr(
+            &album,
+            "meta[property='music:musician']",
+            "content",
+            "https://a.test/",
         );
-        assert!(
-            html.contains("og:description"),
-            "Head should contain Open Graph description"
+        assert_attr(
+            &album,
+            "meta[property='music:release_date']",
+            "content",
+            "2025-03-07",
         );
-        assert!(
-            html.contains("og:image"),
-            "Head should contain Open Graph image"
+
+        assert_attr(&album, "meta[name='dc.date']", "content", "2025-03-07");
+
+        let article = head(OgType::Article {
+            published_time: "2025-01-01".to_string(),
+            author: "https://a.test/".to_string(),
+        });
+        assert_attr(&article, "meta[name='dc.date']", "content", "2025-01-01");
+        assert_attr(&article, "meta[property='og:type']", "content", "article");
+        assert_attr(
+            &article,
+            "meta[property='article:published_time']",
+            "content",
+            "2025-01-01",
+        );
+        assert_attr(
+            &article,
+            "meta[property='article:author']",
+            "content",
+            "https://a.test/",
         );
     }
 
     #[test]
     fn head_contains_twitter_card_tags() {
-        let html = render_head();
-        assert!(
-            html.contains("twitter:card"),
-            "Head should contain Twitter card type"
-        );
-        assert!(
-            html.contains("twitter:title"),
-            "Head should contain Twitter title"
+        let dom = render_head();
+        assert_exists(&dom, "meta[name=\"twitter:card\"]");
+        assert_exists(&dom, "meta[name=\"twitter:title\"]");
+    }
+
+    #[test]
+    fn head_contains_dublin_core_tags() {
+        let dom = render_head();
+        assert_exists(&dom, "head > link[rel='schema.DC']");
+        assert_attr(&dom, "meta[name='dc.creator']", "content", SITE_NAME);
+        assert_attr(
+            &dom,
+            "meta[name='dc.title']",
+            "content",
+            &dom.text("title").unwrap(),
         );
+        assert_attr(&dom, "meta[name='dc.rights']", "content", SITE_RIGHTS);
+        // The homepage is undated, so it carries the build date
+        assert_attr(&dom, "meta[name='dc.date']", "content", &BUILD_TIME[..10]);
     }
 
     #[test]
     fn head_contains_json_ld() {
-        let html = render_head();
-        assert!(
-            html.contains("application/ld+json"),
-            "Head should contain JSON-LD script"
-        );
+        let dom = render_head();
+        let script = dom.text("script[type=\"application/ld+json\"]").unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&script).is_ok());
     }
 
     #[test]
     fn json_ld_has_schema_context() {
-        let json_ld = generate_json_ld();
-        assert!(
-            json_ld.contains("\"@context\": \"https://schema.org\""),
-            "JSON-LD should have schema.org context"
-        );
+        assert_eq!(json_ld()["@context"], "https://schema.org");
     }
 
     #[test]
     fn json_ld_has_person_type() {
-        let json_ld = generate_json_ld();
-        assert!(
-            json_ld.contains("\"@type\": \"Person\""),
-            "JSON-LD should have Person type"
+        assert_eq!(primary_node()["@type"], "Person");
+    }
+
+    #[test]
+    fn json_ld_is_a_single_graph() {
+        let graph = json_ld();
+        let types: Vec<&str> = graph["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|node| node["@type"].as_str())
+            .collect();
+        assert!(types.contains(&"WebSite") && types.contains(&"WebPage"));
+        assert_eq!(
+            render_head().count("script[type=\"application/ld+json\"]"),
+            1
         );
     }
 
     #[test]
     fn json_ld_has_required_fields() {
-        let json_ld = generate_json_ld();
-        assert!(json_ld.contains("\"name\":"), "JSON-LD should have name");
-        assert!(json_ld.contains("\"url\":"), "JSON-LD should have url");
+        let json_ld = primary_node();
+        assert_eq!(json_ld["name"], SITE_NAME);
+        assert_eq!(json_ld["url"], SITE_URL);
+        assert_eq!(json_ld["description"], SITE_DESCRIPTION);
+        assert!(json_ld["image"].is_string(), "JSON-LD should have image");
         assert!(
-            json_ld.contains("\"description\":"),
-            "JSON-LD should have description"
-        );
-        assert!(json_ld.contains("\"image\":"), "JSON-LD should have image");
-        assert!(
-            json_ld.contains("\"sameAs\":"),
+            json_ld["sameAs"].is_array(),
             "JSON-LD should have sameAs array"
         );
     }
 
     #[test]
     fn head_links_stylesheet() {
-        let html = render_head();
-        assert!(
-            html.contains("rel=\"stylesheet\""),
-            "Head should link stylesheet"
+        let dom = render_head();
+        assert_attr(&dom, "link[rel=stylesheet]", "href", "/main.css");
+    }
+
+    #[test]
+    fn head_contains_favicon_ico() {
+        let dom = render_head();
+        assert_exists(&dom, "link[rel=icon][href=\"/favicon.ico\"]");
+    }
+
+    #[test]
+    fn head_contains_favicon_svg() {
+        let dom = render_head();
+        assert_attr(
+            &dom,
+            "link[rel=icon][href=\"/favicon.svg\"]",
+            "type",
+            "image/svg+xml",
         );
-        assert!(
-            html.contains("main.css"),
-            "Head should link to main.css"
+    }
+
+    #[test]
+    fn head_contains_apple_touch_icon() {
+        let dom = render_head();
+        assert_exists(&dom, "link[rel=apple-touch-icon][href]");
+    }
+
+    #[test]
+    fn head_opts_into_view_transitions() {
+        let dom = render_head();
+        assert_attr(&dom, "meta[name=view-transition]", "content", "same-origin");
+    }
+
+    #[test]
+    fn head_contains_web_manifest() {
+        let dom = render_head();
+        assert_attr(&dom, "link[rel=manifest]", "href", "/site.webmanifest");
+    }
+
+    #[test]
+    fn head_contains_theme_color() {
+        let dom = render_head();
+        assert_attr(&dom, "meta[name=theme-color]", "content", THEME_COLOR);
+    }
+
+    proptest! {
+        #[test]
+        fn page_meta_survives_special_characters(
+            title in "[ -~\u{a0}-\u{2fff}\u{1f300}-\u{1f6ff}]*",
+            description in "[ -~\u{a0}-\u{2fff}\u{1f300}-\u{1f6ff}]*",
+        ) {
+            let json_ld = format!(r#"{{"name": {}}}"#, json_string(&title));
+            let dom = parse(&generate_head_html_for(&PageMeta {
+                title: title.clone(),
+                description: description.clone(),
+                canonical_url: SITE_URL.to_string(),
+                og_type: OgType::Website,
+                og_image: None,
+                json_ld,
+                alternates: Vec::new(),
+                pagination: None,
+                noindex: false,
+                hints: Vec::new(),
+            speculation: SpeculationRules::default(),
+            }));
+            let (title, description) = (nfc(&title), nfc(&description));
+            prop_assert_eq!(dom.select("title")[0].text().collect::<String>(), title.clone());
+            prop_assert_eq!(
+                dom.attr("meta[name=description]", "content"),
+                Some(meta_description(&description))
+            );
+            prop_assert_eq!(dom.attr("meta[property=\"og:title\"]", "content"), Some(title.clone()));
+
+            let script = dom.select("script[type=\"application/ld+json\"]")[0]
+                .text()
+                .collect::<String>();
+            let parsed: serde_json::Value = serde_json::from_str(&script).unwrap();
+            prop_assert_eq!(&parsed["name"], &serde_json::Value::String(title));
+        }
+    }
+
+    #[test]
+    fn long_descriptions_are_cut_everywhere() {
+        let dom = parse(&generate_head_html_for(&PageMeta {
+            title: "T".to_string(),
+            description: "Sound and light ".repeat(20),
+            canonical_url: "https://a.test/".to_string(),
+            og_type: OgType::Website,
+            og_image: None,
+            json_ld: "{}".to_string(),
+            alternates: Vec::new(),
+            pagination: None,
+            noindex: false,
+            hints: Vec::new(),
+            speculation: SpeculationRules::default(),
+        }));
+        let description = dom.attr("meta[name=description]", "content").unwrap();
+        assert!(description.chars().count() <= DESCRIPTION_LIMIT);
+        assert!(description.ends_with(" and…"), "{}", description);
+        for tag in [
+            "meta[property=\"og:description\"]",
+            "meta[name=\"twitter:description\"]",
+        ] {
+            assert_eq!(dom.attr(tag, "content").as_ref(), Some(&description));
+        }
+    }
+
+    #[test]
+    fn head_renders_unicode_title_and_rtl_description() {
+        let dom = parse(&generate_head_html_for(&PageMeta {
+            title: "Cafe\u{301} 🎨 | EverythingSings".to_string(),
+            description: "فن رقمي — digital art".to_string(),
+            canonical_url: SITE_URL.to_string(),
+            og_type: OgType::Website,
+            og_image: None,
+            json_ld: format!(r#"{{"name": {}}}"#, json_string("Cafe\u{301} 🎨")),
+            alternates: Vec::new(),
+            pagination: None,
+            noindex: false,
+            hints: Vec::new(),
+            speculation: SpeculationRules::default(),
+        }));
+        assert_eq!(
+            dom.text("title").as_deref(),
+            Some("Caf\u{e9} 🎨 | EverythingSings")
         );
+        assert_attr(
+            &dom,
+            "meta[name=description]",
+            "content",
+            "فن رقمي — digital art",
+        );
+        let script = dom.text("script[type=\"application/ld+json\"]").unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(parsed["name"], "Caf\u{e9} 🎨");
     }
 
     #[test]
-    fn head_contains_favicon_ico() {
-        let html = render_head();
-        assert!(
-            html.contains("favicon.ico"),
-            "Head should link favicon.ico"
+    fn head_advertises_section_feeds() {
+        let dom = parse(&generate_head_html_for(&PageMeta {
+            title: "Log".to_string(),
+            description: String::new(),
+            canonical_url: format!("{}/log/", SITE_URL),
+            og_type: OgType::Website,
+            og_image: None,
+            json_ld: "{}".to_string(),
+            alternates: Vec::new(),
+            pagination: None,
+            noindex: false,
+            hints: Vec::new(),
+            speculation: SpeculationRules::default(),
+        }));
+        assert_eq!(
+            dom.count("link[rel=alternate][type=\"application/rss+xml\"]"),
+            2
+        );
+        assert_attr(
+            &dom,
+            "link[title$=Log][type=\"application/rss+xml\"]",
+            "href",
+            "/log/feed.xml",
         );
     }
 
     #[test]
-    fn head_contains_favicon_svg() {
-        let html = render_head();
-        assert!(
-            html.contains("favicon.svg"),
-            "Head should link favicon.svg"
+    fn only_unlisted_pages_are_noindex() {
+        let meta = |noindex| PageMeta {
+            title: "Draft".to_string(),
+            description: String::new(),
+            canonical_url: format!("{}/preview/0123/", SITE_URL),
+            og_type: OgType::Website,
+            og_image: None,
+            json_ld: "{}".to_string(),
+            alternates: Vec::new(),
+            pagination: None,
+            noindex,
+            hints: Vec::new(),
+            speculation: SpeculationRules::default(),
+        };
+        let dom = parse(&generate_head_html_for(&meta(true)));
+        assert_attr(&dom, "meta[name=robots]", "content", "noindex");
+        let dom = parse(&generate_head_html_for(&meta(false)));
+        assert_eq!(dom.count("meta[name=robots]"), 0);
+    }
+
+    fn paginated_head(page: usize) -> Dom {
+        parse(&generate_head_html_for(&PageMeta {
+            title: "Log".to_string(),
+            description: String::new(),
+            canonical_url: format!("{}/log/", SITE_URL),
+            og_type: OgType::Website,
+            og_image: None,
+            json_ld: "{}".to_string(),
+            alternates: Vec::new(),
+            pagination: Some(Pagination::new(&["log"], page, 3)),
+            noindex: false,
+            hints: Vec::new(),
+            speculation: SpeculationRules::default(),
+        }))
+    }
+
+    #[test]
+    fn first_listing_page_links_only_next() {
+        let dom = paginated_head(1);
+        assert_attr(
+            &dom,
+            "link[rel=canonical]",
+            "href",
+            &format!("{}/log/", SITE_URL),
         );
-        assert!(
-            html.contains("type=\"image/svg+xml\""),
-            "SVG favicon should have correct type"
+        assert!(!dom.exists("link[rel=prev]"));
+        assert_attr(
+            &dom,
+            "link[rel=next]",
+            "href",
+            &format!("{}/log/page/2/", SITE_URL),
         );
     }
 
     #[test]
-    fn head_contains_apple_touch_icon() {
-        let html = render_head();
-        assert!(
-            html.contains("apple-touch-icon"),
-            "Head should link apple-touch-icon"
+    fn middle_listing_page_is_its_own_canonical() {
+        let dom = paginated_head(2);
+        let url = format!("{}/log/page/2/", SITE_URL);
+        assert_attr(&dom, "link[rel=canonical]", "href", &url);
+        assert_attr(&dom, "meta[property='og:url']", "content", &url);
+        assert_attr(
+            &dom,
+            "link[rel=prev]",
+            "href",
+            &format!("{}/log/", SITE_URL),
+        );
+        assert_attr(
+            &dom,
+            "link[rel=next]",
+            "href",
+            &format!("{}/log/page/3/", SITE_URL),
         );
     }
 
     #[test]
-    fn head_contains_web_manifest() {
-        let html = render_head();
-        assert!(
-            html.contains("rel=\"manifest\""),
-            "Head should link web manifest"
+    fn last_listing_page_links_only_prev() {
+        let dom = paginated_head(3);
+        assert_attr(
+            &dom,
+            "link[rel=prev]",
+            "href",
+            &format!("{}/log/page/2/", SITE_URL),
         );
-        assert!(
-            html.contains("site.webmanifest"),
-            "Head should link to site.webmanifest"
+        assert!(!dom.exists("link[rel=next]"));
+        assert_eq!(dom.count("link[rel=alternate][href='/log/feed.xml']"), 1);
+    }
+
+    #[test]
+    fn head_emits_speculation_rules_only_when_set() {
+        assert!(!homepage_head().contains("speculationrules"));
+        let dom = parse(&generate_head_html_for(&PageMeta {
+            title: "Art".to_string(),
+            description: String::new(),
+            canonical_url: SITE_URL.to_string(),
+            og_type: OgType::Website,
+            og_image: None,
+            json_ld: "{}".to_string(),
+            alternates: Vec::new(),
+            pagination: None,
+            noindex: false,
+            hints: Vec::new(),
+            speculation: SpeculationRules::prefetch(["/art/a/"]),
+        }));
+        assert_exists(&dom, "head > script[type=speculationrules]");
+    }
+
+    #[test]
+    fn homepage_prefetches_generated_pages() {
+        let head = generate_head_html(&homepage_graph(), &["/cv/".to_string()]);
+        let dom = parse(&head);
+        assert_exists(&dom, "head > script[type=speculationrules]");
+        assert!(head.contains("\"/cv/\""), "{}", head);
+    }
+
+    #[test]
+    fn head_has_no_analytics_by_default() {
+        let html = homepage_head();
+        assert!(!html.contains("Content-Security-Policy"));
+        assert!(!html.contains("integrity="));
+    }
+
+    #[test]
+    fn head_names_generator_build() {
+        let dom = render_head();
+        assert_attr(
+            &dom,
+            "meta[name=generator]",
+            "content",
+            &crate::provenance::generator(),
         );
     }
 
     #[test]
-    fn head_contains_theme_color() {
-        let html = render_head();
-        assert!(
-            html.contains("name=\"theme-color\""),
-            "Head should have theme-color meta"
+    fn head_hints_external_origins_and_avatar() {
+        let dom = render_head();
+        assert!(dom.count("head > link[rel=preconnect]") > 0);
+        assert_attr(
+            &dom,
+            "link[rel=preload][as=image]",
+            "href",
+            crate::config::AVATAR_PATH,
         );
+        let html = homepage_head();
         assert!(
-            html.contains(THEME_COLOR),
-            "Theme color should match constant"
+            html.find("rel=\"preconnect\"") < html.find("rel=\"stylesheet\""),
+            "Hints should precede the stylesheet"
         );
     }
 
     #[test]
     fn head_contains_rss_feed_link() {
-        let html = render_head();
-        assert!(
-            html.contains("application/rss+xml"),
-            "Head should have RSS feed link type"
+        let dom = render_head();
+        assert_attr(
+            &dom,
+            "link[rel=alternate][type=\"application/rss+xml\"]",
+            "href",
+            "/feed.xml",
         );
-        assert!(
-            html.contains("feed.xml"),
-            "Head should link to feed.xml"
+    }
+
+    #[test]
+    fn head_links_every_site_alternate() {
+        let dom = render_head();
+        for alternate in site_alternates() {
+            assert_attr(
+                &dom,
+                &format!("link[rel=alternate][href=\"{}\"]", alternate.href),
+                "type",
+                alternate.media_type,
+            );
+        }
+    }
+
+    #[test]
+    fn head_links_profile_json() {
+        let dom = render_head();
+        assert_attr(
+            &dom,
+            "link[rel=alternate][type=\"application/json\"]",
+            "href",
+            "/api/profile.json",
         );
     }
 }
@@ -4,82 +4,490 @@
 //!
 //! ## Semantic Features
 //!
-//! - `rel="me"` on all links for IndieWeb identity verification
-//! - Schema.org `sameAs` itemprop for linked profiles
+//! - `rel="me"` on identity links for IndieWeb verification; each link's
+//!   [`RelPolicy`] can drop it or add `sponsored`/`nofollow`
+//! - Schema.org data by [`LinkKind`]: `sameAs` for other profiles, an
+//!   `Offer` for the shop, a `MusicGroup` for the music catalogue, and a
+//!   `WebSite` for sites publishing the work
 //! - Accessible list structure with proper ARIA
 //!
 //! ## Quantum Reveal Effect
 //!
 //! Descriptions exist in a "collapsed" state until observed (hover/focus),
 //! then materialize with blur-to-sharp transition via CSS.
+//!
+//! ## Booking
+//!
+//! Builds with the `booking` feature append a "Book a call" entry after the
+//! profile links. It is not an identity link, so it carries no `rel="me"`;
+//! instead it is marked up as a Schema.org `ScheduleAction`.
+//!
+//! ## Bluesky
+//!
+//! With [`ATPROTO_DID`] configured, the site's domain is the Bluesky handle,
+//! so a [`BLUESKY`] profile link joins the Connect group, before any
+//! booking entry.
+//!
+//! ## Campaign Tagging
+//!
+//! Links marked `campaign` get [`CAMPAIGN_PARAMS`] appended to their
+//! rendered `href`, so the destination can attribute the visit. The
+//! canonical `href` stays untagged everywhere else, and so do `rel="me"`
+//! links: identity checks compare their URL exactly.
+//!
+//! ## Verification
+//!
+//! Links checked by `check-links` carry a `data-verified` timestamp (see
+//! [`crate::linkcheck`]). Links archived by `archive-links` can show their
+//! Wayback Machine snapshot as a fallback (see [`crate::archive`]).
+//!
+//! ## Latest Prints
+//!
+//! When the `/prints/` page was generated (see [`crate::prints`]), the shop
+//! card links to it.
+//!
+//! ## Activity
+//!
+//! When the `/activity/` heatmap was generated (see [`crate::github`]), the
+//! GitHub card in the Build group links to it.
+//!
+//! ## Icons
+//!
+//! Each card can show its site's favicon, harvested at build time and
+//! served from `/icons/` (see [`crate::favicon`]). Icons are decorative:
+//! empty `alt`, lazily loaded.
 
+use crate::archive::ArchivedLinks;
+use crate::config::{ATPROTO_DID, CAMPAIGN_PARAMS};
+use crate::css;
+use crate::entity::primary_entity;
+use crate::favicon::Favicons;
+use crate::github::{profile_url, ACTIVITY_DIR, GITHUB_USER};
+use crate::linkcheck::VerifiedLinks;
+use crate::prints::PRINTS_DIR;
+use crate::text::append_query;
+use crate::typography::typeset;
+use crate::url::page_path;
 use leptos::prelude::*;
 
+/// The kind of activity a link leads to, used to group links in exports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkGroup {
+    Create,
+    Think,
+    Build,
+    Support,
+    Connect,
+}
+
+impl LinkGroup {
+    /// Every group, in display order.
+    pub const ALL: [LinkGroup; 5] = [
+        LinkGroup::Create,
+        LinkGroup::Think,
+        LinkGroup::Build,
+        LinkGroup::Support,
+        LinkGroup::Connect,
+    ];
+
+    /// Display name of the group.
+    pub fn label(self) -> &'static str {
+        match self {
+            LinkGroup::Create => "Create",
+            LinkGroup::Think => "Think",
+            LinkGroup::Build => "Build",
+            LinkGroup::Support => "Support",
+            LinkGroup::Connect => "Connect",
+        }
+    }
+}
+
+/// What a link points at, which decides its structured data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkKind {
+    /// Another profile of the entity (`sameAs`).
+    Profile,
+    /// A store selling the entity's work: an `Offer` it makes.
+    Store,
+    /// The entity's music catalogue: the `MusicGroup` it performs as.
+    Music,
+    /// A site publishing the entity's work: a `WebSite` it authors.
+    Website,
+    /// A scheduling page: the target of a `ScheduleAction`.
+    Schedule,
+}
+
+impl LinkKind {
+    /// Schema.org type of the linked item; `None` for plain profiles.
+    pub fn schema_type(self) -> Option<&'static str> {
+        match self {
+            LinkKind::Profile => None,
+            LinkKind::Store => Some("Offer"),
+            LinkKind::Music => Some("MusicGroup"),
+            LinkKind::Website => Some("WebSite"),
+            LinkKind::Schedule => Some("ScheduleAction"),
+        }
+    }
+}
+
 /// A single link entry with display text and URL.
 #[derive(Clone)]
 pub struct LinkEntry {
     pub label: &'static str,
     pub href: &'static str,
     pub description: Option<&'static str>,
+    pub group: LinkGroup,
+    pub kind: LinkKind,
+    pub rel: RelPolicy,
+    /// Tag the rendered link with [`CAMPAIGN_PARAMS`], unless it is an
+    /// identity link.
+    pub campaign: bool,
+}
+
+impl LinkEntry {
+    /// The `href` to render: `href` tagged with `params` when this link is
+    /// a campaign link without `rel="me"`.
+    pub fn tagged_href(&self, params: &[(&str, &str)]) -> String {
+        if self.campaign && !self.rel.me {
+            append_query(self.href, params)
+        } else {
+            self.href.to_string()
+        }
+    }
+}
+
+/// Which `rel` values a link carries. `noopener` is always added.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RelPolicy {
+    /// Claims the target as the same identity (IndieWeb `rel="me"`).
+    pub me: bool,
+    /// Paid or affiliate link.
+    pub sponsored: bool,
+    /// Not endorsed; search engines should not follow it.
+    pub nofollow: bool,
+}
+
+impl RelPolicy {
+    /// An identity link: `me noopener`.
+    pub const PROFILE: RelPolicy = RelPolicy {
+        me: true,
+        sponsored: false,
+        nofollow: false,
+    };
+
+    /// A plain outbound link: `noopener`.
+    pub const PLAIN: RelPolicy = RelPolicy {
+        me: false,
+        sponsored: false,
+        nofollow: false,
+    };
+
+    /// This policy without `me`.
+    pub const fn without_me(self) -> Self {
+        RelPolicy { me: false, ..self }
+    }
+
+    /// This policy with `sponsored`.
+    pub const fn sponsored(self) -> Self {
+        RelPolicy {
+            sponsored: true,
+            ..self
+        }
+    }
+
+    /// This policy with `nofollow`.
+    pub const fn nofollow(self) -> Self {
+        RelPolicy {
+            nofollow: true,
+            ..self
+        }
+    }
+
+    /// The `rel` values, in a stable order.
+    pub fn values(self) -> Vec<&'static str> {
+        [
+            (self.me, "me"),
+            (self.sponsored, "sponsored"),
+            (self.nofollow, "nofollow"),
+            (true, "noopener"),
+        ]
+        .into_iter()
+        .filter_map(|(on, value)| on.then_some(value))
+        .collect()
+    }
+
+    /// The space-separated `rel` attribute value.
+    pub fn attr(self) -> String {
+        self.values().join(" ")
+    }
 }
 
 /// The five canonical profile links, in display order.
 ///
 /// Intentionally short. Anything more should live on its own page or sub-domain.
-const LINKS: &[LinkEntry] = &[
+pub const LINKS: &[LinkEntry] = &[
     LinkEntry {
         label: "Shop",
         href: "https://bedim.redbubble.com",
         description: Some("AI art prints and merchandise on Redbubble"),
+        group: LinkGroup::Support,
+        kind: LinkKind::Store,
+        // A storefront sells the work; it is not another identity
+        rel: RelPolicy::PROFILE.without_me(),
+        campaign: true,
     },
     LinkEntry {
         label: "GitHub",
         href: "https://github.com/EverythingSings",
         description: Some("Code is art"),
+        group: LinkGroup::Build,
+        kind: LinkKind::Profile,
+        rel: RelPolicy::PROFILE,
+        campaign: false,
     },
     LinkEntry {
         label: "Music",
         href: "https://music.apple.com/artist/1704503690",
         description: Some("Listen on Apple Music"),
+        group: LinkGroup::Create,
+        kind: LinkKind::Music,
+        rel: RelPolicy::PROFILE,
+        campaign: false,
     },
     LinkEntry {
         label: "X",
         href: "https://x.com/everythingSung",
         description: Some("Follow on X"),
+        group: LinkGroup::Connect,
+        kind: LinkKind::Profile,
+        rel: RelPolicy::PROFILE,
+        campaign: false,
     },
     LinkEntry {
         label: "Book Reviews",
         href: "https://books.everythingsings.art",
         description: Some("A personal reading journal — 100+ reviews"),
+        group: LinkGroup::Think,
+        kind: LinkKind::Website,
+        rel: RelPolicy::PROFILE,
+        campaign: false,
     },
 ];
 
-fn render_link(link: &LinkEntry) -> impl IntoView {
+/// The Bluesky profile under the domain handle, rendered when
+/// [`ATPROTO_DID`] is configured.
+pub const BLUESKY: LinkEntry = LinkEntry {
+    label: "Bluesky",
+    href: "https://bsky.app/profile/everythingsings.art",
+    description: Some("Follow on Bluesky"),
+    group: LinkGroup::Connect,
+    kind: LinkKind::Profile,
+    rel: RelPolicy::PROFILE,
+    campaign: false,
+};
+
+/// The scheduling link, rendered when [`BOOKING_ENABLED`].
+pub const BOOKING: LinkEntry = LinkEntry {
+    label: "Book a call",
+    href: "https://cal.com/everythingsings",
+    description: Some("Schedule a conversation"),
+    group: LinkGroup::Connect,
+    kind: LinkKind::Schedule,
+    rel: RelPolicy::PLAIN,
+    campaign: false,
+};
+
+/// Whether this build shows the [`BOOKING`] entry (`--features booking`).
+pub const BOOKING_ENABLED: bool = cfg!(feature = "booking");
+
+/// Every link shown on the homepage, in display order.
+pub fn rendered_links() -> Vec<&'static LinkEntry> {
+    let mut links: Vec<&LinkEntry> = LINKS.iter().collect();
+    if ATPROTO_DID.is_some() {
+        links.push(&BLUESKY);
+    }
+    if BOOKING_ENABLED {
+        links.push(&BOOKING);
+    }
+    links
+}
+
+/// Verification, archive, and icon state of one link, and whether it
+/// links the prints or activity page.
+#[derive(Default)]
+struct LinkStatus {
+    verified: Option<String>,
+    snapshot: Option<String>,
+    icon: Option<String>,
+    prints: bool,
+    activity: bool,
+}
+
+fn render_link(link: &LinkEntry, status: LinkStatus) -> AnyView {
+    match link.kind {
+        LinkKind::Profile => render_profile(link, status).into_any(),
+        LinkKind::Schedule => render_booking(link, status).into_any(),
+        _ => render_item(link, status).into_any(),
+    }
+}
+
+/// The self-hosted favicon shown at the start of a link's label.
+fn render_icon(icon: Option<String>) -> impl IntoView {
+    icon.map(|src| {
+        view! {
+            <img
+                class=css::LINK_ICON
+                src=src
+                alt=""
+                width="16"
+                height="16"
+                loading="lazy"
+                decoding="async"
+            />
+        }
+    })
+}
+
+/// The Wayback Machine fallback for a link, shown beside the card.
+fn render_snapshot(link: &LinkEntry, snapshot: Option<String>) -> impl IntoView {
+    snapshot.map(|href| {
+        view! {
+            <a
+                href=href
+                class=css::LINK_ARCHIVE
+                rel="nofollow noopener"
+                aria-label=format!("Archived copy of {}", link.label)
+            >
+                "Archived copy"
+            </a>
+        }
+    })
+}
+
+fn render_profile(link: &LinkEntry, status: LinkStatus) -> impl IntoView {
     view! {
-        <li class="link-item">
+        <li class=css::LINK_ITEM>
             <a
-                href=link.href
-                rel="me noopener"
+                href=link.tagged_href(CAMPAIGN_PARAMS)
+                rel=link.rel.attr()
+                data-verified=status.verified
                 itemprop="sameAs"
-                class="link-card"
+                class=css::LINK_CARD
                 title=link.description.unwrap_or(link.label)
             >
-                <span class="link-label">{link.label}</span>
+                <span class=css::LINK_LABEL>{render_icon(status.icon)}{link.label}</span>
                 {link.description.map(|desc| {
-                    view! { <span class="link-description">{desc}</span> }
+                    view! { <span class=css::LINK_DESCRIPTION>{typeset(desc)}</span> }
                 })}
             </a>
+            {render_snapshot(link, status.snapshot)}
+            {status.activity.then(|| view! {
+                <a href=page_path(&[ACTIVITY_DIR]) class=css::LINK_MORE>"Activity"</a>
+            })}
+        </li>
+    }
+}
+
+/// A link to something other than a profile, as a `mentions` item of the
+/// page, attributed to the primary entity where the type allows.
+fn render_item(link: &LinkEntry, status: LinkStatus) -> impl IntoView {
+    let entity = primary_entity();
+    let itemtype = format!(
+        "https://schema.org/{}",
+        link.kind.schema_type().unwrap_or("Thing")
+    );
+    // A music group is named after the artist, not the link label
+    let (label_prop, group_name) = match link.kind {
+        LinkKind::Music => (None, Some(entity.name)),
+        _ => (Some("name"), None),
+    };
+    view! {
+        <li class=css::LINK_ITEM itemprop="mentions" itemscope itemtype=itemtype>
+            {group_name.map(|name| view! { <meta itemprop="name" content=name /> })}
+            {(link.kind == LinkKind::Website)
+                .then(|| view! { <link itemprop="author" href=entity.id() /> })}
+            <a
+                href=link.tagged_href(CAMPAIGN_PARAMS)
+                rel=link.rel.attr()
+                data-verified=status.verified
+                itemprop="url"
+                class=css::LINK_CARD
+                title=link.description.unwrap_or(link.label)
+            >
+                <span class=css::LINK_LABEL itemprop=label_prop>
+                    {render_icon(status.icon)}{link.label}
+                </span>
+                {link.description.map(|desc| {
+                    view! { <span class=css::LINK_DESCRIPTION itemprop="description">{desc}</span> }
+                })}
+            </a>
+            {render_snapshot(link, status.snapshot)}
+            {status.prints.then(|| view! {
+                <a href=page_path(&[PRINTS_DIR]) class=css::LINK_MORE>"Latest prints"</a>
+            })}
+        </li>
+    }
+}
+
+fn render_booking(link: &LinkEntry, status: LinkStatus) -> impl IntoView {
+    view! {
+        <li
+            class=css::LINK_ITEM
+            itemprop="potentialAction"
+            itemscope
+            itemtype="https://schema.org/ScheduleAction"
+        >
+            <a
+                href=link.tagged_href(CAMPAIGN_PARAMS)
+                rel=link.rel.attr()
+                data-verified=status.verified
+                itemprop="target"
+                class=css::LINK_CARD
+                title=link.description.unwrap_or(link.label)
+            >
+                <span class=css::LINK_LABEL itemprop="name">
+                    {render_icon(status.icon)}{link.label}
+                </span>
+                {link.description.map(|desc| {
+                    view! { <span class=css::LINK_DESCRIPTION itemprop="description">{desc}</span> }
+                })}
+            </a>
+            {render_snapshot(link, status.snapshot)}
         </li>
     }
 }
 
 /// The link list component.
+///
+/// `verified` supplies the `data-verified` timestamps, `archived` the
+/// snapshot fallbacks, and `icons` the favicons; links missing from any of
+/// them omit it. `prints` links the shop card to the prints page, and
+/// `activity` the GitHub card to the activity page.
 #[component]
-pub fn LinkList() -> impl IntoView {
+pub fn LinkList(
+    #[prop(optional)] verified: VerifiedLinks,
+    #[prop(optional)] archived: ArchivedLinks,
+    #[prop(optional)] icons: Favicons,
+    #[prop(optional)] prints: bool,
+    #[prop(optional)] activity: bool,
+) -> impl IntoView {
+    let github = profile_url(GITHUB_USER);
     view! {
-        <nav class="link-list" aria-label="Profile links">
+        <nav class=css::LINK_LIST aria-label="Profile links">
             <ul>
-                {LINKS.iter().map(render_link).collect::<Vec<_>>()}
+                {rendered_links()
+                    .into_iter()
+                    .map(|link| {
+                        let status = LinkStatus {
+                            verified: verified.get(link.href).map(str::to_string),
+                            snapshot: archived.get(link.href).map(|s| s.snapshot.clone()),
+                            icon: icons.get(link.href).map(str::to_string),
+                            prints: prints && link.kind == LinkKind::Store,
+                            activity: activity && link.href == github,
+                        };
+                        render_link(link, status)
+                    })
+                    .collect::<Vec<_>>()}
             </ul>
         </nav>
     }
@@ -88,27 +496,32 @@ pub fn LinkList() -> impl IntoView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::archive::Snapshot;
+    use crate::test_utils::{assert_attr, parse, Dom};
 
-    fn render_list() -> String {
-        LinkList().to_html()
+    fn render_list() -> Dom {
+        parse(&view! { <LinkList /> }.to_html())
     }
 
     #[test]
     fn list_is_nav_element() {
-        let html = render_list();
-        assert!(html.contains("<nav"));
+        let dom = render_list();
+        assert_eq!(dom.count("nav.link-list"), 1);
     }
 
     #[test]
     fn list_has_aria_label() {
-        let html = render_list();
-        assert!(html.contains("aria-label="));
+        let dom = render_list();
+        assert_attr(&dom, "nav.link-list", "aria-label", "Profile links");
     }
 
     #[test]
     fn list_uses_ul_element() {
-        let html = render_list();
-        assert!(html.contains("<ul>"));
+        let dom = render_list();
+        assert_eq!(
+            dom.count("nav.link-list > ul > li.link-item"),
+            LINKS.len() + BOOKING_ENABLED as usize
+        );
     }
 
     #[test]
@@ -118,40 +531,178 @@ mod tests {
 
     #[test]
     fn links_have_card_class() {
-        let html = render_list();
-        assert!(html.contains("link-card"));
+        let dom = render_list();
+        assert_eq!(
+            dom.count("li.link-item > a.link-card"),
+            rendered_links().len()
+        );
+    }
+
+    #[test]
+    fn only_identity_links_have_rel_me() {
+        let dom = render_list();
+        let identities: Vec<&str> = LINKS.iter().filter(|l| l.rel.me).map(|l| l.href).collect();
+        assert_eq!(dom.attrs("a.link-card[rel~=me]", "href"), identities);
+        assert!(!dom.exists("a[href=\"https://bedim.redbubble.com\"][rel~=me]"));
+    }
+
+    #[test]
+    fn github_links_activity_page_when_generated() {
+        let dom = parse(&view! { <LinkList activity=true /> }.to_html());
+        assert_eq!(dom.attrs(".link-more", "href"), ["/activity/"]);
+        assert_eq!(
+            dom.attrs("li:has(> .link-more) > a.link-card", "href"),
+            [profile_url(GITHUB_USER)]
+        );
+    }
+
+    #[test]
+    fn shop_links_prints_page_when_generated() {
+        assert!(!render_list().exists(".link-more"));
+        let dom = parse(&view! { <LinkList prints=true /> }.to_html());
+        assert_eq!(dom.attrs(".link-more", "href"), ["/prints/"]);
+        assert_eq!(
+            dom.count("li[itemtype=\"https://schema.org/Offer\"] > .link-more"),
+            1
+        );
+    }
+
+    #[test]
+    fn campaign_links_are_tagged_when_rendered() {
+        let params = [("ref", "everythingsings.art")];
+        let shop = &LINKS[0];
+        assert!(shop.campaign);
+        assert_eq!(
+            shop.tagged_href(&params),
+            "https://bedim.redbubble.com?ref=everythingsings.art"
+        );
+        let github = &LINKS[1];
+        assert_eq!(github.tagged_href(&params), github.href);
+    }
+
+    #[test]
+    fn identity_links_are_never_tagged() {
+        let params = [("ref", "everythingsings.art")];
+        for link in LINKS.iter().chain([&BLUESKY, &BOOKING]) {
+            assert!(!(link.rel.me && link.campaign), "{} is rel=me", link.label);
+        }
+        let tagged_profile = LinkEntry {
+            campaign: true,
+            ..LINKS[1].clone()
+        };
+        assert_eq!(tagged_profile.tagged_href(&params), tagged_profile.href);
+    }
+
+    #[test]
+    fn rendered_hrefs_follow_campaign_config() {
+        let dom = render_list();
+        let hrefs: Vec<String> = LINKS
+            .iter()
+            .map(|l| l.tagged_href(CAMPAIGN_PARAMS))
+            .collect();
+        assert_eq!(dom.attrs("a.link-card", "href")[..LINKS.len()], hrefs);
+    }
+
+    #[test]
+    fn rel_policy_composes_values() {
+        assert_eq!(RelPolicy::PROFILE.attr(), "me noopener");
+        assert_eq!(RelPolicy::PLAIN.attr(), "noopener");
+        assert_eq!(RelPolicy::PROFILE.without_me().attr(), "noopener");
+        assert_eq!(RelPolicy::PLAIN.sponsored().attr(), "sponsored noopener");
+        assert_eq!(RelPolicy::PLAIN.nofollow().attr(), "nofollow noopener");
+        assert_eq!(
+            RelPolicy::PROFILE.sponsored().nofollow().attr(),
+            "me sponsored nofollow noopener"
+        );
+        assert_eq!(
+            RelPolicy::PROFILE.nofollow().without_me().attr(),
+            "nofollow noopener"
+        );
     }
 
     #[test]
     fn links_have_rel_me() {
-        let html = render_list();
-        assert!(html.contains("rel=\"me"));
+        let dom = render_list();
+        for rel in dom.attrs("a.link-card[itemprop=sameAs]", "rel") {
+            assert!(rel.split(' ').any(|r| r == "me"), "rel={:?}", rel);
+        }
     }
 
     #[test]
     fn links_have_noopener() {
-        let html = render_list();
-        assert!(html.contains("noopener"));
+        let dom = render_list();
+        for rel in dom.attrs("a.link-card", "rel") {
+            assert!(rel.split(' ').any(|r| r == "noopener"), "rel={:?}", rel);
+        }
+    }
+
+    #[test]
+    fn only_profiles_have_sameas_itemprop() {
+        let dom = render_list();
+        let profiles: Vec<&str> = LINKS
+            .iter()
+            .filter(|l| l.kind == LinkKind::Profile)
+            .map(|l| l.href)
+            .collect();
+        assert_eq!(dom.attrs("a.link-card[itemprop=sameAs]", "href"), profiles);
+    }
+
+    #[test]
+    fn shop_is_an_offer() {
+        let dom = render_list();
+        assert_attr(
+            &dom,
+            "li[itemprop=mentions][itemtype=\"https://schema.org/Offer\"] > a[itemprop=url]",
+            "href",
+            "https://bedim.redbubble.com",
+        );
+        assert_eq!(
+            dom.text("[itemtype=\"https://schema.org/Offer\"] [itemprop=name]")
+                .as_deref(),
+            Some("Shop")
+        );
     }
 
     #[test]
-    fn links_have_sameas_itemprop() {
-        let html = render_list();
-        assert!(html.contains("itemprop=\"sameAs\""));
+    fn music_is_the_entitys_music_group() {
+        let dom = render_list();
+        let group = "li[itemtype=\"https://schema.org/MusicGroup\"]";
+        assert_attr(
+            &dom,
+            &format!("{} > meta[itemprop=name]", group),
+            "content",
+            primary_entity().name,
+        );
+        assert!(!dom.exists(&format!("{} .link-label[itemprop]", group)));
+    }
+
+    #[test]
+    fn websites_credit_the_entity_as_author() {
+        let dom = render_list();
+        assert_attr(
+            &dom,
+            "li[itemtype=\"https://schema.org/WebSite\"] > link[itemprop=author]",
+            "href",
+            &primary_entity().id(),
+        );
     }
 
     #[test]
     fn links_have_title_attribute() {
-        let html = render_list();
-        assert!(html.contains("title=\""));
+        let dom = render_list();
+        assert_eq!(
+            dom.attrs("a.link-card", "title").len(),
+            LINKS.len() + BOOKING_ENABLED as usize
+        );
     }
 
     #[test]
     fn links_contain_all_labels() {
-        let html = render_list();
+        let dom = render_list();
+        let labels = dom.texts("a.link-card > .link-label");
         for link in LINKS {
             assert!(
-                html.contains(link.label),
+                labels.iter().any(|l| l == link.label),
                 "Link list should contain label: {}",
                 link.label
             );
@@ -160,8 +711,10 @@ mod tests {
 
     #[test]
     fn book_reviews_link_present() {
-        let html = render_list();
-        assert!(html.contains("books.everythingsings.art"));
+        let dom = render_list();
+        assert!(dom
+            .attrs("a.link-card", "href")
+            .contains(&"https://books.everythingsings.art".to_string()));
     }
 
     #[test]
@@ -170,5 +723,91 @@ mod tests {
         for (i, link) in LINKS.iter().enumerate() {
             assert_eq!(link.label, expected[i]);
         }
+        let labels = render_list().texts("a.link-card > .link-label");
+        assert_eq!(labels[..LINKS.len()], expected);
+    }
+
+    #[test]
+    fn verified_links_carry_timestamps() {
+        let mut verified = VerifiedLinks::default();
+        verified.record(LINKS[1].href, "2025-01-17T12:00:00Z".to_string());
+        let dom = parse(&view! { <LinkList verified=verified /> }.to_html());
+        assert_eq!(
+            dom.attrs("a.link-card[data-verified]", "href"),
+            [LINKS[1].href]
+        );
+        assert_attr(
+            &dom,
+            "a[data-verified]",
+            "data-verified",
+            "2025-01-17T12:00:00Z",
+        );
+    }
+
+    #[test]
+    fn archived_links_offer_snapshot_fallback() {
+        let mut archived = ArchivedLinks::default();
+        archived.record(
+            LINKS[0].href,
+            Snapshot {
+                snapshot: "https://web.archive.org/web/1/https://bedim.redbubble.com".to_string(),
+                archived_at: "2025-01-17T12:00:00Z".to_string(),
+            },
+        );
+        let dom = parse(&view! { <LinkList archived=archived /> }.to_html());
+        assert_eq!(dom.count("li.link-item > a.link-archive"), 1);
+        assert_attr(
+            &dom,
+            "a.link-archive",
+            "href",
+            "https://web.archive.org/web/1/https://bedim.redbubble.com",
+        );
+        assert_attr(
+            &dom,
+            "a.link-archive",
+            "aria-label",
+            "Archived copy of Shop",
+        );
+        assert!(!render_list().exists("a.link-archive"));
+    }
+
+    #[test]
+    fn harvested_icons_lead_labels() {
+        let mut icons = Favicons::default();
+        icons.insert(LINKS[1].href, "/icons/github.com.png".to_string());
+        let dom = parse(&view! { <LinkList icons=icons /> }.to_html());
+        assert_eq!(
+            dom.count("a.link-card > .link-label > img.link-icon:first-child"),
+            1
+        );
+        assert_attr(&dom, "img.link-icon", "src", "/icons/github.com.png");
+        assert_attr(&dom, "img.link-icon", "alt", "");
+        assert!(!render_list().exists("img.link-icon"));
+    }
+
+    #[test]
+    fn booking_is_a_schedule_action() {
+        let dom = parse(&render_booking(&BOOKING, LinkStatus::default()).to_html());
+        assert_attr(
+            &dom,
+            "li[itemprop=potentialAction]",
+            "itemtype",
+            "https://schema.org/ScheduleAction",
+        );
+        assert_attr(&dom, "a[itemprop=target]", "href", BOOKING.href);
+        assert_eq!(dom.text("[itemprop=name]").as_deref(), Some("Book a call"));
+    }
+
+    #[test]
+    fn booking_is_not_an_identity_link() {
+        let dom = parse(&render_booking(&BOOKING, LinkStatus::default()).to_html());
+        assert_attr(&dom, "a.link-card", "rel", "noopener");
+        assert!(!dom.exists("[itemprop=sameAs]"));
+    }
+
+    #[test]
+    fn booking_follows_build_feature() {
+        let dom = render_list();
+        assert_eq!(dom.exists("[itemprop=potentialAction]"), BOOKING_ENABLED);
     }
 }
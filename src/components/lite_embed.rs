@@ -0,0 +1,109 @@
+//! # Lite Embed Component
+//!
+//! A click-to-load frame for a YouTube video or a Bandcamp or Apple Music
+//! release (see [`crate::embeds`]). The frame starts as a self-hosted
+//! thumbnail; the provider's player loads only once it is clicked. A
+//! caption links to the work on the provider's site for visitors who never
+//! load the player. Uses Schema.org `VideoObject`, `MusicAlbum`, or
+//! `MusicRecording` microdata.
+
+use crate::css;
+use crate::embeds::Embed;
+use leptos::prelude::*;
+
+/// A single lite embed.
+#[component]
+pub fn LiteEmbed(embed: Embed) -> impl IntoView {
+    let (width, height) = embed.provider.frame_size();
+    let srcdoc = embed.srcdoc();
+    view! {
+        <figure
+            class=css::LITE_EMBED
+            itemscope
+            itemtype=format!("https://schema.org/{}", embed.schema_type())
+        >
+            <iframe
+                title=format!("{} on {}", embed.title, embed.provider.name())
+                srcdoc=srcdoc
+                width=width
+                height=height
+                allow="autoplay; encrypted-media; fullscreen; picture-in-picture"
+            ></iframe>
+            <link itemprop="embedUrl" href=embed.player.clone() />
+            {embed
+                .thumbnail
+                .clone()
+                .map(|t| view! { <link itemprop="thumbnailUrl" href=t /> })}
+            <figcaption>
+                <a href=embed.url.clone() itemprop="url" rel="noopener">
+                    <span itemprop="name">{embed.title.clone()}</span>
+                </a>
+                {format!(" on {}", embed.provider.name())}
+            </figcaption>
+        </figure>
+    }
+}
+
+/// The lite embeds of a page; renders nothing when there are none.
+#[component]
+pub fn LiteEmbeds(embeds: Vec<Embed>) -> impl IntoView {
+    (!embeds.is_empty()).then(|| {
+        view! {
+            <div class=css::EMBEDS>
+                {embeds
+                    .into_iter()
+                    .map(|embed| view! { <LiteEmbed embed=embed /> })
+                    .collect::<Vec<_>>()}
+            </div>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeds::tests::sample_embed;
+    use crate::test_utils::{assert_attr, parse};
+
+    fn render() -> String {
+        view! { <LiteEmbed embed=sample_embed() /> }.to_html()
+    }
+
+    #[test]
+    fn frame_loads_nothing_from_the_provider() {
+        let dom = parse(&render());
+        assert!(!dom.exists("iframe[src]"));
+        assert!(!dom.exists("script"));
+        let srcdoc = dom.attrs("iframe", "srcdoc");
+        assert!(srcdoc[0].contains("href=\"https://www.youtube-nocookie.com/embed/abc123"));
+        assert!(srcdoc[0].contains("/embeds/0011223344556677.jpg"));
+        assert_attr(&dom, "iframe", "title", "Neon <Live> on YouTube");
+    }
+
+    #[test]
+    fn caption_links_to_the_work() {
+        let dom = parse(&render());
+        assert_attr(
+            &dom,
+            "figure.lite-embed",
+            "itemtype",
+            "https://schema.org/VideoObject",
+        );
+        assert_attr(
+            &dom,
+            "figcaption > a[itemprop=url]",
+            "href",
+            "https://www.youtube.com/watch?v=abc123",
+        );
+        assert_eq!(
+            dom.text("figcaption").as_deref(),
+            Some("Neon <Live> on YouTube")
+        );
+    }
+
+    #[test]
+    fn no_embeds_render_nothing() {
+        let dom = parse(&view! { <LiteEmbeds embeds=Vec::new() /> }.to_html());
+        assert!(!dom.exists("div"));
+    }
+}
@@ -0,0 +1,147 @@
+//! # Log Page Component
+//!
+//! Renders `/log/` from a [`MediaLog`]. Each entry is a Microformats2
+//! `h-review` of an `h-cite`, and a Schema.org `Book`, `Movie`, or
+//! `MusicAlbum` with a nested `Review` and `Rating`.
+
+use crate::components::{Footer, Nav, Time};
+use crate::css;
+use crate::media::{stars, MediaEntry, MediaLog, BEST_RATING, LOG_DESCRIPTION};
+use leptos::prelude::*;
+
+fn render_rating(rating: u8) -> impl IntoView {
+    let label = format!("{} out of {} stars", rating, BEST_RATING);
+    view! {
+        <p class=css::LOG_RATING itemprop="reviewRating" itemscope itemtype="https://schema.org/Rating">
+            <data class="p-rating" itemprop="ratingValue" value=rating.to_string()>
+                <span aria-hidden="true">{stars(rating)}</span>
+                <span class=css::VISUALLY_HIDDEN>{label}</span>
+            </data>
+            <meta itemprop="bestRating" content=BEST_RATING.to_string() />
+        </p>
+    }
+}
+
+fn render_entry(entry: &MediaEntry) -> impl IntoView {
+    let (creator_prop, creator_type) = entry.kind.creator();
+    let title = match &entry.url {
+        Some(url) => view! {
+            <a href=url.clone() class="p-name u-url" itemprop="name">{entry.title.clone()}</a>
+        }
+        .into_any(),
+        None => {
+            view! { <span class="p-name" itemprop="name">{entry.title.clone()}</span> }.into_any()
+        }
+    };
+    view! {
+        <article
+            id=entry.anchor()
+            aria-labelledby=format!("{}-title", entry.anchor())
+            class=css::join(&["h-review", css::LOG_ENTRY])
+            itemscope
+            itemtype=format!("https://schema.org/{}", entry.kind.schema_type())
+        >
+            <h2 id=format!("{}-title", entry.anchor()) class="p-item h-cite">
+                {title}
+                " — "
+                <span
+                    class="p-author h-card"
+                    itemprop=creator_prop
+                    itemscope
+                    itemtype=format!("https://schema.org/{}", creator_type)
+                >
+                    <span class="p-name" itemprop="name">{entry.creator.clone()}</span>
+                </span>
+                {entry.year.map(|year| view! {
+                    " (" <span itemprop="datePublished">{year.to_string()}</span> ")"
+                })}
+            </h2>
+            <div itemprop="review" itemscope itemtype="https://schema.org/Review">
+                <p class=css::LOG_META>
+                    {entry.kind.label()}
+                    " · "
+                    <Time datetime=entry.date.clone() class="dt-published" itemprop="datePublished" />
+                </p>
+                {entry.rating.map(render_rating)}
+                {(!entry.note.is_empty()).then(|| view! {
+                    <p class="e-content" itemprop="reviewBody">{entry.note.clone()}</p>
+                })}
+            </div>
+        </article>
+    }
+}
+
+/// The media log page.
+#[component]
+pub fn LogPage(log: MediaLog) -> impl IntoView {
+    view! {
+        <body itemscope itemtype="https://schema.org/CollectionPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 itemprop="name">"Log"</h1>
+                <p itemprop="description">{LOG_DESCRIPTION}</p>
+                <p>
+                    <a href="feed.xml" type="application/rss+xml">"RSS feed"</a>
+                </p>
+                {log.entries.iter().map(render_entry).collect::<Vec<_>>()}
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::tests::sample_log;
+    use crate::test_utils::{assert_attr, mf2, parse, Dom};
+
+    fn render() -> String {
+        LogPage(LogPageProps { log: sample_log() }).to_html()
+    }
+
+    fn dom() -> Dom {
+        parse(&render())
+    }
+
+    #[test]
+    fn entries_use_kind_schema_types() {
+        let dom = dom();
+        assert_attr(
+            &dom,
+            "article[id='2025-02-03-untrue']",
+            "itemtype",
+            "https://schema.org/MusicAlbum",
+        );
+        assert_attr(
+            &dom,
+            "article[id='2024-12-30-stalker'] [itemprop=director]",
+            "itemtype",
+            "https://schema.org/Person",
+        );
+    }
+
+    #[test]
+    fn ratings_are_machine_readable() {
+        let dom = dom();
+        assert_attr(&dom, "[itemprop=ratingValue]", "value", "4");
+        assert_attr(&dom, "[itemprop=bestRating]", "content", "5");
+        assert_eq!(
+            dom.count("[itemprop=reviewRating]"),
+            2,
+            "Unrated entries have no rating"
+        );
+    }
+
+    #[test]
+    fn entries_are_h_reviews() {
+        let doc = mf2::parse(&render());
+        let reviews = doc.items_of("h-review");
+        assert_eq!(reviews.len(), 3);
+        assert_eq!(reviews[1].text("content"), Some("Still the best."));
+    }
+}
@@ -0,0 +1,75 @@
+//! # Mention Form Component
+//!
+//! A plain form that sends a webmention to the configured endpoint: the
+//! visitor pastes the URL of their response and the form posts it as
+//! `source`, with the page as `target`. No script, and no IndieWeb software
+//! needed on the visitor's side.
+
+use crate::css;
+use leptos::prelude::*;
+
+/// A form sending `endpoint` a webmention of `target`. `label` names the
+/// URL field and `button` the submit button.
+#[component]
+pub fn MentionForm(
+    target: String,
+    endpoint: &'static str,
+    #[prop(default = "Your post’s URL")] label: &'static str,
+    #[prop(default = "Send webmention")] button: &'static str,
+    /// `id` of the URL field, unique on the page.
+    #[prop(default = "mention-source")]
+    id: &'static str,
+) -> impl IntoView {
+    view! {
+        <form class=css::MENTION_FORM method="post" action=endpoint>
+            <input type="hidden" name="target" value=target />
+            <label for=id>{label}</label>
+            <input id=id type="url" name="source" required placeholder="https://" />
+            <button type="submit">{button}</button>
+        </form>
+    }
+}
+
+/// A page's "Responses" section: an invitation to send a webmention with
+/// the [`MentionForm`].
+#[component]
+pub fn MentionSection(target: String, endpoint: &'static str) -> impl IntoView {
+    view! {
+        <section class=css::COMMENTS aria-labelledby="responses">
+            <h2 id="responses">"Responses"</h2>
+            <p>
+                "Have you written a response? Submit its URL to let us know."
+            </p>
+            <MentionForm target=target endpoint=endpoint button="Submit" />
+        </section>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    #[test]
+    fn section_posts_the_page_as_target() {
+        let dom = parse(
+            &view! {
+                <MentionSection
+                    target="https://a.test/art/neon/".to_string()
+                    endpoint="https://wm.test/webmention"
+                />
+            }
+            .to_html(),
+        );
+        assert_attr(&dom, "form", "action", "https://wm.test/webmention");
+        assert_attr(&dom, "form", "method", "post");
+        assert_attr(
+            &dom,
+            "input[name=target]",
+            "value",
+            "https://a.test/art/neon/",
+        );
+        assert_attr(&dom, "label", "for", "mention-source");
+        assert!(dom.exists("input#mention-source[name=source][type=url][required]"));
+    }
+}
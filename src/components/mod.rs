@@ -9,18 +9,83 @@
 //! - **Microformats2**: h-card classes for IndieWeb compatibility
 //! - **Schema.org microdata**: `itemscope`/`itemprop` attributes
 
+mod activity;
+mod apple_music;
 mod art_index;
 mod art_series;
+mod card_page;
+mod changelog_page;
+mod comments;
+mod cv_page;
+mod email;
+mod footer;
+mod guestbook_page;
 mod head;
 mod link_list;
+mod lite_embed;
+mod log_page;
+mod mention_form;
+mod music_page;
 mod nav;
+mod platform_links;
+mod post_page;
+mod posts_page;
+mod press_page;
+mod prints_page;
 mod profile_card;
+mod property_links;
+mod protected_page;
+mod reaction_counts;
+mod recent_listens;
+mod release_page;
+mod section_nav;
 mod sigil;
+mod site_page;
+mod taxonomy_page;
+mod theme_toggle;
+mod time;
+mod toc;
 
+pub use activity::{
+    ActivityPage, ActivityPageProps, ContributionHeatmap, ContributionHeatmapProps,
+};
+pub use apple_music::{AppleMusicRelease, AppleMusicReleaseProps};
 pub use art_index::{ArtIndexPage, ArtIndexPageProps};
 pub use art_series::{ArtSeriesPage, ArtSeriesPageProps};
-pub use head::{generate_head_html, generate_head_html_for, Head, PageMeta};
-pub use link_list::LinkList;
+pub use card_page::{CardPage, CardPageProps};
+pub use changelog_page::{ChangelogPage, ChangelogPageProps};
+pub use comments::{CommentList, CommentListProps};
+pub use cv_page::{CvPage, CvPageProps};
+pub use email::{obfuscate_email, spoken_email, Email, EmailProps};
+pub use footer::{Footer, FooterProps};
+pub use guestbook_page::{GuestbookPage, GuestbookPageProps};
+pub use head::{generate_head_html, generate_head_html_for, Head, PageMeta, NOINDEX_META};
+pub use link_list::{
+    rendered_links, LinkEntry, LinkGroup, LinkKind, LinkList, RelPolicy, BLUESKY, BOOKING,
+    BOOKING_ENABLED, LINKS,
+};
+pub use lite_embed::{LiteEmbed, LiteEmbedProps, LiteEmbeds, LiteEmbedsProps};
+pub use log_page::{LogPage, LogPageProps};
+pub use mention_form::{MentionForm, MentionFormProps, MentionSection, MentionSectionProps};
+pub use music_page::{MusicPage, MusicPageProps};
 pub use nav::Nav;
-pub use profile_card::ProfileCard;
+pub use platform_links::{PlatformLinks, PlatformLinksProps};
+pub use post_page::{PostPage, PostPageProps};
+pub use posts_page::{PostsPage, PostsPageProps};
+pub use press_page::{PressPage, PressPageProps};
+pub use prints_page::{PrintsPage, PrintsPageProps};
+pub use profile_card::{ProfileCard, ProfileCardProps, ProfileCards};
+pub use property_links::{PropertyLinks, PropertyLinksProps};
+pub use protected_page::{ProtectedPage, ProtectedPageProps, UNLOCK_SCRIPT};
+pub use reaction_counts::{ReactionCounts, ReactionCountsProps};
+pub use recent_listens::{RecentListens, RecentListensProps};
+pub use release_page::{ReleasePage, ReleasePageProps};
+pub use section_nav::{Section, SectionNav, SectionNavProps, LINKS_SECTION, LISTENS_SECTION};
 pub use sigil::SigilPage;
+pub use site_page::{SitePage, SitePageProps};
+pub use taxonomy_page::{
+    TaxonomyPage, TaxonomyPageProps, TermLinks, TermLinksProps, TermPage, TermPageProps,
+};
+pub use theme_toggle::ThemeToggle;
+pub use time::{Time, TimeProps};
+pub use toc::{toc_tree, Toc, TocEntry, TocProps};
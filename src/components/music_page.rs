@@ -0,0 +1,109 @@
+//! # Music Page Component
+//!
+//! Renders `/music/` from the [`Releases`] discography. Each release is a
+//! Microformats2 `h-cite` and a Schema.org `MusicAlbum` linking to its
+//! release page, followed by its [platform links](PlatformLinks).
+
+use crate::components::{Footer, Nav, PlatformLinks, Time};
+use crate::css;
+use crate::music::{Release, Releases, MUSIC_DESCRIPTION};
+use leptos::prelude::*;
+
+fn render_release(release: &Release) -> impl IntoView {
+    let listen = release.links.is_empty().then(|| {
+        view! {
+            <p>
+                <a href=release.url.clone() class="u-url" itemprop="sameAs" rel="noopener">"Listen"</a>
+            </p>
+        }
+    });
+    view! {
+        <article
+            class=css::join(&["h-cite", css::RELEASE])
+            aria-labelledby=format!("release-{}", release.slug())
+            itemscope
+            itemtype="https://schema.org/MusicAlbum"
+        >
+            <h2 id=format!("release-{}", release.slug())>
+                <a href=release.url_path() class="p-name u-url" itemprop="url">
+                    <span itemprop="name">{release.title.clone()}</span>
+                </a>
+            </h2>
+            <p class=css::RELEASE_META>
+                <Time datetime=release.date.clone() class="dt-published" itemprop="datePublished" />
+            </p>
+            <PlatformLinks links=release.links.clone() />
+            {listen}
+        </article>
+    }
+}
+
+/// The music page.
+#[component]
+pub fn MusicPage(releases: Releases) -> impl IntoView {
+    view! {
+        <body itemscope itemtype="https://schema.org/CollectionPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 itemprop="name">"Music"</h1>
+                <p itemprop="description">{MUSIC_DESCRIPTION}</p>
+                {releases.releases.iter().map(render_release).collect::<Vec<_>>()}
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::music::tests::sample_releases;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render() -> String {
+        MusicPage(MusicPageProps {
+            releases: sample_releases(),
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn releases_are_music_album_h_cites() {
+        let html = render();
+        let doc = mf2::parse(&html);
+        let cites = doc.items_of("h-cite");
+        assert_eq!(cites.len(), 2);
+        assert_eq!(cites[0].text("name"), Some("Dawn"));
+        let dom = parse(&html);
+        assert_eq!(
+            dom.count("article.release[itemtype=\"https://schema.org/MusicAlbum\"]"),
+            2
+        );
+        assert_attr(&dom, "article time", "datetime", "2025-03-07");
+    }
+
+    #[test]
+    fn titles_link_to_release_pages() {
+        let dom = parse(&render());
+        assert_eq!(
+            dom.attrs("article h2 > a", "href"),
+            ["/music/dawn/", "/music/neon/"]
+        );
+    }
+
+    #[test]
+    fn unresolved_releases_link_to_their_own_url() {
+        let dom = parse(&render());
+        assert_eq!(dom.count("article:first-of-type ul.platform-links li"), 2);
+        assert_attr(
+            &dom,
+            "article:last-of-type p > a[itemprop=sameAs]",
+            "href",
+            "https://es.bandcamp.com/album/neon",
+        );
+    }
+}
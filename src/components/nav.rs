@@ -3,13 +3,14 @@
 //! Minimal site-wide nav — just the home link.
 
 use crate::config::SITE_NAME;
+use crate::css;
 use leptos::prelude::*;
 
 #[component]
 pub fn Nav() -> impl IntoView {
     view! {
-        <nav class="site-nav" aria-label="Site navigation">
-            <a href="/" class="site-nav-home">{SITE_NAME}</a>
+        <nav class=css::SITE_NAV aria-label="Site navigation">
+            <a href="/" class=css::SITE_NAV_HOME>{SITE_NAME}</a>
         </nav>
     }
 }
@@ -17,20 +18,17 @@ pub fn Nav() -> impl IntoView {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn render_nav() -> String {
-        Nav().to_html()
-    }
+    use crate::test_utils::{assert_attr, parse};
 
     #[test]
     fn nav_has_aria_label() {
-        let html = render_nav();
-        assert!(html.contains("aria-label=\"Site navigation\""));
+        let dom = parse(&Nav().to_html());
+        assert_attr(&dom, "nav", "aria-label", "Site navigation");
     }
 
     #[test]
     fn nav_has_home_link() {
-        let html = render_nav();
-        assert!(html.contains("href=\"/\""));
+        let dom = parse(&Nav().to_html());
+        assert_attr(&dom, "nav > a.site-nav-home", "href", "/");
     }
 }
@@ -0,0 +1,53 @@
+//! # Platform Links Component
+//!
+//! The row of links to a release on each streaming and store platform
+//! (see [`crate::odesli`]). Each link is one of the release's Microformats2
+//! `u-url`s and a Schema.org `sameAs`.
+
+use crate::css;
+use crate::odesli::PlatformLink;
+use leptos::prelude::*;
+
+/// The platform row; renders nothing when there are no links.
+#[component]
+pub fn PlatformLinks(links: Vec<PlatformLink>) -> impl IntoView {
+    (!links.is_empty()).then(|| {
+        view! {
+            <ul class=css::PLATFORM_LINKS aria-label="Listen on">
+                {links
+                    .into_iter()
+                    .map(|link| view! {
+                        <li>
+                            <a href=link.url class="u-url" itemprop="sameAs" rel="noopener">
+                                {link.platform}
+                            </a>
+                        </li>
+                    })
+                    .collect::<Vec<_>>()}
+            </ul>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::music::tests::sample_releases;
+    use crate::test_utils::parse;
+
+    #[test]
+    fn links_keep_platform_order() {
+        let links = sample_releases().releases[0].links.clone();
+        let dom = parse(&view! { <PlatformLinks links=links /> }.to_html());
+        assert_eq!(
+            dom.texts("ul.platform-links a[itemprop=sameAs]"),
+            ["Apple Music", "Spotify"]
+        );
+    }
+
+    #[test]
+    fn no_links_render_nothing() {
+        let dom = parse(&view! { <PlatformLinks links=Vec::new() /> }.to_html());
+        assert!(!dom.exists("ul"));
+    }
+}
@@ -0,0 +1,81 @@
+//! # Post Page Component
+//!
+//! Renders a [post](crate::posts) at its permalink: title, date, and the
+//! Markdown body as HTML, with [links to its terms](TermLinks) and, when
+//! the post asks for one, a [table of contents](Toc). The page is a
+//! Microformats2 `h-entry` and a Schema.org `BlogPosting`.
+
+use crate::components::{Footer, Nav, TermLinks, Time, Toc};
+use crate::css;
+use crate::posts::PostEntry;
+use leptos::prelude::*;
+
+/// The post page, linking back to the listing at `listing`.
+#[component]
+pub fn PostPage(post: PostEntry, listing: String, listing_title: &'static str) -> impl IntoView {
+    view! {
+        <body class="h-entry" itemscope itemtype="https://schema.org/BlogPosting">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <a href=listing class=css::BACK_LINK>{format!("\u{2190} All {}", listing_title)}</a>
+                <article aria-labelledby="post-title">
+                    <header>
+                        <h1 id="post-title" class="p-name" itemprop="headline">{post.meta.title.clone()}</h1>
+                        <p class=css::POST_META>
+                            <Time datetime=post.meta.date.clone() class="dt-published" itemprop="datePublished" />
+                        </p>
+                        <TermLinks terms=post.meta.taxonomies.clone() />
+                    </header>
+                    <Toc headings=post.toc() />
+                    <div class=css::join(&["e-content", css::POST_BODY]) itemprop="articleBody" inner_html=post.body_html()></div>
+                </article>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::POSTS;
+    use crate::posts::tests::sample_posts;
+    use crate::test_utils::{assert_attr, assert_outline, mf2, parse};
+
+    fn render() -> String {
+        PostPage(PostPageProps {
+            post: sample_posts().entries[1].clone(),
+            listing: POSTS.listing_path(),
+            listing_title: POSTS.title,
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn post_is_an_h_entry() {
+        let doc = mf2::parse(&render());
+        let entry = doc.items_of("h-entry")[0];
+        assert_eq!(entry.text("name"), Some("Hello, world"));
+        assert_eq!(entry.text("published"), Some("2025-03-01"));
+        assert_eq!(entry.text("category"), Some("ink"));
+    }
+
+    #[test]
+    fn body_is_rendered_markdown() {
+        let html = render();
+        let dom = parse(&html);
+        assert_attr(&dom, "[itemprop=datePublished]", "datetime", "2025-03-01");
+        assert!(html.contains("<p>First <em>post</em>.<sup"), "{}", html);
+        assert!(dom.exists("[role=doc-endnotes] [role=doc-footnote]"));
+        assert_eq!(
+            dom.attrs("nav[aria-labelledby] a", "href"),
+            ["#ink", "#paper"]
+        );
+        assert!(dom.exists("h2#ink"));
+        assert_outline(&html);
+    }
+}
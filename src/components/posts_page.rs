@@ -0,0 +1,118 @@
+//! # Posts Page Component
+//!
+//! Renders one page of the [posts](crate::posts) listing, `/posts/` and
+//! `/posts/page/<n>/`. The page is a Microformats2 `h-feed` of `h-entry`
+//! summaries and a Schema.org `Blog` of `BlogPosting`s, with links to the
+//! neighbouring pages.
+
+use crate::components::{Footer, Nav, Time};
+use crate::css;
+use crate::pagination::Pagination;
+use crate::posts::PostEntry;
+use crate::typography::typeset;
+use leptos::prelude::*;
+
+fn render_summary(entry: &PostEntry) -> impl IntoView {
+    let summary = (!entry.summary.is_empty()).then(|| {
+        view! { <p class="p-summary" itemprop="description">{entry.summary.clone()}</p> }
+    });
+    view! {
+        <article
+            aria-labelledby=format!("post-{}-title", entry.slug)
+            class=css::join(&["h-entry", css::POST_SUMMARY])
+            itemprop="blogPost"
+            itemscope
+            itemtype="https://schema.org/BlogPosting"
+        >
+            <h2 id=format!("post-{}-title", entry.slug)>
+                <a href=entry.url_path() class="p-name u-url" itemprop="url">
+                    <span itemprop="headline">{entry.meta.title.clone()}</span>
+                </a>
+            </h2>
+            <p class=css::POST_META>
+                <Time datetime=entry.meta.date.clone() class="dt-published" itemprop="datePublished" />
+            </p>
+            {summary}
+        </article>
+    }
+}
+
+/// Page `pagination` of the listing at `base` (e.g. `/posts/`), showing
+/// `entries`.
+#[component]
+pub fn PostsPage(
+    entries: Vec<PostEntry>,
+    pagination: Pagination,
+    base: String,
+    title: &'static str,
+    description: &'static str,
+) -> impl IntoView {
+    let pager = (pagination.pages > 1).then(|| {
+        view! {
+            <nav class=css::PAGER aria-label="Pages">
+                {pagination.prev_path().map(|href| view! { <a href=href rel="prev">"Newer"</a> })}
+                <span>{format!("Page {} of {}", pagination.page, pagination.pages)}</span>
+                {pagination.next_path().map(|href| view! { <a href=href rel="next">"Older"</a> })}
+            </nav>
+        }
+    });
+    view! {
+        <body class="h-feed" itemscope itemtype="https://schema.org/Blog">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 class="p-name" itemprop="name">{title}</h1>
+                <p class="p-summary" itemprop="description">{typeset(description)}</p>
+                <p>
+                    <a href=format!("{}feed.xml", base) type="application/rss+xml">"RSS feed"</a>
+                </p>
+                {entries.iter().map(render_summary).collect::<Vec<_>>()}
+                {pager}
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::POSTS;
+    use crate::posts::tests::sample_posts;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render(pagination: Pagination) -> String {
+        PostsPage(PostsPageProps {
+            entries: sample_posts().entries,
+            pagination,
+            base: POSTS.listing_path(),
+            title: POSTS.title,
+            description: POSTS.description,
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn listing_is_an_h_feed_of_entries() {
+        let doc = mf2::parse(&render(Pagination::new(&[POSTS.dir], 1, 1)));
+        let feed = doc.items_of("h-feed")[0];
+        assert_eq!(feed.text("name"), Some("Posts"));
+        let entries = &feed.children;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text("name"), Some("Night walk"));
+        assert_eq!(entries[1].text("url"), Some("/posts/hello-world/"));
+    }
+
+    #[test]
+    fn pager_links_neighbouring_pages() {
+        assert!(
+            !parse(&render(Pagination::new(&[POSTS.dir], 1, 1))).exists("nav[aria-label=Pages]")
+        );
+        let dom = parse(&render(Pagination::new(&[POSTS.dir], 2, 3)));
+        assert_attr(&dom, "a[rel=prev]", "href", "/posts/");
+        assert_attr(&dom, "a[rel=next]", "href", "/posts/page/3/");
+    }
+}
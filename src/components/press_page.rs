@@ -0,0 +1,193 @@
+//! # Press Kit Page Component
+//!
+//! Renders `/press/`: short and long bio, the downloadable assets as a
+//! Schema.org `ItemList` of `MediaObject`s with their checksums, and the
+//! brand colors from [`crate::tokens`].
+
+use crate::components::{Footer, Nav};
+use crate::css;
+use crate::press::{PressFile, PressKit, PRESS_DIR};
+use crate::tokens::{Palette, DARK, LIGHT};
+use leptos::prelude::*;
+
+fn render_file(file: &PressFile) -> impl IntoView {
+    let details = match file.dimensions {
+        Some((w, h)) => format!(
+            "{}×{} · {} · {:.1} KB",
+            w,
+            h,
+            file.encoding_format,
+            file.bytes as f64 / 1024.0
+        ),
+        None => format!(
+            "{} · {:.1} KB",
+            file.encoding_format,
+            file.bytes as f64 / 1024.0
+        ),
+    };
+    let (width, height) = file
+        .dimensions
+        .map(|(w, h)| (w.to_string(), h.to_string()))
+        .unzip();
+
+    view! {
+        <li itemprop="itemListElement" itemscope itemtype="https://schema.org/MediaObject">
+            <a href=file.url_path() itemprop="contentUrl" download>
+                <span itemprop="name">{file.name.clone()}</span>
+            </a>
+            " "
+            <span class=css::PRESS_DETAILS>{details}</span>
+            <meta itemprop="encodingFormat" content=file.encoding_format />
+            {width.map(|w| view! { <meta itemprop="width" content=w /> })}
+            {height.map(|h| view! { <meta itemprop="height" content=h /> })}
+            <code class=css::PRESS_CHECKSUM itemprop="sha256">{file.sha256.clone()}</code>
+        </li>
+    }
+}
+
+fn render_palette(scheme: &'static str, palette: &Palette) -> impl IntoView {
+    view! {
+        <h3>{scheme}</h3>
+        <ul class=css::COLOR_SWATCHES>
+            {palette
+                .tokens()
+                .into_iter()
+                .map(|(name, value)| {
+                    view! {
+                        <li>
+                            <span
+                                class=css::COLOR_SWATCH
+                                style=format!("background-color: {}", value)
+                                aria-hidden="true"
+                            ></span>
+                            <code>{value}</code>
+                            " "
+                            {name}
+                        </li>
+                    }
+                })
+                .collect::<Vec<_>>()}
+        </ul>
+    }
+}
+
+/// The press kit page.
+#[component]
+pub fn PressPage(kit: PressKit, files: Vec<PressFile>) -> impl IntoView {
+    let paragraphs = kit.long_bio_paragraphs();
+
+    view! {
+        <body itemscope itemtype="https://schema.org/WebPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 itemprop="name">"Press Kit"</h1>
+                <section class=css::PRESS_SECTION aria-labelledby="press-bio">
+                    <h2 id="press-bio">"Bio"</h2>
+                    <p itemprop="description">{kit.short_bio.clone()}</p>
+                    {paragraphs.into_iter().map(|p| view! { <p>{p}</p> }).collect::<Vec<_>>()}
+                </section>
+                <section class=css::PRESS_SECTION aria-labelledby="press-assets">
+                    <h2 id="press-assets">"Assets"</h2>
+                    <ul class=css::PRESS_ASSETS itemscope itemtype="https://schema.org/ItemList">
+                        {files.iter().map(render_file).collect::<Vec<_>>()}
+                    </ul>
+                    <p>
+                        <a href=format!("/{}/SHA256SUMS", PRESS_DIR)>"SHA256SUMS"</a>
+                    </p>
+                </section>
+                <section class=css::PRESS_SECTION aria-labelledby="press-colors">
+                    <h2 id="press-colors">"Brand Colors"</h2>
+                    {render_palette("Dark", &DARK)}
+                    {render_palette("Light", &LIGHT)}
+                </section>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse, Dom};
+
+    fn sample_kit() -> (PressKit, Vec<PressFile>) {
+        let kit = PressKit {
+            short_bio: "Formless art brand.".to_string(),
+            long_bio: "One.\n\nTwo.".to_string(),
+            assets: vec![],
+        };
+        let files = vec![
+            PressFile {
+                name: "Avatar".to_string(),
+                file_name: "avatar-256.png".to_string(),
+                encoding_format: "image/png",
+                dimensions: Some((256, 256)),
+                bytes: 2048,
+                sha256: "ab".repeat(32),
+            },
+            PressFile {
+                name: "Logo".to_string(),
+                file_name: "favicon.svg".to_string(),
+                encoding_format: "image/svg+xml",
+                dimensions: None,
+                bytes: 512,
+                sha256: "cd".repeat(32),
+            },
+        ];
+        (kit, files)
+    }
+
+    fn render() -> Dom {
+        let (kit, files) = sample_kit();
+        parse(&PressPage(PressPageProps { kit, files }).to_html())
+    }
+
+    #[test]
+    fn bios_are_rendered() {
+        let dom = render();
+        assert_eq!(
+            dom.texts(".press-section:first-of-type p"),
+            ["Formless art brand.", "One.", "Two."]
+        );
+    }
+
+    #[test]
+    fn assets_are_media_objects_in_item_list() {
+        let dom = render();
+        assert_attr(
+            &dom,
+            "ul.press-assets",
+            "itemtype",
+            "https://schema.org/ItemList",
+        );
+        assert_eq!(
+            dom.count("[itemprop=itemListElement][itemtype=\"https://schema.org/MediaObject\"]"),
+            2
+        );
+        assert_eq!(
+            dom.attrs("a[itemprop=contentUrl]", "href"),
+            ["/press/avatar-256.png", "/press/favicon.svg"]
+        );
+    }
+
+    #[test]
+    fn assets_carry_checksums_and_dimensions() {
+        let dom = render();
+        assert_eq!(dom.texts("code[itemprop=sha256]")[0], "ab".repeat(32));
+        assert_eq!(dom.attrs("meta[itemprop=width]", "content"), ["256"]);
+    }
+
+    #[test]
+    fn brand_colors_come_from_tokens() {
+        let dom = render();
+        assert!(dom
+            .texts(".color-swatches code")
+            .contains(&DARK.bg.to_string()));
+        assert_eq!(dom.count(".color-swatch"), 2 * DARK.tokens().len());
+    }
+}
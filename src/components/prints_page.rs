@@ -0,0 +1,94 @@
+//! # Prints Page Component
+//!
+//! Renders `/prints/`, a small "Latest prints" grid from the Redbubble shop
+//! feed (see [`crate::prints`]). Each print is a Microformats2 `h-product`
+//! and a Schema.org `Product` linking to its shop page.
+
+use crate::components::{Footer, Nav};
+use crate::css;
+use crate::prints::{Print, PRINTS_DESCRIPTION};
+use leptos::prelude::*;
+
+fn render_print(print: &Print) -> impl IntoView {
+    view! {
+        <li class="h-product" itemscope itemtype="https://schema.org/Product">
+            <a href=print.url.clone() class="u-url" itemprop="url" rel="noopener">
+                {print.image.clone().map(|src| view! {
+                    <img
+                        class=css::join(&["u-photo", css::PRINT_IMAGE])
+                        src=src
+                        alt=""
+                        loading="lazy"
+                        decoding="async"
+                        itemprop="image"
+                    />
+                })}
+                <span class="p-name" itemprop="name">{print.title.clone()}</span>
+            </a>
+        </li>
+    }
+}
+
+/// The prints page. `shop` is the shop's URL.
+#[component]
+pub fn PrintsPage(prints: Vec<Print>, shop: &'static str) -> impl IntoView {
+    view! {
+        <body itemscope itemtype="https://schema.org/CollectionPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 itemprop="name">"Latest prints"</h1>
+                <p itemprop="description">{PRINTS_DESCRIPTION}</p>
+                <ul class=css::PRINT_GRID>
+                    {prints.iter().map(render_print).collect::<Vec<_>>()}
+                </ul>
+                <p>
+                    <a href=shop rel="noopener">"Browse the whole shop"</a>
+                </p>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prints::tests::sample_prints;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render() -> String {
+        PrintsPage(PrintsPageProps {
+            prints: sample_prints(),
+            shop: "https://shop.test",
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn prints_are_products() {
+        let dom = parse(&render());
+        assert_eq!(
+            dom.count(".print-grid > li[itemtype=\"https://schema.org/Product\"]"),
+            3
+        );
+        assert_attr(
+            &dom,
+            "img.print-image",
+            "src",
+            "https://ih1.redbubble.net/image.1.jpg",
+        );
+        assert_eq!(dom.count("img.print-image"), 2);
+    }
+
+    #[test]
+    fn prints_are_h_products() {
+        let doc = mf2::parse(&render());
+        let products = doc.items_of("h-product");
+        assert_eq!(products.len(), 3);
+        assert_eq!(products[1].text("name"), Some("Glass & Smoke"));
+    }
+}
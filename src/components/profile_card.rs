@@ -12,195 +12,337 @@
 //! - `.p-note` - Short description/bio
 //! - `.u-photo` - Profile photo URL
 //! - `.u-url` - Profile URL (rel="me" for identity)
+//! - `.u-email` - Contact address, only when [`PUBLISH_EMAIL`] is enabled
+//! - `.u-key` - Where each [identifier](crate::entity::Identifier) with a
+//!   URL can be checked, e.g. the public key of an OpenPGP fingerprint
+//!
+//! Identifiers are not displayed; each is a hidden microdata
+//! PropertyValue, matching the `identifier` list in JSON-LD.
+//!
+//! Each card carries a `view-transition-name` unique to its entity, so it
+//! animates in place across same-origin navigations.
+//!
+//! The avatar is the largest image above the fold, so it is fetched with high
+//! priority and offers its [responsive variants](crate::responsive) through
+//! `srcset`.
+//!
+//! The cards [contribute](crate::structured_data) their entities' JSON-LD
+//! nodes to the page's graph.
 
-use crate::config::{AVATAR_PATH, SITE_DESCRIPTION, SITE_NAME, SITE_URL};
+use crate::components::{rendered_links, Email};
+use crate::config::{ENTITIES, PRIMARY_ENTITY, PUBLISH_EMAIL};
+use crate::css;
+use crate::entity::{entity_nodes, primary_first, Entity};
+use crate::responsive::{source_width, srcset, AVATAR_SIZES, AVATAR_WIDTHS};
+use crate::structured_data::contribute;
+use crate::typography::typeset;
 use leptos::prelude::*;
 
+/// The `view-transition-name` of the card for the entity keyed `key`.
+fn transition_name(key: &str) -> String {
+    format!("profile-{}", key)
+}
+
 /// The profile card component.
 ///
-/// Displays avatar, name, and bio with full semantic markup.
+/// Displays avatar, name, and bio with full semantic markup. The primary
+/// entity's name is the page `<h1>`; secondary entities use `<h2>` so the
+/// heading outline stays valid.
 #[component]
-pub fn ProfileCard() -> impl IntoView {
+pub fn ProfileCard(entity: Entity, #[prop(optional)] primary: bool) -> impl IntoView {
+    let name_id = format!("{}-name", entity.key);
+    let name = if primary {
+        view! { <h1 class="p-name" itemprop="name">{entity.name}</h1> }.into_any()
+    } else {
+        view! { <h2 id=name_id.clone() class="p-name" itemprop="name">{entity.name}</h2> }
+            .into_any()
+    };
+
+    let srcset = source_width(entity.image).and_then(|w| srcset(entity.image, AVATAR_WIDTHS, w));
+    let sizes = srcset.as_ref().map(|_| AVATAR_SIZES);
+
     view! {
         <article
-            class="h-card profile-card"
+            class=css::join(&["h-card", css::PROFILE_CARD])
+            aria-labelledby=(!primary).then_some(name_id)
             itemscope
-            itemtype="https://schema.org/Person"
+            itemtype=entity.itemtype()
+            itemid=entity.id()
+            style=format!("view-transition-name: {}", transition_name(entity.key))
         >
-            <a href=SITE_URL class="u-url" rel="me" itemprop="url">
+            <a href=entity.url class="u-url" rel="me" itemprop="url">
                 <img
-                    src=AVATAR_PATH
-                    alt=format!("{} avatar", SITE_NAME)
-                    class="u-photo avatar"
+                    src=entity.image
+                    srcset=srcset
+                    sizes=sizes
+                    alt=format!("{} avatar", entity.name)
+                    class=css::join(&["u-photo", css::AVATAR])
                     itemprop="image"
                     width="128"
                     height="128"
+                    fetchpriority="high"
+                    decoding="async"
                 />
             </a>
 
-            <h1 class="p-name" itemprop="name">
-                {SITE_NAME}
-            </h1>
+            {name}
 
-            <p class="p-note" itemprop="description">
-                {SITE_DESCRIPTION}
+            <p class=css::P_NOTE itemprop="description">
+                {typeset(entity.description)}
             </p>
+
+            {entity.email.map(|address| view! {
+                <p>
+                    <Email address=address machine_readable=PUBLISH_EMAIL />
+                </p>
+            })}
+
+            {entity
+                .identifiers(primary)
+                .into_iter()
+                .map(|id| view! {
+                    <span
+                        hidden
+                        itemprop="identifier"
+                        itemscope
+                        itemtype="https://schema.org/PropertyValue"
+                    >
+                        <meta itemprop="propertyID" content=id.property_id />
+                        <meta itemprop="value" content=id.value />
+                        {id.url.map(|url| view! { <link class="u-key" itemprop="url" href=url /> })}
+                    </span>
+                })
+                .collect::<Vec<_>>()}
         </article>
     }
 }
 
+/// Renders one [`ProfileCard`] per entity, primary entity first, and
+/// contributes their nodes, the homepage links describing the primary.
+pub fn render_profile_cards(entities: &[Entity], primary: &str) -> impl IntoView {
+    for node in entity_nodes(entities, primary, &rendered_links()) {
+        contribute(node);
+    }
+    primary_first(entities, primary)
+        .into_iter()
+        .map(|entity| {
+            view! { <ProfileCard entity=*entity primary=entity.key == primary /> }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Profile cards for every configured entity.
+#[component]
+pub fn ProfileCards() -> impl IntoView {
+    render_profile_cards(ENTITIES, PRIMARY_ENTITY)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{AVATAR_PATH, SITE_NAME};
+    use crate::entity::tests::{ARTIST, BRAND};
+    use crate::test_utils::{assert_attr, assert_exists, mf2, parse, Dom};
 
-    fn render_card() -> String {
-        ProfileCard().to_html()
+    fn render_card() -> Dom {
+        parse(&ProfileCards().to_html())
     }
 
     // Microformats2 h-card tests
     #[test]
     fn card_has_hcard_class() {
-        let html = render_card();
-        assert!(
-            html.contains("class=\"h-card"),
-            "Profile card should have h-card class"
+        let dom = render_card();
+        assert_eq!(
+            dom.count("article.h-card"),
+            1,
+            "Profile card should be one h-card"
         );
     }
 
     #[test]
     fn card_has_p_name_class() {
-        let html = render_card();
-        assert!(
-            html.contains("p-name"),
-            "Profile card should have p-name class"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".h-card .p-name");
     }
 
     #[test]
     fn card_has_p_note_class() {
-        let html = render_card();
-        assert!(
-            html.contains("p-note"),
-            "Profile card should have p-note class"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".h-card .p-note");
     }
 
     #[test]
     fn card_has_u_photo_class() {
-        let html = render_card();
-        assert!(
-            html.contains("u-photo"),
-            "Profile card should have u-photo class"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".h-card img.u-photo");
     }
 
     #[test]
     fn card_has_u_url_class() {
-        let html = render_card();
-        assert!(
-            html.contains("u-url"),
-            "Profile card should have u-url class"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".h-card a.u-url");
     }
 
     #[test]
     fn card_link_has_rel_me() {
-        let html = render_card();
-        assert!(
-            html.contains("rel=\"me\""),
-            "Profile URL link should have rel=\"me\" attribute"
-        );
+        let dom = render_card();
+        assert_attr(&dom, "a.u-url", "rel", "me");
     }
 
     // Schema.org microdata tests
     #[test]
     fn card_has_person_itemtype() {
-        let html = render_card();
-        assert!(
-            html.contains("itemtype=\"https://schema.org/Person\""),
-            "Profile card should have Person itemtype"
-        );
+        let dom = render_card();
+        assert_attr(&dom, ".h-card", "itemtype", "https://schema.org/Person");
     }
 
     #[test]
     fn card_has_itemscope() {
-        let html = render_card();
-        assert!(
-            html.contains("itemscope"),
-            "Profile card should have itemscope attribute"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".h-card[itemscope]");
     }
 
     #[test]
     fn card_has_name_itemprop() {
-        let html = render_card();
-        assert!(
-            html.contains("itemprop=\"name\""),
-            "Name should have itemprop attribute"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".p-name[itemprop=name]");
     }
 
     #[test]
     fn card_has_description_itemprop() {
-        let html = render_card();
-        assert!(
-            html.contains("itemprop=\"description\""),
-            "Description should have itemprop attribute"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".p-note[itemprop=description]");
     }
 
     #[test]
     fn card_has_image_itemprop() {
-        let html = render_card();
-        assert!(
-            html.contains("itemprop=\"image\""),
-            "Image should have itemprop attribute"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".u-photo[itemprop=image]");
     }
 
     #[test]
     fn card_has_url_itemprop() {
-        let html = render_card();
-        assert!(
-            html.contains("itemprop=\"url\""),
-            "URL should have itemprop attribute"
-        );
+        let dom = render_card();
+        assert_exists(&dom, ".u-url[itemprop=url]");
     }
 
     // Content tests
     #[test]
     fn card_contains_site_name() {
-        let html = render_card();
-        assert!(
-            html.contains(SITE_NAME),
-            "Profile card should contain site name"
-        );
+        let dom = render_card();
+        assert_eq!(dom.text(".p-name").as_deref(), Some(SITE_NAME));
     }
 
     #[test]
     fn card_contains_avatar_path() {
-        let html = render_card();
-        assert!(
-            html.contains(AVATAR_PATH),
-            "Profile card should contain avatar path"
-        );
+        let dom = render_card();
+        assert_attr(&dom, "img.u-photo", "src", AVATAR_PATH);
     }
 
     #[test]
     fn avatar_has_alt_text() {
-        let html = render_card();
-        assert!(
-            html.contains("alt=\""),
-            "Avatar image should have alt text"
+        let dom = render_card();
+        let alt = dom.attr("img.u-photo", "alt").unwrap_or_default();
+        assert!(!alt.is_empty(), "Avatar image should have alt text");
+    }
+
+    // Multi-entity tests
+    #[test]
+    fn each_entity_is_a_distinct_hcard() {
+        let html = render_profile_cards(&[BRAND, ARTIST], "brand").to_html();
+        let doc = mf2::parse(&html);
+        let cards = doc.items_of("h-card");
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].text("name"), Some(BRAND.name));
+        assert_eq!(cards[1].text("name"), Some(ARTIST.name));
+    }
+
+    #[test]
+    fn cards_have_distinct_transition_names() {
+        let dom = parse(&render_profile_cards(&[BRAND, ARTIST], "brand").to_html());
+        assert_eq!(
+            dom.attrs(".h-card", "style"),
+            [
+                "view-transition-name: profile-brand;",
+                "view-transition-name: profile-artist;"
+            ]
+        );
+    }
+
+    #[test]
+    fn only_primary_entity_is_h1() {
+        let dom = parse(&render_profile_cards(&[ARTIST, BRAND], "brand").to_html());
+        assert_eq!(dom.texts("h1.p-name"), [BRAND.name]);
+        assert_eq!(dom.texts("h2.p-name"), [ARTIST.name]);
+    }
+
+    #[test]
+    fn cards_carry_json_ld_ids() {
+        let dom = parse(&render_profile_cards(&[BRAND, ARTIST], "brand").to_html());
+        assert_eq!(dom.attrs(".h-card", "itemid"), [BRAND.id(), ARTIST.id()]);
+        assert_attr(
+            &dom,
+            ".h-card",
+            "itemtype",
+            "https://schema.org/Organization",
         );
     }
 
     #[test]
     fn avatar_has_dimensions() {
-        let html = render_card();
-        assert!(
-            html.contains("width=\"") && html.contains("height=\""),
-            "Avatar should have width and height attributes"
+        let dom = render_card();
+        assert_attr(&dom, "img.u-photo", "width", "128");
+        assert_attr(&dom, "img.u-photo", "height", "128");
+    }
+
+    #[test]
+    fn avatar_offers_responsive_variants() {
+        let dom = render_card();
+        assert_attr(
+            &dom,
+            "img.u-photo",
+            "srcset",
+            "/avatar-128.png 128w, /avatar-256.png 256w, /avatar-384.png 384w, /avatar.png 512w",
         );
+        assert_attr(&dom, "img.u-photo", "sizes", AVATAR_SIZES);
+    }
+
+    #[test]
+    fn avatar_loads_with_high_priority() {
+        let dom = render_card();
+        assert_attr(&dom, "img.u-photo", "fetchpriority", "high");
+        assert_attr(&dom, "img.u-photo", "decoding", "async");
+    }
+
+    #[test]
+    fn email_is_rendered_obfuscated_inside_card() {
+        let dom = parse(&render_profile_cards(&[ARTIST], "artist").to_html());
+        assert_eq!(dom.count(".h-card .email-obfuscated"), 1);
+        assert!(!dom.exists(".u-email"), "mailto is opt-in");
+    }
+
+    #[test]
+    fn identifiers_are_hidden_property_values() {
+        let html = render_profile_cards(&[ARTIST], "artist").to_html();
+        let dom = parse(&html);
+        let id = &ARTIST.identifiers[0];
+        assert_eq!(
+            dom.attrs("[itemprop=identifier]", "itemtype"),
+            ["https://schema.org/PropertyValue"]
+        );
+        assert_exists(&dom, "[itemprop=identifier][hidden]");
+        assert_eq!(
+            dom.attrs("[itemprop=identifier] [itemprop=value]", "content"),
+            [id.value]
+        );
+        assert_eq!(mf2::parse(&html).items[0].text("key"), id.url);
+        let brand = parse(&render_profile_cards(&[BRAND], "brand").to_html());
+        assert_eq!(brand.count("[itemprop=identifier]"), 0);
+    }
+
+    #[test]
+    fn entity_without_email_has_none() {
+        let dom = parse(&render_profile_cards(&[BRAND], "brand").to_html());
+        assert!(!dom.exists(".email-obfuscated"));
     }
 }
@@ -0,0 +1,74 @@
+//! # Property Links Component
+//!
+//! The cross-linking block shared by the main site and its related
+//! [properties](crate::properties): the main site with `rel="home"`, then
+//! each property, the current site marked with `aria-current`.
+
+use crate::config::{PROPERTIES, SITE_NAME};
+use crate::css;
+use crate::url::absolute;
+use leptos::prelude::*;
+
+/// The property links. `current` is the key of the property rendering
+/// them; `None` on the main site.
+#[component]
+pub fn PropertyLinks(#[prop(optional)] current: Option<String>) -> impl IntoView {
+    let on_main = current.is_none();
+    (!PROPERTIES.is_empty()).then(|| {
+        view! {
+            <nav class=css::PROPERTY_LINKS aria-label=format!("{} sites", SITE_NAME)>
+                <ul>
+                    <li>
+                        <a href=absolute("/") rel="home" aria-current=on_main.then_some("true")>
+                            {SITE_NAME}
+                        </a>
+                    </li>
+                    {PROPERTIES
+                        .iter()
+                        .map(|property| {
+                            let here = current.as_deref() == Some(property.key);
+                            view! {
+                                <li>
+                                    <a
+                                        href=format!("{}/", property.url)
+                                        title=property.description
+                                        aria-current=here.then_some("true")
+                                    >
+                                        {property.name}
+                                    </a>
+                                </li>
+                            }
+                        })
+                        .collect::<Vec<_>>()}
+                </ul>
+            </nav>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    #[test]
+    fn main_site_is_home() {
+        let dom = parse(&view! { <PropertyLinks /> }.to_html());
+        assert_attr(&dom, "a[rel=home]", "href", "https://everythingsings.art/");
+        assert_attr(&dom, "a[rel=home]", "aria-current", "true");
+        assert_eq!(dom.count("nav a"), PROPERTIES.len() + 1);
+    }
+
+    #[test]
+    fn current_property_is_marked() {
+        let key = PROPERTIES[0].key.to_string();
+        let dom = parse(&view! { <PropertyLinks current=key /> }.to_html());
+        assert_eq!(dom.count("[aria-current]"), 1);
+        assert_attr(
+            &dom,
+            "[aria-current=true]",
+            "href",
+            &format!("{}/", PROPERTIES[0].url),
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! # Protected Page Component
+//!
+//! Renders a protected page (see [`crate::protected`]): the passphrase form
+//! carrying the sealed content, which [`UNLOCK_SCRIPT`] decrypts in place.
+//! Without script the form explains that it needs it.
+
+use crate::components::{Footer, Nav};
+use crate::css;
+use crate::protected::Sealed;
+use leptos::prelude::*;
+
+/// The end-of-body script unlocking the form (see
+/// [`crate::document::Document::end_of_body_scripts`]).
+pub const UNLOCK_SCRIPT: &str = r#"<script src="/js/unlock.js" defer></script>"#;
+
+/// A protected page titled `title`.
+#[component]
+pub fn ProtectedPage(title: String, sealed: Sealed) -> impl IntoView {
+    view! {
+        <body itemscope itemtype="https://schema.org/WebPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <h1 itemprop="name">{title}</h1>
+                <form
+                    class=css::UNLOCK_FORM
+                    data-salt=sealed.salt_base64()
+                    data-nonce=sealed.nonce_base64()
+                    data-iterations=sealed.iterations.to_string()
+                    data-ciphertext=sealed.ciphertext_base64()
+                >
+                    <label for="unlock-passphrase">"Passphrase"</label>
+                    <input
+                        id="unlock-passphrase"
+                        type="password"
+                        name="passphrase"
+                        required
+                        autocomplete="off"
+                    />
+                    <button type="submit">"Unlock"</button>
+                    <p role="status"></p>
+                    <noscript>
+                        <p>"This page is encrypted; unlocking it needs JavaScript."</p>
+                    </noscript>
+                </form>
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protected::tests::sample_sealed;
+    use crate::test_utils::{assert_attr, parse};
+
+    #[test]
+    fn form_carries_the_sealed_content() {
+        let sealed = sample_sealed();
+        let html = ProtectedPage(ProtectedPageProps {
+            title: "Press preview".to_string(),
+            sealed: sealed.clone(),
+        })
+        .to_html();
+        let dom = parse(&html);
+        assert_attr(&dom, "form", "data-salt", &sealed.salt_base64());
+        assert_attr(&dom, "form", "data-nonce", &sealed.nonce_base64());
+        assert_attr(&dom, "form", "data-ciphertext", &sealed.ciphertext_base64());
+        assert!(dom.exists("form input[name=passphrase][type=password]"));
+        assert!(!dom.exists("script"), "The document adds UNLOCK_SCRIPT");
+        assert!(!html.contains("Embargoed"));
+    }
+}
@@ -0,0 +1,101 @@
+//! # Reaction Counts Component
+//!
+//! Small static counters for a page's syndicated copies ("Mastodon: 12
+//! favourites · 3 boosts"), each linking to the copy. Counts are Schema.org
+//! `InteractionCounter`s of the page's main item.
+
+use crate::css;
+use crate::reactions::{Count, Reactions};
+use leptos::prelude::*;
+
+fn render_count(count: &Count) -> impl IntoView {
+    view! {
+        <span
+            itemprop="interactionStatistic"
+            itemscope
+            itemtype="https://schema.org/InteractionCounter"
+        >
+            <link itemprop="interactionType" href=format!("https://schema.org/{}", count.action) />
+            <data itemprop="userInteractionCount" value=count.value.to_string()>
+                {count.value.to_string()}
+            </data>
+            " "
+            {count.label}
+        </span>
+    }
+}
+
+fn render_reactions(reactions: &Reactions) -> impl IntoView {
+    let counts = reactions
+        .counts
+        .iter()
+        .enumerate()
+        .map(|(i, count)| view! { {(i > 0).then_some(" · ")} {render_count(count)} })
+        .collect::<Vec<_>>();
+    view! {
+        <li>
+            <a href=reactions.url.clone() rel="nofollow noopener">{reactions.network}</a>
+            ": "
+            {counts}
+        </li>
+    }
+}
+
+/// The reaction counters; renders nothing when there are none.
+#[component]
+pub fn ReactionCounts(reactions: Vec<Reactions>) -> impl IntoView {
+    (!reactions.is_empty()).then(|| {
+        view! {
+            <ul class=css::REACTIONS aria-label="Reactions">
+                {reactions.iter().map(render_reactions).collect::<Vec<_>>()}
+            </ul>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactions::tests::sample_reactions;
+    use crate::test_utils::{assert_attr, parse};
+
+    #[test]
+    fn counters_link_to_copies() {
+        let dom = parse(&view! { <ReactionCounts reactions=sample_reactions() /> }.to_html());
+        assert_eq!(
+            dom.attrs("ul.reactions > li > a", "href"),
+            [
+                "https://mastodon.test/@everythingsings/1",
+                "https://njump.me/note1test"
+            ]
+        );
+        assert_eq!(
+            dom.text("ul.reactions > li").as_deref(),
+            Some("Mastodon: 12 favourites · 3 boosts")
+        );
+    }
+
+    #[test]
+    fn counts_are_interaction_counters() {
+        let dom = parse(&view! { <ReactionCounts reactions=sample_reactions() /> }.to_html());
+        assert_eq!(dom.count("[itemprop=interactionStatistic]"), 3);
+        assert_attr(
+            &dom,
+            "li:last-child [itemprop=interactionType]",
+            "href",
+            "https://schema.org/DonateAction",
+        );
+        assert_attr(
+            &dom,
+            "li:first-child [itemprop=userInteractionCount]",
+            "value",
+            "12",
+        );
+    }
+
+    #[test]
+    fn no_reactions_render_nothing() {
+        let dom = parse(&view! { <ReactionCounts reactions=Vec::new() /> }.to_html());
+        assert!(!dom.exists("ul"));
+    }
+}
@@ -0,0 +1,92 @@
+//! # Recent Listens Component
+//!
+//! A small "Recently listening to" strip for the homepage. Each track is a
+//! Schema.org `MusicRecording` with its artist (`byArtist`) and, when known,
+//! its album (`inAlbum`). Renders nothing when there are no listens.
+
+use crate::components::LISTENS_SECTION;
+use crate::css;
+use crate::listens::Listen;
+use leptos::prelude::*;
+
+fn render_listen(listen: &Listen) -> impl IntoView {
+    let name = match &listen.url {
+        Some(url) => {
+            view! { <a href=url.clone() itemprop="url">{listen.track.clone()}</a> }.into_any()
+        }
+        None => listen.track.clone().into_any(),
+    };
+    view! {
+        <li itemscope itemtype="https://schema.org/MusicRecording">
+            <cite itemprop="name">{name}</cite>
+            " — "
+            <span itemprop="byArtist" itemscope itemtype="https://schema.org/MusicGroup">
+                <span itemprop="name">{listen.artist.clone()}</span>
+            </span>
+            {listen.release.clone().map(|release| view! {
+                <span itemprop="inAlbum" itemscope itemtype="https://schema.org/MusicAlbum">
+                    <meta itemprop="name" content=release />
+                </span>
+            })}
+            {listen.now_playing.then(|| view! {
+                <span class=css::LISTEN_NOW>" (now playing)"</span>
+            })}
+        </li>
+    }
+}
+
+/// The "Recently listening to" strip, the homepage's [`LISTENS_SECTION`].
+#[component]
+pub fn RecentListens(listens: Vec<Listen>) -> impl IntoView {
+    (!listens.is_empty()).then(|| {
+        view! {
+            <section class=css::RECENT_LISTENS aria-labelledby=LISTENS_SECTION.id()>
+                <h2 id=LISTENS_SECTION.id()>{LISTENS_SECTION.title}</h2>
+                <ul>{listens.iter().map(render_listen).collect::<Vec<_>>()}</ul>
+            </section>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    fn listen(track: &str, release: Option<&str>, url: Option<&str>) -> Listen {
+        Listen {
+            track: track.to_string(),
+            artist: "Burial".to_string(),
+            release: release.map(str::to_string),
+            url: url.map(str::to_string),
+            now_playing: false,
+        }
+    }
+
+    fn render(listens: Vec<Listen>) -> String {
+        RecentListens(RecentListensProps { listens }).to_html()
+    }
+
+    #[test]
+    fn tracks_are_music_recordings() {
+        let dom = parse(&render(vec![
+            listen("Archangel", Some("Untrue"), Some("https://example.com/a")),
+            listen("Near Dark", None, None),
+        ]));
+        assert_eq!(
+            dom.count("li[itemtype='https://schema.org/MusicRecording']"),
+            2
+        );
+        assert_eq!(
+            dom.texts("[itemprop=byArtist] [itemprop=name]"),
+            ["Burial", "Burial"]
+        );
+        assert_attr(&dom, "[itemprop=inAlbum] meta", "content", "Untrue");
+        assert_attr(&dom, "a[itemprop=url]", "href", "https://example.com/a");
+    }
+
+    #[test]
+    fn no_listens_renders_nothing() {
+        assert_eq!(parse(&render(vec![])).count("section"), 0);
+    }
+}
@@ -0,0 +1,172 @@
+//! # Release Page Component
+//!
+//! Renders `/music/<slug>/` for one [`Release`]: cover art, release date,
+//! tracklist, and [platform links](PlatformLinks). The page is a
+//! Microformats2 `h-cite` and a Schema.org `MusicAlbum` whose tracks are
+//! `MusicRecording`s.
+
+use crate::apple_music::iso_duration;
+use crate::components::{Footer, MentionSection, Nav, PlatformLinks, Time};
+use crate::config::SITE_NAME;
+use crate::css;
+use crate::music::{Release, Track, MUSIC_DIR};
+use crate::url::{absolute, page_path};
+use leptos::prelude::*;
+
+fn render_track(position: usize, track: &Track) -> impl IntoView {
+    let duration = track
+        .duration_ms()
+        .zip(track.duration.clone())
+        .map(|(ms, label)| {
+            view! {
+                " "
+                <time class=css::TRACK_DURATION itemprop="duration" datetime=iso_duration(ms)>
+                    {label}
+                </time>
+            }
+        });
+    view! {
+        <li itemprop="track" itemscope itemtype="https://schema.org/MusicRecording">
+            <meta itemprop="position" content=position.to_string() />
+            <span itemprop="name">{track.title.clone()}</span>
+            {duration}
+        </li>
+    }
+}
+
+/// The release page, with a form sending webmentions to `mention_endpoint`
+/// when one is configured.
+#[component]
+pub fn ReleasePage(
+    release: Release,
+    #[prop(optional)] mention_endpoint: Option<&'static str>,
+) -> impl IntoView {
+    let mentions = mention_endpoint.map(|endpoint| {
+        view! { <MentionSection target=absolute(&release.url_path()) endpoint=endpoint /> }
+    });
+    let tracks = (!release.tracks.is_empty()).then(|| {
+        view! {
+            <ol class=css::TRACKLIST aria-label="Tracklist">
+                {release
+                    .tracks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| render_track(i + 1, t))
+                    .collect::<Vec<_>>()}
+            </ol>
+        }
+    });
+    view! {
+        <body class="h-cite" itemscope itemtype="https://schema.org/MusicAlbum">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                <a href=page_path(&[MUSIC_DIR]) class=css::BACK_LINK>{"\u{2190} All Music"}</a>
+                <header class=css::RELEASE_HEADER>
+                    {release.cover_url().map(|src| view! {
+                        <img
+                            class=css::join(&["u-photo", css::RELEASE_COVER])
+                            src=src
+                            alt=format!("Cover art of {}", release.title)
+                            width="320"
+                            height="320"
+                            itemprop="image"
+                        />
+                    })}
+                    <h1 class="p-name" itemprop="name">{release.title.clone()}</h1>
+                    <p class=css::RELEASE_META>
+                        <span
+                            class="p-author h-card"
+                            itemprop="byArtist"
+                            itemscope
+                            itemtype="https://schema.org/MusicGroup"
+                        >
+                            <span class="p-name" itemprop="name">{SITE_NAME}</span>
+                        </span>
+                        " · "
+                        <Time datetime=release.date.clone() class="dt-published" itemprop="datePublished" />
+                    </p>
+                    <p class="p-summary" itemprop="description">{release.summary()}</p>
+                </header>
+                {tracks}
+                <PlatformLinks links=release.links.clone() />
+                {release.links.is_empty().then(|| view! {
+                    <p>
+                        <a href=release.url.clone() class="u-url" itemprop="sameAs" rel="noopener">
+                            "Listen"
+                        </a>
+                    </p>
+                })}
+                {mentions}
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::music::tests::sample_releases;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render(i: usize) -> String {
+        ReleasePage(ReleasePageProps {
+            release: sample_releases().releases[i].clone(),
+            mention_endpoint: None,
+        })
+        .to_html()
+    }
+
+    #[test]
+    fn page_is_a_music_album() {
+        let dom = parse(&render(0));
+        assert_attr(&dom, "body", "itemtype", "https://schema.org/MusicAlbum");
+        assert_attr(&dom, "img.release-cover", "src", "/music/dawn/cover.jpg");
+        assert_eq!(dom.text("h1").as_deref(), Some("Dawn"));
+        assert_eq!(mf2::parse(&render(0)).items_of("h-cite").len(), 1);
+    }
+
+    #[test]
+    fn tracklist_is_ordered_recordings() {
+        let dom = parse(&render(0));
+        assert_eq!(
+            dom.texts("ol.tracklist > li [itemprop=name]"),
+            ["First Light", "Noon"]
+        );
+        assert_attr(&dom, "li:first-child time", "datetime", "PT3M20S");
+        assert_eq!(dom.count("li time"), 1, "Untimed tracks show no duration");
+    }
+
+    #[test]
+    fn responses_form_only_with_an_endpoint() {
+        assert!(!parse(&render(0)).exists("form"));
+        let release = sample_releases().releases[0].clone();
+        let target = absolute(&release.url_path());
+        let dom = parse(
+            &ReleasePage(ReleasePageProps {
+                release,
+                mention_endpoint: Some("https://wm.test/webmention"),
+            })
+            .to_html(),
+        );
+        assert_attr(&dom, "form input[name=target]", "value", &target);
+    }
+
+    #[test]
+    fn platform_row_or_single_link() {
+        assert_eq!(parse(&render(0)).count("ul.platform-links li"), 2);
+        let dom = parse(&render(1));
+        assert!(!dom.exists("ul.platform-links"));
+        assert!(!dom.exists("ol.tracklist"));
+        assert_attr(
+            &dom,
+            "a[itemprop=sameAs]",
+            "href",
+            "https://es.bandcamp.com/album/neon",
+        );
+    }
+}
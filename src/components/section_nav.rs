@@ -0,0 +1,95 @@
+//! # Section Navigation Component
+//!
+//! An "On this page" anchor menu for the homepage, generated from the
+//! sections it renders. Each [`Section`] derives its heading `id` from its
+//! title, so the menu, the heading, and the section's `aria-labelledby`
+//! always agree.
+//!
+//! There is no script: the section reached through the menu is the
+//! `:target`, and a rule per section (generated alongside the menu) marks
+//! its menu link as current with `body:has(#id:target)`.
+
+use crate::css;
+use crate::slug::slug;
+use leptos::prelude::*;
+
+/// A homepage section with an anchor heading.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Section {
+    pub title: &'static str,
+}
+
+impl Section {
+    /// The `id` of the section's heading, e.g. `recently-listening-to`.
+    pub fn id(&self) -> String {
+        slug(self.title)
+    }
+}
+
+/// The profile links.
+pub const LINKS_SECTION: Section = Section { title: "Links" };
+
+/// The "Recently listening to" strip.
+pub const LISTENS_SECTION: Section = Section {
+    title: "Recently listening to",
+};
+
+/// Rules marking the menu link of the `:target` section as current.
+fn target_rules(sections: &[Section]) -> String {
+    sections
+        .iter()
+        .map(|section| {
+            format!(
+                "body:has(#{id}:target) .{nav} a[href=\"#{id}\"]",
+                id = section.id(),
+                nav = css::SECTION_NAV
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n")
+        + " { color: var(--color-accent); text-decoration: underline; }"
+}
+
+/// The anchor menu. Renders nothing for fewer than two sections.
+#[component]
+pub fn SectionNav(sections: Vec<Section>) -> impl IntoView {
+    (sections.len() > 1).then(|| {
+        view! {
+            <nav class=css::SECTION_NAV aria-label="On this page">
+                <ul>
+                    {sections
+                        .iter()
+                        .map(|section| view! {
+                            <li><a href=format!("#{}", section.id())>{section.title}</a></li>
+                        })
+                        .collect::<Vec<_>>()}
+                </ul>
+                <style>{target_rules(&sections)}</style>
+            </nav>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::parse;
+
+    #[test]
+    fn menu_links_section_headings() {
+        let html = view! { <SectionNav sections=vec![LINKS_SECTION, LISTENS_SECTION] /> }.to_html();
+        let dom = parse(&html);
+        assert_eq!(
+            dom.attrs("nav a", "href"),
+            ["#links", "#recently-listening-to"]
+        );
+        let rules = dom.text("nav style").unwrap();
+        assert!(rules.contains("body:has(#recently-listening-to:target) .section-nav a[href=\"#recently-listening-to\"]"));
+    }
+
+    #[test]
+    fn single_section_has_no_menu() {
+        let html = view! { <SectionNav sections=vec![LINKS_SECTION] /> }.to_html();
+        assert!(!parse(&html).exists("nav"));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! EverythingSings logo as a Lissajous curve, rendered as pure SVG.
 
+use crate::components::Footer;
+use crate::css;
 use leptos::prelude::*;
 
 use super::nav::Nav;
@@ -36,16 +38,16 @@ pub fn SigilPage() -> impl IntoView {
             <noscript>
                 <style>"#shader-canvas { display: none; }"</style>
             </noscript>
-            <main class="container sigil-container">
+            <main class=css::join(&[css::CONTAINER, css::SIGIL_CONTAINER])>
                 <Nav />
-                <div class="sigil-page" itemscope itemtype="https://schema.org/ImageObject">
-                    <h1 itemprop="name" class="sigil-title">"Sigil"</h1>
-                    <p class="sigil-subtitle" itemprop="description">"EverythingSings — Lissajous curve logo"</p>
-                    <div class="sigil-artwork">
+                <div class=css::SIGIL_PAGE itemscope itemtype="https://schema.org/ImageObject">
+                    <h1 itemprop="name" class=css::SIGIL_TITLE>"Sigil"</h1>
+                    <p class=css::SIGIL_SUBTITLE itemprop="description">"EverythingSings — Lissajous curve logo"</p>
+                    <div class=css::SIGIL_ARTWORK>
                         <svg
                             xmlns="http://www.w3.org/2000/svg"
                             viewBox="-160 -160 320 320"
-                            class="sigil-svg"
+                            class=css::SIGIL_SVG
                             role="img"
                             aria-label="EverythingSings sigil — a Lissajous curve"
                         >
@@ -61,9 +63,9 @@ pub fn SigilPage() -> impl IntoView {
                     </div>
                 </div>
             </main>
-            <footer>
+            <Footer>
                 <p>"EverythingSings"</p>
-            </footer>
+            </Footer>
         </body>
     }
 }
@@ -71,12 +73,16 @@ pub fn SigilPage() -> impl IntoView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{assert_exists, parse};
 
     #[test]
     fn sigil_page_renders_svg() {
-        let html = SigilPage().to_html();
-        assert!(html.contains("<svg"));
-        assert!(html.contains("Lissajous"));
+        let dom = parse(&SigilPage().to_html());
+        assert_exists(&dom, "svg.sigil-svg[role=img] > path[d]");
+        assert!(dom
+            .attr("svg.sigil-svg", "aria-label")
+            .unwrap()
+            .contains("Lissajous"));
     }
 
     #[test]
@@ -0,0 +1,128 @@
+//! # Related Site Page Component
+//!
+//! Renders the landing page of a related site (see [`crate::site`]) with the
+//! same semantic layers as the main site: an h-card with Schema.org
+//! microdata, a list of links, and links to its card pages.
+
+use crate::cards::Card;
+use crate::components::Footer;
+use crate::config::{SITE_NAME, SITE_URL};
+use crate::css;
+use crate::site::{SiteConfig, SiteLink};
+use leptos::prelude::*;
+
+fn render_site_link(link: &SiteLink) -> impl IntoView {
+    let title = link
+        .description
+        .clone()
+        .unwrap_or_else(|| link.label.clone());
+    view! {
+        <li class=css::LINK_ITEM>
+            <a href=link.href.clone() rel="noopener" class=css::LINK_CARD title=title>
+                <span class=css::LINK_LABEL>{link.label.clone()}</span>
+                {link.description.clone().map(|desc| {
+                    view! { <span class=css::LINK_DESCRIPTION>{desc}</span> }
+                })}
+            </a>
+        </li>
+    }
+}
+
+fn render_card_link(card: &Card) -> impl IntoView {
+    view! {
+        <li class=css::LINK_ITEM>
+            <a href=card.url_path() class=css::LINK_CARD>
+                <span class=css::LINK_LABEL>{card.name.clone()}</span>
+                <span class=css::LINK_DESCRIPTION>
+                    {format!("Edition of {} · {}", card.edition_size, card.availability.label())}
+                </span>
+            </a>
+        </li>
+    }
+}
+
+/// The landing page of a related site.
+///
+/// The nav links back to the main site so every property leads home.
+/// `cards` link to the site's card pages.
+#[component]
+pub fn SitePage(site: SiteConfig, #[prop(optional)] cards: Vec<Card>) -> impl IntoView {
+    view! {
+        <body itemscope itemtype="https://schema.org/WebPage">
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <nav class=css::SITE_NAV aria-label="Site navigation">
+                    <a href=SITE_URL class=css::SITE_NAV_HOME>{SITE_NAME}</a>
+                </nav>
+                <article class=css::join(&["h-card", css::PROFILE_CARD]) itemscope itemtype="https://schema.org/WebSite">
+                    <h1 class="p-name" itemprop="name">
+                        <a href=site.url.clone() class="u-url" itemprop="url">{site.name.clone()}</a>
+                    </h1>
+                    <p class=css::P_NOTE itemprop="description">{site.description.clone()}</p>
+                </article>
+                {(!site.links.is_empty()).then(|| view! {
+                    <nav class=css::LINK_LIST aria-label="Links">
+                        <ul>
+                            {site.links.iter().map(render_site_link).collect::<Vec<_>>()}
+                        </ul>
+                    </nav>
+                })}
+                {(!cards.is_empty()).then(|| view! {
+                    <nav class=css::LINK_LIST aria-label="Cards">
+                        <ul>{cards.iter().map(render_card_link).collect::<Vec<_>>()}</ul>
+                    </nav>
+                })}
+            </main>
+            <Footer property=site.key.clone() />
+        </body>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::tests::sample_manifest;
+    use crate::site::tests::sample_site;
+    use crate::test_utils::{assert_attr, mf2, parse};
+
+    fn render() -> String {
+        view! { <SitePage site=sample_site() /> }.to_html()
+    }
+
+    #[test]
+    fn site_page_has_hcard_for_site() {
+        let doc = mf2::parse(&render());
+        let card = doc.items_of("h-card")[0];
+        assert_eq!(card.text("name"), Some("Lumimenta"));
+        assert_eq!(
+            card.text("url"),
+            Some("https://lumimenta.everythingsings.art")
+        );
+    }
+
+    #[test]
+    fn site_page_links_back_to_main_site() {
+        let dom = parse(&render());
+        assert_attr(&dom, "nav.site-nav > a", "href", SITE_URL);
+    }
+
+    #[test]
+    fn site_page_lists_links() {
+        let dom = parse(&render());
+        assert_eq!(dom.texts(".link-label"), ["Shop"]);
+    }
+
+    #[test]
+    fn site_page_links_card_pages() {
+        let html =
+            view! { <SitePage site=sample_site() cards=sample_manifest().cards /> }.to_html();
+        let dom = parse(&html);
+        assert_eq!(
+            dom.attrs("nav[aria-label=Cards] a", "href"),
+            ["/cards/aurora/", "/cards/ember-2/"]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! # Taxonomy Components
+//!
+//! Renders the [taxonomy](crate::taxonomy) pages: a taxonomy's index of
+//! terms (`/medium/`) and each term's page (`/medium/ink/`), an `h-feed` of
+//! `h-entry` summaries and a Schema.org `CollectionPage` about the term.
+//! [`TermLinks`] lists an entry's terms on its own page as `p-category`
+//! links with `rel="tag"`.
+
+use crate::components::{Footer, Nav, Time};
+use crate::config::TAXONOMIES;
+use crate::css;
+use crate::slug::slug;
+use crate::taxonomy::{Tagged, Taxonomy, Term, Terms};
+use crate::typography::typeset;
+use leptos::prelude::*;
+
+fn render_tagged(entry: &Tagged, position: usize) -> impl IntoView {
+    let description = (!entry.description.is_empty()).then(|| {
+        view! { <p class="p-summary">{entry.description.clone()}</p> }
+    });
+    view! {
+        <article aria-labelledby=format!("entry-{}-title", position) class=css::join(&["h-entry", css::POST_SUMMARY])>
+            <h2 id=format!("entry-{}-title", position)>
+                <a href=entry.url.clone() class="p-name u-url">{entry.title.clone()}</a>
+            </h2>
+            <p class=css::POST_META>
+                <Time datetime=entry.date.clone() class="dt-published" />
+            </p>
+            {description}
+        </article>
+    }
+}
+
+fn page<V: IntoView>(itemtype: &'static str, content: V) -> impl IntoView {
+    view! {
+        <body class="h-feed" itemscope itemtype=itemtype>
+            <canvas id="shader-canvas" aria-hidden="true"></canvas>
+            <noscript>
+                <style>{"body { background: linear-gradient(135deg, #0d0d0d 0%, #1a1a1a 50%, #0d0d0d 100%); }"}</style>
+            </noscript>
+            <main class=css::CONTAINER>
+                <Nav />
+                {content}
+            </main>
+            <Footer />
+        </body>
+    }
+}
+
+/// The index of `taxonomy`, listing `terms` with their entry counts.
+#[component]
+pub fn TaxonomyPage(taxonomy: Taxonomy, terms: Vec<Term>) -> impl IntoView {
+    page(
+        "https://schema.org/CollectionPage",
+        view! {
+            <h1 class="p-name" itemprop="name">{taxonomy.title}</h1>
+            <p class="p-summary" itemprop="description">{typeset(taxonomy.description)}</p>
+            <ul class=css::TERM_INDEX>
+                {terms
+                    .iter()
+                    .map(|term| view! {
+                        <li>
+                            <a href=taxonomy.term_path(&term.slug)>{term.name.clone()}</a>
+                            " "
+                            <span class=css::POST_META>{format!("({})", term.entries.len())}</span>
+                        </li>
+                    })
+                    .collect::<Vec<_>>()}
+            </ul>
+        },
+    )
+}
+
+/// The page of `term`, listing its entries.
+#[component]
+pub fn TermPage(taxonomy: Taxonomy, term: Term) -> impl IntoView {
+    page(
+        "https://schema.org/CollectionPage",
+        view! {
+            <a href=taxonomy.index_path() class=css::BACK_LINK>
+                {format!("\u{2190} {}", taxonomy.title)}
+            </a>
+            <h1 class="p-name" itemprop="name">{term.name.clone()}</h1>
+            <p>
+                <a href="feed.xml" type="application/rss+xml">"RSS feed"</a>
+            </p>
+            {term
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| render_tagged(entry, i + 1))
+                .collect::<Vec<_>>()}
+        },
+    )
+}
+
+/// An entry's terms, grouped by taxonomy in config order. Renders nothing
+/// without terms.
+#[component]
+pub fn TermLinks(terms: Terms) -> impl IntoView {
+    let groups: Vec<(Taxonomy, Vec<String>)> = TAXONOMIES
+        .iter()
+        .filter_map(|taxonomy| Some((*taxonomy, terms.get(taxonomy.key)?.clone())))
+        .filter(|(_, names)| !names.is_empty())
+        .collect();
+    (!groups.is_empty()).then(|| {
+        view! {
+            <dl class=css::TERM_LINKS>
+                {groups
+                    .into_iter()
+                    .map(|(taxonomy, names)| view! {
+                        <dt>{taxonomy.title}</dt>
+                        {names
+                            .into_iter()
+                            .map(|name| {
+                                let href = taxonomy.term_path(&slug(&name));
+                                view! {
+                                    <dd>
+                                        <a href=href class="p-category" rel="tag">{name}</a>
+                                    </dd>
+                                }
+                            })
+                            .collect::<Vec<_>>()}
+                    })
+                    .collect::<Vec<_>>()}
+            </dl>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taxonomy::tests::{sample_terms, MEDIUM};
+    use crate::test_utils::{assert_attr, assert_outline, mf2, parse};
+
+    #[test]
+    fn term_page_is_an_h_feed_of_its_entries() {
+        let html = TermPage(TermPageProps {
+            taxonomy: MEDIUM,
+            term: sample_terms()[0].clone(),
+        })
+        .to_html();
+        let doc = mf2::parse(&html);
+        let feed = doc.items_of("h-feed")[0];
+        assert_eq!(feed.text("name"), Some("ink"));
+        assert_eq!(feed.children[0].text("url"), Some("/posts/hello-world/"));
+        assert_outline(&html);
+    }
+
+    #[test]
+    fn index_links_every_term() {
+        let dom = parse(
+            &TaxonomyPage(TaxonomyPageProps {
+                taxonomy: MEDIUM,
+                terms: sample_terms(),
+            })
+            .to_html(),
+        );
+        assert_attr(&dom, "ul a", "href", "/medium/ink/");
+    }
+
+    #[test]
+    fn term_links_are_tags() {
+        let mut terms = Terms::new();
+        terms.insert("medium".to_string(), vec!["Ink".to_string()]);
+        let html = view! { <TermLinks terms=terms /> }.to_html();
+        assert_attr(&parse(&html), "a[rel=tag]", "href", "/medium/ink/");
+        let none = view! { <TermLinks terms=Terms::new() /> }.to_html();
+        assert!(!parse(&none).exists("dl"));
+    }
+}
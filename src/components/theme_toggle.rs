@@ -0,0 +1,34 @@
+//! # Theme Toggle Component
+//!
+//! The button that switches between the light and dark palettes, rendered
+//! in the footer of `theme-toggle` builds (see [`crate::theme`]). It is
+//! `hidden` in the markup; the theme script reveals it and keeps
+//! `aria-pressed` in step with the palette shown.
+
+use crate::css;
+use crate::theme::TOGGLE_ID;
+use leptos::prelude::*;
+
+/// The theme toggle button.
+#[component]
+pub fn ThemeToggle() -> impl IntoView {
+    view! {
+        <button type="button" id=TOGGLE_ID class=css::THEME_TOGGLE aria-pressed="false" hidden>
+            "Light theme"
+        </button>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, assert_exists, parse};
+
+    #[test]
+    fn toggle_is_hidden_until_scripted() {
+        let dom = parse(&ThemeToggle().to_html());
+        assert_exists(&dom, "button#theme-toggle[hidden]");
+        assert_attr(&dom, "button", "type", "button");
+        assert_attr(&dom, "button", "aria-pressed", "false");
+    }
+}
@@ -0,0 +1,61 @@
+//! # Time Component
+//!
+//! A `<time>` element pairing a machine-readable `datetime` with the date
+//! as readers of the site language expect it (see [`crate::datetime`]).
+//! Microformats2 and microdata ride along through `class` and `itemprop`,
+//! e.g. `dt-published` and `datePublished`; every date on the site is an
+//! mf2 property, so `class` is required.
+
+use crate::config::SITE_LANGUAGE;
+use crate::datetime::Date;
+use leptos::prelude::*;
+
+/// Renders `datetime`, an ISO 8601 date or timestamp, as a `<time>`
+/// element showing its date. Values that aren't dates are shown as given.
+#[component]
+pub fn Time(
+    datetime: String,
+    class: &'static str,
+    #[prop(optional)] itemprop: &'static str,
+) -> impl IntoView {
+    let label = Date::parse(&datetime).map_or_else(|| datetime.clone(), |d| d.human(SITE_LANGUAGE));
+    view! {
+        <time
+            class=class
+            itemprop=(!itemprop.is_empty()).then_some(itemprop)
+            datetime=datetime
+        >
+            {label}
+        </time>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    #[test]
+    fn time_shows_the_human_date() {
+        let html = view! {
+            <Time datetime="2025-01-17T10:00:00Z".to_string() class="dt-published" itemprop="dateCreated" />
+        }
+        .to_html();
+        let dom = parse(&html);
+        assert_attr(
+            &dom,
+            "time.dt-published",
+            "datetime",
+            "2025-01-17T10:00:00Z",
+        );
+        assert_attr(&dom, "time", "itemprop", "dateCreated");
+        assert_eq!(dom.text("time").as_deref(), Some("January 17, 2025"));
+    }
+
+    #[test]
+    fn unset_itemprop_is_omitted() {
+        let html = view! { <Time datetime="soon".to_string() class="dt-start" /> }.to_html();
+        assert!(!html.contains("itemprop"), "{}", html);
+        assert!(html.contains(">soon</time>"), "{}", html);
+    }
+}
@@ -0,0 +1,118 @@
+//! # Table of Contents Component
+//!
+//! A "Contents" menu for long posts, generated from the headings of the
+//! Markdown body (see [`crate::markdown::headings`]) and nested the way
+//! they are: a `###` after a `##` is listed under it. Headings deeper than
+//! [`TOC_DEPTH`] are left out.
+//!
+//! Posts opt in with `toc = true` in their frontmatter.
+//!
+//! [`TOC_DEPTH`]: crate::config::TOC_DEPTH
+
+use crate::config::TOC_DEPTH;
+use crate::css;
+use crate::markdown::Heading;
+use leptos::prelude::*;
+
+/// A heading and the headings under it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TocEntry {
+    pub heading: Heading,
+    pub children: Vec<TocEntry>,
+}
+
+/// The tree of `headings` from level 2 to [`TOC_DEPTH`]. A heading goes
+/// under the last heading above it of a shallower level.
+pub fn toc_tree(headings: &[Heading]) -> Vec<TocEntry> {
+    fn insert(entries: &mut Vec<TocEntry>, heading: &Heading) {
+        match entries.last_mut() {
+            Some(last) if last.heading.level < heading.level => insert(&mut last.children, heading),
+            _ => entries.push(TocEntry {
+                heading: heading.clone(),
+                children: Vec::new(),
+            }),
+        }
+    }
+    let mut tree = Vec::new();
+    for heading in headings
+        .iter()
+        .filter(|h| (2..=TOC_DEPTH).contains(&h.level))
+    {
+        insert(&mut tree, heading);
+    }
+    tree
+}
+
+/// One level of the menu.
+fn toc_list(entries: Vec<TocEntry>) -> AnyView {
+    view! {
+        <ol>
+            {entries
+                .into_iter()
+                .map(|entry| view! {
+                    <li>
+                        <a href=format!("#{}", entry.heading.id)>{entry.heading.text}</a>
+                        {(!entry.children.is_empty()).then(|| toc_list(entry.children))}
+                    </li>
+                })
+                .collect::<Vec<_>>()}
+        </ol>
+    }
+    .into_any()
+}
+
+/// The menu. Renders nothing for fewer than two headings.
+#[component]
+pub fn Toc(headings: Vec<Heading>) -> impl IntoView {
+    let tree = toc_tree(&headings);
+    let count = headings
+        .iter()
+        .filter(|h| (2..=TOC_DEPTH).contains(&h.level))
+        .count();
+    (count > 1).then(|| {
+        view! {
+            <nav class=css::TOC aria-labelledby="toc-title">
+                <h2 id="toc-title">"Contents"</h2>
+                {toc_list(tree)}
+            </nav>
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::headings;
+    use crate::test_utils::parse;
+
+    const BODY: &str = "## One\n\n### One.a\n\n#### Deep\n\n### One.b\n\n## Two\n";
+
+    #[test]
+    fn headings_nest_by_level() {
+        let tree = toc_tree(&headings(BODY));
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].heading.id, "one");
+        let children: Vec<&str> = tree[0]
+            .children
+            .iter()
+            .map(|e| e.heading.id.as_str())
+            .collect();
+        assert_eq!(children, ["one-a", "one-b"]);
+        assert!(tree[0].children[0].children.is_empty());
+    }
+
+    #[test]
+    fn menu_links_headings() {
+        let html = view! { <Toc headings=headings(BODY) /> }.to_html();
+        let dom = parse(&html);
+        assert_eq!(dom.attrs("nav > ol > li > a", "href"), ["#one", "#two"]);
+        assert_eq!(dom.count("nav ol ol a"), 2);
+        assert_eq!(dom.attrs("nav", "aria-labelledby"), ["toc-title"]);
+    }
+
+    #[test]
+    fn single_heading_has_no_menu() {
+        let html = view! { <Toc headings=headings("## Only\n\nText.\n") /> }.to_html();
+        assert!(!parse(&html).exists("nav"));
+    }
+}
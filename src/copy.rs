@@ -0,0 +1,317 @@
+//! # Copying Assets
+//!
+//! `public/` (and a related site's `public_dir`) is copied into the output
+//! as it is, with three choices set in
+//! [`ASSET_COPY`](crate::config::ASSET_COPY):
+//!
+//! - **Symlinks** are followed, copying what they point to, or preserved
+//!   as links with the same target. A followed link back to a directory
+//!   being copied is an error rather than an endless copy.
+//! - **Ignored names** such as `.DS_Store` or `*.psd` working files are
+//!   skipped, files and directories alike. `*` matches any run of
+//!   characters.
+//! - **Permissions** of files and directories are kept, so an executable
+//!   script stays executable and a read-only file stays read-only. The
+//!   destination itself keeps its own, since later steps write to it.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What a copy does with a symbolic link.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symlinks {
+    /// Copy the file or directory the link points to.
+    Follow,
+    /// Recreate the link, pointing where the original does.
+    Preserve,
+}
+
+/// How [`copy_dir_contents`] copies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CopyOptions {
+    pub symlinks: Symlinks,
+    /// Patterns of file and directory names left out, e.g. `*.psd`.
+    pub ignore: &'static [&'static str],
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of
+/// characters, including none.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*`: the whole name must match
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Whether `name` matches any of the `ignore` patterns.
+pub fn is_ignored(name: &str, ignore: &[&str]) -> bool {
+    ignore.iter().any(|pattern| matches(pattern, name))
+}
+
+/// Copies everything in `src` into `dst`, creating it, as `options` say.
+pub fn copy_dir_contents(src: &Path, dst: &Path, options: &CopyOptions) -> io::Result<()> {
+    copy_dir_contents_except(src, dst, options, &[])
+}
+
+/// Copies everything in `src` into `dst` but the paths in `except`, given
+/// as found under `src` (e.g. `public/art/draft`).
+pub fn copy_dir_contents_except(
+    src: &Path,
+    dst: &Path,
+    options: &CopyOptions,
+    except: &[&Path],
+) -> io::Result<()> {
+    copy_dir(src, dst, options, except, &mut Vec::new())
+}
+
+/// Copies the directory `src` to `dst`; `ancestors` are the canonical
+/// paths of the directories being copied, to catch followed links back
+/// into them.
+fn copy_dir(
+    src: &Path,
+    dst: &Path,
+    options: &CopyOptions,
+    except: &[&Path],
+    ancestors: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let canonical = src.canonicalize()?;
+    if ancestors.contains(&canonical) {
+        return Err(io::Error::other(format!(
+            "{} links back to a directory being copied",
+            src.display()
+        )));
+    }
+    ancestors.push(canonical);
+    fs::create_dir_all(dst)?;
+    // A read-only directory from the last build is refilled first
+    make_writable(dst)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        if is_ignored(&entry.file_name().to_string_lossy(), options.ignore) {
+            continue;
+        }
+        let src_path = entry.path();
+        if except.contains(&src_path.as_path()) {
+            continue;
+        }
+        let dst_path = dst.join(entry.file_name());
+
+        if entry.file_type()?.is_symlink() && options.symlinks == Symlinks::Preserve {
+            copy_link(&src_path, &dst_path)?;
+        } else if src_path.is_dir() {
+            copy_dir(&src_path, &dst_path, options, except, ancestors)?;
+            // Last, so a read-only directory is still written to
+            fs::set_permissions(&dst_path, fs::metadata(&src_path)?.permissions())?;
+        } else {
+            if fs::symlink_metadata(&dst_path).is_ok_and(|m| m.permissions().readonly()) {
+                fs::remove_file(&dst_path)?;
+            }
+            // Copies the permission bits too
+            fs::copy(&src_path, &dst_path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", src_path.display(), e)))?;
+        }
+    }
+
+    ancestors.pop();
+    Ok(())
+}
+
+/// Recreates the link at `src` as `dst`, replacing what is there.
+fn copy_link(src: &Path, dst: &Path) -> io::Result<()> {
+    let target = fs::read_link(src)?;
+    if let Ok(meta) = fs::symlink_metadata(dst) {
+        match meta.is_dir() {
+            true => fs::remove_dir_all(dst)?,
+            false => fs::remove_file(dst)?,
+        }
+    }
+    symlink(&target, dst, src.is_dir())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path, _dir: bool) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path, dir: bool) -> io::Result<()> {
+    match dir {
+        true => std::os::windows::fs::symlink_dir(target, link),
+        false => std::os::windows::fs::symlink_file(target, link),
+    }
+}
+
+/// Lets the owner write to `dir`.
+#[cfg(unix)]
+fn make_writable(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(dir)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o200);
+    fs::set_permissions(dir, permissions)
+}
+
+/// Read-only directories can be written to on Windows.
+#[cfg(not(unix))]
+fn make_writable(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOLLOW: CopyOptions = CopyOptions {
+        symlinks: Symlinks::Follow,
+        ignore: &[".DS_Store", "*.psd"],
+    };
+
+    const PRESERVE: CopyOptions = CopyOptions {
+        symlinks: Symlinks::Preserve,
+        ..FOLLOW
+    };
+
+    /// An empty scratch directory with `src/` holding `a.txt` and
+    /// `img/b.png`.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("esart-copy-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/img")).unwrap();
+        fs::write(dir.join("src/a.txt"), "a").unwrap();
+        fs::write(dir.join("src/img/b.png"), "b").unwrap();
+        dir
+    }
+
+    #[test]
+    fn patterns_match_whole_names() {
+        assert!(matches(".DS_Store", ".DS_Store"));
+        assert!(!matches(".DS_Store", "a.DS_Store"));
+        assert!(matches("*.psd", "cover.psd"));
+        assert!(!matches("*.psd", "cover.psd.png"));
+        assert!(matches("*", ""));
+        assert!(matches("draft-*-v*.ai", "draft-cover-v2.ai"));
+        assert!(!matches("draft-*-v*.ai", "draft-cover.ai"));
+    }
+
+    #[test]
+    fn directories_are_copied_without_ignored_names() {
+        let dir = scratch("ignore");
+        fs::write(dir.join("src/.DS_Store"), "").unwrap();
+        fs::write(dir.join("src/img/cover.psd"), "layers").unwrap();
+        fs::create_dir_all(dir.join("src/old.psd")).unwrap();
+        copy_dir_contents(&dir.join("src"), &dir.join("out"), &FOLLOW).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("out/a.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dir.join("out/img/b.png")).unwrap(), "b");
+        assert!(!dir.join("out/.DS_Store").exists());
+        assert!(!dir.join("out/img/cover.psd").exists());
+        assert!(!dir.join("out/old.psd").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn excepted_paths_are_left_out() {
+        let dir = scratch("except");
+        let src = dir.join("src");
+        let except = src.join("img");
+        copy_dir_contents_except(&src, &dir.join("out"), &FOLLOW, &[&except]).unwrap();
+        assert!(dir.join("out/a.txt").is_file());
+        assert!(!dir.join("out/img").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn followed_links_are_copied_as_what_they_point_to() {
+        use std::os::unix::fs::symlink;
+        let dir = scratch("follow");
+        symlink("a.txt", dir.join("src/link.txt")).unwrap();
+        symlink("img", dir.join("src/images")).unwrap();
+        copy_dir_contents(&dir.join("src"), &dir.join("out"), &FOLLOW).unwrap();
+        let link = dir.join("out/link.txt");
+        assert!(!fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert_eq!(fs::read_to_string(link).unwrap(), "a");
+        assert_eq!(
+            fs::read_to_string(dir.join("out/images/b.png")).unwrap(),
+            "b"
+        );
+
+        // A link back to a parent would copy forever
+        symlink("..", dir.join("src/img/up")).unwrap();
+        let err = copy_dir_contents(&dir.join("src"), &dir.join("loop"), &FOLLOW).unwrap_err();
+        assert!(err.to_string().contains("links back"), "{}", err);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn preserved_links_keep_their_targets() {
+        use std::os::unix::fs::symlink;
+        let dir = scratch("preserve");
+        symlink("a.txt", dir.join("src/link.txt")).unwrap();
+        symlink("..", dir.join("src/img/up")).unwrap();
+        fs::create_dir_all(dir.join("out")).unwrap();
+        fs::write(dir.join("out/link.txt"), "stale").unwrap();
+        copy_dir_contents(&dir.join("src"), &dir.join("out"), &PRESERVE).unwrap();
+        assert_eq!(
+            fs::read_link(dir.join("out/link.txt")).unwrap(),
+            Path::new("a.txt")
+        );
+        assert_eq!(
+            fs::read_link(dir.join("out/img/up")).unwrap(),
+            Path::new("..")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn permissions_are_kept() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = scratch("permissions");
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        let set = |path: &Path, mode: u32| {
+            fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap()
+        };
+        set(&dir.join("src/a.txt"), 0o755);
+        set(&dir.join("src/img/b.png"), 0o444);
+        set(&dir.join("src/img"), 0o555);
+        copy_dir_contents(&dir.join("src"), &dir.join("out"), &FOLLOW).unwrap();
+        assert_eq!(mode(&dir.join("out/a.txt")), 0o755);
+        assert_eq!(mode(&dir.join("out/img/b.png")), 0o444);
+        assert_eq!(mode(&dir.join("out/img")), 0o555);
+        set(&dir.join("src/img"), 0o755);
+        set(&dir.join("out/img"), 0o755);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_read_only_source_leaves_the_destination_writable() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = scratch("root");
+        let set = |path: &Path, mode: u32| {
+            fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap()
+        };
+        set(&dir.join("src"), 0o555);
+        copy_dir_contents(&dir.join("src"), &dir.join("out"), &FOLLOW).unwrap();
+        let mode = fs::metadata(dir.join("out")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o200, 0o200);
+        fs::write(dir.join("out/main.css"), "body {}").unwrap();
+        set(&dir.join("src"), 0o755);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
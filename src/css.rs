@@ -0,0 +1,293 @@
+//! # Stylesheet Classes
+//!
+//! Constants for every class selector in `style/main.css`, generated by
+//! `build.rs` (`.link-card` → [`LINK_CARD`]). Components use these instead of
+//! string literals, so renaming or deleting a rule breaks the build rather
+//! than silently unstyling the page.
+//!
+//! Microformats2 classes (`h-card`, `p-name`, `u-url`, ...) are markup
+//! semantics, not styling, and stay literal unless the stylesheet targets
+//! them.
+//!
+//! The reverse direction — rules nothing renders — is caught by a test that
+//! renders every page type and checks [`unused_classes`].
+
+use scraper::{Html, Selector};
+use std::collections::BTreeSet;
+
+include!(concat!(env!("OUT_DIR"), "/css_classes.rs"));
+
+/// Classes added at runtime by `public/js`, so never present in static HTML.
+pub const SCRIPT_CLASSES: &[&str] = &[VISIBLE];
+
+/// Joins several classes into one `class` attribute value.
+pub fn join(classes: &[&str]) -> String {
+    classes.join(" ")
+}
+
+/// Every class used in `class` attributes of an HTML document.
+pub fn rendered_classes(html: &str) -> BTreeSet<String> {
+    let doc = Html::parse_document(html);
+    let any = Selector::parse("[class]").expect("static selector is valid");
+    doc.select(&any)
+        .flat_map(|el| el.value().classes().map(str::to_string).collect::<Vec<_>>())
+        .collect()
+}
+
+/// Stylesheet classes that none of `pages` (rendered HTML) use.
+pub fn unused_classes<'a>(pages: impl IntoIterator<Item = &'a str>) -> Vec<&'static str> {
+    let used: BTreeSet<String> = pages.into_iter().flat_map(rendered_classes).collect();
+    ALL.iter()
+        .copied()
+        .filter(|class| !used.contains(*class) && !SCRIPT_CLASSES.contains(class))
+        .collect()
+}
+
+/// Returns true for Microformats2 class names (`h-*`, `p-*`, `u-*`, `dt-*`,
+/// `e-*`).
+pub fn is_microformat(class: &str) -> bool {
+    ["h-", "p-", "u-", "dt-", "e-"]
+        .iter()
+        .any(|prefix| class.starts_with(prefix))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::archive::{ArchivedLinks, Snapshot};
+    use crate::art::{ArtImage, ArtSeries};
+    use crate::components::{
+        ActivityPage, ActivityPageProps, ArtIndexPage, ArtIndexPageProps, ArtSeriesPage,
+        ArtSeriesPageProps, CardPage, CardPageProps, ChangelogPage, ChangelogPageProps, CvPage,
+        CvPageProps, Email, EmailProps, GuestbookPage, LogPage, LogPageProps, MusicPage,
+        MusicPageProps, PostPage, PostPageProps, PostsPage, PostsPageProps, PressPage,
+        PressPageProps, PrintsPage, PrintsPageProps, ProtectedPage, ProtectedPageProps,
+        ReleasePage, ReleasePageProps, SigilPage, TaxonomyPage, TaxonomyPageProps, TermPage,
+        TermPageProps, ThemeToggle,
+    };
+    use crate::favicon::Favicons;
+    use crate::github::ContributionDay;
+    use crate::listens::Listen;
+    use crate::press::{PressFile, PressKit};
+    use crate::App;
+    use leptos::prelude::*;
+
+    fn sample_listen() -> Listen {
+        Listen {
+            track: "Track".to_string(),
+            artist: "Artist".to_string(),
+            release: None,
+            url: None,
+            now_playing: true,
+        }
+    }
+
+    fn sample_series() -> ArtSeries {
+        ArtSeries {
+            slug: "sample".to_string(),
+            source: "public/art/sample".into(),
+            title: "Sample".to_string(),
+            description: "A sample series.".to_string(),
+            date: "2025-01-01".to_string(),
+            cover_url: "/art/sample/one.jpg".to_string(),
+            cover_placeholder: None,
+            images: vec![ArtImage {
+                url: "/art/sample/one.jpg".to_string(),
+                alt: "One".to_string(),
+                title: Some("One".to_string()),
+                description: Some("The first image.".to_string()),
+                placeholder: None,
+            }],
+            comments: None,
+            nostr_note: None,
+            embeds: Vec::new(),
+            apple_music: None,
+            unlisted: false,
+        }
+    }
+
+    fn sample_archive() -> ArchivedLinks {
+        let mut archived = ArchivedLinks::default();
+        archived.record(
+            crate::components::LINKS[0].href,
+            Snapshot {
+                snapshot: "https://web.archive.org/web/1/https://a.test".to_string(),
+                archived_at: "2025-01-01T00:00:00Z".to_string(),
+            },
+        );
+        archived
+    }
+
+    fn sample_icons() -> Favicons {
+        let mut icons = Favicons::default();
+        icons.insert(
+            crate::components::LINKS[0].href,
+            "/icons/a.test.png".to_string(),
+        );
+        icons
+    }
+
+    pub fn rendered_pages() -> Vec<String> {
+        let series = vec![sample_series()];
+        vec![
+            view! {
+                <App
+                    listens=vec![sample_listen()]
+                    archived=sample_archive()
+                    icons=sample_icons()
+                    prints=true
+                    activity=true
+                />
+            }
+            .to_html(),
+            ArtIndexPage(ArtIndexPageProps {
+                series: series.clone(),
+            })
+            .to_html(),
+            ArtSeriesPage(ArtSeriesPageProps {
+                series: series[0].clone(),
+                release: Some(crate::apple_music::tests::sample_item()),
+                embeds: vec![crate::embeds::tests::sample_embed()],
+                reactions: crate::reactions::tests::sample_reactions(),
+                comments: Some(crate::mastodon::tests::sample_comments()),
+                mention_endpoint: None,
+            })
+            .to_html(),
+            SigilPage().to_html(),
+            CvPage(CvPageProps {
+                resume: crate::resume::tests::sample_resume(),
+            })
+            .to_html(),
+            PressPage(PressPageProps {
+                kit: PressKit {
+                    short_bio: "Short.".to_string(),
+                    long_bio: "Long.".to_string(),
+                    assets: vec![],
+                },
+                files: vec![PressFile {
+                    name: "Logo".to_string(),
+                    file_name: "logo.svg".to_string(),
+                    encoding_format: "image/svg+xml",
+                    dimensions: None,
+                    bytes: 1,
+                    sha256: String::new(),
+                }],
+            })
+            .to_html(),
+            ActivityPage(ActivityPageProps {
+                days: vec![ContributionDay {
+                    date: "2025-01-05".to_string(),
+                    level: 1,
+                    count: 1,
+                }],
+            })
+            .to_html(),
+            ChangelogPage(ChangelogPageProps {
+                changes: crate::changelog::tests::sample_changes(),
+            })
+            .to_html(),
+            view! {
+                <GuestbookPage
+                    mentions=crate::webmention::tests::sample_mentions()
+                    url="https://a.test/guestbook/".to_string()
+                    endpoint="https://a.test/webmention"
+                />
+            }
+            .to_html(),
+            MusicPage(MusicPageProps {
+                releases: crate::music::tests::sample_releases(),
+            })
+            .to_html(),
+            ReleasePage(ReleasePageProps {
+                release: crate::music::tests::sample_releases().releases[0].clone(),
+                mention_endpoint: None,
+            })
+            .to_html(),
+            PrintsPage(PrintsPageProps {
+                prints: crate::prints::tests::sample_prints(),
+                shop: "https://shop.test",
+            })
+            .to_html(),
+            ProtectedPage(ProtectedPageProps {
+                title: "Preview".to_string(),
+                sealed: crate::protected::tests::sample_sealed(),
+            })
+            .to_html(),
+            CardPage(CardPageProps {
+                card: crate::cards::tests::sample_manifest().cards[0].clone(),
+                currency: "USD".to_string(),
+                site: crate::site::tests::sample_site(),
+            })
+            .to_html(),
+            LogPage(LogPageProps {
+                log: crate::media::tests::sample_log(),
+            })
+            .to_html(),
+            Email(EmailProps {
+                address: "hello@example.com",
+                machine_readable: false,
+            })
+            .to_html(),
+            ThemeToggle().to_html(),
+            PostsPage(PostsPageProps {
+                entries: crate::posts::tests::sample_posts().entries,
+                pagination: crate::pagination::Pagination::new(&[crate::config::POSTS.dir], 2, 3),
+                base: crate::config::POSTS.listing_path(),
+                title: crate::config::POSTS.title,
+                description: crate::config::POSTS.description,
+            })
+            .to_html(),
+            TaxonomyPage(TaxonomyPageProps {
+                taxonomy: crate::taxonomy::tests::MEDIUM,
+                terms: crate::taxonomy::tests::sample_terms(),
+            })
+            .to_html(),
+            TermPage(TermPageProps {
+                taxonomy: crate::taxonomy::tests::MEDIUM,
+                term: crate::taxonomy::tests::sample_terms()[0].clone(),
+            })
+            .to_html(),
+            PostPage(PostPageProps {
+                post: crate::posts::tests::sample_posts().entries[1].clone(),
+                listing: crate::config::POSTS.listing_path(),
+                listing_title: crate::config::POSTS.title,
+            })
+            .to_html(),
+        ]
+    }
+
+    #[test]
+    fn generated_constants_match_stylesheet() {
+        assert_eq!(LINK_CARD, "link-card");
+        assert!(ALL.contains(&SITE_NAV_HOME));
+        assert!(!ALL.contains(&"art"), "Comments must not yield classes");
+    }
+
+    #[test]
+    fn rendered_styling_classes_are_defined() {
+        for page in rendered_pages() {
+            for class in rendered_classes(&page) {
+                assert!(
+                    is_microformat(&class) || ALL.contains(&class.as_str()),
+                    "class \"{}\" is rendered but not defined in style/main.css",
+                    class
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stylesheet_has_no_dead_classes() {
+        let pages = rendered_pages();
+        let unused = unused_classes(pages.iter().map(String::as_str));
+        assert!(unused.is_empty(), "Unused stylesheet classes: {:?}", unused);
+    }
+
+    #[test]
+    fn unused_classes_reports_unrendered_rules() {
+        let unused = unused_classes([r#"<a class="link-card">x</a>"#]);
+        assert!(unused.contains(&LINK_LABEL));
+        assert!(!unused.contains(&LINK_CARD));
+        assert!(!unused.contains(&VISIBLE), "Script classes are exempt");
+    }
+}
@@ -0,0 +1,354 @@
+//! # Dates and Times
+//!
+//! Every date the site outputs is formatted here, in one of two forms:
+//!
+//! - **Machine**: ISO 8601 dates (`2025-01-17`) and RFC 3339 UTC timestamps
+//!   (`2025-01-17T12:00:00Z`) for `datetime` attributes, JSON-LD, and
+//!   status documents; RFC 822 (`Fri, 17 Jan 2025 00:00:00 +0000`) for RSS.
+//! - **Human**: the date in the page language, e.g. `January 17, 2025` or
+//!   `17. Januar 2025` (see [`Date::human`]).
+//!
+//! Content dates are calendar dates without a time zone; timestamps are
+//! UTC. Components render dates with [`crate::components::Time`], which
+//! pairs both forms in a `<time datetime=...>` element, instead of
+//! formatting them by hand.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Full month names per language, January first.
+const MONTH_NAMES: &[(&str, [&str; 12])] = &[
+    (
+        "en",
+        [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+    ),
+    (
+        "de",
+        [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+    ),
+    (
+        "fr",
+        [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+    ),
+    (
+        "es",
+        [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+    ),
+    (
+        "ar",
+        [
+            "يناير",
+            "فبراير",
+            "مارس",
+            "أبريل",
+            "مايو",
+            "يونيو",
+            "يوليو",
+            "أغسطس",
+            "سبتمبر",
+            "أكتوبر",
+            "نوفمبر",
+            "ديسمبر",
+        ],
+    ),
+    (
+        "he",
+        [
+            "ינואר",
+            "פברואר",
+            "מרץ",
+            "אפריל",
+            "מאי",
+            "יוני",
+            "יולי",
+            "אוגוסט",
+            "ספטמבר",
+            "אוקטובר",
+            "נובמבר",
+            "דצמבר",
+        ],
+    ),
+];
+
+/// A calendar date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i64,
+    /// `1` to `12`.
+    pub month: u32,
+    /// `1` to the length of the month.
+    pub day: u32,
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+impl Date {
+    /// The date, or `None` if it doesn't exist (e.g. February 30).
+    pub fn new(year: i64, month: u32, day: u32) -> Option<Self> {
+        let days_in_month = match month {
+            2 if is_leap(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
+        (1..=days_in_month)
+            .contains(&day)
+            .then_some(Date { year, month, day })
+    }
+
+    /// Parses the `YYYY-MM-DD` date at the start of an ISO 8601 date or
+    /// timestamp, e.g. `2025-01-17` or `2025-01-17T10:00:00.000Z`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let date = text.get(..10)?;
+        if text.len() > 10 && !text[10..].starts_with(['T', 't', ' ']) {
+            return None;
+        }
+        let mut parts = date.splitn(3, '-');
+        let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+        if y.len() != 4 || m.len() != 2 || d.len() != 2 {
+            return None;
+        }
+        Self::new(y.parse().ok()?, m.parse().ok()?, d.parse().ok()?)
+    }
+
+    /// The UTC date of `seconds` since the Unix epoch.
+    pub fn from_unix(seconds: i64) -> Self {
+        // Civil from days (Howard Hinnant)
+        let z = seconds.div_euclid(86_400) + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        Date {
+            year: yoe + era * 400 + i64::from(month <= 2),
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    /// Days since 1970-01-01.
+    fn days(&self) -> i64 {
+        // Days from civil (Howard Hinnant)
+        let (m, d) = (i64::from(self.month), i64::from(self.day));
+        let y = if m <= 2 { self.year - 1 } else { self.year };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Day of the week, `0` = Sunday.
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (self.days() + 4).rem_euclid(7) as u32
+    }
+
+    /// ISO 8601, e.g. `2025-01-17`.
+    pub fn iso(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// RFC 822 at midnight UTC, as RSS expects, e.g.
+    /// `Fri, 17 Jan 2025 00:00:00 +0000`.
+    pub fn rfc822(&self) -> String {
+        format!(
+            "{}, {:02} {} {} 00:00:00 +0000",
+            WEEKDAYS[self.weekday() as usize],
+            self.day,
+            MONTHS[self.month as usize - 1],
+            self.year
+        )
+    }
+
+    /// The date for readers of the BCP 47 language `lang`, e.g.
+    /// `January 17, 2025` in `en`, `17 January 2025` in `en-GB`, or
+    /// `17. Januar 2025` in `de`. Languages without month names here get
+    /// the ISO date.
+    pub fn human(&self, lang: &str) -> String {
+        let mut subtags = lang.split(['-', '_']);
+        let primary = subtags.next().unwrap_or_default().to_ascii_lowercase();
+        let region = subtags.next().unwrap_or_default().to_ascii_uppercase();
+        let Some((_, names)) = MONTH_NAMES.iter().find(|(l, _)| *l == primary) else {
+            return self.iso();
+        };
+        let (day, month, year) = (self.day, names[self.month as usize - 1], self.year);
+        match primary.as_str() {
+            "en" if region.is_empty() || region == "US" => {
+                format!("{} {}, {}", month, day, year)
+            }
+            "de" => format!("{}. {} {}", day, month, year),
+            "fr" if day == 1 => format!("1er {} {}", month, year),
+            "es" => format!("{} de {} de {}", day, month, year),
+            _ => format!("{} {} {}", day, month, year),
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    /// Formats as ISO 8601.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.iso())
+    }
+}
+
+/// Formats seconds since the Unix epoch as an ISO date (UTC).
+pub fn iso_date(seconds: i64) -> String {
+    Date::from_unix(seconds).iso()
+}
+
+/// Converts an ISO date (`YYYY-MM-DD`) to an RFC 822 date at midnight UTC.
+pub fn rfc822_date(date: &str) -> Option<String> {
+    Date::parse(date).map(|date| date.rfc822())
+}
+
+/// Formats a system time as an RFC 3339 UTC timestamp, e.g.
+/// `2025-01-17T12:00:00Z`.
+pub fn rfc3339(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let secs = seconds.rem_euclid(86_400);
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        iso_date(seconds),
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn date(text: &str) -> Date {
+        Date::parse(text).unwrap()
+    }
+
+    #[test]
+    fn dates_and_timestamps_parse() {
+        assert_eq!(date("2025-01-17"), Date::new(2025, 1, 17).unwrap());
+        assert_eq!(date("2025-01-17T10:00:00.000Z").day, 17);
+        assert_eq!(Date::parse("2024-02-29").map(|d| d.month), Some(2));
+        assert_eq!(Date::parse("2025-02-29"), None);
+        assert_eq!(Date::parse("2025-13-01"), None);
+        assert_eq!(Date::parse("2025-1-7"), None);
+        assert_eq!(Date::parse("2025-01-170"), None);
+        assert_eq!(Date::parse("soon"), None);
+    }
+
+    #[test]
+    fn epoch_seconds_format_as_dates() {
+        assert_eq!(iso_date(0), "1970-01-01");
+        assert_eq!(iso_date(1_709_164_800), "2024-02-29");
+        assert_eq!(iso_date(1_737_158_399), "2025-01-17");
+        assert_eq!(Date::from_unix(-86_400).to_string(), "1969-12-31");
+    }
+
+    #[test]
+    fn weekdays_count_from_sunday() {
+        assert_eq!(date("1970-01-01").weekday(), 4);
+        assert_eq!(date("2025-01-05").weekday(), 0);
+        assert_eq!(date("2024-02-29").weekday(), 4);
+    }
+
+    #[test]
+    fn converts_iso_dates_to_rfc822() {
+        assert_eq!(
+            rfc822_date("2025-01-17").as_deref(),
+            Some("Fri, 17 Jan 2025 00:00:00 +0000")
+        );
+        assert_eq!(
+            rfc822_date("2024-02-29").as_deref(),
+            Some("Thu, 29 Feb 2024 00:00:00 +0000")
+        );
+        assert_eq!(rfc822_date("2025-13-01"), None);
+    }
+
+    #[test]
+    fn timestamps_are_rfc3339_utc() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_737_115_200 + 3_723);
+        assert_eq!(rfc3339(time), "2025-01-17T13:02:03Z");
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn human_dates_follow_the_language() {
+        let d = date("2025-01-17");
+        assert_eq!(d.human("en"), "January 17, 2025");
+        assert_eq!(d.human("en-US"), "January 17, 2025");
+        assert_eq!(d.human("en-GB"), "17 January 2025");
+        assert_eq!(d.human("de-AT"), "17. Januar 2025");
+        assert_eq!(d.human("fr"), "17 janvier 2025");
+        assert_eq!(date("2025-03-01").human("fr"), "1er mars 2025");
+        assert_eq!(d.human("es"), "17 de enero de 2025");
+        assert_eq!(d.human("ar"), "17 يناير 2025");
+        assert_eq!(d.human("ja"), "2025-01-17");
+    }
+}
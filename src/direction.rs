@@ -0,0 +1,216 @@
+//! # Text Direction
+//!
+//! Right-to-left support for translated pages (Arabic, Hebrew, ...). Two
+//! halves make a page lay out correctly in either direction:
+//!
+//! - The document declares its direction: [`html_open`] adds `dir="rtl"` to
+//!   the `<html>` tag for right-to-left languages.
+//! - The stylesheet never hardcodes a side: [`logical_properties`] rewrites
+//!   physical declarations (`margin-left`, `right`, `text-align: left`, ...)
+//!   into their flow-relative equivalents (`margin-inline-start`,
+//!   `inset-inline-end`, `text-align: start`) when `main.css` is built, so
+//!   one stylesheet serves both directions and `style/main.css` can be
+//!   written naturally.
+
+/// Primary language subtags written right to left.
+pub const RTL_LANGUAGES: &[&str] = &["ar", "ckb", "dv", "fa", "he", "ps", "sd", "ug", "ur", "yi"];
+
+/// Inline base direction of a document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    /// The direction of the BCP 47 language tag `lang`, e.g. `ar-EG` is
+    /// right to left.
+    pub fn for_lang(lang: &str) -> Self {
+        let primary = lang.split(['-', '_']).next().unwrap_or_default();
+        if RTL_LANGUAGES
+            .iter()
+            .any(|rtl| rtl.eq_ignore_ascii_case(primary))
+        {
+            Direction::Rtl
+        } else {
+            Direction::Ltr
+        }
+    }
+
+    /// The `dir` attribute value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+        }
+    }
+}
+
+/// The opening `<html>` tag of a document in `lang`. Left-to-right
+/// documents omit `dir`, which defaults to `ltr`.
+pub fn html_open(lang: &str) -> String {
+    match Direction::for_lang(lang) {
+        Direction::Ltr => format!(r#"<html lang="{}">"#, lang),
+        dir => format!(r#"<html lang="{}" dir="{}">"#, lang, dir.as_str()),
+    }
+}
+
+/// Physical property names and their flow-relative equivalents. Longer
+/// names come first so `border-left-width` isn't matched as `border-left`.
+const PROPERTIES: &[(&str, &str)] = &[
+    ("margin-left", "margin-inline-start"),
+    ("margin-right", "margin-inline-end"),
+    ("padding-left", "padding-inline-start"),
+    ("padding-right", "padding-inline-end"),
+    ("border-left-width", "border-inline-start-width"),
+    ("border-right-width", "border-inline-end-width"),
+    ("border-left-style", "border-inline-start-style"),
+    ("border-right-style", "border-inline-end-style"),
+    ("border-left-color", "border-inline-start-color"),
+    ("border-right-color", "border-inline-end-color"),
+    ("border-left", "border-inline-start"),
+    ("border-right", "border-inline-end"),
+    ("border-top-left-radius", "border-start-start-radius"),
+    ("border-top-right-radius", "border-start-end-radius"),
+    ("border-bottom-left-radius", "border-end-start-radius"),
+    ("border-bottom-right-radius", "border-end-end-radius"),
+    ("left", "inset-inline-start"),
+    ("right", "inset-inline-end"),
+];
+
+/// Rewrites one declaration (`name: value`), keeping its surrounding
+/// whitespace. Anything that isn't a physical declaration is returned as is.
+fn logical_declaration(declaration: &str) -> String {
+    let Some((name_part, value_part)) = declaration.split_once(':') else {
+        return declaration.to_string();
+    };
+    let name = name_part.trim();
+    let indent = &name_part[..name_part.len() - name_part.trim_start().len()];
+    let value = value_part.trim();
+    let keyword = |value: &str, left: &str, right: &str| match value {
+        "left" => Some(left.to_string()),
+        "right" => Some(right.to_string()),
+        _ => None,
+    };
+
+    let rewritten = match name {
+        "text-align" => keyword(value, "start", "end").map(|v| (name.to_string(), v)),
+        "float" | "clear" => {
+            keyword(value, "inline-start", "inline-end").map(|v| (name.to_string(), v))
+        }
+        "margin" | "padding" => {
+            // Only the four-value form differs between left and right
+            let sides: Vec<&str> = value.split_whitespace().collect();
+            match sides[..] {
+                [top, right, bottom, left] if right != left => {
+                    return format!(
+                        "{indent}{name}-block: {top} {bottom};{indent}{name}-inline: {left} {right}"
+                    );
+                }
+                _ => None,
+            }
+        }
+        _ => PROPERTIES
+            .iter()
+            .find(|(physical, _)| *physical == name)
+            .map(|(_, logical)| (logical.to_string(), value.to_string())),
+    };
+    match rewritten {
+        Some((name, value)) => format!("{}{}: {}", indent, name, value),
+        None => declaration.to_string(),
+    }
+}
+
+/// Rewrites the physical declarations in `css` as flow-relative ones, so
+/// the stylesheet mirrors under `dir="rtl"`. Selectors, at-rule preludes,
+/// and comments are left untouched; four-value `margin`/`padding`
+/// shorthands with different left and right sides become `-block` and
+/// `-inline` pairs.
+pub fn logical_properties(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut segment = String::new();
+    let mut chars = css.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                // Flush the code before the comment, then copy it verbatim
+                out.push_str(&segment);
+                segment.clear();
+                out.push(c);
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '{' => {
+                out.push_str(&segment);
+                out.push(c);
+                segment.clear();
+            }
+            ';' | '}' => {
+                out.push_str(&logical_declaration(&segment));
+                out.push(c);
+                segment.clear();
+            }
+            _ => segment.push(c),
+        }
+    }
+    out.push_str(&segment);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rtl_languages_are_detected() {
+        assert_eq!(Direction::for_lang("ar"), Direction::Rtl);
+        assert_eq!(Direction::for_lang("he-IL"), Direction::Rtl);
+        assert_eq!(Direction::for_lang("FA"), Direction::Rtl);
+        assert_eq!(Direction::for_lang("en"), Direction::Ltr);
+        assert_eq!(Direction::for_lang("arn"), Direction::Ltr);
+        assert_eq!(html_open("en"), r#"<html lang="en">"#);
+        assert_eq!(html_open("ar"), r#"<html lang="ar" dir="rtl">"#);
+    }
+
+    #[test]
+    fn physical_declarations_become_logical() {
+        let css = "\
+/* margin-left: 1px; keeps comments */
+.a:hover, [dir] > .b {
+  position: fixed;
+  right: 1rem;
+  margin-right: var(--spacing-xs);
+  border-left-color: red;
+  text-align: left;
+  float: right;
+  padding: 0 1rem 0 2rem;
+  margin: 0 auto;
+  text-align: center
+}
+@media (min-width: 40rem) { .c { left: 0; } }
+";
+        let expected = "\
+/* margin-left: 1px; keeps comments */
+.a:hover, [dir] > .b {
+  position: fixed;
+  inset-inline-end: 1rem;
+  margin-inline-end: var(--spacing-xs);
+  border-inline-start-color: red;
+  text-align: start;
+  float: inline-end;
+  padding-block: 0 0;
+  padding-inline: 2rem 1rem;
+  margin: 0 auto;
+  text-align: center
+}
+@media (min-width: 40rem) { .c { inset-inline-start: 0; } }
+";
+        assert_eq!(logical_properties(css), expected);
+    }
+}
@@ -0,0 +1,334 @@
+//! # DNS Records
+//!
+//! The records the site's features need, derived from config, for the
+//! `dns-check` command: it prints them as zone-file lines to copy into the
+//! DNS provider, then looks each one up and reports whether live DNS
+//! matches.
+//!
+//! | Record | Config | Purpose |
+//! |--------|--------|---------|
+//! | apex `A` | [`SITE_DOMAIN`] | GitHub Pages (an ALIAS/ANAME flattens to these) |
+//! | `www` `CNAME` | [`GITHUB_USER`] | Redirects `www.` to the apex via GitHub Pages |
+//! | `_keybase` `TXT` | [`KEYBASE_DNS_PROOF`] | Keybase's DNS proof of the domain |
+//! | `default._bimi` `TXT` | [`BIMI_LOGO`] | Brand logo shown next to mail from the domain |
+//! | `_atproto` `TXT` | [`ATPROTO_DID`] | Bluesky handle `@everythingsings.art` |
+//! | any `TXT` | [`DNS_TXT_RECORDS`] | Other verification tokens, e.g. for Nostr relays |
+//!
+//! Lookups go through DNS over HTTPS ([`RESOLVER`]), so results reflect
+//! public DNS rather than the local resolver's cache.
+//!
+//! ```bash
+//! ./target/release/everythingsings dns-check
+//! ```
+//!
+//! [`SITE_DOMAIN`]: crate::config::SITE_DOMAIN
+//! [`KEYBASE_DNS_PROOF`]: crate::config::KEYBASE_DNS_PROOF
+//! [`BIMI_LOGO`]: crate::config::BIMI_LOGO
+//! [`ATPROTO_DID`]: crate::config::ATPROTO_DID
+//! [`DNS_TXT_RECORDS`]: crate::config::DNS_TXT_RECORDS
+
+use crate::cache::fetch_url;
+use crate::config::{ATPROTO_DID, BIMI_LOGO, DNS_TXT_RECORDS, KEYBASE_DNS_PROOF};
+use crate::github::GITHUB_USER;
+use serde_json::Value;
+
+/// JSON endpoint of the DNS-over-HTTPS resolver used for lookups.
+pub const RESOLVER: &str = "https://dns.google/resolve";
+
+/// GitHub Pages' apex addresses.
+pub const PAGES_ADDRESSES: &[&str] = &[
+    "185.199.108.153",
+    "185.199.109.153",
+    "185.199.110.153",
+    "185.199.111.153",
+];
+
+/// What a record should contain.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expected {
+    /// Only addresses from this set, at least one of them.
+    A(&'static [&'static str]),
+    /// An alias of this host.
+    Cname(String),
+    /// One TXT string equal to this.
+    Txt(String),
+}
+
+impl Expected {
+    /// The record type, e.g. `"TXT"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Expected::A(_) => "A",
+            Expected::Cname(_) => "CNAME",
+            Expected::Txt(_) => "TXT",
+        }
+    }
+
+    /// The numeric type DNS answers carry.
+    fn code(&self) -> u64 {
+        match self {
+            Expected::A(_) => 1,
+            Expected::Cname(_) => 5,
+            Expected::Txt(_) => 16,
+        }
+    }
+}
+
+/// One record the site needs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// Fully qualified name, without the trailing dot.
+    pub name: String,
+    pub expected: Expected,
+    pub purpose: &'static str,
+}
+
+impl Record {
+    /// The record as zone-file lines, one per value.
+    pub fn zone_lines(&self) -> Vec<String> {
+        let values = match &self.expected {
+            Expected::A(addresses) => addresses.iter().map(|a| a.to_string()).collect(),
+            Expected::Cname(host) => vec![format!("{}.", host)],
+            Expected::Txt(text) => vec![quote(text)],
+        };
+        values
+            .into_iter()
+            .map(|value| format!("{}. 3600 IN {} {}", self.name, self.expected.kind(), value))
+            .collect()
+    }
+
+    /// Compares the live `answers` (from [`lookup`]) with the expectation.
+    pub fn check(&self, answers: &[String]) -> Result<(), String> {
+        let matches = match &self.expected {
+            Expected::A(addresses) => {
+                !answers.is_empty() && answers.iter().all(|a| addresses.contains(&a.as_str()))
+            }
+            Expected::Cname(host) => answers
+                .iter()
+                .any(|a| a.trim_end_matches('.').eq_ignore_ascii_case(host)),
+            Expected::Txt(text) => answers.iter().any(|a| a == text),
+        };
+        if matches {
+            Ok(())
+        } else if answers.is_empty() {
+            Err("no record".to_string())
+        } else {
+            Err(format!("found {}", answers.join(", ")))
+        }
+    }
+}
+
+/// The configuration the records are derived from.
+#[derive(Clone, Copy, Debug)]
+pub struct Settings<'a> {
+    /// Custom domain of the site.
+    pub domain: &'a str,
+    /// The GitHub Pages host, e.g. `everythingsings.github.io`.
+    pub pages_host: &'a str,
+    pub keybase_dns_proof: Option<&'a str>,
+    pub bimi_logo: Option<&'a str>,
+    pub atproto_did: Option<&'a str>,
+    /// Extra TXT records as `(name, value)`; `@` is the apex.
+    pub txt: &'a [(&'a str, &'a str)],
+}
+
+/// The GitHub Pages host of [`GITHUB_USER`].
+pub fn pages_host() -> String {
+    format!("{}.github.io", GITHUB_USER.to_ascii_lowercase())
+}
+
+impl<'a> Settings<'a> {
+    /// The settings in [`crate::config`] for `domain` served from
+    /// `pages_host`.
+    pub fn from_config(domain: &'a str, pages_host: &'a str) -> Self {
+        Settings {
+            domain,
+            pages_host,
+            keybase_dns_proof: KEYBASE_DNS_PROOF,
+            bimi_logo: BIMI_LOGO,
+            atproto_did: ATPROTO_DID,
+            txt: DNS_TXT_RECORDS,
+        }
+    }
+}
+
+/// The records `settings` needs, apex first.
+pub fn records(settings: &Settings) -> Vec<Record> {
+    let at = |label: &str| match label {
+        "@" => settings.domain.to_string(),
+        _ => format!("{}.{}", label, settings.domain),
+    };
+    let mut records = vec![
+        Record {
+            name: settings.domain.to_string(),
+            expected: Expected::A(PAGES_ADDRESSES),
+            purpose: "GitHub Pages",
+        },
+        Record {
+            name: at("www"),
+            expected: Expected::Cname(settings.pages_host.to_string()),
+            purpose: "www redirect",
+        },
+    ];
+    let mut txt = |label: &str, text: String, purpose| {
+        records.push(Record {
+            name: at(label),
+            expected: Expected::Txt(text),
+            purpose,
+        })
+    };
+    if let Some(proof) = settings.keybase_dns_proof {
+        txt("_keybase", proof.to_string(), "Keybase proof");
+    }
+    if let Some(logo) = settings.bimi_logo {
+        txt(
+            "default._bimi",
+            format!("v=BIMI1; l={};", logo),
+            "BIMI logo",
+        );
+    }
+    if let Some(did) = settings.atproto_did {
+        txt("_atproto", format!("did={}", did), "Bluesky handle");
+    }
+    for (label, value) in settings.txt {
+        txt(label, value.to_string(), "verification");
+    }
+    records
+}
+
+/// `text` as a zone-file string, split into 255-byte chunks.
+fn quote(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    let chunks: Vec<String> = escaped
+        .as_bytes()
+        .chunks(255)
+        .map(|c| format!("\"{}\"", String::from_utf8_lossy(c)))
+        .collect();
+    chunks.join(" ")
+}
+
+/// Joins the quoted strings of a TXT answer, e.g. `"a" "b"` to `ab`.
+fn unquote(data: &str) -> String {
+    if !data.starts_with('"') {
+        return data.to_string();
+    }
+    data.split('"').skip(1).step_by(2).collect()
+}
+
+/// The answers of type `expected.kind()` in a resolver response.
+pub fn parse_answers(json: &str, expected: &Expected) -> Result<Vec<String>, String> {
+    let response: Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid DNS response: {}", e))?;
+    let answers = response["Answer"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    Ok(answers
+        .iter()
+        .filter(|a| a["type"].as_u64() == Some(expected.code()))
+        .filter_map(|a| a["data"].as_str())
+        .map(unquote)
+        .collect())
+}
+
+/// Looks up `record` in public DNS.
+pub fn lookup(record: &Record) -> Result<Vec<String>, String> {
+    let url = format!(
+        "{}?name={}&type={}",
+        RESOLVER,
+        record.name,
+        record.expected.kind()
+    );
+    parse_answers(&fetch_url(&url)?, &record.expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> Settings<'static> {
+        Settings {
+            domain: "a.test",
+            pages_host: "ada.github.io",
+            keybase_dns_proof: Some("keybase-site-verification=abc"),
+            bimi_logo: Some("https://a.test/bimi.svg"),
+            atproto_did: Some("did:plc:z72i7hdynmk6r22z27h6tvur"),
+            txt: &[("@", "token=1")],
+        }
+    }
+
+    fn record(name: &str) -> Record {
+        records(&settings())
+            .into_iter()
+            .find(|r| r.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn records_follow_config() {
+        let names: Vec<String> = records(&settings()).into_iter().map(|r| r.name).collect();
+        assert_eq!(
+            names,
+            [
+                "a.test",
+                "www.a.test",
+                "_keybase.a.test",
+                "default._bimi.a.test",
+                "_atproto.a.test",
+                "a.test"
+            ]
+        );
+        let none = Settings {
+            keybase_dns_proof: None,
+            bimi_logo: None,
+            atproto_did: None,
+            txt: &[],
+            ..settings()
+        };
+        assert_eq!(records(&none).len(), 2);
+    }
+
+    #[test]
+    fn zone_lines_quote_txt() {
+        assert_eq!(
+            record("_atproto.a.test").zone_lines(),
+            ["_atproto.a.test. 3600 IN TXT \"did=did:plc:z72i7hdynmk6r22z27h6tvur\""]
+        );
+        assert_eq!(
+            record("www.a.test").zone_lines(),
+            ["www.a.test. 3600 IN CNAME ada.github.io."]
+        );
+        assert_eq!(record("a.test").zone_lines().len(), PAGES_ADDRESSES.len());
+        assert_eq!(quote(&"x".repeat(300)).matches('"').count(), 4);
+    }
+
+    #[test]
+    fn resolver_answers_are_compared() {
+        let json = r#"{"Status":0,"Answer":[
+            {"name":"www.a.test.","type":5,"TTL":300,"data":"Ada.github.io."},
+            {"name":"ada.github.io.","type":1,"TTL":300,"data":"185.199.108.153"}
+        ]}"#;
+        let www = record("www.a.test");
+        let answers = parse_answers(json, &www.expected).unwrap();
+        assert_eq!(answers, ["Ada.github.io."]);
+        assert!(www.check(&answers).is_ok());
+
+        let txt = record("default._bimi.a.test");
+        let json =
+            r#"{"Answer":[{"type":16,"data":"\"v=BIMI1; \" \"l=https://a.test/bimi.svg;\""}]}"#;
+        assert!(txt
+            .check(&parse_answers(json, &txt.expected).unwrap())
+            .is_ok());
+        assert_eq!(txt.check(&[]).unwrap_err(), "no record");
+    }
+
+    #[test]
+    fn apex_must_point_only_at_pages() {
+        let apex = record("a.test");
+        assert!(apex.check(&["185.199.110.153".to_string()]).is_ok());
+        assert!(apex
+            .check(&["185.199.110.153".to_string(), "192.0.2.1".to_string()])
+            .unwrap_err()
+            .contains("192.0.2.1"));
+    }
+}
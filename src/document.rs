@@ -0,0 +1,209 @@
+//! # Document
+//!
+//! The shell every generated page is served in, serialized in one place:
+//!
+//! ```text
+//! <!DOCTYPE html>
+//! <html lang="en">          dir too, for right-to-left languages
+//! <head>…</head>            see crate::components::generate_head_html_for
+//! <body>…                   as the page component rendered it
+//! <script …></script>       end-of-body scripts, after the content
+//! </body>
+//! </html>
+//! ```
+//!
+//! The head and body are rendered HTML; the document adds only the root
+//! element (see [`html_open`]) and places end-of-body scripts. Every page
+//! runs [`SHADER_SCRIPT`]; page components don't include scripts.
+//!
+//! [`Document::write`] writes the head first, then renders a [`Body::View`]
+//! into the file chunk by chunk as Leptos streams it, through a buffer, so
+//! a build never holds a page's HTML whole. A page whose head is built from
+//! what its body rendered (see [`crate::structured_data`]) has to render
+//! the body first, as [`Body::Html`].
+
+use crate::config::SITE_LANGUAGE;
+use crate::direction::html_open;
+use futures::executor::block_on_stream;
+use leptos::prelude::{AnyView, RenderHtml};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// The script drawing the animated background into each page's
+/// `#shader-canvas`.
+pub const SHADER_SCRIPT: &str = r#"<script src="/js/shader-bg.js" defer></script>"#;
+
+/// A page's `<body>` element.
+pub enum Body {
+    /// Rendered already.
+    Html(String),
+    /// Rendered as the page is written.
+    View(AnyView),
+}
+
+impl Body {
+    /// The body's HTML, in the pieces it renders in.
+    fn chunks(self) -> Box<dyn Iterator<Item = String>> {
+        match self {
+            Body::Html(html) => Box::new(std::iter::once(html)),
+            Body::View(view) => Box::new(block_on_stream(view.to_html_stream_in_order())),
+        }
+    }
+}
+
+impl From<String> for Body {
+    fn from(html: String) -> Self {
+        Body::Html(html)
+    }
+}
+
+impl From<&str> for Body {
+    fn from(html: &str) -> Self {
+        Body::Html(html.to_string())
+    }
+}
+
+impl From<AnyView> for Body {
+    fn from(view: AnyView) -> Self {
+        Body::View(view)
+    }
+}
+
+/// A complete HTML page.
+pub struct Document {
+    /// BCP 47 language of the page, e.g. `en`.
+    pub lang: String,
+    /// The rendered `<head>` element.
+    pub head: String,
+    /// The `<body>` element.
+    pub body: Body,
+    /// `<script>` elements run after the content, placed before `</body>`.
+    pub end_of_body_scripts: Vec<String>,
+}
+
+impl Document {
+    /// A page in [`SITE_LANGUAGE`] running [`SHADER_SCRIPT`].
+    pub fn new(head: impl Into<String>, body: impl Into<Body>) -> Self {
+        Document {
+            lang: SITE_LANGUAGE.to_string(),
+            head: head.into(),
+            body: body.into(),
+            end_of_body_scripts: vec![SHADER_SCRIPT.to_string()],
+        }
+    }
+
+    /// Writes the page as HTML to `out`, piece by piece. End-of-body
+    /// scripts go before the body's closing tag, or after the body when it
+    /// has none.
+    pub fn write_to(self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "<!DOCTYPE html>\n{}\n", html_open(&self.lang))?;
+        out.write_all(self.head.as_bytes())?;
+        out.write_all(b"\n")?;
+        // The last chunk holds the closing tag, so it waits for the next
+        let mut last = String::new();
+        for chunk in self.body.chunks() {
+            out.write_all(last.as_bytes())?;
+            last = chunk;
+        }
+        let (content, close) = match last.rfind("</body>") {
+            Some(end) if !self.end_of_body_scripts.is_empty() => last.split_at(end),
+            _ => (last.as_str(), ""),
+        };
+        out.write_all(content.as_bytes())?;
+        for script in &self.end_of_body_scripts {
+            out.write_all(b"\n")?;
+            out.write_all(script.as_bytes())?;
+        }
+        if !close.is_empty() {
+            out.write_all(b"\n")?;
+            out.write_all(close.as_bytes())?;
+        }
+        out.write_all(b"\n</html>")
+    }
+
+    /// Writes the page to the file at `path`, creating its directory.
+    pub fn write(self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_to(&mut out)?;
+        out.flush()
+    }
+
+    /// The page as HTML.
+    pub fn to_html(self) -> String {
+        let mut html = Vec::new();
+        self.write_to(&mut html)
+            .expect("writing to memory cannot fail");
+        String::from_utf8(html).expect("every piece is a string")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use leptos::prelude::*;
+
+    fn document() -> Document {
+        Document {
+            end_of_body_scripts: Vec::new(),
+            ..Document::new("<head></head>", "<body><p>Hi</p></body>")
+        }
+    }
+
+    #[test]
+    fn shell_wraps_head_and_body() {
+        assert_eq!(
+            document().to_html(),
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head></head>\n<body><p>Hi</p></body>\n</html>"
+        );
+        let rtl = Document {
+            lang: "ar".to_string(),
+            ..document()
+        };
+        assert!(rtl
+            .to_html()
+            .starts_with("<!DOCTYPE html>\n<html lang=\"ar\" dir=\"rtl\">\n"));
+    }
+
+    #[test]
+    fn scripts_close_the_body() {
+        let html = Document::new("<head></head>", "<body><p>Hi</p></body>").to_html();
+        assert!(
+            html.ends_with(&format!("<p>Hi</p>\n{}\n</body>\n</html>", SHADER_SCRIPT)),
+            "{}",
+            html
+        );
+        let bare = Document {
+            body: "<p>Hi</p>".into(),
+            end_of_body_scripts: vec!["<script></script>".to_string()],
+            ..document()
+        };
+        assert!(bare
+            .to_html()
+            .ends_with("<p>Hi</p>\n<script></script>\n</html>"));
+    }
+
+    #[test]
+    fn views_render_as_they_are_written() {
+        let view = view! { <body><p>"Hi"</p></body> }.into_any();
+        let streamed = Document::new("<head></head>", view).to_html();
+        let rendered = Document::new("<head></head>", "<body><p>Hi</p></body>").to_html();
+        assert_eq!(streamed, rendered);
+    }
+
+    #[test]
+    fn pages_stream_to_files() {
+        let dir = std::env::temp_dir().join(format!("esart-document-{}", std::process::id()));
+        let path = dir.join("a/index.html");
+        let view = view! { <body><p>"Hi"</p></body> }.into_any();
+        Document::new("<head></head>", view).write(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            Document::new("<head></head>", "<body><p>Hi</p></body>").to_html()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
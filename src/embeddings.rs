@@ -0,0 +1,232 @@
+//! # Embedding Export
+//!
+//! Each build writes `/embeddings.jsonl`: the site's text split into chunks
+//! sized for embedding models, one JSON record per line, so a retrieval
+//! pipeline can index the site without crawling or chunking it itself.
+//!
+//! ```json
+//! {"url":"https://everythingsings.art/cv/","headings":["EverythingSings","Projects","Lumimenta"],"summary":"Projects and exhibitions.","text":"Physical art trading cards.","tokens":7}
+//! ```
+//!
+//! `summary` is the page's meta description, for a post its
+//! [excerpt](crate::excerpt), and is left out when the page has none.
+//!
+//! Pages are split at their headings (see [`crate::extract::page_sections`]);
+//! a section longer than [`MAX_CHUNK_TOKENS`] is split between paragraphs,
+//! and a paragraph longer than that between words. `tokens` is an estimate
+//! (see [`estimate_tokens`]), not any particular model's count. Pages whose
+//! robots meta says `noindex` or `none`, such as unlisted pages (see
+//! [`crate::unlisted`]) and redirects (see [`crate::redirect`]), are left
+//! out.
+
+use crate::extract::{page_sections, site_pages, PageSection};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Output path of the export, relative to the site root.
+pub const EMBEDDINGS_PATH: &str = "embeddings.jsonl";
+
+/// Largest chunk, in estimated tokens.
+pub const MAX_CHUNK_TOKENS: usize = 400;
+
+/// One chunk of a page.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Chunk {
+    /// Absolute URL of the page.
+    pub url: String,
+    /// Titles of the headings the text sits under, outermost first.
+    pub headings: Vec<String>,
+    /// The page's description.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    pub text: String,
+    /// Estimated token count of `text`.
+    pub tokens: usize,
+}
+
+/// Estimates the tokens in `text` at about four characters each, the usual
+/// rule of thumb for English with BPE tokenizers.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Splits `text` into pieces of at most `max` estimated tokens, first
+/// between paragraphs, then between words.
+fn split_text(text: &str, max: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let fits = |current: &str, next: &str| {
+        current.is_empty() || estimate_tokens(current) + estimate_tokens(next) < max
+    };
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        if !fits(&current, paragraph) {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if estimate_tokens(paragraph) <= max {
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(paragraph);
+            continue;
+        }
+        for word in paragraph.split_whitespace() {
+            if !fits(&current, word) {
+                pieces.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// The chunks of one section of the page at `url` described by `summary`.
+pub fn section_chunks(url: &str, summary: &str, section: &PageSection) -> Vec<Chunk> {
+    split_text(&section.text, MAX_CHUNK_TOKENS)
+        .into_iter()
+        .map(|text| Chunk {
+            url: url.to_string(),
+            headings: section.headings.clone(),
+            summary: summary.to_string(),
+            tokens: estimate_tokens(&text),
+            text,
+        })
+        .collect()
+}
+
+/// The `<meta name="description">` of a rendered page, or an empty string.
+fn page_description(html: &str) -> String {
+    let selector = Selector::parse("meta[name=description]").expect("valid selector");
+    Html::parse_document(html)
+        .select(&selector)
+        .next()
+        .and_then(|meta| meta.value().attr("content"))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Whether a rendered page's `<meta name="robots">` keeps it out of
+/// indexes.
+fn is_noindex(html: &str) -> bool {
+    let selector = Selector::parse("meta[name=robots]").expect("valid selector");
+    Html::parse_document(html)
+        .select(&selector)
+        .filter_map(|meta| meta.value().attr("content"))
+        .flat_map(|content| content.split(','))
+        .any(|directive| {
+            let directive = directive.trim();
+            directive.eq_ignore_ascii_case("noindex") || directive.eq_ignore_ascii_case("none")
+        })
+}
+
+/// The chunks of a rendered page at `url`, in reading order.
+pub fn page_chunks(html: &str, url: &str) -> Vec<Chunk> {
+    let summary = page_description(html);
+    page_sections(html, url)
+        .iter()
+        .flat_map(|section| section_chunks(url, &summary, section))
+        .collect()
+}
+
+/// Builds the export for every page under `dir`, the site at `site_url`.
+pub fn generate_embeddings(dir: &Path, site_url: &str) -> std::io::Result<String> {
+    let mut out = String::new();
+    for (path, url) in site_pages(dir, site_url)? {
+        let html = std::fs::read_to_string(&path)?;
+        if is_noindex(&html) {
+            continue;
+        }
+        for chunk in page_chunks(&html, &url) {
+            out.push_str(&serde_json::to_string(&chunk).expect("chunk serializes"));
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_estimated_from_characters() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens("فن رقمي"), 2);
+    }
+
+    #[test]
+    fn short_sections_stay_whole() {
+        assert_eq!(split_text("One.\n\nTwo.", 10), ["One.\n\nTwo."]);
+    }
+
+    #[test]
+    fn long_sections_split_between_paragraphs_then_words() {
+        let text = format!("{}\n\n{}", "a".repeat(30), "word ".repeat(20));
+        let pieces = split_text(&text, 10);
+        assert_eq!(pieces[0], "a".repeat(30));
+        assert!(pieces.len() > 2);
+        for piece in &pieces {
+            assert!(estimate_tokens(piece) <= 10, "{:?}", piece);
+        }
+        assert_eq!(pieces[1..].join(" "), "word ".repeat(20).trim());
+    }
+
+    #[test]
+    fn chunks_carry_page_and_headings() {
+        let html = "<main><h1>CV</h1><h2>Projects</h2><p>Lumimenta.</p></main>";
+        let chunks = page_chunks(html, "https://a.test/cv/");
+        assert_eq!(
+            chunks,
+            [Chunk {
+                url: "https://a.test/cv/".to_string(),
+                headings: vec!["CV".to_string(), "Projects".to_string()],
+                summary: String::new(),
+                text: "Lumimenta.".to_string(),
+                tokens: 3,
+            }]
+        );
+        let line = serde_json::to_string(&chunks[0]).unwrap();
+        assert!(
+            line.starts_with(r#"{"url":"https://a.test/cv/","headings":["CV","Projects"],"text""#)
+        );
+    }
+
+    #[test]
+    fn robots_meta_is_parsed() {
+        assert!(is_noindex(crate::components::NOINDEX_META));
+        assert!(is_noindex(r#"<meta name="robots" content="noindex">"#));
+        assert!(is_noindex(
+            r#"<meta content="NOFOLLOW, NoIndex" name="robots">"#
+        ));
+        assert!(is_noindex(r#"<meta name="robots" content="none">"#));
+        assert!(!is_noindex(
+            r#"<meta name="robots" content="index, follow">"#
+        ));
+        assert!(!is_noindex(
+            r#"<meta name="description" content="noindex">"#
+        ));
+        assert!(is_noindex(&crate::redirect::page("https://a.test/")));
+    }
+
+    #[test]
+    fn chunks_carry_the_page_description() {
+        let html = r#"<head><meta name="description" content="An excerpt."></head>
+            <main><h1>Post</h1><p>Body.</p></main>"#;
+        let chunks = page_chunks(html, "https://a.test/posts/a/");
+        assert_eq!(chunks[0].summary, "An excerpt.");
+        let line = serde_json::to_string(&chunks[0]).unwrap();
+        assert!(
+            line.contains(r#""summary":"An excerpt.","text":"Body.""#),
+            "{}",
+            line
+        );
+    }
+}
@@ -0,0 +1,404 @@
+//! # Lite Embeds
+//!
+//! YouTube videos and Bandcamp or Apple Music releases, embedded without
+//! loading anything from the provider until the visitor asks for it. A
+//! series lists them in its `series.toml`:
+//!
+//! ```toml
+//! embeds = [
+//!     "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+//!     "https://everythingsings.bandcamp.com/album/neon",
+//! ]
+//! ```
+//!
+//! At build time each URL's title, thumbnail, and player URL are fetched
+//! (through [`crate::cache`]) and the thumbnail is written to
+//! `/embeds/<key>.jpg` on this site. The page shows it inside an iframe
+//! whose `srcdoc` is a local play link; clicking it navigates the frame to
+//! the provider's player. Until then the provider sees no request, no
+//! cookie, and no script runs.
+
+use crate::cache::{fetch_bytes, fetch_url, offline, url_key, CacheEntry, CACHE_DIR};
+use crate::escape::escape_html;
+use crate::text::encode_path_segment;
+use image::imageops::FilterType;
+use image::ImageFormat;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+
+/// Output subdirectory (and URL path segment) for embed thumbnails.
+pub const EMBEDS_DIR: &str = "embeds";
+
+/// Width of stored thumbnails in pixels.
+pub const THUMBNAIL_WIDTH: u32 = 640;
+
+/// Origins the players are loaded from, for `frame-src`.
+pub const PLAYER_ORIGINS: &[&str] = &[
+    "https://www.youtube-nocookie.com",
+    "https://bandcamp.com",
+    "https://embed.music.apple.com",
+];
+
+/// How long cached metadata and thumbnails are used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A supported embed provider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Provider {
+    YouTube,
+    Bandcamp,
+    AppleMusic,
+}
+
+impl Provider {
+    /// The provider of the page at `url`, if supported.
+    pub fn detect(url: &str) -> Option<Provider> {
+        let rest = url.strip_prefix("https://")?;
+        let host = rest.split(['/', '?', '#']).next()?.to_ascii_lowercase();
+        match host.as_str() {
+            "youtube.com" | "www.youtube.com" | "m.youtube.com" | "youtu.be" => {
+                Some(Provider::YouTube)
+            }
+            "music.apple.com" => Some(Provider::AppleMusic),
+            h if h == "bandcamp.com" || h.ends_with(".bandcamp.com") => Some(Provider::Bandcamp),
+            _ => None,
+        }
+    }
+
+    /// Display name, e.g. `YouTube`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::YouTube => "YouTube",
+            Provider::Bandcamp => "Bandcamp",
+            Provider::AppleMusic => "Apple Music",
+        }
+    }
+
+    /// Player iframe size in CSS pixels.
+    pub fn frame_size(&self) -> (u32, u32) {
+        match self {
+            Provider::YouTube => (560, 315),
+            Provider::Bandcamp | Provider::AppleMusic => (560, 450),
+        }
+    }
+}
+
+/// The video id of a YouTube watch, short, or `youtu.be` URL.
+pub fn youtube_id(url: &str) -> Option<&str> {
+    let rest = url.strip_prefix("https://")?;
+    let (host, path) = rest.split_once('/')?;
+    let id = if host == "youtu.be" {
+        path.split(['?', '#']).next()?
+    } else if let Some(short) = path.strip_prefix("shorts/") {
+        short.split(['?', '#', '/']).next()?
+    } else {
+        let query = path.strip_prefix("watch?")?.split('#').next()?;
+        query.split('&').find_map(|pair| pair.strip_prefix("v="))?
+    };
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(id)
+}
+
+/// What is fetched about an embedded page; cached as JSON.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EmbedMeta {
+    pub title: String,
+    /// The provider's player URL, loaded into the frame on click.
+    pub player: String,
+    pub thumbnail_url: Option<String>,
+}
+
+/// A resolved embed ready for rendering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Embed {
+    pub provider: Provider,
+    /// The page on the provider's site.
+    pub url: String,
+    pub title: String,
+    pub player: String,
+    /// Site-relative path of the self-hosted thumbnail.
+    pub thumbnail: Option<String>,
+}
+
+impl Embed {
+    /// Schema.org type of the embedded work.
+    pub fn schema_type(&self) -> &'static str {
+        match self.provider {
+            Provider::YouTube => "VideoObject",
+            _ if self.url.contains("/track/") || self.url.contains("?i=") => "MusicRecording",
+            _ => "MusicAlbum",
+        }
+    }
+
+    /// The frame's `srcdoc`: the thumbnail as a play link into the player.
+    pub fn srcdoc(&self) -> String {
+        let background = self
+            .thumbnail
+            .as_ref()
+            .map(|t| format!("url({}) center/cover,", t))
+            .unwrap_or_default();
+        format!(
+            "<style>*{{margin:0}}a{{display:grid;place-items:center;height:100vh;\
+             background:{}#111;color:#fff;font:3rem sans-serif;text-decoration:none}}</style>\
+             <a href=\"{}\" aria-label=\"Play {}\">▶</a>",
+            background,
+            escape_html(&self.player),
+            escape_html(&self.title)
+        )
+    }
+}
+
+/// Reads `og:title`, `og:image`, and `og:video` from a page.
+pub fn open_graph(html: &str) -> (Option<String>, Option<String>, Option<String>) {
+    let doc = Html::parse_document(html);
+    let content = |property: &str| {
+        let selector = Selector::parse(&format!("meta[property=\"{}\"][content]", property))
+            .expect("valid selector");
+        doc.select(&selector)
+            .next()
+            .and_then(|m| m.value().attr("content"))
+            .map(str::to_string)
+    };
+    (
+        content("og:title"),
+        content("og:image"),
+        content("og:video:secure_url").or_else(|| content("og:video")),
+    )
+}
+
+/// Metadata of a YouTube video from the oEmbed response `json`.
+pub fn parse_youtube(id: &str, json: &str) -> Option<EmbedMeta> {
+    let doc: Value = serde_json::from_str(json).ok()?;
+    Some(EmbedMeta {
+        title: doc["title"].as_str()?.to_string(),
+        player: format!("https://www.youtube-nocookie.com/embed/{}?autoplay=1", id),
+        thumbnail_url: Some(format!("https://i.ytimg.com/vi/{}/hqdefault.jpg", id)),
+    })
+}
+
+/// Metadata of a Bandcamp or Apple Music page from its `html`.
+pub fn parse_page(provider: Provider, url: &str, html: &str) -> Option<EmbedMeta> {
+    let (title, image, video) = open_graph(html);
+    let player = match provider {
+        Provider::YouTube => return None,
+        Provider::Bandcamp => video.filter(|v| v.starts_with("https://bandcamp.com/"))?,
+        Provider::AppleMusic => url.replacen(
+            "https://music.apple.com",
+            "https://embed.music.apple.com",
+            1,
+        ),
+    };
+    Some(EmbedMeta {
+        title: title?,
+        player,
+        thumbnail_url: image,
+    })
+}
+
+fn fetch_meta(provider: Provider, url: &str) -> Result<EmbedMeta, String> {
+    let meta = match provider {
+        Provider::YouTube => {
+            let id = youtube_id(url).ok_or_else(|| format!("No video id in {}", url))?;
+            let oembed = format!(
+                "https://www.youtube.com/oembed?format=json&url={}",
+                encode_path_segment(url)
+            );
+            parse_youtube(id, &fetch_url(&oembed)?)
+        }
+        _ => parse_page(provider, url, &fetch_url(url)?),
+    };
+    meta.ok_or_else(|| format!("No embed metadata for {}", url))
+}
+
+/// Decodes a thumbnail and re-encodes it as a JPEG at most
+/// [`THUMBNAIL_WIDTH`] wide.
+pub fn normalize(data: &[u8]) -> Result<Vec<u8>, String> {
+    let image = image::load_from_memory(data).map_err(|e| e.to_string())?;
+    let image = if image.width() > THUMBNAIL_WIDTH {
+        image.resize(THUMBNAIL_WIDTH, u32::MAX, FilterType::Triangle)
+    } else {
+        image
+    };
+    let mut jpeg = std::io::Cursor::new(Vec::new());
+    image
+        .to_rgb8()
+        .write_to(&mut jpeg, ImageFormat::Jpeg)
+        .map_err(|e| e.to_string())?;
+    Ok(jpeg.into_inner())
+}
+
+/// Resolves `urls` into embeds, writing thumbnails to
+/// `<site_dir>/embeds/`. Unsupported URLs and pages whose metadata is
+/// unavailable are skipped with a warning.
+pub fn resolve(urls: &[String], site_dir: &Path) -> std::io::Result<Vec<Embed>> {
+    let cache_dir = Path::new(CACHE_DIR).join(EMBEDS_DIR);
+    let out_dir = site_dir.join(EMBEDS_DIR);
+    let mut embeds = Vec::new();
+    for url in urls {
+        let Some(provider) = Provider::detect(url) else {
+            eprintln!("Warning: Unsupported embed {}", url);
+            continue;
+        };
+        let key = url_key(url);
+        let meta = CacheEntry::new(&cache_dir, &format!("{}.json", key))
+            .get_or_fetch(MAX_AGE, offline(), || {
+                let meta = fetch_meta(provider, url)?;
+                serde_json::to_string(&meta).map_err(|e| e.to_string())
+            })
+            .and_then(|json| serde_json::from_str::<EmbedMeta>(&json).ok());
+        let Some(meta) = meta else {
+            eprintln!("Warning: No metadata for embed {}", url);
+            continue;
+        };
+
+        let file_name = format!("{}.jpg", key);
+        let thumbnail = meta.thumbnail_url.as_deref().and_then(|src| {
+            CacheEntry::new(&cache_dir, &file_name).get_or_fetch_bytes(MAX_AGE, offline(), || {
+                fetch_bytes(src).and_then(|data| normalize(&data))
+            })
+        });
+        let thumbnail = match thumbnail {
+            Some(jpeg) => {
+                std::fs::create_dir_all(&out_dir)?;
+                std::fs::write(out_dir.join(&file_name), jpeg)?;
+                Some(format!("/{}/{}", EMBEDS_DIR, file_name))
+            }
+            None => None,
+        };
+        embeds.push(Embed {
+            provider,
+            url: url.clone(),
+            title: meta.title,
+            player: meta.player,
+            thumbnail,
+        });
+    }
+    Ok(embeds)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub fn sample_embed() -> Embed {
+        Embed {
+            provider: Provider::YouTube,
+            url: "https://www.youtube.com/watch?v=abc123".to_string(),
+            title: "Neon <Live>".to_string(),
+            player: "https://www.youtube-nocookie.com/embed/abc123?autoplay=1".to_string(),
+            thumbnail: Some("/embeds/0011223344556677.jpg".to_string()),
+        }
+    }
+
+    #[test]
+    fn providers_are_detected_by_host() {
+        let detect = Provider::detect;
+        assert_eq!(detect("https://youtu.be/abc"), Some(Provider::YouTube));
+        assert_eq!(
+            detect("https://artist.bandcamp.com/album/x"),
+            Some(Provider::Bandcamp)
+        );
+        assert_eq!(
+            detect("https://music.apple.com/us/album/x/1"),
+            Some(Provider::AppleMusic)
+        );
+        assert_eq!(detect("https://notbandcamp.com/album/x"), None);
+        assert_eq!(detect("http://youtu.be/abc"), None);
+    }
+
+    #[test]
+    fn youtube_ids_come_from_every_url_form() {
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/watch?feature=share&v=dQw4w9WgXcQ#t=1",
+            "https://youtu.be/dQw4w9WgXcQ?t=3",
+            "https://youtube.com/shorts/dQw4w9WgXcQ",
+        ] {
+            assert_eq!(youtube_id(url), Some("dQw4w9WgXcQ"), "{}", url);
+        }
+        assert_eq!(youtube_id("https://www.youtube.com/watch?v=a\"b"), None);
+        assert_eq!(youtube_id("https://www.youtube.com/@channel"), None);
+    }
+
+    #[test]
+    fn youtube_player_is_cookie_free() {
+        let meta = parse_youtube("abc", r#"{"title": "Neon", "author_name": "ES"}"#).unwrap();
+        assert_eq!(meta.title, "Neon");
+        assert_eq!(
+            meta.player,
+            "https://www.youtube-nocookie.com/embed/abc?autoplay=1"
+        );
+        assert!(PLAYER_ORIGINS.iter().any(|o| meta.player.starts_with(o)));
+    }
+
+    #[test]
+    fn pages_give_title_thumbnail_and_player() {
+        let html = r#"<head>
+            <meta property="og:title" content="Neon, by EverythingSings">
+            <meta property="og:image" content="https://f4.bcbits.com/img/a1_5.jpg">
+            <meta property="og:video" content="https://bandcamp.com/EmbeddedPlayer/v=2/album=1/">
+        </head>"#;
+        let url = "https://es.bandcamp.com/album/neon";
+        let meta = parse_page(Provider::Bandcamp, url, html).unwrap();
+        assert_eq!(
+            meta.player,
+            "https://bandcamp.com/EmbeddedPlayer/v=2/album=1/"
+        );
+        assert_eq!(
+            meta.thumbnail_url.as_deref(),
+            Some("https://f4.bcbits.com/img/a1_5.jpg")
+        );
+
+        let apple = "https://music.apple.com/us/album/neon/1?i=2";
+        let meta = parse_page(Provider::AppleMusic, apple, html).unwrap();
+        assert_eq!(
+            meta.player,
+            "https://embed.music.apple.com/us/album/neon/1?i=2"
+        );
+        assert_eq!(parse_page(Provider::Bandcamp, url, "<head></head>"), None);
+    }
+
+    #[test]
+    fn srcdoc_links_into_the_player() {
+        let srcdoc = sample_embed().srcdoc();
+        assert!(srcdoc.contains(
+            "<a href=\"https://www.youtube-nocookie.com/embed/abc123?autoplay=1\" \
+             aria-label=\"Play Neon &lt;Live&gt;\">"
+        ));
+        assert!(srcdoc.contains("url(/embeds/0011223344556677.jpg)"));
+        assert!(!srcdoc.contains("<script"));
+    }
+
+    #[test]
+    fn thumbnails_are_shrunk_to_jpeg() {
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::RgbImage::new(1280, 720)
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        let jpeg = normalize(png.get_ref()).unwrap();
+        let thumb = image::load_from_memory_with_format(&jpeg, ImageFormat::Jpeg).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (THUMBNAIL_WIDTH, 360));
+    }
+
+    #[test]
+    fn schema_type_follows_the_work() {
+        assert_eq!(sample_embed().schema_type(), "VideoObject");
+        let album = Embed {
+            provider: Provider::Bandcamp,
+            url: "https://es.bandcamp.com/album/neon".to_string(),
+            ..sample_embed()
+        };
+        assert_eq!(album.schema_type(), "MusicAlbum");
+        let track = Embed {
+            url: "https://es.bandcamp.com/track/one".to_string(),
+            ..album
+        };
+        assert_eq!(track.schema_type(), "MusicRecording");
+    }
+}
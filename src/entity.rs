@@ -0,0 +1,364 @@
+//! # Profile Entities
+//!
+//! The people and organizations the site represents, e.g. the EverythingSings
+//! brand and the human behind it. Each entity renders as its own h-card and
+//! JSON-LD node; nodes reference each other by `@id` so crawlers can tell
+//! they are related but distinct. The nodes join the site-wide
+//! [`@graph`](crate::schema).
+//!
+//! The entity list lives in [`crate::config::ENTITIES`]; the entity named by
+//! [`crate::config::PRIMARY_ENTITY`] supplies the homepage title and Open
+//! Graph tags and is described by the profile links (see
+//! [`link_fields`]).
+//!
+//! Cryptographic identity claims (an OpenPGP fingerprint, a Keybase or
+//! Nostr key) are [`Identifier`]s: `identifier` PropertyValues in JSON-LD
+//! and `u-key` links on the h-card, next to the `rel="me"` profiles they
+//! back up. Proof files such as `keybase.txt` are published through
+//! [`crate::well_known`]. The primary entity is also identified by its
+//! Bluesky account's DID, [`ATPROTO_DID`].
+//!
+//! [`ATPROTO_DID`]: crate::config::ATPROTO_DID
+
+use crate::components::{LinkEntry, LinkKind};
+use crate::config::{ATPROTO_DID, ENTITIES, PRIMARY_ENTITY};
+use crate::escape::json_string;
+use crate::schema::website_id;
+use crate::url::absolute;
+use serde_json::Value;
+
+/// A person or organization described by the site.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entity {
+    /// Stable fragment identifier, used for the JSON-LD `@id` (`/#key`).
+    pub key: &'static str,
+    /// Schema.org type, e.g. `"Person"` or `"Organization"`.
+    pub schema_type: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Site-relative image path.
+    pub image: &'static str,
+    pub url: &'static str,
+    /// Contact address, shown obfuscated on the profile card (see
+    /// [`crate::components::Email`]).
+    pub email: Option<&'static str>,
+    /// Schema.org properties pointing at other entities, as
+    /// `(property, entity key)` pairs, e.g. `("founder", "artist")`.
+    pub relations: &'static [(&'static str, &'static str)],
+    /// Identity claims, e.g. an OpenPGP key fingerprint.
+    pub identifiers: &'static [Identifier],
+}
+
+/// An identifier of an entity, published as a Schema.org PropertyValue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Identifier {
+    /// What the value identifies in, e.g. `"OpenPGP"` or `"Keybase"`.
+    pub property_id: &'static str,
+    /// The identifier itself, e.g. a key fingerprint or username.
+    pub value: &'static str,
+    /// Where the claim can be checked, e.g. the public key on a keyserver.
+    pub url: Option<&'static str>,
+}
+
+impl Identifier {
+    /// The DID of an AT Protocol (Bluesky) account.
+    pub const fn atproto(did: &'static str) -> Self {
+        Identifier {
+            property_id: "atproto",
+            value: did,
+            url: None,
+        }
+    }
+
+    fn json_ld(&self) -> String {
+        let mut fields = vec![
+            "\"@type\": \"PropertyValue\"".to_string(),
+            format!("\"propertyID\": {}", json_string(self.property_id)),
+            format!("\"value\": {}", json_string(self.value)),
+        ];
+        if let Some(url) = self.url {
+            fields.push(format!("\"url\": {}", json_string(url)));
+        }
+        format!("{{ {} }}", fields.join(", "))
+    }
+}
+
+impl Entity {
+    /// The JSON-LD `@id` of this entity.
+    pub fn id(&self) -> String {
+        absolute(&format!("/#{}", self.key))
+    }
+
+    /// The Schema.org type URL used for microdata `itemtype`.
+    pub fn itemtype(&self) -> String {
+        format!("https://schema.org/{}", self.schema_type)
+    }
+
+    /// This entity as a JSON-LD node (without `@context`).
+    pub fn json_ld_node(&self) -> String {
+        format!("{{\n  {}\n}}", self.fields(&[], false).join(",\n  "))
+    }
+
+    /// This entity as the primary JSON-LD node: described by `links` and
+    /// the subject of the site.
+    pub fn json_ld_node_with(&self, links: &[&LinkEntry]) -> String {
+        let mut fields = self.fields(links, true);
+        fields.push(format!(
+            "\"subjectOf\": {{ \"@id\": {} }}",
+            json_string(&website_id())
+        ));
+        format!("{{\n  {}\n}}", fields.join(",\n  "))
+    }
+
+    /// The identifiers of this entity, plus the [`ATPROTO_DID`] when it is
+    /// the primary entity.
+    pub fn identifiers(&self, primary: bool) -> Vec<Identifier> {
+        with_did(self.identifiers, ATPROTO_DID.filter(|_| primary))
+    }
+
+    fn fields(&self, links: &[&LinkEntry], primary: bool) -> Vec<String> {
+        let mut fields = vec![
+            format!("\"@type\": {}", json_string(self.schema_type)),
+            format!("\"@id\": {}", json_string(&self.id())),
+            format!("\"name\": {}", json_string(self.name)),
+            format!("\"url\": {}", json_string(self.url)),
+            format!("\"description\": {}", json_string(self.description)),
+            format!("\"image\": {}", json_string(&absolute(self.image))),
+        ];
+        fields.extend(link_fields(self, links));
+        for (property, key) in self.relations {
+            fields.push(format!(
+                "{}: {{ \"@id\": {} }}",
+                json_string(property),
+                json_string(&absolute(&format!("/#{}", key)))
+            ));
+        }
+        let identifiers: Vec<String> = self
+            .identifiers(primary)
+            .iter()
+            .map(|i| i.json_ld())
+            .collect();
+        if !identifiers.is_empty() {
+            fields.push(format!("\"identifier\": {}", json_array(&identifiers)));
+        }
+        fields
+    }
+}
+
+/// `identifiers` followed by the AT Protocol `did`, if any.
+fn with_did(identifiers: &[Identifier], did: Option<&'static str>) -> Vec<Identifier> {
+    identifiers
+        .iter()
+        .copied()
+        .chain(did.map(Identifier::atproto))
+        .collect()
+}
+
+fn json_array(items: &[String]) -> String {
+    format!("[{}]", items.join(", "))
+}
+
+/// JSON-LD fields describing `links` from `entity`'s point of view.
+///
+/// Profiles become `sameAs`, stores a `makesOffer` Offer, the music
+/// catalogue a `memberOf` MusicGroup named after the entity, websites a
+/// WebSite authored by the entity (`@reverse`), and scheduling pages a
+/// `potentialAction`. `sameAs` is always present, possibly empty.
+pub fn link_fields(entity: &Entity, links: &[&LinkEntry]) -> Vec<String> {
+    let of_kind = |kind: LinkKind| links.iter().filter(move |l| l.kind == kind);
+    let node = |link: &LinkEntry, name: &str| {
+        let mut fields = vec![
+            format!(
+                "\"@type\": {}",
+                json_string(link.kind.schema_type().unwrap_or("Thing"))
+            ),
+            format!("\"name\": {}", json_string(name)),
+            format!("\"url\": {}", json_string(link.href)),
+        ];
+        if let Some(description) = link.description {
+            fields.push(format!("\"description\": {}", json_string(description)));
+        }
+        format!("{{ {} }}", fields.join(", "))
+    };
+
+    let same_as: Vec<String> = of_kind(LinkKind::Profile)
+        .map(|l| json_string(l.href))
+        .collect();
+    let mut fields = vec![format!("\"sameAs\": {}", json_array(&same_as))];
+
+    let offers: Vec<String> = of_kind(LinkKind::Store).map(|l| node(l, l.label)).collect();
+    if !offers.is_empty() {
+        fields.push(format!("\"makesOffer\": {}", json_array(&offers)));
+    }
+    let groups: Vec<String> = of_kind(LinkKind::Music)
+        .map(|l| node(l, entity.name))
+        .collect();
+    if !groups.is_empty() {
+        fields.push(format!("\"memberOf\": {}", json_array(&groups)));
+    }
+    let sites: Vec<String> = of_kind(LinkKind::Website)
+        .map(|l| node(l, l.label))
+        .collect();
+    if !sites.is_empty() {
+        fields.push(format!(
+            "\"@reverse\": {{ \"author\": {} }}",
+            json_array(&sites)
+        ));
+    }
+    let actions: Vec<String> = of_kind(LinkKind::Schedule)
+        .map(|l| {
+            format!(
+                "{{ \"@type\": \"ScheduleAction\", \"name\": {}, \"target\": {} }}",
+                json_string(l.label),
+                json_string(l.href)
+            )
+        })
+        .collect();
+    if !actions.is_empty() {
+        fields.push(format!("\"potentialAction\": {}", json_array(&actions)));
+    }
+    fields
+}
+
+// The fallback below needs an entity to fall back to
+const _: () = assert!(!ENTITIES.is_empty(), "config::ENTITIES is empty");
+
+/// Returns the primary entity, falling back to the first configured one.
+pub fn primary_entity() -> &'static Entity {
+    ENTITIES
+        .iter()
+        .find(|e| e.key == PRIMARY_ENTITY)
+        .unwrap_or(&ENTITIES[0])
+}
+
+/// Orders `entities` with the one keyed `primary` first.
+pub fn primary_first<'a>(entities: &'a [Entity], primary: &str) -> Vec<&'a Entity> {
+    let mut ordered: Vec<&Entity> = entities.iter().collect();
+    ordered.sort_by_key(|e| e.key != primary);
+    ordered
+}
+
+/// Builds the JSON-LD nodes of `entities`, primary first, with `links`
+/// describing the primary entity.
+pub fn entity_nodes(entities: &[Entity], primary: &str, links: &[&LinkEntry]) -> Vec<Value> {
+    primary_first(entities, primary)
+        .into_iter()
+        .map(|e| {
+            let node = if e.key == primary {
+                e.json_ld_node_with(links)
+            } else {
+                e.json_ld_node()
+            };
+            serde_json::from_str(&node).expect("entity JSON-LD is built from valid JSON")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const BRAND: Entity = Entity {
+        key: "brand",
+        schema_type: "Organization",
+        name: "EverythingSings",
+        description: "Formless art brand.",
+        image: "/avatar.png",
+        url: "https://everythingsings.art",
+        email: None,
+        relations: &[("founder", "artist")],
+        identifiers: &[],
+    };
+
+    pub(crate) const ARTIST: Entity = Entity {
+        key: "artist",
+        schema_type: "Person",
+        name: "The Artist",
+        description: "The human behind the brand.",
+        image: "/artist.png",
+        url: "https://everythingsings.art/about/",
+        email: Some("artist@example.com"),
+        relations: &[("brand", "brand")],
+        identifiers: &[Identifier {
+            property_id: "OpenPGP",
+            value: "0123456789ABCDEF0123456789ABCDEF01234567",
+            url: Some("https://keys.openpgp.org/vks/v1/by-fingerprint/0123456789ABCDEF0123456789ABCDEF01234567"),
+        }],
+    };
+
+    #[test]
+    fn entity_node_has_stable_id() {
+        let nodes = entity_nodes(&[BRAND], "brand", &[]);
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0]["@type"], "Organization");
+        assert_eq!(nodes[0]["@id"], "https://everythingsings.art/#brand");
+    }
+
+    #[test]
+    fn multiple_entities_are_linked_by_id() {
+        let graph = entity_nodes(&[ARTIST, BRAND], "brand", &[]);
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph[0]["@id"], BRAND.id(), "Primary entity comes first");
+        assert_eq!(graph[0]["founder"]["@id"], ARTIST.id());
+        assert_eq!(graph[1]["brand"]["@id"], BRAND.id());
+    }
+
+    #[test]
+    fn links_describe_the_primary_entity_by_kind() {
+        let links: Vec<&LinkEntry> = crate::components::LINKS
+            .iter()
+            .chain([&crate::components::BOOKING])
+            .collect();
+        let graph = entity_nodes(&[ARTIST, BRAND], "artist", &links);
+        let artist = &graph[0];
+        assert_eq!(
+            artist["sameAs"],
+            serde_json::json!([
+                "https://github.com/EverythingSings",
+                "https://x.com/everythingSung"
+            ])
+        );
+        assert_eq!(artist["makesOffer"][0]["@type"], "Offer");
+        assert_eq!(
+            artist["makesOffer"][0]["url"],
+            "https://bedim.redbubble.com"
+        );
+        assert_eq!(artist["memberOf"][0]["@type"], "MusicGroup");
+        assert_eq!(artist["memberOf"][0]["name"], ARTIST.name);
+        assert_eq!(artist["@reverse"]["author"][0]["@type"], "WebSite");
+        assert_eq!(artist["potentialAction"][0]["@type"], "ScheduleAction");
+        assert_eq!(graph[1]["sameAs"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn primary_entity_is_subject_of_the_sites() {
+        let graph = entity_nodes(&[ARTIST, BRAND], "brand", &[]);
+        assert_eq!(graph[0]["subjectOf"]["@id"], website_id());
+        assert!(graph[1].get("subjectOf").is_none());
+    }
+
+    #[test]
+    fn identifiers_are_property_values() {
+        let graph = entity_nodes(&[ARTIST, BRAND], "brand", &[]);
+        let identifier = &graph[1]["identifier"][0];
+        assert_eq!(identifier["@type"], "PropertyValue");
+        assert_eq!(identifier["propertyID"], "OpenPGP");
+        assert_eq!(identifier["value"], ARTIST.identifiers[0].value);
+        assert!(identifier["url"].as_str().unwrap().starts_with("https://"));
+        assert!(graph[0].get("identifier").is_none());
+    }
+
+    #[test]
+    fn did_follows_configured_identifiers() {
+        let ids = with_did(ARTIST.identifiers, Some("did:plc:abc"));
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[1].property_id, "atproto");
+        assert_eq!(ids[1].value, "did:plc:abc");
+        assert_eq!(with_did(BRAND.identifiers, None), []);
+    }
+
+    #[test]
+    fn primary_entity_is_configured() {
+        assert_eq!(primary_entity().key, PRIMARY_ENTITY);
+    }
+}
@@ -0,0 +1,342 @@
+//! # EPUB Export
+//!
+//! The `export-epub` command bundles the site's written content into an
+//! EPUB 3 book for e-readers and offline reading. Until the site has blog
+//! posts, that content is the [media log](crate::media): one chapter per
+//! entry, newest first, with its note.
+//!
+//! ```bash
+//! ./target/release/everythingsings export-epub
+//! ```
+//!
+//! The book carries Dublin Core metadata (identifier, title, creator,
+//! language, publisher, rights, date) and a cover drawn from the brand
+//! assets: the avatar on the dark palette's background, framed in its
+//! border color. The `mimetype` entry comes first and uncompressed, as the
+//! OCF container requires; the other text entries are deflated.
+
+use crate::escape::escape_html;
+use crate::media::{stars, MediaLog, BEST_RATING};
+use crate::tokens::{parse_hex_color, Palette};
+use image::imageops::FilterType;
+use image::{ImageFormat, Rgb, RgbImage};
+use std::io::{Cursor, Seek, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Where `export-epub` writes the book, relative to the project root.
+pub const EPUB_PATH: &str = "target/everythingsings.epub";
+
+/// Cover size in pixels, the 1.6 aspect ratio e-book stores recommend.
+pub const COVER_SIZE: (u32, u32) = (1600, 2560);
+
+/// Width of the cover's border frame, in pixels.
+const COVER_FRAME: u32 = 24;
+
+/// A book to export.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Book {
+    /// Unique identifier, e.g. the site URL.
+    pub identifier: String,
+    pub title: String,
+    pub creator: String,
+    pub language: String,
+    pub publisher: String,
+    pub rights: String,
+    /// Last modification, as an RFC 3339 UTC timestamp without fractions.
+    pub modified: String,
+    /// Cover image as PNG.
+    pub cover: Option<Vec<u8>>,
+    pub chapters: Vec<Chapter>,
+}
+
+/// One chapter of a book.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    /// XHTML body content, already escaped.
+    pub body: String,
+}
+
+/// One chapter per media log entry, newest first.
+pub fn log_chapters(log: &MediaLog) -> Vec<Chapter> {
+    log.entries
+        .iter()
+        .map(|entry| {
+            let mut body = format!(
+                "<p>{} by {}",
+                entry.kind.label(),
+                escape_html(&entry.creator)
+            );
+            if let Some(year) = entry.year {
+                body.push_str(&format!(" ({})", year));
+            }
+            body.push_str(&format!(
+                "</p>\n<p><time datetime=\"{0}\">{0}</time></p>\n",
+                escape_html(&entry.date)
+            ));
+            if let Some(rating) = entry.rating {
+                body.push_str(&format!(
+                    "<p>{} out of {}: {}</p>\n",
+                    rating.min(BEST_RATING),
+                    BEST_RATING,
+                    stars(rating)
+                ));
+            }
+            for paragraph in entry.note.split("\n\n").filter(|p| !p.trim().is_empty()) {
+                body.push_str(&format!("<p>{}</p>\n", escape_html(paragraph.trim())));
+            }
+            Chapter {
+                title: entry.title.clone(),
+                body,
+            }
+        })
+        .collect()
+}
+
+/// Draws the cover: `avatar` (any format `image` reads) centered on the
+/// palette's background inside a frame of its border color.
+pub fn cover(avatar: Option<&[u8]>, palette: &Palette) -> Result<Vec<u8>, String> {
+    let color = |hex: &str| {
+        parse_hex_color(hex)
+            .map(|(r, g, b)| Rgb([r, g, b]))
+            .ok_or_else(|| format!("Invalid palette color {}", hex))
+    };
+    let (width, height) = COVER_SIZE;
+    let mut canvas = RgbImage::from_pixel(width, height, color(palette.border)?);
+    let background = color(palette.bg)?;
+    for y in COVER_FRAME..height - COVER_FRAME {
+        for x in COVER_FRAME..width - COVER_FRAME {
+            canvas.put_pixel(x, y, background);
+        }
+    }
+    if let Some(data) = avatar {
+        let side = width / 2;
+        let avatar = image::load_from_memory(data)
+            .map_err(|e| format!("Could not read the avatar: {}", e))?
+            .resize_to_fill(side, side, FilterType::Lanczos3)
+            .to_rgb8();
+        image::imageops::overlay(
+            &mut canvas,
+            &avatar,
+            i64::from((width - side) / 2),
+            i64::from((height - side) / 3),
+        );
+    }
+    let mut png = Cursor::new(Vec::new());
+    canvas
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(png.into_inner())
+}
+
+/// File name of the chapter at `index` inside the book.
+fn chapter_file(index: usize) -> String {
+    format!("chapter-{:03}.xhtml", index + 1)
+}
+
+/// An XHTML content document titled `title` around `body`.
+fn xhtml(language: &str, title: &str, body: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops" xml:lang="{0}" lang="{0}">
+<head><meta charset="UTF-8" /><title>{1}</title></head>
+<body>
+{2}</body>
+</html>
+"#,
+        escape_html(language),
+        escape_html(title),
+        body
+    )
+}
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+impl Book {
+    /// The package document: Dublin Core metadata, manifest, and spine.
+    pub fn package(&self) -> String {
+        let mut manifest = String::from(
+            "    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n",
+        );
+        let mut spine = String::new();
+        if self.cover.is_some() {
+            manifest.push_str("    <item id=\"cover-image\" href=\"cover.png\" media-type=\"image/png\" properties=\"cover-image\"/>\n");
+            manifest.push_str("    <item id=\"cover\" href=\"cover.xhtml\" media-type=\"application/xhtml+xml\"/>\n");
+            spine.push_str("    <itemref idref=\"cover\" linear=\"no\"/>\n");
+        }
+        spine.push_str("    <itemref idref=\"nav\"/>\n");
+        for index in 0..self.chapters.len() {
+            manifest.push_str(&format!(
+                "    <item id=\"chapter-{0}\" href=\"{1}\" media-type=\"application/xhtml+xml\"/>\n",
+                index + 1,
+                chapter_file(index)
+            ));
+            spine.push_str(&format!("    <itemref idref=\"chapter-{}\"/>\n", index + 1));
+        }
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="book-id" xml:lang="{language}">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="book-id">{identifier}</dc:identifier>
+    <dc:title>{title}</dc:title>
+    <dc:creator>{creator}</dc:creator>
+    <dc:language>{language}</dc:language>
+    <dc:publisher>{publisher}</dc:publisher>
+    <dc:rights>{rights}</dc:rights>
+    <dc:date>{modified}</dc:date>
+    <meta property="dcterms:modified">{modified}</meta>
+  </metadata>
+  <manifest>
+{manifest}  </manifest>
+  <spine>
+{spine}  </spine>
+</package>
+"#,
+            identifier = escape_html(&self.identifier),
+            title = escape_html(&self.title),
+            creator = escape_html(&self.creator),
+            language = escape_html(&self.language),
+            publisher = escape_html(&self.publisher),
+            rights = escape_html(&self.rights),
+            modified = escape_html(&self.modified),
+        )
+    }
+
+    /// The navigation document: the table of contents.
+    pub fn nav(&self) -> String {
+        let items: String = self
+            .chapters
+            .iter()
+            .enumerate()
+            .map(|(index, chapter)| {
+                format!(
+                    "<li><a href=\"{}\">{}</a></li>\n",
+                    chapter_file(index),
+                    escape_html(&chapter.title)
+                )
+            })
+            .collect();
+        let body = format!(
+            "<nav epub:type=\"toc\" id=\"toc\">\n<h1>{}</h1>\n<ol>\n{}</ol>\n</nav>\n",
+            escape_html(&self.title),
+            items
+        );
+        xhtml(&self.language, &self.title, &body)
+    }
+
+    /// Writes the book as an EPUB container.
+    pub fn write<W: Write + Seek>(&self, writer: W) -> Result<W, String> {
+        let err = |e: zip::result::ZipError| e.to_string();
+        let io_err = |e: std::io::Error| e.to_string();
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(writer);
+        let text = |zip: &mut ZipWriter<W>, name: &str, content: &str| {
+            zip.start_file(name, deflated).map_err(err)?;
+            zip.write_all(content.as_bytes()).map_err(io_err)
+        };
+
+        zip.start_file("mimetype", stored).map_err(err)?;
+        zip.write_all(b"application/epub+zip").map_err(io_err)?;
+        text(&mut zip, "META-INF/container.xml", CONTAINER_XML)?;
+        text(&mut zip, "OEBPS/content.opf", &self.package())?;
+        text(&mut zip, "OEBPS/nav.xhtml", &self.nav())?;
+        if let Some(cover) = &self.cover {
+            zip.start_file("OEBPS/cover.png", stored).map_err(err)?;
+            zip.write_all(cover).map_err(io_err)?;
+            let body = format!(
+                "<section epub:type=\"cover\"><img src=\"cover.png\" alt=\"{}\" /></section>\n",
+                escape_html(&self.title)
+            );
+            text(
+                &mut zip,
+                "OEBPS/cover.xhtml",
+                &xhtml(&self.language, &self.title, &body),
+            )?;
+        }
+        for (index, chapter) in self.chapters.iter().enumerate() {
+            let body = format!("<h1>{}</h1>\n{}", escape_html(&chapter.title), chapter.body);
+            text(
+                &mut zip,
+                &format!("OEBPS/{}", chapter_file(index)),
+                &xhtml(&self.language, &chapter.title, &body),
+            )?;
+        }
+        zip.finish().map_err(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::DARK;
+    use std::io::Read;
+
+    fn sample_book() -> Book {
+        Book {
+            identifier: "https://a.test/".to_string(),
+            title: "Log & Notes".to_string(),
+            creator: "A".to_string(),
+            language: "en".to_string(),
+            publisher: "A".to_string(),
+            rights: "© A".to_string(),
+            modified: "2025-01-17T12:00:00Z".to_string(),
+            cover: Some(cover(None, &DARK).unwrap()),
+            chapters: log_chapters(
+                &MediaLog::parse(
+                    "[[entry]]\nkind = \"book\"\ntitle = \"The Dispossessed\"\n\
+                     creator = \"Ursula K. Le Guin\"\ndate = \"2025-01-17\"\n\
+                     rating = 5\nnote = \"Still <the> best.\"\n",
+                )
+                .unwrap(),
+            ),
+        }
+    }
+
+    #[test]
+    fn container_starts_with_the_stored_mimetype() {
+        let data = sample_book().write(Cursor::new(Vec::new())).unwrap();
+        let data = data.into_inner();
+        // The OCF magic: `mimetype` uncompressed at offset 30.
+        assert_eq!(&data[30..38], b"mimetype");
+        assert_eq!(&data[38..58], b"application/epub+zip");
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+        let mut chapter = String::new();
+        archive
+            .by_name("OEBPS/chapter-001.xhtml")
+            .unwrap()
+            .read_to_string(&mut chapter)
+            .unwrap();
+        assert!(chapter.contains("<h1>The Dispossessed</h1>"));
+        assert!(chapter.contains("Still &lt;the&gt; best."));
+        assert!(archive.by_name("OEBPS/cover.png").is_ok());
+    }
+
+    #[test]
+    fn package_carries_dublin_core_metadata() {
+        let opf = sample_book().package();
+        assert!(opf.contains("<dc:title>Log &amp; Notes</dc:title>"));
+        assert!(opf.contains("<dc:identifier id=\"book-id\">https://a.test/</dc:identifier>"));
+        assert!(opf.contains("<meta property=\"dcterms:modified\">2025-01-17T12:00:00Z</meta>"));
+        assert!(opf.contains("properties=\"cover-image\""));
+        assert!(opf.contains("<itemref idref=\"chapter-1\"/>"));
+    }
+
+    #[test]
+    fn cover_is_framed_in_the_border_color() {
+        let png = cover(None, &DARK).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), COVER_SIZE);
+        assert_eq!(image.get_pixel(0, 0), &Rgb([0x33, 0x33, 0x33]));
+        assert_eq!(image.get_pixel(800, 1280), &Rgb([0x0d, 0x0d, 0x0d]));
+    }
+}
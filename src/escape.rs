@@ -0,0 +1,127 @@
+//! # Escaping
+//!
+//! Helpers for embedding arbitrary text in the hand-built HTML, XML, and
+//! JSON-LD output. Anything that is not a compile-time literal should pass
+//! through one of these before reaching a `format!` template.
+//!
+//! Both helpers NFC-normalize their input (see [`crate::text`]), so text that
+//! is escaped is also normalized consistently across output formats.
+
+use crate::text::nfc;
+
+/// Escapes text for HTML/XML element content and quoted attribute values.
+pub fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in nfc(s).chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Encodes `s` as a quoted JSON string safe to embed in a `<script>` block.
+///
+/// `<`, `>` and `&` are written as `\u` escapes so the value can never close
+/// the surrounding `<script type="application/ld+json">` element.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in nfc(s).chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '<' | '>' | '&' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if (c as u32) < 0x20 || c == '\u{2028}' || c == '\u{2029}' => {
+                out.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Serializes `value` as pretty-printed JSON safe to embed in a `<script>`
+/// block, with the same `\u` escaping and normalization as [`json_string`].
+///
+/// Use this for JSON-LD assembled with `serde_json` rather than `format!`.
+pub fn json_script(value: &serde_json::Value) -> String {
+    let json = serde_json::to_string_pretty(value).expect("JSON value serializes");
+    // Outside strings JSON never contains these characters, so a plain
+    // replacement only touches string contents.
+    nfc(&json)
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn html_escapes_markup_characters() {
+        assert_eq!(
+            escape_html(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn html_leaves_unicode_untouched() {
+        assert_eq!(escape_html("Ωmega 🎵"), "Ωmega 🎵");
+    }
+
+    #[test]
+    fn json_script_cannot_close_script_element() {
+        let value = serde_json::json!({ "name": "</script><b>&" });
+        let json = json_script(&value);
+        assert!(!json.contains('<') && !json.contains('&'), "{}", json);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, value);
+    }
+
+    #[test]
+    fn escaping_normalizes_to_nfc() {
+        assert_eq!(escape_html("cafe\u{301}"), "caf\u{e9}");
+        assert_eq!(json_string("cafe\u{301}"), "\"caf\u{e9}\"");
+    }
+
+    #[test]
+    fn json_string_cannot_close_script() {
+        let encoded = json_string("</script><script>alert(1)</script>");
+        assert!(!encoded.contains('<'));
+    }
+
+    proptest! {
+        #[test]
+        fn json_string_round_trips(s in any::<String>()) {
+            let parsed: String = serde_json::from_str(&json_string(&s)).unwrap();
+            prop_assert_eq!(parsed, nfc(&s));
+        }
+
+        #[test]
+        fn escaped_html_has_no_raw_markup(s in any::<String>()) {
+            let escaped = escape_html(&s);
+            prop_assert!(!escaped.contains(['<', '>', '"', '\'']));
+        }
+
+        #[test]
+        fn escaped_attribute_parses_back(s in "[ -~\u{a0}-\u{2fff}\u{1f300}-\u{1f6ff}]*") {
+            let html = format!(r#"<p title="{}">{}</p>"#, escape_html(&s), escape_html(&s));
+            let dom = crate::test_utils::parse(&html);
+            prop_assert_eq!(dom.attr("p", "title"), Some(nfc(&s)));
+            prop_assert_eq!(dom.select("p")[0].text().collect::<String>(), nfc(&s));
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! # Excerpts
+//!
+//! The summary of a [collection](crate::collection) entry, shown in
+//! listings and used as its meta description, feed description, and the
+//! `summary` of its chunks in the [embedding export](crate::embeddings).
+//! In order of precedence:
+//!
+//! 1. The `description` set in the entry's frontmatter.
+//! 2. The body up to a [`MORE_MARKER`] line:
+//!
+//!    ```markdown
+//!    The opening paragraph, which is the excerpt.
+//!
+//!    <!-- more -->
+//!
+//!    The rest of the post.
+//!    ```
+//!
+//! 3. The first [`EXCERPT_SENTENCES`] sentences of the body.
+//!
+//! Excerpts are plain text: Markdown is stripped (see
+//! [`crate::markdown::plain_text`]) and whitespace collapsed.
+
+use crate::markdown::plain_text;
+
+/// Marks the end of the excerpt in an entry's body.
+pub const MORE_MARKER: &str = "<!-- more -->";
+
+/// Sentences in an excerpt taken from a body without a [`MORE_MARKER`].
+pub const EXCERPT_SENTENCES: usize = 2;
+
+/// The summary of an entry with frontmatter `description` and Markdown
+/// `body`.
+pub fn summary(description: &str, body: &str) -> String {
+    if !description.trim().is_empty() {
+        return collapse(description);
+    }
+    excerpt(body, EXCERPT_SENTENCES)
+}
+
+/// The excerpt of a Markdown `body`: the text before its [`MORE_MARKER`],
+/// or else its first `sentences` sentences.
+pub fn excerpt(body: &str, sentences: usize) -> String {
+    match body.find(MORE_MARKER) {
+        Some(end) => collapse(&plain_text(&body[..end])),
+        None => first_sentences(&collapse(&plain_text(body)), sentences),
+    }
+}
+
+/// Collapses runs of whitespace, including newlines, to single spaces.
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The first `count` sentences of `text`. A sentence ends at `.`, `!`,
+/// `?`, or `…`, possibly followed by closing quotes or brackets, before a
+/// space; so `3.5` and `e.g.,` don't end one.
+fn first_sentences(text: &str, count: usize) -> String {
+    let mut ended = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if !matches!(c, '.' | '!' | '?' | '…') {
+            continue;
+        }
+        while let Some(&(_, '"' | '\'' | '”' | '’' | ')' | ']')) = chars.peek() {
+            chars.next();
+        }
+        if let Some(&(i, ' ')) = chars.peek() {
+            ended += 1;
+            if ended == count {
+                return text[..i].to_string();
+            }
+        }
+    }
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontmatter_description_wins() {
+        assert_eq!(summary(" Set  by hand. ", "Body. More."), "Set by hand.");
+        assert_eq!(summary("", "One. Two. Three."), "One. Two.");
+    }
+
+    #[test]
+    fn more_marker_ends_the_excerpt() {
+        let body = "First *paragraph*.\n\nSecond one.\n\n<!-- more -->\n\nHidden.";
+        assert_eq!(excerpt(body, 1), "First paragraph. Second one.");
+    }
+
+    #[test]
+    fn sentences_end_before_spaces() {
+        assert_eq!(
+            first_sentences("Version 3.5 is out, e.g., today! \"Really?\" Yes. No.", 2),
+            "Version 3.5 is out, e.g., today! \"Really?\""
+        );
+        assert_eq!(first_sentences("No ending", 2), "No ending");
+        assert_eq!(first_sentences("Ünïcödé… Ende.", 1), "Ünïcödé…");
+    }
+}
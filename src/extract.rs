@@ -0,0 +1,457 @@
+//! # Plain-Text Extraction
+//!
+//! `--extract-text` writes a `content.txt` beside every page of the built
+//! site, so LLM pipelines can ingest it without HTML-stripping heuristics.
+//! Only the page's semantic text is kept:
+//!
+//! - The page `<title>`, then the contents of `<main>`.
+//! - Site chrome is dropped: the site and section navigation, the footer,
+//!   scripts, styles, SVG, and anything `hidden` or `aria-hidden`.
+//! - Headings, paragraphs, and list items become lines; list items start
+//!   with `- `.
+//! - Links are flattened to `label (url)`, with URLs made absolute; images
+//!   keep their alt text.
+//!
+//! [`page_sections`] splits the same text at its headings for the
+//! [embedding export](crate::embeddings).
+
+use crate::css;
+use scraper::{ElementRef, Html, Node, Selector};
+use std::path::{Path, PathBuf};
+
+/// File name of a page's text beside its `index.html`.
+pub const CONTENT_FILE: &str = "content.txt";
+
+/// Elements whose content is never text.
+const SKIPPED: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "head", "footer",
+];
+
+/// Elements that start a new line.
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "header",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// Elements followed by a blank line.
+const PARAGRAPHS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6", "p"];
+
+/// A heading, its plain title, and the index of its line.
+struct Heading {
+    level: usize,
+    title: String,
+    line: usize,
+}
+
+/// Text collected line by line.
+#[derive(Default)]
+struct Text {
+    lines: Vec<String>,
+    line: String,
+    headings: Vec<Heading>,
+}
+
+/// The text under one heading of a page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageSection {
+    /// Titles of the enclosing headings, outermost first; empty for text
+    /// before the first heading.
+    pub headings: Vec<String>,
+    /// Paragraphs separated by blank lines.
+    pub text: String,
+}
+
+impl Text {
+    /// Appends inline text, collapsing whitespace.
+    fn push(&mut self, text: &str) {
+        for (i, word) in text.split_whitespace().enumerate() {
+            let space = i > 0 || text.starts_with(char::is_whitespace);
+            if space && !self.line.is_empty() && !self.line.ends_with([' ', '(']) {
+                self.line.push(' ');
+            }
+            self.line.push_str(word);
+        }
+        if text.ends_with(char::is_whitespace) && !self.line.is_empty() && !self.line.ends_with(' ')
+        {
+            self.line.push(' ');
+        }
+    }
+
+    /// Ends the current line; `gap` also leaves a blank line.
+    fn end_line(&mut self, gap: bool) {
+        let line = self.line.trim().to_string();
+        self.line.clear();
+        if !line.is_empty() {
+            self.lines.push(line);
+        }
+        if gap && self.lines.last().is_some_and(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.end_line(false);
+        while self.lines.last().is_some_and(String::is_empty) {
+            self.lines.pop();
+        }
+        let mut out = self.lines.join("\n");
+        out.push('\n');
+        out
+    }
+
+    /// Splits the text at its headings.
+    fn sections(mut self) -> Vec<PageSection> {
+        self.end_line(false);
+        let mut sections = Vec::new();
+        let mut outline: Vec<(usize, String)> = Vec::new();
+        let mut start = 0;
+        let ends = self
+            .headings
+            .iter()
+            .map(|h| h.line)
+            .chain([self.lines.len()]);
+        for (i, end) in ends.enumerate() {
+            let text = self.lines[start..end].join("\n").trim().to_string();
+            if !text.is_empty() {
+                sections.push(PageSection {
+                    headings: outline.iter().map(|(_, title)| title.clone()).collect(),
+                    text,
+                });
+            }
+            if let Some(heading) = self.headings.get(i) {
+                outline.retain(|(level, _)| *level < heading.level);
+                outline.push((heading.level, heading.title.clone()));
+                start = heading.line + 1;
+            }
+        }
+        sections
+    }
+}
+
+/// `href` as an absolute URL, relative to the page at `page_url`.
+fn absolute(href: &str, page_url: &str) -> String {
+    if href.contains(':') || href.starts_with("//") {
+        return href.to_string();
+    }
+    let origin_end = page_url
+        .find("://")
+        .and_then(|scheme| page_url[scheme + 3..].find('/').map(|i| scheme + 3 + i))
+        .unwrap_or(page_url.len());
+    if href.starts_with('/') {
+        return format!("{}{}", &page_url[..origin_end], href);
+    }
+    if href.starts_with('#') {
+        return format!("{}{}", page_url.split('#').next().unwrap_or(page_url), href);
+    }
+    let dir_end = page_url.rfind('/').map_or(page_url.len(), |i| i + 1);
+    format!("{}{}", &page_url[..dir_end.max(origin_end)], href)
+}
+
+fn skipped(element: &ElementRef) -> bool {
+    let el = element.value();
+    SKIPPED.contains(&el.name())
+        || el.attr("hidden").is_some()
+        || el.attr("aria-hidden") == Some("true")
+        || (el.name() == "nav"
+            && el
+                .classes()
+                .any(|c| c == css::SITE_NAV || c == css::SECTION_NAV))
+}
+
+fn walk(element: ElementRef, page_url: &str, out: &mut Text) {
+    if skipped(&element) {
+        return;
+    }
+    let name = element.value().name();
+    match name {
+        "br" => return out.end_line(false),
+        "img" => {
+            if let Some(alt) = element.value().attr("alt").filter(|a| !a.trim().is_empty()) {
+                out.push(&format!(" {} ", alt));
+            }
+            return;
+        }
+        _ => {}
+    }
+    let block = BLOCKS.contains(&name);
+    let paragraph = PARAGRAPHS.contains(&name);
+    if block || paragraph {
+        out.end_line(false);
+    }
+    let first_line = out.lines.len();
+    if name == "li" {
+        out.line.push_str("- ");
+    }
+
+    let label_start = out.line.len();
+    let lines_before = out.lines.len();
+    let mut after_element = false;
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => {
+                out.push(text);
+                after_element = false;
+            }
+            Node::Element(_) => {
+                // Adjacent elements, e.g. a link card's label and
+                // description spans, are separate words
+                if after_element {
+                    out.push(" ");
+                }
+                if let Some(child) = ElementRef::wrap(child) {
+                    walk(child, page_url, out);
+                }
+                after_element = true;
+            }
+            _ => {}
+        }
+    }
+
+    if name == "a" {
+        if let Some(href) = element.value().attr("href") {
+            let url = absolute(href, page_url);
+            let label = match out.line.get(label_start..) {
+                Some(label) if out.lines.len() == lines_before => label.trim().to_string(),
+                // The link wraps blocks, which ended lines inside it
+                _ => element.text().collect::<String>().trim().to_string(),
+            };
+            if label.is_empty() {
+                let label = element.value().attr("aria-label").unwrap_or_default();
+                out.push(&format!(" {} ({}) ", label, url));
+            } else if label != url && label != href {
+                out.push(&format!(" ({}) ", url));
+            }
+        }
+    }
+    if block || paragraph {
+        out.end_line(paragraph);
+    }
+    let level = name
+        .strip_prefix('h')
+        .and_then(|n| n.parse::<usize>().ok())
+        .filter(|_| paragraph);
+    if let Some(level) = level.filter(|_| out.lines.len() > first_line) {
+        out.headings.push(Heading {
+            level,
+            title: element
+                .text()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+            line: first_line,
+        });
+    }
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+/// Collects the text of `doc`'s `<main>` (or `<body>`) into `out`.
+fn main_text(doc: &Html, page_url: &str, out: &mut Text) {
+    if let Some(root) = doc
+        .select(&selector("main"))
+        .next()
+        .or_else(|| doc.select(&selector("body")).next())
+    {
+        walk(root, page_url, out);
+    }
+}
+
+/// The semantic text of a rendered page at `page_url`.
+pub fn page_text(html: &str, page_url: &str) -> String {
+    let doc = Html::parse_document(html);
+    let mut out = Text::default();
+    if let Some(title) = doc.select(&selector("title")).next() {
+        out.push(&title.text().collect::<String>());
+        out.end_line(true);
+    }
+    main_text(&doc, page_url, &mut out);
+    out.finish()
+}
+
+/// The semantic text of a rendered page at `page_url`, split at its
+/// headings. The `<title>` is left out; the `<h1>` heads the outline.
+pub fn page_sections(html: &str, page_url: &str) -> Vec<PageSection> {
+    let doc = Html::parse_document(html);
+    let mut out = Text::default();
+    main_text(&doc, page_url, &mut out);
+    out.sections()
+}
+
+/// Where the text of the page at `path` goes: `content.txt` beside an
+/// `index.html`, `<name>.content.txt` beside any other page.
+pub fn content_path(path: &Path) -> PathBuf {
+    match path.file_stem().and_then(|s| s.to_str()) {
+        Some("index") | None => path.with_file_name(CONTENT_FILE),
+        Some(stem) => path.with_file_name(format!("{}.{}", stem, CONTENT_FILE)),
+    }
+}
+
+fn collect(dir: &Path, out: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, out)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("html") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Every page under `dir`, the site at `site_url`, with its URL, in path
+/// order.
+pub fn site_pages(dir: &Path, site_url: &str) -> std::io::Result<Vec<(PathBuf, String)>> {
+    let mut paths = Vec::new();
+    collect(dir, &mut paths)?;
+    paths.sort();
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let relative = path.strip_prefix(dir).unwrap_or(&path).to_string_lossy();
+            let url_path = relative.strip_suffix("index.html").unwrap_or(&relative);
+            let url = format!("{}/{}", site_url, url_path.replace('\\', "/"));
+            (path, url)
+        })
+        .collect())
+}
+
+/// Writes the text of every page under `dir`, the site at `site_url`.
+/// Returns the number of pages extracted.
+pub fn extract_site(dir: &Path, site_url: &str) -> std::io::Result<usize> {
+    let pages = site_pages(dir, site_url)?;
+    for (path, url) in &pages {
+        let text = page_text(&std::fs::read_to_string(path)?, url);
+        std::fs::write(content_path(path), text)?;
+    }
+    Ok(pages.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head><title>Log | Site</title><style>body{}</style></head>
+<body>
+<canvas aria-hidden="true"></canvas>
+<main class="container">
+  <nav class="site-nav" aria-label="Site navigation"><a href="/">Site</a></nav>
+  <h1>Log</h1>
+  <p>Books,   films,
+     and <em>albums</em>.</p>
+  <p><a href="feed.xml">RSS feed</a> or <a href="https://x.test/">https://x.test/</a></p>
+  <ul>
+    <li><a href="/log/#dune"><cite>Dune</cite></a> <img src="/d.jpg" alt="Cover"></li>
+    <li><a href="#x" aria-label="Permalink"><svg></svg></a></li>
+  </ul>
+  <script>ignored()</script>
+</main>
+<footer><p>v1</p></footer>
+</body>
+</html>"##;
+
+    #[test]
+    fn text_keeps_content_and_flattens_links() {
+        assert_eq!(
+            page_text(PAGE, "https://a.test/log/"),
+            "\
+Log | Site
+
+Log
+
+Books, films, and albums.
+
+RSS feed (https://a.test/log/feed.xml) or https://x.test/
+
+- Dune (https://a.test/log/#dune) Cover
+- Permalink (https://a.test/log/#x)
+"
+        );
+    }
+
+    #[test]
+    fn sections_follow_the_heading_outline() {
+        let html = "<main><p>Intro</p><h1>Log</h1><p>All.</p><h2>Books</h2>\
+            <p>Dune.</p><p>Emma.</p><h3>Empty</h3><h2>Films</h2><p>Heat.</p></main>";
+        let sections = page_sections(html, "https://a.test/");
+        let outline: Vec<(Vec<&str>, &str)> = sections
+            .iter()
+            .map(|s| {
+                let headings = s.headings.iter().map(String::as_str).collect();
+                (headings, s.text.as_str())
+            })
+            .collect();
+        assert_eq!(
+            outline,
+            [
+                (vec![], "Intro"),
+                (vec!["Log"], "All."),
+                (vec!["Log", "Books"], "Dune.\n\nEmma."),
+                (vec!["Log", "Films"], "Heat."),
+            ]
+        );
+    }
+
+    #[test]
+    fn links_may_wrap_blocks() {
+        let html = "<main><a href=\"/art/neon/\"><img alt=\"\" src=\"\">\
+            <div><h2>Neon</h2><p>Lights.</p></div></a></main>";
+        assert_eq!(
+            page_text(html, "https://a.test/"),
+            "Neon\n\nLights.\n\n(https://a.test/art/neon/)\n"
+        );
+    }
+
+    #[test]
+    fn relative_links_resolve_against_the_page() {
+        let page = "https://a.test/art/neon/";
+        assert_eq!(absolute("/cv/", page), "https://a.test/cv/");
+        assert_eq!(absolute("001.jpg", page), "https://a.test/art/neon/001.jpg");
+        assert_eq!(absolute("#top", page), "https://a.test/art/neon/#top");
+        assert_eq!(absolute("mailto:a@b.test", page), "mailto:a@b.test");
+        assert_eq!(absolute("/x", "https://a.test"), "https://a.test/x");
+    }
+
+    #[test]
+    fn text_sits_beside_its_page() {
+        assert_eq!(
+            content_path(Path::new("site/cv/index.html")),
+            Path::new("site/cv/content.txt")
+        );
+        assert_eq!(
+            content_path(Path::new("site/404.html")),
+            Path::new("site/404.content.txt")
+        );
+    }
+
+    #[test]
+    fn rendered_pages_extract_without_markup() {
+        for html in crate::css::tests::rendered_pages() {
+            let text = page_text(&html, "https://a.test/");
+            assert!(!text.contains("</"), "{}", text);
+        }
+    }
+}
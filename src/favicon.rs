@@ -0,0 +1,196 @@
+//! # Link Favicons
+//!
+//! Icons for the link cards, harvested at build time and served from this
+//! site, so no visit leaks to a third-party favicon service. For each
+//! external origin the homepage links to, the icon declared by the site's
+//! `<link rel="icon">` (or `/favicon.ico`) is fetched, converted to a
+//! [`FAVICON_SIZE`] square PNG, cached under `data/cache/favicons/`, and
+//! written to `/icons/<host>.png`.
+//!
+//! Fetches go through [`crate::cache`], so `ESART_OFFLINE=1` builds use the
+//! cached icons and a site that is down keeps its last icon.
+
+use crate::cache::{fetch_bytes, fetch_url, offline, CacheEntry, CACHE_DIR};
+use crate::components::LinkEntry;
+use crate::hints::origin;
+use image::imageops::FilterType;
+use image::ImageFormat;
+use scraper::{Html, Selector};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Output subdirectory (and URL path segment) for harvested icons.
+pub const ICONS_DIR: &str = "icons";
+
+/// Width and height of harvested icons in pixels (2x the displayed size).
+pub const FAVICON_SIZE: u32 = 32;
+
+/// How long a cached icon is used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Site-relative icon paths by link URL.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Favicons(BTreeMap<String, String>);
+
+impl Favicons {
+    /// The icon path for the link `href`, if one was harvested.
+    pub fn get(&self, href: &str) -> Option<&str> {
+        self.0.get(href).map(String::as_str)
+    }
+
+    /// Records `path` as the icon of the link `href`.
+    pub fn insert(&mut self, href: &str, path: String) {
+        self.0.insert(href.to_string(), path);
+    }
+}
+
+/// Icon file name for an origin, e.g. `github.com.png`.
+pub fn icon_file_name(origin: &str) -> String {
+    let host = origin.split_once("://").map_or(origin, |(_, host)| host);
+    format!("{}.png", host.replace(':', "_"))
+}
+
+/// Resolves `href` found on the page at `origin` to an absolute URL.
+fn resolve(origin: &str, href: &str) -> String {
+    if href.starts_with("https://") || href.starts_with("http://") {
+        href.to_string()
+    } else if let Some(rest) = href.strip_prefix("//") {
+        format!("https://{}", rest)
+    } else {
+        format!("{}/{}", origin, href.trim_start_matches('/'))
+    }
+}
+
+/// Icon URLs to try for the site at `origin`, given its homepage `html`:
+/// declared icons first (largest `sizes` first, SVG skipped), then
+/// `/favicon.ico`.
+pub fn icon_candidates(origin: &str, html: &str) -> Vec<String> {
+    let doc = Html::parse_document(html);
+    let selector = Selector::parse("link[rel][href]").expect("valid selector");
+    let mut declared: Vec<(u32, String)> = doc
+        .select(&selector)
+        .filter(|link| {
+            let rel = link.value().attr("rel").unwrap_or("").to_ascii_lowercase();
+            rel.split_whitespace()
+                .any(|r| r == "icon" || r == "apple-touch-icon")
+        })
+        .filter_map(|link| {
+            let href = link.value().attr("href")?;
+            let svg = link.value().attr("type") == Some("image/svg+xml")
+                || href.split(['?', '#']).next()?.ends_with(".svg");
+            let size = link
+                .value()
+                .attr("sizes")
+                .and_then(|s| s.split(['x', 'X']).next()?.parse().ok())
+                .unwrap_or(0);
+            (!svg && !href.starts_with("data:")).then(|| (size, resolve(origin, href)))
+        })
+        .collect();
+    declared.sort_by_key(|(size, _)| std::cmp::Reverse(*size));
+    let mut candidates: Vec<String> = declared.into_iter().map(|(_, url)| url).collect();
+    candidates.push(format!("{}/favicon.ico", origin));
+    candidates.dedup();
+    candidates
+}
+
+/// Decodes an icon (PNG, JPEG, or ICO) and re-encodes it as a
+/// [`FAVICON_SIZE`] square PNG.
+pub fn normalize(data: &[u8]) -> Result<Vec<u8>, String> {
+    let icon = image::load_from_memory(data).map_err(|e| e.to_string())?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    icon.resize_exact(FAVICON_SIZE, FAVICON_SIZE, FilterType::Lanczos3)
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(png.into_inner())
+}
+
+/// Fetches and normalizes the icon of the site at `origin`.
+fn fetch_icon(origin: &str) -> Result<Vec<u8>, String> {
+    let html = fetch_url(origin).unwrap_or_default();
+    let mut last_error = format!("No icon found for {}", origin);
+    for url in icon_candidates(origin, &html) {
+        match fetch_bytes(&url).and_then(|data| normalize(&data)) {
+            Ok(png) => return Ok(png),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// Harvests icons for `links` into `<site_dir>/icons/`.
+///
+/// Links sharing an origin share an icon; links whose icon cannot be
+/// fetched or decoded get none.
+pub fn harvest(links: &[&LinkEntry], site_dir: &Path) -> std::io::Result<Favicons> {
+    let cache_dir = Path::new(CACHE_DIR).join("favicons");
+    let out_dir = site_dir.join(ICONS_DIR);
+    let mut icons = Favicons::default();
+    for link in links {
+        let Some(origin) = origin(link.href) else {
+            continue;
+        };
+        let file_name = icon_file_name(&origin);
+        let png =
+            CacheEntry::new(&cache_dir, &file_name)
+                .get_or_fetch_bytes(MAX_AGE, offline(), || fetch_icon(&origin));
+        if let Some(png) = png {
+            std::fs::create_dir_all(&out_dir)?;
+            std::fs::write(out_dir.join(&file_name), png)?;
+            icons.insert(link.href, format!("/{}/{}", ICONS_DIR, file_name));
+        }
+    }
+    Ok(icons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icon_names_come_from_host() {
+        assert_eq!(icon_file_name("https://github.com"), "github.com.png");
+        assert_eq!(
+            icon_file_name("http://localhost:8080"),
+            "localhost_8080.png"
+        );
+    }
+
+    #[test]
+    fn declared_icons_are_tried_largest_first() {
+        let html = r#"<head>
+            <link rel="icon" href="/small.png" sizes="16x16">
+            <link rel="shortcut icon" href="https://cdn.test/big.png" sizes="64x64">
+            <link rel="icon" type="image/svg+xml" href="/logo.svg">
+            <link rel="stylesheet" href="/main.css">
+        </head>"#;
+        assert_eq!(
+            icon_candidates("https://a.test", html),
+            [
+                "https://cdn.test/big.png",
+                "https://a.test/small.png",
+                "https://a.test/favicon.ico"
+            ]
+        );
+    }
+
+    #[test]
+    fn undeclared_icon_falls_back_to_favicon_ico() {
+        assert_eq!(
+            icon_candidates("https://a.test", ""),
+            ["https://a.test/favicon.ico"]
+        );
+    }
+
+    #[test]
+    fn icons_are_normalized_to_square_png() {
+        let mut ico = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::new(48, 48)
+            .write_to(&mut ico, ImageFormat::Ico)
+            .unwrap();
+        let png = normalize(ico.get_ref()).unwrap();
+        let icon = image::load_from_memory_with_format(&png, ImageFormat::Png).unwrap();
+        assert_eq!((icon.width(), icon.height()), (FAVICON_SIZE, FAVICON_SIZE));
+        assert!(normalize(b"<svg/>").is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! # RSS Feeds
+//!
+//! Builds RSS 2.0 documents for generated sections (e.g. `/log/feed.xml`).
+//! Item dates are ISO 8601 calendar dates (`YYYY-MM-DD`) in the source data
+//! and are converted to the RFC 822 form RSS requires (see
+//! [`crate::datetime`]).
+//!
+//! ## Registry
+//!
+//! Every feed is declared once in [`feeds`]. Generators take their channel
+//! and output path from the declaration, and the head advertises it (see
+//! [`Feed::alternate`]): site-wide feeds on every page, section feeds on
+//! their section's page. A new feed only needs a declaration and a
+//! generator.
+//!
+//! With a WebSub hub configured, every generated feed names it (see
+//! [`crate::websub`]).
+
+use crate::alternates::{Alternate, RSS_TYPE};
+use crate::changelog::{CHANGELOG_DESCRIPTION, CHANGELOG_DIR};
+use crate::config::{POSTS, SITE_DESCRIPTION, SITE_NAME, WEBSUB_HUB};
+use crate::datetime::rfc822_date;
+use crate::escape::escape_html;
+use crate::media::{LOG_DESCRIPTION, LOG_DIR};
+use crate::url::{absolute, page_path};
+
+/// Key of the site feed, `/feed.xml`.
+pub const SITE_FEED: &str = "site";
+
+/// Key of the media log feed.
+pub const LOG_FEED: &str = "log";
+
+/// Key of the changelog feed.
+pub const CHANGES_FEED: &str = "changes";
+
+/// A feed the site publishes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feed {
+    pub key: &'static str,
+    pub title: String,
+    pub description: String,
+    /// Site-relative URL of the feed, e.g. `/log/feed.xml`.
+    pub path: String,
+    /// Site-relative URL of the page the feed belongs to, e.g. `/log/`, or
+    /// `None` for site-wide feeds.
+    pub section: Option<String>,
+}
+
+impl Feed {
+    /// The feed's channel metadata.
+    pub fn channel(&self) -> Channel {
+        Channel {
+            title: self.title.clone(),
+            link: absolute(self.section.as_deref().unwrap_or("/")),
+            description: self.description.clone(),
+            self_url: absolute(&self.path),
+            hub: WEBSUB_HUB.map(str::to_string),
+        }
+    }
+
+    /// Output path of the feed, relative to the site root.
+    pub fn file(&self) -> &str {
+        self.path.trim_start_matches('/')
+    }
+
+    /// The feed's autodiscovery link.
+    pub fn alternate(&self) -> Alternate {
+        Alternate::new(RSS_TYPE, self.title.clone(), self.path.clone())
+    }
+
+    /// Whether the head of the page at `page_path` advertises the feed.
+    pub fn advertised_on(&self, page_path: &str) -> bool {
+        self.section
+            .as_deref()
+            .is_none_or(|section| section == page_path)
+    }
+}
+
+/// Every feed the site publishes, site-wide feeds first.
+///
+/// The site feed is a static file in `public/`; the others are generated
+/// with their section.
+pub fn feeds() -> Vec<Feed> {
+    vec![
+        Feed {
+            key: SITE_FEED,
+            title: format!("{} RSS Feed", SITE_NAME),
+            description: SITE_DESCRIPTION.to_string(),
+            path: "/feed.xml".to_string(),
+            section: None,
+        },
+        Feed {
+            key: LOG_FEED,
+            title: format!("{} Log", SITE_NAME),
+            description: LOG_DESCRIPTION.to_string(),
+            path: format!("/{}/feed.xml", LOG_DIR),
+            section: Some(page_path(&[LOG_DIR])),
+        },
+        Feed {
+            key: CHANGES_FEED,
+            title: format!("{} Changes", SITE_NAME),
+            description: CHANGELOG_DESCRIPTION.to_string(),
+            path: "/changes.xml".to_string(),
+            section: Some(page_path(&[CHANGELOG_DIR])),
+        },
+        POSTS.feed(),
+    ]
+}
+
+/// The declared feed `key`.
+///
+/// # Panics
+///
+/// If no feed is declared under `key`.
+pub fn feed(key: &str) -> Feed {
+    feeds()
+        .into_iter()
+        .find(|feed| feed.key == key)
+        .unwrap_or_else(|| panic!("no feed declared as {:?}", key))
+}
+
+/// Autodiscovery links of the feeds advertised on the page at `page_path`.
+pub fn feed_alternates(page_path: &str) -> Vec<Alternate> {
+    feeds()
+        .iter()
+        .filter(|feed| feed.advertised_on(page_path))
+        .map(Feed::alternate)
+        .collect()
+}
+
+/// Feed-level metadata.
+pub struct Channel {
+    pub title: String,
+    /// Absolute URL of the page the feed describes.
+    pub link: String,
+    pub description: String,
+    /// Absolute URL of the feed itself (`atom:link rel="self"`).
+    pub self_url: String,
+    /// WebSub hub readers can subscribe through (`atom:link rel="hub"`).
+    pub hub: Option<String>,
+}
+
+/// One feed entry.
+pub struct FeedItem {
+    pub title: String,
+    /// Absolute URL of the entry.
+    pub link: String,
+    pub description: String,
+    /// ISO date, e.g. `2025-01-17`.
+    pub date: String,
+}
+
+/// Renders an RSS 2.0 feed; items are written in the order given.
+///
+/// `lastBuildDate` is the newest item date so unchanged content produces
+/// identical bytes.
+pub fn rss(channel: &Channel, items: &[FeedItem]) -> String {
+    let last_build = items
+        .iter()
+        .map(|item| item.date.as_str())
+        .max()
+        .and_then(rfc822_date);
+
+    let mut out = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>{}</title>
+    <link>{}</link>
+    <description>{}</description>
+    <language>en-us</language>
+"#,
+        escape_html(&channel.title),
+        escape_html(&channel.link),
+        escape_html(&channel.description),
+    );
+    if let Some(date) = last_build {
+        out.push_str(&format!("    <lastBuildDate>{}</lastBuildDate>\n", date));
+    }
+    out.push_str(&format!(
+        "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+        escape_html(&channel.self_url)
+    ));
+    if let Some(hub) = &channel.hub {
+        out.push_str(&format!(
+            "    <atom:link href=\"{}\" rel=\"hub\"/>\n",
+            escape_html(hub)
+        ));
+    }
+
+    for item in items {
+        out.push_str(&format!(
+            r#"
+    <item>
+      <title>{title}</title>
+      <link>{link}</link>
+      <description>{description}</description>
+"#,
+            title = escape_html(&item.title),
+            link = escape_html(&item.link),
+            description = escape_html(&item.description),
+        ));
+        if let Some(date) = rfc822_date(&item.date) {
+            out.push_str(&format!("      <pubDate>{}</pubDate>\n", date));
+        }
+        out.push_str(&format!(
+            "      <guid>{}</guid>\n    </item>\n",
+            escape_html(&item.link)
+        ));
+    }
+
+    out.push_str("  </channel>\n</rss>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SITE_URL;
+
+    fn channel() -> Channel {
+        Channel {
+            title: "Log".to_string(),
+            link: "https://x.test/log/".to_string(),
+            description: "Things & stuff".to_string(),
+            self_url: "https://x.test/log/feed.xml".to_string(),
+            hub: None,
+        }
+    }
+
+    fn item(date: &str) -> FeedItem {
+        FeedItem {
+            title: "Dune <novel>".to_string(),
+            link: format!("https://x.test/log/#{}", date),
+            description: "Good.".to_string(),
+            date: date.to_string(),
+        }
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let xml = rss(&channel(), &[item("2025-01-17")]);
+        assert!(xml.contains("<title>Dune &lt;novel&gt;</title>"));
+        assert!(xml.contains("Things &amp; stuff"));
+        assert!(xml.contains("<pubDate>Fri, 17 Jan 2025 00:00:00 +0000</pubDate>"));
+    }
+
+    #[test]
+    fn hub_is_linked_when_configured() {
+        assert!(!rss(&channel(), &[]).contains("rel=\"hub\""));
+        let channel = Channel {
+            hub: Some("https://hub.test/".to_string()),
+            ..channel()
+        };
+        assert!(rss(&channel, &[]).contains(r#"<atom:link href="https://hub.test/" rel="hub"/>"#));
+    }
+
+    #[test]
+    fn last_build_date_is_newest_item() {
+        let xml = rss(&channel(), &[item("2025-01-17"), item("2025-03-02")]);
+        assert!(xml.contains("<lastBuildDate>Sun, 02 Mar 2025 00:00:00 +0000</lastBuildDate>"));
+    }
+
+    #[test]
+    fn feeds_have_unique_keys_and_paths() {
+        let feeds = feeds();
+        for (i, feed) in feeds.iter().enumerate() {
+            assert!(feed.path.starts_with('/'), "{:?}", feed.path);
+            for other in &feeds[i + 1..] {
+                assert_ne!(feed.key, other.key);
+                assert_ne!(feed.path, other.path);
+            }
+        }
+    }
+
+    #[test]
+    fn section_feeds_are_advertised_on_their_page() {
+        let paths = |page: &str| -> Vec<String> {
+            feed_alternates(page).into_iter().map(|a| a.href).collect()
+        };
+        assert_eq!(paths("/"), ["/feed.xml"]);
+        assert_eq!(paths("/log/"), ["/feed.xml", "/log/feed.xml"]);
+        assert_eq!(paths("/changelog/"), ["/feed.xml", "/changes.xml"]);
+        assert_eq!(paths("/posts/"), ["/feed.xml", "/posts/feed.xml"]);
+    }
+
+    #[test]
+    fn declared_feeds_describe_their_channel() {
+        let channel = feed(LOG_FEED).channel();
+        assert_eq!(channel.link, format!("{}/log/", SITE_URL));
+        assert_eq!(channel.self_url, format!("{}/log/feed.xml", SITE_URL));
+        assert_eq!(feed(CHANGES_FEED).file(), "changes.xml");
+    }
+
+    #[test]
+    fn empty_feed_has_no_build_date() {
+        let xml = rss(&channel(), &[]);
+        assert!(!xml.contains("lastBuildDate"));
+        assert!(xml.ends_with("</rss>\n"));
+    }
+}
@@ -0,0 +1,166 @@
+//! # Gemini Mirror
+//!
+//! A [Gemtext](https://geminiprotocol.net/docs/gemtext.gmi) mirror of the
+//! site under `/gemini/`, for serving over the Gemini protocol: point a
+//! capsule server (e.g. Agate) at `target/site/gemini/`.
+//!
+//! - `index.gmi`: the profile, with links to the other documents
+//! - `links.gmi`: every homepage link, by [`LinkGroup`]
+//! - `log.gmi`: the media log, when there is one
+//! - `art.gmi`: the listed art series, linking their web pages
+//!
+//! Links between the documents are relative, so the mirror works from any
+//! capsule root. Gemtext has no escaping: text is folded onto one line and
+//! lines that would read as markup get a leading space.
+
+use crate::art::ArtSeries;
+use crate::components::{rendered_links, LinkGroup};
+use crate::entity::Entity;
+use crate::media::{stars, MediaLog};
+use crate::url::absolute;
+
+/// Output subdirectory of the mirror.
+pub const GEMINI_DIR: &str = "gemini";
+
+/// Line-type prefixes of Gemtext.
+const MARKUP: [&str; 5] = ["=>", "#", "*", ">", "```"];
+
+/// `text` as one plain Gemtext line.
+fn line(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if MARKUP.iter().any(|prefix| text.starts_with(prefix)) {
+        format!(" {}", text)
+    } else {
+        text
+    }
+}
+
+/// A link line to `url` labelled `label`.
+fn link(url: &str, label: &str) -> String {
+    format!("=> {} {}", url, line(label))
+}
+
+/// `index.gmi`: the profile of `entity`, linking the log and art documents
+/// when they exist.
+pub fn profile(entity: &Entity, has_log: bool, has_art: bool) -> String {
+    let mut out = vec![
+        format!("# {}", line(entity.name)),
+        String::new(),
+        line(entity.description),
+        String::new(),
+        link("links.gmi", "Links"),
+    ];
+    if has_log {
+        out.push(link("log.gmi", "Log: books, films, and albums"));
+    }
+    if has_art {
+        out.push(link("art.gmi", "Art series"));
+    }
+    out.push(link(entity.url, "Website"));
+    out.join("\n") + "\n"
+}
+
+/// `links.gmi`: the homepage links by group, in display order.
+pub fn links() -> String {
+    let links = rendered_links();
+    let mut out = vec!["# Links".to_string()];
+    for group in LinkGroup::ALL {
+        let members: Vec<_> = links.iter().filter(|l| l.group == group).collect();
+        if members.is_empty() {
+            continue;
+        }
+        out.push(String::new());
+        out.push(format!("## {}", group.label()));
+        for l in members {
+            out.push(link(l.href, l.label));
+            if let Some(description) = l.description {
+                out.push(line(description));
+            }
+        }
+    }
+    out.join("\n") + "\n"
+}
+
+/// `log.gmi`: the media log, newest first.
+pub fn log(log: &MediaLog) -> String {
+    let mut out = vec!["# Log".to_string()];
+    for entry in &log.entries {
+        out.push(String::new());
+        out.push(format!("## {}", line(&entry.title)));
+        let mut byline = format!("{} by {}", entry.kind.label(), entry.creator);
+        if let Some(year) = entry.year {
+            byline.push_str(&format!(" ({})", year));
+        }
+        byline.push_str(&format!(", {}", entry.date));
+        if let Some(rating) = entry.rating {
+            byline.push_str(&format!(" {}", stars(rating)));
+        }
+        out.push(line(&byline));
+        for paragraph in entry.note.split("\n\n").filter(|p| !p.trim().is_empty()) {
+            out.push(line(paragraph));
+        }
+        if let Some(url) = &entry.url {
+            out.push(link(url, &entry.title));
+        }
+    }
+    out.join("\n") + "\n"
+}
+
+/// `art.gmi`: the art series, each linking its web page.
+pub fn art(series: &[ArtSeries]) -> String {
+    let mut out = vec!["# Art series".to_string()];
+    for s in series {
+        out.push(String::new());
+        out.push(link(
+            &absolute(&s.url_path()),
+            &format!("{} ({})", s.title, s.date),
+        ));
+        out.push(line(&s.description));
+    }
+    out.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::LINKS;
+    use crate::entity::primary_entity;
+
+    #[test]
+    fn markup_in_text_stays_text() {
+        assert_eq!(line("# not a heading"), " # not a heading");
+        assert_eq!(line("=> nor\na link"), " => nor a link");
+        assert_eq!(line("plain  text"), "plain text");
+    }
+
+    #[test]
+    fn profile_links_the_documents_that_exist() {
+        let gmi = profile(primary_entity(), true, false);
+        assert!(gmi.starts_with(&format!("# {}\n", primary_entity().name)));
+        assert!(gmi.contains("=> links.gmi Links\n"));
+        assert!(gmi.contains("=> log.gmi "));
+        assert!(!gmi.contains("art.gmi"));
+    }
+
+    #[test]
+    fn links_are_grouped() {
+        let gmi = links();
+        let first = &LINKS[0];
+        assert!(gmi.contains(&format!("## {}\n", first.group.label())));
+        assert!(gmi.contains(&format!("=> {} {}\n", first.href, first.label)));
+    }
+
+    #[test]
+    fn log_entries_become_sections() {
+        let media = MediaLog::parse(
+            "[[entry]]\nkind = \"book\"\ntitle = \"The Dispossessed\"\n\
+             creator = \"Ursula K. Le Guin\"\nyear = 1974\ndate = \"2025-01-17\"\n\
+             rating = 4\nnote = \"Still the best.\"\n",
+        )
+        .unwrap();
+        let gmi = log(&media);
+        assert!(gmi.contains(
+            "## The Dispossessed\nBook by Ursula K. Le Guin (1974), 2025-01-17 ★★★★☆\nStill the best.\n"
+        ));
+    }
+}
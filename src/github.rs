@@ -0,0 +1,137 @@
+//! # GitHub Contribution Calendar
+//!
+//! Fetches the public contribution calendar for [`GITHUB_USER`] at build
+//! time (through [`crate::cache`]) and parses it into days for the `/activity/`
+//! heatmap. No API token is needed: the calendar is the same HTML fragment
+//! GitHub renders on the profile page.
+
+use crate::cache::cached_fetch;
+use scraper::{Html, Selector};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// GitHub account whose contributions are shown.
+pub const GITHUB_USER: &str = "EverythingSings";
+
+/// Directory of the heatmap page.
+pub const ACTIVITY_DIR: &str = "activity";
+
+/// Cache entry holding the calendar fragment.
+pub const CACHE_NAME: &str = "github-contributions.html";
+
+/// How long a cached calendar is used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// One day of the contribution calendar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContributionDay {
+    /// ISO date, e.g. `2025-01-31`.
+    pub date: String,
+    /// GitHub's intensity bucket, 0 (none) to 4 (most).
+    pub level: u8,
+    pub count: u32,
+}
+
+/// URL of the calendar fragment for `user`.
+pub fn contributions_url(user: &str) -> String {
+    format!("https://github.com/users/{}/contributions", user)
+}
+
+/// URL of the GitHub profile for `user`.
+pub fn profile_url(user: &str) -> String {
+    format!("https://github.com/{}", user)
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+/// Parses the calendar fragment into days, sorted by date.
+///
+/// Levels come from each cell's `data-level`; counts from the matching
+/// `<tool-tip>` text ("3 contributions on …", "No contributions on …").
+pub fn parse_contributions(html: &str) -> Vec<ContributionDay> {
+    let doc = Html::parse_fragment(html);
+
+    let counts: HashMap<&str, u32> = doc
+        .select(&selector("tool-tip[for]"))
+        .filter_map(|tip| {
+            let text = tip.text().collect::<String>();
+            let count = text.split_whitespace().next()?.parse().unwrap_or(0);
+            Some((tip.value().attr("for")?, count))
+        })
+        .collect();
+
+    let mut days: Vec<ContributionDay> = doc
+        .select(&selector("td[data-date][data-level]"))
+        .filter_map(|cell| {
+            let el = cell.value();
+            Some(ContributionDay {
+                date: el.attr("data-date")?.to_string(),
+                level: el.attr("data-level")?.parse().ok()?,
+                count: el
+                    .attr("id")
+                    .and_then(|id| counts.get(id).copied())
+                    .unwrap_or(0),
+            })
+        })
+        .collect();
+    days.sort_by(|a, b| a.date.cmp(&b.date));
+    days
+}
+
+/// Loads the calendar for [`GITHUB_USER`], fetching it if the cache is stale.
+///
+/// Returns no days when the calendar is neither cached nor reachable.
+pub fn load_contributions() -> Vec<ContributionDay> {
+    cached_fetch(CACHE_NAME, &contributions_url(GITHUB_USER), MAX_AGE)
+        .map(|html| parse_contributions(&html))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAGMENT: &str = r#"
+<table class="ContributionCalendar-grid"><tbody>
+<tr>
+  <td data-date="2025-01-05" id="contribution-day-component-0-0" data-level="0" class="ContributionCalendar-day"></td>
+  <td data-date="2025-01-12" id="contribution-day-component-0-1" data-level="3" class="ContributionCalendar-day"></td>
+</tr>
+<tr>
+  <td data-date="2025-01-06" id="contribution-day-component-1-0" data-level="1" class="ContributionCalendar-day"></td>
+</tr>
+</tbody></table>
+<tool-tip for="contribution-day-component-0-0">No contributions on January 5th.</tool-tip>
+<tool-tip for="contribution-day-component-0-1">12 contributions on January 12th.</tool-tip>
+<tool-tip for="contribution-day-component-1-0">1 contribution on January 6th.</tool-tip>
+"#;
+
+    #[test]
+    fn parses_days_in_date_order() {
+        let days = parse_contributions(FRAGMENT);
+        let dates: Vec<&str> = days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2025-01-05", "2025-01-06", "2025-01-12"]);
+    }
+
+    #[test]
+    fn parses_levels_and_counts() {
+        let days = parse_contributions(FRAGMENT);
+        assert_eq!(
+            days[2],
+            ContributionDay {
+                date: "2025-01-12".to_string(),
+                level: 3,
+                count: 12,
+            }
+        );
+        assert_eq!(days[0].count, 0, "\"No contributions\" is zero");
+        assert_eq!(days[1].count, 1);
+    }
+
+    #[test]
+    fn unrelated_html_has_no_days() {
+        assert!(parse_contributions("<p>rate limited</p>").is_empty());
+    }
+}
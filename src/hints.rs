@@ -0,0 +1,225 @@
+//! # Resource Hints
+//!
+//! Early `<link>` hints for the homepage head: `preconnect` for the external
+//! origins the page links to most prominently, `dns-prefetch` for the rest,
+//! and `preload` for the avatar and any self-hosted fonts. Origins come from
+//! the rendered links, so a hint never points somewhere the page doesn't.
+//!
+//! Limits are deliberately small: every preconnect costs a socket and a TLS
+//! handshake whether or not the visitor follows the link.
+
+use crate::components::rendered_links;
+use crate::config::{AVATAR_PATH, SITE_URL};
+use crate::escape::escape_html;
+use crate::responsive::{source_width, srcset, AVATAR_SIZES, AVATAR_WIDTHS};
+
+/// Origins given a `preconnect` hint, in link order.
+pub const PRECONNECT_LIMIT: usize = 2;
+
+/// Further origins given a `dns-prefetch` hint.
+pub const DNS_PREFETCH_LIMIT: usize = 4;
+
+/// Self-hosted font files to preload (site-relative `.woff2` paths).
+///
+/// Empty while the site uses the system font stack.
+pub const PRELOAD_FONTS: &[&str] = &[];
+
+/// A single resource hint.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResourceHint {
+    Preconnect(String),
+    DnsPrefetch(String),
+    /// A resource needed for first render, with its `as` destination.
+    Preload {
+        href: String,
+        destination: &'static str,
+        mime: Option<&'static str>,
+        /// Responsive candidates and sizes, matching the `<img>` so the
+        /// preload fetches the same variant.
+        srcset: Option<(String, &'static str)>,
+    },
+}
+
+impl ResourceHint {
+    /// Renders the hint as a `<link>` element.
+    pub fn to_html(&self) -> String {
+        match self {
+            ResourceHint::Preconnect(origin) => {
+                format!(
+                    r#"<link rel="preconnect" href="{}" />"#,
+                    escape_html(origin)
+                )
+            }
+            ResourceHint::DnsPrefetch(origin) => {
+                format!(
+                    r#"<link rel="dns-prefetch" href="{}" />"#,
+                    escape_html(origin)
+                )
+            }
+            ResourceHint::Preload {
+                href,
+                destination,
+                mime,
+                srcset,
+            } => {
+                let mut html = format!(
+                    r#"<link rel="preload" href="{}" as="{}""#,
+                    escape_html(href),
+                    destination
+                );
+                if let Some(mime) = mime {
+                    html.push_str(&format!(r#" type="{}""#, mime));
+                }
+                if let Some((candidates, sizes)) = srcset {
+                    html.push_str(&format!(
+                        r#" imagesrcset="{}" imagesizes="{}""#,
+                        escape_html(candidates),
+                        sizes
+                    ));
+                }
+                // Fonts are always fetched in CORS mode
+                if *destination == "font" {
+                    html.push_str(" crossorigin");
+                }
+                if *destination == "image" {
+                    html.push_str(r#" fetchpriority="high""#);
+                }
+                html.push_str(" />");
+                html
+            }
+        }
+    }
+}
+
+/// The origin (`scheme://host[:port]`) of an absolute http(s) URL.
+pub fn origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    if scheme != "https" && scheme != "http" {
+        return None;
+    }
+    let host = rest.split(['/', '?', '#']).next()?;
+    (!host.is_empty()).then(|| format!("{}://{}", scheme, host.to_ascii_lowercase()))
+}
+
+/// Distinct external origins of `urls` in first-seen order, excluding the
+/// site's own origin.
+pub fn external_origins<'a>(urls: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let own = origin(SITE_URL);
+    let mut origins: Vec<String> = Vec::new();
+    for o in urls.into_iter().filter_map(origin) {
+        if Some(&o) != own.as_ref() && !origins.contains(&o) {
+            origins.push(o);
+        }
+    }
+    origins
+}
+
+/// Builds hints for `origins` (capped at the preconnect and dns-prefetch
+/// limits) followed by preloads for `avatar` and `fonts`.
+pub fn build_hints(origins: &[String], avatar: Option<&str>, fonts: &[&str]) -> Vec<ResourceHint> {
+    let mut hints: Vec<ResourceHint> = origins
+        .iter()
+        .take(PRECONNECT_LIMIT + DNS_PREFETCH_LIMIT)
+        .enumerate()
+        .map(|(i, o)| {
+            if i < PRECONNECT_LIMIT {
+                ResourceHint::Preconnect(o.clone())
+            } else {
+                ResourceHint::DnsPrefetch(o.clone())
+            }
+        })
+        .collect();
+    hints.extend(avatar.map(|href| {
+        ResourceHint::Preload {
+            href: href.to_string(),
+            destination: "image",
+            mime: None,
+            srcset: source_width(href)
+                .and_then(|w| srcset(href, AVATAR_WIDTHS, w))
+                .map(|candidates| (candidates, AVATAR_SIZES)),
+        }
+    }));
+    hints.extend(fonts.iter().map(|href| ResourceHint::Preload {
+        href: href.to_string(),
+        destination: "font",
+        mime: Some("font/woff2"),
+        srcset: None,
+    }));
+    hints
+}
+
+/// Hints for the homepage: its profile links, avatar, and fonts.
+pub fn homepage_hints() -> Vec<ResourceHint> {
+    let urls = rendered_links().into_iter().map(|link| link.href);
+    build_hints(&external_origins(urls), Some(AVATAR_PATH), PRELOAD_FONTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::LINKS;
+
+    #[test]
+    fn origins_drop_paths_and_lowercase_hosts() {
+        assert_eq!(
+            origin("https://Music.Apple.com/us/artist/1?x=y").as_deref(),
+            Some("https://music.apple.com")
+        );
+        assert_eq!(
+            origin("http://localhost:8080").as_deref(),
+            Some("http://localhost:8080")
+        );
+        assert_eq!(origin("mailto:a@b.c"), None);
+        assert_eq!(origin("/relative"), None);
+    }
+
+    #[test]
+    fn external_origins_are_distinct_and_exclude_site() {
+        let origins = external_origins([
+            "https://a.test/1",
+            "https://everythingsings.art/cv/",
+            "https://a.test/2",
+            "https://b.test",
+        ]);
+        assert_eq!(origins, ["https://a.test", "https://b.test"]);
+    }
+
+    #[test]
+    fn hints_are_capped() {
+        let origins: Vec<String> = (0..10).map(|i| format!("https://{}.test", i)).collect();
+        let hints = build_hints(&origins, None, &[]);
+        assert_eq!(hints.len(), PRECONNECT_LIMIT + DNS_PREFETCH_LIMIT);
+        assert_eq!(
+            hints[0],
+            ResourceHint::Preconnect("https://0.test".to_string())
+        );
+        assert_eq!(
+            hints[PRECONNECT_LIMIT],
+            ResourceHint::DnsPrefetch(format!("https://{}.test", PRECONNECT_LIMIT))
+        );
+    }
+
+    #[test]
+    fn preloads_render_destination_attributes() {
+        let hints = build_hints(&[], Some("/avatar.png"), &["/fonts/a.woff2"]);
+        assert_eq!(
+            hints[0].to_html(),
+            r#"<link rel="preload" href="/avatar.png" as="image" imagesrcset="/avatar-128.png 128w, /avatar-256.png 256w, /avatar-384.png 384w, /avatar.png 512w" imagesizes="128px" fetchpriority="high" />"#
+        );
+        assert_eq!(
+            hints[1].to_html(),
+            r#"<link rel="preload" href="/fonts/a.woff2" as="font" type="font/woff2" crossorigin />"#
+        );
+    }
+
+    #[test]
+    fn homepage_hints_cover_profile_links() {
+        let hints = homepage_hints();
+        let first = origin(LINKS[0].href).unwrap();
+        assert!(hints.contains(&ResourceHint::Preconnect(first)));
+        assert!(hints.iter().any(|h| matches!(
+            h,
+            ResourceHint::Preload { href, .. } if href == AVATAR_PATH
+        )));
+    }
+}
@@ -0,0 +1,152 @@
+//! # Internal Links
+//!
+//! Markdown links to other content by its source file, resolved to the
+//! page's permalink when the site is built:
+//!
+//! ```markdown
+//! See [the last walk](@/posts/night-walk.md) or
+//! [its notes](@/posts/night-walk/index.md#notes).
+//! ```
+//!
+//! Paths after `@/` are relative to `content/`, so a link keeps working
+//! when the target's slug, date, or permalink template changes. A link to
+//! a file that isn't loaded content (a typo, a deleted post, a draft) fails
+//! the build naming both files, instead of shipping a 404.
+
+use crate::collection::{Collection, Content, INDEX_FILE};
+use crate::markdown::link_destinations;
+use std::collections::BTreeMap;
+
+/// Prefix of an internal link's destination.
+pub const INTERNAL_PREFIX: &str = "@/";
+
+/// Directory internal link paths are relative to.
+pub const CONTENT_DIR: &str = "content";
+
+/// The pages internal links can point at: content path (e.g.
+/// `posts/night-walk.md`) to site-relative URL.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkTargets {
+    urls: BTreeMap<String, String>,
+}
+
+impl LinkTargets {
+    /// Adds the entries of `collection`.
+    pub fn add<T: Content>(&mut self, collection: &Collection<T>) {
+        let dir = collection
+            .def
+            .source
+            .strip_prefix(CONTENT_DIR)
+            .map_or(collection.def.source, |rest| rest.trim_start_matches('/'));
+        for entry in &collection.entries {
+            let file = |path: &std::path::Path| {
+                path.file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            };
+            let name = match entry.source.ends_with(INDEX_FILE) {
+                true => match entry.source.parent() {
+                    Some(parent) => format!("{}/{}", file(parent), INDEX_FILE),
+                    None => INDEX_FILE.to_string(),
+                },
+                false => file(&entry.source),
+            };
+            self.urls
+                .insert(format!("{}/{}", dir, name), entry.url_path());
+        }
+    }
+
+    /// The URL `destination` links to: `Ok(None)` for a destination that
+    /// isn't internal, an error when its target isn't known.
+    pub fn resolve(&self, destination: &str) -> Result<Option<String>, String> {
+        let Some(path) = destination.strip_prefix(INTERNAL_PREFIX) else {
+            return Ok(None);
+        };
+        let (path, fragment) = match path.split_once('#') {
+            Some((path, fragment)) => (path, Some(fragment)),
+            None => (path, None),
+        };
+        let url = self
+            .urls
+            .get(path)
+            .ok_or_else(|| format!("Link to {}: no such content", destination))?;
+        Ok(Some(match fragment {
+            Some(fragment) => format!("{}#{}", url, fragment),
+            None => url.clone(),
+        }))
+    }
+
+    /// Resolves the internal links of every entry in `collection`, for
+    /// rendering. An error names the entry and its broken link.
+    pub fn resolve_collection<T: Content>(
+        &self,
+        collection: &mut Collection<T>,
+    ) -> Result<(), String> {
+        for entry in &mut collection.entries {
+            for destination in link_destinations(&entry.body) {
+                if let Some(url) = self
+                    .resolve(&destination)
+                    .map_err(|e| format!("{}: {}", entry.source.display(), e))?
+                {
+                    entry.links.insert(destination, url);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posts::tests::sample_posts;
+
+    fn targets() -> LinkTargets {
+        let mut targets = LinkTargets::default();
+        targets.add(&sample_posts());
+        targets
+    }
+
+    #[test]
+    fn content_paths_resolve_to_permalinks() {
+        let targets = targets();
+        assert_eq!(
+            targets.resolve("@/posts/night-walk.md").unwrap().as_deref(),
+            Some("/posts/night-walk/")
+        );
+        assert_eq!(
+            targets
+                .resolve("@/posts/hello-world.md#ink")
+                .unwrap()
+                .as_deref(),
+            Some("/posts/hello-world/#ink")
+        );
+        assert_eq!(targets.resolve("https://a.test/").unwrap(), None);
+        assert_eq!(targets.resolve("/posts/").unwrap(), None);
+    }
+
+    #[test]
+    fn missing_targets_are_errors() {
+        let targets = targets();
+        assert!(targets
+            .resolve("@/posts/unfinished.md")
+            .unwrap_err()
+            .contains("@/posts/unfinished.md"));
+        assert!(targets.resolve("@/posts/nope.md").is_err());
+    }
+
+    #[test]
+    fn entries_record_their_resolved_links() {
+        let targets = targets();
+        let mut posts = sample_posts();
+        posts.entries[0].body = "[Hello](@/posts/hello-world.md)".to_string();
+        targets.resolve_collection(&mut posts).unwrap();
+        assert!(posts.entries[0]
+            .body_html()
+            .contains(r#"<a href="/posts/hello-world/">"#));
+
+        posts.entries[1].body = "[Gone](@/posts/gone.md)".to_string();
+        let err = targets.resolve_collection(&mut posts).unwrap_err();
+        assert!(err.starts_with("hello-world.md: "), "{}", err);
+    }
+}
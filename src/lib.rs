@@ -12,14 +12,102 @@
 //! - No client-side JavaScript required for content access
 //! - Designed for ~24KB WASM stub with zero islands
 
+pub mod a11y;
+pub mod alternates;
+pub mod analytics;
+pub mod api;
 pub mod app;
+pub mod apple_music;
+pub mod archive;
 pub mod art;
+pub mod budget;
+pub mod build;
+pub mod cache;
+pub mod cards;
+pub mod changelog;
+pub mod clean;
+pub mod collection;
 pub mod components;
+pub mod copy;
+pub mod css;
+pub mod datetime;
+pub mod direction;
+pub mod dns;
+pub mod document;
+pub mod embeddings;
+pub mod embeds;
+pub mod entity;
+pub mod epub;
+pub mod escape;
+pub mod excerpt;
+pub mod extract;
+pub mod favicon;
+pub mod feed;
+pub mod gemini;
+pub mod github;
+pub mod hints;
+pub mod internal_links;
+pub mod linkcheck;
+pub mod listens;
+pub mod markdown;
+pub mod mastodon;
+pub mod mcp;
+pub mod media;
+pub mod meta_tags;
+pub mod music;
+pub mod nostr;
+pub mod odesli;
+pub mod oembed;
+pub mod pagination;
+pub mod permalink;
+pub mod placeholder;
+pub mod plan;
+pub mod portfolio;
+pub mod posts;
+pub mod press;
+pub mod prints;
+pub mod properties;
+pub mod protected;
+pub mod provenance;
+pub mod reactions;
+pub mod redirect;
+pub mod report;
+pub mod responsive;
+pub mod resume;
+pub mod schema;
+pub mod serve;
+pub mod single_file;
+pub mod site;
+pub mod slug;
+pub mod speculation;
+pub mod status;
+pub mod structured_data;
+pub mod taxonomy;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod text;
+pub mod theme;
+pub mod tokens;
+pub mod typography;
+pub mod unlisted;
+pub mod url;
+pub mod webmention;
+pub mod websub;
+pub mod well_known;
 
 pub use app::App;
 
 /// Site configuration constants.
 pub mod config {
+    use crate::analytics::Analytics;
+    use crate::collection::CollectionDef;
+    use crate::copy::{CopyOptions, Symlinks};
+    use crate::entity::Entity;
+    use crate::listens::ListenSource;
+    use crate::properties::Property;
+    use crate::taxonomy::{Taxonomy, TermProperty};
+    use crate::well_known::{Lnurlp, TdmRep};
+
     /// The artist/site name.
     pub const SITE_NAME: &str = "EverythingSings";
 
@@ -29,12 +117,225 @@ pub mod config {
     /// Full site URL.
     pub const SITE_URL: &str = "https://everythingsings.art";
 
+    /// BCP 47 language of the homepage. Right-to-left languages such as
+    /// `ar` or `he` also set `dir="rtl"` (see [`crate::direction`]).
+    pub const SITE_LANGUAGE: &str = "en";
+
+    /// Open Graph locale of the site's pages (see [`crate::meta_tags`]).
+    pub const SITE_LOCALE: &str = "en_US";
+
+    /// Output layout of pages: `Directory` writes `cv/index.html`, linked
+    /// as `/cv/`; `File` writes `cv.html`, linked as `/cv.html` (see
+    /// [`crate::url`]).
+    pub const OUTPUT_LAYOUT: crate::url::Layout = crate::url::Layout::Directory;
+
+    /// How `public/` is copied into the output: symlinks followed, and
+    /// Finder metadata and Photoshop sources left out (see [`crate::copy`]).
+    pub const ASSET_COPY: CopyOptions = CopyOptions {
+        symlinks: Symlinks::Follow,
+        ignore: &[".DS_Store", "*.psd"],
+    };
+
+    /// Permalink template of art series pages (see [`crate::permalink`]).
+    pub const ART_PERMALINK: &str = "/art/:slug/";
+
+    /// Permalink template of release pages.
+    pub const RELEASE_PERMALINK: &str = "/music/:slug/";
+
+    /// Curly quotes, dashes, and no-break spaces in Markdown and in the
+    /// config text shown on pages (see [`crate::typography`]).
+    pub const TYPOGRAPHY: bool = true;
+
+    /// Deepest heading level listed in a post's table of contents (see
+    /// [`crate::components::Toc`]).
+    pub const TOC_DEPTH: u8 = 3;
+
+    /// Blog posts, from `content/posts/` (see [`crate::posts`]). Another
+    /// content type is another [`CollectionDef`].
+    pub const POSTS: CollectionDef = CollectionDef {
+        key: "posts",
+        source: "content/posts",
+        dir: "posts",
+        permalink: "/posts/:slug/",
+        title: "Posts",
+        description: "Notes on art, tools, and process.",
+        per_page: 10,
+    };
+
+    /// Taxonomies entries can be filed under, shared by every collection
+    /// (see [`crate::taxonomy`]).
+    pub const TAXONOMIES: &[Taxonomy] = &[
+        Taxonomy {
+            key: "series",
+            dir: "series",
+            title: "Series",
+            description: "Work that belongs together.",
+            property: TermProperty::About,
+        },
+        Taxonomy {
+            key: "medium",
+            dir: "medium",
+            title: "Medium",
+            description: "Work by medium and tool.",
+            property: TermProperty::Keywords,
+        },
+        Taxonomy {
+            key: "mood",
+            dir: "mood",
+            title: "Mood",
+            description: "Work by mood.",
+            property: TermProperty::Keywords,
+        },
+    ];
+
     /// Site description for meta tags and JSON-LD.
     pub const SITE_DESCRIPTION: &str =
         "Formless art brand for the future. Exploring AI, art, and sovereign technology.";
 
+    /// Rights statement for the site's content, published as `dc.rights`.
+    pub const SITE_RIGHTS: &str = "© EverythingSings. All rights reserved.";
+
     /// Path to avatar image (relative to site root).
     pub const AVATAR_PATH: &str = "/avatar.png";
+
+    /// Entities rendered as profile cards and JSON-LD nodes, in display order.
+    /// At least one is required; the build fails to compile without.
+    ///
+    /// Add a second entry (e.g. the human behind the brand) with `relations`
+    /// pointing at the other entity's `key` to link them in the JSON-LD graph.
+    pub const ENTITIES: &[Entity] = &[Entity {
+        key: "everythingsings",
+        schema_type: "Person",
+        name: SITE_NAME,
+        description: SITE_DESCRIPTION,
+        image: AVATAR_PATH,
+        url: SITE_URL,
+        email: None,
+        relations: &[],
+        identifiers: &[],
+    }];
+
+    /// Related sites on subdomains, linked from every page's footer and
+    /// described as parts of this site in JSON-LD.
+    pub const PROPERTIES: &[Property] = &[
+        Property {
+            key: "lumimenta",
+            name: "Lumimenta",
+            url: "https://lumimenta.everythingsings.art",
+            description: "Art trading cards.",
+        },
+        Property {
+            key: "sigil",
+            name: "Sigil",
+            url: "https://sigil.everythingsings.art",
+            description: "Generative sigils drawn as Lissajous curves.",
+        },
+    ];
+
+    /// Key of the entity used for the homepage title and Open Graph tags.
+    pub const PRIMARY_ENTITY: &str = "everythingsings";
+
+    /// Publish entity emails as machine-readable `mailto:` links (`u-email`).
+    ///
+    /// Off by default: the obfuscated address stays readable to people while
+    /// keeping the plain address out of the markup.
+    pub const PUBLISH_EMAIL: bool = false;
+
+    /// Source for the homepage "Recently listening to" strip, e.g.
+    /// `Some(ListenSource::ListenBrainz("username"))`; `None` hides it.
+    pub const LISTENS_SOURCE: Option<ListenSource> = None;
+
+    /// Show each link's Wayback Machine snapshot (recorded by
+    /// `archive-links`) as a fallback beside it.
+    pub const ARCHIVE_FALLBACK_LINKS: bool = false;
+
+    /// Fetch each linked site's favicon at build time and show it on its
+    /// link card, self-hosted under `/icons/`. Off by default, since it
+    /// fetches from every linked site on the first build.
+    pub const LINK_FAVICONS: bool = false;
+
+    /// Redbubble shop RSS feed for the `/prints/` "Latest prints" page,
+    /// e.g. `Some("https://www.redbubble.com/people/bedim/shop.rss")`;
+    /// `None` omits the page.
+    pub const REDBUBBLE_FEED: Option<&str> = None;
+
+    /// Webmention endpoint that receives mentions of every page, e.g.
+    /// `Some("https://webmention.io/everythingsings.art/webmention")`. It is
+    /// advertised in the head and the local server's `Link` header, art and
+    /// release pages get a form posting to it, and guestbook signatures go
+    /// to it; `None` omits all of that and the `/guestbook/` page.
+    pub const WEBMENTION_ENDPOINT: Option<&str> = None;
+
+    /// Pingback endpoint advertised alongside the webmention endpoint, for
+    /// older blog engines, e.g.
+    /// `Some("https://webmention.io/everythingsings.art/xmlrpc")`.
+    pub const PINGBACK_ENDPOINT: Option<&str> = None;
+
+    /// WebSub hub named in generated feeds and pinged by `ping-hub` (see
+    /// [`crate::websub`]), e.g. `Some("https://pubsubhubbub.appspot.com/")`.
+    pub const WEBSUB_HUB: Option<&str> = None;
+
+    /// Contact published in `/.well-known/security.txt`, e.g.
+    /// `Some("mailto:security@everythingsings.art")`; `None` omits the file.
+    /// (All `.well-known` documents: see [`crate::well_known`].)
+    pub const SECURITY_CONTACT: Option<&str> = None;
+
+    /// NIP-05 names and their hex public keys for
+    /// `/.well-known/nostr.json`, e.g. `&[("_", "3bf0c63f…")]` to verify
+    /// `_@everythingsings.art` (shown by clients as `everythingsings.art`).
+    pub const NOSTR_NAMES: &[(&str, &str)] = &[];
+
+    /// Fediverse account `/.well-known/webfinger` points at, e.g.
+    /// `Some("@everythingsings@mastodon.social")`, so searching for any
+    /// `@…@everythingsings.art` finds it.
+    pub const FEDIVERSE_ACCOUNT: Option<&str> = None;
+
+    /// Lightning address served from `/.well-known/lnurlp/<user>`.
+    pub const LNURLP: Option<Lnurlp> = None;
+
+    /// Text and data mining reservation published as
+    /// `/.well-known/tdmrep.json`, e.g.
+    /// `Some(TdmRep { reserved: true, policy: None })`.
+    pub const TDMREP: Option<TdmRep> = None;
+
+    /// Keybase's signed website proof, relative to the project root,
+    /// published as `/.well-known/keybase.txt`; e.g. `Some("keybase.txt")`
+    /// after saving the statement `keybase prove web` prints. Other
+    /// identity claims go in each entity's `identifiers`.
+    pub const KEYBASE_PROOF: Option<&str> = None;
+
+    /// Keybase's DNS proof of the domain, e.g.
+    /// `Some("keybase-site-verification=…")`, checked by `dns-check` at
+    /// `_keybase.everythingsings.art`.
+    pub const KEYBASE_DNS_PROOF: Option<&str> = None;
+
+    /// URL of the brand's SVG Tiny PS logo for BIMI, checked by `dns-check`
+    /// at `default._bimi.everythingsings.art`.
+    pub const BIMI_LOGO: Option<&str> = None;
+
+    /// The AT Protocol DID of the Bluesky account using the domain as its
+    /// handle, e.g. `Some("did:plc:…")`. Published as
+    /// `/.well-known/atproto-did` and in the primary entity's `identifier`
+    /// list, adds the Bluesky link to Connect, and is checked by `dns-check`
+    /// at `_atproto.everythingsings.art`.
+    pub const ATPROTO_DID: Option<&str> = None;
+
+    /// Other TXT records `dns-check` expects, as `(name, value)` with `@`
+    /// for the apex, e.g. a Nostr relay's domain verification token.
+    pub const DNS_TXT_RECORDS: &[(&str, &str)] = &[];
+
+    /// Show guestbook signatures only once approved in `guestbook.toml`.
+    pub const GUESTBOOK_REQUIRE_APPROVAL: bool = true;
+
+    /// Page-view analytics. `Analytics::Off` adds nothing; a script
+    /// provider, e.g. `Analytics::Plausible { domain: SITE_DOMAIN,
+    /// integrity: "sha384-…" }`, adds its script and a matching CSP.
+    pub const ANALYTICS: Analytics = Analytics::Off;
+
+    /// Query parameters appended to links marked `campaign` when rendered,
+    /// e.g. `&[("ref", "everythingsings.art")]`. Empty disables tagging.
+    /// Exports (JSON-LD, `/api/*.json`) always use the untagged URL.
+    pub const CAMPAIGN_PARAMS: &[(&str, &str)] = &[];
 }
 
 #[cfg(test)]
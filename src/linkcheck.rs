@@ -0,0 +1,118 @@
+//! # Link Verification
+//!
+//! `check-links` requests every homepage link and records when each last
+//! answered successfully in `data/links-verified.json`:
+//!
+//! ```json
+//! { "https://github.com/EverythingSings": "2025-01-17T12:00:00Z" }
+//! ```
+//!
+//! The build reads the file (if present) to add `data-verified` to links
+//! and a `verified` field to `/api/links.json`, so consumers can tell how
+//! fresh each link is. Failed checks keep the previous timestamp.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Verification state file, relative to the project root.
+pub const VERIFIED_PATH: &str = "data/links-verified.json";
+
+/// User agent sent with link checks.
+const USER_AGENT: &str = concat!("everythingsings-linkcheck/", env!("CARGO_PKG_VERSION"));
+
+/// When each link URL was last verified, as RFC 3339 timestamps.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VerifiedLinks(BTreeMap<String, String>);
+
+impl VerifiedLinks {
+    /// Loads the state from `path`; `Ok(None)` when the file is absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map(Some)
+            .map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    /// Parses the state file contents.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json)
+            .map(VerifiedLinks)
+            .map_err(|e| e.to_string())
+    }
+
+    /// When `url` was last verified.
+    pub fn get(&self, url: &str) -> Option<&str> {
+        self.0.get(url).map(String::as_str)
+    }
+
+    /// Records that `url` was verified at `at`.
+    pub fn record(&mut self, url: &str, at: String) {
+        self.0.insert(url.to_string(), at);
+    }
+
+    /// The most recent verification time of any link.
+    pub fn latest(&self) -> Option<&str> {
+        self.0.values().map(String::as_str).max()
+    }
+
+    /// Serializes the state, one URL per line in sorted order so diffs stay
+    /// small.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.0).expect("link state serializes")
+    }
+
+    /// Writes the state to `path`, creating its directory.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_json() + "\n")
+    }
+}
+
+/// Requests `url`; any 2xx or 3xx response (after redirects) verifies it.
+pub fn check_url(url: &str) -> Result<(), String> {
+    ureq::get(url)
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .map(|_| ())
+        .map_err(|e| format!("GET {}: {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_round_trips_sorted_by_url() {
+        let mut state = VerifiedLinks::default();
+        state.record("https://b.test", "2025-01-02T00:00:00Z".to_string());
+        state.record("https://a.test", "2025-01-01T00:00:00Z".to_string());
+        let json = state.to_json();
+        assert!(json.find("a.test") < json.find("b.test"));
+        assert_eq!(VerifiedLinks::parse(&json).unwrap(), state);
+    }
+
+    #[test]
+    fn recording_replaces_timestamp() {
+        let mut state = VerifiedLinks::default();
+        state.record("https://a.test", "2025-01-01T00:00:00Z".to_string());
+        state.record("https://a.test", "2025-02-01T00:00:00Z".to_string());
+        assert_eq!(state.get("https://a.test"), Some("2025-02-01T00:00:00Z"));
+        assert_eq!(state.latest(), Some("2025-02-01T00:00:00Z"));
+        assert_eq!(state.get("https://b.test"), None);
+    }
+
+    #[test]
+    fn malformed_state_is_an_error() {
+        assert!(VerifiedLinks::parse("[1, 2]").is_err());
+        assert_eq!(
+            VerifiedLinks::load(Path::new("missing/links.json")),
+            Ok(None)
+        );
+    }
+}
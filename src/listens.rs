@@ -0,0 +1,241 @@
+//! # Recent Listens
+//!
+//! Pulls recently played tracks from ListenBrainz or Last.fm at build time
+//! (through [`crate::cache`]) for the homepage "Recently listening to"
+//! strip. The integration is off unless
+//! [`LISTENS_SOURCE`](crate::config::LISTENS_SOURCE) is set.
+//!
+//! ListenBrainz listens are public and need no credentials. Last.fm needs an
+//! API key in the `LASTFM_API_KEY` environment variable.
+
+use crate::cache::{cached_fetch, json_text};
+use crate::text::{append_query, encode_path_segment};
+use serde_json::Value;
+use std::time::Duration;
+
+/// Number of tracks shown in the strip.
+pub const LISTENS_LIMIT: usize = 5;
+
+/// Environment variable holding the Last.fm API key.
+pub const LASTFM_API_KEY_ENV: &str = "LASTFM_API_KEY";
+
+/// How long cached listens are used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Where recent listens come from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListenSource {
+    /// A ListenBrainz username.
+    ListenBrainz(&'static str),
+    /// A Last.fm username.
+    LastFm(&'static str),
+}
+
+impl ListenSource {
+    /// Cache entry name for this source and user, so changing either
+    /// never serves the previous one's listens.
+    pub fn cache_name(&self) -> String {
+        let (service, user) = match self {
+            ListenSource::ListenBrainz(user) => ("listenbrainz", user),
+            ListenSource::LastFm(user) => ("lastfm", user),
+        };
+        format!("listens-{}-{}.json", service, encode_path_segment(user))
+    }
+
+    /// API URL for the most recent listens with `api_key`, which only
+    /// Last.fm needs.
+    fn api_url(&self, api_key: &str) -> String {
+        let limit = LISTENS_LIMIT.to_string();
+        match self {
+            ListenSource::ListenBrainz(user) => append_query(
+                &format!(
+                    "https://api.listenbrainz.org/1/user/{}/listens",
+                    encode_path_segment(user)
+                ),
+                &[("count", &limit)],
+            ),
+            ListenSource::LastFm(user) => append_query(
+                "https://ws.audioscrobbler.com/2.0/",
+                &[
+                    ("method", "user.getrecenttracks"),
+                    ("user", user),
+                    ("api_key", api_key),
+                    ("format", "json"),
+                    ("limit", &limit),
+                ],
+            ),
+        }
+    }
+
+    /// API URL for the most recent listens, or `None` when a required
+    /// credential is missing.
+    fn url(&self) -> Option<String> {
+        match self {
+            ListenSource::ListenBrainz(_) => Some(self.api_url("")),
+            ListenSource::LastFm(_) => {
+                let key = std::env::var(LASTFM_API_KEY_ENV).ok()?;
+                Some(self.api_url(&key))
+            }
+        }
+    }
+
+    /// Parses an API response from this source.
+    pub fn parse(&self, json: &str) -> Vec<Listen> {
+        match self {
+            ListenSource::ListenBrainz(_) => parse_listenbrainz(json),
+            ListenSource::LastFm(_) => parse_lastfm(json),
+        }
+    }
+}
+
+/// One played track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Listen {
+    pub track: String,
+    pub artist: String,
+    pub release: Option<String>,
+    /// Canonical page for the recording (MusicBrainz or Last.fm).
+    pub url: Option<String>,
+    /// Currently playing rather than already scrobbled.
+    pub now_playing: bool,
+}
+
+/// Parses a ListenBrainz `/1/user/{user}/listens` response.
+pub fn parse_listenbrainz(json: &str) -> Vec<Listen> {
+    let Ok(doc) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    doc["payload"]["listens"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|listen| {
+            let meta = &listen["track_metadata"];
+            let mbid = json_text(&meta["additional_info"], "recording_mbid")
+                .or_else(|| json_text(&meta["mbid_mapping"], "recording_mbid"));
+            Some(Listen {
+                track: json_text(meta, "track_name")?,
+                artist: json_text(meta, "artist_name")?,
+                release: json_text(meta, "release_name"),
+                url: mbid.map(|id| format!("https://musicbrainz.org/recording/{}", id)),
+                now_playing: listen["playing_now"].as_bool().unwrap_or(false),
+            })
+        })
+        .take(LISTENS_LIMIT)
+        .collect()
+}
+
+/// Parses a Last.fm `user.getrecenttracks` response.
+pub fn parse_lastfm(json: &str) -> Vec<Listen> {
+    let Ok(doc) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    doc["recenttracks"]["track"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|track| {
+            Some(Listen {
+                track: json_text(track, "name")?,
+                artist: json_text(&track["artist"], "#text")?,
+                release: json_text(&track["album"], "#text"),
+                url: json_text(track, "url"),
+                now_playing: track["@attr"]["nowplaying"] == "true",
+            })
+        })
+        .take(LISTENS_LIMIT)
+        .collect()
+}
+
+/// Loads recent listens for the configured source.
+///
+/// Returns no listens when the integration is off, a credential is missing,
+/// or the source is neither cached nor reachable.
+pub fn load_recent_listens() -> Vec<Listen> {
+    let Some(source) = crate::config::LISTENS_SOURCE else {
+        return Vec::new();
+    };
+    let Some(url) = source.url() else {
+        eprintln!(
+            "Warning: {} is not set; skipping recent listens",
+            LASTFM_API_KEY_ENV
+        );
+        return Vec::new();
+    };
+    cached_fetch(&source.cache_name(), &url, MAX_AGE)
+        .map(|json| source.parse(&json))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTENBRAINZ: &str = r#"{"payload": {"count": 2, "listens": [
+        {"listened_at": 1736000000, "track_metadata": {
+            "artist_name": "Boards of Canada", "track_name": "Roygbiv",
+            "release_name": "Music Has the Right to Children",
+            "additional_info": {"recording_mbid": "abc-123"}}},
+        {"listened_at": 1735990000, "track_metadata": {
+            "artist_name": "Aphex Twin", "track_name": "Xtal"}}
+    ]}}"#;
+
+    const LASTFM: &str = r##"{"recenttracks": {"track": [
+        {"artist": {"#text": "Burial"}, "name": "Archangel",
+         "album": {"#text": "Untrue"}, "url": "https://www.last.fm/music/Burial/_/Archangel",
+         "@attr": {"nowplaying": "true"}},
+        {"artist": {"#text": "Burial"}, "name": "Near Dark", "album": {"#text": ""},
+         "url": "https://www.last.fm/music/Burial/_/Near+Dark", "date": {"uts": "1736000000"}}
+    ]}}"##;
+
+    #[test]
+    fn parses_listenbrainz_listens() {
+        let listens = parse_listenbrainz(LISTENBRAINZ);
+        assert_eq!(
+            listens[0],
+            Listen {
+                track: "Roygbiv".to_string(),
+                artist: "Boards of Canada".to_string(),
+                release: Some("Music Has the Right to Children".to_string()),
+                url: Some("https://musicbrainz.org/recording/abc-123".to_string()),
+                now_playing: false,
+            }
+        );
+        assert_eq!(listens[1].release, None);
+        assert_eq!(listens[1].url, None);
+    }
+
+    #[test]
+    fn parses_lastfm_tracks() {
+        let listens = parse_lastfm(LASTFM);
+        assert_eq!(listens.len(), 2);
+        assert!(listens[0].now_playing);
+        assert_eq!(listens[0].release.as_deref(), Some("Untrue"));
+        assert!(!listens[1].now_playing);
+        assert_eq!(listens[1].release, None, "Empty album is omitted");
+    }
+
+    #[test]
+    fn malformed_responses_have_no_listens() {
+        assert!(parse_listenbrainz("<html>").is_empty());
+        assert!(parse_lastfm(r#"{"error": 10}"#).is_empty());
+    }
+
+    #[test]
+    fn users_are_encoded_and_cached_apart() {
+        let source = ListenSource::LastFm("a b&c");
+        assert_eq!(
+            source.api_url("k"),
+            "https://ws.audioscrobbler.com/2.0/?method=user.getrecenttracks&user=a%20b%26c&api_key=k&format=json&limit=5"
+        );
+        assert_eq!(
+            ListenSource::ListenBrainz("a/b").api_url(""),
+            "https://api.listenbrainz.org/1/user/a%2Fb/listens?count=5"
+        );
+        assert_eq!(source.cache_name(), "listens-lastfm-a%20b%26c.json");
+        assert_ne!(
+            ListenSource::ListenBrainz("a").cache_name(),
+            ListenSource::ListenBrainz("b").cache_name()
+        );
+    }
+}
@@ -3,196 +3,882 @@
 //! Entry point for generating the static site. Run with `--generate-static`
 //! to output HTML to `target/site/`.
 
-use everythingsings::art::{discover_series, ArtSeries};
+use everythingsings::a11y::{audit_outline_site, audit_site};
+use everythingsings::alternates::{Alternate, JSON_TYPE};
+use everythingsings::analytics::integrity_mismatch;
+use everythingsings::api::{generate_links_json, generate_profile_json, API_DIR};
+use everythingsings::apple_music::{self, AppleMusicItem};
+use everythingsings::archive::{
+    external_links, save_url, ArchivedLinks, Snapshot, ARCHIVE_PATH, REARCHIVE_AFTER,
+    REQUEST_INTERVAL,
+};
+use everythingsings::art::{self, discover_series, ArtSeries};
+use everythingsings::budget::{Budgets, BUDGET_PATH};
+use everythingsings::build::{Scope, Task};
+use everythingsings::cache::{offline, with_offline, CacheEntry, CACHE_DIR};
+use everythingsings::cards::{Card, CardManifest, CARDS_DIR};
+use everythingsings::changelog::{
+    feed_items, load_changes, Change, CHANGELOG_DESCRIPTION, CHANGELOG_DIR, CHANGELOG_LIMIT,
+};
+use everythingsings::clean::{self, MARKER_FILE};
 use everythingsings::components::{
-    generate_head_html, generate_head_html_for, ArtIndexPage, ArtIndexPageProps, ArtSeriesPage,
-    ArtSeriesPageProps, PageMeta, SigilPage,
+    generate_head_html, generate_head_html_for, rendered_links, ActivityPage, ActivityPageProps,
+    ArtIndexPage, ArtIndexPageProps, ArtSeriesPage, ArtSeriesPageProps, CardPage, CardPageProps,
+    ChangelogPage, ChangelogPageProps, CvPage, CvPageProps, GuestbookPage, LinkKind, LogPage,
+    LogPageProps, MusicPage, MusicPageProps, PageMeta, PostPage, PostPageProps, PostsPage,
+    PostsPageProps, PressPage, PressPageProps, PrintsPage, PrintsPageProps, ProtectedPage,
+    ProtectedPageProps, ReleasePage, ReleasePageProps, SigilPage, SitePage, TaxonomyPage,
+    TaxonomyPageProps, TermPage, TermPageProps, UNLOCK_SCRIPT,
+};
+use everythingsings::config::{
+    ANALYTICS, ARCHIVE_FALLBACK_LINKS, ASSET_COPY, AVATAR_PATH, ENTITIES,
+    GUESTBOOK_REQUIRE_APPROVAL, KEYBASE_PROOF, LINK_FAVICONS, LISTENS_SOURCE, POSTS,
+    REDBUBBLE_FEED, SITE_DESCRIPTION, SITE_DOMAIN, SITE_LANGUAGE, SITE_NAME, SITE_RIGHTS, SITE_URL,
+    TAXONOMIES, WEBMENTION_ENDPOINT, WEBSUB_HUB,
+};
+use everythingsings::copy::{copy_dir_contents, copy_dir_contents_except};
+use everythingsings::datetime::{rfc3339, Date};
+use everythingsings::direction::logical_properties;
+use everythingsings::dns;
+use everythingsings::document::Document;
+use everythingsings::embeddings::{generate_embeddings, Chunk, EMBEDDINGS_PATH};
+use everythingsings::embeds::{resolve as resolve_embeds, Embed};
+use everythingsings::entity::primary_entity;
+use everythingsings::epub::{self, Book, EPUB_PATH};
+use everythingsings::escape::{escape_html, json_string};
+use everythingsings::extract::{extract_site, site_pages, CONTENT_FILE};
+use everythingsings::favicon::{harvest, Favicons};
+use everythingsings::feed::{feed, feeds, rss, CHANGES_FEED, LOG_FEED};
+use everythingsings::gemini::{self, GEMINI_DIR};
+use everythingsings::github::{load_contributions, ContributionDay, ACTIVITY_DIR, CACHE_NAME};
+use everythingsings::internal_links::LinkTargets;
+use everythingsings::linkcheck::{check_url, VerifiedLinks, VERIFIED_PATH};
+use everythingsings::listens::{load_recent_listens, Listen};
+use everythingsings::mastodon::{self, load_comments, Comments};
+use everythingsings::mcp::{gallery_json, posts_json, McpServer, Resource};
+use everythingsings::media::{MediaLog, LOG_DESCRIPTION, LOG_DIR, MEDIA_PATH};
+use everythingsings::meta_tags::{MetaImage, OgType};
+use everythingsings::music::{
+    og_image, Release, Releases, MUSIC_DESCRIPTION, MUSIC_DIR, OG_FILE, OG_HEIGHT, OG_WIDTH,
+    RELEASES_PATH,
 };
-use everythingsings::config::{SITE_NAME, SITE_URL};
+use everythingsings::nostr;
+use everythingsings::odesli::load_links;
+use everythingsings::oembed::{
+    oembed_alternate, release_oembed, series_oembed, site_oembed, OEMBED_FILE,
+};
+use everythingsings::pagination::Pagination;
+use everythingsings::permalink::check_unique;
+use everythingsings::plan::{generate_plan, PLAN_PATH};
+use everythingsings::portfolio::{self, PORTFOLIO_PATH};
+use everythingsings::posts::{listing_json_ld, PostEntry, Posts};
+use everythingsings::press::{checksums_file, PressFile, PressKit, PRESS_DIR, PRESS_PATH};
+use everythingsings::prints::{self, prints_json_ld, Print, PRINTS_DESCRIPTION, PRINTS_DIR};
+use everythingsings::properties::website_json_ld;
+use everythingsings::protected::{self, ProtectedPages, Sealed, PROTECTED_DIR, PROTECTED_PATH};
+use everythingsings::reactions::Reactions;
+use everythingsings::redirect;
+use everythingsings::report::{BuildReport, ReportDiff, REPORT_PATH};
+use everythingsings::responsive::{is_raster, write_variants, AVATAR_WIDTHS};
+use everythingsings::resume::{Resume, RESUME_PATH};
+#[cfg(feature = "embed")]
+use everythingsings::serve::EmbeddedFiles;
+use everythingsings::serve::{serve, DiskFiles, DEFAULT_ADDR};
+use everythingsings::single_file::{self, SINGLE_FILE_PATH};
+use everythingsings::site::{discover_sites, SiteConfig, SITES_DIR, SITES_OUTPUT_DIR};
+use everythingsings::speculation::SpeculationRules;
+use everythingsings::status::{generate_status_json, STATUS_PATH};
+use everythingsings::structured_data::render_with_graph;
+use everythingsings::taxonomy::{index_json_ld, term_json_ld, terms, Taxonomy, Term};
+use everythingsings::tokens::{
+    check_theme_contrast, generate_tokens_css, generate_tokens_json, ContrastPolicy,
+    CONTRAST_POLICY, DARK, LIGHT,
+};
+use everythingsings::unlisted;
+use everythingsings::url::{absolute, page_file, page_path, HOME};
+use everythingsings::webmention::{
+    guestbook_json_ld, load_mentions, Mention, Moderation, GUESTBOOK_DESCRIPTION, GUESTBOOK_DIR,
+    MODERATION_PATH,
+};
+use everythingsings::websub;
+use everythingsings::well_known::{self, WELL_KNOWN_DIR};
 use everythingsings::App;
 use leptos::prelude::*;
+use std::collections::HashSet;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-/// Generates the complete HTML document for the homepage.
-fn render_to_html() -> String {
-    let head_html = generate_head_html();
-    let body_html = App().to_html();
+/// Generates the complete HTML document for the homepage in `lang`. The
+/// pages the build `generated` are prefetched, and the prints page and
+/// activity heatmap linked when among them.
+fn render_to_html(
+    lang: &str,
+    listens: Vec<Listen>,
+    verified: VerifiedLinks,
+    archived: ArchivedLinks,
+    icons: Favicons,
+    generated: &[String],
+) -> Document {
+    let prints = generated.contains(&page_path(&[PRINTS_DIR]));
+    let activity = generated.contains(&page_path(&[ACTIVITY_DIR]));
+    let (body_html, graph) = render_with_graph(|| {
+        view! {
+            <App
+                listens=listens
+                verified=verified
+                archived=archived
+                icons=icons
+                prints=prints
+                activity=activity
+            />
+        }
+        .to_html()
+    });
+    let head_html = generate_head_html(&graph, generated);
 
-    format!(
-        r#"<!DOCTYPE html>
-<html lang="en">
-{head_html}
-{body_html}
-</html>"#
-    )
+    Document {
+        lang: lang.to_string(),
+        ..Document::new(head_html, body_html)
+    }
 }
 
 /// Generates the art index page HTML.
-fn render_art_index(series: &[ArtSeries]) -> String {
+fn render_art_index(series: &[ArtSeries]) -> Document {
     let json_ld = format!(
         r#"{{
   "@context": "https://schema.org",
   "@type": "CollectionPage",
-  "name": "{name} Art Gallery",
-  "url": "{url}/art/",
-  "description": "AI art series by {name}"
+  "name": {name},
+  "url": {url},
+  "description": {description}
 }}"#,
-        name = SITE_NAME,
-        url = SITE_URL,
+        name = json_string(&format!("{} Art Gallery", SITE_NAME)),
+        url = json_string(&absolute(&page_path(&["art"]))),
+        description = json_string(&format!("AI art series by {}", SITE_NAME)),
     );
 
     let head_html = generate_head_html_for(&PageMeta {
         title: format!("Art Gallery | {}", SITE_NAME),
         description: format!("AI art series by {}", SITE_NAME),
-        canonical_url: format!("{}/art/", SITE_URL),
-        og_type: "website".to_string(),
-        og_image: series
-            .first()
-            .map(|s| format!("{}{}", SITE_URL, s.cover_url))
-            .unwrap_or_default(),
+        canonical_url: absolute(&page_path(&["art"])),
+        og_type: OgType::Website,
+        og_image: series.first().map(ArtSeries::cover_image),
         json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(
+            std::iter::once("/".to_string()).chain(series.iter().map(ArtSeries::url_path)),
+        ),
     });
 
-    let body_html = ArtIndexPage(ArtIndexPageProps {
+    let body = ArtIndexPage(ArtIndexPageProps {
         series: series.to_vec(),
     })
-    .to_html();
+    .into_any();
 
-    format!(
-        r#"<!DOCTYPE html>
-<html lang="en">
-{head_html}
-{body_html}
-</html>"#
-    )
+    Document::new(head_html, body)
+}
+
+/// Loads the reaction counts of a series' syndicated copies.
+fn load_series_reactions(series: &ArtSeries) -> Vec<Reactions> {
+    let mastodon = series.comments.as_ref().and_then(mastodon::load_reactions);
+    let nostr = series.nostr_note.as_deref().and_then(nostr::load_reactions);
+    mastodon.into_iter().chain(nostr).collect()
 }
 
 /// Generates an individual art series page HTML.
-fn render_art_series(series: &ArtSeries) -> String {
+fn render_art_series(
+    series: &ArtSeries,
+    release: Option<AppleMusicItem>,
+    embeds: Vec<Embed>,
+    reactions: Vec<Reactions>,
+    comments: Option<Comments>,
+) -> Document {
+    let (body_html, graph) = render_with_graph(|| {
+        ArtSeriesPage(ArtSeriesPageProps {
+            series: series.clone(),
+            release,
+            embeds,
+            reactions,
+            comments,
+            mention_endpoint: WEBMENTION_ENDPOINT,
+        })
+        .to_html()
+    });
+
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {} Art", series.title, SITE_NAME),
+        description: series.description.clone(),
+        canonical_url: absolute(&series.url_path()),
+        og_type: OgType::Article {
+            published_time: series.date.clone(),
+            author: absolute("/"),
+        },
+        og_image: Some(series.cover_image()),
+        json_ld: graph.to_json_ld(),
+        alternates: vec![oembed_alternate(&series.title, &series.dir_path())],
+        pagination: None,
+        noindex: series.unlisted,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch([page_path(&["art"]), "/".to_string()]),
+    });
+
+    Document::new(head_html, body_html)
+}
+
+/// Generates the sigil page HTML.
+fn render_sigil() -> Document {
     let json_ld = format!(
         r#"{{
   "@context": "https://schema.org",
-  "@type": "ImageGallery",
-  "name": "{title}",
-  "url": "{url}/art/{slug}/",
-  "description": "{description}",
-  "numberOfItems": {count}
+  "@type": "ImageObject",
+  "name": {name},
+  "url": {url},
+  "description": {description}
 }}"#,
-        title = series.title,
-        url = SITE_URL,
-        slug = series.slug,
-        description = series.description,
-        count = series.images.len(),
+        name = json_string(&format!("{} Sigil", SITE_NAME)),
+        url = json_string(&absolute(&page_path(&["sigil"]))),
+        description = json_string("EverythingSings logo — a Lissajous curve"),
     );
 
     let head_html = generate_head_html_for(&PageMeta {
-        title: format!("{} | {} Art", series.title, SITE_NAME),
-        description: series.description.clone(),
-        canonical_url: format!("{}/art/{}/", SITE_URL, series.slug),
-        og_type: "website".to_string(),
-        og_image: format!("{}{}", SITE_URL, series.cover_url),
+        title: format!("Sigil | {}", SITE_NAME),
+        description: "EverythingSings logo — a Lissajous curve".to_string(),
+        canonical_url: absolute(&page_path(&["sigil"])),
+        og_type: OgType::Website,
+        og_image: None,
         json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
     });
 
-    let body_html = ArtSeriesPage(ArtSeriesPageProps {
-        series: series.clone(),
+    let body = SigilPage().into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the HTML of the protected page `page`, its content `sealed`.
+fn render_protected_page(page: &protected::ProtectedPage, sealed: Sealed) -> Document {
+    let url = absolute(&page_path(&[PROTECTED_DIR, &page.slug]));
+    let json_ld = format!(
+        r#"{{
+  "@context": "https://schema.org",
+  "@type": "WebPage",
+  "name": {name},
+  "url": {url}
+}}"#,
+        name = json_string(&page.title),
+        url = json_string(&url),
+    );
+
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {}", page.title, SITE_NAME),
+        description: "A passphrase-protected page.".to_string(),
+        canonical_url: url,
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: true,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = ProtectedPage(ProtectedPageProps {
+        title: page.title.clone(),
+        sealed,
     })
-    .to_html();
+    .into_any();
 
-    format!(
-        r#"<!DOCTYPE html>
-<html lang="en">
-{head_html}
-{body_html}
-</html>"#
-    )
+    let mut page = Document::new(head_html, body);
+    page.end_of_body_scripts.push(UNLOCK_SCRIPT.to_string());
+    page
 }
 
-/// Generates the sigil page HTML.
-fn render_sigil() -> String {
+/// Generates the press kit page HTML.
+fn render_press_page(kit: &PressKit, files: &[PressFile]) -> Document {
+    let items: Vec<String> = files
+        .iter()
+        .map(|f| {
+            let mut fields = vec![
+                "\"@type\": \"MediaObject\"".to_string(),
+                format!("\"name\": {}", json_string(&f.name)),
+                format!("\"contentUrl\": {}", json_string(&absolute(&f.url_path()))),
+                format!("\"encodingFormat\": {}", json_string(f.encoding_format)),
+                format!("\"contentSize\": \"{} B\"", f.bytes),
+                format!("\"sha256\": {}", json_string(&f.sha256)),
+            ];
+            if let Some((w, h)) = f.dimensions {
+                fields.push(format!("\"width\": {}, \"height\": {}", w, h));
+            }
+            format!("    {{ {} }}", fields.join(", "))
+        })
+        .collect();
+
     let json_ld = format!(
         r#"{{
   "@context": "https://schema.org",
-  "@type": "ImageObject",
-  "name": "{name} Sigil",
-  "url": "{url}/sigil/",
-  "description": "EverythingSings logo — a Lissajous curve"
+  "@type": "ItemList",
+  "name": {name},
+  "url": {url},
+  "description": {description},
+  "numberOfItems": {count},
+  "itemListElement": [
+{items}
+  ]
 }}"#,
-        name = SITE_NAME,
-        url = SITE_URL,
+        name = json_string(&format!("{} Press Kit", SITE_NAME)),
+        url = json_string(&absolute(&page_path(&[PRESS_DIR]))),
+        description = json_string(&kit.short_bio),
+        count = files.len(),
+        items = items.join(",\n"),
     );
 
     let head_html = generate_head_html_for(&PageMeta {
-        title: format!("Sigil | {}", SITE_NAME),
-        description: "EverythingSings logo — a Lissajous curve".to_string(),
-        canonical_url: format!("{}/sigil/", SITE_URL),
-        og_type: "website".to_string(),
-        og_image: String::new(),
+        title: format!("Press Kit | {}", SITE_NAME),
+        description: kit.short_bio.clone(),
+        canonical_url: absolute(&page_path(&[PRESS_DIR])),
+        og_type: OgType::Website,
+        og_image: None,
         json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
     });
 
-    let body_html = SigilPage().to_html();
+    let body = PressPage(PressPageProps {
+        kit: kit.clone(),
+        files: files.to_vec(),
+    })
+    .into_any();
 
-    format!(
-        r#"<!DOCTYPE html>
-<html lang="en">
-{head_html}
-{body_html}
-</html>"#
-    )
+    Document::new(head_html, body)
 }
 
-/// Generates sitemap.xml content including art pages.
-fn generate_sitemap(series: &[ArtSeries]) -> String {
-    let mut urls = vec![
+/// Generates the CV page HTML.
+fn render_cv_page(resume: &Resume) -> Document {
+    let url = absolute(&page_path(&["cv"]));
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("CV | {}", resume.basics.name),
+        description: resume.basics.summary.clone(),
+        canonical_url: url.clone(),
+        og_type: OgType::Profile,
+        og_image: None,
+        json_ld: resume.to_json_ld(&primary_entity().id(), &url),
+        alternates: vec![Alternate::new(JSON_TYPE, "JSON Resume", "/resume.json")],
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = CvPage(CvPageProps {
+        resume: resume.clone(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the media log page HTML.
+fn render_log_page(log: &MediaLog) -> Document {
+    let url = absolute(&page_path(&[LOG_DIR]));
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("Log | {}", SITE_NAME),
+        description: LOG_DESCRIPTION.to_string(),
+        canonical_url: url.clone(),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: log.to_json_ld(&url, &primary_entity().id()),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = LogPage(LogPageProps { log: log.clone() }).into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the music page HTML.
+fn render_music_page(releases: &Releases) -> Document {
+    let url = absolute(&page_path(&[MUSIC_DIR]));
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("Music | {}", SITE_NAME),
+        description: MUSIC_DESCRIPTION.to_string(),
+        canonical_url: url.clone(),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: releases.to_json_ld(&url, &primary_entity().id()),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = MusicPage(MusicPageProps {
+        releases: releases.clone(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the "Latest prints" page HTML. `shop` is the shop's URL.
+fn render_prints_page(prints: &[Print], shop: &'static str) -> Document {
+    let url = absolute(&page_path(&[PRINTS_DIR]));
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("Latest prints | {}", SITE_NAME),
+        description: PRINTS_DESCRIPTION.to_string(),
+        canonical_url: url.clone(),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: prints_json_ld(prints, &url, SITE_URL),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = PrintsPage(PrintsPageProps {
+        prints: prints.to_vec(),
+        shop,
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates a release page HTML. `og_image` is the site-relative path of
+/// its Open Graph image, if one was generated.
+fn render_release_page(release: &Release, og_image: Option<&str>) -> Document {
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {} Music", release.title, SITE_NAME),
+        description: release.summary(),
+        canonical_url: absolute(&release.url_path()),
+        og_type: OgType::MusicAlbum {
+            musician: absolute("/"),
+            release_date: release.date.clone(),
+        },
+        og_image: og_image.map(|path| {
+            MetaImage::new(absolute(path))
+                .size(OG_WIDTH, OG_HEIGHT)
+                .alt(format!("Cover of {}", release.title))
+        }),
+        json_ld: release.to_json_ld(&primary_entity().id()),
+        alternates: vec![oembed_alternate(&release.title, &release.dir_path())],
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch([page_path(&[MUSIC_DIR]), "/".to_string()]),
+    });
+
+    let body = ReleasePage(ReleasePageProps {
+        release: release.clone(),
+        mention_endpoint: WEBMENTION_ENDPOINT,
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates page `pagination` of the posts listing, showing `entries`.
+fn render_posts_page(posts: &Posts, pagination: Pagination, entries: &[PostEntry]) -> Document {
+    let base = posts.def.listing_path();
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {}", posts.def.title, SITE_NAME),
+        description: posts.def.description.to_string(),
+        canonical_url: absolute(&base),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: listing_json_ld(
+            posts,
+            &absolute(&pagination.path()),
+            entries,
+            &primary_entity().id(),
+        ),
+        alternates: Vec::new(),
+        pagination: Some(pagination.clone()),
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = PostsPage(PostsPageProps {
+        entries: entries.to_vec(),
+        pagination,
+        base,
+        title: posts.def.title,
+        description: posts.def.description,
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates a post page HTML.
+fn render_post_page(posts: &Posts, post: &PostEntry) -> Document {
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {}", post.meta.title, SITE_NAME),
+        description: post.summary.clone(),
+        canonical_url: absolute(&post.url_path()),
+        og_type: OgType::Article {
+            published_time: post.meta.date.clone(),
+            author: absolute("/"),
+        },
+        og_image: None,
+        json_ld: post.to_json_ld(&primary_entity().id()),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch([posts.def.listing_path(), "/".to_string()]),
+    });
+
+    let body = PostPage(PostPageProps {
+        post: post.clone(),
+        listing: posts.def.listing_path(),
+        listing_title: posts.def.title,
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the index page of `taxonomy`.
+fn render_taxonomy_page(taxonomy: &Taxonomy, terms: &[Term]) -> Document {
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {}", taxonomy.title, SITE_NAME),
+        description: taxonomy.description.to_string(),
+        canonical_url: absolute(&taxonomy.index_path()),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: index_json_ld(taxonomy, terms),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = TaxonomyPage(TaxonomyPageProps {
+        taxonomy: *taxonomy,
+        terms: terms.to_vec(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the page of `term` in `taxonomy`, advertising its feed.
+fn render_term_page(taxonomy: &Taxonomy, term: &Term) -> Document {
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {} | {}", term.name, taxonomy.title, SITE_NAME),
+        description: format!("{}: {}", taxonomy.title, term.name),
+        canonical_url: absolute(&taxonomy.term_path(&term.slug)),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: term_json_ld(taxonomy, term),
+        alternates: vec![taxonomy.feed(term).alternate()],
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch([taxonomy.index_path(), "/".to_string()]),
+    });
+
+    let body = TermPage(TermPageProps {
+        taxonomy: *taxonomy,
+        term: term.clone(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the media log RSS feed.
+fn generate_log_feed(log: &MediaLog) -> String {
+    let channel = feed(LOG_FEED).channel();
+    rss(&channel, &log.feed_items(&channel.link))
+}
+
+/// Generates the guestbook page HTML.
+fn render_guestbook_page(mentions: &[Mention], endpoint: &'static str) -> Document {
+    let url = absolute(&page_path(&[GUESTBOOK_DIR]));
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("Guestbook | {}", SITE_NAME),
+        description: GUESTBOOK_DESCRIPTION.to_string(),
+        canonical_url: url.clone(),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld: guestbook_json_ld(&url, mentions),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = view! {
+        <GuestbookPage
+            mentions=mentions.to_vec()
+            url=url
+            endpoint=endpoint
+            moderated=GUESTBOOK_REQUIRE_APPROVAL
+        />
+    }
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the changelog page HTML.
+fn render_changelog_page(changes: &[Change]) -> Document {
+    let url = absolute(&page_path(&[CHANGELOG_DIR]));
+    let json_ld = format!(
+        r#"{{
+  "@context": "https://schema.org",
+  "@type": "WebPage",
+  "name": "Changelog",
+  "url": {url},
+  "description": {description}
+}}"#,
+        url = json_string(&url),
+        description = json_string(CHANGELOG_DESCRIPTION),
+    );
+
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("Changelog | {}", SITE_NAME),
+        description: CHANGELOG_DESCRIPTION.to_string(),
+        canonical_url: url,
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = ChangelogPage(ChangelogPageProps {
+        changes: changes.to_vec(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the changelog feed, `/changes.xml`.
+fn generate_changes_feed(changes: &[Change]) -> String {
+    let channel = feed(CHANGES_FEED).channel();
+    rss(&channel, &feed_items(changes, &channel.link))
+}
+
+/// Generates the GitHub activity page HTML.
+fn render_activity_page(days: &[ContributionDay]) -> Document {
+    let url = absolute(&page_path(&[ACTIVITY_DIR]));
+    let total: u32 = days.iter().map(|d| d.count).sum();
+    let description = format!("{} contributions on GitHub in the last year.", total);
+
+    let json_ld = format!(
+        r#"{{
+  "@context": "https://schema.org",
+  "@type": "WebPage",
+  "name": "Activity",
+  "url": {url},
+  "description": {description},
+  "about": {{ "@id": {id} }}
+}}"#,
+        url = json_string(&url),
+        description = json_string(&description),
+        id = json_string(&primary_entity().id()),
+    );
+
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("Activity | {}", SITE_NAME),
+        description,
+        canonical_url: url,
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = ActivityPage(ActivityPageProps {
+        days: days.to_vec(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates the landing page HTML of a related site.
+fn render_site_page(site: &SiteConfig, cards: &[Card]) -> Document {
+    let json_ld = website_json_ld(
+        &site.key,
+        &site.name,
+        &site.url,
+        &site.description,
+        &primary_entity().id(),
+    );
+
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {}", site.name, SITE_NAME),
+        description: site.description.clone(),
+        canonical_url: format!("{}/", site.url),
+        og_type: OgType::Website,
+        og_image: None,
+        json_ld,
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::default(),
+    });
+
+    let body = view! { <SitePage site=site.clone() cards=cards.to_vec() /> }.into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates a card page HTML of a related site.
+fn render_card_page(site: &SiteConfig, card: &Card, currency: &str) -> Document {
+    let head_html = generate_head_html_for(&PageMeta {
+        title: format!("{} | {}", card.name, site.name),
+        description: card.description.clone(),
+        canonical_url: format!("{}{}", site.url, card.url_path()),
+        og_type: OgType::Website,
+        og_image: Some(MetaImage::new(format!("{}{}", site.url, card.image_path()))),
+        json_ld: card.to_json_ld(&site.url, &site.name, currency),
+        alternates: Vec::new(),
+        pagination: None,
+        noindex: false,
+        hints: Vec::new(),
+        speculation: SpeculationRules::prefetch(["/"]),
+    });
+
+    let body = CardPage(CardPageProps {
+        card: card.clone(),
+        currency: currency.to_string(),
+        site: site.clone(),
+    })
+    .into_any();
+
+    Document::new(head_html, body)
+}
+
+/// Generates a related site into its own output tree under `sites_dir`.
+fn generate_related_site(site: &SiteConfig, sites_dir: &Path) -> std::io::Result<()> {
+    let output_dir = site.output_dir(sites_dir);
+    clean::mark(&output_dir)?;
+
+    fs::write(output_dir.join("main.css"), render_stylesheet()?)?;
+    if let Some(public_dir) = &site.public_dir {
+        copy_dir_contents(Path::new(public_dir), &output_dir, &ASSET_COPY)?;
+    }
+
+    let manifest = match &site.cards {
+        Some(path) => CardManifest::load(Path::new(path)).map_err(std::io::Error::other)?,
+        None => None,
+    };
+    let (currency, cards) = manifest.map(|m| (m.currency, m.cards)).unwrap_or_default();
+    for card in &cards {
+        write_page(
+            &output_dir,
+            &[CARDS_DIR, &card.slug],
+            render_card_page(site, card, &currency),
+        )?;
+    }
+
+    write_page(&output_dir, HOME, render_site_page(site, &cards))?;
+    let card_urls: String = cards
+        .iter()
+        .map(|card| {
+            format!(
+                r#"
+  <url>
+    <loc>{}{}</loc>
+    <changefreq>monthly</changefreq>
+    <priority>0.8</priority>
+  </url>"#,
+                escape_html(&site.url),
+                escape_html(&card.url_path())
+            )
+        })
+        .collect();
+    fs::write(
+        output_dir.join("sitemap.xml"),
         format!(
-            r#"  <url>
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
     <loc>{}/</loc>
     <changefreq>monthly</changefreq>
     <priority>1.0</priority>
-  </url>"#,
-            SITE_URL
+  </url>{}
+</urlset>
+"#,
+            escape_html(&site.url),
+            card_urls
         ),
+    )?;
+    fs::write(
+        output_dir.join("robots.txt"),
         format!(
-            r#"  <url>
-    <loc>{}/llms.txt</loc>
-    <changefreq>monthly</changefreq>
-    <priority>0.5</priority>
-  </url>"#,
-            SITE_URL
+            "User-agent: *\nAllow: /\n\nSitemap: {}/sitemap.xml\n",
+            site.url
         ),
-    ];
+    )?;
 
-    urls.push(format!(
-        r#"  <url>
-    <loc>{}/sigil/</loc>
-    <changefreq>yearly</changefreq>
-    <priority>0.5</priority>
-  </url>"#,
-        SITE_URL
-    ));
+    println!("Generated site {}: {}", site.url, output_dir.display());
+    Ok(())
+}
 
-    if !series.is_empty() {
-        urls.push(format!(
+/// Generates sitemap.xml content including art pages and the optional pages
+/// in `pages` (site-relative paths such as `/press/`).
+fn generate_sitemap(series: &[ArtSeries], pages: &[String]) -> String {
+    let entry = |path: &str, changefreq: &str, priority: &str| {
+        format!(
             r#"  <url>
-    <loc>{}/art/</loc>
-    <changefreq>weekly</changefreq>
-    <priority>0.8</priority>
+    <loc>{}</loc>
+    <changefreq>{}</changefreq>
+    <priority>{}</priority>
   </url>"#,
-            SITE_URL
-        ));
-
+            escape_html(&absolute(path)),
+            changefreq,
+            priority
+        )
+    };
+    let mut urls = vec![
+        entry("/", "monthly", "1.0"),
+        entry("/llms.txt", "monthly", "0.5"),
+        entry(&page_path(&["sigil"]), "yearly", "0.5"),
+    ];
+    for page in pages {
+        urls.push(entry(page, "yearly", "0.5"));
+    }
+    if !series.is_empty() {
+        urls.push(entry(&page_path(&["art"]), "weekly", "0.8"));
         for s in series {
-            urls.push(format!(
-                r#"  <url>
-    <loc>{}/art/{}/</loc>
-    <changefreq>monthly</changefreq>
-    <priority>0.7</priority>
-  </url>"#,
-                SITE_URL, s.slug
-            ));
+            urls.push(entry(&s.url_path(), "monthly", "0.7"));
         }
     }
 
@@ -236,8 +922,10 @@ EverythingSings is a digital artist and creative technologist. This site serves
         sections.push_str("\n## Art Series\n\n");
         for s in series {
             sections.push_str(&format!(
-                "- [{}]({}/art/{}/): {}\n",
-                s.title, SITE_URL, s.slug, s.description
+                "- [{}]({}): {}\n",
+                s.title,
+                absolute(&s.url_path()),
+                s.description
             ));
         }
     }
@@ -254,6 +942,8 @@ This site is designed to be AI-crawler accessible:
 - Schema.org microdata attributes
 - RSS feed available at /feed.xml
 - Sitemap at /sitemap.xml
+- Plain-text profile at /plan.txt
+- Embedding-ready text chunks at /embeddings.jsonl (one JSON record per line)
 
 ## For AI Assistants
 
@@ -272,102 +962,1439 @@ For inquiries, please use the contact form or social media links on the main sit
     sections
 }
 
-/// Copies all files from source directory to destination.
-fn copy_dir_contents(src: &Path, dst: &Path) -> std::io::Result<()> {
-    if !dst.exists() {
-        fs::create_dir_all(dst)?;
+/// Builds `main.css`: generated design tokens followed by `style/main.css`
+/// with its physical properties made logical, so it also lays out
+/// right-to-left pages.
+fn render_stylesheet() -> std::io::Result<String> {
+    let mut css = generate_tokens_css();
+    let style_src = Path::new("style/main.css");
+    if style_src.exists() {
+        css.push('\n');
+        css.push_str(&logical_properties(&fs::read_to_string(style_src)?));
     }
+    Ok(css)
+}
+
+/// Writes the page at `segments` under `output_dir` in the configured
+/// layout (see [`page_file`]). Returns the path written.
+fn write_page<S: AsRef<str>>(
+    output_dir: &Path,
+    segments: &[S],
+    page: Document,
+) -> std::io::Result<PathBuf> {
+    let path = output_dir.join(page_file(segments));
+    page.write(&path)?;
+    Ok(path)
+}
+
+/// Source files copied into the site as they are.
+const PUBLIC_DIR: &str = "public";
 
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
+/// Where a build writes: the site, the related sites, and the build report.
+struct Outputs {
+    site: PathBuf,
+    sites: PathBuf,
+    report: PathBuf,
+}
 
-        if src_path.is_dir() {
-            copy_dir_contents(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path)?;
+impl Outputs {
+    /// The usual locations, under `target/`.
+    fn target() -> Self {
+        Outputs {
+            site: PathBuf::from("target/site"),
+            sites: PathBuf::from(SITES_OUTPUT_DIR),
+            report: PathBuf::from(REPORT_PATH),
+        }
+    }
+
+    /// Everything under `dir`.
+    fn under(dir: &Path) -> Self {
+        Outputs {
+            site: dir.join("site"),
+            sites: dir.join("sites"),
+            report: dir.join("build-report.json"),
         }
     }
-    Ok(())
 }
 
-/// Generates the static site to `target/site/`.
-fn generate_static_site() -> std::io::Result<()> {
-    let output_dir = Path::new("target/site");
-    let public_dir = Path::new("public");
+/// Generates the static site to `target/site/`, or the directory given by
+/// `--out-dir` in `args`, or only the parts named by `--only` options (see
+/// [`everythingsings::build`]). With `--dry-run`, see [`dry_run`].
+fn generate_static_site(args: &[String]) -> std::io::Result<()> {
+    let mut outputs = Outputs::target();
+    let mut only = Vec::new();
+    let mut dry = false;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--dry-run" => dry = true,
+            "--out-dir" => outputs.site = out_dir(rest.next())?,
+            "--only" => only.push(
+                rest.next()
+                    .ok_or_else(|| std::io::Error::other("--only needs a part"))?,
+            ),
+            other => {
+                return Err(std::io::Error::other(format!(
+                    "Unknown --generate-static option: {}",
+                    other
+                )))
+            }
+        }
+    }
+    let scope = match only.is_empty() {
+        true => Scope::full(),
+        false => Scope::only(&only).map_err(std::io::Error::other)?,
+    };
+    match dry {
+        true => dry_run(&outputs, &scope),
+        false => generate_site(&outputs, &scope, offline()),
+    }
+}
 
-    // Create output directory
-    fs::create_dir_all(output_dir)?;
+/// The value of an `--out-dir` option.
+fn out_dir(value: Option<&String>) -> std::io::Result<PathBuf> {
+    value
+        .map(PathBuf::from)
+        .ok_or_else(|| std::io::Error::other("--out-dir needs a directory"))
+}
 
-    // Render and write index.html
-    let html = render_to_html();
-    let index_path = output_dir.join("index.html");
-    fs::write(&index_path, &html)?;
-    println!("Generated: {}", index_path.display());
+/// Runs the build with every check but leaves `target` untouched: the
+/// output is staged in a temporary directory, listed with the size of each
+/// file as it would be written, compared with the last build report, and
+/// removed. It runs offline, reading data sources from `data/cache/`
+/// without refreshing them.
+fn dry_run(target: &Outputs, scope: &Scope) -> std::io::Result<()> {
+    let staging = env::temp_dir().join(format!("esart-dry-run-{}", std::process::id()));
+    let staged = Outputs::under(&staging);
+    let result = generate_site(&staged, scope, true).and_then(|()| {
+        let mut total = BuildReport::default();
+        for (dir, real) in [(&staged.site, &target.site), (&staged.sites, &target.sites)] {
+            if !dir.exists() {
+                continue;
+            }
+            let report = BuildReport::from_dir(dir)?;
+            println!("\n{}:", real.display());
+            for file in &report.files {
+                println!("{:>10}  {}", file.bytes, file.path);
+            }
+            if dir == &staged.site {
+                total.files.extend(report.files);
+            }
+        }
+        println!(
+            "\nWould write {} files, {} bytes, to {}",
+            total.files.len(),
+            total.total_bytes(),
+            target.site.display()
+        );
+        if scope.is_full() && target.report.exists() {
+            let last = BuildReport::read(&target.report)?;
+            print!(
+                "\nSince the last build:\n{}",
+                ReportDiff::between(&last, &total)
+            );
+        }
+        Ok(())
+    });
+    // The build may have failed before creating the staging directory
+    let cleanup = clean::remove(&staging);
+    result?;
+    cleanup.map(|_| ())
+}
 
-    // Copy public assets if directory exists
-    if public_dir.exists() {
-        copy_dir_contents(public_dir, output_dir)?;
-        println!("Copied public assets to {}", output_dir.display());
+/// Generates `scope` of the static site to `outputs`, one task of the
+/// build graph at a time (see [`everythingsings::build`]). Content, config
+/// files, and `public/` are read from the working directory. An `offline`
+/// build only reads data sources from their caches (see
+/// [`everythingsings::cache`]).
+fn generate_site(outputs: &Outputs, scope: &Scope, offline: bool) -> std::io::Result<()> {
+    with_offline(offline, || run_tasks(outputs, scope))
+}
+
+/// [`generate_site`] in the current offline mode.
+fn run_tasks(outputs: &Outputs, scope: &Scope) -> std::io::Result<()> {
+    let output_dir = outputs.site.as_path();
+    // Check theme contrast before writing anything
+    let contrast_issues = check_theme_contrast();
+    for issue in &contrast_issues {
+        eprintln!("Contrast: {}", issue);
+    }
+    if !contrast_issues.is_empty() && CONTRAST_POLICY == ContrastPolicy::Fail {
+        return Err(std::io::Error::other(format!(
+            "{} theme color pair(s) below WCAG AA",
+            contrast_issues.len()
+        )));
     }
 
-    // Copy CSS if it exists
-    let style_src = Path::new("style/main.css");
-    if style_src.exists() {
-        let style_dst = output_dir.join("main.css");
-        fs::copy(style_src, &style_dst)?;
-        println!("Copied: {}", style_dst.display());
+    // Browsers refuse an analytics script whose hash has changed
+    if let Some(actual) = integrity_mismatch(&ANALYTICS) {
+        eprintln!(
+            "Warning: Analytics script integrity is out of date; set integrity to \"{}\"",
+            actual
+        );
     }
 
-    // Generate sigil page
-    let sigil_dir = output_dir.join("sigil");
-    fs::create_dir_all(&sigil_dir)?;
-    let sigil_path = sigil_dir.join("index.html");
-    fs::write(&sigil_path, render_sigil())?;
-    println!("Generated: {}", sigil_path.display());
+    // Create the output directory, marked so `clean` may remove it
+    clean::mark(output_dir)?;
 
-    // Discover and generate art pages
-    let series = discover_series(public_dir);
-    if !series.is_empty() {
-        // Generate art index page
-        let art_dir = output_dir.join("art");
-        fs::create_dir_all(&art_dir)?;
-        let art_index_path = art_dir.join("index.html");
-        fs::write(&art_index_path, render_art_index(&series))?;
-        println!("Generated: {}", art_index_path.display());
+    let mut build = Build {
+        output_dir,
+        sites_dir: &outputs.sites,
+        report_path: &outputs.report,
+        content: Content::default(),
+        freshness: Vec::new(),
+        optional_pages: Vec::new(),
+    };
+    // Fail rather than publish a site where one page replaced another
+    build.content.check_page_paths()?;
 
-        // Generate individual series pages
-        for s in &series {
-            let series_dir = art_dir.join(&s.slug);
-            fs::create_dir_all(&series_dir)?;
-            let series_path = series_dir.join("index.html");
-            fs::write(&series_path, render_art_series(s))?;
-            println!("Generated: {}", series_path.display());
+    let plan = scope.plan(|task| task.state(output_dir));
+    for task in scope
+        .tasks()
+        .into_iter()
+        .filter(|task| !plan.contains(task))
+    {
+        println!("Up to date: {}", task.name());
+    }
+    for task in plan {
+        match task {
+            Task::Assets => build_assets(&mut build)?,
+            Task::Pages => build_pages(&mut build)?,
+            Task::Posts => build_posts(&mut build)?,
+            Task::Feeds => build_feeds(&mut build)?,
+            Task::Sitemap => build_sitemap(&mut build)?,
+            Task::Discovery => build_discovery(&mut build)?,
+            Task::Manifest => build_manifest(&build)?,
         }
+    }
 
-        println!("Generated {} art series pages", series.len());
+    match scope.is_full() {
+        true => println!("\nStatic site generated at: {}", output_dir.display()),
+        false => println!("\nPartial build written to: {}", output_dir.display()),
     }
+    Ok(())
+}
 
-    // Generate dynamic sitemap.xml and llms.txt (overwrite static versions)
-    let sitemap_path = output_dir.join("sitemap.xml");
-    fs::write(&sitemap_path, generate_sitemap(&series))?;
+/// What the tasks of one build share.
+struct Build<'a> {
+    output_dir: &'a Path,
+    sites_dir: &'a Path,
+    report_path: &'a Path,
+    content: Content,
+    /// When each content source last changed, for status.json
+    freshness: Vec<(&'static str, String)>,
+    /// Optional pages, added to the sitemap when generated
+    optional_pages: Vec<String>,
+}
+
+/// Content read by more than one task, loaded when first asked for.
+#[derive(Default)]
+struct Content {
+    media_log: Option<Option<MediaLog>>,
+    posts: Option<Option<Posts>>,
+    changes: Option<Vec<Change>>,
+    series: Option<Vec<ArtSeries>>,
+}
+
+impl Content {
+    /// The media log, when media.toml exists.
+    fn media_log(&mut self) -> std::io::Result<Option<&MediaLog>> {
+        if self.media_log.is_none() {
+            let log = MediaLog::load(Path::new(MEDIA_PATH)).map_err(std::io::Error::other)?;
+            self.media_log = Some(log);
+        }
+        Ok(self.media_log.as_ref().and_then(Option::as_ref))
+    }
+
+    /// The posts with their `@/` links resolved, when content/posts/ exists.
+    fn posts(&mut self) -> std::io::Result<Option<&Posts>> {
+        if self.posts.is_none() {
+            let mut posts = Posts::load(POSTS, Path::new(".")).map_err(std::io::Error::other)?;
+
+            // Resolve @/ links between content once every collection is loaded
+            let mut link_targets = LinkTargets::default();
+            if let Some(posts) = &posts {
+                link_targets.add(posts);
+            }
+            if let Some(posts) = &mut posts {
+                link_targets
+                    .resolve_collection(posts)
+                    .map_err(std::io::Error::other)?;
+            }
+            self.posts = Some(posts);
+        }
+        Ok(self.posts.as_ref().and_then(Option::as_ref))
+    }
+
+    /// The latest changes from git history, none when it can't be read.
+    fn changes(&mut self) -> &[Change] {
+        self.changes.get_or_insert_with(|| {
+            load_changes(Path::new("."), CHANGELOG_LIMIT).unwrap_or_else(|e| {
+                eprintln!("Warning: Skipping changelog: {}", e);
+                Vec::new()
+            })
+        })
+    }
+
+    /// Every art series, unlisted ones too. Errors when some are unlisted
+    /// but their paths would not be secret (see [`unlisted::key`]).
+    fn series(&mut self) -> std::io::Result<&[ArtSeries]> {
+        if self.series.is_none() {
+            let series = discover_series(Path::new(PUBLIC_DIR));
+            if series.iter().any(|s| s.unlisted) {
+                unlisted::key().map_err(std::io::Error::other)?;
+            }
+            self.series = Some(series);
+        }
+        Ok(self.series.as_deref().unwrap_or_default())
+    }
+
+    /// Checks no two pages of any task resolve to one path. Fixed pages are
+    /// reserved whether or not this build generates them.
+    fn check_page_paths(&mut self) -> std::io::Result<()> {
+        let mut pages: Vec<(String, String)> = [
+            HOME,
+            &["sigil"],
+            &["art"],
+            &[PRESS_DIR],
+            &["cv"],
+            &[LOG_DIR],
+            &[PRINTS_DIR],
+            &[MUSIC_DIR],
+            &[GUESTBOOK_DIR],
+            &[CHANGELOG_DIR],
+            &[ACTIVITY_DIR],
+        ]
+        .iter()
+        .map(|segments| (page_path(segments), page_path(segments)))
+        .collect();
+        if let Some(protected) =
+            ProtectedPages::load(Path::new(PROTECTED_PATH)).map_err(std::io::Error::other)?
+        {
+            pages.extend(
+                protected
+                    .pages
+                    .iter()
+                    .map(|p| (page_path(&[PROTECTED_DIR, &p.slug]), p.title.clone())),
+            );
+        }
+        if let Some(releases) =
+            Releases::load(Path::new(RELEASES_PATH)).map_err(std::io::Error::other)?
+        {
+            pages.extend(
+                releases
+                    .releases
+                    .iter()
+                    .map(|r| (r.url_path(), r.title.clone())),
+            );
+        }
+        pages.extend(
+            self.series()?
+                .iter()
+                .map(|s| (s.url_path(), s.title.clone())),
+        );
+        let posts = self.posts()?;
+        if let Some(posts) = posts {
+            pages.extend(
+                posts
+                    .entries
+                    .iter()
+                    .map(|p| (p.url_path(), p.meta.title.clone())),
+            );
+            for (pagination, _) in posts.pages() {
+                pages.push((pagination.path(), pagination.path()));
+            }
+        }
+        for taxonomy in TAXONOMIES {
+            let terms = terms(taxonomy, posts.iter().flat_map(|p| p.tagged()));
+            if terms.is_empty() {
+                continue;
+            }
+            pages.push((taxonomy.index_path(), taxonomy.index_path()));
+            pages.extend(terms.iter().map(|term| {
+                let path = taxonomy.term_path(&term.slug);
+                (path.clone(), path)
+            }));
+        }
+        check_unique(
+            pages
+                .iter()
+                .map(|(path, owner)| (path.clone(), owner.as_str())),
+        )
+        .map_err(std::io::Error::other)
+    }
+}
+
+/// The `assets` task: public files, avatar variants, and the stylesheet.
+fn build_assets(build: &mut Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+    let public_dir = Path::new(PUBLIC_DIR);
+
+    // Copy public assets if directory exists, unlisted series only beside
+    // their hashed pages
+    if public_dir.exists() {
+        let unlisted: Vec<&ArtSeries> = build
+            .content
+            .series()?
+            .iter()
+            .filter(|s| s.unlisted)
+            .collect();
+        let except: Vec<&Path> = unlisted.iter().map(|s| s.source.as_path()).collect();
+        copy_dir_contents_except(public_dir, output_dir, &ASSET_COPY, &except)?;
+        for s in unlisted {
+            let dir = output_dir.join(s.segments().iter().collect::<PathBuf>());
+            copy_dir_contents(&s.source, &dir, &ASSET_COPY)?;
+        }
+        println!("Copied public assets to {}", output_dir.display());
+    }
+
+    // Generate responsive avatar variants for the profile cards' srcset
+    for image in ENTITIES.iter().map(|e| e.image).filter(|i| is_raster(i)) {
+        let relative = image.trim_start_matches('/');
+        let source = public_dir.join(relative);
+        if source.is_file() {
+            let dir = output_dir.join(Path::new(relative).parent().unwrap_or(Path::new("")));
+            let count = write_variants(&source, &dir, AVATAR_WIDTHS)?;
+            println!("Generated: {} variants of {}", count, image);
+        }
+    }
+
+    // Write CSS with generated design tokens prepended
+    let style_dst = output_dir.join("main.css");
+    fs::write(&style_dst, render_stylesheet()?)?;
+    println!("Generated: {}", style_dst.display());
+
+    let tokens_path = output_dir.join("tokens.json");
+    fs::write(&tokens_path, generate_tokens_json())?;
+    println!("Generated: {}", tokens_path.display());
+    Ok(())
+}
+
+/// The `pages` task: the homepage and every page outside a collection.
+fn build_pages(build: &mut Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+    let cache_modified = |name: &str| {
+        CacheEntry::new(Path::new(CACHE_DIR), name)
+            .modified()
+            .map(rfc3339)
+    };
+
+    // What the homepage shows beside its links
+    let listens = load_recent_listens();
+    if let Some(source) = LISTENS_SOURCE.filter(|_| !listens.is_empty()) {
+        build
+            .freshness
+            .extend(cache_modified(&source.cache_name()).map(|t| ("listens", t)));
+    }
+    let verified = VerifiedLinks::load(Path::new(VERIFIED_PATH))
+        .map_err(std::io::Error::other)?
+        .unwrap_or_default();
+    build
+        .freshness
+        .extend(verified.latest().map(|t| ("links_verified", t.to_string())));
+    let archived = if ARCHIVE_FALLBACK_LINKS {
+        ArchivedLinks::load(Path::new(ARCHIVE_PATH))
+            .map_err(std::io::Error::other)?
+            .unwrap_or_default()
+    } else {
+        ArchivedLinks::default()
+    };
+    let icons = if LINK_FAVICONS {
+        harvest(&rendered_links(), output_dir)?
+    } else {
+        Favicons::default()
+    };
+    // Generate JSON API documents
+    let api_dir = output_dir.join(API_DIR);
+    fs::create_dir_all(&api_dir)?;
+    let profile_path = api_dir.join("profile.json");
+    fs::write(&profile_path, generate_profile_json())?;
+    println!("Generated: {}", profile_path.display());
+    let links_path = api_dir.join("links.json");
+    fs::write(&links_path, generate_links_json(&verified))?;
+    println!("Generated: {}", links_path.display());
+
+    let oembed_path = output_dir.join(OEMBED_FILE);
+    fs::write(&oembed_path, site_oembed().to_json())?;
+    println!("Generated: {}", oembed_path.display());
+
+    // Generate sigil page
+    let sigil_path = write_page(output_dir, &["sigil"], render_sigil())?;
+    println!("Generated: {}", sigil_path.display());
+
+    // Generate protected pages when protected.toml exists
+    if let Some(pages) =
+        ProtectedPages::load(Path::new(PROTECTED_PATH)).map_err(std::io::Error::other)?
+    {
+        for page in &pages.pages {
+            let Some(passphrase) = page.passphrase() else {
+                eprintln!(
+                    "Warning: Skipping protected page {}: {} is not set",
+                    page.slug, page.passphrase_env
+                );
+                continue;
+            };
+            let content = fs::read_to_string(&page.source)?;
+            let sealed = Sealed::seal(&content, &passphrase, protected::ITERATIONS)
+                .map_err(std::io::Error::other)?;
+            let path = write_page(
+                output_dir,
+                &[PROTECTED_DIR, &page.slug],
+                render_protected_page(page, sealed),
+            )?;
+            println!("Generated: {} (protected)", path.display());
+        }
+    }
+
+    // Generate press kit page and assets when press.toml exists
+    if let Some(kit) = PressKit::load(Path::new(PRESS_PATH)).map_err(std::io::Error::other)? {
+        let press_dir = output_dir.join(PRESS_DIR);
+        let files = kit.export_assets(Path::new("."), &press_dir)?;
+        fs::write(press_dir.join("SHA256SUMS"), checksums_file(&files))?;
+        let press_path = write_page(output_dir, &[PRESS_DIR], render_press_page(&kit, &files))?;
+        println!(
+            "Generated: {} ({} assets)",
+            press_path.display(),
+            files.len()
+        );
+        build.optional_pages.push(page_path(&[PRESS_DIR]));
+    }
+
+    // Generate CV page and JSON Resume when resume.toml exists
+    if let Some(resume) = Resume::load(Path::new(RESUME_PATH)).map_err(std::io::Error::other)? {
+        let cv_path = write_page(output_dir, &["cv"], render_cv_page(&resume))?;
+        println!("Generated: {}", cv_path.display());
+
+        let resume_path = output_dir.join("resume.json");
+        fs::write(&resume_path, resume.to_json_resume())?;
+        println!("Generated: {}", resume_path.display());
+        build.optional_pages.push(page_path(&["cv"]));
+    }
+
+    // Generate media log page when media.toml exists
+    if let Some(log) = build.content.media_log()? {
+        let log_path = write_page(output_dir, &[LOG_DIR], render_log_page(log))?;
+        println!(
+            "Generated: {} ({} entries)",
+            log_path.display(),
+            log.entries.len()
+        );
+        if let Some(latest) = log.entries.first() {
+            build.freshness.push(("log", latest.date.clone()));
+        }
+        build.optional_pages.push(page_path(&[LOG_DIR]));
+    }
+
+    // Generate the prints page when the shop feed had any prints
+    let shop = rendered_links()
+        .into_iter()
+        .find(|link| link.kind == LinkKind::Store)
+        .map(|link| link.href);
+    let latest_prints = match (REDBUBBLE_FEED, shop) {
+        (Some(feed), Some(_)) => prints::load(feed, output_dir)?,
+        _ => Vec::new(),
+    };
+    if let Some(shop) = shop.filter(|_| !latest_prints.is_empty()) {
+        let prints_path = write_page(
+            output_dir,
+            &[PRINTS_DIR],
+            render_prints_page(&latest_prints, shop),
+        )?;
+        println!(
+            "Generated: {} ({} prints)",
+            prints_path.display(),
+            latest_prints.len()
+        );
+        build
+            .freshness
+            .extend(cache_modified(prints::CACHE_NAME).map(|t| ("prints", t)));
+        build.optional_pages.push(page_path(&[PRINTS_DIR]));
+    }
+
+    // Generate music page when releases.toml exists, with each release's
+    // platform links resolved through Odesli
+    if let Some(mut releases) =
+        Releases::load(Path::new(RELEASES_PATH)).map_err(std::io::Error::other)?
+    {
+        for release in &mut releases.releases {
+            release.links = load_links(&release.url);
+        }
+        let music_path = write_page(output_dir, &[MUSIC_DIR], render_music_page(&releases))?;
+        println!(
+            "Generated: {} ({} releases)",
+            music_path.display(),
+            releases.releases.len()
+        );
+        build.optional_pages.push(page_path(&[MUSIC_DIR]));
+
+        for release in &releases.releases {
+            let release_dir: PathBuf =
+                output_dir.join(release.segments().iter().collect::<PathBuf>());
+            fs::create_dir_all(&release_dir)?;
+            // Derive the OG image from the cover in public/
+            let og = match &release.cover {
+                Some(cover) => {
+                    let source = Path::new(PUBLIC_DIR)
+                        .join(MUSIC_DIR)
+                        .join(release.slug())
+                        .join(cover);
+                    match fs::read(source)
+                        .map_err(|e| e.to_string())
+                        .and_then(|data| og_image(&data))
+                    {
+                        Ok(jpeg) => {
+                            fs::write(release_dir.join(OG_FILE), jpeg)?;
+                            Some(format!("{}{}", release.dir_path(), OG_FILE))
+                        }
+                        Err(e) => {
+                            eprintln!("Warning: No OG image for {}: {}", release.title, e);
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
+            let release_path = write_page(
+                output_dir,
+                &release.segments(),
+                render_release_page(release, og.as_deref()),
+            )?;
+            fs::write(
+                release_dir.join(OEMBED_FILE),
+                release_oembed(release, og.as_deref()).to_json(),
+            )?;
+            println!("Generated: {}", release_path.display());
+            build.optional_pages.push(release.url_path());
+        }
+    }
+
+    // Generate guestbook page from moderated webmentions
+    if let Some(endpoint) = WEBMENTION_ENDPOINT {
+        let moderation = Moderation::load(Path::new(MODERATION_PATH))
+            .map_err(std::io::Error::other)?
+            .unwrap_or_default();
+        let received = load_mentions(&absolute(&page_path(&[GUESTBOOK_DIR])));
+        let (shown, held): (Vec<Mention>, Vec<Mention>) = received
+            .into_iter()
+            .filter(|m| !moderation.is_blocked(m))
+            .partition(|m| moderation.allows(m, GUESTBOOK_REQUIRE_APPROVAL));
+        let guestbook_path = write_page(
+            output_dir,
+            &[GUESTBOOK_DIR],
+            render_guestbook_page(&shown, endpoint),
+        )?;
+        println!(
+            "Generated: {} ({} signatures)",
+            guestbook_path.display(),
+            shown.len()
+        );
+        for mention in &held {
+            println!(
+                "Guestbook: awaiting approval in {}: {}",
+                MODERATION_PATH, mention.source
+            );
+        }
+        build.optional_pages.push(page_path(&[GUESTBOOK_DIR]));
+    }
+
+    // Generate changelog page from git history
+    let changes = build.content.changes();
+    if !changes.is_empty() {
+        let changelog_path =
+            write_page(output_dir, &[CHANGELOG_DIR], render_changelog_page(changes))?;
+        println!(
+            "Generated: {} ({} changes)",
+            changelog_path.display(),
+            changes.len()
+        );
+        build.freshness.push(("changelog", changes[0].date.clone()));
+        build.optional_pages.push(page_path(&[CHANGELOG_DIR]));
+    }
+
+    // Generate activity heatmap when the contribution calendar is available
+    let contributions = load_contributions();
+    if !contributions.is_empty() {
+        let activity_path = write_page(
+            output_dir,
+            &[ACTIVITY_DIR],
+            render_activity_page(&contributions),
+        )?;
+        println!("Generated: {}", activity_path.display());
+        build.optional_pages.push(page_path(&[ACTIVITY_DIR]));
+        build
+            .freshness
+            .extend(cache_modified(CACHE_NAME).map(|t| ("github_contributions", t)));
+    }
+
+    // Discover and generate art pages; unlisted series get a page only
+    let series = build.content.series()?;
+    let listed_series = art::listed(series);
+    let art_listed = !listed_series.is_empty();
+    if art_listed {
+        let art_index_path = write_page(output_dir, &["art"], render_art_index(&listed_series))?;
+        println!("Generated: {}", art_index_path.display());
+    }
+    if !series.is_empty() {
+        // Generate individual series pages
+        for s in series {
+            let series_dir = output_dir.join(s.segments().iter().collect::<PathBuf>());
+            fs::create_dir_all(&series_dir)?;
+            let series_path = write_page(
+                output_dir,
+                &s.segments(),
+                render_art_series(
+                    s,
+                    match &s.apple_music {
+                        Some(url) => apple_music::load(url, output_dir)?,
+                        None => None,
+                    },
+                    resolve_embeds(&s.embeds, output_dir)?,
+                    load_series_reactions(s),
+                    s.comments.as_ref().and_then(load_comments),
+                ),
+            )?;
+            fs::write(series_dir.join(OEMBED_FILE), series_oembed(s).to_json())?;
+            println!("Generated: {}", series_path.display());
+            if s.unlisted {
+                println!("Unlisted: {} at {}", s.title, absolute(&s.url_path()));
+            }
+        }
+
+        println!("Generated {} art series pages", series.len());
+
+        // Leave redirects where pages were before series had slugs, unless a
+        // series' page is there now, in any case: case-insensitive
+        // filesystems would write both to one file
+        let fold = |segments: &[String]| -> Vec<String> {
+            segments.iter().map(|s| s.to_lowercase()).collect()
+        };
+        let pages: HashSet<Vec<String>> = series.iter().map(|s| fold(&s.segments())).collect();
+        for s in series {
+            let Some(old) = s.old_segments().filter(|old| !pages.contains(&fold(old))) else {
+                continue;
+            };
+            let old_path = output_dir.join(page_file(&old));
+            fs::create_dir_all(old_path.parent().unwrap_or(output_dir))?;
+            fs::write(&old_path, redirect::page(&absolute(&s.url_path())))?;
+            println!("Redirected: {} to {}", old_path.display(), s.url_path());
+        }
+    }
+
+    // Render and write index.html last, once the pages it links and
+    // prefetches are known
+    let mut generated = build.optional_pages.clone();
+    if art_listed {
+        generated.push(page_path(&["art"]));
+    }
+    if let Some(posts) = build.content.posts()? {
+        generated.push(posts.def.listing_path());
+    }
+    let html = render_to_html(
+        SITE_LANGUAGE,
+        listens,
+        verified,
+        archived,
+        icons,
+        &generated,
+    );
+    let index_path = write_page(output_dir, HOME, html)?;
+    println!("Generated: {}", index_path.display());
+
+    // Generate related sites from sites/*.toml into their own output trees
+    for site in discover_sites(Path::new(SITES_DIR)) {
+        generate_related_site(&site, build.sites_dir)?;
+    }
+    Ok(())
+}
+
+/// The `posts` task: posts, their listing pages, and taxonomy pages.
+fn build_posts(build: &mut Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+
+    // Generate posts and their listing pages when content/posts/ exists
+    let posts = build.content.posts()?;
+    if let Some(posts) = posts {
+        for post in &posts.entries {
+            let post_path = write_page(output_dir, &post.segments, render_post_page(posts, post))?;
+            println!("Generated: {}", post_path.display());
+            build.optional_pages.push(post.url_path());
+            for (source, output) in post.asset_outputs() {
+                let target = output_dir.join(output);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&source, &target)?;
+                println!("Copied: {}", target.display());
+            }
+        }
+        let pages = posts.pages();
+        for (pagination, entries) in &pages {
+            let listing_path = write_page(
+                output_dir,
+                &pagination.segments(),
+                render_posts_page(posts, pagination.clone(), entries),
+            )?;
+            println!(
+                "Generated: {} ({} of {} posts)",
+                listing_path.display(),
+                entries.len(),
+                posts.entries.len()
+            );
+            build.optional_pages.push(pagination.path());
+        }
+        if let Some(latest) = posts.entries.first() {
+            build.freshness.push(("posts", latest.meta.date.clone()));
+        }
+    }
+
+    // Generate each taxonomy's index and term pages from every collection
+    for taxonomy in TAXONOMIES {
+        let terms = terms(taxonomy, posts.iter().flat_map(|p| p.tagged()));
+        if terms.is_empty() {
+            continue;
+        }
+        let index_path = write_page(
+            output_dir,
+            &[taxonomy.dir],
+            render_taxonomy_page(taxonomy, &terms),
+        )?;
+        println!(
+            "Generated: {} ({} terms)",
+            index_path.display(),
+            terms.len()
+        );
+        build.optional_pages.push(taxonomy.index_path());
+        for term in &terms {
+            let segments = taxonomy.term_segments(&term.slug);
+            write_page(output_dir, &segments, render_term_page(taxonomy, term))?;
+            build.optional_pages.push(taxonomy.term_path(&term.slug));
+        }
+    }
+    Ok(())
+}
+
+/// The `feeds` task: the RSS feeds of posts, the log, the changelog, and
+/// taxonomy terms.
+fn build_feeds(build: &mut Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+
+    if let Some(log) = build.content.media_log()? {
+        fs::create_dir_all(output_dir.join(LOG_DIR))?;
+        fs::write(
+            output_dir.join(feed(LOG_FEED).file()),
+            generate_log_feed(log),
+        )?;
+    }
+
+    let changes = build.content.changes();
+    if !changes.is_empty() {
+        fs::write(
+            output_dir.join(feed(CHANGES_FEED).file()),
+            generate_changes_feed(changes),
+        )?;
+    }
+
+    let posts = build.content.posts()?;
+    if let Some(posts) = posts {
+        let channel = feed(POSTS.key).channel();
+        fs::create_dir_all(output_dir.join(POSTS.dir))?;
+        fs::write(
+            output_dir.join(feed(POSTS.key).file()),
+            rss(&channel, &posts.feed_items()),
+        )?;
+    }
+    for taxonomy in TAXONOMIES {
+        for term in terms(taxonomy, posts.iter().flat_map(|p| p.tagged())) {
+            let term_feed = taxonomy.feed(&term);
+            let segments = taxonomy.term_segments(&term.slug);
+            fs::create_dir_all(output_dir.join(segments.iter().collect::<PathBuf>()))?;
+            fs::write(
+                output_dir.join(term_feed.file()),
+                rss(&term_feed.channel(), &term.feed_items()),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The `sitemap` task: sitemap.xml and llms.txt over every page.
+fn build_sitemap(build: &mut Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+    let series = build.content.series()?;
+    let listed_series = art::listed(series);
+
+    // Generate dynamic sitemap.xml and llms.txt (overwrite static versions)
+    let sitemap_path = output_dir.join("sitemap.xml");
+    fs::write(
+        &sitemap_path,
+        generate_sitemap(&listed_series, &build.optional_pages),
+    )?;
     println!("Generated: {}", sitemap_path.display());
 
     let llms_path = output_dir.join("llms.txt");
-    fs::write(&llms_path, generate_llms_txt(&series))?;
+    fs::write(&llms_path, generate_llms_txt(&listed_series))?;
     println!("Generated: {}", llms_path.display());
+    Ok(())
+}
 
-    println!("\nStatic site generated at: {}", output_dir.display());
+/// The `discovery` task: documents that describe the site to other
+/// software, and its Gemini mirror.
+fn build_discovery(build: &mut Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+    let today = Date::from_unix(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64),
+    );
+    let keybase_proof = KEYBASE_PROOF.map(fs::read_to_string).transpose()?;
+    let settings = well_known::Settings {
+        keybase_proof: keybase_proof.as_deref(),
+        ..well_known::Settings::from_config()
+    };
+    let documents = well_known::documents(&settings, today).map_err(std::io::Error::other)?;
+    for doc in &documents {
+        let path = output_dir.join(WELL_KNOWN_DIR).join(&doc.path);
+        fs::create_dir_all(path.parent().unwrap_or(output_dir))?;
+        fs::write(&path, &doc.body)?;
+        println!("Generated: {} ({})", doc.url_path(), doc.content_type());
+    }
+
+    let plan_path = output_dir.join(PLAN_PATH);
+    fs::write(&plan_path, generate_plan(primary_entity()))?;
+    println!("Generated: {}", plan_path.display());
+
+    let listed_series = art::listed(build.content.series()?);
+    let media_log = build.content.media_log()?;
+    let gemini_dir = output_dir.join(GEMINI_DIR);
+    fs::create_dir_all(&gemini_dir)?;
+    fs::write(
+        gemini_dir.join("index.gmi"),
+        gemini::profile(
+            primary_entity(),
+            media_log.is_some(),
+            !listed_series.is_empty(),
+        ),
+    )?;
+    fs::write(gemini_dir.join("links.gmi"), gemini::links())?;
+    if let Some(log) = media_log {
+        fs::write(gemini_dir.join("log.gmi"), gemini::log(log))?;
+    }
+    if !listed_series.is_empty() {
+        fs::write(gemini_dir.join("art.gmi"), gemini::art(&listed_series))?;
+    }
+    println!("Generated: {}", gemini_dir.display());
+
+    let embeddings_path = output_dir.join(EMBEDDINGS_PATH);
+    let embeddings = generate_embeddings(output_dir, SITE_URL)?;
+    fs::write(&embeddings_path, &embeddings)?;
+    println!(
+        "Generated: {} ({} chunks)",
+        embeddings_path.display(),
+        embeddings.lines().count()
+    );
     Ok(())
 }
 
+/// The `manifest` task: status.json and the build report, then the checks
+/// over the finished site.
+fn build_manifest(build: &Build) -> std::io::Result<()> {
+    let output_dir = build.output_dir;
+
+    // Generate status.json for uptime monitoring
+    let pages = BuildReport::from_dir(output_dir)?.page_count();
+    let status_path = output_dir.join(STATUS_PATH);
+    fs::write(
+        &status_path,
+        generate_status_json(SystemTime::now(), pages, &build.freshness),
+    )?;
+    println!("Generated: {} ({} pages)", status_path.display(), pages);
+
+    let report = BuildReport::from_dir(output_dir)?;
+    fs::write(build.report_path, report.to_json())?;
+    println!("Wrote build report: {}", build.report_path.display());
+
+    // Enforce page-weight budgets
+    let budgets = Budgets::load(Path::new(BUDGET_PATH)).map_err(std::io::Error::other)?;
+    let violations = budgets.check(&report);
+    if !violations.is_empty() {
+        eprintln!("\nPage-weight budget exceeded:");
+        for v in &violations {
+            eprintln!("  {}", v);
+        }
+        return Err(std::io::Error::other(format!(
+            "{} budget(s) exceeded",
+            violations.len()
+        )));
+    }
+
+    // Enforce a sound heading outline on every page
+    let outlines = audit_outline_site(output_dir)?;
+    if !outlines.is_empty() {
+        eprintln!("\nHeading outline problems:");
+        for (page, findings) in &outlines {
+            for f in findings {
+                eprintln!("  {} [{}] {}", page, f.rule, f.message);
+            }
+        }
+        return Err(std::io::Error::other(format!(
+            "{} page(s) with heading outline problems",
+            outlines.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Removes the build output in `target/`, or the site directory given by
+/// `--out-dir` in `args`, with the build report, the exports, and the data
+/// source caches. If any output directory lacks the build marker, nothing
+/// is removed (see [`everythingsings::clean`]).
+fn clean_outputs(args: &[String]) -> std::io::Result<()> {
+    let mut outputs = Outputs::target();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--out-dir" => outputs.site = out_dir(rest.next())?,
+            other => {
+                return Err(std::io::Error::other(format!(
+                    "Unknown clean option: {}",
+                    other
+                )))
+            }
+        }
+    }
+
+    // Check every output directory before removing any
+    let mut dirs = vec![outputs.site.clone()];
+    if outputs.sites.is_dir() {
+        for entry in fs::read_dir(&outputs.sites)? {
+            dirs.push(entry?.path());
+        }
+    }
+    for dir in &dirs {
+        clean::check_output(dir)?;
+    }
+
+    let files = [
+        outputs.report.as_path(),
+        Path::new(EPUB_PATH),
+        Path::new(PORTFOLIO_PATH),
+        Path::new(SINGLE_FILE_PATH),
+        Path::new(CACHE_DIR),
+    ];
+    for path in dirs.iter().map(PathBuf::as_path).chain(files) {
+        if clean::remove(path)? {
+            println!("Removed {}", path.display());
+        }
+    }
+    // Left empty once every related site is gone
+    if outputs.sites.is_dir() && fs::read_dir(&outputs.sites)?.next().is_none() {
+        fs::remove_dir(&outputs.sites)?;
+    }
+    Ok(())
+}
+
+/// Prints the differences between two build reports.
+fn diff_reports(old_path: &str, new_path: &str) -> std::io::Result<()> {
+    let old = BuildReport::read(Path::new(old_path))?;
+    let new = BuildReport::read(Path::new(new_path))?;
+    print!("{}", ReportDiff::between(&old, &new));
+    Ok(())
+}
+
+/// Audits the generated site and prints findings grouped by page.
+///
+/// Returns the number of findings.
+fn check_a11y() -> std::io::Result<usize> {
+    let output_dir = Path::new("target/site");
+    if !output_dir.exists() {
+        return Err(std::io::Error::other(
+            "target/site does not exist; run --generate-static first",
+        ));
+    }
+
+    let results = audit_site(output_dir)?;
+    let mut count = 0;
+    for (page, findings) in &results {
+        println!("{}", page);
+        for f in findings {
+            println!("  [{}] {}", f.rule, f.message);
+        }
+        count += findings.len();
+    }
+    println!(
+        "\n{} accessibility finding(s) across {} file(s)",
+        count,
+        results.len()
+    );
+    Ok(count)
+}
+
+/// Runs `--extract-text`: writes the semantic text of every page in
+/// target/site/ beside it.
+fn extract_text() -> std::io::Result<()> {
+    let output_dir = Path::new("target/site");
+    if !output_dir.exists() {
+        return Err(std::io::Error::other(
+            "target/site does not exist; run --generate-static first",
+        ));
+    }
+    let pages = extract_site(output_dir, SITE_URL)?;
+    println!("Extracted {} page(s) to {}", pages, CONTENT_FILE);
+    Ok(())
+}
+
+/// Runs `--single-file`: writes the generated homepage, with its
+/// stylesheet and images inlined, as one self-contained file.
+fn single_file_export() -> std::io::Result<()> {
+    let output_dir = Path::new("target/site");
+    let html = fs::read_to_string(output_dir.join(page_file(HOME))).map_err(|e| {
+        std::io::Error::other(format!(
+            "Could not read the homepage ({}); run --generate-static first",
+            e
+        ))
+    })?;
+    let html = single_file::inline(&html, output_dir)?;
+    fs::write(SINGLE_FILE_PATH, &html)?;
+    println!("Wrote {} ({} bytes)", SINGLE_FILE_PATH, html.len());
+    Ok(())
+}
+
+/// Runs the `export-epub` command: bundles the media log into an EPUB at
+/// [`EPUB_PATH`], with a cover drawn from the avatar.
+fn export_epub() -> std::io::Result<()> {
+    let log = MediaLog::load(Path::new(MEDIA_PATH))
+        .map_err(std::io::Error::other)?
+        .ok_or_else(|| std::io::Error::other(format!("{} does not exist", MEDIA_PATH)))?;
+    let avatar = fs::read(Path::new("public").join(AVATAR_PATH.trim_start_matches('/'))).ok();
+    let book = Book {
+        identifier: absolute(&format!("/{}/", LOG_DIR)),
+        title: format!("{} — Log", SITE_NAME),
+        creator: SITE_NAME.to_string(),
+        language: SITE_LANGUAGE.to_string(),
+        publisher: SITE_NAME.to_string(),
+        rights: SITE_RIGHTS.to_string(),
+        modified: rfc3339(SystemTime::now()),
+        cover: Some(epub::cover(avatar.as_deref(), &DARK).map_err(std::io::Error::other)?),
+        chapters: epub::log_chapters(&log),
+    };
+    let file = fs::File::create(EPUB_PATH)?;
+    book.write(file).map_err(std::io::Error::other)?;
+    println!("Wrote {} chapter(s) to {}", book.chapters.len(), EPUB_PATH);
+    Ok(())
+}
+
+/// Runs the `export-portfolio` command: renders the listed art series into
+/// a PDF at [`PORTFOLIO_PATH`].
+fn export_portfolio() -> std::io::Result<()> {
+    let public_dir = Path::new("public");
+    let series = art::listed(&discover_series(public_dir));
+    if series.is_empty() {
+        return Err(std::io::Error::other("No art series in public/art/"));
+    }
+    let pdf = portfolio::render(
+        &series,
+        public_dir,
+        (SITE_NAME, SITE_DESCRIPTION, SITE_URL),
+        &LIGHT,
+    )
+    .map_err(std::io::Error::other)?;
+    fs::write(PORTFOLIO_PATH, pdf)?;
+    println!("Wrote {} series to {}", series.len(), PORTFOLIO_PATH);
+    Ok(())
+}
+
+/// Runs the `mcp-serve` command: answers Model Context Protocol requests on
+/// stdio. Search uses the embedding chunks of the last build, if any.
+fn mcp_serve() -> std::io::Result<()> {
+    let verified = VerifiedLinks::load(Path::new(VERIFIED_PATH))
+        .map_err(std::io::Error::other)?
+        .unwrap_or_default();
+    let log = MediaLog::load(Path::new(MEDIA_PATH))
+        .map_err(std::io::Error::other)?
+        .unwrap_or_default();
+    let series = art::listed(&discover_series(Path::new("public")));
+    let resources = vec![
+        Resource::new(
+            "profile",
+            "Profile",
+            "Name, bio, avatar, and profile links",
+            generate_profile_json(),
+        ),
+        Resource::new(
+            "links",
+            "Links",
+            "Profile links by group, with when each was last verified",
+            generate_links_json(&verified),
+        ),
+        Resource::new(
+            "posts",
+            "Posts",
+            "Log of books, films, and albums, newest first",
+            posts_json(&log),
+        ),
+        Resource::new(
+            "gallery",
+            "Gallery",
+            "Art series and their images",
+            gallery_json(&series),
+        ),
+    ];
+
+    let embeddings = Path::new("target/site").join(EMBEDDINGS_PATH);
+    let chunks: Vec<Chunk> = match fs::read_to_string(&embeddings) {
+        Ok(jsonl) => jsonl
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map_err(std::io::Error::other)?,
+        Err(_) => {
+            eprintln!(
+                "Warning: {} not found; search returns nothing until --generate-static runs",
+                embeddings.display()
+            );
+            Vec::new()
+        }
+    };
+
+    let stdin = std::io::stdin();
+    McpServer::new(resources, chunks).run(stdin.lock(), std::io::stdout())
+}
+
+/// Runs the `check-links` command: requests every homepage link and records
+/// successful checks in [`VERIFIED_PATH`]. Returns the number of failures.
+fn check_links() -> std::io::Result<usize> {
+    let path = Path::new(VERIFIED_PATH);
+    let mut verified = VerifiedLinks::load(path)
+        .map_err(std::io::Error::other)?
+        .unwrap_or_default();
+    let mut failures = 0;
+    for link in rendered_links() {
+        match check_url(link.href) {
+            Ok(()) => {
+                println!("ok    {}", link.href);
+                verified.record(link.href, rfc3339(SystemTime::now()));
+            }
+            Err(e) => {
+                println!("FAIL  {}", e);
+                failures += 1;
+            }
+        }
+    }
+    verified.save(path)?;
+    println!(
+        "\n{} link(s) checked, {} failed; wrote {}",
+        rendered_links().len(),
+        failures,
+        VERIFIED_PATH
+    );
+    Ok(failures)
+}
+
+/// Runs the `ping-hub` command: tells the configured WebSub hub that every
+/// feed changed. Returns the number of failures.
+fn ping_hub() -> std::io::Result<usize> {
+    let hub = WEBSUB_HUB.ok_or_else(|| std::io::Error::other("WEBSUB_HUB is not configured"))?;
+    let mut failures = 0;
+    for feed in feeds() {
+        let url = absolute(&feed.path);
+        match websub::publish(hub, &url) {
+            Ok(()) => println!("ok    {}", url),
+            Err(e) => {
+                println!("FAIL  {}", e);
+                failures += 1;
+            }
+        }
+    }
+    Ok(failures)
+}
+
+/// Runs the `dns-check` command: prints the records the site needs as
+/// zone-file lines, then compares each with public DNS. Returns the number
+/// of records that do not match.
+fn dns_check() -> usize {
+    let pages_host = dns::pages_host();
+    let records = dns::records(&dns::Settings::from_config(SITE_DOMAIN, &pages_host));
+    for record in &records {
+        println!("; {}", record.purpose);
+        for line in record.zone_lines() {
+            println!("{}", line);
+        }
+    }
+    println!();
+
+    let mut failures = 0;
+    for record in &records {
+        let kind = record.expected.kind();
+        match dns::lookup(record).and_then(|answers| record.check(&answers)) {
+            Ok(()) => println!("ok    {} {}", kind, record.name),
+            Err(e) => {
+                println!("FAIL  {} {}: {}", kind, record.name, e);
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+/// Runs the `archive-links` command: submits every external link of the
+/// generated site without a recent snapshot to the Wayback Machine and
+/// records the snapshots in [`ARCHIVE_PATH`]. Returns the number of
+/// failures.
+fn archive_links() -> std::io::Result<usize> {
+    let output_dir = Path::new("target/site");
+    if !output_dir.exists() {
+        return Err(std::io::Error::other(
+            "target/site does not exist; run --generate-static first",
+        ));
+    }
+    let mut links: Vec<String> = Vec::new();
+    for (page, _) in site_pages(output_dir, SITE_URL)? {
+        for href in external_links(&fs::read_to_string(&page)?, SITE_URL) {
+            if !links.contains(&href) {
+                links.push(href);
+            }
+        }
+    }
+
+    let path = Path::new(ARCHIVE_PATH);
+    let mut archived = ArchivedLinks::load(path)
+        .map_err(std::io::Error::other)?
+        .unwrap_or_default();
+    let now = SystemTime::now();
+    let cutoff = rfc3339(now.checked_sub(REARCHIVE_AFTER).unwrap_or(now));
+    let due: Vec<&String> = links
+        .iter()
+        .filter(|href| archived.is_due(href, &cutoff))
+        .collect();
+
+    let mut failures = 0;
+    for (i, href) in due.iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(REQUEST_INTERVAL);
+        }
+        match save_url(href) {
+            Ok(snapshot) => {
+                println!("saved {} -> {}", href, snapshot);
+                let archived_at = rfc3339(SystemTime::now());
+                archived.record(
+                    href,
+                    Snapshot {
+                        snapshot,
+                        archived_at,
+                    },
+                );
+                // Save as we go so an interrupted run keeps its progress
+                archived.save(path)?;
+            }
+            Err(e) => {
+                println!("FAIL  {}", e);
+                failures += 1;
+            }
+        }
+    }
+    println!(
+        "\n{} link(s) due, {} failed; snapshots in {}",
+        due.len(),
+        failures,
+        ARCHIVE_PATH
+    );
+    Ok(failures)
+}
+
+/// Runs the `serve` command: `serve [--release] [--addr <host:port>]`.
+fn serve_site(args: &[String]) -> std::io::Result<()> {
+    let mut release = false;
+    let mut addr = DEFAULT_ADDR.to_string();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--release" => release = true,
+            "--addr" => {
+                addr = rest
+                    .next()
+                    .ok_or_else(|| std::io::Error::other("--addr needs a value"))?
+                    .clone()
+            }
+            other => {
+                return Err(std::io::Error::other(format!(
+                    "Unknown serve option: {}",
+                    other
+                )))
+            }
+        }
+    }
+
+    if release {
+        #[cfg(feature = "embed")]
+        return serve(&EmbeddedFiles, &addr);
+        #[cfg(not(feature = "embed"))]
+        return Err(std::io::Error::other(
+            "serve --release needs a binary built with --features embed",
+        ));
+    }
+    let site = Path::new("target/site");
+    if !site.exists() {
+        return Err(std::io::Error::other(
+            "target/site/ not found. Run --generate-static first.",
+        ));
+    }
+    serve(&DiskFiles(site.to_path_buf()), &addr)
+}
+
 fn print_usage() {
     eprintln!("Usage: everythingsings [OPTIONS]");
+    eprintln!("       everythingsings diff <old-report> <new-report>");
     eprintln!();
     eprintln!("Options:");
-    eprintln!("  --generate-static  Generate static site to target/site/");
+    eprintln!("  --generate-static  Generate static site to target/site/ (and sites/*.toml");
+    eprintln!("                     to target/sites/<name>/); --only posts|assets|feeds");
+    eprintln!("                     regenerates one part into an existing build;");
+    eprintln!("                     --dry-run lists what would be written, writing nothing;");
+    eprintln!("                     --out-dir <dir> writes the site to <dir> instead");
+    eprintln!(
+        "  --extract-text     Write each page's plain text beside it as {}",
+        CONTENT_FILE
+    );
+    eprintln!(
+        "  --single-file      Write the homepage, stylesheet and images inlined, to {}",
+        SINGLE_FILE_PATH
+    );
     eprintln!("  --help             Show this help message");
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!(
+        "  diff               Compare two build reports (see {})",
+        REPORT_PATH
+    );
+    eprintln!("  clean              Remove build output, the build report, exports, and caches");
+    eprintln!("                     (--out-dir for another site directory); only directories");
+    eprintln!(
+        "                     holding a {} marker are removed",
+        MARKER_FILE
+    );
+    eprintln!("  check-a11y         Run static accessibility checks over target/site/");
+    eprintln!(
+        "  check-links        Check every homepage link and record successes in {}",
+        VERIFIED_PATH
+    );
+    eprintln!("  archive-links      Save every external link in target/site/ to the Wayback");
+    eprintln!("                     Machine ({})", ARCHIVE_PATH);
+    eprintln!(
+        "  export-epub        Bundle the media log into an EPUB at {}",
+        EPUB_PATH
+    );
+    eprintln!(
+        "  export-portfolio   Render the art series into a PDF at {}",
+        PORTFOLIO_PATH
+    );
+    eprintln!("  ping-hub           Tell the WebSub hub every feed changed (after deploying)");
+    eprintln!("  dns-check          Print the DNS records the site needs and compare live DNS");
+    eprintln!(
+        "  serve              Serve target/site/ on {} (--addr to change);",
+        DEFAULT_ADDR
+    );
+    eprintln!("                     --release serves the copy embedded by --features embed");
+    eprintln!("  mcp-serve          Answer Model Context Protocol requests on stdio");
 }
 
 fn main() {
@@ -380,11 +2407,100 @@ fn main() {
 
     match args[1].as_str() {
         "--generate-static" => {
-            if let Err(e) = generate_static_site() {
+            if let Err(e) = generate_static_site(&args[2..]) {
                 eprintln!("Error generating static site: {}", e);
                 std::process::exit(1);
             }
         }
+        "--extract-text" => {
+            if let Err(e) = extract_text() {
+                eprintln!("Error extracting text: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "--single-file" => {
+            if let Err(e) = single_file_export() {
+                eprintln!("Error exporting single file: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "diff" => {
+            if args.len() != 4 {
+                print_usage();
+                std::process::exit(1);
+            }
+            if let Err(e) = diff_reports(&args[2], &args[3]) {
+                eprintln!("Error comparing build reports: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "clean" => {
+            if let Err(e) = clean_outputs(&args[2..]) {
+                eprintln!("Error cleaning: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "check-a11y" => match check_a11y() {
+            Ok(0) => {}
+            Ok(_) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error auditing site: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "check-links" => match check_links() {
+            Ok(0) => {}
+            Ok(_) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error checking links: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "archive-links" => match archive_links() {
+            Ok(0) => {}
+            Ok(_) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error archiving links: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "export-epub" => {
+            if let Err(e) = export_epub() {
+                eprintln!("Error exporting EPUB: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "export-portfolio" => {
+            if let Err(e) = export_portfolio() {
+                eprintln!("Error exporting portfolio: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "ping-hub" => match ping_hub() {
+            Ok(0) => {}
+            Ok(_) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("Error pinging hub: {}", e);
+                std::process::exit(1);
+            }
+        },
+        "dns-check" => {
+            if dns_check() > 0 {
+                std::process::exit(1);
+            }
+        }
+        "serve" => {
+            if let Err(e) = serve_site(&args[2..]) {
+                eprintln!("Error serving site: {}", e);
+                std::process::exit(1);
+            }
+        }
+        "mcp-serve" => {
+            if let Err(e) = mcp_serve() {
+                eprintln!("Error serving MCP: {}", e);
+                std::process::exit(1);
+            }
+        }
         "--help" | "-h" => {
             print_usage();
         }
@@ -395,3 +2511,176 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unicode_series() -> ArtSeries {
+        ArtSeries {
+            slug: "café".to_string(),
+            source: "public/art/café".into(),
+            title: "Café 🎨".to_string(),
+            description: "فن رقمي".to_string(),
+            date: "2025-01-01".to_string(),
+            cover_url: "/art/caf%C3%A9/001.jpg".to_string(),
+            cover_placeholder: None,
+            images: Vec::new(),
+            comments: None,
+            nostr_note: None,
+            embeds: Vec::new(),
+            apple_music: None,
+            unlisted: false,
+        }
+    }
+
+    #[test]
+    fn rtl_homepage_declares_direction() {
+        let html = render_to_html(
+            "ar",
+            Vec::new(),
+            VerifiedLinks::default(),
+            ArchivedLinks::default(),
+            Favicons::default(),
+            &[],
+        )
+        .to_html();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"ar\" dir=\"rtl\">\n<head>"));
+        assert!(html.ends_with("</body>\n</html>"));
+        assert_eq!(html.matches(" dir=").count(), 1, "Only the root sets dir");
+    }
+
+    #[test]
+    fn stylesheet_has_no_physical_sides() {
+        let css = render_stylesheet().unwrap();
+        assert_eq!(logical_properties(&css), css);
+        assert!(css.contains("margin-inline-end: var(--spacing-xs);"));
+    }
+
+    #[test]
+    fn sitemap_percent_encodes_unicode_slugs() {
+        let sitemap = generate_sitemap(&[unicode_series()], &[]);
+        assert!(sitemap.contains("<loc>https://everythingsings.art/art/caf%C3%A9/</loc>"));
+    }
+
+    #[test]
+    fn llms_txt_keeps_unicode_text() {
+        let llms = generate_llms_txt(&[unicode_series()]);
+        assert!(llms.contains("- [Café 🎨](https://everythingsings.art/art/caf%C3%A9/): فن رقمي"));
+    }
+
+    #[test]
+    fn series_json_ld_is_valid_with_unicode() {
+        let html =
+            render_art_series(&unicode_series(), None, Vec::new(), Vec::new(), None).to_html();
+        let start = html.find("application/ld+json\">").unwrap() + 21;
+        let end = html[start..].find("</script>").unwrap() + start;
+        let json: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        let gallery = &json["@graph"][0];
+        assert_eq!(gallery["@type"], "ImageGallery");
+        assert_eq!(gallery["name"], "Café 🎨");
+        assert_eq!(gallery["description"], "فن رقمي");
+    }
+
+    /// Files every build writes, whatever the content and config.
+    const SITE_FILES: &[&str] = &[
+        "index.html",
+        "main.css",
+        "tokens.json",
+        "feed.xml",
+        "sitemap.xml",
+        "robots.txt",
+        "llms.txt",
+        "plan.txt",
+        "status.json",
+        "oembed.json",
+        "embeddings.jsonl",
+        "api/profile.json",
+        "api/links.json",
+        "gemini/index.gmi",
+        "gemini/links.gmi",
+        "favicon.svg",
+        "site.webmanifest",
+    ];
+
+    #[test]
+    fn full_build_writes_every_output() {
+        let dir = std::env::temp_dir().join(format!("esart-site-{}", std::process::id()));
+        let outputs = Outputs::under(&dir);
+        generate_site(&outputs, &Scope::full(), true).unwrap();
+        let site = outputs.site;
+        let read = |path: &str| {
+            fs::read_to_string(site.join(path)).unwrap_or_else(|e| panic!("{}: {}", path, e))
+        };
+
+        assert!(site.join(MARKER_FILE).is_file());
+        for path in SITE_FILES {
+            assert!(site.join(path).is_file(), "{} was not generated", path);
+        }
+        for path in [
+            "status.json",
+            "tokens.json",
+            "oembed.json",
+            "api/profile.json",
+            "api/links.json",
+        ] {
+            serde_json::from_str::<serde_json::Value>(&read(path))
+                .unwrap_or_else(|e| panic!("{} is not JSON: {}", path, e));
+        }
+        let index = read("index.html");
+        assert!(index.starts_with("<!DOCTYPE html>\n<html lang="));
+        assert!(index.contains("application/ld+json"));
+        assert!(read("feed.xml").contains("<rss"));
+        assert!(read("robots.txt").contains("/sitemap.xml"));
+
+        // Every page in the sitemap was written
+        let sitemap = read("sitemap.xml");
+        let locs: Vec<&str> = sitemap
+            .split("<loc>")
+            .skip(1)
+            .filter_map(|s| s.split("</loc>").next())
+            .collect();
+        assert!(!locs.is_empty());
+        for loc in locs {
+            let path = loc
+                .strip_prefix(SITE_URL)
+                .unwrap_or_else(|| panic!("{} is off-site", loc))
+                .trim_start_matches('/');
+            let file = match path.is_empty() || path.ends_with('/') {
+                true => site.join(path).join("index.html"),
+                false => site.join(path),
+            };
+            assert!(
+                file.is_file(),
+                "{} is in the sitemap but not generated",
+                loc
+            );
+        }
+
+        let settings = well_known::Settings::from_config();
+        for doc in well_known::documents(&settings, Date::from_unix(0)).unwrap() {
+            assert!(site.join(WELL_KNOWN_DIR).join(&doc.path).is_file());
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn partial_build_writes_only_its_parts() {
+        let dir = std::env::temp_dir().join(format!("esart-partial-{}", std::process::id()));
+        let outputs = Outputs::under(&dir);
+        let scope = Scope::only(&["assets"]).unwrap();
+        generate_site(&outputs, &scope, true).unwrap();
+        assert!(outputs.site.join("main.css").is_file());
+        assert!(!outputs.site.join("index.html").exists());
+        assert!(!outputs.site.join("sitemap.xml").exists());
+        assert!(!outputs.report.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dry_run_removes_its_staging() {
+        dry_run(&Outputs::target(), &Scope::only(&["feeds"]).unwrap()).unwrap();
+        let staging = env::temp_dir().join(format!("esart-dry-run-{}", std::process::id()));
+        assert!(!staging.exists());
+    }
+}
@@ -0,0 +1,449 @@
+//! # Markdown
+//!
+//! Renders the Markdown bodies of [collection](crate::collection) entries
+//! to HTML with CommonMark plus tables, strikethrough, and footnotes. Raw
+//! HTML in the source is passed through: content is written by the site's
+//! owner, not by visitors. Text outside code gets the
+//! [typography](crate::typography) pass when it is on.
+//!
+//! ## Footnotes
+//!
+//! `[^label]` references are numbered in the order they are first used and
+//! link to endnotes at the end of the body, a `doc-endnotes` section whose
+//! `doc-footnote` notes link back to every reference. The first reference
+//! to each note also carries a copy of it as a sidenote, hidden from
+//! assistive technology (the endnote is the accessible one) and shown in
+//! the margin on wide screens.
+//!
+//! ## Headings
+//!
+//! Every heading gets an `id` slugged from its text, numbered when taken
+//! (see [`Slugs`]), so sections can be linked to and listed in a
+//! [table of contents](crate::components::Toc) built from [`headings`].
+//!
+//! ## Links
+//!
+//! [`to_html_with`] rewrites link and image destinations as it renders,
+//! e.g. [internal links](crate::internal_links) to their permalinks.
+
+use crate::config::TYPOGRAPHY;
+use crate::css;
+use crate::slug::Slugs;
+use crate::typography::{prevent_widow, Typesetter};
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+
+/// Ids of the elements rendered around a body's headings: the titles of
+/// the post, its endnotes, and its table of contents.
+const RESERVED_IDS: &[&str] = &["post-title", "footnotes-title", "toc-title"];
+
+/// A heading of a Markdown body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heading {
+    /// 1 for `#`, 2 for `##`, …
+    pub level: u8,
+    /// The heading's `id`, unique within the body.
+    pub id: String,
+    /// The heading's text, typeset, without markup.
+    pub text: String,
+}
+
+/// The extensions enabled on top of CommonMark.
+fn options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES
+}
+
+/// The events of `markdown`, typeset and with anchored headings.
+fn events(markdown: &str) -> (Vec<Event<'_>>, Vec<Heading>) {
+    let events: Vec<Event> = Parser::new_ext(markdown, options()).collect();
+    let mut events = if TYPOGRAPHY { typeset(events) } else { events };
+    let headings = anchor_headings(&mut events);
+    (events, headings)
+}
+
+/// Renders `markdown` to an HTML fragment.
+pub fn to_html(markdown: &str) -> String {
+    to_html_with(markdown, |_| None)
+}
+
+/// Renders `markdown` to an HTML fragment, replacing the destination of
+/// each link and image for which `rewrite` returns a URL.
+pub fn to_html_with(markdown: &str, rewrite: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(markdown.len() * 3 / 2);
+    let (mut events, _) = events(markdown);
+    for event in &mut events {
+        if let Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. }) = event {
+            if let Some(url) = rewrite(dest_url) {
+                *dest_url = url.into();
+            }
+        }
+    }
+    html::push_html(&mut out, footnotes(events).into_iter());
+    out
+}
+
+/// The destinations of every link and image in `markdown`, in order.
+pub fn link_destinations(markdown: &str) -> Vec<String> {
+    Parser::new_ext(markdown, options())
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. }) => {
+                Some(dest_url.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The headings of `markdown`, in order, with the ids [`to_html`] gives
+/// them.
+pub fn headings(markdown: &str) -> Vec<Heading> {
+    events(markdown).1
+}
+
+/// Gives every heading in `events` an `id` slugged from its text.
+fn anchor_headings(events: &mut [Event<'_>]) -> Vec<Heading> {
+    let mut slugs = Slugs::default();
+    for id in RESERVED_IDS {
+        slugs.claim(id);
+    }
+    let mut headings = Vec::new();
+    let mut open: Option<(usize, u8, String)> = None;
+    for i in 0..events.len() {
+        match &events[i] {
+            Event::Start(Tag::Heading { level, .. }) => {
+                open = Some((i, *level as u8, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, heading)) = &mut open {
+                    heading.push_str(text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((start, level, text)) = open.take() else {
+                    continue;
+                };
+                let id = slugs.unique(&text);
+                if let Event::Start(Tag::Heading { id: anchor, .. }) = &mut events[start] {
+                    *anchor = Some(CowStr::from(id.clone()));
+                }
+                headings.push(Heading { level, id, text });
+            }
+            _ => {}
+        }
+    }
+    headings
+}
+
+/// Applies the typography pass to the text of `events`, block by block.
+/// The last text of each paragraph, heading, or list item keeps its last
+/// two words together.
+fn typeset(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut out = Vec::with_capacity(events.len());
+    let mut setter = Typesetter::default();
+    let mut in_code = false;
+    let mut last_text = None;
+    for event in events {
+        match &event {
+            Event::Start(Tag::CodeBlock(_)) => in_code = true,
+            Event::End(TagEnd::CodeBlock) => in_code = false,
+            Event::Start(Tag::Paragraph | Tag::Heading { .. } | Tag::Item) => {
+                setter.reset();
+                last_text = None;
+            }
+            Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item) => {
+                if let Some(i) = last_text.take() {
+                    if let Event::Text(text) = &out[i] {
+                        out[i] = Event::Text(prevent_widow(text).into());
+                    }
+                }
+            }
+            Event::Text(text) if !in_code => {
+                last_text = Some(out.len());
+                out.push(Event::Text(setter.text(text).into()));
+                continue;
+            }
+            Event::Code(code) => setter.skip(code),
+            Event::SoftBreak | Event::HardBreak => setter.skip(" "),
+            _ => {}
+        }
+        out.push(event);
+    }
+    out
+}
+
+/// A footnote's content, and how many times it has been referenced.
+struct Note<'a> {
+    events: Vec<Event<'a>>,
+    refs: usize,
+}
+
+/// Replaces footnote references in `events` with links to endnotes (and
+/// sidenotes), and moves the definitions to an endnotes section at the end.
+/// Definitions nobody references are dropped.
+fn footnotes(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut body = Vec::with_capacity(events.len());
+    let mut notes: HashMap<String, Note> = HashMap::new();
+    let mut open: Option<(String, Vec<Event>)> = None;
+    for event in events {
+        match event {
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                open = Some((label.to_lowercase(), Vec::new()));
+            }
+            Event::End(TagEnd::FootnoteDefinition) => {
+                if let Some((label, events)) = open.take() {
+                    notes.entry(label).or_insert(Note { events, refs: 0 });
+                }
+            }
+            // Notes within notes aren't supported.
+            Event::FootnoteReference(_) if open.is_some() => {}
+            event => match &mut open {
+                Some((_, events)) => events.push(event),
+                None => body.push(event),
+            },
+        }
+    }
+    if notes.is_empty() {
+        return body;
+    }
+
+    let mut order: Vec<String> = Vec::new();
+    let mut out = Vec::with_capacity(body.len() + 2);
+    for event in body {
+        let Event::FootnoteReference(label) = &event else {
+            out.push(event);
+            continue;
+        };
+        let label = label.to_lowercase();
+        let Some(note) = notes.get_mut(&label) else {
+            out.push(event);
+            continue;
+        };
+        if note.refs == 0 {
+            order.push(label.clone());
+        }
+        note.refs += 1;
+        let n = order.iter().position(|l| *l == label).map_or(0, |i| i + 1);
+        let sidenote = (note.refs == 1).then(|| inline_html(&note.events));
+        out.push(Event::InlineHtml(
+            note_ref(n, note.refs, sidenote.as_deref()).into(),
+        ));
+    }
+    if !order.is_empty() {
+        let numbered = order.iter().map(|label| &notes[label]);
+        out.push(Event::Html(endnotes(numbered).into()));
+    }
+    out
+}
+
+/// Id of a note's endnote.
+fn note_id(n: usize) -> String {
+    format!("fn-{}", n)
+}
+
+/// Id of the `k`th reference to a note.
+fn ref_id(n: usize, k: usize) -> String {
+    match k {
+        1 => format!("fnref-{}", n),
+        _ => format!("fnref-{}-{}", n, k),
+    }
+}
+
+/// The `k`th reference to note `n`, followed by its sidenote if given.
+fn note_ref(n: usize, k: usize, sidenote: Option<&str>) -> String {
+    let mut html = format!(
+        r##"<sup class="{}"><a href="#{}" id="{}" role="doc-noteref">{}</a></sup>"##,
+        css::NOTE_REF,
+        note_id(n),
+        ref_id(n, k),
+        n
+    );
+    if let Some(sidenote) = sidenote {
+        html.push_str(&format!(
+            r#"<span class="{}" aria-hidden="true"><span class="{}">{}</span> {}</span>"#,
+            css::SIDENOTE,
+            css::SIDENOTE_NUMBER,
+            n,
+            sidenote
+        ));
+    }
+    html
+}
+
+/// A note's content as inline HTML, its paragraphs run together.
+fn inline_html(events: &[Event<'_>]) -> String {
+    let mut out = String::new();
+    let inline = events.iter().cloned().filter_map(|event| match event {
+        Event::Start(Tag::Paragraph) => None,
+        Event::End(TagEnd::Paragraph) => Some(Event::Text(" ".into())),
+        event => Some(event),
+    });
+    html::push_html(&mut out, inline);
+    out.trim_end().to_string()
+}
+
+/// The endnotes section, numbered from 1 in the order given. Each note ends
+/// with a back-link to every reference to it.
+fn endnotes<'a, 'b: 'a>(notes: impl Iterator<Item = &'a Note<'b>>) -> String {
+    let mut html = format!(
+        r#"<section class="{}" role="doc-endnotes" aria-labelledby="footnotes-title"><h2 id="footnotes-title">Notes</h2>"#,
+        css::FOOTNOTES
+    );
+    for (i, note) in notes.enumerate() {
+        let n = i + 1;
+        let backlinks: String = (1..=note.refs)
+            .map(|k| {
+                let label = match note.refs {
+                    1 => format!("Back to reference {}", n),
+                    _ => format!("Back to reference {}, use {}", n, k),
+                };
+                format!(
+                    r##" <a href="#{}" class="{}" role="doc-backlink" aria-label="{}">↩&#xfe0e;</a>"##,
+                    ref_id(n, k),
+                    css::FOOTNOTE_BACKLINK,
+                    label
+                )
+            })
+            .collect();
+        let mut events = note.events.clone();
+        // Back-links go at the end of the last paragraph when there is one.
+        match events.last() {
+            Some(Event::End(TagEnd::Paragraph)) => {
+                events.insert(events.len() - 1, Event::InlineHtml(backlinks.into()));
+            }
+            _ => events.push(Event::Html(format!("<p>{}</p>", backlinks.trim()).into())),
+        }
+        html.push_str(&format!(
+            r#"<div class="{}" id="{}" role="doc-footnote">"#,
+            css::FOOTNOTE,
+            note_id(n)
+        ));
+        html::push_html(&mut html, events.into_iter());
+        html.push_str("</div>");
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+/// The text of `markdown` without markup: one line per paragraph, heading,
+/// or list item, with raw HTML, images, and footnotes dropped.
+pub fn plain_text(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_note = false;
+    for event in Parser::new_ext(markdown, options()) {
+        match event {
+            Event::Start(Tag::FootnoteDefinition(_)) => in_note = true,
+            Event::End(TagEnd::FootnoteDefinition) => in_note = false,
+            _ if in_note => {}
+            Event::Text(text) | Event::Code(text) => out.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => out.push(' '),
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableRow,
+            ) => out.push('\n'),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_commonmark_and_tables() {
+        assert_eq!(to_html("Hello *world*"), "<p>Hello <em>world</em></p>\n");
+        let table = to_html("| a | b |\n|---|---|\n| 1 | 2 |\n");
+        assert!(table.contains("<table>"), "{}", table);
+        assert!(to_html("~~old~~").contains("<del>old</del>"));
+    }
+
+    #[test]
+    fn typography_skips_code() {
+        let html = to_html("It's \"art\" -- `don't` touch it\n\n```\n\"raw\"\n```\n");
+        if TYPOGRAPHY {
+            assert!(
+                html.contains("It’s “art” – <code>don't</code> touch\u{a0}it"),
+                "{}",
+                html
+            );
+        } else {
+            assert!(html.contains("It's \"art\" -- <code>"), "{}", html);
+        }
+        assert!(html.contains("<code>\"raw\"\n</code>"), "{}", html);
+    }
+
+    #[test]
+    fn link_destinations_can_be_rewritten() {
+        let markdown = "[a](@/x.md) ![b](cover.jpg \"Cover\")\n\n[c]: @/y.md\n\nAnd [c].";
+        assert_eq!(
+            link_destinations(markdown),
+            ["@/x.md", "cover.jpg", "@/y.md"]
+        );
+        let html = to_html_with(markdown, |url| {
+            url.strip_prefix("@/").map(|u| format!("/{}", u))
+        });
+        assert!(html.contains(r#"<a href="/x.md">"#), "{}", html);
+        assert!(html.contains(r#"<img src="cover.jpg""#), "{}", html);
+        assert!(html.contains(r#"<a href="/y.md">"#), "{}", html);
+    }
+
+    #[test]
+    fn headings_get_unique_ids() {
+        let markdown = "# Intro\n\n## Café `code`\n\n## Intro\n\n### Notes title\n";
+        let html = to_html(markdown);
+        assert!(html.contains(r#"<h1 id="intro">"#), "{}", html);
+        assert!(html.contains(r#"<h2 id="cafe-code">"#), "{}", html);
+        assert!(html.contains(r#"<h2 id="intro-2">"#), "{}", html);
+        let headings = headings(markdown);
+        let ids: Vec<&str> = headings.iter().map(|h| h.id.as_str()).collect();
+        assert_eq!(ids, ["intro", "cafe-code", "intro-2", "notes-title"]);
+        assert_eq!(headings[1].level, 2);
+        assert_eq!(headings[1].text, "Café code");
+    }
+
+    #[test]
+    fn footnotes_become_endnotes_and_sidenotes() {
+        let html = to_html("One[^b] and two[^a], one again[^b].\n\n[^a]: First.\n[^b]: *Second*.\n[^unused]: Dropped.\n");
+        let refs: Vec<&str> = html
+            .match_indices("role=\"doc-noteref\">")
+            .map(|(i, _)| &html[i + 19..i + 20])
+            .collect();
+        assert_eq!(refs, ["1", "2", "1"], "{}", html);
+        assert!(
+            html.contains(r##"<a href="#fn-1" id="fnref-1" role="doc-noteref">"##),
+            "{}",
+            html
+        );
+        assert!(html.contains(r##"id="fnref-1-2""##), "{}", html);
+        assert_eq!(html.matches(css::SIDENOTE_NUMBER).count(), 2, "{}", html);
+        assert!(html.contains("<em>Second</em>."), "{}", html);
+
+        let notes = &html[html.find("role=\"doc-endnotes\"").expect(&html)..];
+        assert!(
+            notes.contains(
+                r##"id="fn-1" role="doc-footnote"><p><em>Second</em>. <a href="#fnref-1""##
+            ),
+            "{}",
+            notes
+        );
+        assert!(notes.contains(r##"href="#fnref-1-2""##), "{}", notes);
+        assert!(notes.contains(r#"role="doc-backlink""#), "{}", notes);
+        assert!(!notes.contains("Dropped"), "{}", notes);
+        assert_eq!(html.matches("doc-footnote\"").count(), 2);
+    }
+
+    #[test]
+    fn text_without_references_has_no_notes() {
+        let html = to_html("Unreferenced.\n\n[^a]: Unused.\n");
+        assert_eq!(html, "<p>Unreferenced.</p>\n");
+    }
+
+    #[test]
+    fn plain_text_drops_markup() {
+        assert_eq!(
+            plain_text("# Title\n\nSome *em*\nand `code` <b>x</b>.\n\n- one\n- two\n"),
+            "Title\nSome em and code x.\none\ntwo\n"
+        );
+        assert_eq!(plain_text("Text[^1].\n\n[^1]: Note.\n"), "Text.\n");
+    }
+}
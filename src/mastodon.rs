@@ -0,0 +1,271 @@
+//! # Mastodon Comments
+//!
+//! A series can name the Mastodon status announcing it in its
+//! `series.toml`:
+//!
+//! ```toml
+//! [comments]
+//! instance = "mastodon.social"
+//! status = "113456789012345678"
+//! ```
+//!
+//! At build time the status and its public replies are fetched from the
+//! instance's API (through [`crate::cache`]) and baked into the page as a
+//! static comments section with a "Reply on Mastodon" link. Reply HTML is
+//! reduced to plain text, so nothing from a reply but its words reaches
+//! the page. The status's favourite and boost counts become the page's
+//! Mastodon [`Reactions`].
+
+use crate::cache::{cached_fetch, json_text};
+use crate::reactions::{count_at, Count, Reactions};
+use crate::text::plain_text;
+use crate::webmention::{web_url, Mention, MENTION_TEXT_LIMIT};
+use scraper::{ElementRef, Html};
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// How long a cached thread is used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// A Mastodon status whose replies are a page's comments.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MastodonThread {
+    /// Host of the instance the status lives on, e.g. `mastodon.social`.
+    pub instance: String,
+    /// The status id.
+    pub status: String,
+}
+
+impl MastodonThread {
+    /// Whether the instance looks like a host name and the id like an id,
+    /// so neither can redirect requests or escape the cache directory.
+    pub fn is_valid(&self) -> bool {
+        let host_ok = !self.instance.is_empty()
+            && self
+                .instance
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+        let id_ok =
+            !self.status.is_empty() && self.status.chars().all(|c| c.is_ascii_alphanumeric());
+        host_ok && id_ok
+    }
+
+    fn api_url(&self, suffix: &str) -> String {
+        format!(
+            "https://{}/api/v1/statuses/{}{}",
+            self.instance, self.status, suffix
+        )
+    }
+
+    fn cache_name(&self, suffix: &str) -> String {
+        format!("mastodon-{}-{}{}.json", self.instance, self.status, suffix)
+    }
+}
+
+/// A fetched thread: where to reply, and the public replies so far.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Comments {
+    /// Web URL of the status on its instance.
+    pub reply_url: String,
+    pub replies: Vec<Mention>,
+}
+
+/// Reduces status HTML to its visible text. Mastodon hides parts of long
+/// link text in `span.invisible`; those (and any script or style) are
+/// dropped, and paragraphs and line breaks become spaces.
+pub fn status_text(html: &str) -> String {
+    fn collect(element: ElementRef, out: &mut String) {
+        for child in element.children() {
+            if let Some(text) = child.value().as_text() {
+                out.push_str(text);
+            } else if let Some(el) = ElementRef::wrap(child) {
+                let name = el.value().name();
+                if matches!(name, "p" | "br") {
+                    out.push(' ');
+                }
+                let hidden = matches!(name, "script" | "style")
+                    || el.value().classes().any(|c| c == "invisible");
+                if !hidden {
+                    collect(el, out);
+                }
+            }
+        }
+    }
+    let fragment = Html::parse_fragment(html);
+    let mut out = String::new();
+    collect(fragment.root_element(), &mut out);
+    plain_text(&out, MENTION_TEXT_LIMIT)
+}
+
+/// Converts a public status to a [`Mention`]; `None` for private,
+/// direct, or malformed statuses.
+fn parse_status(status: &Value) -> Option<Mention> {
+    if !matches!(status["visibility"].as_str(), Some("public" | "unlisted")) {
+        return None;
+    }
+    let account = &status["account"];
+    let name = json_text(account, "display_name").or_else(|| json_text(account, "acct"))?;
+    Some(Mention {
+        source: web_url(json_text(status, "url"))?,
+        author_name: plain_text(&name, MENTION_TEXT_LIMIT),
+        author_url: web_url(json_text(account, "url")),
+        published: json_text(status, "created_at")?,
+        text: status_text(status["content"].as_str().unwrap_or("")),
+    })
+}
+
+/// Parses a `GET /api/v1/statuses/:id/context` response into the public
+/// replies, oldest first.
+pub fn parse_context(json: &str) -> Vec<Mention> {
+    let Ok(doc) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    let mut replies: Vec<Mention> = doc["descendants"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(parse_status)
+        .collect();
+    replies.sort_by(|a, b| a.published.cmp(&b.published));
+    replies
+}
+
+/// Parses the favourite and boost counts of a `GET /api/v1/statuses/:id`
+/// response.
+pub fn parse_reactions(json: &str) -> Option<Reactions> {
+    let status: Value = serde_json::from_str(json).ok()?;
+    let counts = [
+        ("favourites", "LikeAction", "favourites_count"),
+        ("boosts", "ShareAction", "reblogs_count"),
+    ]
+    .into_iter()
+    .filter_map(|(label, action, key)| {
+        Some(Count {
+            label,
+            action,
+            value: count_at(&status, key)?,
+        })
+    })
+    .collect();
+    Some(Reactions {
+        network: "Mastodon",
+        url: web_url(json_text(&status, "url"))?,
+        counts,
+    })
+}
+
+/// Loads the favourite and boost counts of `thread`'s status, sharing the
+/// cached status with [`load_comments`].
+pub fn load_reactions(thread: &MastodonThread) -> Option<Reactions> {
+    if !thread.is_valid() {
+        return None;
+    }
+    cached_fetch(&thread.cache_name(""), &thread.api_url(""), MAX_AGE)
+        .and_then(|json| parse_reactions(&json))
+}
+
+/// Loads the comments for `thread`, fetching them if the cache is stale.
+///
+/// Returns `None` when the thread is invalid or its status is neither
+/// cached nor reachable; a reachable status without reachable replies has
+/// no comments yet.
+pub fn load_comments(thread: &MastodonThread) -> Option<Comments> {
+    if !thread.is_valid() {
+        eprintln!(
+            "Warning: Invalid Mastodon thread {}/{}",
+            thread.instance, thread.status
+        );
+        return None;
+    }
+    let status = cached_fetch(&thread.cache_name(""), &thread.api_url(""), MAX_AGE)?;
+    let reply_url = serde_json::from_str::<Value>(&status)
+        .ok()
+        .and_then(|s| web_url(json_text(&s, "url")))?;
+    let replies = cached_fetch(
+        &thread.cache_name("-context"),
+        &thread.api_url("/context"),
+        MAX_AGE,
+    )
+    .map(|json| parse_context(&json))
+    .unwrap_or_default();
+    Some(Comments { reply_url, replies })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const CONTEXT: &str = r#"{"ancestors": [], "descendants": [
+        {"id": "2", "url": "https://mastodon.test/@b/2", "visibility": "public",
+         "created_at": "2025-01-18T10:00:00.000Z",
+         "content": "<p>Second <a href=\"https://x.test/long/path\"><span class=\"invisible\">https://</span><span class=\"ellipsis\">x.test/long</span><span class=\"invisible\">/path</span></a></p><p>line</p>",
+         "account": {"acct": "b@other.test", "display_name": "", "url": "https://other.test/@b"}},
+        {"id": "1", "url": "https://mastodon.test/@a/1", "visibility": "public",
+         "created_at": "2025-01-17T10:00:00.000Z",
+         "content": "<p>Lovely<br>work <script>alert(1)</script></p>",
+         "account": {"acct": "a", "display_name": "Ada", "url": "https://mastodon.test/@a"}},
+        {"id": "3", "url": "https://mastodon.test/@c/3", "visibility": "direct",
+         "created_at": "2025-01-19T10:00:00.000Z", "content": "<p>secret</p>",
+         "account": {"acct": "c", "display_name": "C", "url": "https://mastodon.test/@c"}}
+    ]}"#;
+
+    pub fn sample_comments() -> Comments {
+        Comments {
+            reply_url: "https://mastodon.test/@everythingsings/1".to_string(),
+            replies: parse_context(CONTEXT),
+        }
+    }
+
+    #[test]
+    fn public_replies_are_parsed_oldest_first() {
+        let replies = parse_context(CONTEXT);
+        assert_eq!(replies.len(), 2, "Direct replies are not shown");
+        assert_eq!(replies[0].author_name, "Ada");
+        assert_eq!(replies[0].source, "https://mastodon.test/@a/1");
+        assert_eq!(replies[1].author_name, "b@other.test");
+        assert_eq!(
+            replies[1].author_url.as_deref(),
+            Some("https://other.test/@b")
+        );
+    }
+
+    #[test]
+    fn status_html_becomes_plain_text() {
+        let replies = parse_context(CONTEXT);
+        assert_eq!(replies[0].text, "Lovely work");
+        assert_eq!(replies[1].text, "Second x.test/long line");
+    }
+
+    #[test]
+    fn status_counts_become_reactions() {
+        let reactions = parse_reactions(
+            r#"{"id": "1", "url": "https://mastodon.test/@e/1",
+                "favourites_count": 12, "reblogs_count": 3, "replies_count": 2}"#,
+        )
+        .unwrap();
+        assert_eq!(reactions.network, "Mastodon");
+        assert_eq!(reactions.url, "https://mastodon.test/@e/1");
+        let counts: Vec<(&str, u64)> = reactions
+            .counts
+            .iter()
+            .map(|c| (c.label, c.value))
+            .collect();
+        assert_eq!(counts, [("favourites", 12), ("boosts", 3)]);
+    }
+
+    #[test]
+    fn threads_are_validated() {
+        let thread = |instance: &str, status: &str| MastodonThread {
+            instance: instance.to_string(),
+            status: status.to_string(),
+        };
+        assert!(thread("mastodon.social", "113456789").is_valid());
+        assert!(!thread("evil.test/x?", "1").is_valid());
+        assert!(!thread("mastodon.social", "../1").is_valid());
+        assert_eq!(
+            thread("mastodon.social", "1").api_url("/context"),
+            "https://mastodon.social/api/v1/statuses/1/context"
+        );
+    }
+}
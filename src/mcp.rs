@@ -0,0 +1,385 @@
+//! # MCP Server
+//!
+//! `mcp-serve` speaks the [Model Context Protocol](https://modelcontextprotocol.io)
+//! over stdio, so an AI assistant can query the site's structured content
+//! directly instead of scraping pages. Configure it as a stdio server:
+//!
+//! ```json
+//! { "command": "everythingsings", "args": ["mcp-serve"] }
+//! ```
+//!
+//! ## Resources
+//!
+//! | URI | Content |
+//! |-----|---------|
+//! | `site://profile` | Name, bio, avatar, and profile links (as `/api/profile.json`) |
+//! | `site://links` | Links by group (as `/api/links.json`) |
+//! | `site://posts` | Log entries: books, films, and albums, newest first |
+//! | `site://gallery` | Art series with their images' titles and alt text |
+//!
+//! ## Tools
+//!
+//! - `search`: finds the [embedding chunks](crate::embeddings) of the last
+//!   build that best match a query, so it needs `--generate-static` first.
+//!
+//! Messages are newline-delimited JSON-RPC 2.0; the server answers
+//! `initialize`, `ping`, `resources/list`, `resources/read`, `tools/list`,
+//! and `tools/call`, and ignores notifications.
+
+use crate::art::ArtSeries;
+use crate::embeddings::Chunk;
+use crate::media::MediaLog;
+use crate::provenance::VERSION;
+use crate::url::absolute;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+/// MCP revision implemented.
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Results returned by `search` unless the call asks for fewer.
+const SEARCH_LIMIT: usize = 5;
+
+/// JSON-RPC error codes.
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// A document the server exposes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resource {
+    pub uri: String,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// JSON text.
+    pub text: String,
+}
+
+impl Resource {
+    /// A JSON resource at `site://<key>`.
+    pub fn new(key: &str, name: &'static str, description: &'static str, text: String) -> Self {
+        Resource {
+            uri: format!("site://{}", key),
+            name,
+            description,
+            text,
+        }
+    }
+}
+
+/// The log entries as JSON, newest first.
+pub fn posts_json(log: &MediaLog) -> String {
+    let entries: Vec<Value> = log
+        .entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "kind": entry.kind.label(),
+                "title": entry.title,
+                "creator": entry.creator,
+                "year": entry.year,
+                "date": entry.date,
+                "rating": entry.rating,
+                "note": entry.note,
+                "url": absolute(&format!("/log/#{}", entry.anchor())),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&entries).expect("posts serialize")
+}
+
+/// The art series and their images as JSON, newest first.
+pub fn gallery_json(series: &[ArtSeries]) -> String {
+    let series: Vec<Value> = series
+        .iter()
+        .map(|s| {
+            let images: Vec<Value> = s
+                .images
+                .iter()
+                .map(|image| {
+                    json!({
+                        "url": absolute(&image.url),
+                        "title": image.title,
+                        "alt": image.alt,
+                        "description": image.description,
+                    })
+                })
+                .collect();
+            json!({
+                "title": s.title,
+                "description": s.description,
+                "date": s.date,
+                "url": absolute(&s.url_path()),
+                "images": images,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&series).expect("gallery serializes")
+}
+
+/// Ranks `chunks` by how many of the query's words they contain (in their
+/// text or headings), best first, dropping those with none.
+fn search<'a>(chunks: &'a [Chunk], query: &str, limit: usize) -> Vec<&'a Chunk> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut scored: Vec<(usize, &Chunk)> = chunks
+        .iter()
+        .map(|chunk| {
+            let haystack = format!("{} {}", chunk.headings.join(" "), chunk.text).to_lowercase();
+            let score = words
+                .iter()
+                .map(|w| haystack.matches(w.as_str()).count())
+                .sum();
+            (score, chunk)
+        })
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    // Stable, so equal scores keep site order
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().take(limit).map(|(_, c)| c).collect()
+}
+
+/// An MCP server over the site's content.
+pub struct McpServer {
+    resources: Vec<Resource>,
+    chunks: Vec<Chunk>,
+}
+
+fn error(id: &Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+impl McpServer {
+    /// Serves `resources` and searches `chunks`.
+    pub fn new(resources: Vec<Resource>, chunks: Vec<Chunk>) -> Self {
+        McpServer { resources, chunks }
+    }
+
+    fn tools() -> Value {
+        json!([{
+            "name": "search",
+            "description": "Search the site's text. Returns the best-matching passages with their page URL and headings.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Words to look for" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum passages returned" },
+                },
+                "required": ["query"],
+            },
+        }])
+    }
+
+    fn call_tool(&self, params: &Value) -> Result<Value, &'static str> {
+        if params["name"] != "search" {
+            return Err("Unknown tool");
+        }
+        let query = params["arguments"]["query"]
+            .as_str()
+            .ok_or("search needs a query")?;
+        let limit = params["arguments"]["limit"]
+            .as_u64()
+            .map_or(SEARCH_LIMIT, |l| l as usize);
+        let hits: Vec<Value> = search(&self.chunks, query, limit)
+            .into_iter()
+            .map(|chunk| json!(chunk))
+            .collect();
+        let text = if hits.is_empty() {
+            "No matches.".to_string()
+        } else {
+            serde_json::to_string_pretty(&hits).expect("hits serialize")
+        };
+        Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    }
+
+    /// Answers one JSON-RPC message; `None` for notifications.
+    pub fn handle(&self, message: &Value) -> Option<Value> {
+        let id = message.get("id")?;
+        let params = &message["params"];
+        let result = match message["method"].as_str().unwrap_or_default() {
+            "initialize" => json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "resources": {}, "tools": {} },
+                "serverInfo": { "name": "everythingsings", "version": VERSION },
+            }),
+            "ping" => json!({}),
+            "resources/list" => {
+                let resources: Vec<Value> = self
+                    .resources
+                    .iter()
+                    .map(|r| {
+                        json!({
+                            "uri": r.uri,
+                            "name": r.name,
+                            "description": r.description,
+                            "mimeType": "application/json",
+                        })
+                    })
+                    .collect();
+                json!({ "resources": resources })
+            }
+            "resources/read" => {
+                let uri = params["uri"].as_str().unwrap_or_default();
+                let Some(resource) = self.resources.iter().find(|r| r.uri == uri) else {
+                    return Some(error(id, INVALID_PARAMS, "Unknown resource"));
+                };
+                json!({ "contents": [{
+                    "uri": resource.uri,
+                    "mimeType": "application/json",
+                    "text": resource.text,
+                }] })
+            }
+            "tools/list" => json!({ "tools": Self::tools() }),
+            "tools/call" => match self.call_tool(params) {
+                Ok(result) => result,
+                Err(message) => return Some(error(id, INVALID_PARAMS, message)),
+            },
+            _ => return Some(error(id, METHOD_NOT_FOUND, "Method not found")),
+        };
+        Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+
+    /// Answers newline-delimited messages from `input` on `output` until
+    /// `input` ends.
+    pub fn run(&self, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(&message),
+                Err(_) => Some(error(&Value::Null, PARSE_ERROR, "Parse error")),
+            };
+            if let Some(response) = response {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::MediaEntry;
+
+    fn chunk(url: &str, heading: &str, text: &str) -> Chunk {
+        Chunk {
+            url: url.to_string(),
+            headings: vec![heading.to_string()],
+            summary: String::new(),
+            text: text.to_string(),
+            tokens: 1,
+        }
+    }
+
+    fn server() -> McpServer {
+        McpServer::new(
+            vec![Resource::new(
+                "profile",
+                "Profile",
+                "Who this is",
+                r#"{"name":"EverythingSings"}"#.to_string(),
+            )],
+            vec![
+                chunk("https://a.test/cv/", "Projects", "Lumimenta trading cards."),
+                chunk("https://a.test/", "Links", "Shop for art prints and cards."),
+                chunk("https://a.test/log/", "Log", "Dune."),
+            ],
+        )
+    }
+
+    fn call(method: &str, params: Value) -> Value {
+        let message = json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params });
+        server().handle(&message).expect("requests are answered")
+    }
+
+    #[test]
+    fn initialize_announces_capabilities() {
+        let response = call("initialize", json!({}));
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(response["result"]["capabilities"]["resources"].is_object());
+    }
+
+    #[test]
+    fn resources_are_listed_and_read() {
+        let list = call("resources/list", json!({}));
+        assert_eq!(list["result"]["resources"][0]["uri"], "site://profile");
+        let read = call("resources/read", json!({ "uri": "site://profile" }));
+        assert_eq!(
+            read["result"]["contents"][0]["text"],
+            r#"{"name":"EverythingSings"}"#
+        );
+        let missing = call("resources/read", json!({ "uri": "site://nope" }));
+        assert_eq!(missing["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn search_ranks_matching_chunks() {
+        let response = call(
+            "tools/call",
+            json!({ "name": "search", "arguments": { "query": "Cards lumimenta" } }),
+        );
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        let hits: Vec<Value> = serde_json::from_str(text).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0]["url"], "https://a.test/cv/");
+
+        let none = call(
+            "tools/call",
+            json!({ "name": "search", "arguments": { "query": "zebra" } }),
+        );
+        assert_eq!(none["result"]["content"][0]["text"], "No matches.");
+        let bad = call("tools/call", json!({ "name": "search", "arguments": {} }));
+        assert_eq!(bad["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn stdio_skips_notifications_and_reports_bad_json() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            "\n",
+            "not json\n",
+            r#"{"jsonrpc":"2.0","id":1,"method":"bogus"}"#,
+            "\n",
+        );
+        let mut output = Vec::new();
+        server().run(input.as_bytes(), &mut output).unwrap();
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["error"]["code"], PARSE_ERROR);
+        assert_eq!(lines[1]["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn posts_link_to_log_entries() {
+        let log = MediaLog {
+            entries: vec![MediaEntry {
+                kind: crate::media::MediaKind::Book,
+                title: "Dune".to_string(),
+                creator: "Frank Herbert".to_string(),
+                year: Some(1965),
+                date: "2025-01-17".to_string(),
+                rating: Some(5),
+                url: None,
+                note: String::new(),
+            }],
+        };
+        let posts: Value = serde_json::from_str(&posts_json(&log)).unwrap();
+        assert_eq!(posts[0]["kind"], "Book");
+        assert_eq!(
+            posts[0]["url"],
+            "https://everythingsings.art/log/#2025-01-17-dune"
+        );
+    }
+}
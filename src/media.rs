@@ -0,0 +1,327 @@
+//! # Media Log
+//!
+//! Reads `media.toml`, a log of books read, films watched, and albums
+//! listened to, for the `/log/` page and its `/log/feed.xml` feed. Each
+//! entry becomes a Schema.org `Book`, `Movie`, or `MusicAlbum` with an
+//! optional star rating.
+//!
+//! ## Example
+//!
+//! ```toml
+//! [[entry]]
+//! kind = "book"            # book | movie | album
+//! title = "The Dispossessed"
+//! creator = "Ursula K. Le Guin"
+//! year = 1974
+//! date = "2025-01-17"      # when it was finished
+//! rating = 5               # optional, 1–5
+//! note = "Still the best."
+//! ```
+
+use crate::escape::json_script;
+use crate::feed::FeedItem;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Media log file, relative to the project root.
+pub const MEDIA_PATH: &str = "media.toml";
+
+/// Output directory of the log page.
+pub const LOG_DIR: &str = "log";
+
+/// Description of the log page and feed.
+pub const LOG_DESCRIPTION: &str = "Books, films, and albums, newest first.";
+
+/// Highest star rating.
+pub const BEST_RATING: u8 = 5;
+
+/// What kind of work an entry is.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Book,
+    Movie,
+    Album,
+}
+
+impl MediaKind {
+    /// Display label.
+    pub fn label(&self) -> &'static str {
+        match self {
+            MediaKind::Book => "Book",
+            MediaKind::Movie => "Film",
+            MediaKind::Album => "Album",
+        }
+    }
+
+    /// Schema.org type of the work.
+    pub fn schema_type(&self) -> &'static str {
+        match self {
+            MediaKind::Book => "Book",
+            MediaKind::Movie => "Movie",
+            MediaKind::Album => "MusicAlbum",
+        }
+    }
+
+    /// Schema.org property and type linking the work to its creator.
+    pub fn creator(&self) -> (&'static str, &'static str) {
+        match self {
+            MediaKind::Book => ("author", "Person"),
+            MediaKind::Movie => ("director", "Person"),
+            MediaKind::Album => ("byArtist", "MusicGroup"),
+        }
+    }
+}
+
+/// One logged work.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MediaEntry {
+    pub kind: MediaKind,
+    pub title: String,
+    /// Author, director, or artist.
+    pub creator: String,
+    /// Year of release or publication.
+    #[serde(default)]
+    pub year: Option<u32>,
+    /// ISO date the entry was logged, e.g. `2025-01-17`.
+    pub date: String,
+    /// Star rating from 1 to [`BEST_RATING`].
+    #[serde(default)]
+    pub rating: Option<u8>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl MediaEntry {
+    /// Fragment id of the entry on the log page, e.g. `2025-01-17-the-dispossessed`.
+    pub fn anchor(&self) -> String {
+        let mut slug = String::new();
+        for c in self.title.to_lowercase().chars() {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        format!("{}-{}", self.date, slug.trim_end_matches('-'))
+    }
+}
+
+/// Renders a rating as filled and empty stars, e.g. `★★★★☆`.
+pub fn stars(rating: u8) -> String {
+    let filled = rating.min(BEST_RATING) as usize;
+    format!(
+        "{}{}",
+        "★".repeat(filled),
+        "☆".repeat(BEST_RATING as usize - filled)
+    )
+}
+
+/// The media log, newest entry first.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct MediaLog {
+    #[serde(default, rename = "entry")]
+    pub entries: Vec<MediaEntry>,
+}
+
+impl MediaLog {
+    /// Loads the log from `path`; `Ok(None)` when the file is absent.
+    ///
+    /// Entries are sorted newest first. Ratings outside 1–5 are an error.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let log = Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Some(log))
+    }
+
+    /// Parses and validates log TOML.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut log: MediaLog =
+            toml::from_str(content).map_err(|e| format!("Could not parse: {}", e))?;
+        if let Some(entry) = log
+            .entries
+            .iter()
+            .find(|e| e.rating.is_some_and(|r| r == 0 || r > BEST_RATING))
+        {
+            return Err(format!(
+                "Rating for \"{}\" must be 1–{}",
+                entry.title, BEST_RATING
+            ));
+        }
+        log.entries.sort_by(|a, b| b.date.cmp(&a.date));
+        Ok(log)
+    }
+
+    /// Feed items for `/log/feed.xml`, linking to each entry's anchor under `page_url`.
+    pub fn feed_items(&self, page_url: &str) -> Vec<FeedItem> {
+        self.entries
+            .iter()
+            .map(|e| {
+                let mut title = format!("{}: {} — {}", e.kind.label(), e.title, e.creator);
+                if let Some(rating) = e.rating {
+                    title.push_str(&format!(" {}", stars(rating)));
+                }
+                FeedItem {
+                    title,
+                    link: format!("{}#{}", page_url, e.anchor()),
+                    description: e.note.clone(),
+                    date: e.date.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// Builds the `/log/` JSON-LD: an `ItemList` of works, each with a
+    /// `Review` by `author_id` carrying its rating.
+    pub fn to_json_ld(&self, url: &str, author_id: &str) -> String {
+        let items: Vec<Value> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let (creator_prop, creator_type) = e.kind.creator();
+                let mut review = json!({
+                    "@type": "Review",
+                    "author": { "@id": author_id },
+                    "datePublished": e.date,
+                });
+                if let Some(rating) = e.rating {
+                    review["reviewRating"] = json!({
+                        "@type": "Rating",
+                        "ratingValue": rating,
+                        "bestRating": BEST_RATING,
+                    });
+                }
+                if !e.note.is_empty() {
+                    review["reviewBody"] = json!(e.note);
+                }
+                let mut work = json!({
+                    "@type": e.kind.schema_type(),
+                    "name": e.title,
+                    creator_prop: { "@type": creator_type, "name": e.creator },
+                    "review": review,
+                });
+                if let Some(year) = e.year {
+                    work["datePublished"] = json!(year.to_string());
+                }
+                if let Some(link) = &e.url {
+                    work["url"] = json!(link);
+                }
+                json!({ "@type": "ListItem", "position": i + 1, "item": work })
+            })
+            .collect();
+
+        json_script(&json!({
+            "@context": "https://schema.org",
+            "@type": "ItemList",
+            "name": "Log",
+            "url": url,
+            "numberOfItems": items.len(),
+            "itemListElement": items,
+        }))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn sample_log() -> MediaLog {
+        MediaLog::parse(
+            r#"
+[[entry]]
+kind = "book"
+title = "The Dispossessed"
+creator = "Ursula K. Le Guin"
+year = 1974
+date = "2025-01-17"
+rating = 5
+note = "Still the best."
+
+[[entry]]
+kind = "album"
+title = "Untrue"
+creator = "Burial"
+date = "2025-02-03"
+rating = 4
+
+[[entry]]
+kind = "movie"
+title = "Stalker"
+creator = "Andrei Tarkovsky"
+date = "2024-12-30"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn missing_config_has_no_log() {
+        assert_eq!(MediaLog::load(Path::new("does-not-exist.toml")), Ok(None));
+    }
+
+    #[test]
+    fn entries_are_newest_first() {
+        let titles: Vec<_> = sample_log().entries.into_iter().map(|e| e.title).collect();
+        assert_eq!(titles, ["Untrue", "The Dispossessed", "Stalker"]);
+    }
+
+    #[test]
+    fn out_of_range_rating_is_rejected() {
+        let err = MediaLog::parse(
+            "[[entry]]\nkind = \"book\"\ntitle = \"X\"\ncreator = \"Y\"\ndate = \"2025-01-01\"\nrating = 6\n",
+        )
+        .unwrap_err();
+        assert!(err.contains("\"X\""), "{}", err);
+    }
+
+    #[test]
+    fn unknown_kind_is_rejected() {
+        assert!(MediaLog::parse(
+            "[[entry]]\nkind = \"game\"\ntitle = \"X\"\ncreator = \"Y\"\ndate = \"2025-01-01\"\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn stars_fill_up_to_rating() {
+        assert_eq!(stars(4), "★★★★☆");
+        assert_eq!(stars(9), "★★★★★");
+    }
+
+    #[test]
+    fn anchors_are_date_and_slug() {
+        assert_eq!(
+            sample_log().entries[1].anchor(),
+            "2025-01-17-the-dispossessed"
+        );
+    }
+
+    #[test]
+    fn feed_items_link_to_anchors() {
+        let items = sample_log().feed_items("https://x.test/log/");
+        assert_eq!(items[0].link, "https://x.test/log/#2025-02-03-untrue");
+        assert_eq!(items[0].title, "Album: Untrue — Burial ★★★★☆");
+        assert_eq!(items[2].title, "Film: Stalker — Andrei Tarkovsky");
+    }
+
+    #[test]
+    fn json_ld_types_follow_kind() {
+        let json: Value =
+            serde_json::from_str(&sample_log().to_json_ld("https://x.test/log/", "#me")).unwrap();
+        let items = &json["itemListElement"];
+        assert_eq!(items[0]["item"]["@type"], "MusicAlbum");
+        assert_eq!(items[0]["item"]["byArtist"]["name"], "Burial");
+        assert_eq!(items[1]["item"]["@type"], "Book");
+        assert_eq!(items[1]["item"]["review"]["reviewRating"]["ratingValue"], 5);
+        assert_eq!(items[2]["item"]["director"]["@type"], "Person");
+        assert!(items[2]["item"]["review"].get("reviewRating").is_none());
+    }
+}
@@ -0,0 +1,326 @@
+//! # Meta Tags
+//!
+//! A page's title, description, and Open Graph and Twitter Card tags as one
+//! typed value, populated with a builder and serialized once by
+//! [`MetaTags::to_html`], which escapes every value:
+//!
+//! ```text
+//! MetaTags::new("Neon | EverythingSings", "Night scenes.", url)
+//!     .og_type(OgType::Article { .. })
+//!     .image(MetaImage::new(cover).size(1200, 630).alt("A neon street"))
+//! ```
+//!
+//! The description is cut to a length search results show whole (see
+//! [`crate::text::meta_description`]) for every tag that carries it. The
+//! Twitter card is `summary_large_image` for a landscape image known to be
+//! at least [`LARGE_IMAGE_WIDTH`] wide, `summary` otherwise, unless set with
+//! [`MetaTags::card`].
+
+use crate::config::{SITE_LOCALE, SITE_NAME};
+use crate::escape::escape_html;
+use crate::text::{meta_description, nfc};
+
+/// Narrowest image shown as a large Twitter card.
+pub const LARGE_IMAGE_WIDTH: u32 = 600;
+
+/// The Open Graph type of a page, with the structured properties of that
+/// type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OgType {
+    Website,
+    Profile,
+    /// A release. `musician` is the URL of the artist's profile and
+    /// `release_date` an ISO date.
+    MusicAlbum {
+        musician: String,
+        release_date: String,
+    },
+    /// A dated post. `published_time` is ISO 8601 and `author` the URL of
+    /// the author's profile.
+    Article {
+        published_time: String,
+        author: String,
+    },
+}
+
+impl OgType {
+    /// The `og:type` value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OgType::Website => "website",
+            OgType::Profile => "profile",
+            OgType::MusicAlbum { .. } => "music.album",
+            OgType::Article { .. } => "article",
+        }
+    }
+
+    /// When the page's work was published or released, for dated types.
+    pub fn date(&self) -> Option<&str> {
+        match self {
+            OgType::Website | OgType::Profile => None,
+            OgType::MusicAlbum { release_date, .. } => Some(release_date),
+            OgType::Article { published_time, .. } => Some(published_time),
+        }
+    }
+
+    /// The type's structured properties, e.g. `music:release_date`.
+    fn properties(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            OgType::Website | OgType::Profile => Vec::new(),
+            OgType::MusicAlbum {
+                musician,
+                release_date,
+            } => vec![
+                ("music:musician", musician),
+                ("music:release_date", release_date),
+            ],
+            OgType::Article {
+                published_time,
+                author,
+            } => vec![
+                ("article:published_time", published_time),
+                ("article:author", author),
+            ],
+        }
+    }
+}
+
+/// A page's preview image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetaImage {
+    /// Absolute URL.
+    pub url: String,
+    /// Width and height in pixels, when known.
+    pub size: Option<(u32, u32)>,
+    /// Text alternative, when the image says more than the page's title.
+    pub alt: Option<String>,
+}
+
+impl MetaImage {
+    /// The image at absolute `url`, of unknown size and without alt text.
+    pub fn new(url: impl Into<String>) -> Self {
+        MetaImage {
+            url: url.into(),
+            size: None,
+            alt: None,
+        }
+    }
+
+    /// Sets the image's dimensions.
+    pub fn size(self, width: u32, height: u32) -> Self {
+        MetaImage {
+            size: Some((width, height)),
+            ..self
+        }
+    }
+
+    /// Sets the image's text alternative.
+    pub fn alt(self, alt: impl Into<String>) -> Self {
+        MetaImage {
+            alt: Some(alt.into()),
+            ..self
+        }
+    }
+
+    /// Whether the image is wide enough for a large card.
+    fn is_large(&self) -> bool {
+        self.size
+            .is_some_and(|(width, height)| width >= LARGE_IMAGE_WIDTH && width > height)
+    }
+}
+
+/// A Twitter card layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TwitterCard {
+    Summary,
+    SummaryLargeImage,
+}
+
+impl TwitterCard {
+    /// The `twitter:card` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TwitterCard::Summary => "summary",
+            TwitterCard::SummaryLargeImage => "summary_large_image",
+        }
+    }
+}
+
+/// The title, description, Open Graph, and Twitter Card tags of a page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetaTags {
+    title: String,
+    description: String,
+    url: String,
+    og_type: OgType,
+    image: Option<MetaImage>,
+    card: Option<TwitterCard>,
+    locale: &'static str,
+}
+
+impl MetaTags {
+    /// Tags of the page at absolute `url`, a website in [`SITE_LOCALE`]
+    /// without an image until set otherwise.
+    pub fn new(
+        title: impl Into<String>,
+        description: impl Into<String>,
+        url: impl Into<String>,
+    ) -> Self {
+        MetaTags {
+            title: title.into(),
+            description: description.into(),
+            url: url.into(),
+            og_type: OgType::Website,
+            image: None,
+            card: None,
+            locale: SITE_LOCALE,
+        }
+    }
+
+    /// Sets the Open Graph type.
+    pub fn og_type(self, og_type: OgType) -> Self {
+        MetaTags { og_type, ..self }
+    }
+
+    /// Sets the preview image.
+    pub fn image(self, image: MetaImage) -> Self {
+        MetaTags {
+            image: Some(image),
+            ..self
+        }
+    }
+
+    /// Sets the Twitter card, overriding the one the image implies.
+    pub fn card(self, card: TwitterCard) -> Self {
+        MetaTags {
+            card: Some(card),
+            ..self
+        }
+    }
+
+    /// Sets the `og:locale`, e.g. `en_US`.
+    pub fn locale(self, locale: &'static str) -> Self {
+        MetaTags { locale, ..self }
+    }
+
+    /// The Twitter card: as set, or the one the image implies.
+    pub fn twitter_card(&self) -> TwitterCard {
+        self.card.unwrap_or(match &self.image {
+            Some(image) if image.is_large() => TwitterCard::SummaryLargeImage,
+            _ => TwitterCard::Summary,
+        })
+    }
+
+    /// The tags, one per line, every value escaped.
+    pub fn to_html(&self) -> String {
+        let description = meta_description(&nfc(&self.description));
+        let mut lines = vec![
+            format!("<title>{}</title>", escape_html(&self.title)),
+            meta("name", "description", &description),
+            meta("property", "og:type", self.og_type.as_str()),
+        ];
+        for (property, content) in self.og_type.properties() {
+            lines.push(meta("property", property, content));
+        }
+        lines.extend([
+            meta("property", "og:site_name", SITE_NAME),
+            meta("property", "og:locale", self.locale),
+            meta("property", "og:title", &self.title),
+            meta("property", "og:description", &description),
+            meta("property", "og:url", &self.url),
+        ]);
+        if let Some(image) = &self.image {
+            lines.push(meta("property", "og:image", &image.url));
+            if let Some((width, height)) = image.size {
+                lines.push(meta("property", "og:image:width", &width.to_string()));
+                lines.push(meta("property", "og:image:height", &height.to_string()));
+            }
+            if let Some(alt) = &image.alt {
+                lines.push(meta("property", "og:image:alt", alt));
+            }
+        }
+        lines.extend([
+            meta("name", "twitter:card", self.twitter_card().as_str()),
+            meta("name", "twitter:title", &self.title),
+            meta("name", "twitter:description", &description),
+        ]);
+        if let Some(image) = &self.image {
+            lines.push(meta("name", "twitter:image", &image.url));
+            if let Some(alt) = &image.alt {
+                lines.push(meta("name", "twitter:image:alt", alt));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// A `<meta>` tag naming `key` with the `attribute` (`name` or `property`).
+fn meta(attribute: &str, key: &str, content: &str) -> String {
+    format!(
+        "<meta {}=\"{}\" content=\"{}\" />",
+        attribute,
+        key,
+        escape_html(content)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_attr, parse};
+
+    fn tags() -> MetaTags {
+        MetaTags::new("A & B", "Say \"hi\"", "https://a.test/?a=1&b=2")
+    }
+
+    #[test]
+    fn values_are_escaped_once() {
+        let html = tags().to_html();
+        assert!(html.contains("<title>A &amp; B</title>"), "{}", html);
+        assert!(html.contains(r#"content="Say &quot;hi&quot;""#), "{}", html);
+        assert!(
+            html.contains(r#"content="https://a.test/?a=1&amp;b=2""#),
+            "{}",
+            html
+        );
+        let dom = parse(&html);
+        assert_attr(&dom, "meta[property='og:title']", "content", "A & B");
+        assert_attr(&dom, "meta[property='og:locale']", "content", SITE_LOCALE);
+    }
+
+    #[test]
+    fn images_carry_size_and_alt() {
+        let html = tags()
+            .image(
+                MetaImage::new("https://a.test/c.jpg")
+                    .size(1200, 630)
+                    .alt("A cover"),
+            )
+            .to_html();
+        let dom = parse(&html);
+        assert_attr(&dom, "meta[property='og:image:width']", "content", "1200");
+        assert_attr(&dom, "meta[property='og:image:alt']", "content", "A cover");
+        assert_attr(&dom, "meta[name='twitter:image:alt']", "content", "A cover");
+        assert_attr(
+            &dom,
+            "meta[name='twitter:card']",
+            "content",
+            "summary_large_image",
+        );
+        assert!(!tags().to_html().contains("og:image"));
+    }
+
+    #[test]
+    fn cards_follow_the_image_unless_set() {
+        let square = MetaImage::new("x").size(800, 800);
+        assert_eq!(tags().image(square).twitter_card(), TwitterCard::Summary);
+        assert_eq!(
+            tags().image(MetaImage::new("x")).twitter_card(),
+            TwitterCard::Summary
+        );
+        assert_eq!(
+            tags().card(TwitterCard::SummaryLargeImage).twitter_card(),
+            TwitterCard::SummaryLargeImage
+        );
+    }
+}
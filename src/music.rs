@@ -0,0 +1,503 @@
+//! # Music Releases
+//!
+//! Reads `releases.toml`, the discography, for the `/music/` page and a
+//! page per release at `/music/<slug>/`. Each release names one link to it
+//! on any platform; the others are resolved at build time through
+//! [`crate::odesli`] and shown as a row of platform links.
+//!
+//! ## Example
+//!
+//! ```toml
+//! [[release]]
+//! title = "Neon"
+//! date = "2024-11-01"
+//! url = "https://everythingsings.bandcamp.com/album/neon"
+//! slug = "neon"          # optional, defaults to the title (see crate::slug)
+//! cover = "cover.jpg"    # optional, in public/music/<slug>/
+//! description = "Eight tracks of night-drive synthwave."  # optional
+//!
+//! [[release.tracks]]
+//! title = "Dawn"
+//! duration = "3:20"      # optional, m:ss or h:mm:ss
+//! ```
+//!
+//! The cover also becomes the release page's Open Graph image: the art on
+//! a blurred, darkened copy of itself, at [`OG_WIDTH`]×[`OG_HEIGHT`].
+
+use crate::apple_music::iso_duration;
+use crate::config::{RELEASE_PERMALINK, SITE_NAME};
+use crate::escape::json_script;
+use crate::odesli::PlatformLink;
+use crate::permalink;
+use crate::slug::{slug, Slugs};
+use crate::text::encode_path_segment;
+use crate::url::{absolute, dir_path, page_path};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, RgbImage};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Discography file, relative to the project root.
+pub const RELEASES_PATH: &str = "releases.toml";
+
+/// Output directory of the music page.
+pub const MUSIC_DIR: &str = "music";
+
+/// Description of the music page.
+pub const MUSIC_DESCRIPTION: &str = "Albums and singles, on every platform.";
+
+/// File name of a release page's generated Open Graph image.
+pub const OG_FILE: &str = "og.jpg";
+
+/// Open Graph image size in pixels.
+pub const OG_WIDTH: u32 = 1200;
+pub const OG_HEIGHT: u32 = 630;
+
+/// One track of a release.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Track {
+    pub title: String,
+    /// Length as `m:ss` or `h:mm:ss`.
+    #[serde(default)]
+    pub duration: Option<String>,
+}
+
+impl Track {
+    /// The length in milliseconds, if given and well-formed.
+    pub fn duration_ms(&self) -> Option<u64> {
+        let mut secs = 0u64;
+        let parts: Vec<&str> = self.duration.as_deref()?.split(':').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return None;
+        }
+        for (i, part) in parts.iter().enumerate() {
+            let n: u64 = part.parse().ok()?;
+            if i > 0 && (n >= 60 || part.len() != 2) {
+                return None;
+            }
+            secs = secs * 60 + n;
+        }
+        Some(secs * 1000)
+    }
+}
+
+/// One release.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Release {
+    pub title: String,
+    /// ISO release date, e.g. `2024-11-01`.
+    pub date: String,
+    /// A link to the release on any platform Odesli knows.
+    pub url: String,
+    /// URL path segment of the release page; defaults to the title.
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Cover art file in `public/music/<slug>/`.
+    #[serde(default)]
+    pub cover: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tracks: Vec<Track>,
+    /// The release on each platform, resolved at build time.
+    #[serde(skip)]
+    pub links: Vec<PlatformLink>,
+}
+
+impl Release {
+    /// The page slug: `slug` if set, else the slug of the title, e.g.
+    /// `night-drive`. [`Releases::parse`] sets it on every release, numbering
+    /// titles that slug alike.
+    pub fn slug(&self) -> String {
+        match &self.slug {
+            Some(slug) => slug.clone(),
+            None => slug(&self.title),
+        }
+    }
+
+    /// Path segments of the release page under [`RELEASE_PERMALINK`].
+    pub fn segments(&self) -> Vec<String> {
+        permalink::resolve(RELEASE_PERMALINK, &self.slug(), &self.date)
+            .expect("release permalinks are checked when parsed")
+    }
+
+    /// Site-relative URL of the release page, e.g. `/music/neon/`.
+    pub fn url_path(&self) -> String {
+        page_path(&self.segments())
+    }
+
+    /// Site-relative URL of the release page's directory, which holds its
+    /// Open Graph image and oEmbed document in either page layout.
+    pub fn dir_path(&self) -> String {
+        dir_path(&self.segments())
+    }
+
+    /// Site-relative URL of the cover art, if any. Covers stay where
+    /// `public/music/<slug>/` puts them, whatever the permalink.
+    pub fn cover_url(&self) -> Option<String> {
+        let cover = self.cover.as_ref()?;
+        Some(format!(
+            "{}{}",
+            dir_path(&[MUSIC_DIR, &self.slug()]),
+            encode_path_segment(cover)
+        ))
+    }
+
+    /// The page description: `description`, or a sentence naming the
+    /// release and its date.
+    pub fn summary(&self) -> String {
+        self.description
+            .clone()
+            .unwrap_or_else(|| format!("{} by {}, released {}.", self.title, SITE_NAME, self.date))
+    }
+
+    /// The release's platform links, or its own link alone when none were
+    /// resolved.
+    pub fn platform_urls(&self) -> Vec<&str> {
+        if self.links.is_empty() {
+            vec![self.url.as_str()]
+        } else {
+            self.links.iter().map(|l| l.url.as_str()).collect()
+        }
+    }
+
+    /// Builds the release page JSON-LD: a `MusicAlbum` by `artist_id` with
+    /// its tracklist and platform copies.
+    pub fn to_json_ld(&self, artist_id: &str) -> String {
+        let tracks: Vec<Value> = self
+            .tracks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let mut track = json!({
+                    "@type": "MusicRecording",
+                    "name": t.title,
+                    "position": i + 1,
+                    "byArtist": { "@id": artist_id },
+                });
+                if let Some(ms) = t.duration_ms() {
+                    track["duration"] = json!(iso_duration(ms));
+                }
+                track
+            })
+            .collect();
+        let mut album = json!({
+            "@context": "https://schema.org",
+            "@type": "MusicAlbum",
+            "name": self.title,
+            "url": absolute(&self.url_path()),
+            "description": self.summary(),
+            "datePublished": self.date,
+            "byArtist": { "@id": artist_id },
+            "sameAs": self.platform_urls(),
+        });
+        if let Some(cover) = self.cover_url() {
+            album["image"] = json!(absolute(&cover));
+        }
+        if !tracks.is_empty() {
+            album["numTracks"] = json!(tracks.len());
+            album["track"] = json!(tracks);
+        }
+        json_script(&album)
+    }
+}
+
+/// Renders an Open Graph image from cover art: the cover, centered and
+/// full height, over a blurred and darkened copy of itself filling
+/// [`OG_WIDTH`]×[`OG_HEIGHT`]. Returns JPEG bytes.
+pub fn og_image(cover: &[u8]) -> Result<Vec<u8>, String> {
+    let cover = image::load_from_memory(cover).map_err(|e| e.to_string())?;
+    // Shrinking to a few pixels and back is a cheap, strong blur
+    let mut canvas = cover
+        .resize_to_fill(24, 12, FilterType::Triangle)
+        .resize_exact(OG_WIDTH, OG_HEIGHT, FilterType::Triangle)
+        .brighten(-60)
+        .to_rgb8();
+    let art: RgbImage = cover
+        .resize(OG_HEIGHT, OG_HEIGHT, FilterType::Lanczos3)
+        .to_rgb8();
+    let x = (OG_WIDTH - art.width()) / 2;
+    let y = (OG_HEIGHT - art.height()) / 2;
+    image::imageops::overlay(&mut canvas, &art, x.into(), y.into());
+
+    let mut jpeg = std::io::Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(canvas)
+        .write_to(&mut jpeg, ImageFormat::Jpeg)
+        .map_err(|e| e.to_string())?;
+    Ok(jpeg.into_inner())
+}
+
+/// The discography, newest release first.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Releases {
+    #[serde(default, rename = "release")]
+    pub releases: Vec<Release>,
+}
+
+impl Releases {
+    /// Loads the discography from `path`; `Ok(None)` when the file is
+    /// absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let releases = Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Some(releases))
+    }
+
+    /// Parses and validates discography TOML. Release links must be
+    /// `https` URLs, slugs set by hand non-empty and unique, permalinks
+    /// resolvable and unique, and track durations well-formed. Releases
+    /// without a slug get their title's, numbered in file order when taken.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut releases: Releases =
+            toml::from_str(content).map_err(|e| format!("Could not parse: {}", e))?;
+        let mut slugs = Slugs::default();
+        for release in &releases.releases {
+            if let Some(slug) = &release.slug {
+                if slug.is_empty() || slug.contains('/') || !slugs.claim(slug) {
+                    return Err(format!(
+                        "Slug \"{}\" of \"{}\" is empty, has a slash, or is taken",
+                        slug, release.title
+                    ));
+                }
+            }
+        }
+        for release in &mut releases.releases {
+            if release.slug.is_none() {
+                release.slug = Some(slugs.unique(&release.title));
+            }
+        }
+        for release in &releases.releases {
+            if !release.url.starts_with("https://") {
+                return Err(format!(
+                    "Link for \"{}\" must be an https URL",
+                    release.title
+                ));
+            }
+            permalink::resolve(RELEASE_PERMALINK, &release.slug(), &release.date)
+                .map_err(|e| format!("Permalink of \"{}\": {}", release.title, e))?;
+            if let Some(track) = release
+                .tracks
+                .iter()
+                .find(|t| t.duration.is_some() && t.duration_ms().is_none())
+            {
+                return Err(format!(
+                    "Duration of \"{}\" must be m:ss or h:mm:ss",
+                    track.title
+                ));
+            }
+        }
+        permalink::check_unique(
+            releases
+                .releases
+                .iter()
+                .map(|r| (r.url_path(), r.title.as_str())),
+        )?;
+        releases.releases.sort_by(|a, b| b.date.cmp(&a.date));
+        Ok(releases)
+    }
+
+    /// Builds the `/music/` JSON-LD: an `ItemList` of `MusicAlbum`s by
+    /// `artist_id`, each the same as its platform copies.
+    pub fn to_json_ld(&self, url: &str, artist_id: &str) -> String {
+        let items: Vec<Value> = self
+            .releases
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                json!({
+                    "@type": "ListItem",
+                    "position": i + 1,
+                    "item": {
+                        "@type": "MusicAlbum",
+                        "name": r.title,
+                        "url": absolute(&r.url_path()),
+                        "datePublished": r.date,
+                        "byArtist": { "@id": artist_id },
+                        "sameAs": r.platform_urls(),
+                    },
+                })
+            })
+            .collect();
+        json_script(&json!({
+            "@context": "https://schema.org",
+            "@type": "ItemList",
+            "name": "Music",
+            "url": url,
+            "numberOfItems": items.len(),
+            "itemListElement": items,
+        }))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::config::SITE_URL;
+
+    const TOML: &str = r#"
+[[release]]
+title = "Neon"
+date = "2024-11-01"
+url = "https://es.bandcamp.com/album/neon"
+
+[[release]]
+title = "Dawn"
+date = "2025-03-07"
+url = "https://music.apple.com/us/album/dawn/2"
+cover = "cover.jpg"
+
+[[release.tracks]]
+title = "First Light"
+duration = "3:20"
+
+[[release.tracks]]
+title = "Noon"
+"#;
+
+    pub fn sample_releases() -> Releases {
+        let mut releases = Releases::parse(TOML).unwrap();
+        releases.releases[0].links = vec![
+            PlatformLink {
+                platform: "Apple Music",
+                url: "https://music.apple.com/us/album/dawn/2".to_string(),
+            },
+            PlatformLink {
+                platform: "Spotify",
+                url: "https://open.spotify.com/album/d".to_string(),
+            },
+        ];
+        releases
+    }
+
+    #[test]
+    fn releases_are_newest_first() {
+        let titles: Vec<String> = Releases::parse(TOML)
+            .unwrap()
+            .releases
+            .into_iter()
+            .map(|r| r.title)
+            .collect();
+        assert_eq!(titles, ["Dawn", "Neon"]);
+    }
+
+    #[test]
+    fn non_https_links_are_rejected() {
+        let err = Releases::parse(
+            "[[release]]\ntitle = \"X\"\ndate = \"2025-01-01\"\nurl = \"http://a.test\"",
+        )
+        .unwrap_err();
+        assert!(err.contains("\"X\""));
+    }
+
+    #[test]
+    fn slugs_default_to_the_title() {
+        let release = |title: &str, slug: Option<&str>| Release {
+            title: title.to_string(),
+            date: String::new(),
+            url: String::new(),
+            slug: slug.map(str::to_string),
+            cover: None,
+            description: None,
+            tracks: Vec::new(),
+            links: Vec::new(),
+        };
+        assert_eq!(
+            release("Night Drive (Live!)", None).slug(),
+            "night-drive-live"
+        );
+        assert_eq!(release("Café", None).url_path(), "/music/cafe/");
+        assert_eq!(
+            release("Neon", Some("neon-lp")).url_path(),
+            "/music/neon-lp/"
+        );
+    }
+
+    #[test]
+    fn duplicate_slugs_are_rejected() {
+        let toml = "[[release]]\ntitle = \"Neon\"\ndate = \"2024-01-01\"\nurl = \"https://a.test/1\"\nslug = \"neon\"\n\
+                    [[release]]\ntitle = \"Neon II\"\ndate = \"2025-01-01\"\nurl = \"https://a.test/2\"\nslug = \"neon\"";
+        assert!(Releases::parse(toml).unwrap_err().contains("\"neon\""));
+    }
+
+    #[test]
+    fn titles_that_slug_alike_are_numbered() {
+        let toml = "[[release]]\ntitle = \"Neon\"\ndate = \"2024-01-01\"\nurl = \"https://a.test/1\"\n\
+                    [[release]]\ntitle = \"NEON\"\ndate = \"2025-01-01\"\nurl = \"https://a.test/2\"\n\
+                    [[release]]\ntitle = \"Neon 2\"\ndate = \"2023-01-01\"\nurl = \"https://a.test/3\"\nslug = \"neon-2\"";
+        let slugs: Vec<String> = Releases::parse(toml)
+            .unwrap()
+            .releases
+            .iter()
+            .map(Release::slug)
+            .collect();
+        assert_eq!(slugs, ["neon-3", "neon", "neon-2"]);
+    }
+
+    #[test]
+    fn durations_parse_as_minutes_and_seconds() {
+        let track = |d: &str| Track {
+            title: String::new(),
+            duration: Some(d.to_string()),
+        };
+        assert_eq!(track("3:20").duration_ms(), Some(200_000));
+        assert_eq!(track("1:02:05").duration_ms(), Some(3_725_000));
+        assert_eq!(track("3:2").duration_ms(), None);
+        assert_eq!(track("3:75").duration_ms(), None);
+        assert_eq!(track("200").duration_ms(), None);
+        let bad = TOML.replace("\"3:20\"", "\"soon\"");
+        assert!(Releases::parse(&bad).unwrap_err().contains("First Light"));
+    }
+
+    #[test]
+    fn release_json_ld_has_tracklist_and_cover() {
+        let release = &sample_releases().releases[0];
+        let json: Value =
+            serde_json::from_str(&release.to_json_ld("https://a.test/#person")).unwrap();
+        assert_eq!(json["@type"], "MusicAlbum");
+        assert_eq!(json["url"], format!("{}/music/dawn/", SITE_URL));
+        assert_eq!(json["image"], format!("{}/music/dawn/cover.jpg", SITE_URL));
+        assert_eq!(json["numTracks"], 2);
+        assert_eq!(json["track"][0]["duration"], "PT3M20S");
+        assert!(json["track"][1].get("duration").is_none());
+        assert_eq!(json["sameAs"][1], "https://open.spotify.com/album/d");
+    }
+
+    #[test]
+    fn og_image_is_1200_by_630() {
+        let mut png = std::io::Cursor::new(Vec::new());
+        RgbImage::from_pixel(300, 300, image::Rgb([200, 40, 90]))
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        let jpeg = og_image(png.get_ref()).unwrap();
+        let og = image::load_from_memory_with_format(&jpeg, ImageFormat::Jpeg).unwrap();
+        assert_eq!((og.width(), og.height()), (OG_WIDTH, OG_HEIGHT));
+        assert!(og_image(b"not an image").is_err());
+    }
+
+    #[test]
+    fn json_ld_lists_albums_with_platform_copies() {
+        let json: Value = serde_json::from_str(
+            &sample_releases().to_json_ld("https://a.test/music/", "https://a.test/#person"),
+        )
+        .unwrap();
+        let items = &json["itemListElement"];
+        assert_eq!(items[0]["item"]["name"], "Dawn");
+        assert_eq!(
+            items[0]["item"]["sameAs"][1],
+            "https://open.spotify.com/album/d"
+        );
+        assert_eq!(
+            items[1]["item"]["sameAs"],
+            json!(["https://es.bandcamp.com/album/neon"])
+        );
+        assert_eq!(
+            items[1]["item"]["byArtist"]["@id"],
+            "https://a.test/#person"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! # Nostr Reactions
+//!
+//! A series syndicated to Nostr names its note in `series.toml`:
+//!
+//! ```toml
+//! nostr_note = "note1…"
+//! ```
+//!
+//! Relays speak WebSocket, which a static build has no use for, so counts
+//! come from the [nostr.band](https://nostr.band) HTTP stats API (through
+//! [`crate::cache`]). The counter links to the note on
+//! [njump](https://njump.me), which renders it without a Nostr client.
+
+use crate::cache::cached_fetch;
+use crate::reactions::{count_at, Count, Reactions};
+use serde_json::Value;
+use std::time::Duration;
+
+/// How long cached stats are used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = (chk & 0x1ffffff) << 5 ^ v as u32;
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// Decodes a bech32 string with human-readable part `hrp` (BIP 173) into
+/// its payload bytes; `None` if malformed or the checksum fails.
+pub fn bech32_decode(hrp: &str, s: &str) -> Option<Vec<u8>> {
+    let s = s.to_ascii_lowercase();
+    let data = s.strip_prefix(hrp)?.strip_prefix('1')?;
+    let values: Vec<u8> = data
+        .bytes()
+        .map(|b| BECH32_CHARSET.iter().position(|&c| c == b).map(|p| p as u8))
+        .collect::<Option<_>>()?;
+    if values.len() < 6 {
+        return None;
+    }
+    let mut check: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    check.push(0);
+    check.extend(hrp.bytes().map(|b| b & 31));
+    check.extend(&values);
+    if bech32_polymod(&check) != 1 {
+        return None;
+    }
+
+    // Regroup the 5-bit payload into bytes
+    let mut bytes = Vec::new();
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for &v in &values[..values.len() - 6] {
+        acc = acc << 5 | v as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    (bits < 5 && acc == 0).then_some(bytes)
+}
+
+/// The hex event id of a `note1…` id.
+pub fn note_event_id(note: &str) -> Option<String> {
+    let bytes = bech32_decode("note", note).filter(|b| b.len() == 32)?;
+    Some(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Parses a nostr.band `/v0/stats/event/:id` response for `event_id`.
+pub fn parse_stats(json: &str, event_id: &str, url: &str) -> Option<Reactions> {
+    let doc: Value = serde_json::from_str(json).ok()?;
+    let stats = doc["stats"].get(event_id)?;
+    let counts = [
+        ("reactions", "LikeAction", count_at(stats, "reaction_count")),
+        ("reposts", "ShareAction", count_at(stats, "repost_count")),
+        ("zaps", "DonateAction", count_at(&stats["zaps"], "count")),
+    ]
+    .into_iter()
+    .filter_map(|(label, action, value)| {
+        Some(Count {
+            label,
+            action,
+            value: value?,
+        })
+    })
+    .collect();
+    Some(Reactions {
+        network: "Nostr",
+        url: url.to_string(),
+        counts,
+    })
+}
+
+/// Loads the reaction counts of `note`, fetching them if the cache is
+/// stale. `None` when the id is invalid or the stats are unavailable.
+pub fn load_reactions(note: &str) -> Option<Reactions> {
+    let Some(event_id) = note_event_id(note) else {
+        eprintln!("Warning: Invalid Nostr note id {}", note);
+        return None;
+    };
+    let json = cached_fetch(
+        &format!("nostr-{}.json", event_id),
+        &format!("https://api.nostr.band/v0/stats/event/{}", event_id),
+        MAX_AGE,
+    )?;
+    parse_stats(&json, &event_id, &format!("https://njump.me/{}", note))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // note1 encoding of the event id 00 01 02 … 1f
+    const NOTE: &str = "note1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0s675dzg";
+    const EVENT_ID: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn note_ids_decode_to_event_ids() {
+        assert_eq!(note_event_id(NOTE).as_deref(), Some(EVENT_ID));
+        assert_eq!(
+            note_event_id(&NOTE.to_ascii_uppercase()).as_deref(),
+            Some(EVENT_ID)
+        );
+    }
+
+    #[test]
+    fn corrupted_note_ids_are_rejected() {
+        let mut corrupted = NOTE.to_string();
+        corrupted.replace_range(10..11, "z");
+        assert_eq!(note_event_id(&corrupted), None);
+        assert_eq!(note_event_id("npub1qqqsyqcyq5rqwzqf"), None);
+        assert_eq!(note_event_id("note1"), None);
+    }
+
+    #[test]
+    fn stats_become_reactions() {
+        let json = format!(
+            r#"{{"stats": {{"{}": {{"reply_count": 1, "repost_count": 2,
+                "reaction_count": 5, "zaps": {{"count": 3, "msats": 21000}}}}}}}}"#,
+            EVENT_ID
+        );
+        let reactions = parse_stats(&json, EVENT_ID, "https://njump.me/note1x").unwrap();
+        let counts: Vec<(&str, u64)> = reactions
+            .counts
+            .iter()
+            .map(|c| (c.label, c.value))
+            .collect();
+        assert_eq!(counts, [("reactions", 5), ("reposts", 2), ("zaps", 3)]);
+        assert_eq!(parse_stats(r#"{"stats": {}}"#, EVENT_ID, ""), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! # Streaming Platform Links
+//!
+//! Resolves one link to a release into links on every platform carrying
+//! it, using the [Odesli](https://odesli.co) (song.link) API at build
+//! time. Responses are cached through [`crate::cache`], so the platform
+//! row of a release page is static and survives the API being down.
+
+use crate::cache::{cached_fetch, url_key};
+use crate::text::encode_path_segment;
+use serde_json::Value;
+use std::time::Duration;
+
+/// How long a cached resolution is used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Platforms shown on a release, in display order: Odesli key and name.
+pub const PLATFORMS: &[(&str, &str)] = &[
+    ("bandcamp", "Bandcamp"),
+    ("appleMusic", "Apple Music"),
+    ("spotify", "Spotify"),
+    ("youtubeMusic", "YouTube Music"),
+    ("tidal", "Tidal"),
+    ("deezer", "Deezer"),
+    ("amazonMusic", "Amazon Music"),
+    ("soundcloud", "SoundCloud"),
+];
+
+/// A release on one streaming or store platform.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlatformLink {
+    /// Display name, e.g. `Spotify`.
+    pub platform: &'static str,
+    pub url: String,
+}
+
+/// The Odesli API URL resolving `url`.
+pub fn api_url(url: &str) -> String {
+    format!(
+        "https://api.song.link/v1-alpha.1/links?url={}",
+        encode_path_segment(url)
+    )
+}
+
+/// Parses an Odesli response into links for the known [`PLATFORMS`], in
+/// display order. Only `https` links are kept.
+pub fn parse_links(json: &str) -> Option<Vec<PlatformLink>> {
+    let doc: Value = serde_json::from_str(json).ok()?;
+    let by_platform = doc["linksByPlatform"].as_object()?;
+    Some(
+        PLATFORMS
+            .iter()
+            .filter_map(|(key, name)| {
+                let url = by_platform.get(*key)?["url"].as_str()?;
+                url.starts_with("https://").then(|| PlatformLink {
+                    platform: name,
+                    url: url.to_string(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Loads the platform links of the release at `url`, fetching them if the
+/// cache is stale. Empty when the release cannot be resolved.
+pub fn load_links(url: &str) -> Vec<PlatformLink> {
+    let json = cached_fetch(
+        &format!("odesli-{}.json", url_key(url)),
+        &api_url(url),
+        MAX_AGE,
+    );
+    json.as_deref().and_then(parse_links).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_url_encodes_the_release_url() {
+        assert_eq!(
+            api_url("https://es.bandcamp.com/album/neon?x=1"),
+            "https://api.song.link/v1-alpha.1/links?url=https%3A%2F%2Fes.bandcamp.com%2Falbum%2Fneon%3Fx%3D1"
+        );
+    }
+
+    #[test]
+    fn links_follow_display_order() {
+        let json = r#"{"pageUrl": "https://album.link/i/1",
+            "linksByPlatform": {
+                "spotify": {"url": "https://open.spotify.com/album/x", "entityUniqueId": "S"},
+                "appleMusic": {"url": "https://music.apple.com/us/album/neon/1"},
+                "napster": {"url": "https://play.napster.com/album/x"},
+                "tidal": {"url": "javascript:alert(1)"}
+            }}"#;
+        let links = parse_links(json).unwrap();
+        let platforms: Vec<&str> = links.iter().map(|l| l.platform).collect();
+        assert_eq!(platforms, ["Apple Music", "Spotify"]);
+        assert_eq!(links[1].url, "https://open.spotify.com/album/x");
+        assert_eq!(parse_links(r#"{"statusCode": 404}"#), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! # oEmbed
+//!
+//! [oEmbed](https://oembed.com/) documents describe a page to sites that
+//! unfurl pasted URLs, so a link to the site shows its title, author, and
+//! picture instead of a bare URL. The site is static, so instead of an
+//! endpoint taking `?url=` each page has its own document next to it:
+//!
+//! - `/oembed.json`: the homepage
+//! - `/art/<slug>/oembed.json`: each art series
+//! - `/music/<slug>/oembed.json`: each release
+//!
+//! Pages point to theirs with a discovery link in the head (see
+//! [`oembed_alternate`]):
+//!
+//! ```html
+//! <link rel="alternate" type="application/json+oembed" href="https://everythingsings.art/oembed.json" />
+//! ```
+//!
+//! Documents are of type `link`, with the page's image as the thumbnail
+//! when its size is known (the spec requires width and height with it).
+
+use crate::alternates::Alternate;
+use crate::art::ArtSeries;
+use crate::config::SITE_NAME;
+use crate::entity::primary_entity;
+use crate::music::{Release, OG_HEIGHT, OG_WIDTH};
+use crate::url::absolute;
+use serde::Serialize;
+
+/// File name of a page's oEmbed document, in the page's directory.
+pub const OEMBED_FILE: &str = "oembed.json";
+
+/// MIME type of oEmbed JSON documents, for discovery links.
+pub const OEMBED_TYPE: &str = "application/json+oembed";
+
+/// How long consumers may cache a document, in seconds.
+const CACHE_AGE: u32 = 86_400;
+
+/// A preview image of a page.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Thumbnail {
+    #[serde(rename = "thumbnail_url")]
+    pub url: String,
+    #[serde(rename = "thumbnail_width")]
+    pub width: u32,
+    #[serde(rename = "thumbnail_height")]
+    pub height: u32,
+}
+
+/// An oEmbed response of type `link`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OEmbed {
+    pub version: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub title: String,
+    pub author_name: &'static str,
+    pub author_url: String,
+    pub provider_name: &'static str,
+    pub provider_url: String,
+    pub cache_age: u32,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<Thumbnail>,
+}
+
+impl OEmbed {
+    /// A document for a page titled `title` by the primary entity.
+    pub fn new(title: &str, thumbnail: Option<Thumbnail>) -> Self {
+        OEmbed {
+            version: "1.0",
+            kind: "link",
+            title: title.to_string(),
+            author_name: primary_entity().name,
+            author_url: absolute("/"),
+            provider_name: SITE_NAME,
+            provider_url: absolute("/"),
+            cache_age: CACHE_AGE,
+            thumbnail,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("oEmbed document serializes")
+    }
+}
+
+/// Site-relative URL of the oEmbed document in the page directory `dir`
+/// (see [`crate::url::dir_path`]), e.g. `/art/neon-dreams/oembed.json` for
+/// `/art/neon-dreams/`.
+pub fn oembed_path(dir: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), OEMBED_FILE)
+}
+
+/// Absolute URL of the oEmbed document in the page directory `dir`.
+pub fn oembed_url(dir: &str) -> String {
+    absolute(&oembed_path(dir))
+}
+
+/// The discovery link of the oEmbed document in the page directory `dir`,
+/// titled `title`.
+pub fn oembed_alternate(title: &str, dir: &str) -> Alternate {
+    Alternate::new(OEMBED_TYPE, title, oembed_url(dir))
+}
+
+/// The homepage's document.
+pub fn site_oembed() -> OEmbed {
+    OEmbed::new(primary_entity().name, None)
+}
+
+/// An art series' document, with its cover as the thumbnail when the cover
+/// could be decoded.
+pub fn series_oembed(series: &ArtSeries) -> OEmbed {
+    let thumbnail = series.cover_placeholder.as_ref().map(|p| Thumbnail {
+        url: absolute(&series.cover_url),
+        width: p.width,
+        height: p.height,
+    });
+    OEmbed::new(&series.title, thumbnail)
+}
+
+/// A release's document. `og_image` is the site-relative path of its Open
+/// Graph image, if one was generated.
+pub fn release_oembed(release: &Release, og_image: Option<&str>) -> OEmbed {
+    let thumbnail = og_image.map(|path| Thumbnail {
+        url: absolute(path),
+        width: OG_WIDTH,
+        height: OG_HEIGHT,
+    });
+    OEmbed::new(&release.title, thumbnail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SITE_URL;
+    use serde_json::Value;
+
+    #[test]
+    fn documents_sit_next_to_their_page() {
+        assert_eq!(oembed_path("/"), "/oembed.json");
+        assert_eq!(oembed_path("/art/neon/"), "/art/neon/oembed.json");
+        assert_eq!(oembed_url("/"), format!("{}/oembed.json", SITE_URL));
+    }
+
+    #[test]
+    fn documents_have_the_required_fields() {
+        let doc: Value = serde_json::from_str(&site_oembed().to_json()).unwrap();
+        assert_eq!(doc["version"], "1.0");
+        assert_eq!(doc["type"], "link");
+        assert_eq!(doc["author_name"], primary_entity().name);
+        assert_eq!(doc["provider_name"], SITE_NAME);
+        assert!(doc.get("thumbnail_url").is_none());
+    }
+
+    #[test]
+    fn thumbnails_carry_their_size() {
+        let doc = OEmbed::new(
+            "Night Drive",
+            Some(Thumbnail {
+                url: "https://a.test/og.jpg".to_string(),
+                width: OG_WIDTH,
+                height: OG_HEIGHT,
+            }),
+        );
+        let doc: Value = serde_json::from_str(&doc.to_json()).unwrap();
+        assert_eq!(doc["title"], "Night Drive");
+        assert_eq!(doc["thumbnail_url"], "https://a.test/og.jpg");
+        assert_eq!(doc["thumbnail_width"], OG_WIDTH);
+        assert_eq!(doc["thumbnail_height"], OG_HEIGHT);
+    }
+}
@@ -0,0 +1,168 @@
+//! # Pagination
+//!
+//! A listing split over several pages keeps its first page at the listing's
+//! URL and puts the rest under `page/<n>`, in the configured
+//! [page layout](crate::url#page-layout):
+//!
+//! ```text
+//! /log/          page 1
+//! /log/page/2/   page 2
+//! /log/page/3/   page 3
+//! ```
+//!
+//! Each page is its own canonical URL (canonicalizing every page to the
+//! first would hide the later pages' items from search engines), and the
+//! head links neighbours with `rel="prev"` and `rel="next"` (see
+//! [`PageMeta::pagination`](crate::components::PageMeta)).
+
+use crate::escape::escape_html;
+use crate::url::{absolute, page_path};
+
+/// Where a page sits in a paginated listing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    /// Path segments of the listing's first page, e.g. `["log"]`.
+    pub base: Vec<String>,
+    /// `1` to `pages`.
+    pub page: usize,
+    pub pages: usize,
+}
+
+impl Pagination {
+    /// Page `page` of `pages` of the listing at `base`.
+    ///
+    /// # Panics
+    ///
+    /// If `page` is not between `1` and `pages`.
+    pub fn new<S: AsRef<str>>(base: &[S], page: usize, pages: usize) -> Self {
+        assert!(
+            (1..=pages).contains(&page),
+            "page {} out of 1..={}",
+            page,
+            pages
+        );
+        Pagination {
+            base: base.iter().map(|s| s.as_ref().to_string()).collect(),
+            page,
+            pages,
+        }
+    }
+
+    /// Path segments of page `page`: the listing's, then `["page", "<n>"]`
+    /// after the first.
+    pub fn page_segments(&self, page: usize) -> Vec<String> {
+        let mut segments = self.base.clone();
+        if page > 1 {
+            segments.extend(["page".to_string(), page.to_string()]);
+        }
+        segments
+    }
+
+    /// Path segments of this page.
+    pub fn segments(&self) -> Vec<String> {
+        self.page_segments(self.page)
+    }
+
+    /// Site-relative URL of this page.
+    pub fn path(&self) -> String {
+        page_path(&self.segments())
+    }
+
+    /// Site-relative URL of the previous page, unless this is the first.
+    pub fn prev_path(&self) -> Option<String> {
+        (self.page > 1).then(|| page_path(&self.page_segments(self.page - 1)))
+    }
+
+    /// Site-relative URL of the next page, unless this is the last.
+    pub fn next_path(&self) -> Option<String> {
+        (self.page < self.pages).then(|| page_path(&self.page_segments(self.page + 1)))
+    }
+
+    /// The `rel="prev"` and `rel="next"` links of this page, each on its
+    /// own line after a newline.
+    pub fn head_links(&self) -> String {
+        [("prev", self.prev_path()), ("next", self.next_path())]
+            .into_iter()
+            .filter_map(|(rel, path)| {
+                path.map(|path| {
+                    format!(
+                        "\n<link rel=\"{}\" href=\"{}\" />",
+                        rel,
+                        escape_html(&absolute(&path))
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Splits `items` into pages of at most `per_page`. An empty listing still
+/// has one (empty) page.
+pub fn paginate<T>(items: &[T], per_page: usize) -> Vec<&[T]> {
+    if items.is_empty() {
+        return vec![items];
+    }
+    items.chunks(per_page.max(1)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::SITE_URL;
+    use crate::url::Layout;
+
+    const BASE: &[&str] = &["log"];
+
+    #[test]
+    fn first_page_is_the_listing() {
+        let p = Pagination::new(BASE, 1, 3);
+        assert_eq!(p.segments(), ["log"]);
+        assert_eq!(p.path(), page_path(BASE));
+        assert_eq!(p.prev_path(), None);
+        assert_eq!(p.next_path(), Some(page_path(&["log", "page", "2"])));
+    }
+
+    #[test]
+    fn middle_pages_link_both_ways() {
+        let p = Pagination::new(BASE, 2, 3);
+        assert_eq!(p.segments(), ["log", "page", "2"]);
+        assert_eq!(p.prev_path(), Some(page_path(BASE)));
+        assert_eq!(p.next_path(), Some(page_path(&["log", "page", "3"])));
+    }
+
+    #[test]
+    fn last_page_has_no_next() {
+        let p = Pagination::new(BASE, 3, 3);
+        assert_eq!(p.next_path(), None);
+        assert_eq!(
+            p.head_links(),
+            format!(
+                "\n<link rel=\"prev\" href=\"{}{}\" />",
+                SITE_URL,
+                page_path(&["log", "page", "2"])
+            )
+        );
+        assert_eq!(Pagination::new(BASE, 1, 1).head_links(), "");
+    }
+
+    #[test]
+    fn pages_follow_the_layout() {
+        let segments = Pagination::new(BASE, 2, 3).segments();
+        assert_eq!(Layout::Directory.page_path(&segments), "/log/page/2/");
+        assert_eq!(Layout::File.page_path(&segments), "/log/page/2.html");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of")]
+    fn pages_past_the_end_are_rejected() {
+        Pagination::new(BASE, 4, 3);
+    }
+
+    #[test]
+    fn items_split_into_pages() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(paginate(&items, 2), [&[1, 2][..], &[3, 4], &[5]]);
+        assert_eq!(paginate::<u8>(&[], 2).len(), 1);
+    }
+}
@@ -0,0 +1,115 @@
+//! # Permalinks
+//!
+//! Where a content type's pages live is a template in the config, not code:
+//!
+//! ```text
+//! /art/:slug/            /art/neon-dreams/
+//! /music/:year/:slug/    /music/2024/night-drive/
+//! ```
+//!
+//! See [`ART_PERMALINK`](crate::config::ART_PERMALINK) and
+//! [`RELEASE_PERMALINK`](crate::config::RELEASE_PERMALINK).
+//!
+//! A template is a `/`-separated path whose segments may contain the
+//! placeholders `:slug`, `:year`, `:month`, and `:day` (the last three from
+//! the item's ISO date). Templates resolve to path segments, which the page
+//! layout turns into a URL and an output file (see [`crate::url`]), so the
+//! trailing slash in a template is only for readability.
+//!
+//! Templates are resolved when content is loaded, so a template needing a
+//! date an item lacks fails the build with the item named; and since two
+//! items can resolve to the same page, the build fails unless every page
+//! path is unique (see [`check_unique`]).
+
+use crate::datetime::Date;
+use std::collections::BTreeMap;
+
+/// Resolves `template` for the item with `slug` and ISO `date` into its
+/// page's path segments, e.g. `["art", "neon-dreams"]`.
+pub fn resolve(template: &str, slug: &str, date: &str) -> Result<Vec<String>, String> {
+    let parsed = Date::parse(date);
+    let field = |name: &str| -> Result<String, String> {
+        let date = || parsed.ok_or_else(|| format!("`:{}` needs a date, got {:?}", name, date));
+        match name {
+            "slug" => Ok(slug.to_string()),
+            "year" => Ok(format!("{:04}", date()?.year)),
+            "month" => Ok(format!("{:02}", date()?.month)),
+            "day" => Ok(format!("{:02}", date()?.day)),
+            _ => Err(format!("Unknown placeholder `:{}` in {:?}", name, template)),
+        }
+    };
+    let mut segments = Vec::new();
+    for part in template.split('/').filter(|p| !p.is_empty()) {
+        let mut segment = String::new();
+        let mut rest = part;
+        while let Some(start) = rest.find(':') {
+            segment.push_str(&rest[..start]);
+            let name = &rest[start + 1..];
+            let end = name
+                .find(|c: char| !c.is_ascii_lowercase())
+                .unwrap_or(name.len());
+            segment.push_str(&field(&name[..end])?);
+            rest = &name[end..];
+        }
+        segment.push_str(rest);
+        segments.push(segment);
+    }
+    if segments.is_empty() {
+        return Err(format!("Permalink {:?} resolves to the homepage", template));
+    }
+    Ok(segments)
+}
+
+/// Checks no two pages share a path. `pages` are `(path, owner)` pairs,
+/// the owner naming the page in the error.
+pub fn check_unique<'a>(pages: impl IntoIterator<Item = (String, &'a str)>) -> Result<(), String> {
+    let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+    for (path, owner) in pages {
+        if let Some(first) = seen.insert(path.clone(), owner) {
+            return Err(format!("{} and {} both resolve to {}", first, owner, path));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ART_PERMALINK, RELEASE_PERMALINK};
+
+    #[test]
+    fn templates_resolve_placeholders() {
+        assert_eq!(resolve("/art/:slug/", "neon", "").unwrap(), ["art", "neon"]);
+        assert_eq!(
+            resolve("/posts/:year/:month-:day-:slug/", "dune", "2025-01-07").unwrap(),
+            ["posts", "2025", "01-07-dune"]
+        );
+    }
+
+    #[test]
+    fn configured_templates_resolve() {
+        for template in [ART_PERMALINK, RELEASE_PERMALINK] {
+            assert!(resolve(template, "a", "2025-01-07").is_ok(), "{}", template);
+        }
+    }
+
+    #[test]
+    fn bad_templates_and_dates_are_errors() {
+        assert!(resolve("/x/:slug/", "a", "").is_ok());
+        let err = resolve("/x/:year/:slug/", "a", "someday").unwrap_err();
+        assert!(err.contains(":year"), "{}", err);
+        assert!(resolve("/x/:author/", "a", "")
+            .unwrap_err()
+            .contains("Unknown"));
+        assert!(resolve("/", "a", "").is_err());
+    }
+
+    #[test]
+    fn collisions_name_both_pages() {
+        assert!(check_unique([("/a/".to_string(), "A"), ("/b/".to_string(), "B")]).is_ok());
+        assert_eq!(
+            check_unique([("/a/".to_string(), "A"), ("/a/".to_string(), "B")]),
+            Err("A and B both resolve to /a/".to_string())
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! # Image Placeholders
+//!
+//! Low-quality image placeholders (LQIP) for artwork: each image is shrunk to
+//! a few pixels wide, blurred, and inlined as a PNG data URI background on
+//! its `<img>`. The browser paints the blurred colours immediately and the
+//! lazily loaded image covers them, so galleries never show empty boxes. No
+//! script is involved.
+
+use crate::text::base64;
+use image::imageops::FilterType;
+use image::ImageFormat;
+use std::path::Path;
+
+/// Placeholder width in pixels; the height keeps the aspect ratio.
+pub const PLACEHOLDER_WIDTH: u32 = 16;
+
+/// Blur radius applied to the shrunk image.
+const BLUR_SIGMA: f32 = 1.0;
+
+/// A blurred inline preview of an image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placeholder {
+    /// `data:image/png;base64,…` URI of the blurred preview.
+    pub data_uri: String,
+    /// Dimensions of the full image, for `width`/`height` attributes.
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Placeholder {
+    /// Inline `style` showing the preview behind the image.
+    pub fn style(&self) -> String {
+        format!(
+            "background-image: url({}); background-size: cover",
+            self.data_uri
+        )
+    }
+}
+
+/// Builds the placeholder for an encoded image.
+pub fn from_bytes(data: &[u8]) -> Option<Placeholder> {
+    let original = image::load_from_memory(data).ok()?;
+    let (width, height) = (original.width(), original.height());
+    let small_height =
+        ((height as u64 * PLACEHOLDER_WIDTH as u64) / width.max(1) as u64).max(1) as u32;
+    let preview = original
+        .resize_exact(PLACEHOLDER_WIDTH, small_height, FilterType::Triangle)
+        .blur(BLUR_SIGMA)
+        .to_rgb8();
+    let mut png = std::io::Cursor::new(Vec::new());
+    preview.write_to(&mut png, ImageFormat::Png).ok()?;
+    Some(Placeholder {
+        data_uri: format!("data:image/png;base64,{}", base64(png.get_ref())),
+        width,
+        height,
+    })
+}
+
+/// Builds the placeholder for the image file at `path`; `None` when it is
+/// missing or not a decodable raster image.
+pub fn from_file(path: &Path) -> Option<Placeholder> {
+    from_bytes(&std::fs::read(path).ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholder_is_a_small_png_with_full_dimensions() {
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::RgbImage::from_pixel(640, 480, image::Rgb([200, 40, 90]))
+            .write_to(&mut png, ImageFormat::Png)
+            .unwrap();
+        let placeholder = from_bytes(png.get_ref()).unwrap();
+        assert_eq!((placeholder.width, placeholder.height), (640, 480));
+        assert!(placeholder.data_uri.starts_with("data:image/png;base64,"));
+        assert!(
+            placeholder.data_uri.len() < 1024,
+            "Placeholder should stay small"
+        );
+    }
+
+    #[test]
+    fn undecodable_images_have_no_placeholder() {
+        assert_eq!(from_bytes(b"not an image"), None);
+        assert_eq!(from_file(Path::new("missing.png")), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! # Plan File
+//!
+//! `/plan.txt`: the profile as a finger-style plain-text `.plan`, for
+//! terminal users (`curl https://everythingsings.art/plan.txt`) and for
+//! finger or Gopher mirrors to serve as is. Generated from the
+//! [primary entity](crate::entity::primary_entity) and the homepage links,
+//! wrapped at [`WIDTH`] columns.
+
+use crate::components::{rendered_links, LinkGroup};
+use crate::entity::Entity;
+use crate::text::wrap;
+
+/// Output file of the plan, relative to the site root.
+pub const PLAN_PATH: &str = "plan.txt";
+
+/// Line width of the plan, in characters.
+pub const WIDTH: usize = 72;
+
+/// Generates `plan.txt` for `entity`.
+pub fn generate_plan(entity: &Entity) -> String {
+    let mut out = vec![
+        format!("Login: {:<32}Name: {}", entity.key, entity.name),
+        format!("Directory: {}", entity.url),
+        String::new(),
+        "Plan:".to_string(),
+    ];
+    out.extend(wrap(entity.description, WIDTH));
+
+    let links = rendered_links();
+    let width = links
+        .iter()
+        .map(|l| l.label.chars().count())
+        .max()
+        .unwrap_or(0);
+    for group in LinkGroup::ALL {
+        let members: Vec<_> = links.iter().filter(|l| l.group == group).collect();
+        if members.is_empty() {
+            continue;
+        }
+        out.push(String::new());
+        out.push(format!("{}:", group.label()));
+        for link in members {
+            out.push(format!("  {:<width$}  {}", link.label, link.href));
+        }
+    }
+    out.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::LINKS;
+    use crate::entity::primary_entity;
+
+    #[test]
+    fn plan_has_finger_header_and_links() {
+        let entity = primary_entity();
+        let plan = generate_plan(entity);
+        assert!(plan.starts_with(&format!("Login: {}", entity.key)));
+        assert!(plan.contains(&format!("Name: {}\n", entity.name)));
+        assert!(plan.contains("\nPlan:\n"));
+        for link in LINKS {
+            assert!(plan.contains(link.href), "{} missing", link.href);
+        }
+        assert!(plan
+            .lines()
+            .filter(|l| !l.contains("://"))
+            .all(|l| l.chars().count() <= WIDTH));
+    }
+}
@@ -0,0 +1,344 @@
+//! # PDF Portfolio
+//!
+//! The `export-portfolio` command renders the listed art series (the same
+//! [`discover_series`](crate::art::discover_series) manifests the gallery
+//! is built from) into an A4 PDF for print and for sending to galleries:
+//!
+//! - a cover page with the site name, description, and URL;
+//! - per series, an opening page with its title, date, and description,
+//!   then one page per image with its title and description.
+//!
+//! ```bash
+//! ./target/release/everythingsings export-portfolio
+//! ```
+//!
+//! Colors come from the light palette (see [`crate::tokens`]), since the
+//! dark one would flood the paper with ink; type is Helvetica, one of the
+//! PDF base fonts, so nothing is embedded. Images are downscaled to
+//! [`PRINT_DPI`] at their printed size and embedded as JPEG.
+
+use crate::art::ArtSeries;
+use crate::text;
+use crate::tokens::{parse_hex_color, Palette};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use printpdf::{
+    BuiltinFont, Color, ColorBits, ColorSpace, Image, ImageFilter, ImageTransform, ImageXObject,
+    IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerIndex, PdfLayerReference,
+    PdfPageIndex, Px, Rect, Rgb,
+};
+use std::path::Path;
+
+/// Where `export-portfolio` writes the PDF, relative to the project root.
+pub const PORTFOLIO_PATH: &str = "target/portfolio.pdf";
+
+/// Page size (A4 portrait), in millimetres.
+pub const PAGE_SIZE: (f32, f32) = (210.0, 297.0);
+
+/// Page margin, in millimetres.
+const MARGIN: f32 = 20.0;
+
+/// Resolution images are downscaled to at their printed size.
+pub const PRINT_DPI: f32 = 300.0;
+
+/// Quality images are JPEG-encoded at; the PDF carries them as is.
+const JPEG_QUALITY: u8 = 90;
+
+/// Millimetres per inch.
+const MM_PER_INCH: f32 = 25.4;
+
+/// Millimetres per typographic point.
+const MM_PER_PT: f32 = MM_PER_INCH / 72.0;
+
+/// Average Helvetica glyph width, as a fraction of the font size; used to
+/// wrap text without font metrics.
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+
+/// Space reserved under each image for its caption, in millimetres.
+const CAPTION_HEIGHT: f32 = 30.0;
+
+/// An image to place: decoded and resolved against the public directory.
+pub struct PortfolioImage {
+    pub image: image::DynamicImage,
+    pub title: Option<String>,
+    pub caption: Option<String>,
+}
+
+/// Where an image of `size` pixels goes in a box at (`x`, `y`) of
+/// `width` × `height` millimetres, centered: its lower-left corner and the
+/// DPI that makes it fill the box in its tighter dimension.
+pub fn fit(size: (u32, u32), x: f32, y: f32, width: f32, height: f32) -> (f32, f32, f32) {
+    let (w, h) = (size.0.max(1) as f32, size.1.max(1) as f32);
+    let mm_per_px = (width / w).min(height / h);
+    let (printed_w, printed_h) = (w * mm_per_px, h * mm_per_px);
+    (
+        x + (width - printed_w) / 2.0,
+        y + (height - printed_h) / 2.0,
+        MM_PER_INCH / mm_per_px,
+    )
+}
+
+/// Splits `text` into lines of at most `width` millimetres at `size` points.
+pub fn wrap(text: &str, width: f32, size: f32) -> Vec<String> {
+    let max_chars = (width / (size * AVERAGE_GLYPH_WIDTH * MM_PER_PT)).max(1.0) as usize;
+    text::wrap(text, max_chars)
+}
+
+/// Loads the images of `series` from `public_dir`; images that cannot be
+/// read are skipped with a warning.
+pub fn load_images(series: &ArtSeries, public_dir: &Path) -> Vec<PortfolioImage> {
+    series
+        .images
+        .iter()
+        .filter_map(|img| {
+            let file = crate::serve::percent_decode(img.url.trim_start_matches('/'))
+                .map(|path| public_dir.join(path))?;
+            match image::open(&file) {
+                Ok(image) => Some(PortfolioImage {
+                    image,
+                    title: img.title.clone(),
+                    caption: img.description.clone().or_else(|| Some(img.alt.clone())),
+                }),
+                Err(e) => {
+                    eprintln!("Warning: Skipping {}: {}", file.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Fill color for a palette entry; palettes are checked for valid colors
+/// when the stylesheet is generated.
+fn color(hex: &str) -> Color {
+    let (r, g, b) = parse_hex_color(hex).unwrap_or((0, 0, 0));
+    Color::Rgb(Rgb::new(
+        f32::from(r) / 255.0,
+        f32::from(g) / 255.0,
+        f32::from(b) / 255.0,
+        None,
+    ))
+}
+
+/// Draws the portfolio pages onto a document.
+struct Portfolio<'a> {
+    doc: PdfDocumentReference,
+    /// The page the document was created with, until it is drawn on.
+    first_page: Option<(PdfPageIndex, PdfLayerIndex)>,
+    palette: &'a Palette,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    footer: String,
+    pages: usize,
+}
+
+impl Portfolio<'_> {
+    /// A new page with the palette's background and the footer.
+    fn page(&mut self) -> PdfLayerReference {
+        let (width, height) = PAGE_SIZE;
+        let (page, layer) = self
+            .first_page
+            .take()
+            .unwrap_or_else(|| self.doc.add_page(Mm(width), Mm(height), "Page"));
+        let layer = self.doc.get_page(page).get_layer(layer);
+        self.pages += 1;
+        layer.set_fill_color(color(self.palette.bg));
+        layer.add_rect(Rect::new(Mm(0.0), Mm(0.0), Mm(width), Mm(height)));
+        layer.set_fill_color(color(self.palette.text_muted));
+        layer.use_text(
+            format!("{}  ·  {}", self.footer, self.pages),
+            8.0,
+            Mm(MARGIN),
+            Mm(MARGIN / 2.0),
+            &self.regular,
+        );
+        layer
+    }
+
+    /// Writes `text` wrapped from `y` down; returns the `y` below it.
+    fn text(&self, layer: &PdfLayerReference, text: &str, size: f32, bold: bool, y: f32) -> f32 {
+        let font = if bold { &self.bold } else { &self.regular };
+        let leading = size * MM_PER_PT * 1.4;
+        let mut y = y;
+        for line in wrap(text, PAGE_SIZE.0 - 2.0 * MARGIN, size) {
+            layer.use_text(line, size, Mm(MARGIN), Mm(y), font);
+            y -= leading;
+        }
+        y
+    }
+
+    fn cover(&mut self, title: &str, description: &str, url: &str) {
+        let layer = self.page();
+        layer.set_fill_color(color(self.palette.accent));
+        let y = self.text(&layer, title, 36.0, true, PAGE_SIZE.1 * 0.6);
+        layer.set_fill_color(color(self.palette.text));
+        let y = self.text(&layer, description, 12.0, false, y - 4.0);
+        layer.set_fill_color(color(self.palette.text_muted));
+        self.text(&layer, url, 10.0, false, y - 4.0);
+    }
+
+    fn series(&mut self, series: &ArtSeries, images: Vec<PortfolioImage>) -> Result<(), String> {
+        let layer = self.page();
+        layer.set_fill_color(color(self.palette.accent));
+        let y = self.text(
+            &layer,
+            &series.title,
+            24.0,
+            true,
+            PAGE_SIZE.1 - 2.0 * MARGIN,
+        );
+        layer.set_fill_color(color(self.palette.text_muted));
+        let y = self.text(&layer, &series.date, 10.0, false, y - 2.0);
+        layer.set_fill_color(color(self.palette.text));
+        self.text(&layer, &series.description, 11.0, false, y - 6.0);
+
+        for image in images {
+            self.image(&series.title, image)?;
+        }
+        Ok(())
+    }
+
+    fn image(&mut self, series_title: &str, image: PortfolioImage) -> Result<(), String> {
+        let layer = self.page();
+        let (page_w, page_h) = PAGE_SIZE;
+        layer.set_fill_color(color(self.palette.text_muted));
+        self.text(&layer, series_title, 9.0, false, page_h - MARGIN);
+
+        let (box_x, box_y) = (MARGIN, MARGIN + CAPTION_HEIGHT);
+        let (box_w, box_h) = (
+            page_w - 2.0 * MARGIN,
+            page_h - 3.0 * MARGIN - CAPTION_HEIGHT,
+        );
+        let max_px = |mm: f32| (mm / MM_PER_INCH * PRINT_DPI) as u32;
+        let mut pixels = image.image;
+        if pixels.width() > max_px(box_w) || pixels.height() > max_px(box_h) {
+            pixels = pixels.resize(max_px(box_w), max_px(box_h), FilterType::Lanczos3);
+        }
+        let pixels = pixels.to_rgb8();
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY)
+            .encode_image(&pixels)
+            .map_err(|e| e.to_string())?;
+        let (x, y, dpi) = fit(pixels.dimensions(), box_x, box_y, box_w, box_h);
+        Image::from(ImageXObject {
+            width: Px(pixels.width() as usize),
+            height: Px(pixels.height() as usize),
+            color_space: ColorSpace::Rgb,
+            bits_per_component: ColorBits::Bit8,
+            interpolate: true,
+            image_data: jpeg,
+            image_filter: Some(ImageFilter::DCT),
+            smask: None,
+            clipping_bbox: None,
+        })
+        .add_to_layer(
+            layer.clone(),
+            ImageTransform {
+                translate_x: Some(Mm(x)),
+                translate_y: Some(Mm(y)),
+                dpi: Some(dpi),
+                ..Default::default()
+            },
+        );
+
+        let mut y = MARGIN + CAPTION_HEIGHT - 8.0;
+        if let Some(title) = &image.title {
+            layer.set_fill_color(color(self.palette.text));
+            y = self.text(&layer, title, 12.0, true, y);
+        }
+        if let Some(caption) = &image.caption {
+            layer.set_fill_color(color(self.palette.text_muted));
+            self.text(&layer, caption, 9.0, false, y - 1.0);
+        }
+        Ok(())
+    }
+}
+
+/// Renders the portfolio of `series`, whose images are under `public_dir`,
+/// to PDF bytes. `site` is the site's (name, description, URL).
+pub fn render(
+    series: &[ArtSeries],
+    public_dir: &Path,
+    site: (&str, &str, &str),
+    palette: &Palette,
+) -> Result<Vec<u8>, String> {
+    let (name, description, url) = site;
+    let (width, height) = PAGE_SIZE;
+    let (doc, first_page, first_layer) = PdfDocument::new(
+        format!("{} — Portfolio", name),
+        Mm(width),
+        Mm(height),
+        "Page",
+    );
+    let doc = doc.with_author(name).with_subject(description);
+    let err = |e: printpdf::Error| e.to_string();
+    let mut portfolio = Portfolio {
+        regular: doc.add_builtin_font(BuiltinFont::Helvetica).map_err(err)?,
+        bold: doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(err)?,
+        doc,
+        first_page: Some((first_page, first_layer)),
+        palette,
+        footer: format!("{} — {}", name, url),
+        pages: 0,
+    };
+    portfolio.cover(name, description, url);
+    for s in series {
+        portfolio.series(s, load_images(s, public_dir))?;
+    }
+    portfolio.doc.save_to_bytes().map_err(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art::discover_series;
+    use crate::tokens::LIGHT;
+
+    #[test]
+    fn images_fill_their_box_centered() {
+        // A square image in a wide box fills its height.
+        let (x, y, dpi) = fit((300, 300), 10.0, 20.0, 200.0, 100.0);
+        assert_eq!((x, y), (60.0, 20.0));
+        assert!((dpi - 76.2).abs() < 0.01);
+    }
+
+    #[test]
+    fn text_wraps_at_words() {
+        let lines = wrap("one two three four", 10.0, 10.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.join(" "), "one two three four");
+        assert_eq!(wrap("a\nb", 100.0, 10.0), ["a", "b"]);
+    }
+
+    #[test]
+    fn portfolio_renders_each_series() {
+        let root = std::env::temp_dir().join(format!("portfolio-{}", std::process::id()));
+        let dir = root.join("art/neon");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("series.toml"),
+            "title = \"Neon\"\ndescription = \"Lights.\"\ndate = \"2025-01-01\"\n\
+             [[images]]\nfile = \"a.png\"\nalt = \"A glow\"\n",
+        )
+        .unwrap();
+        image::RgbImage::new(40, 20)
+            .save(dir.join("a.png"))
+            .unwrap();
+        let series = discover_series(&root);
+        assert_eq!(load_images(&series[0], &root).len(), 1);
+
+        let pdf = render(&series, &root, ("A", "B", "https://a.test/"), &LIGHT).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+        // Cover, series opening, and image pages.
+        assert_eq!(count_pages(&pdf), 3);
+    }
+
+    /// Counts `/Type/Page` dictionaries, however the writer spaces them.
+    fn count_pages(pdf: &[u8]) -> usize {
+        let text = String::from_utf8_lossy(pdf).replace(' ', "");
+        text.matches("/Type/Page").count() - text.matches("/Type/Pages").count()
+    }
+}
@@ -0,0 +1,251 @@
+//! # Posts
+//!
+//! Blog posts: the [collection](crate::collection) declared as
+//! [`POSTS`](crate::config::POSTS), read from `content/posts/`. Each post
+//! is a Schema.org `BlogPosting` authored by the primary entity, and the
+//! listing is a `Blog`.
+//!
+//! ## Frontmatter
+//!
+//! ```toml
+//! title = "Hello, world"
+//! date = "2025-03-01"
+//! description = "Why this site has a blog now."   # optional, overrides the excerpt
+//! draft = true                                     # optional, skips the post
+//! slug = "hello"                                   # optional, overrides the file name
+//! toc = true                                       # optional, lists the headings
+//!
+//! [taxonomies]                                     # optional, see crate::taxonomy
+//! medium = ["ink"]
+//! ```
+
+use crate::collection::{Collection, Content, Entry};
+use crate::config::TAXONOMIES;
+use crate::escape::json_script;
+use crate::markdown::{self, Heading};
+use crate::taxonomy::{structured_data, Terms};
+use crate::url::absolute;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// A post's frontmatter.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Post {
+    pub title: String,
+    /// ISO date, e.g. `2025-03-01`.
+    pub date: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Whether the page shows a table of contents.
+    #[serde(default)]
+    pub toc: bool,
+    #[serde(default)]
+    pub taxonomies: Terms,
+}
+
+impl Content for Post {
+    fn title(&self) -> &str {
+        &self.title
+    }
+    fn date(&self) -> &str {
+        &self.date
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+    fn draft(&self) -> bool {
+        self.draft
+    }
+    fn slug(&self) -> Option<&str> {
+        self.slug.as_deref()
+    }
+    fn terms(&self) -> &Terms {
+        &self.taxonomies
+    }
+}
+
+/// The loaded posts, newest first.
+pub type Posts = Collection<Post>;
+
+/// A post as loaded, with its slug and Markdown body.
+pub type PostEntry = Entry<Post>;
+
+impl Entry<Post> {
+    /// The post's body as HTML, with its internal links resolved and its
+    /// co-located assets linked by URL.
+    pub fn body_html(&self) -> String {
+        markdown::to_html_with(&self.body, |url| {
+            self.links
+                .get(url)
+                .cloned()
+                .or_else(|| self.asset_link(url))
+        })
+    }
+
+    /// The headings of the post's table of contents: none unless its
+    /// frontmatter asks for one.
+    pub fn toc(&self) -> Vec<Heading> {
+        match self.meta.toc {
+            true => markdown::headings(&self.body),
+            false => Vec::new(),
+        }
+    }
+
+    /// The post's `BlogPosting` node.
+    fn node(&self, author_id: &str) -> Value {
+        let mut post = json!({
+            "@type": "BlogPosting",
+            "headline": self.meta.title,
+            "url": absolute(&self.url_path()),
+            "datePublished": self.meta.date,
+            "author": { "@id": author_id },
+        });
+        if !self.summary.is_empty() {
+            post["description"] = json!(self.summary);
+        }
+        for (property, value) in structured_data(&self.meta.taxonomies, TAXONOMIES) {
+            post[property] = value;
+        }
+        post
+    }
+
+    /// JSON-LD of the post's page.
+    pub fn to_json_ld(&self, author_id: &str) -> String {
+        let mut post = self.node(author_id);
+        post["@context"] = json!("https://schema.org");
+        post["mainEntityOfPage"] = json!(absolute(&self.url_path()));
+        json_script(&post)
+    }
+}
+
+/// JSON-LD of a listing page at `url` showing `entries`.
+pub fn listing_json_ld(posts: &Posts, url: &str, entries: &[PostEntry], author_id: &str) -> String {
+    let items: Vec<Value> = entries.iter().map(|e| e.node(author_id)).collect();
+    json_script(&json!({
+        "@context": "https://schema.org",
+        "@type": "Blog",
+        "name": posts.def.title,
+        "description": posts.def.description,
+        "url": url,
+        "author": { "@id": author_id },
+        "blogPost": items,
+    }))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::components::{PostPage, PostPageProps};
+    use crate::config::POSTS;
+    use crate::internal_links::LinkTargets;
+    use leptos::prelude::*;
+    use std::path::PathBuf;
+
+    /// Two posts and a draft.
+    pub(crate) fn sample_posts() -> Posts {
+        let post = |name: &str, text: &str| {
+            let path = PathBuf::from(format!("{}.md", name));
+            (name.to_string(), path, text.to_string())
+        };
+        Collection::from_sources(
+            POSTS,
+            vec![
+                post(
+                    "hello-world",
+                    "+++\ntitle = \"Hello, world\"\ndate = \"2025-03-01\"\ntoc = true\ndescription = \"Why this site has a blog now.\"\n\n[taxonomies]\nmedium = [\"ink\"]\n+++\n\nFirst *post*.[^1]\n\n## Ink\n\n## Paper\n\n[^1]: A note.\n",
+                ),
+                post(
+                    "night-walk",
+                    "+++\ntitle = \"Night walk\"\ndate = \"2025-04-12\"\n+++\n\nSecond post.\n",
+                ),
+                post(
+                    "unfinished",
+                    "+++\ntitle = \"Unfinished\"\ndate = \"2025-05-01\"\ndraft = true\n+++\n",
+                ),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn parse(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn posts_are_blog_postings() {
+        let posts = sample_posts();
+        assert_eq!(posts.entries.len(), 2);
+        let hello = &posts.entries[1];
+        assert!(hello.body_html().starts_with("<p>First <em>post</em>.<sup"));
+        let node = parse(&hello.to_json_ld("https://a.test/#artist"));
+        assert_eq!(node["@type"], "BlogPosting");
+        assert_eq!(node["headline"], "Hello, world");
+        assert_eq!(node["url"], absolute("/posts/hello-world/"));
+        assert_eq!(node["author"]["@id"], "https://a.test/#artist");
+        assert_eq!(node["keywords"], json!(["ink"]));
+    }
+
+    #[test]
+    fn listing_is_a_blog() {
+        let posts = sample_posts();
+        let node = parse(&listing_json_ld(
+            &posts,
+            "https://a.test/posts/",
+            &posts.entries,
+            "#a",
+        ));
+        assert_eq!(node["@type"], "Blog");
+        assert_eq!(node["blogPost"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            node["blogPost"][1]["description"],
+            "Why this site has a blog now."
+        );
+        assert_eq!(node["blogPost"][0]["description"], "Second post.");
+    }
+
+    /// Builds the post whose source file is `text`, as the fuzz targets in
+    /// `fuzz/` do.
+    fn build_post(text: &str) {
+        let source = (
+            "fuzz".to_string(),
+            PathBuf::from("fuzz.md"),
+            text.to_string(),
+        );
+        let Ok(mut posts) = Collection::from_sources(POSTS, vec![source]) else {
+            return;
+        };
+        let mut targets = LinkTargets::default();
+        targets.add(&posts);
+        if targets.resolve_collection(&mut posts).is_err() {
+            return;
+        }
+        for post in &posts.entries {
+            post.toc();
+            post.to_json_ld("#fuzz");
+            PostPage(PostPageProps {
+                post: post.clone(),
+                listing: posts.def.listing_path(),
+                listing_title: posts.def.title,
+            })
+            .to_html();
+        }
+    }
+
+    /// Inputs under `fuzz/regressions/<target>/`, each as its fuzz target
+    /// feeds it: a source file for `content`, a body for `markdown`.
+    #[test]
+    fn fuzz_regressions_build() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions");
+        let frontmatter = "+++\ntitle = \"Fuzz\"\ndate = \"2025-01-01\"\ntoc = true\n+++\n";
+        for (target, prefix) in [("content", ""), ("markdown", frontmatter)] {
+            for file in std::fs::read_dir(dir.join(target)).unwrap() {
+                let input = std::fs::read(file.unwrap().path()).unwrap();
+                build_post(&format!("{}{}", prefix, String::from_utf8_lossy(&input)));
+            }
+        }
+    }
+}
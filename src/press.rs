@@ -0,0 +1,319 @@
+//! # Press Kit
+//!
+//! Reads `press.toml` and exports the approved assets for the `/press/` page:
+//! raster images are resized to each configured width, everything else is
+//! copied as-is, and every exported file gets a SHA-256 checksum so
+//! downloads can be verified.
+//!
+//! ## Example
+//!
+//! ```toml
+//! short_bio = "Formless art brand."
+//! long_bio = """
+//! First paragraph.
+//!
+//! Second paragraph.
+//! """
+//!
+//! [[assets]]
+//! name = "Avatar"
+//! source = "public/avatar.png"
+//! widths = [128, 256, 512]
+//!
+//! [[assets]]
+//! name = "Logo"
+//! source = "public/favicon.svg"
+//! ```
+
+use image::imageops::FilterType;
+use image::ImageFormat;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Press kit configuration file, relative to the project root.
+pub const PRESS_PATH: &str = "press.toml";
+
+/// Output subdirectory (and URL path segment) for the press kit.
+pub const PRESS_DIR: &str = "press";
+
+/// Press kit configuration.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PressKit {
+    /// One or two sentences for listings and captions.
+    pub short_bio: String,
+    /// Full bio; paragraphs are separated by blank lines.
+    pub long_bio: String,
+    #[serde(default)]
+    pub assets: Vec<PressAsset>,
+}
+
+/// An approved asset.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PressAsset {
+    pub name: String,
+    /// Source file, relative to the project root.
+    pub source: String,
+    /// Widths to export for raster images. Empty exports the original file.
+    #[serde(default)]
+    pub widths: Vec<u32>,
+}
+
+/// A file written to the press kit output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PressFile {
+    /// Name of the asset this file was exported from.
+    pub name: String,
+    pub file_name: String,
+    /// MIME type, e.g. `image/png`.
+    pub encoding_format: &'static str,
+    /// Pixel dimensions; `None` for vector or non-image files.
+    pub dimensions: Option<(u32, u32)>,
+    pub bytes: u64,
+    /// Lowercase hex SHA-256 of the file contents.
+    pub sha256: String,
+}
+
+impl PressFile {
+    /// Site-relative URL, e.g. `/press/avatar-256.png`.
+    pub fn url_path(&self) -> String {
+        format!("/{}/{}", PRESS_DIR, self.file_name)
+    }
+}
+
+impl PressKit {
+    /// Loads the press kit from `path`; `Ok(None)` when the file is absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    /// The long bio split into paragraphs.
+    pub fn long_bio_paragraphs(&self) -> Vec<String> {
+        self.long_bio
+            .split("\n\n")
+            .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    /// Writes every asset into `dir`, resolving sources against `root`.
+    pub fn export_assets(&self, root: &Path, dir: &Path) -> std::io::Result<Vec<PressFile>> {
+        std::fs::create_dir_all(dir)?;
+        let mut files = Vec::new();
+        for asset in &self.assets {
+            let source = root.join(&asset.source);
+            let data = std::fs::read(&source)?;
+            let file_name = source
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| std::io::Error::other(format!("Bad asset path {}", asset.source)))?;
+            let encoding_format = encoding_format(file_name);
+
+            if asset.widths.is_empty() {
+                let dimensions = image::load_from_memory(&data)
+                    .ok()
+                    .map(|img| (img.width(), img.height()));
+                files.push(write_file(
+                    dir,
+                    asset,
+                    file_name,
+                    encoding_format,
+                    dimensions,
+                    &data,
+                )?);
+                continue;
+            }
+
+            let format = ImageFormat::from_path(&source).map_err(std::io::Error::other)?;
+            let original = image::load_from_memory_with_format(&data, format)
+                .map_err(std::io::Error::other)?;
+            let (stem, ext) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+            // Never upscale: widths past the original's all export the
+            // original, once
+            let mut widths: Vec<u32> = Vec::new();
+            for width in asset.widths.iter().map(|&w| w.min(original.width())) {
+                if !widths.contains(&width) {
+                    widths.push(width);
+                }
+            }
+            for width in widths {
+                // The original bytes are the best full-size export
+                if width == original.width() {
+                    let name = format!("{}-{}.{}", stem, original.width(), ext);
+                    let dimensions = Some((original.width(), original.height()));
+                    files.push(write_file(
+                        dir,
+                        asset,
+                        &name,
+                        encoding_format,
+                        dimensions,
+                        &data,
+                    )?);
+                    continue;
+                }
+                let height = (original.height() as u64 * width as u64 / original.width() as u64)
+                    .max(1) as u32;
+                let resized = original.resize_exact(width, height, FilterType::Lanczos3);
+                let mut encoded = std::io::Cursor::new(Vec::new());
+                resized
+                    .write_to(&mut encoded, format)
+                    .map_err(std::io::Error::other)?;
+                let name = format!("{}-{}.{}", stem, width, ext);
+                files.push(write_file(
+                    dir,
+                    asset,
+                    &name,
+                    encoding_format,
+                    Some((width, height)),
+                    encoded.get_ref(),
+                )?);
+            }
+        }
+        Ok(files)
+    }
+}
+
+fn write_file(
+    dir: &Path,
+    asset: &PressAsset,
+    file_name: &str,
+    encoding_format: &'static str,
+    dimensions: Option<(u32, u32)>,
+    data: &[u8],
+) -> std::io::Result<PressFile> {
+    std::fs::write(dir.join(file_name), data)?;
+    Ok(PressFile {
+        name: asset.name.clone(),
+        file_name: file_name.to_string(),
+        encoding_format,
+        dimensions,
+        bytes: data.len() as u64,
+        sha256: sha256_hex(data),
+    })
+}
+
+/// MIME type for a file name, by extension.
+pub fn encoding_format(file_name: &str) -> &'static str {
+    let ext = file_name.rsplit('.').next().unwrap_or("");
+    match ext.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Lowercase hex SHA-256 of `data`.
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// A `SHA256SUMS` file in `sha256sum` format, verifiable with
+/// `sha256sum -c SHA256SUMS`.
+pub fn checksums_file(files: &[PressFile]) -> String {
+    files
+        .iter()
+        .map(|f| format!("{}  {}\n", f.sha256, f.file_name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn tempdir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("esart-press-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn kit(assets: Vec<PressAsset>) -> PressKit {
+        PressKit {
+            short_bio: "Short.".to_string(),
+            long_bio: "First\nline.\n\nSecond.".to_string(),
+            assets,
+        }
+    }
+
+    #[test]
+    fn sha256_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn missing_config_has_no_press_kit() {
+        assert_eq!(PressKit::load(Path::new("does-not-exist.toml")), Ok(None));
+    }
+
+    #[test]
+    fn long_bio_is_split_into_paragraphs() {
+        assert_eq!(
+            kit(vec![]).long_bio_paragraphs(),
+            ["First line.", "Second."]
+        );
+    }
+
+    #[test]
+    fn raster_assets_are_resized_with_checksums() {
+        let root = tempdir("raster");
+        image::RgbImage::new(64, 32)
+            .save(root.join("logo.png"))
+            .unwrap();
+        let out = root.join("out");
+        let files = kit(vec![PressAsset {
+            name: "Logo".to_string(),
+            source: "logo.png".to_string(),
+            widths: vec![16, 128, 256],
+        }])
+        .export_assets(&root, &out)
+        .unwrap();
+
+        assert_eq!(files.len(), 2, "Clamped widths are exported once");
+        assert_eq!(files[0].file_name, "logo-16.png");
+        assert_eq!(files[0].dimensions, Some((16, 8)));
+        assert_eq!(files[1].file_name, "logo-64.png", "Never upscaled");
+        let written = fs::read(out.join("logo-16.png")).unwrap();
+        assert_eq!(files[0].sha256, sha256_hex(&written));
+        assert_eq!(files[0].url_path(), "/press/logo-16.png");
+    }
+
+    #[test]
+    fn other_assets_are_copied() {
+        let root = tempdir("copy");
+        fs::write(root.join("mark.svg"), "<svg/>").unwrap();
+        let out = root.join("out");
+        let files = kit(vec![PressAsset {
+            name: "Mark".to_string(),
+            source: "mark.svg".to_string(),
+            widths: vec![],
+        }])
+        .export_assets(&root, &out)
+        .unwrap();
+
+        assert_eq!(files[0].encoding_format, "image/svg+xml");
+        assert_eq!(files[0].dimensions, None);
+        assert_eq!(fs::read_to_string(out.join("mark.svg")).unwrap(), "<svg/>");
+        assert_eq!(
+            checksums_file(&files),
+            format!("{}  mark.svg\n", sha256_hex(b"<svg/>"))
+        );
+    }
+}
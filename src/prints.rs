@@ -0,0 +1,307 @@
+//! # Latest Prints
+//!
+//! A snapshot of the newest works in the Redbubble shop, taken from its RSS
+//! feed at build time (through [`crate::cache`]) for the `/prints/` page.
+//! Product images are self-hosted like the [lite embed](crate::embeds)
+//! thumbnails, so the page loads nothing from Redbubble. The integration is
+//! off unless [`REDBUBBLE_FEED`](crate::config::REDBUBBLE_FEED) is set.
+
+use crate::cache::{cached_fetch, fetch_bytes, offline, url_key, CacheEntry, CACHE_DIR};
+use crate::config::SITE_NAME;
+use crate::embeds::normalize;
+use crate::escape::json_script;
+use scraper::{Html, Selector};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+
+/// Output directory of the prints page and its images.
+pub const PRINTS_DIR: &str = "prints";
+
+/// Number of prints shown.
+pub const PRINTS_LIMIT: usize = 6;
+
+/// Description of the prints page.
+pub const PRINTS_DESCRIPTION: &str = "The latest AI art prints in the shop.";
+
+/// Cache entry name of the shop feed.
+pub const CACHE_NAME: &str = "redbubble.xml";
+
+/// How long the cached feed and images are used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// One work in the shop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Print {
+    pub title: String,
+    /// The work's shop page.
+    pub url: String,
+    /// Product image: the remote URL when parsed, the self-hosted path once
+    /// loaded.
+    pub image: Option<String>,
+}
+
+impl Print {
+    /// The print as a Schema.org `Product`. `image_base` prefixes a
+    /// self-hosted image path.
+    pub fn to_json_ld(&self, image_base: &str) -> Value {
+        let mut product = json!({
+            "@type": "Product",
+            "name": self.title,
+            "url": self.url,
+            "brand": { "@type": "Brand", "name": SITE_NAME },
+        });
+        if let Some(image) = &self.image {
+            product["image"] = json!(format!("{}{}", image_base, image));
+        }
+        product
+    }
+}
+
+/// Builds the prints page JSON-LD: a `CollectionPage` whose `mainEntity`
+/// lists the prints as `Product`s. `url` is the page URL and `site_url` the
+/// base of the self-hosted images.
+pub fn prints_json_ld(prints: &[Print], url: &str, site_url: &str) -> String {
+    let items: Vec<Value> = prints
+        .iter()
+        .enumerate()
+        .map(|(i, print)| {
+            json!({
+                "@type": "ListItem",
+                "position": i + 1,
+                "item": print.to_json_ld(site_url),
+            })
+        })
+        .collect();
+    json_script(&json!({
+        "@context": "https://schema.org",
+        "@type": "CollectionPage",
+        "name": format!("Latest prints | {}", SITE_NAME),
+        "url": url,
+        "description": PRINTS_DESCRIPTION,
+        "mainEntity": {
+            "@type": "ItemList",
+            "numberOfItems": items.len(),
+            "itemListElement": items,
+        },
+    }))
+}
+
+/// Decodes the XML entities and numeric references in `text`.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => {
+                    let code = match entity.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The text content of the first `<name>` element in `xml`, unwrapped from
+/// CDATA and unescaped.
+fn element(xml: &str, name: &str) -> Option<String> {
+    let open = format!("<{}", name);
+    let mut from = 0;
+    let start = loop {
+        let at = from + xml[from..].find(&open)?;
+        let after = at + open.len();
+        // Skip longer names sharing the prefix, e.g. <linkedin> for <link>
+        if xml[after..].starts_with(['>', ' ', '\t', '\n', '\r']) {
+            break after + xml[after..].find('>')? + 1;
+        }
+        from = after;
+    };
+    let end = start + xml[start..].find(&format!("</{}>", name))?;
+    let content = xml[start..end].trim();
+    Some(
+        match content
+            .strip_prefix("<![CDATA[")
+            .and_then(|c| c.strip_suffix("]]>"))
+        {
+            Some(cdata) => cdata.to_string(),
+            None => unescape(content),
+        },
+    )
+}
+
+/// The `attr` value of the first `<name …>` tag in `xml`.
+fn attribute(xml: &str, name: &str, attr: &str) -> Option<String> {
+    let start = xml.find(&format!("<{} ", name))?;
+    let tag = &xml[start..start + xml[start..].find('>')?];
+    let value_start = tag.find(&format!(" {}=\"", attr))? + attr.len() + 3;
+    let value = &tag[value_start..value_start + tag[value_start..].find('"')?];
+    Some(unescape(value))
+}
+
+/// The first image in an item's HTML description.
+fn description_image(html: &str) -> Option<String> {
+    let img = Selector::parse("img[src]").unwrap();
+    Html::parse_fragment(html)
+        .select(&img)
+        .next()?
+        .value()
+        .attr("src")
+        .map(str::to_string)
+}
+
+/// Parses the items of an RSS feed into prints, newest first as listed, up
+/// to [`PRINTS_LIMIT`]. The image is the item's `media:content`,
+/// `media:thumbnail`, or `enclosure`, falling back to the first image in
+/// its description; only `https` links and images are kept.
+pub fn parse_feed(xml: &str) -> Vec<Print> {
+    xml.split("<item")
+        .skip(1)
+        .filter(|item| item.starts_with(['>', ' ']))
+        .filter_map(|item| {
+            let item = &item[..item.find("</item>").unwrap_or(item.len())];
+            let title = element(item, "title")?;
+            let url = element(item, "link").filter(|u| u.starts_with("https://"))?;
+            let image = ["media:content", "media:thumbnail", "enclosure"]
+                .iter()
+                .find_map(|tag| attribute(item, tag, "url"))
+                .or_else(|| element(item, "description").and_then(|d| description_image(&d)))
+                .filter(|src| src.starts_with("https://"));
+            Some(Print { title, url, image })
+        })
+        .take(PRINTS_LIMIT)
+        .collect()
+}
+
+/// Loads the latest prints from `feed`, self-hosting their images under
+/// `<site_dir>/prints/`. Empty when the feed is unavailable.
+pub fn load(feed: &str, site_dir: &Path) -> std::io::Result<Vec<Print>> {
+    let Some(xml) = cached_fetch(CACHE_NAME, feed, MAX_AGE) else {
+        return Ok(Vec::new());
+    };
+    let mut prints = parse_feed(&xml);
+    let cache_dir = Path::new(CACHE_DIR).join(PRINTS_DIR);
+    let out_dir = site_dir.join(PRINTS_DIR);
+    for print in &mut prints {
+        let image = print.image.take().and_then(|src| {
+            let file_name = format!("{}.jpg", url_key(&src));
+            let jpeg = CacheEntry::new(&cache_dir, &file_name).get_or_fetch_bytes(
+                MAX_AGE,
+                offline(),
+                || fetch_bytes(&src).and_then(|data| normalize(&data)),
+            )?;
+            Some((file_name, jpeg))
+        });
+        if let Some((file_name, jpeg)) = image {
+            std::fs::create_dir_all(&out_dir)?;
+            std::fs::write(out_dir.join(&file_name), jpeg)?;
+            print.image = Some(format!("/{}/{}", PRINTS_DIR, file_name));
+        }
+    }
+    Ok(prints)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/">
+<channel>
+  <title>bedim's Shop</title>
+  <link>https://www.redbubble.com/people/bedim/shop</link>
+  <item>
+    <title>Neon Tide</title>
+    <link>https://www.redbubble.com/i/art-print/Neon-Tide/1.UGYPM</link>
+    <media:content url="https://ih1.redbubble.net/image.1.jpg" medium="image" />
+  </item>
+  <item>
+    <title><![CDATA[Glass & Smoke]]></title>
+    <link>https://www.redbubble.com/i/poster/Glass-Smoke/2.LVTDI</link>
+    <description>&lt;p&gt;&lt;img src="https://ih1.redbubble.net/image.2.jpg" /&gt;&lt;/p&gt;</description>
+  </item>
+  <item>
+    <title>Static &amp; Bloom</title>
+    <link>https://www.redbubble.com/i/sticker/Static/3.EJUG5</link>
+  </item>
+  <item>
+    <title>Insecure</title>
+    <link>http://www.redbubble.com/i/art-print/x/4</link>
+  </item>
+</channel>
+</rss>"#;
+
+    pub fn sample_prints() -> Vec<Print> {
+        parse_feed(FEED)
+    }
+
+    #[test]
+    fn feed_items_become_prints() {
+        let prints = sample_prints();
+        let titles: Vec<&str> = prints.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, ["Neon Tide", "Glass & Smoke", "Static & Bloom"]);
+        assert_eq!(
+            prints[0].image.as_deref(),
+            Some("https://ih1.redbubble.net/image.1.jpg")
+        );
+        assert_eq!(
+            prints[1].image.as_deref(),
+            Some("https://ih1.redbubble.net/image.2.jpg")
+        );
+        assert_eq!(prints[2].image, None);
+    }
+
+    #[test]
+    fn feed_is_capped() {
+        let item = "<item><title>T</title><link>https://rb.test/i</link></item>";
+        assert_eq!(parse_feed(&item.repeat(10)).len(), PRINTS_LIMIT);
+    }
+
+    #[test]
+    fn entities_are_decoded() {
+        assert_eq!(
+            unescape("a &amp; b &#39;c&#x27; &bogus"),
+            "a & b 'c' &bogus"
+        );
+    }
+
+    #[test]
+    fn json_ld_lists_products() {
+        let mut prints = sample_prints();
+        prints[0].image = Some("/prints/abc.jpg".to_string());
+        let ld: Value = serde_json::from_str(&prints_json_ld(
+            &prints,
+            "https://a.test/prints/",
+            "https://a.test",
+        ))
+        .unwrap();
+        let items = &ld["mainEntity"]["itemListElement"];
+        assert_eq!(ld["mainEntity"]["numberOfItems"], 3);
+        assert_eq!(items[0]["item"]["@type"], "Product");
+        assert_eq!(items[0]["item"]["image"], "https://a.test/prints/abc.jpg");
+        assert_eq!(items[1]["item"]["name"], "Glass & Smoke");
+    }
+}
@@ -0,0 +1,143 @@
+//! # Related Properties
+//!
+//! The subdomain sites belonging to the same entity as this one, e.g.
+//! Lumimenta and Sigil. They are listed in
+//! [`crate::config::PROPERTIES`] and tie the sites together two ways:
+//!
+//! - In JSON-LD, the main `WebSite` of the [site graph](crate::schema)
+//!   `hasPart` each property's `WebSite`, which is `isPartOf` it in turn,
+//!   also on the related site's own landing page (see
+//!   [`website_json_ld`]).
+//! - In markup, every page's footer carries the same cross-linking block
+//!   (see [`crate::components::PropertyLinks`]), with `rel="home"` on the
+//!   main site.
+
+use crate::config::PROPERTIES;
+use crate::escape::json_string;
+use crate::schema::website_id;
+use serde_json::{json, Value};
+
+/// A related site on a subdomain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Property {
+    /// Matches the `key` of the related site's [config](crate::site), if
+    /// this build generates it.
+    pub key: &'static str,
+    pub name: &'static str,
+    /// Base URL without a trailing slash.
+    pub url: &'static str,
+    pub description: &'static str,
+}
+
+impl Property {
+    /// The JSON-LD `@id` of this property's `WebSite`.
+    pub fn id(&self) -> String {
+        format!("{}/#website", self.url)
+    }
+
+    /// This property as a JSON-LD `WebSite` node, part of the main site.
+    pub fn json_ld_node(&self) -> Value {
+        json!({
+            "@type": "WebSite",
+            "@id": self.id(),
+            "name": self.name,
+            "url": format!("{}/", self.url),
+            "description": self.description,
+            "isPartOf": { "@id": website_id() },
+        })
+    }
+}
+
+/// JSON-LD for a related site's landing page: a `WebSite` published by
+/// `publisher` and part of the main site. `key` names its
+/// [`Property`], whose `@id` it reuses.
+pub fn website_json_ld(
+    key: &str,
+    name: &str,
+    url: &str,
+    description: &str,
+    publisher: &str,
+) -> String {
+    let id = PROPERTIES
+        .iter()
+        .find(|p| p.key == key)
+        .map(Property::id)
+        .unwrap_or_else(|| format!("{}/#website", url));
+    format!(
+        r#"{{
+  "@context": "https://schema.org",
+  "@type": "WebSite",
+  "@id": {id},
+  "name": {name},
+  "url": {url},
+  "description": {description},
+  "publisher": {{ "@id": {publisher} }},
+  "about": {{ "@id": {publisher} }},
+  "isPartOf": {{ "@id": {main} }}
+}}"#,
+        id = json_string(&id),
+        name = json_string(name),
+        url = json_string(&format!("{}/", url)),
+        description = json_string(description),
+        publisher = json_string(publisher),
+        main = json_string(&website_id()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARDS: Property = Property {
+        key: "cards",
+        name: "Cards",
+        url: "https://cards.everythingsings.art",
+        description: "Trading cards.",
+    };
+
+    fn parse(json: &str) -> serde_json::Value {
+        serde_json::from_str(json).expect("JSON-LD should be valid JSON")
+    }
+
+    #[test]
+    fn property_node_is_part_of_main_site() {
+        let node = CARDS.json_ld_node();
+        assert_eq!(node["@id"], "https://cards.everythingsings.art/#website");
+        assert_eq!(node["url"], "https://cards.everythingsings.art/");
+        assert_eq!(
+            node["isPartOf"]["@id"],
+            "https://everythingsings.art/#website"
+        );
+    }
+
+    #[test]
+    fn related_site_is_part_of_main_site() {
+        let json = parse(&website_json_ld(
+            "cards",
+            "Cards",
+            "https://cards.test",
+            "Trading cards.",
+            "https://everythingsings.art/#everythingsings",
+        ));
+        assert_eq!(json["@id"], "https://cards.test/#website");
+        assert_eq!(json["isPartOf"]["@id"], website_id());
+        assert_eq!(
+            json["about"]["@id"],
+            "https://everythingsings.art/#everythingsings"
+        );
+    }
+
+    #[test]
+    fn configured_properties_reuse_their_id() {
+        if let Some(property) = PROPERTIES.first() {
+            let json = parse(&website_json_ld(
+                property.key,
+                property.name,
+                "https://elsewhere.test",
+                property.description,
+                "https://everythingsings.art/#everythingsings",
+            ));
+            assert_eq!(json["@id"], property.id());
+        }
+    }
+}
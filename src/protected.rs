@@ -0,0 +1,242 @@
+//! # Protected Pages
+//!
+//! Reads `protected.toml`: pages whose content is encrypted at build time
+//! with a passphrase, for sharing private press or preview material from a
+//! static host. Each page is built at `/protected/<slug>/` as a passphrase
+//! form; `public/js/unlock.js` decrypts the content in the browser with the
+//! Web Crypto API and puts it in place of the form.
+//!
+//! ## Example
+//!
+//! ```toml
+//! [[page]]
+//! slug = "press-preview"
+//! title = "Press preview"
+//! source = "private/press-preview.html"   # an HTML fragment
+//! passphrase_env = "PRESS_PREVIEW_PASSPHRASE"
+//! ```
+//!
+//! Passphrases come from environment variables so they stay out of the
+//! repository; a page whose variable is unset is skipped. Keep the sources
+//! out of the repository too (and out of `public/`), or they are published
+//! in the clear.
+//!
+//! ## Format
+//!
+//! The key is derived from the passphrase with PBKDF2-HMAC-SHA256 over a
+//! random salt ([`ITERATIONS`] rounds), and the content sealed with
+//! AES-256-GCM under a random nonce. Salt, nonce, and ciphertext (with its
+//! tag) are base64 `data-` attributes of the form (see [`Sealed`]). Pages
+//! are `noindex` and left out of the sitemap: search engines would only
+//! ever see the form.
+
+use crate::text::base64;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2::{self, PBKDF2_HMAC_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+/// Protected pages configuration file, relative to the project root.
+pub const PROTECTED_PATH: &str = "protected.toml";
+
+/// Output subdirectory (and URL path segment) of protected pages.
+pub const PROTECTED_DIR: &str = "protected";
+
+/// PBKDF2 rounds, as OWASP recommends for PBKDF2-HMAC-SHA256.
+pub const ITERATIONS: u32 = 600_000;
+
+/// Length of the random salt, in bytes.
+const SALT_LEN: usize = 16;
+
+/// Length of the derived AES-256 key, in bytes.
+const KEY_LEN: usize = 32;
+
+/// The protected pages.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProtectedPages {
+    #[serde(rename = "page", default)]
+    pub pages: Vec<ProtectedPage>,
+}
+
+/// A page encrypted with a passphrase.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProtectedPage {
+    /// URL segment: lowercase ASCII letters, digits, and dashes.
+    pub slug: String,
+    pub title: String,
+    /// HTML fragment to encrypt, relative to the project root.
+    pub source: String,
+    /// Environment variable holding the passphrase.
+    pub passphrase_env: String,
+}
+
+impl ProtectedPages {
+    /// Loads the pages from `path`; `Ok(None)` when the file is absent.
+    /// Slugs must be non-empty and unique.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let pages: ProtectedPages = toml::from_str(&content)
+            .map_err(|e| format!("Could not parse {}: {}", path.display(), e))?;
+        let mut slugs = std::collections::BTreeSet::new();
+        for page in &pages.pages {
+            let slug_ok = !page.slug.is_empty()
+                && page
+                    .slug
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !slug_ok || !slugs.insert(&page.slug) {
+                return Err(format!(
+                    "Slug \"{}\" of \"{}\" must be unique lowercase letters, digits, and dashes",
+                    page.slug, page.title
+                ));
+            }
+        }
+        Ok(Some(pages))
+    }
+}
+
+impl ProtectedPage {
+    /// The page's passphrase, or `None` when its variable is unset.
+    pub fn passphrase(&self) -> Option<String> {
+        std::env::var(&self.passphrase_env)
+            .ok()
+            .filter(|p| !p.is_empty())
+    }
+}
+
+/// Content sealed with a passphrase.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sealed {
+    pub salt: Vec<u8>,
+    pub nonce: [u8; NONCE_LEN],
+    pub iterations: u32,
+    /// The ciphertext followed by the GCM tag, as Web Crypto expects it.
+    pub ciphertext: Vec<u8>,
+}
+
+/// Derives the AES key for `passphrase` and `salt`.
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey, String> {
+    let rounds = NonZeroU32::new(iterations).ok_or("Iterations must be positive")?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        PBKDF2_HMAC_SHA256,
+        rounds,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    UnboundKey::new(&AES_256_GCM, &key)
+        .map(LessSafeKey::new)
+        .map_err(|_| "Could not create the key".to_string())
+}
+
+impl Sealed {
+    /// Encrypts `content` with `passphrase`, deriving the key over
+    /// `iterations` PBKDF2 rounds.
+    pub fn seal(content: &str, passphrase: &str, iterations: u32) -> Result<Self, String> {
+        let rng = SystemRandom::new();
+        let mut salt = vec![0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|_| rng.fill(&mut nonce))
+            .map_err(|_| "Could not generate random bytes".to_string())?;
+        let mut ciphertext = content.as_bytes().to_vec();
+        derive_key(passphrase, &salt, iterations)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut ciphertext,
+            )
+            .map_err(|_| "Could not encrypt".to_string())?;
+        Ok(Sealed {
+            salt,
+            nonce,
+            iterations,
+            ciphertext,
+        })
+    }
+
+    /// Decrypts the content with `passphrase`, as the browser does.
+    pub fn open(&self, passphrase: &str) -> Result<String, String> {
+        let mut data = self.ciphertext.clone();
+        let plain = derive_key(passphrase, &self.salt, self.iterations)?
+            .open_in_place(
+                Nonce::assume_unique_for_key(self.nonce),
+                Aad::empty(),
+                &mut data,
+            )
+            .map_err(|_| "Wrong passphrase".to_string())?;
+        String::from_utf8(plain.to_vec()).map_err(|e| e.to_string())
+    }
+
+    pub fn salt_base64(&self) -> String {
+        base64(&self.salt)
+    }
+
+    pub fn nonce_base64(&self) -> String {
+        base64(&self.nonce)
+    }
+
+    pub fn ciphertext_base64(&self) -> String {
+        base64(&self.ciphertext)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Few rounds, to keep tests fast.
+    const TEST_ITERATIONS: u32 = 10;
+
+    pub fn sample_sealed() -> Sealed {
+        Sealed::seal("<p>Embargoed.</p>", "hunter2", TEST_ITERATIONS).unwrap()
+    }
+
+    #[test]
+    fn sealed_content_opens_with_its_passphrase_only() {
+        let sealed = sample_sealed();
+        assert_eq!(sealed.open("hunter2").unwrap(), "<p>Embargoed.</p>");
+        assert!(sealed.open("hunter3").is_err());
+        assert!(!String::from_utf8_lossy(&sealed.ciphertext).contains("Embargoed"));
+    }
+
+    #[test]
+    fn every_seal_is_salted_afresh() {
+        let (a, b) = (sample_sealed(), sample_sealed());
+        assert_ne!(a.salt, b.salt);
+        assert_ne!(a.ciphertext, b.ciphertext);
+        assert_eq!(a.salt.len(), SALT_LEN);
+    }
+
+    #[test]
+    fn slugs_must_be_unique_and_url_safe() {
+        let dir = std::env::temp_dir().join(format!("protected-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(PROTECTED_PATH);
+        let page =
+            "[[page]]\nslug = \"a\"\ntitle = \"A\"\nsource = \"a.html\"\npassphrase_env = \"A\"\n";
+        std::fs::write(&path, page.repeat(2)).unwrap();
+        assert!(ProtectedPages::load(&path).unwrap_err().contains("\"a\""));
+        for bad in ["..", "a b", "A", "a?b", ""] {
+            std::fs::write(
+                &path,
+                page.replace("\"a\"\ntitle", &format!("\"{}\"\ntitle", bad)),
+            )
+            .unwrap();
+            assert!(ProtectedPages::load(&path).is_err(), "{:?}", bad);
+        }
+        std::fs::write(&path, page).unwrap();
+        assert_eq!(ProtectedPages::load(&path).unwrap().unwrap().pages.len(), 1);
+        assert!(ProtectedPages::load(&dir.join("missing.toml"))
+            .unwrap()
+            .is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+//! # Build Provenance
+//!
+//! Identifies the generator build behind every page: crate version, git
+//! commit, and build time, recorded by `build.rs`. Rendered as the
+//! `<meta name="generator">` tag and the footer version link.
+
+use crate::changelog::REPO_URL;
+
+/// Crate version, e.g. `0.1.0`.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Full commit hash, or empty when built outside a git checkout.
+pub const GIT_HASH: &str = env!("ESART_GIT_HASH");
+
+/// RFC 3339 UTC build time, e.g. `2025-01-17T12:00:00Z`.
+pub const BUILD_TIME: &str = env!("ESART_BUILD_TIME");
+
+/// Abbreviated commit hash, or `None` without git information.
+pub fn short_hash() -> Option<&'static str> {
+    (!GIT_HASH.is_empty()).then(|| &GIT_HASH[..GIT_HASH.len().min(7)])
+}
+
+/// The commit on GitHub, or the repository when the commit is unknown.
+pub fn commit_url() -> String {
+    match short_hash() {
+        Some(_) => format!("{}/commit/{}", REPO_URL, GIT_HASH),
+        None => REPO_URL.to_string(),
+    }
+}
+
+/// Footer label, e.g. `v0.1.0 · abc1234`.
+pub fn version_label() -> String {
+    match short_hash() {
+        Some(hash) => format!("v{} · {}", VERSION, hash),
+        None => format!("v{}", VERSION),
+    }
+}
+
+/// `<meta name="generator">` content, e.g.
+/// `everythingsings 0.1.0 (abc1234, 2025-01-17T12:00:00Z)`.
+pub fn generator() -> String {
+    match short_hash() {
+        Some(hash) => format!(
+            "{} {} ({}, {})",
+            env!("CARGO_PKG_NAME"),
+            VERSION,
+            hash,
+            BUILD_TIME
+        ),
+        None => format!("{} {} ({})", env!("CARGO_PKG_NAME"), VERSION, BUILD_TIME),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_time_is_rfc3339_utc() {
+        assert_eq!(BUILD_TIME.len(), 20, "{}", BUILD_TIME);
+        assert!(BUILD_TIME.ends_with('Z'));
+        assert_eq!(&BUILD_TIME[10..11], "T");
+    }
+
+    #[test]
+    fn labels_agree_with_commit() {
+        assert!(version_label().starts_with(&format!("v{}", VERSION)));
+        assert!(generator().contains(BUILD_TIME));
+        match short_hash() {
+            Some(hash) => {
+                assert!(version_label().ends_with(hash));
+                assert!(commit_url().ends_with(GIT_HASH));
+            }
+            None => assert_eq!(commit_url(), REPO_URL),
+        }
+    }
+}
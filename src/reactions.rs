@@ -0,0 +1,109 @@
+//! # Reaction Counts
+//!
+//! Favourites, boosts, reactions, and zaps collected by a page's syndicated
+//! copies, fetched at build time from Mastodon ([`crate::mastodon`]) and
+//! Nostr ([`crate::nostr`]). Pages show them as small static counters
+//! linking to each copy; the numbers are as fresh as the last build.
+
+use serde_json::{json, Value};
+
+/// One counted kind of interaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Count {
+    /// Plural display label, e.g. `boosts`.
+    pub label: &'static str,
+    /// Schema.org action type counted, e.g. `ShareAction`.
+    pub action: &'static str,
+    pub value: u64,
+}
+
+/// The reactions to one syndicated copy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reactions {
+    /// Network name shown on the counter, e.g. `Mastodon`.
+    pub network: &'static str,
+    /// Web URL of the copy, where people can react.
+    pub url: String,
+    pub counts: Vec<Count>,
+}
+
+impl Reactions {
+    /// The counts as Schema.org `InteractionCounter`s.
+    pub fn to_interaction_statistic(&self) -> Vec<Value> {
+        self.counts
+            .iter()
+            .map(|c| {
+                json!({
+                    "@type": "InteractionCounter",
+                    "interactionType": format!("https://schema.org/{}", c.action),
+                    "userInteractionCount": c.value,
+                    "interactionService": { "@type": "WebSite", "name": self.network, "url": self.url },
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads a count at `value[key]`, accepting numbers or numeric strings.
+pub fn count_at(value: &Value, key: &str) -> Option<u64> {
+    let field = value.get(key)?;
+    field
+        .as_u64()
+        .or_else(|| field.as_str().and_then(|s| s.parse().ok()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub fn sample_reactions() -> Vec<Reactions> {
+        vec![
+            Reactions {
+                network: "Mastodon",
+                url: "https://mastodon.test/@everythingsings/1".to_string(),
+                counts: vec![
+                    Count {
+                        label: "favourites",
+                        action: "LikeAction",
+                        value: 12,
+                    },
+                    Count {
+                        label: "boosts",
+                        action: "ShareAction",
+                        value: 3,
+                    },
+                ],
+            },
+            Reactions {
+                network: "Nostr",
+                url: "https://njump.me/note1test".to_string(),
+                counts: vec![Count {
+                    label: "zaps",
+                    action: "DonateAction",
+                    value: 2,
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn counts_become_interaction_counters() {
+        let stats = sample_reactions()[0].to_interaction_statistic();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[1]["interactionType"],
+            "https://schema.org/ShareAction"
+        );
+        assert_eq!(stats[1]["userInteractionCount"], 3);
+        assert_eq!(stats[1]["interactionService"]["name"], "Mastodon");
+    }
+
+    #[test]
+    fn counts_accept_numbers_and_strings() {
+        let value = json!({ "a": 4, "b": "5", "c": "x" });
+        assert_eq!(count_at(&value, "a"), Some(4));
+        assert_eq!(count_at(&value, "b"), Some(5));
+        assert_eq!(count_at(&value, "c"), None);
+        assert_eq!(count_at(&value, "d"), None);
+    }
+}
@@ -0,0 +1,36 @@
+//! # Redirects
+//!
+//! GitHub Pages can't answer with a `301`, so a page that moved leaves a
+//! stub at its old path: a `meta refresh` to the new URL, with a canonical
+//! link so search engines move their index too, and a plain link for
+//! clients that don't follow either.
+
+use crate::components::NOINDEX_META;
+use crate::escape::escape_html;
+
+/// The stub left at a moved page's old path, sending visitors to `to`.
+pub fn page(to: &str) -> String {
+    let to = escape_html(to);
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Moved</title>\n{NOINDEX_META}\n\
+         <link rel=\"canonical\" href=\"{to}\">\n\
+         <meta http-equiv=\"refresh\" content=\"0; url={to}\">\n</head>\n\
+         <body>\n<h1>Moved</h1>\n<p>This page moved to <a href=\"{to}\">{to}</a>.</p>\n</body>\n</html>\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stubs_point_at_the_new_url() {
+        let html = page("https://example.com/art/cafe/?a=1&b=2");
+        let to = "https://example.com/art/cafe/?a=1&amp;b=2";
+        assert!(html.contains(&format!("<link rel=\"canonical\" href=\"{}\">", to)));
+        assert!(html.contains(&format!("content=\"0; url={}\"", to)));
+        assert!(html.contains(&format!("<a href=\"{}\">", to)));
+        assert!(html.contains(NOINDEX_META));
+    }
+}
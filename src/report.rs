@@ -0,0 +1,263 @@
+//! # Build Reports
+//!
+//! Records every file written to the output tree along with its size, and
+//! compares two reports so a large content change can be reviewed before
+//! deploying. Files under `/.well-known/` also get a content hash: a
+//! changed key or address there rarely changes the size.
+
+use crate::well_known::WELL_KNOWN_DIR;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::Path;
+
+/// Default location of the build report, outside the deployed tree.
+pub const REPORT_PATH: &str = "target/build-report.json";
+
+/// A single generated file and its size in bytes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReportEntry {
+    pub path: String,
+    pub bytes: u64,
+    /// SHA-256 of the content, for files under `/.well-known/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Snapshot of a generated site, sorted by path.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildReport {
+    pub files: Vec<ReportEntry>,
+}
+
+impl BuildReport {
+    /// Walks `dir` and records every file relative to it.
+    pub fn from_dir(dir: &Path) -> std::io::Result<Self> {
+        let mut files = Vec::new();
+        collect_files(dir, dir, &mut files)?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(Self { files })
+    }
+
+    /// Total size of all recorded files.
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|f| f.bytes).sum()
+    }
+
+    /// Number of HTML pages.
+    pub fn page_count(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| f.path.ends_with(".html"))
+            .count()
+    }
+
+    /// Serializes the report as pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("build report serializes")
+    }
+
+    /// Reads a report previously written with [`BuildReport::to_json`].
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<ReportEntry>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+        } else {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let url = format!("/{}", relative.to_string_lossy().replace('\\', "/"));
+            let sha256 = if url.starts_with(&format!("/{}/", WELL_KNOWN_DIR)) {
+                let digest = Sha256::digest(std::fs::read(&path)?);
+                Some(digest.iter().map(|b| format!("{:02x}", b)).collect())
+            } else {
+                None
+            };
+            out.push(ReportEntry {
+                path: url,
+                bytes: entry.metadata()?.len(),
+                sha256,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A file present in both reports whose size (or recorded hash) differs.
+#[derive(Debug, PartialEq)]
+pub struct ChangedEntry {
+    pub path: String,
+    pub old_bytes: u64,
+    pub new_bytes: u64,
+}
+
+impl ChangedEntry {
+    /// Size difference, positive when the file grew.
+    pub fn delta(&self) -> i64 {
+        self.new_bytes as i64 - self.old_bytes as i64
+    }
+}
+
+/// Differences between two build reports.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReportDiff {
+    pub added: Vec<ReportEntry>,
+    pub removed: Vec<ReportEntry>,
+    pub changed: Vec<ChangedEntry>,
+    pub old_total: u64,
+    pub new_total: u64,
+}
+
+impl ReportDiff {
+    /// Compares `old` against `new`.
+    pub fn between(old: &BuildReport, new: &BuildReport) -> Self {
+        let mut diff = ReportDiff {
+            old_total: old.total_bytes(),
+            new_total: new.total_bytes(),
+            ..Default::default()
+        };
+
+        for entry in &new.files {
+            match old.files.iter().find(|o| o.path == entry.path) {
+                None => diff.added.push(entry.clone()),
+                Some(o) if o.bytes != entry.bytes || o.sha256 != entry.sha256 => {
+                    diff.changed.push(ChangedEntry {
+                        path: entry.path.clone(),
+                        old_bytes: o.bytes,
+                        new_bytes: entry.bytes,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        diff.removed = old
+            .files
+            .iter()
+            .filter(|o| !new.files.iter().any(|n| n.path == o.path))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    /// True when both reports describe the same files and sizes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for ReportDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+        for entry in &self.added {
+            writeln!(f, "+ {} ({} bytes)", entry.path, entry.bytes)?;
+        }
+        for entry in &self.removed {
+            writeln!(f, "- {} ({} bytes)", entry.path, entry.bytes)?;
+        }
+        for entry in &self.changed {
+            writeln!(
+                f,
+                "~ {} ({} -> {} bytes, {:+})",
+                entry.path,
+                entry.old_bytes,
+                entry.new_bytes,
+                entry.delta()
+            )?;
+        }
+        writeln!(
+            f,
+            "\n{} added, {} removed, {} changed; total {} -> {} bytes ({:+})",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len(),
+            self.old_total,
+            self.new_total,
+            self.new_total as i64 - self.old_total as i64
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(files: &[(&str, u64)]) -> BuildReport {
+        BuildReport {
+            files: files
+                .iter()
+                .map(|(path, bytes)| ReportEntry {
+                    path: path.to_string(),
+                    bytes: *bytes,
+                    sha256: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn page_count_includes_only_html() {
+        let a = report(&[("/index.html", 1), ("/cv/index.html", 1), ("/main.css", 1)]);
+        assert_eq!(a.page_count(), 2);
+    }
+
+    #[test]
+    fn identical_reports_have_no_diff() {
+        let a = report(&[("/index.html", 100)]);
+        assert!(ReportDiff::between(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn diff_detects_added_removed_and_changed() {
+        let old = report(&[("/index.html", 100), ("/old.html", 50)]);
+        let new = report(&[("/index.html", 120), ("/new.html", 70)]);
+        let diff = ReportDiff::between(&old, &new);
+        assert_eq!(diff.added[0].path, "/new.html");
+        assert_eq!(diff.removed[0].path, "/old.html");
+        assert_eq!(diff.changed[0].delta(), 20);
+        assert_eq!(diff.new_total as i64 - diff.old_total as i64, 40);
+    }
+
+    #[test]
+    fn well_known_changes_of_the_same_size_are_detected() {
+        let dir = std::env::temp_dir().join(format!("report-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".well-known")).unwrap();
+        let write = |key: &str| {
+            std::fs::write(dir.join(".well-known/nostr.json"), key).unwrap();
+            std::fs::write(dir.join("index.html"), key).unwrap();
+            BuildReport::from_dir(&dir).unwrap()
+        };
+        let (old, new) = (write("aaaa"), write("bbbb"));
+        std::fs::remove_dir_all(&dir).unwrap();
+        let diff = ReportDiff::between(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].path, "/.well-known/nostr.json");
+        assert_eq!(diff.changed[0].delta(), 0);
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let a = report(&[("/index.html", 100), ("/art/index.html", 42)]);
+        let parsed: BuildReport = serde_json::from_str(&a.to_json()).unwrap();
+        assert_eq!(parsed, a);
+    }
+
+    #[test]
+    fn display_lists_each_change() {
+        let old = report(&[("/a.html", 10)]);
+        let new = report(&[("/a.html", 5), ("/b.html", 1)]);
+        let text = ReportDiff::between(&old, &new).to_string();
+        assert!(text.contains("+ /b.html"));
+        assert!(text.contains("~ /a.html (10 -> 5 bytes, -5)"));
+    }
+}
@@ -0,0 +1,145 @@
+//! # Responsive Images
+//!
+//! Width variants of raster avatars for `srcset`. Variants are written next
+//! to the original at build time (`/avatar.png` → `/avatar-256.png`), so a
+//! 2x or 3x display downloads a sharp image and a 1x display a small one.
+//!
+//! Images are never upscaled: widths at or above the original's are skipped,
+//! and the original itself closes the `srcset` at its real width.
+
+use image::imageops::FilterType;
+use image::ImageFormat;
+use std::path::Path;
+
+/// Directory site-relative image paths resolve against.
+const PUBLIC_DIR: &str = "public";
+
+/// Avatar widths in pixels: 1x, 2x, and 3x of the displayed size.
+pub const AVATAR_WIDTHS: &[u32] = &[128, 256, 384];
+
+/// Displayed avatar width, for the `sizes` attribute.
+pub const AVATAR_SIZES: &str = "128px";
+
+/// Whether `path` is a raster image that variants can be generated for,
+/// i.e. one of the formats the `image` dependency is built to decode.
+pub fn is_raster(path: &str) -> bool {
+    let ext = path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    matches!(ext.as_deref(), Some("png" | "jpg" | "jpeg"))
+}
+
+/// Pixel width of the image at site-relative `path` under `public/`; `None`
+/// when it is missing or not a raster image.
+pub fn source_width(path: &str) -> Option<u32> {
+    if !is_raster(path) {
+        return None;
+    }
+    let source = Path::new(PUBLIC_DIR).join(path.trim_start_matches('/'));
+    image::image_dimensions(source).ok().map(|(width, _)| width)
+}
+
+/// The `widths` strictly below `original`, which get variants.
+pub fn variant_widths(widths: &[u32], original: u32) -> Vec<u32> {
+    widths.iter().copied().filter(|&w| w < original).collect()
+}
+
+/// Path of the `width` variant of `path`, e.g. `/avatar-256.png`.
+pub fn variant_path(path: &str, width: u32) -> String {
+    match path.rsplit_once('.') {
+        Some((stem, ext)) if !stem.ends_with('/') => format!("{}-{}.{}", stem, width, ext),
+        _ => format!("{}-{}", path, width),
+    }
+}
+
+/// `srcset` value listing the variants of `path` below `original` pixels
+/// wide, then `path` itself at `original`; `None` for vector or unknown
+/// formats, which need no variants.
+pub fn srcset(path: &str, widths: &[u32], original: u32) -> Option<String> {
+    if !is_raster(path) || widths.is_empty() {
+        return None;
+    }
+    let mut candidates: Vec<String> = variant_widths(widths, original)
+        .into_iter()
+        .map(|w| format!("{} {}w", variant_path(path, w), w))
+        .collect();
+    candidates.push(format!("{} {}w", path, original));
+    Some(candidates.join(", "))
+}
+
+/// Writes the variants of `source` into `dir`, named by [`variant_path`],
+/// returning how many were written.
+///
+/// Widths at or above the original's are skipped, matching [`srcset`].
+pub fn write_variants(source: &Path, dir: &Path, widths: &[u32]) -> std::io::Result<usize> {
+    let data = std::fs::read(source)?;
+    let file_name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| std::io::Error::other(format!("Bad image path {}", source.display())))?;
+    let format = ImageFormat::from_path(source).map_err(std::io::Error::other)?;
+    let original =
+        image::load_from_memory_with_format(&data, format).map_err(std::io::Error::other)?;
+
+    std::fs::create_dir_all(dir)?;
+    let widths = variant_widths(widths, original.width());
+    for &width in &widths {
+        let target = dir.join(variant_path(file_name, width));
+        let height =
+            (original.height() as u64 * width as u64 / original.width() as u64).max(1) as u32;
+        original
+            .resize_exact(width, height, FilterType::Lanczos3)
+            .save_with_format(target, format)
+            .map_err(std::io::Error::other)?;
+    }
+    Ok(widths.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variants_are_named_by_width() {
+        assert_eq!(variant_path("/avatar.png", 256), "/avatar-256.png");
+        assert_eq!(variant_path("/img/a.b.jpg", 128), "/img/a.b-128.jpg");
+    }
+
+    #[test]
+    fn srcset_lists_raster_variants_only() {
+        assert_eq!(
+            srcset("/avatar.png", &[128, 256], 512).as_deref(),
+            Some("/avatar-128.png 128w, /avatar-256.png 256w, /avatar.png 512w")
+        );
+        assert_eq!(srcset("/logo.svg", AVATAR_WIDTHS, 512), None);
+        assert_eq!(srcset("/photo.webp", AVATAR_WIDTHS, 512), None);
+    }
+
+    #[test]
+    fn srcset_never_offers_upscaled_widths() {
+        assert_eq!(
+            srcset("/avatar.png", AVATAR_WIDTHS, 256).as_deref(),
+            Some("/avatar-128.png 128w, /avatar.png 256w")
+        );
+        assert_eq!(
+            srcset("/avatar.png", AVATAR_WIDTHS, 100).as_deref(),
+            Some("/avatar.png 100w")
+        );
+    }
+
+    #[test]
+    fn variants_are_resized_without_upscaling() {
+        let dir = std::env::temp_dir().join(format!("esart-responsive-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("a.png");
+        image::RgbImage::new(200, 100).save(&source).unwrap();
+
+        let out = dir.join("out");
+        assert_eq!(write_variants(&source, &out, &[100, 200, 400]).unwrap(), 1);
+        let small = image::open(out.join("a-100.png")).unwrap();
+        assert_eq!((small.width(), small.height()), (100, 50));
+        assert!(!out.join("a-200.png").exists());
+        assert!(!out.join("a-400.png").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
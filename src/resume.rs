@@ -0,0 +1,306 @@
+//! # Resume
+//!
+//! Reads `resume.toml`, whose tables mirror the [JSON Resume] schema, for
+//! the human `/cv/` page and the machine-readable `/resume.json`.
+//!
+//! `basics.profiles` is not configured by hand: it is filled from the
+//! profile links so the CV and homepage never disagree.
+//!
+//! [JSON Resume]: https://jsonresume.org/schema
+//!
+//! ## Example
+//!
+//! ```toml
+//! [basics]
+//! name = "EverythingSings"
+//! label = "Digital Artist"
+//! summary = "Formless art brand."
+//!
+//! [[projects]]
+//! name = "Lumimenta"
+//! description = "Physical art trading cards."
+//! url = "https://lumimenta.everythingsings.art"
+//! startDate = "2024"
+//! ```
+
+use crate::components::LINKS;
+use crate::escape::json_script;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// Resume configuration file, relative to the project root.
+pub const RESUME_PATH: &str = "resume.toml";
+
+/// JSON Resume schema the export conforms to.
+pub const JSON_RESUME_SCHEMA: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// A resume in JSON Resume structure.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Resume {
+    pub basics: Basics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<Work>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<Project>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<Education>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<Skill>,
+}
+
+/// Who the resume is about.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Basics {
+    pub name: String,
+    /// Headline, e.g. "Digital Artist".
+    #[serde(default)]
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub summary: String,
+}
+
+/// A position held.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    /// Organization name.
+    pub name: String,
+    pub position: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// ISO 8601 date (`YYYY`, `YYYY-MM`, or `YYYY-MM-DD`).
+    pub start_date: String,
+    /// Omitted for current positions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+/// A project.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+/// Formal education.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Education {
+    pub institution: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub area: String,
+    #[serde(default)]
+    pub study_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+}
+
+/// A skill area with related keywords.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Skill {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+/// Formats a start/end pair for display, e.g. `2021 – Present`.
+pub fn date_range(start: Option<&str>, end: Option<&str>) -> Option<String> {
+    match (start, end) {
+        (Some(s), Some(e)) => Some(format!("{} – {}", s, e)),
+        (Some(s), None) => Some(format!("{} – Present", s)),
+        (None, Some(e)) => Some(e.to_string()),
+        (None, None) => None,
+    }
+}
+
+impl Resume {
+    /// Loads the resume from `path`; `Ok(None)` when the file is absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    /// Generates `/resume.json` in JSON Resume format, with
+    /// `basics.profiles` taken from the profile links.
+    pub fn to_json_resume(&self) -> String {
+        let mut doc = serde_json::to_value(self).expect("resume serializes");
+        doc["$schema"] = json!(JSON_RESUME_SCHEMA);
+        doc["basics"]["profiles"] = Value::Array(
+            LINKS
+                .iter()
+                .map(|link| json!({ "network": link.label, "url": link.href }))
+                .collect(),
+        );
+        serde_json::to_string_pretty(&doc).expect("resume serializes")
+    }
+
+    /// Builds the `/cv/` JSON-LD: a `Person` with one `Occupation` per
+    /// position (or the headline when there is no work history).
+    ///
+    /// `id` should be the primary entity's `@id` so crawlers merge this node
+    /// with the homepage's.
+    pub fn to_json_ld(&self, id: &str, url: &str) -> String {
+        let occupations: Vec<Value> = if self.work.is_empty() {
+            (!self.basics.label.is_empty())
+                .then(|| json!({ "@type": "Occupation", "name": self.basics.label }))
+                .into_iter()
+                .collect()
+        } else {
+            self.work
+                .iter()
+                .map(|w| {
+                    json!({
+                        "@type": "Occupation",
+                        "name": w.position,
+                        "description": w.summary,
+                    })
+                })
+                .collect()
+        };
+
+        let mut person = json!({
+            "@context": "https://schema.org",
+            "@type": "Person",
+            "@id": id,
+            "name": self.basics.name,
+            "jobTitle": self.basics.label,
+            "description": self.basics.summary,
+            "url": url,
+            "hasOccupation": occupations,
+        });
+        if !self.skills.is_empty() {
+            person["knowsAbout"] = json!(self.skills.iter().map(|s| &s.name).collect::<Vec<_>>());
+        }
+        if !self.education.is_empty() {
+            person["alumniOf"] = Value::Array(
+                self.education
+                    .iter()
+                    .map(|e| json!({ "@type": "EducationalOrganization", "name": e.institution }))
+                    .collect(),
+            );
+        }
+        json_script(&person)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn sample_resume() -> Resume {
+        toml::from_str(
+            r#"
+[basics]
+name = "Ada"
+label = "Digital Artist"
+summary = "Makes things."
+
+[[work]]
+name = "Studio"
+position = "Artist"
+startDate = "2021-03"
+highlights = ["Shipped a thing"]
+
+[[projects]]
+name = "Cards"
+description = "Trading cards."
+url = "https://example.com/cards"
+startDate = "2024"
+
+[[skills]]
+name = "Rust"
+keywords = ["Leptos"]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn missing_config_has_no_resume() {
+        assert_eq!(Resume::load(Path::new("does-not-exist.toml")), Ok(None));
+    }
+
+    #[test]
+    fn json_resume_uses_schema_field_names() {
+        let json: Value = serde_json::from_str(&sample_resume().to_json_resume()).unwrap();
+        assert_eq!(json["$schema"], JSON_RESUME_SCHEMA);
+        assert_eq!(json["basics"]["name"], "Ada");
+        assert_eq!(json["work"][0]["startDate"], "2021-03");
+        assert!(json["work"][0].get("endDate").is_none());
+        assert_eq!(json["projects"][0]["url"], "https://example.com/cards");
+        assert!(
+            json.get("education").is_none(),
+            "Empty sections are omitted"
+        );
+    }
+
+    #[test]
+    fn profiles_come_from_links() {
+        let json: Value = serde_json::from_str(&sample_resume().to_json_resume()).unwrap();
+        let profiles = json["basics"]["profiles"].as_array().unwrap();
+        assert_eq!(profiles.len(), LINKS.len());
+        assert_eq!(profiles[0]["url"], LINKS[0].href);
+    }
+
+    #[test]
+    fn date_ranges_are_open_ended() {
+        assert_eq!(
+            date_range(Some("2021"), None).as_deref(),
+            Some("2021 – Present")
+        );
+        assert_eq!(date_range(None, None), None);
+    }
+
+    #[test]
+    fn json_ld_has_occupation_per_position() {
+        let json: Value = serde_json::from_str(
+            &sample_resume().to_json_ld("https://x.test/#me", "https://x.test/cv/"),
+        )
+        .unwrap();
+        assert_eq!(json["@type"], "Person");
+        assert_eq!(json["@id"], "https://x.test/#me");
+        assert_eq!(json["hasOccupation"][0]["@type"], "Occupation");
+        assert_eq!(json["hasOccupation"][0]["name"], "Artist");
+        assert_eq!(json["knowsAbout"], json!(["Rust"]));
+    }
+
+    #[test]
+    fn headline_is_occupation_without_work_history() {
+        let resume = Resume {
+            work: vec![],
+            ..sample_resume()
+        };
+        let json: Value = serde_json::from_str(&resume.to_json_ld("id", "url")).unwrap();
+        assert_eq!(json["hasOccupation"][0]["name"], "Digital Artist");
+    }
+}
@@ -0,0 +1,187 @@
+//! # Site Graph
+//!
+//! The homepage JSON-LD: one `@graph` document combining every node the
+//! site describes, each with a stable `@id` so other pages and sites can
+//! refer to it:
+//!
+//! | Node | `@id` |
+//! |------|-------|
+//! | [Entities](crate::entity) (`Person`, `Organization`) | `/#<key>` |
+//! | The site (`WebSite`) | `/#website` |
+//! | The homepage (`WebPage`) | `/#webpage` |
+//! | [Related properties](crate::properties) (`WebSite`) | `<property>/#website` |
+//!
+//! Creative works join as [`Graph::push`]ed nodes referencing these `@id`s.
+//! Pages assemble their graph from what their components contribute (see
+//! [`crate::structured_data`]); [`site_graph`] is the homepage's graph
+//! built directly.
+
+use crate::components::LinkEntry;
+use crate::config::{PROPERTIES, SITE_DESCRIPTION, SITE_LANGUAGE, SITE_NAME};
+use crate::entity::{entity_nodes, Entity};
+use crate::escape::json_script;
+use crate::properties::Property;
+use crate::url::absolute;
+use serde_json::{json, Value};
+
+/// The JSON-LD `@id` of the site's `WebSite`.
+pub fn website_id() -> String {
+    absolute("/#website")
+}
+
+/// The JSON-LD `@id` of the `WebPage` at `url`.
+pub fn webpage_id(url: &str) -> String {
+    format!("{}#webpage", url)
+}
+
+/// A JSON-LD `@graph` document.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    nodes: Vec<Value>,
+}
+
+impl Graph {
+    /// Adds `node`, which should carry an `@id`.
+    pub fn push(&mut self, node: Value) {
+        self.nodes.push(node);
+    }
+
+    /// The node with `@id` `id`.
+    pub fn node(&self, id: &str) -> Option<&Value> {
+        self.nodes.iter().find(|node| node["@id"] == id)
+    }
+
+    /// The graph as a JSON-LD document, safe inside a `<script>` element.
+    pub fn to_json_ld(&self) -> String {
+        json_script(&json!({
+            "@context": "https://schema.org",
+            "@graph": self.nodes,
+        }))
+    }
+}
+
+/// The site's `WebSite` node, published by `publisher` and made up of
+/// `properties`.
+pub fn website_node(publisher: &str, properties: &[Property]) -> Value {
+    let mut site = json!({
+        "@type": "WebSite",
+        "@id": website_id(),
+        "name": SITE_NAME,
+        "url": absolute("/"),
+        "description": SITE_DESCRIPTION,
+        "inLanguage": SITE_LANGUAGE,
+        "publisher": { "@id": publisher },
+    });
+    if !properties.is_empty() {
+        let parts: Vec<Value> = properties
+            .iter()
+            .map(|p| json!({ "@id": p.id() }))
+            .collect();
+        site["hasPart"] = json!(parts);
+    }
+    site
+}
+
+/// The `WebPage` node at `url`, part of the site and about `subject`.
+pub fn webpage_node(url: &str, name: &str, description: &str, subject: &str) -> Value {
+    json!({
+        "@type": "WebPage",
+        "@id": webpage_id(url),
+        "url": url,
+        "name": name,
+        "description": description,
+        "inLanguage": SITE_LANGUAGE,
+        "isPartOf": { "@id": website_id() },
+        "about": { "@id": subject },
+        "mainEntity": { "@id": subject },
+    })
+}
+
+/// The homepage's nodes besides the entities: the site, published by
+/// `primary`, the homepage about it, and the configured properties.
+pub fn site_nodes(primary: &Entity) -> Vec<Value> {
+    let mut nodes = vec![
+        website_node(&primary.id(), PROPERTIES),
+        webpage_node(
+            &absolute("/"),
+            primary.name,
+            SITE_DESCRIPTION,
+            &primary.id(),
+        ),
+    ];
+    nodes.extend(PROPERTIES.iter().map(Property::json_ld_node));
+    nodes
+}
+
+/// Builds the homepage graph: `entities` (the one keyed `primary` described
+/// by `links`), then the [`site_nodes`].
+pub fn site_graph(entities: &[Entity], primary: &str, links: &[&LinkEntry]) -> Graph {
+    let mut graph = Graph::default();
+    for node in entity_nodes(entities, primary, links) {
+        graph.push(node);
+    }
+    if let Some(primary) = entities.iter().find(|e| e.key == primary) {
+        for node in site_nodes(primary) {
+            graph.push(node);
+        }
+    }
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::tests::{ARTIST, BRAND};
+
+    fn parse(graph: &Graph) -> Value {
+        serde_json::from_str(&graph.to_json_ld()).expect("JSON-LD should be valid JSON")
+    }
+
+    #[test]
+    fn graph_combines_entities_site_and_page() {
+        let graph = site_graph(&[ARTIST, BRAND], "brand", &[]);
+        let json = parse(&graph);
+        assert_eq!(json["@context"], "https://schema.org");
+        let types: Vec<&str> = json["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|node| node["@type"].as_str())
+            .collect();
+        assert_eq!(
+            &types[..4],
+            ["Organization", "Person", "WebSite", "WebPage"]
+        );
+        assert_eq!(types.len(), 4 + PROPERTIES.len());
+    }
+
+    #[test]
+    fn ids_are_unique_and_references_resolve() {
+        let graph = site_graph(&[ARTIST, BRAND], "brand", &[]);
+        let json = parse(&graph);
+        let nodes = json["@graph"].as_array().unwrap();
+        let mut ids: Vec<&str> = nodes.iter().filter_map(|n| n["@id"].as_str()).collect();
+        assert_eq!(ids.len(), nodes.len(), "Every node has an @id");
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), nodes.len(), "No @id repeats");
+
+        let page = graph
+            .node(&webpage_id("https://everythingsings.art/"))
+            .unwrap();
+        assert_eq!(page["mainEntity"]["@id"], BRAND.id());
+        assert!(graph
+            .node(page["isPartOf"]["@id"].as_str().unwrap())
+            .is_some());
+        let site = graph.node(&website_id()).unwrap();
+        assert_eq!(site["publisher"]["@id"], BRAND.id());
+        for part in site["hasPart"].as_array().into_iter().flatten() {
+            assert!(graph.node(part["@id"].as_str().unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn site_has_parts_only_with_properties() {
+        assert!(website_node("x", &[]).get("hasPart").is_none());
+    }
+}
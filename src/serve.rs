@@ -0,0 +1,395 @@
+//! # Static File Server
+//!
+//! Serves a generated site over HTTP for self-hosting. `serve` reads from
+//! `target/site/` on disk; `serve --release` serves the copy embedded into
+//! the binary by the `embed` feature, so the whole site deploys as one
+//! executable:
+//!
+//! ```bash
+//! cargo run --release -- --generate-static
+//! cargo build --release --features embed
+//! ./target/release/everythingsings serve --release --addr 0.0.0.0:8080
+//! ```
+//!
+//! Responses carry a content type, an `ETag` (SHA-256 of the body) with
+//! `If-None-Match` revalidation, cache headers (HTML revalidates, assets are
+//! cached for a day), and `nosniff`/referrer headers. Pages also carry a
+//! `Link` header advertising the webmention and pingback endpoints, when
+//! configured (see [`crate::webmention::endpoints`]).
+
+use crate::text::encode_path_segment;
+use crate::webmention::{endpoints, link_header};
+use crate::well_known::{self, WELL_KNOWN_DIR};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// Default listen address.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+/// A tree of site files addressed by relative path (`cv/index.html`).
+pub trait FileSource {
+    fn read(&self, path: &str) -> Option<Cow<'static, [u8]>>;
+}
+
+/// Files in a directory on disk.
+pub struct DiskFiles(pub PathBuf);
+
+impl FileSource for DiskFiles {
+    fn read(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        let full = self.0.join(path);
+        full.is_file()
+            .then(|| std::fs::read(full).ok())
+            .flatten()
+            .map(Cow::Owned)
+    }
+}
+
+/// The generated site embedded at compile time.
+#[cfg(feature = "embed")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "target/site/"]
+pub struct EmbeddedFiles;
+
+#[cfg(feature = "embed")]
+impl FileSource for EmbeddedFiles {
+    fn read(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        <EmbeddedFiles as rust_embed::RustEmbed>::get(path).map(|file| file.data)
+    }
+}
+
+/// A response ready to send.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// The value of header `name`, if set.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Content type for a file, by extension.
+pub fn content_type(path: &str) -> &'static str {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("webmanifest") => "application/manifest+json",
+        Some("xml") => "application/xml",
+        Some("txt") | None => "text/plain; charset=utf-8",
+        Some("gmi") => "text/gemini; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("gif") => "image/gif",
+        Some("ico") => "image/x-icon",
+        Some("glsl") | Some("frag") | Some("vert") => "text/plain; charset=utf-8",
+        Some("woff2") => "font/woff2",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// `Cache-Control` for a file: pages and feeds revalidate every time, other
+/// assets are cached for a day.
+pub fn cache_control(path: &str) -> &'static str {
+    let revalidate = [".html", ".xml", ".json", ".txt"];
+    if revalidate.iter().any(|ext| path.ends_with(ext)) {
+        "no-cache"
+    } else {
+        "public, max-age=86400"
+    }
+}
+
+/// Decodes `%XX` escapes; `None` for malformed escapes or invalid UTF-8.
+pub fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Maps a request path to a file path, or `None` if it escapes the root.
+fn file_path(url_path: &str) -> Option<String> {
+    let path = url_path.split(['?', '#']).next().unwrap_or("");
+    let path = percent_decode(path)?;
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments
+        .iter()
+        .any(|s| *s == ".." || *s == "." || s.contains('\\'))
+    {
+        return None;
+    }
+    let mut file = segments.join("/");
+    if path.ends_with('/') {
+        if !file.is_empty() {
+            file.push('/');
+        }
+        file.push_str("index.html");
+    }
+    Some(file)
+}
+
+fn etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("\"{}\"", hex)
+}
+
+fn base_headers() -> Vec<(&'static str, String)> {
+    vec![
+        ("X-Content-Type-Options", "nosniff".to_string()),
+        (
+            "Referrer-Policy",
+            "strict-origin-when-cross-origin".to_string(),
+        ),
+    ]
+}
+
+fn not_found(files: &dyn FileSource, head: bool) -> Response {
+    let (content_type, body) = match files.read("404.html") {
+        Some(page) => ("text/html; charset=utf-8", page.into_owned()),
+        None => ("text/plain; charset=utf-8", b"Not Found\n".to_vec()),
+    };
+    let mut headers = base_headers();
+    headers.push(("Content-Type", content_type.to_string()));
+    Response {
+        status: 404,
+        headers,
+        body: if head { Vec::new() } else { body },
+    }
+}
+
+/// Builds the response for a request.
+///
+/// Directory paths without a trailing slash redirect to the slashed form,
+/// matching how GitHub Pages serves `index.html`.
+pub fn respond(
+    files: &dyn FileSource,
+    method: &str,
+    url_path: &str,
+    if_none_match: Option<&str>,
+) -> Response {
+    let head = method.eq_ignore_ascii_case("HEAD");
+    if !head && !method.eq_ignore_ascii_case("GET") {
+        let mut headers = base_headers();
+        headers.push(("Allow", "GET, HEAD".to_string()));
+        return Response {
+            status: 405,
+            headers,
+            body: Vec::new(),
+        };
+    }
+
+    let Some(path) = file_path(url_path) else {
+        return not_found(files, head);
+    };
+
+    let Some(body) = files.read(&path) else {
+        let index = format!("{}/index.html", path);
+        if !path.is_empty() && !path.ends_with(".html") && files.read(&index).is_some() {
+            let mut headers = base_headers();
+            let encoded: Vec<String> = path.split('/').map(encode_path_segment).collect();
+            headers.push(("Location", format!("/{}/", encoded.join("/"))));
+            return Response {
+                status: 301,
+                headers,
+                body: Vec::new(),
+            };
+        }
+        return not_found(files, head);
+    };
+
+    let tag = etag(&body);
+    let mut headers = base_headers();
+    headers.push(("ETag", tag.clone()));
+    headers.push(("Cache-Control", cache_control(&path).to_string()));
+
+    if if_none_match.is_some_and(|value| value.split(',').any(|t| t.trim() == tag)) {
+        return Response {
+            status: 304,
+            headers,
+            body: Vec::new(),
+        };
+    }
+
+    match path.strip_prefix(&format!("{}/", WELL_KNOWN_DIR)) {
+        Some(name) => {
+            headers.push(("Content-Type", well_known::content_type(name).to_string()));
+            headers.push(("Access-Control-Allow-Origin", "*".to_string()));
+        }
+        None => headers.push(("Content-Type", content_type(&path).to_string())),
+    }
+    if path.ends_with(".html") {
+        headers.extend(link_header(&endpoints()).map(|link| ("Link", link)));
+    }
+    Response {
+        status: 200,
+        headers,
+        body: if head { Vec::new() } else { body.into_owned() },
+    }
+}
+
+/// Serves `files` on `addr` until the process is stopped.
+pub fn serve(files: &dyn FileSource, addr: &str) -> std::io::Result<()> {
+    let server = tiny_http::Server::http(addr).map_err(std::io::Error::other)?;
+    println!("Serving on http://{}/ (Ctrl+C to stop)", addr);
+
+    for request in server.incoming_requests() {
+        let if_none_match = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("If-None-Match"))
+            .map(|h| h.value.to_string());
+        let response = respond(
+            files,
+            request.method().as_str(),
+            request.url(),
+            if_none_match.as_deref(),
+        );
+
+        let mut reply =
+            tiny_http::Response::from_data(response.body).with_status_code(response.status);
+        for (name, value) in &response.headers {
+            if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+                reply.add_header(header);
+            }
+        }
+        if let Err(e) = request.respond(reply) {
+            eprintln!("Warning: Could not send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MemoryFiles(HashMap<&'static str, &'static str>);
+
+    impl FileSource for MemoryFiles {
+        fn read(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+            self.0.get(path).map(|s| Cow::Borrowed(s.as_bytes()))
+        }
+    }
+
+    fn files() -> MemoryFiles {
+        MemoryFiles(HashMap::from([
+            ("index.html", "<h1>Home</h1>"),
+            ("cv/index.html", "<h1>CV</h1>"),
+            ("main.css", "body{}"),
+            ("art/café/index.html", "<h1>Café</h1>"),
+            (".well-known/webfinger", "{}"),
+        ]))
+    }
+
+    fn get(path: &str) -> Response {
+        respond(&files(), "GET", path, None)
+    }
+
+    #[test]
+    fn well_known_documents_are_typed_and_shared() {
+        let res = get("/.well-known/webfinger");
+        assert_eq!(res.header("content-type"), Some("application/jrd+json"));
+        assert_eq!(res.header("access-control-allow-origin"), Some("*"));
+        assert_eq!(get("/main.css").header("access-control-allow-origin"), None);
+    }
+
+    #[test]
+    fn serves_index_for_directories() {
+        let res = get("/");
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body, b"<h1>Home</h1>");
+        assert_eq!(res.header("content-type"), Some("text/html; charset=utf-8"));
+        assert_eq!(get("/cv/").body, b"<h1>CV</h1>");
+        assert_eq!(
+            get("/art/caf%C3%A9/?ref=x").body,
+            "<h1>Café</h1>".as_bytes()
+        );
+    }
+
+    #[test]
+    fn directories_without_slash_redirect() {
+        let res = get("/cv");
+        assert_eq!(res.status, 301);
+        assert_eq!(res.header("location"), Some("/cv/"));
+        assert_eq!(
+            get("/art/caf%C3%A9").header("location"),
+            Some("/art/caf%C3%A9/")
+        );
+    }
+
+    #[test]
+    fn assets_are_cached_and_pages_revalidate() {
+        assert_eq!(
+            get("/main.css").header("cache-control"),
+            Some("public, max-age=86400")
+        );
+        assert_eq!(get("/").header("cache-control"), Some("no-cache"));
+        assert_eq!(
+            get("/main.css").header("x-content-type-options"),
+            Some("nosniff")
+        );
+    }
+
+    #[test]
+    fn matching_etag_is_not_modified() {
+        let tag = get("/").header("etag").unwrap().to_string();
+        let res = respond(&files(), "GET", "/", Some(&tag));
+        assert_eq!(res.status, 304);
+        assert!(res.body.is_empty());
+    }
+
+    #[test]
+    fn traversal_and_missing_files_are_not_found() {
+        assert_eq!(get("/../Cargo.toml").status, 404);
+        assert_eq!(get("/%2e%2e/Cargo.toml").status, 404);
+        assert_eq!(get("/nope.html").status, 404);
+    }
+
+    #[test]
+    fn head_has_no_body_and_post_is_rejected() {
+        let res = respond(&files(), "HEAD", "/", None);
+        assert_eq!(res.status, 200);
+        assert!(res.body.is_empty());
+        assert_eq!(respond(&files(), "POST", "/", None).status, 405);
+    }
+
+    #[test]
+    fn content_types_follow_extension() {
+        assert_eq!(content_type("feed.xml"), "application/xml");
+        assert_eq!(
+            content_type("site.webmanifest"),
+            "application/manifest+json"
+        );
+        assert_eq!(content_type("CNAME"), "text/plain; charset=utf-8");
+        assert_eq!(content_type("a.bin"), "application/octet-stream");
+    }
+}
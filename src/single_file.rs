@@ -0,0 +1,172 @@
+//! # Single-File Export
+//!
+//! `--single-file` turns the generated homepage, which carries every section
+//! of the site, into one self-contained HTML file for email, archiving, or
+//! pinning to IPFS as a single object:
+//!
+//! - stylesheets are inlined as `<style>` elements;
+//! - images (`src`, and icon `href`s) become base64 `data:` URIs, and
+//!   `srcset`s are dropped in favour of the inlined `src`;
+//! - external scripts are removed (the page reads the same without the
+//!   shader background); inline JSON-LD stays;
+//! - every other root-relative link points at the live site, as do images
+//!   missing from the build (such as favicons not fetched offline).
+//!
+//! ```bash
+//! ./target/release/everythingsings --generate-static
+//! ./target/release/everythingsings --single-file
+//! ```
+
+use crate::serve::content_type;
+use crate::text::base64;
+use crate::url::absolute;
+use std::path::Path;
+
+/// Where `--single-file` writes the export, relative to the project root.
+pub const SINGLE_FILE_PATH: &str = "target/everythingsings.html";
+
+/// Inlines the stylesheets and images of `html`, a page generated into
+/// `root`, drops its external scripts, and makes its other links absolute.
+pub fn inline(html: &str, root: &Path) -> std::io::Result<String> {
+    let html = inline_stylesheets(html, root)?;
+    let html = remove_external_scripts(&html);
+    Ok(rewrite_urls(&html, root))
+}
+
+/// Reads the file behind the root-relative `path`.
+fn read(root: &Path, path: &str) -> std::io::Result<Vec<u8>> {
+    let file = root.join(path.trim_start_matches('/'));
+    std::fs::read(&file)
+        .map_err(|e| std::io::Error::other(format!("Could not read {}: {}", file.display(), e)))
+}
+
+/// `path` under `root` as a `data:` URI, or `None` when it is missing.
+fn data_uri(root: &Path, path: &str) -> Option<String> {
+    let data = std::fs::read(root.join(path.trim_start_matches('/'))).ok()?;
+    Some(format!(
+        "data:{};base64,{}",
+        content_type(path),
+        base64(&data)
+    ))
+}
+
+/// Replaces each `<link rel="stylesheet">` with a `<style>` holding the
+/// stylesheet.
+fn inline_stylesheets(html: &str, root: &Path) -> std::io::Result<String> {
+    const OPEN: &str = "<link rel=\"stylesheet\" href=\"";
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(OPEN) {
+        let after = &rest[start + OPEN.len()..];
+        let (Some(quote), Some(close)) = (after.find('"'), after.find('>')) else {
+            break;
+        };
+        let css = String::from_utf8_lossy(&read(root, &after[..quote])?).into_owned();
+        out.push_str(&rest[..start]);
+        out.push_str("<style>");
+        out.push_str(&css);
+        out.push_str("</style>");
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Removes `<script src>` elements, keeping inline scripts.
+fn remove_external_scripts(html: &str) -> String {
+    const OPEN: &str = "<script src=\"";
+    const CLOSE: &str = "</script>";
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(OPEN) {
+        let Some(end) = rest[start..].find(CLOSE) else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        rest = &rest[start + end + CLOSE.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether the root-relative `path` is an image, by extension.
+fn is_image(path: &str) -> bool {
+    content_type(path).starts_with("image/")
+}
+
+/// Rewrites the `src`, `srcset`, and `href` attributes (and preloads'
+/// `imagesrcset`) holding root-relative
+/// URLs: images become `data:` URIs, `srcset`s go, and the rest become
+/// absolute.
+fn rewrite_urls(html: &str, root: &Path) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(eq) = rest.find("=\"/") {
+        let name_start = rest[..eq]
+            .rfind(|c: char| c.is_ascii_whitespace())
+            .map_or(0, |i| i + 1);
+        let name = &rest[name_start..eq];
+        let value_start = eq + 2;
+        let Some(len) = rest[value_start..].find('"') else {
+            break;
+        };
+        let value = &rest[value_start..value_start + len];
+        let end = value_start + len + 1;
+        match name {
+            "srcset" | "imagesrcset" => out.push_str(rest[..name_start].trim_end()),
+            "src" | "href" if value.starts_with("//") => out.push_str(&rest[..end]),
+            "src" | "href" => {
+                let url = is_image(value)
+                    .then(|| data_uri(root, value))
+                    .flatten()
+                    .unwrap_or_else(|| absolute(value));
+                out.push_str(&rest[..value_start]);
+                out.push_str(&url);
+                out.push('"');
+            }
+            _ => out.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SITE_URL;
+
+    #[test]
+    fn export_needs_nothing_from_the_site() {
+        let root = std::env::temp_dir().join(format!("single-file-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("main.css"), "body { color: red; }").unwrap();
+        std::fs::write(root.join("avatar.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        let html = concat!(
+            "<head><link rel=\"stylesheet\" href=\"/main.css\" />",
+            "<script type=\"application/ld+json\">{\"@id\":\"/\"}</script></head>",
+            "<body><img srcset=\"/avatar-128.png 128w\" src=\"/avatar.png\" alt=\"\" />",
+            "<link rel=\"icon\" href=\"/favicon.ico\" />",
+            "<a href=\"/feed.xml\">Feed</a><a href=\"https://b.test/\">B</a>",
+            "<script src=\"/js/shader-bg.js\" defer></script></body>"
+        );
+        let out = inline(html, &root).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(out.contains("<style>body { color: red; }</style>"));
+        assert!(out.contains("<img src=\"data:image/png;base64,iVBORw==\" alt=\"\" />"));
+        assert!(out.contains(&format!(
+            "href=\"{}/feed.xml\"",
+            SITE_URL.trim_end_matches('/')
+        )));
+        assert!(out.contains(&format!(
+            "href=\"{}/favicon.ico\"",
+            SITE_URL.trim_end_matches('/')
+        )));
+        assert!(out.contains("href=\"https://b.test/\""));
+        assert!(out.contains("{\"@id\":\"/\"}"));
+        assert!(!out.contains("shader-bg"));
+        assert!(!out.contains("srcset"));
+    }
+}
@@ -0,0 +1,176 @@
+//! # Related Sites
+//!
+//! Reads per-site configuration (`sites/<name>.toml`) for the related
+//! properties generated alongside the main site, such as
+//! `lumimenta.everythingsings.art`. Each site shares the components and
+//! stylesheet but gets its own output tree under `target/sites/<name>/`.
+//!
+//! ## Example
+//!
+//! ```toml
+//! name = "Lumimenta"
+//! url = "https://lumimenta.everythingsings.art"
+//! description = "Physical art trading cards."
+//!
+//! cards = "lumimenta-cards.toml"   # optional, see crate::cards
+//!
+//! [[links]]
+//! label = "Shop"
+//! href = "https://bedim.redbubble.com"
+//! description = "Prints and merchandise"
+//! ```
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Directory holding per-site configuration files.
+pub const SITES_DIR: &str = "sites";
+
+/// Root directory for related-site output trees.
+pub const SITES_OUTPUT_DIR: &str = "target/sites";
+
+/// A link rendered on a related site's landing page.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SiteLink {
+    pub label: String,
+    pub href: String,
+    pub description: Option<String>,
+}
+
+/// Configuration for one related site.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct SiteConfig {
+    /// Identifier taken from the config file name (not part of the TOML).
+    #[serde(skip)]
+    pub key: String,
+    pub name: String,
+    /// Absolute base URL without a trailing slash.
+    pub url: String,
+    pub description: String,
+    /// Optional site-specific assets, copied over the output after the
+    /// shared stylesheet.
+    pub public_dir: Option<String>,
+    /// Optional [cards manifest](crate::cards) for per-card pages.
+    pub cards: Option<String>,
+    #[serde(default)]
+    pub links: Vec<SiteLink>,
+}
+
+impl SiteConfig {
+    /// Output directory for this site under `root`, normally
+    /// [`SITES_OUTPUT_DIR`].
+    pub fn output_dir(&self, root: &Path) -> PathBuf {
+        root.join(&self.key)
+    }
+}
+
+/// Discovers all site configs in `dir`, sorted by key.
+pub fn discover_sites(dir: &Path) -> Vec<SiteConfig> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut sites = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+            continue;
+        }
+
+        let key = match path.file_stem().and_then(|n| n.to_str()) {
+            Some(k) => k.to_string(),
+            None => continue,
+        };
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Warning: Could not read {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        match toml::from_str::<SiteConfig>(&content) {
+            Ok(mut site) => {
+                site.key = key;
+                site.url = site.url.trim_end_matches('/').to_string();
+                sites.push(site);
+            }
+            Err(e) => eprintln!("Warning: Could not parse {}: {}", path.display(), e),
+        }
+    }
+
+    sites.sort_by(|a, b| a.key.cmp(&b.key));
+    sites
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::fs;
+
+    pub fn sample_site() -> SiteConfig {
+        SiteConfig {
+            key: "lumimenta".to_string(),
+            name: "Lumimenta".to_string(),
+            url: "https://lumimenta.everythingsings.art".to_string(),
+            description: "Art trading cards.".to_string(),
+            public_dir: None,
+            cards: None,
+            links: vec![SiteLink {
+                label: "Shop".to_string(),
+                href: "https://example.com/shop".to_string(),
+                description: None,
+            }],
+        }
+    }
+
+    fn tempdir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("esart-sites-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn missing_dir_has_no_sites() {
+        assert!(discover_sites(Path::new("does-not-exist")).is_empty());
+    }
+
+    #[test]
+    fn discovers_sites_with_keys_from_file_names() {
+        let dir = tempdir("discover");
+        fs::write(
+            dir.join("lumimenta.toml"),
+            r#"
+name = "Lumimenta"
+url = "https://lumimenta.everythingsings.art/"
+description = "Cards."
+
+[[links]]
+label = "Home"
+href = "https://everythingsings.art"
+"#,
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let sites = discover_sites(&dir);
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].key, "lumimenta");
+        assert_eq!(sites[0].url, "https://lumimenta.everythingsings.art");
+        assert_eq!(sites[0].links[0].label, "Home");
+        assert_eq!(
+            sites[0].output_dir(Path::new(SITES_OUTPUT_DIR)),
+            Path::new("target/sites/lumimenta")
+        );
+    }
+
+    #[test]
+    fn invalid_config_is_skipped() {
+        let dir = tempdir("invalid");
+        fs::write(dir.join("broken.toml"), "name = ").unwrap();
+        assert!(discover_sites(&dir).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! # Slugs
+//!
+//! Titles become URL slugs and heading `id`s here, so a release, an art
+//! series, and a homepage section all follow the same rules:
+//!
+//! ```text
+//! Night Drive (Live!)   night-drive-live
+//! Café Straße           cafe-strasse
+//! Œuvre № 2             oeuvre-no-2
+//! ```
+//!
+//! Latin letters lose their accents (and ligatures are spelled out), so most
+//! slugs are plain ASCII; letters of other scripts are kept and
+//! percent-encoded in URLs (see [`crate::text::encode_path_segment`]).
+//!
+//! ## Collisions
+//!
+//! [`Slugs`] hands out slugs within one namespace (one content type's
+//! directory). A slug already taken gets the first free `-2`, `-3`, …
+//! suffix, so the same inputs in the same order always get the same slugs.
+//! Names the layout itself uses in a listing's directory ([`RESERVED`]) are
+//! taken from the start.
+
+use std::collections::BTreeSet;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Slugs a listing's directory already uses: `index` is its page in the
+/// file layout (see [`crate::url::Layout`]) and `page` holds its later
+/// pages (see [`crate::pagination`]).
+pub const RESERVED: &[&str] = &["index", "page"];
+
+/// The slug of a title with nothing sluggable, e.g. only emoji.
+const FALLBACK: &str = "untitled";
+
+/// Lowercases `text`, transliterates accented Latin letters, and turns runs
+/// of other characters into single dashes, e.g. `Café Straße!` becomes
+/// `cafe-strasse`. Empty when `text` has no letters or digits.
+pub fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.nfkd().filter(|c| !is_combining_mark(*c)) {
+        for c in transliterate(c).chars().flat_map(char::to_lowercase) {
+            if c.is_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Latin letters that don't decompose into a base letter and accents.
+fn transliterate(c: char) -> String {
+    match c {
+        'ß' => "ss".into(),
+        'Æ' | 'æ' => "ae".into(),
+        'Œ' | 'œ' => "oe".into(),
+        'Ø' | 'ø' => "o".into(),
+        'Ł' | 'ł' => "l".into(),
+        'Đ' | 'đ' | 'Ð' | 'ð' => "d".into(),
+        'Þ' | 'þ' => "th".into(),
+        'ı' => "i".into(),
+        _ => c.into(),
+    }
+}
+
+/// The slugs handed out in one namespace.
+#[derive(Clone, Debug)]
+pub struct Slugs {
+    taken: BTreeSet<String>,
+}
+
+impl Default for Slugs {
+    fn default() -> Self {
+        Slugs {
+            taken: RESERVED.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl Slugs {
+    /// Claims a slug chosen by hand, as is. False when it is taken.
+    pub fn claim(&mut self, slug: &str) -> bool {
+        self.taken.insert(slug.to_string())
+    }
+
+    /// The slug of `text`, suffixed with `-2`, `-3`, … when taken.
+    pub fn unique(&mut self, text: &str) -> String {
+        let base = match slug(text) {
+            s if s.is_empty() => FALLBACK.to_string(),
+            s => s,
+        };
+        let mut candidate = base.clone();
+        let mut n = 1;
+        while !self.claim(&candidate) {
+            n += 1;
+            candidate = format!("{}-{}", base, n);
+        }
+        candidate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_of_other_characters_become_dashes() {
+        assert_eq!(slug("Night Drive (Live!)"), "night-drive-live");
+        assert_eq!(slug("  Recently listening to"), "recently-listening-to");
+        assert_eq!(slug("🎨"), "");
+    }
+
+    #[test]
+    fn latin_letters_are_transliterated() {
+        assert_eq!(slug("Café Straße"), "cafe-strasse");
+        assert_eq!(slug("Cafe\u{301}"), "cafe");
+        assert_eq!(slug("Œuvre № 2"), "oeuvre-no-2");
+        assert_eq!(slug("Łódź Ærø"), "lodz-aero");
+    }
+
+    #[test]
+    fn other_scripts_are_kept() {
+        assert_eq!(slug("فن 夜"), "فن-夜");
+    }
+
+    #[test]
+    fn taken_slugs_get_numbered() {
+        let mut slugs = Slugs::default();
+        assert_eq!(slugs.unique("Neon"), "neon");
+        assert_eq!(slugs.unique("NEON!"), "neon-2");
+        assert!(slugs.claim("neon-3"));
+        assert_eq!(slugs.unique("neon"), "neon-4");
+        assert!(!slugs.claim("neon"));
+    }
+
+    #[test]
+    fn reserved_and_empty_slugs_are_avoided() {
+        let mut slugs = Slugs::default();
+        assert_eq!(slugs.unique("Page"), "page-2");
+        assert_eq!(slugs.unique("Index"), "index-2");
+        assert!(!slugs.claim("page"));
+        assert_eq!(slugs.unique("🎨"), "untitled");
+        assert_eq!(slugs.unique("✨"), "untitled-2");
+    }
+}
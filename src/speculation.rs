@@ -0,0 +1,156 @@
+//! # Speculation Rules
+//!
+//! A `<script type="speculationrules">` block asks supporting browsers to
+//! prefetch or prerender the internal pages a page links to, so following
+//! a link feels instant. Browsers without support ignore the block; it is
+//! data, not script, so the zero-JS promise holds.
+//!
+//! Rules are set per page through [`PageMeta`](crate::components::PageMeta);
+//! the default is none.
+
+use crate::escape::json_script;
+use serde_json::{json, Value};
+
+/// When the browser acts on a rule.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Eagerness {
+    /// As soon as the rules are seen.
+    Eager,
+    /// On hover or pointer-down.
+    #[default]
+    Moderate,
+    /// On pointer-down only.
+    Conservative,
+}
+
+impl Eagerness {
+    fn as_str(self) -> &'static str {
+        match self {
+            Eagerness::Eager => "eager",
+            Eagerness::Moderate => "moderate",
+            Eagerness::Conservative => "conservative",
+        }
+    }
+}
+
+/// Internal pages to prefetch and prerender.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpeculationRules {
+    pub prefetch: Vec<String>,
+    pub prerender: Vec<String>,
+    pub eagerness: Eagerness,
+}
+
+/// Whether `url` is a same-site path (`/cv/`, not `//cdn…` or `https://…`).
+fn is_internal(url: &str) -> bool {
+    url.starts_with('/') && !url.starts_with("//")
+}
+
+impl SpeculationRules {
+    /// Prefetches `urls` with the default eagerness.
+    pub fn prefetch<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        SpeculationRules {
+            prefetch: urls.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether no internal page would be speculated.
+    pub fn is_empty(&self) -> bool {
+        !self
+            .prefetch
+            .iter()
+            .chain(&self.prerender)
+            .any(|url| is_internal(url))
+    }
+
+    fn list_rule(&self, urls: &[String]) -> Option<Value> {
+        let mut internal: Vec<&str> = Vec::new();
+        for url in urls.iter().map(String::as_str).filter(|u| is_internal(u)) {
+            if !internal.contains(&url) {
+                internal.push(url);
+            }
+        }
+        (!internal.is_empty()).then(|| {
+            json!([{
+                "source": "list",
+                "urls": internal,
+                "eagerness": self.eagerness.as_str(),
+            }])
+        })
+    }
+
+    /// Renders the `<script type="speculationrules">` element, or an empty
+    /// string when there is nothing to speculate.
+    ///
+    /// External URLs are dropped: speculation is only for this site.
+    pub fn to_html(&self) -> String {
+        let mut rules = serde_json::Map::new();
+        if let Some(rule) = self.list_rule(&self.prefetch) {
+            rules.insert("prefetch".to_string(), rule);
+        }
+        if let Some(rule) = self.list_rule(&self.prerender) {
+            rules.insert("prerender".to_string(), rule);
+        }
+        if rules.is_empty() {
+            return String::new();
+        }
+        format!(
+            "<script type=\"speculationrules\">{}</script>",
+            json_script(&Value::Object(rules))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules_json(rules: &SpeculationRules) -> Value {
+        let html = rules.to_html();
+        let json = html
+            .strip_prefix("<script type=\"speculationrules\">")
+            .and_then(|s| s.strip_suffix("</script>"))
+            .unwrap();
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn empty_rules_render_nothing() {
+        assert!(SpeculationRules::default().is_empty());
+        assert_eq!(SpeculationRules::default().to_html(), "");
+    }
+
+    #[test]
+    fn prefetch_lists_internal_urls_once() {
+        let rules = SpeculationRules::prefetch(["/art/a/", "https://x.test/", "/art/a/", "//cdn"]);
+        let json = rules_json(&rules);
+        assert_eq!(json["prefetch"][0]["source"], "list");
+        assert_eq!(json["prefetch"][0]["urls"], json!(["/art/a/"]));
+        assert_eq!(json["prefetch"][0]["eagerness"], "moderate");
+        assert!(json.get("prerender").is_none());
+    }
+
+    #[test]
+    fn external_only_rules_are_empty() {
+        let rules = SpeculationRules::prefetch(["https://x.test/"]);
+        assert!(rules.is_empty());
+        assert_eq!(rules.to_html(), "");
+    }
+
+    #[test]
+    fn prerender_uses_configured_eagerness() {
+        let rules = SpeculationRules {
+            prerender: vec!["/cv/".to_string()],
+            eagerness: Eagerness::Conservative,
+            ..Default::default()
+        };
+        let json = rules_json(&rules);
+        assert_eq!(json["prerender"][0]["urls"], json!(["/cv/"]));
+        assert_eq!(json["prerender"][0]["eagerness"], "conservative");
+    }
+}
@@ -0,0 +1,79 @@
+//! # Status Document
+//!
+//! `/status.json` lets an external uptime monitor check that the deployed
+//! site is current, not just reachable: it records when the site was
+//! generated, by which build, how many pages it has, and when each content
+//! source last changed. Alert when `generated_at` or a `content` timestamp
+//! is older than expected.
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "status": "ok",
+//!   "generated_at": "2025-01-17T12:00:00Z",
+//!   "version": "0.1.0",
+//!   "commit": "abc1234…",
+//!   "built_at": "2025-01-17T11:58:00Z",
+//!   "pages": 12,
+//!   "content": { "changelog": "2025-01-16", "github_contributions": "2025-01-17T06:00:00Z" }
+//! }
+//! ```
+
+use crate::datetime::rfc3339;
+use crate::provenance::{BUILD_TIME, GIT_HASH, VERSION};
+use serde_json::{json, Map, Value};
+use std::time::SystemTime;
+
+/// Output path of the status document, relative to the site root.
+pub const STATUS_PATH: &str = "status.json";
+
+/// Version of the `/status.json` format; bump on incompatible changes.
+pub const STATUS_SCHEMA_VERSION: u32 = 1;
+
+/// Generates `/status.json`.
+///
+/// `content` pairs a source name with when it last changed (an ISO date or
+/// RFC 3339 timestamp); sources are listed by name.
+pub fn generate_status_json(
+    generated_at: SystemTime,
+    pages: usize,
+    content: &[(&str, String)],
+) -> String {
+    let content: Map<String, Value> = content
+        .iter()
+        .map(|(name, updated)| (name.to_string(), json!(updated)))
+        .collect();
+    let doc = json!({
+        "schema_version": STATUS_SCHEMA_VERSION,
+        "status": "ok",
+        "generated_at": rfc3339(generated_at),
+        "version": VERSION,
+        "commit": (!GIT_HASH.is_empty()).then_some(GIT_HASH),
+        "built_at": BUILD_TIME,
+        "pages": pages,
+        "content": content,
+    });
+    serde_json::to_string_pretty(&doc).expect("status serializes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn status_reports_build_pages_and_content() {
+        let json: Value = serde_json::from_str(&generate_status_json(
+            UNIX_EPOCH,
+            7,
+            &[("changelog", "2025-01-16".to_string())],
+        ))
+        .unwrap();
+        assert_eq!(json["schema_version"], STATUS_SCHEMA_VERSION);
+        assert_eq!(json["generated_at"], "1970-01-01T00:00:00Z");
+        assert_eq!(json["version"], VERSION);
+        assert_eq!(json["built_at"], BUILD_TIME);
+        assert_eq!(json["pages"], 7);
+        assert_eq!(json["content"]["changelog"], "2025-01-16");
+    }
+}
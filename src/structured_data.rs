@@ -0,0 +1,103 @@
+//! # Structured Data Registry
+//!
+//! Components contribute the JSON-LD nodes their markup implies while they
+//! render, and the page's `@graph` is assembled from what was rendered:
+//!
+//! | Component | Contributes |
+//! |-----------|-------------|
+//! | [`ProfileCards`](crate::components::ProfileCards) | the [entities](crate::entity) (`Person`, `Organization`) |
+//! | [`Body`](crate::app::Body) | the site, the homepage, and the [properties](crate::properties) |
+//! | [`ArtSeriesPage`](crate::components::ArtSeriesPage) | the series' `ImageGallery` |
+//!
+//! A page renders its body through [`render_with_graph`] first, then passes
+//! the [`Graph`] to its head. Nodes are kept in the order contributed; a
+//! node whose `@id` is already in the graph is dropped, so a component
+//! rendered twice describes its subject once. Outside [`render_with_graph`],
+//! [`contribute`] does nothing, so components render the same anywhere.
+
+use crate::schema::Graph;
+use leptos::prelude::*;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// The nodes contributed by the components of the page being rendered.
+#[derive(Clone, Debug, Default)]
+struct Contributions(Arc<Mutex<Vec<Value>>>);
+
+/// Adds `node` to the `@graph` of the page being rendered.
+pub fn contribute(node: Value) {
+    if let Some(contributions) = use_context::<Contributions>() {
+        if let Ok(mut nodes) = contributions.0.lock() {
+            nodes.push(node);
+        }
+    }
+}
+
+/// Runs `render` (which renders a page body to HTML) and returns its HTML
+/// with the graph of the nodes its components contributed.
+pub fn render_with_graph(render: impl FnOnce() -> String) -> (String, Graph) {
+    let contributions = Contributions::default();
+    let owner = Owner::new();
+    let html = owner.with(|| {
+        provide_context(contributions.clone());
+        render()
+    });
+    let mut graph = Graph::default();
+    let nodes = contributions
+        .0
+        .lock()
+        .map(|mut nodes| std::mem::take(&mut *nodes))
+        .unwrap_or_default();
+    for node in nodes {
+        let known = node["@id"]
+            .as_str()
+            .is_some_and(|id| graph.node(id).is_some());
+        if !known {
+            graph.push(node);
+        }
+    }
+    (html, graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[component]
+    fn Described(id: &'static str) -> impl IntoView {
+        contribute(json!({ "@id": id, "@type": "Thing" }));
+        view! { <p>{id}</p> }
+    }
+
+    #[test]
+    fn rendered_components_make_the_graph() {
+        let (html, graph) = render_with_graph(|| {
+            view! {
+                <div>
+                    <Described id="#a" />
+                    <Described id="#b" />
+                    <Described id="#a" />
+                </div>
+            }
+            .to_html()
+        });
+        assert!(html.contains("<p>#b</p>"), "{}", html);
+        let json: Value = serde_json::from_str(&graph.to_json_ld()).unwrap();
+        let ids: Vec<&str> = json["@graph"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|n| n["@id"].as_str())
+            .collect();
+        assert_eq!(ids, ["#a", "#b"]);
+    }
+
+    #[test]
+    fn contributions_outside_a_page_are_ignored() {
+        let html = view! { <Described id="#a" /> }.to_html();
+        assert_eq!(html, "<p>#a</p>");
+        let (_, graph) = render_with_graph(String::new);
+        assert!(graph.node("#a").is_none());
+    }
+}
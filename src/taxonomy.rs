@@ -0,0 +1,335 @@
+//! # Taxonomies
+//!
+//! Ways of grouping entries across every [collection](crate::collection),
+//! declared in [`TAXONOMIES`]: an art series, a medium, a mood. Entries
+//! name their terms in a `[taxonomies]` frontmatter table:
+//!
+//! ```toml
+//! [taxonomies]
+//! series = ["Night Walks"]
+//! medium = ["ink", "generative"]
+//! ```
+//!
+//! Each taxonomy gets an index of its terms, and each term a page listing
+//! its entries newest first, with its own feed:
+//!
+//! ```text
+//! /medium/                    every medium
+//! /medium/ink/                entries in ink
+//! /medium/ink/feed.xml
+//! ```
+//!
+//! Terms that slug alike (`Ink`, `ink`) are one term, shown as first
+//! written. In an entry's JSON-LD, terms become `about` (as `DefinedTerm`s
+//! linking their page) or `keywords`, as the taxonomy declares; see
+//! [`structured_data`]. A taxonomy the config doesn't declare is an error
+//! when the entry is loaded, so a typo never silently drops a term.
+//!
+//! [`TAXONOMIES`]: crate::config::TAXONOMIES
+
+use crate::config::SITE_NAME;
+use crate::escape::json_script;
+use crate::feed::{Feed, FeedItem};
+use crate::slug::slug;
+use crate::url::{absolute, page_path};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// An entry's terms: taxonomy key to term names.
+pub type Terms = BTreeMap<String, Vec<String>>;
+
+/// Terms of entries without any.
+pub static NO_TERMS: Terms = BTreeMap::new();
+
+/// The Schema.org property an entry's terms are published as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TermProperty {
+    /// `about`, one `DefinedTerm` per term.
+    About,
+    /// `keywords`, the term names.
+    Keywords,
+}
+
+/// A way of grouping entries.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Taxonomy {
+    /// Key in the `[taxonomies]` frontmatter table, e.g. `medium`.
+    pub key: &'static str,
+    /// Output directory of the index and term pages.
+    pub dir: &'static str,
+    /// Heading of the index page, e.g. `Medium`.
+    pub title: &'static str,
+    pub description: &'static str,
+    pub property: TermProperty,
+}
+
+impl Taxonomy {
+    /// Site-relative URL of the taxonomy's index, e.g. `/medium/`.
+    pub fn index_path(&self) -> String {
+        page_path(&[self.dir])
+    }
+
+    /// Path segments of the page of the term slugged `slug`.
+    pub fn term_segments(&self, slug: &str) -> Vec<String> {
+        vec![self.dir.to_string(), slug.to_string()]
+    }
+
+    /// Site-relative URL of the page of the term slugged `slug`.
+    pub fn term_path(&self, slug: &str) -> String {
+        page_path(&self.term_segments(slug))
+    }
+
+    /// The feed of `term`, next to its page.
+    pub fn feed(&self, term: &Term) -> Feed {
+        Feed {
+            key: self.key,
+            title: format!("{} {}: {}", SITE_NAME, self.title, term.name),
+            description: format!("{}: {}", self.title, term.name),
+            path: format!("{}feed.xml", self.term_path(&term.slug)),
+            section: Some(self.term_path(&term.slug)),
+        }
+    }
+}
+
+/// An entry as a term page lists it, whatever its collection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tagged {
+    pub title: String,
+    /// Site-relative URL of the entry's page.
+    pub url: String,
+    /// ISO date.
+    pub date: String,
+    pub description: String,
+}
+
+/// One term and the entries filed under it, newest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Term {
+    /// The name as first written.
+    pub name: String,
+    pub slug: String,
+    pub entries: Vec<Tagged>,
+}
+
+impl Term {
+    /// Feed items for the term's entries.
+    pub fn feed_items(&self) -> Vec<FeedItem> {
+        self.entries
+            .iter()
+            .map(|e| FeedItem {
+                title: e.title.clone(),
+                link: absolute(&e.url),
+                description: e.description.clone(),
+                date: e.date.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Checks that every taxonomy in `terms` is declared in `taxonomies` and
+/// every term has a slug.
+pub fn check_terms(terms: &Terms, taxonomies: &[Taxonomy]) -> Result<(), String> {
+    for (key, names) in terms {
+        if !taxonomies.iter().any(|t| t.key == key) {
+            return Err(format!("Unknown taxonomy {:?}", key));
+        }
+        if let Some(name) = names.iter().find(|name| slug(name).is_empty()) {
+            return Err(format!("{} term {:?} has no letters or digits", key, name));
+        }
+    }
+    Ok(())
+}
+
+/// The terms of `taxonomy` used by `entries`, in slug order.
+pub fn terms<'a>(
+    taxonomy: &Taxonomy,
+    entries: impl IntoIterator<Item = (&'a Terms, Tagged)>,
+) -> Vec<Term> {
+    let mut by_slug: BTreeMap<String, Term> = BTreeMap::new();
+    for (terms, tagged) in entries {
+        for name in terms.get(taxonomy.key).into_iter().flatten() {
+            let term = by_slug.entry(slug(name)).or_insert_with_key(|slug| Term {
+                name: name.clone(),
+                slug: slug.clone(),
+                entries: Vec::new(),
+            });
+            if !term.entries.contains(&tagged) {
+                term.entries.push(tagged.clone());
+            }
+        }
+    }
+    let mut terms: Vec<Term> = by_slug.into_values().collect();
+    for term in &mut terms {
+        term.entries.sort_by(|a, b| b.date.cmp(&a.date));
+    }
+    terms
+}
+
+/// A term as a JSON-LD `DefinedTerm` in its taxonomy's term set.
+pub fn defined_term(taxonomy: &Taxonomy, name: &str) -> Value {
+    json!({
+        "@type": "DefinedTerm",
+        "name": name,
+        "url": absolute(&taxonomy.term_path(&slug(name))),
+        "inDefinedTermSet": absolute(&taxonomy.index_path()),
+    })
+}
+
+/// The `about` and `keywords` properties of an entry with `terms`, to merge
+/// into its JSON-LD node. Empty without terms.
+pub fn structured_data(terms: &Terms, taxonomies: &[Taxonomy]) -> Map<String, Value> {
+    let mut about = Vec::new();
+    let mut keywords = Vec::new();
+    for taxonomy in taxonomies {
+        for name in terms.get(taxonomy.key).into_iter().flatten() {
+            match taxonomy.property {
+                TermProperty::About => about.push(defined_term(taxonomy, name)),
+                TermProperty::Keywords => keywords.push(json!(name)),
+            }
+        }
+    }
+    let mut data = Map::new();
+    if !about.is_empty() {
+        data.insert("about".to_string(), json!(about));
+    }
+    if !keywords.is_empty() {
+        data.insert("keywords".to_string(), json!(keywords));
+    }
+    data
+}
+
+/// JSON-LD of the page of `term`: a `CollectionPage` about the term.
+pub fn term_json_ld(taxonomy: &Taxonomy, term: &Term) -> String {
+    let items: Vec<Value> = term
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            json!({
+                "@type": "ListItem",
+                "position": i + 1,
+                "name": e.title,
+                "url": absolute(&e.url),
+            })
+        })
+        .collect();
+    json_script(&json!({
+        "@context": "https://schema.org",
+        "@type": "CollectionPage",
+        "name": format!("{}: {}", taxonomy.title, term.name),
+        "url": absolute(&taxonomy.term_path(&term.slug)),
+        "about": defined_term(taxonomy, &term.name),
+        "mainEntity": { "@type": "ItemList", "itemListElement": items },
+    }))
+}
+
+/// JSON-LD of a taxonomy's index: its `DefinedTermSet`.
+pub fn index_json_ld(taxonomy: &Taxonomy, terms: &[Term]) -> String {
+    let defined: Vec<Value> = terms
+        .iter()
+        .map(|t| defined_term(taxonomy, &t.name))
+        .collect();
+    json_script(&json!({
+        "@context": "https://schema.org",
+        "@type": "DefinedTermSet",
+        "name": taxonomy.title,
+        "description": taxonomy.description,
+        "url": absolute(&taxonomy.index_path()),
+        "hasDefinedTerm": defined,
+    }))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const MEDIUM: Taxonomy = Taxonomy {
+        key: "medium",
+        dir: "medium",
+        title: "Medium",
+        description: "Work by medium.",
+        property: TermProperty::Keywords,
+    };
+
+    pub(crate) const SERIES: Taxonomy = Taxonomy {
+        key: "series",
+        dir: "series",
+        title: "Series",
+        description: "Work by series.",
+        property: TermProperty::About,
+    };
+
+    /// The medium terms of the sample posts.
+    pub(crate) fn sample_terms() -> Vec<Term> {
+        terms(&MEDIUM, crate::posts::tests::sample_posts().tagged())
+    }
+
+    fn terms_of(pairs: &[(&str, &[&str])]) -> Terms {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.iter().map(|s| s.to_string()).collect()))
+            .collect()
+    }
+
+    fn tagged(title: &str, date: &str) -> Tagged {
+        Tagged {
+            title: title.to_string(),
+            url: format!("/posts/{}/", slug(title)),
+            date: date.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn terms_group_entries_by_slug() {
+        let a = terms_of(&[("medium", &["Ink", "code"])]);
+        let b = terms_of(&[("medium", &["ink"]), ("series", &["Walks"])]);
+        let terms = terms(
+            &MEDIUM,
+            [
+                (&a, tagged("Old", "2024-01-01")),
+                (&b, tagged("New", "2025-01-01")),
+            ],
+        );
+        let names: Vec<&str> = terms.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["code", "Ink"]);
+        assert_eq!(terms[1].slug, "ink");
+        assert_eq!(terms[1].entries[0].title, "New");
+        assert_eq!(MEDIUM.feed(&terms[1]).path, "/medium/ink/feed.xml");
+    }
+
+    #[test]
+    fn undeclared_taxonomies_are_errors() {
+        let taxonomies = [MEDIUM, SERIES];
+        assert!(check_terms(&terms_of(&[("medium", &["ink"])]), &taxonomies).is_ok());
+        assert!(
+            check_terms(&terms_of(&[("mediums", &["ink"])]), &taxonomies)
+                .unwrap_err()
+                .contains("mediums")
+        );
+        assert!(check_terms(&terms_of(&[("medium", &["✨"])]), &taxonomies).is_err());
+    }
+
+    #[test]
+    fn terms_become_about_and_keywords() {
+        let data = structured_data(
+            &terms_of(&[("medium", &["ink", "code"]), ("series", &["Night Walks"])]),
+            &[SERIES, MEDIUM],
+        );
+        assert_eq!(data["keywords"], json!(["ink", "code"]));
+        assert_eq!(data["about"][0]["@type"], "DefinedTerm");
+        assert_eq!(data["about"][0]["url"], absolute("/series/night-walks/"));
+        assert!(structured_data(&NO_TERMS, &[SERIES]).is_empty());
+    }
+
+    #[test]
+    fn term_pages_are_about_their_term() {
+        let t = terms_of(&[("series", &["Walks"])]);
+        let terms = terms(&SERIES, [(&t, tagged("A", "2025-01-01"))]);
+        let page: Value = serde_json::from_str(&term_json_ld(&SERIES, &terms[0])).unwrap();
+        assert_eq!(page["about"]["name"], "Walks");
+        assert_eq!(page["mainEntity"]["itemListElement"][0]["name"], "A");
+        let index: Value = serde_json::from_str(&index_json_ld(&SERIES, &terms)).unwrap();
+        assert_eq!(index["hasDefinedTerm"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,249 @@
+//! # Microformats2 Test Harness
+//!
+//! A small microformats2 parser used by tests to assert on parsed h-card and
+//! h-entry property values rather than class-name substrings. It follows the
+//! [mf2 parsing spec](https://microformats.org/wiki/microformats2-parsing)
+//! closely enough to catch structural mistakes, such as a `p-name` placed
+//! outside its `h-card`, without implicit property or URL resolution.
+
+use scraper::{ElementRef, Html};
+use std::collections::BTreeMap;
+
+/// A parsed property value: plain text or a nested microformat.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Item(Item),
+}
+
+impl Value {
+    /// The text of a plain value, or `None` for a nested item.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(t) => Some(t),
+            Value::Item(_) => None,
+        }
+    }
+}
+
+/// A parsed microformat item (`h-card`, `h-entry`, ...).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Item {
+    pub types: Vec<String>,
+    pub properties: BTreeMap<String, Vec<Value>>,
+    pub children: Vec<Item>,
+}
+
+impl Item {
+    /// The first text value of `name`, if any.
+    pub fn text(&self, name: &str) -> Option<&str> {
+        self.properties.get(name)?.iter().find_map(Value::as_text)
+    }
+}
+
+/// The parsed document: top-level items plus `rel` values.
+#[derive(Debug, Default)]
+pub struct Document {
+    pub items: Vec<Item>,
+    pub rels: BTreeMap<String, Vec<String>>,
+}
+
+impl Document {
+    /// Top-level items of the given type, e.g. `"h-card"`.
+    pub fn items_of(&self, kind: &str) -> Vec<&Item> {
+        self.items
+            .iter()
+            .filter(|i| i.types.iter().any(|t| t == kind))
+            .collect()
+    }
+}
+
+fn classes<'a>(el: &'a ElementRef) -> impl Iterator<Item = &'a str> {
+    el.value().classes()
+}
+
+fn root_types(el: &ElementRef) -> Vec<String> {
+    let mut types: Vec<String> = classes(el)
+        .filter(|c| c.starts_with("h-") && c.len() > 2)
+        .map(String::from)
+        .collect();
+    types.sort();
+    types
+}
+
+fn property_classes(el: &ElementRef) -> Vec<(char, String)> {
+    classes(el)
+        .filter_map(|c| {
+            let (prefix, name) = c.split_once('-')?;
+            match prefix {
+                "p" | "u" | "dt" | "e" if !name.is_empty() => {
+                    Some((prefix.chars().next().unwrap(), name.to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn text_of(el: &ElementRef) -> String {
+    el.text().collect::<String>().trim().to_string()
+}
+
+fn property_value(el: &ElementRef, prefix: char) -> String {
+    let attr = |names: &[&str]| names.iter().find_map(|n| el.value().attr(n));
+    let tag = el.value().name();
+    let value = match prefix {
+        'p' => match tag {
+            "img" | "area" => attr(&["alt"]),
+            "abbr" | "link" => attr(&["title"]),
+            "data" | "input" => attr(&["value"]),
+            _ => None,
+        },
+        'u' => match tag {
+            "a" | "area" | "link" => attr(&["href"]),
+            "img" | "audio" | "video" | "source" | "iframe" => attr(&["src"]),
+            "object" => attr(&["data"]),
+            _ => None,
+        },
+        'd' => match tag {
+            "time" | "ins" | "del" => attr(&["datetime"]),
+            _ => None,
+        },
+        _ => return el.inner_html().trim().to_string(),
+    };
+    value.map(String::from).unwrap_or_else(|| text_of(el))
+}
+
+fn parse_item(root: &ElementRef) -> Item {
+    let mut item = Item {
+        types: root_types(root),
+        ..Default::default()
+    };
+    collect_properties(root, &mut item);
+    item
+}
+
+fn collect_properties(parent: &ElementRef, item: &mut Item) {
+    for child in parent.children().filter_map(ElementRef::wrap) {
+        let props = property_classes(&child);
+        if !root_types(&child).is_empty() {
+            let nested = parse_item(&child);
+            if props.is_empty() {
+                item.children.push(nested);
+            } else {
+                for (_, name) in props {
+                    item.properties
+                        .entry(name)
+                        .or_default()
+                        .push(Value::Item(nested.clone()));
+                }
+            }
+            continue;
+        }
+        for (prefix, name) in props {
+            item.properties
+                .entry(name)
+                .or_default()
+                .push(Value::Text(property_value(&child, prefix)));
+        }
+        collect_properties(&child, item);
+    }
+}
+
+fn collect_roots(parent: &ElementRef, items: &mut Vec<Item>) {
+    for child in parent.children().filter_map(ElementRef::wrap) {
+        if root_types(&child).is_empty() {
+            collect_roots(&child, items);
+        } else {
+            items.push(parse_item(&child));
+        }
+    }
+}
+
+/// Parses microformats2 items and rels from an HTML document or fragment.
+pub fn parse(html: &str) -> Document {
+    let html = Html::parse_document(html);
+    let mut doc = Document::default();
+    collect_roots(&html.root_element(), &mut doc.items);
+
+    for el in html
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+    {
+        let (Some(rel), Some(href)) = (el.value().attr("rel"), el.value().attr("href")) else {
+            continue;
+        };
+        for r in rel.split_ascii_whitespace() {
+            doc.rels
+                .entry(r.to_string())
+                .or_default()
+                .push(href.to_string());
+        }
+    }
+
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{LinkList, ProfileCards};
+    use crate::config::{AVATAR_PATH, SITE_DESCRIPTION, SITE_NAME, SITE_URL};
+    use leptos::prelude::*;
+
+    #[test]
+    fn parses_simple_hcard() {
+        let doc = parse(r#"<div class="h-card"><span class="p-name">Ada</span></div>"#);
+        let card = doc.items_of("h-card")[0];
+        assert_eq!(card.text("name"), Some("Ada"));
+    }
+
+    #[test]
+    fn property_outside_root_is_ignored() {
+        let doc = parse(r#"<div class="h-card"></div><span class="p-name">Stray</span>"#);
+        assert_eq!(doc.items_of("h-card")[0].text("name"), None);
+    }
+
+    #[test]
+    fn nested_property_item_is_not_a_child() {
+        let doc = parse(
+            r#"<article class="h-entry"><a class="p-author h-card" href="/">Ada</a></article>"#,
+        );
+        let entry = doc.items_of("h-entry")[0];
+        assert!(entry.children.is_empty());
+        assert!(matches!(entry.properties["author"][0], Value::Item(_)));
+    }
+
+    #[test]
+    fn url_and_datetime_use_attributes() {
+        let doc = parse(
+            r#"<div class="h-entry"><a class="u-url" href="/x">x</a><time class="dt-published" datetime="2025-01-01">Jan</time></div>"#,
+        );
+        let entry = doc.items_of("h-entry")[0];
+        assert_eq!(entry.text("url"), Some("/x"));
+        assert_eq!(entry.text("published"), Some("2025-01-01"));
+    }
+
+    #[test]
+    fn profile_card_parses_as_hcard() {
+        let doc = parse(&ProfileCards().to_html());
+        let cards = doc.items_of("h-card");
+        assert_eq!(cards.len(), 1, "Expected exactly one top-level h-card");
+        let card = cards[0];
+        assert_eq!(card.text("name"), Some(SITE_NAME));
+        assert_eq!(
+            card.text("note"),
+            Some(crate::typography::typeset(SITE_DESCRIPTION).as_str())
+        );
+        assert_eq!(card.text("photo"), Some(AVATAR_PATH));
+        assert_eq!(card.text("url"), Some(SITE_URL));
+    }
+
+    #[test]
+    fn link_list_exposes_rel_me_urls() {
+        let doc = parse(&view! { <LinkList /> }.to_html());
+        let me = &doc.rels["me"];
+        assert!(me.iter().any(|u| u == "https://github.com/EverythingSings"));
+    }
+}
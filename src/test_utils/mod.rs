@@ -0,0 +1,128 @@
+//! # Test Utilities
+//!
+//! DOM-based helpers for component tests. Rendered HTML is parsed with an
+//! HTML5 parser and queried with CSS selectors, so assertions check real
+//! elements and attributes instead of matching substrings that could appear
+//! anywhere in the output.
+//!
+//! Note: The parser always synthesizes `<html>`, `<head>` and `<body>`
+//! elements, so tests for those tags should assert on their attributes or
+//! children rather than their mere presence.
+
+pub mod mf2;
+
+use scraper::{ElementRef, Html, Selector};
+
+/// Asserts that `html` passes the heading outline checks of
+/// [`audit_outline`](crate::a11y::audit_outline).
+pub fn assert_outline(html: &str) {
+    let findings = crate::a11y::audit_outline(html);
+    assert!(findings.is_empty(), "Outline problems: {:#?}", findings);
+}
+
+/// A parsed HTML document.
+pub struct Dom {
+    html: Html,
+}
+
+/// Parses a rendered document or fragment.
+pub fn parse(html: &str) -> Dom {
+    Dom {
+        html: Html::parse_document(html),
+    }
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).unwrap_or_else(|e| panic!("Invalid selector {:?}: {:?}", css, e))
+}
+
+fn text_of(el: &ElementRef) -> String {
+    el.text().collect::<String>().trim().to_string()
+}
+
+impl Dom {
+    /// All elements matching `css`, in document order.
+    pub fn select(&self, css: &str) -> Vec<ElementRef<'_>> {
+        self.html.select(&selector(css)).collect()
+    }
+
+    /// Number of elements matching `css`.
+    pub fn count(&self, css: &str) -> usize {
+        self.select(css).len()
+    }
+
+    /// True when at least one element matches `css`.
+    pub fn exists(&self, css: &str) -> bool {
+        self.count(css) > 0
+    }
+
+    /// The `name` attribute of the first element matching `css`.
+    pub fn attr(&self, css: &str, name: &str) -> Option<String> {
+        self.select(css)
+            .first()
+            .and_then(|el| el.value().attr(name))
+            .map(String::from)
+    }
+
+    /// The `name` attribute of every element matching `css` that has it.
+    pub fn attrs(&self, css: &str, name: &str) -> Vec<String> {
+        self.select(css)
+            .iter()
+            .filter_map(|el| el.value().attr(name))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Trimmed text content of the first element matching `css`.
+    pub fn text(&self, css: &str) -> Option<String> {
+        self.select(css).first().map(text_of)
+    }
+
+    /// Trimmed text content of every element matching `css`.
+    pub fn texts(&self, css: &str) -> Vec<String> {
+        self.select(css).iter().map(text_of).collect()
+    }
+}
+
+/// Asserts that the first element matching `css` has `name="expected"`.
+#[track_caller]
+pub fn assert_attr(dom: &Dom, css: &str, name: &str, expected: &str) {
+    assert!(dom.exists(css), "No element matches {:?}", css);
+    assert_eq!(
+        dom.attr(css, name).as_deref(),
+        Some(expected),
+        "Attribute {:?} of {:?}",
+        name,
+        css
+    );
+}
+
+/// Asserts that at least one element matches `css`.
+#[track_caller]
+pub fn assert_exists(dom: &Dom, css: &str) {
+    assert!(dom.exists(css), "Expected an element matching {:?}", css);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_ignores_text_that_looks_like_markup() {
+        let dom = parse("<p>&lt;ul&gt; is not a list</p>");
+        assert!(!dom.exists("ul"));
+    }
+
+    #[test]
+    fn attr_reads_first_match() {
+        let dom = parse(r#"<a href="/a">a</a><a href="/b">b</a>"#);
+        assert_eq!(dom.attr("a", "href").as_deref(), Some("/a"));
+        assert_eq!(dom.attrs("a", "href"), ["/a", "/b"]);
+    }
+
+    #[test]
+    fn text_is_trimmed() {
+        let dom = parse("<h1>  Title </h1>");
+        assert_eq!(dom.text("h1").as_deref(), Some("Title"));
+    }
+}
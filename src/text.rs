@@ -0,0 +1,266 @@
+//! # Text Normalization
+//!
+//! Unicode handling shared by every output format. Content may come from
+//! config constants, TOML files, or directory names, and can arrive in
+//! different normalization forms depending on the editor or filesystem
+//! (macOS stores decomposed names). Everything is normalized to NFC before
+//! rendering so the same title produces identical bytes in the head, JSON-LD,
+//! sitemap, and llms.txt.
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Longest meta description, in characters, before search results cut it.
+pub const DESCRIPTION_LIMIT: usize = 155;
+
+/// Returns `s` in Unicode Normalization Form C.
+pub fn nfc(s: &str) -> String {
+    s.nfc().collect()
+}
+
+/// Percent-encodes a single URL path segment (e.g. a slug).
+///
+/// The segment is NFC-normalized first, then every byte outside the RFC 3986
+/// unreserved set is encoded, so non-ASCII slugs produce valid URLs for the
+/// sitemap and canonical links.
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::new();
+    for byte in nfc(segment).bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard padded base64.
+pub fn base64(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Collapses runs of whitespace to single spaces and cuts the result to
+/// `limit` characters, marking a cut with `…`.
+pub fn plain_text(text: &str, limit: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= limit {
+        return collapsed;
+    }
+    let cut: String = collapsed.chars().take(limit).collect();
+    format!("{}…", cut.trim_end())
+}
+
+/// `text` as a meta description: whitespace collapsed and, past
+/// [`DESCRIPTION_LIMIT`], cut at the last word boundary that fits with a
+/// closing `…`. A single word too long to fit is cut between characters,
+/// never between a letter and its combining marks.
+pub fn meta_description(text: &str) -> String {
+    truncate_words(text, DESCRIPTION_LIMIT)
+}
+
+/// Collapses whitespace in `text` and cuts it to at most `limit`
+/// characters, `…` included, at a word boundary. A first word too long to
+/// fit is cut between characters; with no room for `…`, nothing is left.
+pub fn truncate_words(text: &str, limit: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= limit {
+        return collapsed;
+    }
+    if limit == 0 {
+        return String::new();
+    }
+    let chars: Vec<char> = collapsed.chars().collect();
+    let room = limit.saturating_sub(1);
+    let mut end = match chars[..=room].iter().rposition(|c| *c == ' ') {
+        Some(space) if space > 0 => space,
+        _ => room,
+    };
+    while end > 0 && is_combining_mark(chars[end]) {
+        end -= 1;
+    }
+    let cut: String = chars[..end].iter().collect();
+    let cut = cut.trim_end_matches(|c: char| c.is_whitespace() || ",;:-–—".contains(c));
+    format!("{}…", cut)
+}
+
+/// Splits `text` into lines of at most `width` characters at spaces, one
+/// paragraph per input line. A word longer than a line gets a line of its
+/// own.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Appends `params` to the query string of `url`, keeping any fragment last.
+///
+/// Keys and values are percent-encoded; a key already present in the query
+/// is left as is.
+pub fn append_query(url: &str, params: &[(&str, &str)]) -> String {
+    let (base, fragment) = match url.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (url, None),
+    };
+    let existing: Vec<&str> = base
+        .split_once('?')
+        .map(|(_, query)| {
+            query
+                .split('&')
+                .map(|p| p.split('=').next().unwrap_or(""))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut out = base.to_string();
+    for (key, value) in params {
+        let key = encode_path_segment(key);
+        if existing.contains(&key.as_str()) {
+            continue;
+        }
+        out.push(if out.contains('?') { '&' } else { '?' });
+        out.push_str(&format!("{}={}", key, encode_path_segment(value)));
+    }
+    if let Some(fragment) = fragment {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_at_spaces() {
+        assert_eq!(wrap("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap("a\nb", 10), ["a", "b"]);
+        assert_eq!(wrap("unbreakable", 4), ["unbreakable"]);
+    }
+
+    #[test]
+    fn nfc_composes_decomposed_text() {
+        // "é" as e + combining acute accent
+        assert_eq!(nfc("Caf\u{65}\u{301}"), "Caf\u{e9}");
+    }
+
+    #[test]
+    fn nfc_preserves_emoji_and_rtl() {
+        assert_eq!(nfc("🎨 فن"), "🎨 فن");
+    }
+
+    #[test]
+    fn ascii_slug_is_unchanged() {
+        assert_eq!(encode_path_segment("neon-dreams_2"), "neon-dreams_2");
+    }
+
+    #[test]
+    fn unicode_slug_is_percent_encoded() {
+        assert_eq!(encode_path_segment("café"), "caf%C3%A9");
+        assert_eq!(
+            encode_path_segment("cafe\u{301}"),
+            encode_path_segment("café")
+        );
+        assert_eq!(encode_path_segment("🎵"), "%F0%9F%8E%B5");
+    }
+
+    #[test]
+    fn query_params_are_appended_before_fragment() {
+        let params = [("ref", "everythingsings.art"), ("utm_source", "home page")];
+        assert_eq!(
+            append_query("https://a.test/x", &params),
+            "https://a.test/x?ref=everythingsings.art&utm_source=home%20page"
+        );
+        assert_eq!(
+            append_query("https://a.test/?q=1#top", &params[..1]),
+            "https://a.test/?q=1&ref=everythingsings.art#top"
+        );
+        assert_eq!(append_query("https://a.test/", &[]), "https://a.test/");
+    }
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn descriptions_cut_at_word_boundaries() {
+        assert_eq!(meta_description("  Short\n text "), "Short text");
+        let long = "word ".repeat(40);
+        let cut = meta_description(&long);
+        assert!(cut.chars().count() <= DESCRIPTION_LIMIT);
+        assert!(cut.ends_with("word…"), "{}", cut);
+        assert_eq!(truncate_words("one two, three", 10), "one two…");
+        assert_eq!(truncate_words("exactly ten", 11), "exactly ten");
+    }
+
+    #[test]
+    fn descriptions_never_split_characters() {
+        assert_eq!(truncate_words("🎨🎨🎨🎨", 3), "🎨🎨…");
+        assert_eq!(truncate_words("فن رقمي جديد", 9), "فن رقمي…");
+        // "é" as e + combining acute accent stays whole
+        assert_eq!(truncate_words("cafe\u{301}s", 5), "caf…");
+    }
+
+    #[test]
+    fn descriptions_fit_their_limit() {
+        assert_eq!(truncate_words("abc", 0), "");
+        assert_eq!(truncate_words("abc", 1), "…");
+        assert_eq!(truncate_words("Supercalifragilistic day", 6), "Super…");
+        let text = "Extraordinarily long-winded words, then short ones";
+        for limit in 0..60 {
+            let cut = truncate_words(text, limit);
+            assert!(cut.chars().count() <= limit, "{}: {}", limit, cut);
+        }
+    }
+
+    #[test]
+    fn plain_text_collapses_and_cuts() {
+        assert_eq!(plain_text(" a\n\n b\tc ", 10), "a b c");
+        let cut = plain_text(&"word ".repeat(10), 12);
+        assert_eq!(cut, "word word wo…");
+    }
+
+    #[test]
+    fn existing_query_keys_are_kept() {
+        assert_eq!(
+            append_query("https://a.test/?ref=other", &[("ref", "x"), ("s", "y")]),
+            "https://a.test/?ref=other&s=y"
+        );
+    }
+}
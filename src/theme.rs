@@ -0,0 +1,79 @@
+//! # Theme Toggle
+//!
+//! Builds with the `theme-toggle` feature let visitors override
+//! `prefers-color-scheme`: a [toggle](crate::components::ThemeToggle) in the
+//! footer switches between the light and dark palettes and remembers the
+//! choice in `localStorage`. Default builds ship neither the button nor any
+//! script.
+//!
+//! The choice is applied by [`INIT_SCRIPT`], inlined in `<head>` before the
+//! stylesheet, which sets `data-theme` on `<html>` before first paint, so a
+//! stored preference never flashes the other palette. The generated tokens
+//! honour the attribute (see [`crate::tokens::generate_tokens_css`]). The
+//! button stays `hidden` until the script reveals it, so it never shows
+//! without a working script. With a Content Security Policy, the script is
+//! allowed by its hash ([`csp_source`]).
+
+use crate::text::base64;
+use sha2::{Digest, Sha256};
+
+/// Whether this build has the toggle (`--features theme-toggle`).
+pub const THEME_TOGGLE_ENABLED: bool = cfg!(feature = "theme-toggle");
+
+/// `id` of the toggle button, which the script looks up.
+pub const TOGGLE_ID: &str = "theme-toggle";
+
+/// Applies the stored theme, then wires up the toggle once the page loads.
+pub const INIT_SCRIPT: &str = concat!(
+    "(function(){var r=document.documentElement,k=\"theme\";",
+    "try{var t=localStorage.getItem(k);if(t===\"light\"||t===\"dark\")r.dataset.theme=t}catch(e){}",
+    "document.addEventListener(\"DOMContentLoaded\",function(){",
+    "var b=document.getElementById(\"theme-toggle\");if(!b)return;",
+    "var light=function(){return(r.dataset.theme||",
+    "(matchMedia(\"(prefers-color-scheme: light)\").matches?\"light\":\"dark\"))===\"light\"};",
+    "var sync=function(){b.setAttribute(\"aria-pressed\",String(light()))};",
+    "b.addEventListener(\"click\",function(){var t=light()?\"dark\":\"light\";",
+    "r.dataset.theme=t;try{localStorage.setItem(k,t)}catch(e){}sync()});",
+    "sync();b.hidden=false})})();"
+);
+
+/// The inline `<script>` element, or an empty string without the feature.
+pub fn init_script_html() -> String {
+    if THEME_TOGGLE_ENABLED {
+        format!("<script>{}</script>", INIT_SCRIPT)
+    } else {
+        String::new()
+    }
+}
+
+/// The CSP `script-src` source allowing [`INIT_SCRIPT`], or `None` without
+/// the feature.
+pub fn csp_source() -> Option<String> {
+    THEME_TOGGLE_ENABLED.then(|| script_hash(INIT_SCRIPT))
+}
+
+/// The CSP hash source (`'sha256-…'`) of an inline `script`.
+fn script_hash(script: &str) -> String {
+    format!("'sha256-{}'", base64(&Sha256::digest(script.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_targets_the_toggle() {
+        assert!(INIT_SCRIPT.contains(&format!("getElementById(\"{}\")", TOGGLE_ID)));
+        assert!(!INIT_SCRIPT.contains("</script"));
+    }
+
+    #[test]
+    fn hash_is_a_csp_source() {
+        // Known value from the CSP specification's examples
+        assert_eq!(
+            script_hash("alert('Hello, world.');"),
+            "'sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng='"
+        );
+        assert_eq!(csp_source().is_some(), THEME_TOGGLE_ENABLED);
+    }
+}
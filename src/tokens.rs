@@ -0,0 +1,338 @@
+//! # Design Tokens
+//!
+//! The single source of truth for colors, spacing, typography, and motion.
+//! At build time the tokens are written as `:root` CSS custom properties
+//! (prepended to `main.css`), where the shader script reads its palette, and
+//! exported as `/tokens.json` for any non-CSS consumer.
+//!
+//! Before anything is written, every pair in [`CONTRAST_PAIRS`] is checked
+//! against WCAG AA in both palettes; [`CONTRAST_POLICY`] decides whether a
+//! failure stops the build or only prints a warning.
+
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+/// A named token and its CSS value; the name omits the `--` prefix.
+pub type Token = (&'static str, &'static str);
+
+/// Color tokens for one color scheme.
+pub struct Palette {
+    pub bg: &'static str,
+    pub text: &'static str,
+    pub text_muted: &'static str,
+    pub accent: &'static str,
+    pub link: &'static str,
+    pub link_hover: &'static str,
+    pub border: &'static str,
+}
+
+impl Palette {
+    /// The palette as `(name, value)` custom properties, in stylesheet order.
+    pub fn tokens(&self) -> [Token; 7] {
+        [
+            ("color-bg", self.bg),
+            ("color-text", self.text),
+            ("color-text-muted", self.text_muted),
+            ("color-accent", self.accent),
+            ("color-link", self.link),
+            ("color-link-hover", self.link_hover),
+            ("color-border", self.border),
+        ]
+    }
+}
+
+/// Default (dark) color scheme.
+pub const DARK: Palette = Palette {
+    bg: "#0d0d0d",
+    text: "#e8e8e8",
+    text_muted: "#a0a0a0",
+    accent: "#ffffff",
+    link: "#e8e8e8",
+    link_hover: "#ffffff",
+    border: "#333333",
+};
+
+/// Color scheme applied under `prefers-color-scheme: light`.
+pub const LIGHT: Palette = Palette {
+    bg: "#ffffff",
+    text: "#1a1a1a",
+    text_muted: "#666666",
+    accent: "#000000",
+    link: "#1a1a1a",
+    link_hover: "#000000",
+    border: "#e0e0e0",
+};
+
+/// Minimum WCAG AA contrast ratio for body text.
+pub const MIN_CONTRAST: f64 = 4.5;
+
+/// Foreground/background token pairs that must reach [`MIN_CONTRAST`].
+pub const CONTRAST_PAIRS: &[(&str, &str)] = &[
+    ("color-text", "color-bg"),
+    ("color-text-muted", "color-bg"),
+    ("color-accent", "color-bg"),
+    ("color-link", "color-bg"),
+    ("color-link-hover", "color-bg"),
+];
+
+/// What the build does when a contrast pair falls below AA.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContrastPolicy {
+    /// Abort generation with an error.
+    Fail,
+    /// Print a warning and continue.
+    Warn,
+}
+
+/// Active contrast policy.
+pub const CONTRAST_POLICY: ContrastPolicy = ContrastPolicy::Fail;
+
+/// Typography tokens.
+pub const TYPOGRAPHY: &[Token] = &[
+    (
+        "font-family",
+        "-apple-system, BlinkMacSystemFont, \"Segoe UI\", Roboto,\n    Oxygen, Ubuntu, Cantarell, \"Open Sans\", \"Helvetica Neue\", sans-serif",
+    ),
+    ("font-size-base", "16px"),
+    ("font-size-sm", "0.875rem"),
+    ("font-size-lg", "1.25rem"),
+];
+
+/// Spacing scale.
+pub const SPACING: &[Token] = &[
+    ("spacing-xs", "0.5rem"),
+    ("spacing-sm", "1rem"),
+    ("spacing-md", "1.5rem"),
+    ("spacing-lg", "2rem"),
+    ("spacing-xl", "3rem"),
+];
+
+/// Layout tokens.
+pub const LAYOUT: &[Token] = &[("max-width", "600px"), ("border-radius", "8px")];
+
+/// Quantum reveal animation timing.
+pub const MOTION: &[Token] = &[
+    ("ease-quantum", "cubic-bezier(0.16, 1, 0.3, 1)"),
+    ("ease-spring", "cubic-bezier(0.34, 1.56, 0.64, 1)"),
+    ("reveal-duration", "400ms"),
+];
+
+/// Parses a `#rgb` or `#rrggbb` hex color.
+pub fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.strip_prefix('#')?;
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// WCAG 2.x contrast ratio between two colors, from 1.0 to 21.0.
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (light, dark) = if la > lb { (la, lb) } else { (lb, la) };
+    (light + 0.05) / (dark + 0.05)
+}
+
+/// A configured color pair that falls below [`MIN_CONTRAST`].
+#[derive(Debug, PartialEq)]
+pub struct ContrastIssue {
+    pub scheme: &'static str,
+    pub foreground: &'static str,
+    pub background: &'static str,
+    pub ratio: f64,
+}
+
+impl fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} theme: --{} on --{} is {:.2}:1 (WCAG AA needs {}:1)",
+            self.scheme, self.foreground, self.background, self.ratio, MIN_CONTRAST
+        )
+    }
+}
+
+/// Checks `pairs` in each named palette, returning every pair below AA.
+///
+/// Pairs naming an unknown token or an unparsable color are reported with a
+/// ratio of 0 so typos in the configuration cannot pass silently.
+pub fn check_contrast(
+    palettes: &[(&'static str, &Palette)],
+    pairs: &[(&'static str, &'static str)],
+) -> Vec<ContrastIssue> {
+    let mut issues = Vec::new();
+    for (scheme, palette) in palettes {
+        let tokens = palette.tokens();
+        let color = |name: &str| {
+            tokens
+                .iter()
+                .find(|(n, _)| *n == name)
+                .and_then(|(_, v)| parse_hex_color(v))
+        };
+        for &(foreground, background) in pairs {
+            let ratio = match (color(foreground), color(background)) {
+                (Some(fg), Some(bg)) => contrast_ratio(fg, bg),
+                _ => 0.0,
+            };
+            if ratio < MIN_CONTRAST {
+                issues.push(ContrastIssue {
+                    scheme,
+                    foreground,
+                    background,
+                    ratio,
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Checks the configured pairs in the dark and light palettes.
+pub fn check_theme_contrast() -> Vec<ContrastIssue> {
+    check_contrast(&[("dark", &DARK), ("light", &LIGHT)], CONTRAST_PAIRS)
+}
+
+fn declarations(tokens: &[Token], indent: &str) -> String {
+    tokens
+        .iter()
+        .map(|(name, value)| format!("{}--{}: {};\n", indent, name, value))
+        .collect()
+}
+
+/// Generates the `:root` custom properties, dark by default with a light
+/// override under `prefers-color-scheme`. A `data-theme` attribute on
+/// `<html>` (set by the [theme toggle](crate::theme)) wins over the media
+/// query either way.
+pub fn generate_tokens_css() -> String {
+    let mut css = String::from(
+        "/* Design tokens - generated from src/tokens.rs, do not edit by hand */\n:root {\n",
+    );
+    for group in [&DARK.tokens()[..], TYPOGRAPHY, SPACING, LAYOUT, MOTION] {
+        css.push_str(&declarations(group, "  "));
+        css.push('\n');
+    }
+    css.pop();
+    css.push_str(
+        "}\n\n@media (prefers-color-scheme: light) {\n  :root:not([data-theme=\"dark\"]) {\n",
+    );
+    css.push_str(&declarations(&LIGHT.tokens(), "    "));
+    css.push_str("  }\n}\n\n:root[data-theme=\"light\"] {\n");
+    css.push_str(&declarations(&LIGHT.tokens(), "  "));
+    css.push_str("}\n");
+    css
+}
+
+fn token_map(tokens: &[Token]) -> Value {
+    Value::Object(
+        tokens
+            .iter()
+            .map(|(name, value)| (name.to_string(), json!(value.replace("\n    ", " "))))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+/// Generates the `tokens.json` export.
+pub fn generate_tokens_json() -> String {
+    let doc = json!({
+        "color": {
+            "dark": token_map(&DARK.tokens()),
+            "light": token_map(&LIGHT.tokens()),
+        },
+        "typography": token_map(TYPOGRAPHY),
+        "spacing": token_map(SPACING),
+        "layout": token_map(LAYOUT),
+        "motion": token_map(MOTION),
+    });
+    serde_json::to_string_pretty(&doc).expect("tokens serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css_declares_every_token() {
+        let css = generate_tokens_css();
+        for (name, _) in DARK.tokens().iter().chain(SPACING).chain(MOTION) {
+            assert!(css.contains(&format!("--{}:", name)), "missing --{}", name);
+        }
+    }
+
+    #[test]
+    fn css_has_light_override() {
+        let css = generate_tokens_css();
+        let light = &css[css.find("prefers-color-scheme: light").unwrap()..];
+        assert!(light.contains(&format!("--color-bg: {};", LIGHT.bg)));
+    }
+
+    #[test]
+    fn data_theme_overrides_the_media_query() {
+        let css = generate_tokens_css();
+        assert!(css.contains(":root:not([data-theme=\"dark\"]) {"));
+        let forced = &css[css.find(":root[data-theme=\"light\"]").unwrap()..];
+        assert!(forced.contains(&format!("--color-bg: {};", LIGHT.bg)));
+    }
+
+    #[test]
+    fn json_export_matches_palette() {
+        let json: Value = serde_json::from_str(&generate_tokens_json()).unwrap();
+        assert_eq!(json["color"]["dark"]["color-bg"], DARK.bg);
+        assert_eq!(json["color"]["light"]["color-text"], LIGHT.text);
+        assert_eq!(json["spacing"]["spacing-md"], "1.5rem");
+    }
+
+    #[test]
+    fn contrast_ratio_matches_wcag_extremes() {
+        let ratio = contrast_ratio((0, 0, 0), (255, 255, 255));
+        assert!((ratio - 21.0).abs() < 0.01);
+        assert!((contrast_ratio((10, 10, 10), (10, 10, 10)) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn shipped_palettes_meet_aa() {
+        let issues = check_theme_contrast();
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn low_contrast_pair_is_reported() {
+        let washed_out = Palette {
+            text_muted: "#bbbbbb",
+            ..LIGHT
+        };
+        let issues = check_contrast(&[("light", &washed_out)], CONTRAST_PAIRS);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].foreground, "color-text-muted");
+        assert!(issues[0].to_string().contains("light theme"));
+    }
+
+    #[test]
+    fn unknown_token_is_reported() {
+        let issues = check_contrast(&[("dark", &DARK)], &[("color-nope", "color-bg")]);
+        assert_eq!(issues[0].ratio, 0.0);
+    }
+
+    #[test]
+    fn stylesheet_does_not_redeclare_tokens() {
+        let css = include_str!("../style/main.css");
+        assert!(
+            !css.contains("--color-bg:"),
+            "Tokens belong in src/tokens.rs, not style/main.css"
+        );
+    }
+}
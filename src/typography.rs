@@ -0,0 +1,178 @@
+//! # Typography
+//!
+//! An optional pass over rendered text, on when [`TYPOGRAPHY`] is set,
+//! applied to Markdown as it renders (see [`crate::markdown`]) and to the
+//! config strings shown in page bodies through [`typeset`]:
+//!
+//! | Source | Result |
+//! |--------|--------|
+//! | `"quoted"`, `it's` | “quoted”, it’s |
+//! | `--`, `---`, ` - ` | –, —, ` — ` |
+//! | `...` | … |
+//! | `24 MB`, `120 BPM` | a no-break space before the unit |
+//! | `…the last word` | a no-break space before the last word of a block, so it never sits alone on a line |
+//!
+//! Code spans and blocks are left alone. No-break spaces are written as
+//! U+00A0, which is `&nbsp;` without the entity.
+//!
+//! [`TYPOGRAPHY`]: crate::config::TYPOGRAPHY
+
+use crate::config::TYPOGRAPHY;
+
+/// No-break space.
+pub const NBSP: char = '\u{a0}';
+
+/// Units kept on the line of the number before them.
+const UNITS: &[&str] = &[
+    "%", "°C", "°F", "px", "pt", "mm", "cm", "m", "km", "g", "kg", "ms", "s", "min", "h", "Hz",
+    "kHz", "MHz", "KB", "MB", "GB", "TB", "dpi", "fps", "BPM", "bpm",
+];
+
+/// Rewrites punctuation in a run of text split over several calls (e.g.
+/// Markdown text around emphasis), so quotes open and close correctly
+/// across the pieces.
+#[derive(Clone, Debug, Default)]
+pub struct Typesetter {
+    /// The last character seen, typeset or not.
+    prev: Option<char>,
+}
+
+impl Typesetter {
+    /// Records `text` as seen without rewriting it, e.g. inline code.
+    pub fn skip(&mut self, text: &str) {
+        self.prev = text.chars().last().or(self.prev);
+    }
+
+    /// Starts a new block: the next quote opens.
+    pub fn reset(&mut self) {
+        self.prev = None;
+    }
+
+    /// `text` with smart quotes, dashes, ellipses, and no-break spaces
+    /// before units.
+    pub fn text(&mut self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::with_capacity(text.len() + 8);
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let opens = self
+                .prev
+                .is_none_or(|p| p.is_whitespace() || "([{—–".contains(p));
+            let run = chars[i..].iter().take_while(|&&d| d == c).count();
+            let replaced = match c {
+                '"' => (if opens { '“' } else { '”' }, 1),
+                '\'' => (if opens { '‘' } else { '’' }, 1),
+                '-' if run >= 3 => ('—', 3),
+                '-' if run == 2 => ('–', 2),
+                '-' if opens && chars.get(i + 1) == Some(&' ') && self.prev.is_some() => ('—', 1),
+                '.' if run >= 3 => ('…', 3),
+                ' ' if self.prev.is_some_and(|p| p.is_ascii_digit())
+                    && unit_follows(&chars[i + 1..]) =>
+                {
+                    (NBSP, 1)
+                }
+                _ => (c, 1),
+            };
+            out.push(replaced.0);
+            self.prev = Some(replaced.0);
+            i += replaced.1;
+        }
+        out
+    }
+}
+
+/// Whether `rest` starts with a unit followed by the end of the word.
+fn unit_follows(rest: &[char]) -> bool {
+    UNITS.iter().any(|unit| {
+        let len = unit.chars().count();
+        rest.len() >= len
+            && rest[..len].iter().copied().eq(unit.chars())
+            && rest.get(len).is_none_or(|c| !c.is_alphanumeric())
+    })
+}
+
+/// `text` with its last two words joined by a no-break space. Text of a
+/// single word, perhaps after a space, is left as is.
+pub fn prevent_widow(text: &str) -> String {
+    let trimmed = text.trim_end();
+    match trimmed.rfind(' ') {
+        Some(i) if !trimmed[..i].trim().is_empty() => {
+            format!("{}{}{}", &text[..i], NBSP, &text[i + 1..])
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// The full pass over a standalone string, e.g. a description from
+/// config, or `text` unchanged when [`TYPOGRAPHY`] is off.
+pub fn typeset(text: &str) -> String {
+    if !TYPOGRAPHY {
+        return text.to_string();
+    }
+    prevent_widow(&Typesetter::default().text(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> String {
+        Typesetter::default().text(s)
+    }
+
+    #[test]
+    fn quotes_curl_by_position() {
+        assert_eq!(
+            text(r#""Hello," she said. It's 'fine'."#),
+            "“Hello,” she said. It’s ‘fine’."
+        );
+        assert_eq!(text(r#"("quoted")"#), "(“quoted”)");
+    }
+
+    #[test]
+    fn quotes_close_across_pieces() {
+        let mut t = Typesetter::default();
+        let pieces = [t.text("\""), t.text("emphasis"), t.text("\" here")];
+        assert_eq!(pieces.concat(), "“emphasis” here");
+        t.reset();
+        assert_eq!(t.text("'a'"), "‘a’");
+    }
+
+    #[test]
+    fn dashes_and_ellipses() {
+        assert_eq!(text("1990--2000"), "1990–2000");
+        assert_eq!(text("art---or not"), "art—or not");
+        assert_eq!(text("art - or not"), "art — or not");
+        assert_eq!(text("- list-like"), "- list-like");
+        assert_eq!(text("well..."), "well…");
+    }
+
+    #[test]
+    fn units_stay_with_numbers() {
+        assert_eq!(
+            text("A 24 MB file at 120 BPM"),
+            "A 24\u{a0}MB file at 120\u{a0}BPM"
+        );
+        assert_eq!(text("5 months"), "5 months");
+        assert_eq!(text("3 s."), "3\u{a0}s.");
+    }
+
+    #[test]
+    fn last_words_are_joined() {
+        assert_eq!(prevent_widow("one two three"), "one two\u{a0}three");
+        assert_eq!(prevent_widow(" two"), " two");
+        assert_eq!(prevent_widow("one"), "one");
+        assert_eq!(prevent_widow("one two three\n"), "one two\u{a0}three\n");
+    }
+
+    #[test]
+    fn typeset_follows_config() {
+        let set = typeset("It's \"art\" and more");
+        if TYPOGRAPHY {
+            assert_eq!(set, "It’s “art” and\u{a0}more");
+        } else {
+            assert_eq!(set, "It's \"art\" and more");
+        }
+    }
+}
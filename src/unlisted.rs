@@ -0,0 +1,87 @@
+//! # Unlisted Pages
+//!
+//! A series with `unlisted = true` in its `series.toml` is built, but only
+//! reachable by its link, for sharing a draft with collaborators:
+//!
+//! ```text
+//! /art/neon-dreams/                        listed
+//! /preview/3f9c1a…e2/                      unlisted
+//! ```
+//!
+//! The path is a keyed hash of the page's slug, so it can't be guessed from
+//! the title and stays the same across builds (and edits) while the key
+//! does. The key is [`UNLISTED_KEY_ENV`]; without it anyone who knows the
+//! slug could recompute the hash, so the build fails when a series is
+//! unlisted and the key is unset. The series' source directory is copied
+//! under the hashed path only, never to `/art/<dir>/`.
+//!
+//! Unlisted pages are left out of the sitemap, feeds, listings, `llms.txt`,
+//! and exports, and are marked `noindex` (see
+//! [`PageMeta::noindex`](crate::components::PageMeta)) in case the link
+//! gets around.
+
+use sha2::{Digest, Sha256};
+
+/// Directory of unlisted pages.
+pub const UNLISTED_DIR: &str = "preview";
+
+/// Environment variable holding the secret unlisted paths are hashed with.
+pub const UNLISTED_KEY_ENV: &str = "ESART_UNLISTED_KEY";
+
+/// Hex digits of the hash in a path (128 bits).
+const TOKEN_LEN: usize = 32;
+
+/// The unguessable name of the page `id` (e.g. `art/neon-dreams`) under
+/// `key`.
+pub fn token(key: &str, id: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hasher.update([0]);
+    hasher.update(id.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>()[..TOKEN_LEN]
+        .to_string()
+}
+
+/// The key in [`UNLISTED_KEY_ENV`], an error when it is unset or empty.
+pub fn key() -> Result<String, String> {
+    match std::env::var(UNLISTED_KEY_ENV) {
+        Ok(key) if !key.is_empty() => Ok(key),
+        _ => Err(format!(
+            "{} is not set; unlisted paths could be derived from their slugs",
+            UNLISTED_KEY_ENV
+        )),
+    }
+}
+
+/// Path segments of the unlisted page `id`, hashed with the key in
+/// [`UNLISTED_KEY_ENV`]. Builds check the key first (see [`key`]).
+pub fn segments(id: &str) -> Vec<String> {
+    vec![
+        UNLISTED_DIR.to_string(),
+        token(&key().unwrap_or_default(), id),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_stable_and_keyed() {
+        let token = token("secret", "art/neon");
+        assert_eq!(token.len(), TOKEN_LEN);
+        assert!(token.bytes().all(|b| b.is_ascii_hexdigit()));
+        assert_eq!(token, super::token("secret", "art/neon"));
+        assert_ne!(token, super::token("other", "art/neon"));
+        assert_ne!(token, super::token("secret", "art/neon-2"));
+    }
+
+    #[test]
+    fn pages_live_in_the_preview_directory() {
+        assert_eq!(segments("art/neon")[0], UNLISTED_DIR);
+    }
+}
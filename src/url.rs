@@ -0,0 +1,189 @@
+//! # URLs
+//!
+//! Every absolute URL the site outputs (head tags, sitemap, feeds, JSON-LD,
+//! JSON exports) is built here from a site-relative path, instead of by
+//! concatenating strings:
+//!
+//! ```text
+//! absolute("/art/")                  https://everythingsings.art/art/
+//! absolute("art//neon/")             https://everythingsings.art/art/neon/
+//! dir_path(&["art", "néon dreams"])  /art/n%C3%A9on%20dreams/
+//! ```
+//!
+//! ## Trailing slashes
+//!
+//! Directory URLs end in `/`; files (`/feed.xml`, `/api/profile.json`)
+//! don't. [`normalize_path`] leaves the slash policy of a path as it is,
+//! only fixing slashes that would change its meaning.
+//!
+//! ## Page layout
+//!
+//! Where pages are written, and so their URLs, follows
+//! [`OUTPUT_LAYOUT`](crate::config::OUTPUT_LAYOUT) (see [`Layout`]). Pages
+//! are addressed by their path segments, e.g. `["art", "neon"]`, and
+//! [`page_path`] and [`page_file`] give their URL and output file. Assets
+//! next to a page (covers, oEmbed documents) live in its directory,
+//! [`dir_path`], in either layout.
+
+use crate::config::{OUTPUT_LAYOUT, SITE_URL};
+use crate::text::encode_path_segment;
+use std::path::PathBuf;
+
+/// How pages are written to disk, and so what their URLs look like.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// `art/neon/index.html`, linked as `/art/neon/`.
+    Directory,
+    /// `art/neon.html`, linked as `/art/neon.html`.
+    File,
+}
+
+/// Path segments of the homepage.
+pub const HOME: &[&str] = &[];
+
+impl Layout {
+    /// The site-relative URL of the page at `segments`, each
+    /// percent-encoded. The homepage ([`HOME`]) is `/` in either layout.
+    pub fn page_path<S: AsRef<str>>(self, segments: &[S]) -> String {
+        match self {
+            Layout::Directory => dir_path(segments),
+            Layout::File => match dir_path(segments).strip_suffix('/') {
+                Some("") | None => "/".to_string(),
+                Some(path) => format!("{}.html", path),
+            },
+        }
+    }
+
+    /// The output file of the page at `segments`, relative to the site root.
+    pub fn page_file<S: AsRef<str>>(self, segments: &[S]) -> PathBuf {
+        let segments: Vec<&str> = segments
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|s| !s.is_empty())
+            .collect();
+        let mut path: PathBuf = segments.iter().collect();
+        match (self, segments.last()) {
+            (Layout::File, Some(last)) => path.set_file_name(format!("{}.html", last)),
+            _ => path.push("index.html"),
+        }
+        path
+    }
+}
+
+/// `path` with a leading `/` and runs of `/` collapsed, up to any query or
+/// fragment, which is kept as is.
+pub fn normalize_path(path: &str) -> String {
+    let split = path.find(['?', '#']).unwrap_or(path.len());
+    let (path, rest) = path.split_at(split);
+    let mut out = String::from("/");
+    for c in path.chars() {
+        if c != '/' || !out.ends_with('/') {
+            out.push(c);
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Joins the absolute URL `base` and the path `path`, with exactly one `/`
+/// between them.
+pub fn join(base: &str, path: &str) -> String {
+    format!("{}{}", base.trim_end_matches('/'), normalize_path(path))
+}
+
+/// The absolute URL of the site-relative `path` on the site.
+pub fn absolute(path: &str) -> String {
+    join(SITE_URL, path)
+}
+
+/// The site-relative URL of the page at `segments` in the configured
+/// layout, e.g. `/art/neon-dreams/`.
+pub fn page_path<S: AsRef<str>>(segments: &[S]) -> String {
+    OUTPUT_LAYOUT.page_path(segments)
+}
+
+/// The output file of the page at `segments` in the configured layout,
+/// e.g. `art/neon-dreams/index.html`.
+pub fn page_file<S: AsRef<str>>(segments: &[S]) -> PathBuf {
+    OUTPUT_LAYOUT.page_file(segments)
+}
+
+/// The site-relative URL of the directory at `segments`, each
+/// percent-encoded, e.g. `/art/neon-dreams/`.
+pub fn dir_path<S: AsRef<str>>(segments: &[S]) -> String {
+    let mut out = String::from("/");
+    for segment in segments.iter().map(AsRef::as_ref).filter(|s| !s.is_empty()) {
+        out.push_str(&encode_path_segment(segment));
+        out.push('/');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_rooted_with_single_slashes() {
+        assert_eq!(normalize_path(""), "/");
+        assert_eq!(normalize_path("art/"), "/art/");
+        assert_eq!(normalize_path("//art///neon/"), "/art/neon/");
+        assert_eq!(normalize_path("/feed.xml"), "/feed.xml");
+    }
+
+    #[test]
+    fn queries_and_fragments_are_kept() {
+        assert_eq!(normalize_path("/#website"), "/#website");
+        assert_eq!(normalize_path("/log/#a//b"), "/log/#a//b");
+        assert_eq!(
+            normalize_path("/search?u=https://x.test/"),
+            "/search?u=https://x.test/"
+        );
+    }
+
+    #[test]
+    fn joins_never_double_slashes() {
+        assert_eq!(join("https://a.test", "/art/"), "https://a.test/art/");
+        assert_eq!(join("https://a.test/", "/art/"), "https://a.test/art/");
+        assert_eq!(join("https://a.test/", "art"), "https://a.test/art");
+        assert_eq!(join("https://a.test", "/"), "https://a.test/");
+        assert_eq!(absolute("/"), format!("{}/", SITE_URL));
+    }
+
+    #[test]
+    fn directory_paths_are_encoded_and_end_in_a_slash() {
+        assert_eq!(dir_path(HOME), "/");
+        assert_eq!(dir_path(&["art", "neon-dreams"]), "/art/neon-dreams/");
+        assert_eq!(
+            dir_path(&["art", "néon dreams"]),
+            "/art/n%C3%A9on%20dreams/"
+        );
+        assert_eq!(dir_path(&["a/b"]), "/a%2Fb/");
+    }
+
+    #[test]
+    fn directory_layout_writes_index_files() {
+        let layout = Layout::Directory;
+        assert_eq!(layout.page_path(&["art", "neon"]), "/art/neon/");
+        assert_eq!(
+            layout.page_file(&["art", "neon"]),
+            PathBuf::from("art/neon/index.html")
+        );
+        assert_eq!(layout.page_path(HOME), "/");
+        assert_eq!(layout.page_file(HOME), PathBuf::from("index.html"));
+    }
+
+    #[test]
+    fn file_layout_writes_html_files() {
+        let layout = Layout::File;
+        assert_eq!(layout.page_path(&["art", "néon"]), "/art/n%C3%A9on.html");
+        assert_eq!(
+            layout.page_file(&["art", "néon"]),
+            PathBuf::from("art/néon.html")
+        );
+        assert_eq!(layout.page_path(&["cv"]), "/cv.html");
+        assert_eq!(layout.page_file(&["v1.2"]), PathBuf::from("v1.2.html"));
+        assert_eq!(layout.page_path(HOME), "/");
+        assert_eq!(layout.page_file(HOME), PathBuf::from("index.html"));
+    }
+}
@@ -0,0 +1,332 @@
+//! # Webmentions
+//!
+//! Received webmentions, read from the [webmention.io](https://webmention.io)
+//! JF2 export at build time (through [`crate::cache`]). The `/guestbook/`
+//! page is their target: anyone can sign it by linking to the page from
+//! their own site and sending a webmention, and the next build bakes the
+//! mention into the page.
+//!
+//! The guestbook is moderated by `guestbook.toml`. With
+//! [`GUESTBOOK_REQUIRE_APPROVAL`](crate::config::GUESTBOOK_REQUIRE_APPROVAL)
+//! on, only approved mentions are shown; blocked ones never are. Entries are
+//! source URLs or origins:
+//!
+//! ```toml
+//! approved = ["https://friend.example/2025/01/hello"]
+//! blocked = ["https://spam.example"]
+//! ```
+//!
+//! Mention text is reduced to plain text: no markup or images from the
+//! sender reach the page.
+//!
+//! ## Endpoint discovery
+//!
+//! The configured endpoints ([`endpoints`]) are advertised from every page's
+//! head and, by the local server, in a `Link` header, so IndieWeb software
+//! finds where to send mentions. Visitors without such software use the
+//! form on art and release pages (see
+//! [`MentionForm`](crate::components::MentionForm)).
+
+use crate::cache::{cached_fetch, json_text};
+use crate::config::{PINGBACK_ENDPOINT, WEBMENTION_ENDPOINT};
+use crate::escape::escape_html;
+use crate::escape::json_script;
+use crate::hints::origin;
+use crate::text::{append_query, plain_text};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Duration;
+
+/// Output directory of the guestbook page.
+pub const GUESTBOOK_DIR: &str = "guestbook";
+
+/// Moderation file, relative to the project root.
+pub const MODERATION_PATH: &str = "guestbook.toml";
+
+/// Description of the guestbook page.
+pub const GUESTBOOK_DESCRIPTION: &str =
+    "Sign by linking to this page from your own site and sending a webmention.";
+
+/// Cache entry holding the guestbook's mentions.
+pub const CACHE_NAME: &str = "webmentions-guestbook.json";
+
+/// Mention text longer than this many characters is cut short.
+pub const MENTION_TEXT_LIMIT: usize = 500;
+
+/// How long cached mentions are used before refetching.
+const MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// A received webmention, or a reply from another network presented as one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mention {
+    /// URL of the post that mentions the target.
+    pub source: String,
+    pub author_name: String,
+    /// The author's site; only `http(s)` URLs are kept.
+    pub author_url: Option<String>,
+    /// RFC 3339 time the post was published, or received when unknown.
+    pub published: String,
+    /// Plain text of the post, whitespace collapsed and length-limited.
+    pub text: String,
+}
+
+impl Mention {
+    /// The mention as a Schema.org `Comment`.
+    pub fn to_comment(&self) -> Value {
+        let mut author = json!({ "@type": "Person", "name": self.author_name });
+        if let Some(author_url) = &self.author_url {
+            author["url"] = json!(author_url);
+        }
+        json!({
+            "@type": "Comment",
+            "url": self.source,
+            "author": author,
+            "dateCreated": self.published,
+            "text": self.text,
+        })
+    }
+}
+
+/// `url` if it is an absolute `http(s)` URL.
+pub fn web_url(url: Option<String>) -> Option<String> {
+    url.filter(|u| u.starts_with("https://") || u.starts_with("http://"))
+}
+
+/// Parses a webmention.io JF2 feed, newest first. Private mentions and
+/// entries without a source URL are dropped.
+pub fn parse_jf2(json: &str) -> Vec<Mention> {
+    let Ok(doc) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+    let mut mentions: Vec<Mention> = doc["children"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry["wm-private"] != true)
+        .filter_map(|entry| {
+            let source =
+                web_url(json_text(entry, "wm-source").or_else(|| json_text(entry, "url")))?;
+            let author = &entry["author"];
+            let author_url = web_url(json_text(author, "url"));
+            let author_name = json_text(author, "name")
+                .or_else(|| author_url.as_deref().and_then(origin))
+                .or_else(|| origin(&source))?;
+            let content = json_text(&entry["content"], "text")
+                .or_else(|| json_text(entry, "name"))
+                .unwrap_or_default();
+            Some(Mention {
+                author_name: plain_text(&author_name, MENTION_TEXT_LIMIT),
+                author_url,
+                published: json_text(entry, "published")
+                    .or_else(|| json_text(entry, "wm-received"))?,
+                text: plain_text(&content, MENTION_TEXT_LIMIT),
+                source,
+            })
+        })
+        .collect();
+    mentions.sort_by(|a, b| b.published.cmp(&a.published));
+    mentions
+}
+
+/// Approved and blocked sources, from `guestbook.toml`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Moderation {
+    #[serde(default)]
+    pub approved: Vec<String>,
+    #[serde(default)]
+    pub blocked: Vec<String>,
+}
+
+/// Whether `entry` (a URL or an origin) names the `source` post.
+fn matches(entry: &str, source: &str) -> bool {
+    let entry = entry.trim_end_matches('/');
+    entry == source.trim_end_matches('/') || origin(source).as_deref() == Some(entry)
+}
+
+impl Moderation {
+    /// Loads the moderation file from `path`; `Ok(None)` when it is absent.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parses moderation TOML.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Could not parse: {}", e))
+    }
+
+    pub fn is_approved(&self, mention: &Mention) -> bool {
+        self.approved.iter().any(|e| matches(e, &mention.source))
+    }
+
+    pub fn is_blocked(&self, mention: &Mention) -> bool {
+        self.blocked.iter().any(|e| matches(e, &mention.source))
+    }
+
+    /// Whether `mention` is shown: never when blocked, and only when
+    /// approved if `require_approval`.
+    pub fn allows(&self, mention: &Mention, require_approval: bool) -> bool {
+        !self.is_blocked(mention) && (!require_approval || self.is_approved(mention))
+    }
+}
+
+/// The configured endpoints as `(rel, URL)` pairs: webmention, then
+/// pingback.
+pub fn endpoints() -> Vec<(&'static str, &'static str)> {
+    [
+        ("webmention", WEBMENTION_ENDPOINT),
+        ("pingback", PINGBACK_ENDPOINT),
+    ]
+    .into_iter()
+    .filter_map(|(rel, url)| url.map(|url| (rel, url)))
+    .collect()
+}
+
+/// `<link>` elements advertising `endpoints`, each on its own line after a
+/// newline.
+pub fn endpoint_links_html(endpoints: &[(&str, &str)]) -> String {
+    endpoints
+        .iter()
+        .map(|(rel, url)| format!("\n<link rel=\"{}\" href=\"{}\" />", rel, escape_html(url)))
+        .collect()
+}
+
+/// The HTTP `Link` header value advertising `endpoints`; `None` without
+/// any.
+pub fn link_header(endpoints: &[(&str, &str)]) -> Option<String> {
+    (!endpoints.is_empty()).then(|| {
+        endpoints
+            .iter()
+            .map(|(rel, url)| format!("<{}>; rel=\"{}\"", url, rel))
+            .collect::<Vec<_>>()
+            .join(", ")
+    })
+}
+
+/// webmention.io API URL listing the mentions of `target`.
+pub fn export_url(target: &str) -> String {
+    append_query(
+        "https://webmention.io/api/mentions.jf2",
+        &[("target", target), ("per-page", "100")],
+    )
+}
+
+/// Loads the mentions of the guestbook page at `target`, fetching them if
+/// the cache is stale. Returns none when neither cached nor reachable.
+pub fn load_mentions(target: &str) -> Vec<Mention> {
+    cached_fetch(CACHE_NAME, &export_url(target), MAX_AGE)
+        .map(|json| parse_jf2(&json))
+        .unwrap_or_default()
+}
+
+/// Builds the `/guestbook/` JSON-LD: a `WebPage` with each mention as a
+/// `Comment`.
+pub fn guestbook_json_ld(url: &str, mentions: &[Mention]) -> String {
+    let comments: Vec<Value> = mentions.iter().map(Mention::to_comment).collect();
+    json_script(&json!({
+        "@context": "https://schema.org",
+        "@type": "WebPage",
+        "name": "Guestbook",
+        "url": url,
+        "description": GUESTBOOK_DESCRIPTION,
+        "commentCount": comments.len(),
+        "comment": comments,
+    }))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const JF2: &str = r#"{"type": "feed", "children": [
+        {"type": "entry", "wm-id": 1, "wm-source": "https://friend.test/hello",
+         "url": "https://friend.test/hello", "published": "2025-01-17T12:00:00+00:00",
+         "author": {"type": "card", "name": "Friend", "url": "https://friend.test/",
+                    "photo": "https://friend.test/me.jpg"},
+         "content": {"html": "<p>Hi <script>x</script></p>", "text": "Hi\n\n  there"}},
+        {"type": "entry", "wm-id": 2, "wm-source": "https://spam.test/buy",
+         "wm-received": "2025-01-18T00:00:00Z",
+         "author": {"name": "", "url": "javascript:alert(1)"}},
+        {"type": "entry", "wm-id": 3, "wm-source": "https://private.test/p",
+         "wm-private": true, "published": "2025-01-19T00:00:00Z"}
+    ]}"#;
+
+    pub fn sample_mentions() -> Vec<Mention> {
+        parse_jf2(JF2)
+    }
+
+    const ENDPOINTS: &[(&str, &str)] = &[
+        ("webmention", "https://wm.test/webmention"),
+        ("pingback", "https://wm.test/xmlrpc"),
+    ];
+
+    #[test]
+    fn endpoints_are_advertised_as_links() {
+        assert_eq!(
+            endpoint_links_html(ENDPOINTS),
+            "\n<link rel=\"webmention\" href=\"https://wm.test/webmention\" />\
+             \n<link rel=\"pingback\" href=\"https://wm.test/xmlrpc\" />"
+        );
+        assert_eq!(
+            link_header(ENDPOINTS).as_deref(),
+            Some("<https://wm.test/webmention>; rel=\"webmention\", <https://wm.test/xmlrpc>; rel=\"pingback\"")
+        );
+        assert_eq!(link_header(&[]), None);
+    }
+
+    #[test]
+    fn parses_public_mentions_newest_first() {
+        let mentions = sample_mentions();
+        assert_eq!(mentions.len(), 2);
+        assert_eq!(mentions[0].source, "https://spam.test/buy");
+        assert_eq!(mentions[0].published, "2025-01-18T00:00:00Z");
+        assert_eq!(
+            mentions[1],
+            Mention {
+                source: "https://friend.test/hello".to_string(),
+                author_name: "Friend".to_string(),
+                author_url: Some("https://friend.test/".to_string()),
+                published: "2025-01-17T12:00:00+00:00".to_string(),
+                text: "Hi there".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn unsafe_author_urls_are_dropped() {
+        let spam = &sample_mentions()[0];
+        assert_eq!(spam.author_url, None);
+        assert_eq!(spam.author_name, "https://spam.test");
+    }
+
+    #[test]
+    fn moderation_matches_urls_and_origins() {
+        let moderation = Moderation::parse(
+            r#"
+approved = ["https://friend.test/hello/"]
+blocked = ["https://spam.test"]
+"#,
+        )
+        .unwrap();
+        let mentions = sample_mentions();
+        assert!(moderation.allows(&mentions[1], true));
+        assert!(!moderation.allows(&mentions[0], false));
+        assert!(Moderation::default().allows(&mentions[0], false));
+        assert!(!Moderation::default().allows(&mentions[0], true));
+    }
+
+    #[test]
+    fn export_url_encodes_target() {
+        assert_eq!(
+            export_url("https://a.test/guestbook/"),
+            "https://webmention.io/api/mentions.jf2?target=https%3A%2F%2Fa.test%2Fguestbook%2F&per-page=100"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! # WebSub
+//!
+//! With a [WebSub](https://www.w3.org/TR/websub/) hub configured
+//! ([`WEBSUB_HUB`](crate::config::WEBSUB_HUB)), generated feeds name it with
+//! `<atom:link rel="hub">`, so readers can subscribe through the hub and get
+//! new items pushed instead of polling. After a deploy, the `ping-hub`
+//! command tells the hub every feed changed ([`publish`]), and the hub
+//! fetches and fans them out:
+//!
+//! ```bash
+//! ./target/release/everythingsings ping-hub
+//! ```
+//!
+//! Pinging before the new feeds are live would have the hub fetch the old
+//! ones, so the command is separate from `--generate-static`.
+
+/// Identifies publish requests to the hub.
+const USER_AGENT: &str = concat!("everythingsings-websub/", env!("CARGO_PKG_VERSION"));
+
+/// The form fields of a publish request for the feed at `feed_url`.
+pub fn publish_form(feed_url: &str) -> [(&'static str, &str); 2] {
+    [("hub.mode", "publish"), ("hub.url", feed_url)]
+}
+
+/// Tells `hub` the feed at `feed_url` changed. Hubs answer a valid publish
+/// request with a 2xx status.
+pub fn publish(hub: &str, feed_url: &str) -> Result<(), String> {
+    ureq::post(hub)
+        .header("User-Agent", USER_AGENT)
+        .send_form(publish_form(feed_url))
+        .map(|_| ())
+        .map_err(|e| format!("Publish {} to {}: {}", feed_url, hub, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publish_requests_name_the_feed() {
+        assert_eq!(
+            publish_form("https://a.test/log/feed.xml"),
+            [
+                ("hub.mode", "publish"),
+                ("hub.url", "https://a.test/log/feed.xml")
+            ]
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! # `.well-known` Registry
+//!
+//! Every document published under `/.well-known/` comes from
+//! [`documents`], each from its own config constant, so they are generated
+//! together, listed together in the build output, served with the right
+//! content type by the local server, and covered by one set of tests:
+//!
+//! | Document | Config | Purpose |
+//! |----------|--------|---------|
+//! | `security.txt` | [`SECURITY_CONTACT`] | Where to report vulnerabilities (RFC 9116) |
+//! | `nostr.json` | [`NOSTR_NAMES`] | NIP-05 names such as `_@everythingsings.art` |
+//! | `webfinger` | [`FEDIVERSE_ACCOUNT`] | Finds the fediverse account from `@…@everythingsings.art` |
+//! | `lnurlp/<user>` | [`LNURLP`] | Lightning address `user@everythingsings.art` |
+//! | `tdmrep.json` | [`TDMREP`] | Text and data mining reservation (TDMRep) |
+//! | `keybase.txt` | [`KEYBASE_PROOF`] | Keybase's signed proof that the site is the owner's |
+//! | `atproto-did` | [`ATPROTO_DID`] | Verifies the domain as a Bluesky handle |
+//!
+//! Documents whose constant is unset are not written. The Keybase proof is
+//! signed text, so it is copied from the file the constant names rather
+//! than generated. A static host answers
+//! every WebFinger query with the same document, which is what an alias for
+//! a single account needs. The build report records a hash of each
+//! document (see [`crate::report`]), so `diff` catches a changed key or
+//! address even when its size stays the same.
+//!
+//! [`SECURITY_CONTACT`]: crate::config::SECURITY_CONTACT
+//! [`NOSTR_NAMES`]: crate::config::NOSTR_NAMES
+//! [`FEDIVERSE_ACCOUNT`]: crate::config::FEDIVERSE_ACCOUNT
+//! [`LNURLP`]: crate::config::LNURLP
+//! [`TDMREP`]: crate::config::TDMREP
+//! [`KEYBASE_PROOF`]: crate::config::KEYBASE_PROOF
+//! [`ATPROTO_DID`]: crate::config::ATPROTO_DID
+
+use crate::config::{
+    ATPROTO_DID, FEDIVERSE_ACCOUNT, LNURLP, NOSTR_NAMES, SECURITY_CONTACT, SITE_DOMAIN,
+    SITE_LANGUAGE, TDMREP,
+};
+use crate::datetime::Date;
+use crate::url::absolute;
+use serde_json::json;
+
+/// Output subdirectory of the documents.
+pub const WELL_KNOWN_DIR: &str = ".well-known";
+
+/// A Lightning address served as a static LNURL-pay document, copied from
+/// the wallet provider's own `/.well-known/lnurlp/<user>`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lnurlp {
+    /// The local part of the address, e.g. `tips` for `tips@everythingsings.art`.
+    pub user: &'static str,
+    /// The provider's callback URL that issues invoices.
+    pub callback: &'static str,
+    /// Smallest and largest payment, in millisatoshis.
+    pub min_sendable: u64,
+    pub max_sendable: u64,
+}
+
+/// A TDMRep declaration for the whole site.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TdmRep {
+    /// Whether text and data mining rights are reserved.
+    pub reserved: bool,
+    /// URL of a machine-readable policy offering licences, if any.
+    pub policy: Option<&'static str>,
+}
+
+/// The configuration the documents are generated from.
+#[derive(Clone, Copy, Debug)]
+pub struct Settings<'a> {
+    pub security_contact: Option<&'a str>,
+    pub nostr_names: &'a [(&'a str, &'a str)],
+    pub fediverse_account: Option<&'a str>,
+    pub lnurlp: Option<Lnurlp>,
+    pub tdmrep: Option<TdmRep>,
+    /// Contents of the Keybase proof, read from [`KEYBASE_PROOF`].
+    ///
+    /// [`KEYBASE_PROOF`]: crate::config::KEYBASE_PROOF
+    pub keybase_proof: Option<&'a str>,
+    pub atproto_did: Option<&'a str>,
+}
+
+impl Settings<'static> {
+    /// The settings in [`crate::config`], without the Keybase proof, which
+    /// has to be read from disk.
+    pub fn from_config() -> Self {
+        Settings {
+            security_contact: SECURITY_CONTACT,
+            nostr_names: NOSTR_NAMES,
+            fediverse_account: FEDIVERSE_ACCOUNT,
+            lnurlp: LNURLP,
+            tdmrep: TDMREP,
+            keybase_proof: None,
+            atproto_did: ATPROTO_DID,
+        }
+    }
+}
+
+/// One generated document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Document {
+    /// Path under `/.well-known/`, e.g. `lnurlp/tips`.
+    pub path: String,
+    pub body: String,
+}
+
+impl Document {
+    /// Site-relative URL, e.g. `/.well-known/nostr.json`.
+    pub fn url_path(&self) -> String {
+        format!("/{}/{}", WELL_KNOWN_DIR, self.path)
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        content_type(&self.path)
+    }
+}
+
+/// Content type of the document at `path` under `/.well-known/`; the local
+/// server uses it too, since most of the names have no extension.
+pub fn content_type(path: &str) -> &'static str {
+    match path {
+        "webfinger" => "application/jrd+json",
+        "security.txt" | "keybase.txt" | "atproto-did" => "text/plain; charset=utf-8",
+        _ => "application/json",
+    }
+}
+
+/// `security.txt`, expiring on the first of the month a year from `today`.
+fn security_txt(contact: &str, today: Date) -> String {
+    let expires = Date::new(today.year + 1, today.month, 1).expect("the first always exists");
+    format!(
+        "Contact: {}\nExpires: {}T00:00:00Z\nPreferred-Languages: {}\nCanonical: {}\n",
+        contact,
+        expires.iso(),
+        SITE_LANGUAGE,
+        absolute(&format!("/{}/security.txt", WELL_KNOWN_DIR))
+    )
+}
+
+/// `nostr.json` mapping each name to its hex public key.
+fn nostr_json(names: &[(&str, &str)]) -> Result<String, String> {
+    let mut map = serde_json::Map::new();
+    for (name, key) in names {
+        if key.len() != 64 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(format!(
+                "Nostr key of \"{}\" must be 64 hex digits (not npub)",
+                name
+            ));
+        }
+        map.insert(name.to_string(), json!(key.to_ascii_lowercase()));
+    }
+    Ok(serde_json::to_string_pretty(&json!({ "names": map })).expect("nostr.json serializes"))
+}
+
+/// `webfinger` pointing at `account`, e.g. `@name@mastodon.social`.
+fn webfinger(account: &str) -> Result<String, String> {
+    let (user, host) = account
+        .trim_start_matches('@')
+        .split_once('@')
+        .filter(|(user, host)| !user.is_empty() && host.contains('.'))
+        .ok_or_else(|| format!("Fediverse account {} is not @user@host", account))?;
+    let doc = json!({
+        "subject": format!("acct:{}@{}", user, host),
+        "aliases": [
+            format!("https://{}/@{}", host, user),
+            format!("https://{}/users/{}", host, user),
+        ],
+        "links": [
+            {
+                "rel": "http://webfinger.net/rel/profile-page",
+                "type": "text/html",
+                "href": format!("https://{}/@{}", host, user),
+            },
+            {
+                "rel": "self",
+                "type": "application/activity+json",
+                "href": format!("https://{}/users/{}", host, user),
+            },
+        ],
+    });
+    Ok(serde_json::to_string_pretty(&doc).expect("webfinger serializes"))
+}
+
+/// `lnurlp/<user>`: the LNURL-pay request of the Lightning address.
+fn lnurlp(pay: &Lnurlp) -> String {
+    let address = format!("{}@{}", pay.user, SITE_DOMAIN);
+    let metadata = json!([
+        ["text/plain", format!("Payment to {}", address)],
+        ["text/identifier", address],
+    ]);
+    let doc = json!({
+        "tag": "payRequest",
+        "callback": pay.callback,
+        "minSendable": pay.min_sendable,
+        "maxSendable": pay.max_sendable,
+        "metadata": metadata.to_string(),
+    });
+    serde_json::to_string_pretty(&doc).expect("lnurlp serializes")
+}
+
+/// `tdmrep.json` covering every path.
+fn tdmrep(rep: &TdmRep) -> String {
+    let mut rule = json!({
+        "location": "/*",
+        "tdm-reservation": u8::from(rep.reserved),
+    });
+    if let Some(policy) = rep.policy {
+        rule["tdm-policy"] = json!(policy);
+    }
+    serde_json::to_string_pretty(&json!([rule])).expect("tdmrep serializes")
+}
+
+/// The documents `settings` enables, as of `today`.
+pub fn documents(settings: &Settings, today: Date) -> Result<Vec<Document>, String> {
+    let mut docs = Vec::new();
+    let mut add = |path: String, body: String| docs.push(Document { path, body });
+    if let Some(contact) = settings.security_contact {
+        add("security.txt".to_string(), security_txt(contact, today));
+    }
+    if !settings.nostr_names.is_empty() {
+        add("nostr.json".to_string(), nostr_json(settings.nostr_names)?);
+    }
+    if let Some(account) = settings.fediverse_account {
+        add("webfinger".to_string(), webfinger(account)?);
+    }
+    if let Some(pay) = &settings.lnurlp {
+        add(format!("lnurlp/{}", pay.user), lnurlp(pay));
+    }
+    if let Some(rep) = &settings.tdmrep {
+        add("tdmrep.json".to_string(), tdmrep(rep));
+    }
+    if let Some(proof) = settings.keybase_proof {
+        if !proof.contains("BEGIN KEYBASE SALTPACK SIGNED MESSAGE") {
+            return Err("Keybase proof has no signed statement".to_string());
+        }
+        add("keybase.txt".to_string(), proof.to_string());
+    }
+    if let Some(did) = settings.atproto_did {
+        if !did.starts_with("did:plc:") && !did.starts_with("did:web:") {
+            return Err(format!("AT Protocol DID {} is not did:plc or did:web", did));
+        }
+        add("atproto-did".to_string(), did.to_string());
+    }
+    Ok(docs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d";
+
+    fn all() -> Settings<'static> {
+        Settings {
+            security_contact: Some("mailto:security@a.test"),
+            nostr_names: &[("_", KEY)],
+            fediverse_account: Some("@ada@mastodon.test"),
+            lnurlp: Some(Lnurlp {
+                user: "tips",
+                callback: "https://wallet.test/lnurlp/ada/callback",
+                min_sendable: 1_000,
+                max_sendable: 100_000_000,
+            }),
+            tdmrep: Some(TdmRep {
+                reserved: true,
+                policy: Some("https://a.test/tdm-policy.json"),
+            }),
+            keybase_proof: Some(PROOF),
+            atproto_did: Some("did:plc:z72i7hdynmk6r22z27h6tvur"),
+        }
+    }
+
+    const PROOF: &str = "==================================================================\n\
+        https://keybase.io/ada\n\
+        --------------------------------------------------------------------\n\
+        BEGIN KEYBASE SALTPACK SIGNED MESSAGE. kXR7VktZdyH7rvq v5weRa0zkSjiJmm. \
+        END KEYBASE SALTPACK SIGNED MESSAGE.\n";
+
+    fn today() -> Date {
+        Date::new(2025, 6, 15).unwrap()
+    }
+
+    fn document(path: &str) -> Document {
+        documents(&all(), today())
+            .unwrap()
+            .into_iter()
+            .find(|d| d.path == path)
+            .unwrap()
+    }
+
+    #[test]
+    fn every_document_is_well_formed() {
+        let docs = documents(&all(), today()).unwrap();
+        let paths: Vec<&str> = docs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "security.txt",
+                "nostr.json",
+                "webfinger",
+                "lnurlp/tips",
+                "tdmrep.json",
+                "keybase.txt",
+                "atproto-did"
+            ]
+        );
+        for doc in &docs {
+            assert!(doc.url_path().starts_with("/.well-known/"));
+            if doc.content_type() != "text/plain; charset=utf-8" {
+                serde_json::from_str::<serde_json::Value>(&doc.body)
+                    .unwrap_or_else(|e| panic!("{}: {}", doc.path, e));
+            }
+        }
+    }
+
+    #[test]
+    fn unset_config_writes_nothing() {
+        let none = Settings {
+            security_contact: None,
+            nostr_names: &[],
+            fediverse_account: None,
+            lnurlp: None,
+            tdmrep: None,
+            keybase_proof: None,
+            atproto_did: None,
+        };
+        assert!(documents(&none, today()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn atproto_did_is_served_bare() {
+        let doc = document("atproto-did");
+        assert_eq!(doc.body, "did:plc:z72i7hdynmk6r22z27h6tvur");
+        assert_eq!(doc.content_type(), "text/plain; charset=utf-8");
+        let handle = Settings {
+            atproto_did: Some("everythingsings.art"),
+            ..all()
+        };
+        assert!(documents(&handle, today()).unwrap_err().contains("did:plc"));
+    }
+
+    #[test]
+    fn keybase_proof_is_copied_verbatim() {
+        let doc = document("keybase.txt");
+        assert_eq!(doc.body, PROOF);
+        assert_eq!(doc.content_type(), "text/plain; charset=utf-8");
+        let unsigned = Settings {
+            keybase_proof: Some("I am ada on Keybase."),
+            ..all()
+        };
+        assert!(documents(&unsigned, today()).is_err());
+    }
+
+    #[test]
+    fn security_txt_expires_within_a_year() {
+        let body = document("security.txt").body;
+        assert!(body.contains("Contact: mailto:security@a.test\n"));
+        assert!(body.contains("Expires: 2026-06-01T00:00:00Z\n"));
+        assert!(body.contains("Canonical: https://"));
+    }
+
+    #[test]
+    fn webfinger_aliases_the_account() {
+        let doc: serde_json::Value = serde_json::from_str(&document("webfinger").body).unwrap();
+        assert_eq!(doc["subject"], "acct:ada@mastodon.test");
+        assert_eq!(doc["links"][1]["href"], "https://mastodon.test/users/ada");
+        let bad = Settings {
+            fediverse_account: Some("ada"),
+            ..all()
+        };
+        assert!(documents(&bad, today()).is_err());
+    }
+
+    #[test]
+    fn nostr_keys_must_be_hex() {
+        let doc: serde_json::Value = serde_json::from_str(&document("nostr.json").body).unwrap();
+        assert_eq!(doc["names"]["_"], KEY);
+        let npub = Settings {
+            nostr_names: &[("_", "npub1xyz")],
+            ..all()
+        };
+        assert!(documents(&npub, today()).unwrap_err().contains("hex"));
+    }
+
+    #[test]
+    fn lnurlp_metadata_names_the_address() {
+        let doc: serde_json::Value = serde_json::from_str(&document("lnurlp/tips").body).unwrap();
+        assert_eq!(doc["tag"], "payRequest");
+        let metadata: serde_json::Value =
+            serde_json::from_str(doc["metadata"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            metadata[1][1],
+            format!("tips@{}", crate::config::SITE_DOMAIN)
+        );
+    }
+
+    #[test]
+    fn tdmrep_reserves_every_path() {
+        let doc: serde_json::Value = serde_json::from_str(&document("tdmrep.json").body).unwrap();
+        assert_eq!(doc[0]["location"], "/*");
+        assert_eq!(doc[0]["tdm-reservation"], 1);
+        assert_eq!(doc[0]["tdm-policy"], "https://a.test/tdm-policy.json");
+    }
+}